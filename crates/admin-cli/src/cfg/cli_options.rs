@@ -23,11 +23,11 @@ use crate::{
     dpa, dpu, dpu_remediation, expected_machines, expected_power_shelf, expected_rack,
     expected_switch, extension_service, firmware, generate_shell_complete, host, ib_partition,
     instance, instance_type, inventory, ip, ipxe_template, jump, machine, machine_interfaces,
-    machine_validation, managed_host, managed_switch, mlx, network_devices, network_security_group,
-    network_segment, nvl_logical_partition, nvl_partition, operating_system, os_image, ping,
-    power_shelf, rack, rack_firmware, redfish, resource_pool, rms, route_server, scout_stream, set,
-    site_explorer, sku, ssh, switch, tenant, tenant_keyset, tpm_ca, trim_table, version, vpc,
-    vpc_peering, vpc_prefix,
+    machine_validation, maintenance_window, managed_host, managed_switch, mlx, network_devices,
+    network_security_group, network_segment, nvl_logical_partition, nvl_partition,
+    operating_system, os_image, ping, power_shelf, rack, rack_firmware, redfish, resource_pool,
+    rms, route_server, scout_stream, set, site_explorer, sku, ssh, switch, tenant, tenant_keyset,
+    tpm_ca, trim_table, version, vpc, vpc_peering, vpc_prefix,
 };
 
 #[derive(Parser, Debug)]
@@ -193,6 +193,8 @@ pub enum CliCommand {
     Credential(credential::Cmd),
     #[clap(about = "Route server handling", subcommand)]
     RouteServer(route_server::Cmd),
+    #[clap(about = "Maintenance window handling", subcommand)]
+    MaintenanceWindow(maintenance_window::Cmd),
     #[clap(about = "Site explorer functions", subcommand)]
     SiteExplorer(site_explorer::Cmd),
     #[clap(
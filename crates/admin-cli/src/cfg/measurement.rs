@@ -26,7 +26,7 @@ use ::rpc::admin_cli::OutputFormat;
 use clap::Parser;
 use measured_boot::pcr::PcrRegisterValue;
 
-use crate::measurement::{bundle, journal, machine, profile, report, site};
+use crate::measurement::{bundle, journal, machine, policy, profile, report, site};
 
 // KvPair is a really simple struct for holding
 // a key/value pair, and is used for parsing
@@ -106,6 +106,13 @@ pub enum Cmd {
     )]
     Profile(profile::args::CmdProfile),
 
+    #[clap(
+        subcommand,
+        about = "Work with event-level measurement policies",
+        visible_alias = "po"
+    )]
+    Policy(policy::args::CmdPolicy),
+
     #[clap(subcommand, about = "Work with site-wide things.", visible_alias = "s")]
     Site(site::args::CmdSite),
 }
@@ -0,0 +1,124 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult, OutputFormat};
+use ::rpc::forge as forgerpc;
+use prettytable::{Table, row};
+
+use super::args::Args;
+use crate::rpc::ApiClient;
+use crate::{async_write, async_write_table_as_csv, async_writeln};
+
+fn kind_name(kind: forgerpc::MachineNextStepKind) -> &'static str {
+    match kind {
+        forgerpc::MachineNextStepKind::Unspecified => "Unknown",
+        forgerpc::MachineNextStepKind::Transition => "Transition",
+        forgerpc::MachineNextStepKind::Wait => "Wait",
+        forgerpc::MachineNextStepKind::DoNothing => "Do nothing",
+        forgerpc::MachineNextStepKind::Deleted => "Delete",
+        forgerpc::MachineNextStepKind::Error => "Error",
+    }
+}
+
+fn convert_next_step_to_nice_table(
+    next_step: &forgerpc::ExplainMachineNextStepResponse,
+) -> Box<Table> {
+    let mut table = Table::new();
+    table.add_row(row!["Current State", next_step.current_state]);
+    table.add_row(row!["Current Version", next_step.current_version]);
+    table.add_row(row![
+        "Time In State Above SLA",
+        next_step.time_in_state_above_sla
+    ]);
+    table.add_row(row!["Next Step", kind_name(next_step.kind())]);
+    if let Some(next_state) = &next_step.next_state {
+        table.add_row(row!["Next State", next_state]);
+    }
+    if let Some(wait_reason) = &next_step.wait_reason {
+        table.add_row(row!["Wait Reason", wait_reason]);
+    }
+    if let Some(error) = &next_step.error {
+        table.add_row(row!["Error", error]);
+    }
+    table.add_row(row![
+        "Source",
+        next_step.source_ref.as_deref().unwrap_or_default()
+    ]);
+    table.add_row(row!["DB Writes", next_step.db_writes.join("\n")]);
+    table.add_row(row![
+        "Transaction Discarded",
+        next_step.transaction_discarded
+    ]);
+
+    table.into()
+}
+
+fn convert_side_effects_to_nice_table(
+    next_step: &forgerpc::ExplainMachineNextStepResponse,
+) -> Box<Table> {
+    let mut table = Table::new();
+    table.set_titles(row!["Target", "Host", "Operation", "Details"]);
+    for side_effect in &next_step.side_effects {
+        table.add_row(row![
+            side_effect.target,
+            side_effect.host,
+            side_effect.operation,
+            side_effect.details,
+        ]);
+    }
+
+    table.into()
+}
+
+pub async fn explain_next_step(
+    args: Args,
+    output_format: &OutputFormat,
+    output_file: &mut Box<dyn tokio::io::AsyncWrite + Unpin>,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let next_step = api_client
+        .0
+        .explain_machine_next_step(forgerpc::ExplainMachineNextStepRequest {
+            machine_id: Some(args.machine),
+        })
+        .await?;
+
+    match output_format {
+        OutputFormat::Json => {
+            let mut value = serde_json::to_value(&next_step)?;
+            value["kind"] = next_step.kind().as_str_name().into();
+            async_writeln!(output_file, "{}", serde_json::to_string_pretty(&value)?)?;
+        }
+        OutputFormat::AsciiTable => {
+            let table = convert_next_step_to_nice_table(&next_step);
+            async_write!(output_file, "{}", table)?;
+            async_writeln!(output_file, "Recorded side effects (not executed):")?;
+            let table = convert_side_effects_to_nice_table(&next_step);
+            async_write!(output_file, "{}", table)?;
+        }
+        OutputFormat::Csv => {
+            let table = convert_side_effects_to_nice_table(&next_step);
+            async_write_table_as_csv!(output_file, table)?;
+        }
+        OutputFormat::Yaml => {
+            return Err(CarbideCliError::NotImplemented(
+                "YAML formatted output".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
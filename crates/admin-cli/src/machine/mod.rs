@@ -17,6 +17,7 @@
 
 pub mod auto_update;
 pub mod common;
+pub mod explain_next_step;
 pub mod force_delete;
 pub mod hardware_info;
 pub mod health_report;
@@ -26,6 +27,7 @@ pub mod nvlink_info;
 pub mod positions;
 pub mod reboot;
 pub mod show;
+pub mod timeline;
 
 #[cfg(test)]
 mod tests;
@@ -78,4 +80,14 @@ pub enum Cmd {
     Positions(positions::Args),
     #[clap(subcommand, about = "Update/show NVLink info for an MNNVL machine")]
     NvlinkInfo(nvlink_info::Args),
+    #[clap(about = "Show the states a host went through, with dwell times and state SLAs")]
+    Timeline(timeline::Args),
+    #[clap(
+        about = "Explain what the state controller would do to a host right now",
+        long_about = "Explain what the state controller would do to a host right now.\n\n\
+            Runs the state handler for the host without applying its outcome and shows \
+            the transition or wait reason it would return, the database writes it would \
+            perform and the Redfish/IPMI operations it would send to the BMC."
+    )]
+    ExplainNextStep(explain_next_step::Args),
 }
@@ -288,6 +288,52 @@ fn parse_positions() {
     }
 }
 
+// parse_timeline ensures timeline parses with a
+// machine ID.
+#[test]
+fn parse_timeline() {
+    let cmd = Cmd::try_parse_from(["machine", "timeline", TEST_MACHINE_ID])
+        .expect("should parse timeline");
+
+    match cmd {
+        Cmd::Timeline(args) => {
+            assert_eq!(args.machine.to_string(), TEST_MACHINE_ID);
+        }
+        _ => panic!("expected Timeline variant"),
+    }
+}
+
+// parse_timeline_missing_machine_fails ensures timeline
+// requires a machine ID.
+#[test]
+fn parse_timeline_missing_machine_fails() {
+    let result = Cmd::try_parse_from(["machine", "timeline"]);
+    assert!(result.is_err(), "should fail without machine ID");
+}
+
+// parse_explain_next_step ensures explain-next-step parses
+// with a machine ID.
+#[test]
+fn parse_explain_next_step() {
+    let cmd = Cmd::try_parse_from(["machine", "explain-next-step", TEST_MACHINE_ID])
+        .expect("should parse explain-next-step");
+
+    match cmd {
+        Cmd::ExplainNextStep(args) => {
+            assert_eq!(args.machine.to_string(), TEST_MACHINE_ID);
+        }
+        _ => panic!("expected ExplainNextStep variant"),
+    }
+}
+
+// parse_explain_next_step_missing_machine_fails ensures
+// explain-next-step requires a machine ID.
+#[test]
+fn parse_explain_next_step_missing_machine_fails() {
+    let result = Cmd::try_parse_from(["machine", "explain-next-step"]);
+    assert!(result.is_err(), "should fail without machine ID");
+}
+
 /////////////////////////////////////////////////////////////////////////////
 // ValueEnum Parsing
 //
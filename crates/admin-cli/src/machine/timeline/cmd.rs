@@ -0,0 +1,100 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult, OutputFormat};
+use ::rpc::forge as forgerpc;
+use prettytable::{Table, row};
+
+use super::args::Args;
+use crate::rpc::ApiClient;
+use crate::{async_write, async_write_table_as_csv, async_writeln};
+
+fn format_duration(duration: Option<::rpc::Duration>) -> String {
+    duration
+        .map(|duration| {
+            config_version::format_duration(
+                chrono::TimeDelta::try_from(duration).unwrap_or(chrono::TimeDelta::MAX),
+            )
+        })
+        .unwrap_or_default()
+}
+
+fn convert_timeline_to_nice_table(timeline: forgerpc::ManagedHostStateTimeline) -> Box<Table> {
+    let mut table = Table::new();
+    table.set_titles(row![
+        "State",
+        "Substate",
+        "Entered At",
+        "Left At",
+        "Dwell Time",
+        "SLA",
+        "SLA Exceeded",
+        "Breach Detected At",
+    ]);
+
+    for entry in timeline.entries {
+        table.add_row(row![
+            entry.state_name,
+            entry.substate_name,
+            entry.entered_at.map(|t| t.to_string()).unwrap_or_default(),
+            entry.left_at.map(|t| t.to_string()).unwrap_or_default(),
+            format_duration(entry.dwell_time),
+            format_duration(entry.sla),
+            entry.sla_exceeded,
+            entry
+                .breach_detected_at
+                .map(|t| t.to_string())
+                .unwrap_or_default(),
+        ]);
+    }
+
+    table.into()
+}
+
+pub async fn timeline(
+    args: Args,
+    output_format: &OutputFormat,
+    output_file: &mut Box<dyn tokio::io::AsyncWrite + Unpin>,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let timeline = api_client
+        .0
+        .get_managed_host_state_timeline(forgerpc::ManagedHostStateTimelineRequest {
+            machine_id: Some(args.machine),
+        })
+        .await?;
+
+    match output_format {
+        OutputFormat::Json => {
+            async_writeln!(output_file, "{}", serde_json::to_string_pretty(&timeline)?)?;
+        }
+        OutputFormat::AsciiTable => {
+            let table = convert_timeline_to_nice_table(timeline);
+            async_write!(output_file, "{}", table)?;
+        }
+        OutputFormat::Csv => {
+            let table = convert_timeline_to_nice_table(timeline);
+            async_write_table_as_csv!(output_file, table)?;
+        }
+        OutputFormat::Yaml => {
+            return Err(CarbideCliError::NotImplemented(
+                "YAML formatted output".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
@@ -69,6 +69,7 @@ mod jump;
 mod machine;
 mod machine_interfaces;
 mod machine_validation;
+mod maintenance_window;
 mod managed_host;
 mod managed_switch;
 mod measurement;
@@ -248,6 +249,7 @@ async fn main() -> color_eyre::Result<()> {
         CliCommand::Rack(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::ResourcePool(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::RouteServer(cmd) => cmd.dispatch(ctx).await?,
+        CliCommand::MaintenanceWindow(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::ScoutStream(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::Set(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::Ssh(cmd) => cmd.dispatch(ctx).await?,
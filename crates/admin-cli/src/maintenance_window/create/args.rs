@@ -0,0 +1,139 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::machine::MachineId;
+use carbide_uuid::rack::RackId;
+use carbide_uuid::switch::SwitchId;
+use chrono::{DateTime, Utc};
+use clap::{Parser, ValueEnum};
+use rpc::forge as forgerpc;
+
+use crate::metadata::parse_rpc_labels;
+
+#[derive(Parser, Debug)]
+pub struct Args {
+    #[clap(long, help = "Name of the maintenance window")]
+    pub name: String,
+
+    #[clap(
+        long,
+        default_value = "",
+        help = "Description of the maintenance window"
+    )]
+    pub description: String,
+
+    #[clap(
+        long,
+        help = "Start of the window in RFC 3339 format, e.g. 2026-01-31T22:00:00Z"
+    )]
+    pub start: DateTime<Utc>,
+
+    #[clap(
+        long,
+        help = "End of the window in RFC 3339 format, e.g. 2026-02-01T04:00:00Z"
+    )]
+    pub end: DateTime<Utc>,
+
+    #[clap(
+        long,
+        help = "Host machine IDs covered by the window",
+        num_args = 1..,
+        value_delimiter = ','
+    )]
+    pub machine_ids: Vec<MachineId>,
+
+    #[clap(
+        long,
+        help = "Rack IDs covered by the window, including their hosts and switches",
+        num_args = 1..,
+        value_delimiter = ','
+    )]
+    pub rack_ids: Vec<RackId>,
+
+    #[clap(
+        long,
+        help = "Switch IDs covered by the window",
+        num_args = 1..,
+        value_delimiter = ','
+    )]
+    pub switch_ids: Vec<SwitchId>,
+
+    #[clap(
+        long,
+        help = "Hosts with any of these SKUs are covered by the window",
+        num_args = 1..,
+        value_delimiter = ','
+    )]
+    pub skus: Vec<String>,
+
+    #[clap(
+        long,
+        help = "Objects with all of these labels (key:value, or key to match any value) are covered by the window",
+        num_args = 1..,
+        value_delimiter = ','
+    )]
+    pub labels: Vec<String>,
+
+    #[clap(
+        long,
+        help = "Actions allowed during the window (omit for all)",
+        num_args = 1..,
+        value_delimiter = ','
+    )]
+    pub actions: Vec<MaintenanceAction>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum MaintenanceAction {
+    FirmwareUpdate,
+    Reprovision,
+}
+
+impl From<MaintenanceAction> for forgerpc::MaintenanceWindowAction {
+    fn from(action: MaintenanceAction) -> Self {
+        match action {
+            MaintenanceAction::FirmwareUpdate => forgerpc::MaintenanceWindowAction::FirmwareUpdate,
+            MaintenanceAction::Reprovision => forgerpc::MaintenanceWindowAction::Reprovision,
+        }
+    }
+}
+
+impl From<Args> for forgerpc::CreateMaintenanceWindowRequest {
+    fn from(args: Args) -> Self {
+        Self {
+            metadata: Some(forgerpc::Metadata {
+                name: args.name,
+                description: args.description,
+                labels: vec![],
+            }),
+            start_time: Some(args.start.into()),
+            end_time: Some(args.end.into()),
+            scope: Some(forgerpc::MaintenanceWindowScope {
+                machine_ids: args.machine_ids,
+                rack_ids: args.rack_ids,
+                switch_ids: args.switch_ids,
+                skus: args.skus,
+                label_selector: parse_rpc_labels(args.labels),
+            }),
+            allowed_actions: args
+                .actions
+                .into_iter()
+                .map(|action| forgerpc::MaintenanceWindowAction::from(action) as i32)
+                .collect(),
+        }
+    }
+}
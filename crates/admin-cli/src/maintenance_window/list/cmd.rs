@@ -0,0 +1,120 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliResult, OutputFormat};
+use ::rpc::forge as forgerpc;
+use prettytable::{Cell, Row, Table};
+
+use super::args::Args;
+use crate::rpc::ApiClient;
+
+pub async fn list(
+    args: Args,
+    format: OutputFormat,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let windows = api_client
+        .0
+        .find_maintenance_windows(forgerpc::FindMaintenanceWindowsRequest {
+            include_inactive: args.include_inactive,
+        })
+        .await?;
+    if windows.windows.is_empty() && format == OutputFormat::AsciiTable {
+        println!("No maintenance windows found");
+        return Ok(());
+    }
+    print_windows(format, &windows.windows)
+}
+
+pub(crate) fn print_windows(
+    format: OutputFormat,
+    windows: &[forgerpc::MaintenanceWindow],
+) -> CarbideCliResult<()> {
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(windows)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(windows)?),
+        _ => windows_to_table(windows).printstd(),
+    }
+    Ok(())
+}
+
+// windows_to_table converts the MaintenanceWindows into a
+// pretty ASCII table.
+fn windows_to_table(windows: &[forgerpc::MaintenanceWindow]) -> Table {
+    let mut table = Table::new();
+    table.set_titles(Row::new(
+        ["ID", "Name", "Start", "End", "Scope", "Actions", "Status"]
+            .into_iter()
+            .map(Cell::new)
+            .collect(),
+    ));
+
+    for window in windows {
+        let actions = if window.allowed_actions.is_empty() {
+            "all".to_string()
+        } else {
+            window
+                .allowed_actions
+                .iter()
+                .map(
+                    |action| match forgerpc::MaintenanceWindowAction::try_from(*action) {
+                        Ok(action) => action.as_str_name().to_string(),
+                        Err(_) => action.to_string(),
+                    },
+                )
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let status = if window.cancelled.is_some() {
+            "cancelled"
+        } else {
+            "scheduled"
+        };
+
+        table.add_row(prettytable::row![
+            window.id.map(|id| id.to_string()).unwrap_or_default(),
+            window
+                .metadata
+                .as_ref()
+                .map(|m| m.name.as_str())
+                .unwrap_or_default(),
+            window.start_time.map(|t| t.to_string()).unwrap_or_default(),
+            window.end_time.map(|t| t.to_string()).unwrap_or_default(),
+            window.scope.as_ref().map(scope_summary).unwrap_or_default(),
+            actions,
+            status,
+        ]);
+    }
+
+    table
+}
+
+fn scope_summary(scope: &forgerpc::MaintenanceWindowScope) -> String {
+    let mut lines = Vec::new();
+    lines.extend(scope.machine_ids.iter().map(|id| format!("machine {id}")));
+    lines.extend(scope.rack_ids.iter().map(|id| format!("rack {id}")));
+    lines.extend(scope.switch_ids.iter().map(|id| format!("switch {id}")));
+    lines.extend(scope.skus.iter().map(|sku| format!("sku {sku}")));
+    lines.extend(scope.label_selector.iter().map(|label| {
+        format!(
+            "label {}:{}",
+            label.key,
+            label.value.as_deref().unwrap_or("*")
+        )
+    }));
+    lines.join("\n")
+}
@@ -0,0 +1,177 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// The intent of the tests.rs file is to test the integrity of the
+// command, including things like basic structure parsing, enum
+// translations, and any external input validators that are
+// configured. Specific "categories" are:
+//
+// Command Structure - Baseline debug_assert() of the entire command.
+// Argument Parsing  - Ensure required/optional arg combinations parse correctly.
+// ValueEnum Parsing - Test clap ValueEnum translations (if applicable).
+
+use clap::{CommandFactory, Parser};
+use rpc::forge as forgerpc;
+
+use super::*;
+
+// verify_cmd_structure runs a baseline clap debug_assert()
+// to do basic command configuration checking and validation,
+// ensuring things like unique argument definitions, group
+// configurations, argument references, etc. Things that would
+// otherwise be missed until runtime.
+#[test]
+fn verify_cmd_structure() {
+    Cmd::command().debug_assert();
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Argument Parsing
+//
+// This section contains tests specific to argument parsing,
+// including testing required arguments, as well as optional
+// flag-specific checking.
+
+// parse_create_with_scope ensures create parses a window
+// with a rack and label based scope.
+#[test]
+fn parse_create_with_scope() {
+    let cmd = Cmd::try_parse_from([
+        "maintenance-window",
+        "create",
+        "--name",
+        "rack-upgrades",
+        "--start",
+        "2026-01-31T22:00:00Z",
+        "--end",
+        "2026-02-01T04:00:00Z",
+        "--rack-ids",
+        "rack-1,rack-2",
+        "--labels",
+        "pool:a,canary",
+    ])
+    .expect("should parse create");
+
+    match cmd {
+        Cmd::Create(args) => {
+            assert_eq!(args.rack_ids.len(), 2);
+            assert!(args.actions.is_empty());
+            let request = forgerpc::CreateMaintenanceWindowRequest::from(args);
+            let scope = request.scope.unwrap();
+            assert_eq!(scope.label_selector.len(), 2);
+            assert_eq!(scope.label_selector[1].value, None);
+        }
+        _ => panic!("expected Create variant"),
+    }
+}
+
+// parse_create_missing_end fails when no end time
+// is provided.
+#[test]
+fn parse_create_missing_end() {
+    let result = Cmd::try_parse_from([
+        "maintenance-window",
+        "create",
+        "--name",
+        "rack-upgrades",
+        "--start",
+        "2026-01-31T22:00:00Z",
+    ]);
+
+    assert!(result.is_err(), "should fail without --end");
+}
+
+// parse_create_invalid_time fails when a time is not
+// in RFC 3339 format.
+#[test]
+fn parse_create_invalid_time() {
+    let result = Cmd::try_parse_from([
+        "maintenance-window",
+        "create",
+        "--name",
+        "rack-upgrades",
+        "--start",
+        "tomorrow",
+        "--end",
+        "2026-02-01T04:00:00Z",
+    ]);
+
+    assert!(result.is_err(), "should fail with invalid --start");
+}
+
+// parse_list_include_inactive ensures list parses with
+// the --include-inactive flag.
+#[test]
+fn parse_list_include_inactive() {
+    let cmd = Cmd::try_parse_from(["maintenance-window", "list", "--include-inactive"])
+        .expect("should parse list");
+
+    match cmd {
+        Cmd::List(args) => assert!(args.include_inactive),
+        _ => panic!("expected List variant"),
+    }
+}
+
+// parse_cancel_missing_id fails when no window ID
+// is provided.
+#[test]
+fn parse_cancel_missing_id() {
+    let result = Cmd::try_parse_from(["maintenance-window", "cancel"]);
+
+    assert!(result.is_err(), "should fail without id");
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// ValueEnum Parsing
+//
+// This section tests clap ValueEnum translations for the
+// actions argument.
+
+// parse_create_with_actions ensures create translates
+// --actions into the proto enum values.
+#[test]
+fn parse_create_with_actions() {
+    let cmd = Cmd::try_parse_from([
+        "maintenance-window",
+        "create",
+        "--name",
+        "fw",
+        "--start",
+        "2026-01-31T22:00:00Z",
+        "--end",
+        "2026-02-01T04:00:00Z",
+        "--skus",
+        "sku1",
+        "--actions",
+        "firmware-update,reprovision",
+    ])
+    .expect("should parse create with actions");
+
+    match cmd {
+        Cmd::Create(args) => {
+            let request = forgerpc::CreateMaintenanceWindowRequest::from(args);
+            assert_eq!(
+                request.allowed_actions,
+                vec![
+                    forgerpc::MaintenanceWindowAction::FirmwareUpdate as i32,
+                    forgerpc::MaintenanceWindowAction::Reprovision as i32,
+                ]
+            );
+        }
+        _ => panic!("expected Create variant"),
+    }
+}
@@ -23,6 +23,7 @@ pub mod bundle;
 pub mod global;
 pub mod journal;
 pub mod machine;
+pub mod policy;
 pub mod profile;
 pub mod report;
 pub mod site;
@@ -64,6 +65,9 @@ impl Dispatch for Cmd {
             // Handle everything with the `machine` subcommand.
             Cmd::Machine(subcmd) => machine::cmds::dispatch(subcmd, &mut cli_data).await?,
 
+            // Handle everything with the `policy` subcommand.
+            Cmd::Policy(subcmd) => policy::cmds::dispatch(subcmd, &mut cli_data).await?,
+
             // Handle everything with the `site` subcommand.
             Cmd::Site(subcmd) => site::cmds::dispatch(subcmd, &mut cli_data).await?,
         }
@@ -0,0 +1,168 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+/*!
+ *  Measured Boot CLI arguments for the `measurement policy` subcommand.
+ *
+ * This provides the CLI subcommands and arguments for:
+ *  - `policy create`: Create a new event-level measurement policy.
+ *  - `policy delete`: Delete a policy.
+ *  - `policy show`: Show all policies, or those for a profile.
+ */
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use ::rpc::admin_cli::CarbideCliError;
+use ::rpc::protos::measured_boot::{
+    CreateMeasurementPolicyRequest, DeleteMeasurementPolicyRequest, ShowMeasurementPoliciesRequest,
+};
+use carbide_uuid::measured_boot::{MeasurementPolicyId, MeasurementSystemProfileId};
+use clap::Parser;
+use measured_boot::policy::PolicyRule;
+
+use crate::cfg::measurement::{KvPair, parse_colon_pairs};
+
+/// CmdPolicy provides a container for the `policy` subcommand, which itself
+/// contains other subcommands for working with measurement policies.
+#[derive(Parser, Debug)]
+pub enum CmdPolicy {
+    #[clap(about = "Create a new measurement policy.", visible_alias = "c")]
+    Create(Create),
+
+    #[clap(about = "Delete a measurement policy.", visible_alias = "d")]
+    Delete(Delete),
+
+    #[clap(
+        about = "Show all policies, or the policies for a profile.",
+        visible_alias = "s"
+    )]
+    Show(Show),
+}
+
+/// Create is used to create a new measurement policy. Rules can be
+/// given with flags, or as a JSON file of rules, or both.
+#[derive(Parser, Debug)]
+pub struct Create {
+    #[clap(help = "The [unique] name of the policy.")]
+    pub name: String,
+
+    #[clap(
+        long,
+        help = "Limit the policy to a system profile (applies to all profiles if unset)."
+    )]
+    pub profile_id: Option<MeasurementSystemProfileId>,
+
+    #[clap(long, help = "An allowed boot loader (shim, grub, ...) digest.")]
+    pub bootloader_digest: Vec<String>,
+
+    #[clap(
+        long,
+        help = "An allowed Secure Boot variable digest, as <variable>:<digest> (e.g. db:ab12...)."
+    )]
+    #[arg(value_parser = parse_colon_pairs)]
+    pub secure_boot_digest: Vec<KvPair>,
+
+    #[clap(long, help = "An allowed kernel command line (regular expression).")]
+    pub kernel_cmdline: Vec<String>,
+
+    #[clap(long, help = "A JSON file containing a list of rules.")]
+    pub rules_file: Option<PathBuf>,
+}
+
+/// Delete is used to delete an existing policy.
+#[derive(Parser, Debug)]
+pub struct Delete {
+    #[clap(help = "The policy ID to delete.")]
+    pub policy_id: MeasurementPolicyId,
+}
+
+/// Show is used to show all policies, or only the
+/// policies that apply to a system profile.
+#[derive(Parser, Debug)]
+pub struct Show {
+    #[clap(long, help = "Only show policies that apply to this profile ID.")]
+    pub profile_id: Option<MeasurementSystemProfileId>,
+}
+
+impl Create {
+    /// rules builds the policy rules from the flags, plus
+    /// the rules from the rules file (if any).
+    pub fn rules(&self) -> Result<Vec<PolicyRule>, CarbideCliError> {
+        let mut rules: Vec<PolicyRule> = match &self.rules_file {
+            Some(path) => serde_json::from_reader(BufReader::new(File::open(path)?))?,
+            None => Vec::new(),
+        };
+        if !self.bootloader_digest.is_empty() {
+            rules.push(PolicyRule::BootloaderDigests {
+                allowed_digests: self.bootloader_digest.clone(),
+            });
+        }
+        let mut secure_boot: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for pair in self.secure_boot_digest.iter() {
+            secure_boot
+                .entry(pair.key.clone())
+                .or_default()
+                .push(pair.value.clone());
+        }
+        for (variable, allowed_digests) in secure_boot {
+            rules.push(PolicyRule::SecureBootVariable {
+                variable,
+                allowed_digests,
+            });
+        }
+        if !self.kernel_cmdline.is_empty() {
+            rules.push(PolicyRule::KernelCommandLine {
+                allowed_patterns: self.kernel_cmdline.clone(),
+            });
+        }
+        if rules.is_empty() {
+            return Err(CarbideCliError::GenericError(String::from(
+                "at least one rule must be given",
+            )));
+        }
+        Ok(rules)
+    }
+}
+
+impl TryFrom<Create> for CreateMeasurementPolicyRequest {
+    type Error = CarbideCliError;
+    fn try_from(create: Create) -> Result<Self, Self::Error> {
+        Ok(Self {
+            rules: create.rules()?.into_iter().map(Into::into).collect(),
+            name: create.name,
+            profile_id: create.profile_id,
+        })
+    }
+}
+
+impl From<Delete> for DeleteMeasurementPolicyRequest {
+    fn from(delete: Delete) -> Self {
+        Self {
+            policy_id: Some(delete.policy_id),
+        }
+    }
+}
+
+impl From<Show> for ShowMeasurementPoliciesRequest {
+    fn from(show: Show) -> Self {
+        Self {
+            profile_id: show.profile_id,
+        }
+    }
+}
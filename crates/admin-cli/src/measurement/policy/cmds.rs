@@ -0,0 +1,122 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//!
+//! `measurement policy` subcommand dispatcher + backing functions.
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult, ToTable, cli_output};
+use ::rpc::protos::measured_boot::CreateMeasurementPolicyRequest;
+use measured_boot::policy::MeasurementPolicy;
+use serde::Serialize;
+
+use crate::measurement::global;
+use crate::measurement::policy::args::{CmdPolicy, Create, Delete, Show};
+use crate::rpc::ApiClient;
+
+/// dispatch matches + dispatches the correct command for
+/// the `policy` subcommand.
+pub async fn dispatch(
+    cmd: CmdPolicy,
+    cli: &mut global::cmds::CliData<'_, '_>,
+) -> CarbideCliResult<()> {
+    match cmd {
+        CmdPolicy::Create(local_args) => {
+            cli_output(
+                create(cli.grpc_conn, local_args).await?,
+                &cli.args.format,
+                ::rpc::admin_cli::Destination::Stdout(),
+            )?;
+        }
+        CmdPolicy::Delete(local_args) => {
+            cli_output(
+                delete(cli.grpc_conn, local_args).await?,
+                &cli.args.format,
+                ::rpc::admin_cli::Destination::Stdout(),
+            )?;
+        }
+        CmdPolicy::Show(local_args) => {
+            cli_output(
+                show(cli.grpc_conn, local_args).await?,
+                &cli.args.format,
+                ::rpc::admin_cli::Destination::Stdout(),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// create creates a new measurement policy.
+///
+/// `policy create <name> [--profile-id <profile-id>] [--bootloader-digest <digest>]...
+///     [--secure-boot-digest <variable>:<digest>]... [--kernel-cmdline <pattern>]...
+///     [--rules-file <path>]`
+pub async fn create(grpc_conn: &ApiClient, create: Create) -> CarbideCliResult<MeasurementPolicy> {
+    let response = grpc_conn
+        .0
+        .create_measurement_policy(CreateMeasurementPolicyRequest::try_from(create)?)
+        .await?;
+
+    MeasurementPolicy::from_grpc(response.policy.as_ref())
+        .map_err(|e| CarbideCliError::GenericError(e.to_string()))
+}
+
+/// delete deletes an existing policy.
+///
+/// `policy delete <policy-id>`
+pub async fn delete(grpc_conn: &ApiClient, delete: Delete) -> CarbideCliResult<MeasurementPolicy> {
+    let response = grpc_conn.0.delete_measurement_policy(delete).await?;
+
+    MeasurementPolicy::from_grpc(response.policy.as_ref())
+        .map_err(|e| CarbideCliError::GenericError(e.to_string()))
+}
+
+/// show shows all policies, or the policies that apply to a profile.
+///
+/// `policy show [--profile-id <profile-id>]`
+pub async fn show(grpc_conn: &ApiClient, show: Show) -> CarbideCliResult<MeasurementPolicyList> {
+    Ok(MeasurementPolicyList(
+        grpc_conn
+            .0
+            .show_measurement_policies(show)
+            .await?
+            .policies
+            .drain(..)
+            .map(|policy| {
+                MeasurementPolicy::from_grpc(Some(&policy))
+                    .map_err(|e| CarbideCliError::GenericError(e.to_string()))
+            })
+            .collect::<CarbideCliResult<Vec<MeasurementPolicy>>>()?,
+    ))
+}
+
+/// MeasurementPolicyList just implements a newtype
+/// pattern for a Vec<MeasurementPolicy> so the ToTable
+/// trait can be leveraged (since we don't define Vec).
+#[derive(Serialize)]
+pub struct MeasurementPolicyList(Vec<MeasurementPolicy>);
+
+// When `policy show` gets called, and the output format is
+// the default table view, this gets used to print a pretty table.
+impl ToTable for MeasurementPolicyList {
+    fn into_table(self) -> eyre::Result<String> {
+        let mut table = prettytable::Table::new();
+        table.add_row(prettytable::row!["policy_id", "details"]);
+        for policy in self.0 {
+            table.add_row(prettytable::row![policy.policy_id, policy.into_table()?]);
+        }
+        Ok(table.to_string())
+    }
+}
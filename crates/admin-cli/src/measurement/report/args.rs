@@ -24,7 +24,8 @@
  *  - `report promote`: Promote a machine measurement report to a bundle.
  *  - `report revoke`: Create a revoked measurement bundle from a report.
  *  - `report show all`: Show all info about all measurement reports.
- *  - `report show id`: Show all info about a specific report.
+ *  - `report show id`: Show all info about a specific report, including
+ *    the replay and diff of its event log.
  *  - `report show machine`: Show all info about reports for a given machine.
  *  - `report list all`: List high level info about all reports.
  *  - `report list machine`: List all reports for a given machine.
@@ -160,11 +161,19 @@ pub enum ShowFor {
     All,
 }
 
-/// Show a report for the given ID.
+/// Show a report for the given ID. If the machine sent its event
+/// log with the quote, this includes the PCR replay of the log and
+/// the event-level diff against a reference report.
 #[derive(Parser, Debug)]
 pub struct ShowForId {
     #[clap(help = "The report ID.")]
     pub report_id: MeasurementReportId,
+
+    #[clap(
+        long,
+        help = "Diff the event log against this report (defaults to the latest measured report for the machine)."
+    )]
+    pub reference: Option<MeasurementReportId>,
 }
 
 /// Show all reports for a machine.
@@ -256,6 +265,7 @@ impl From<ShowForId> for ShowMeasurementReportForIdRequest {
     fn from(show_for_id: ShowForId) -> Self {
         Self {
             report_id: Some(show_for_id.report_id),
+            reference_report_id: show_for_id.reference,
         }
     }
 }
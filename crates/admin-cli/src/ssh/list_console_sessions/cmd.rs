@@ -0,0 +1,81 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult, OutputFormat};
+use prettytable::{Table, row};
+use ssh_console::session_recording::{SessionIndexEntry, list_sessions};
+
+use super::args::Args;
+
+fn convert_sessions_to_table(sessions: &[SessionIndexEntry]) -> Box<Table> {
+    let mut table = Box::new(Table::new());
+
+    table.set_titles(row![
+        "Session ID",
+        "Started",
+        "Ended",
+        "Connected As",
+        "Auth Method",
+        "Principal",
+        "Peer Address",
+        "Command",
+    ]);
+
+    for entry in sessions {
+        table.add_row(row![
+            entry.session.session_id,
+            entry.started_at.to_rfc3339(),
+            entry
+                .ended_at
+                .map(|t| t.to_rfc3339())
+                .unwrap_or_else(|| "-".to_string()),
+            entry.session.connected_as,
+            format!("{:?}", entry.session.principal.auth_method),
+            entry.session.principal,
+            entry.session.peer_addr,
+            entry.session.exec_command.as_deref().unwrap_or("<shell>"),
+        ]);
+    }
+
+    table
+}
+
+/// List the recorded ssh-console sessions of a machine.
+pub fn list_console_sessions(args: Args, output_format: OutputFormat) -> CarbideCliResult<()> {
+    let sessions = list_sessions(&args.recordings_path, &args.machine_id)
+        .map_err(|e| CarbideCliError::GenericError(e.to_string()))?;
+
+    match output_format {
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&sessions).map_err(CarbideCliError::JsonError)?
+        ),
+        OutputFormat::Yaml => println!(
+            "{}",
+            serde_yaml::to_string(&sessions).map_err(CarbideCliError::YamlError)?
+        ),
+        OutputFormat::Csv => {
+            convert_sessions_to_table(&sessions)
+                .to_csv(std::io::stdout())
+                .map_err(CarbideCliError::CsvError)?
+                .flush()?;
+        }
+        _ => convert_sessions_to_table(&sessions).printstd(),
+    }
+
+    Ok(())
+}
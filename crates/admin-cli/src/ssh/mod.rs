@@ -20,6 +20,8 @@ mod copy_bfb;
 mod disable_rshim;
 mod enable_rshim;
 mod get_rshim_status;
+mod list_console_sessions;
+mod play_console_session;
 mod show_obmc_log;
 
 #[cfg(test)]
@@ -42,4 +44,8 @@ pub enum Cmd {
     CopyBfb(copy_bfb::Args),
     #[clap(about = "Show the DPU's BMC's OBMC log")]
     ShowObmcLog(show_obmc_log::Args),
+    #[clap(about = "List the ssh-console sessions recorded for a machine")]
+    ListConsoleSessions(list_console_sessions::Args),
+    #[clap(about = "Play back a recorded ssh-console session")]
+    PlayConsoleSession(play_console_session::Args),
 }
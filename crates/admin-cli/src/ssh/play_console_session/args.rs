@@ -0,0 +1,48 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use uuid::Uuid;
+
+#[derive(Parser, Debug, Clone)]
+pub struct Args {
+    #[clap(help = "Machine ID the session was recorded for")]
+    pub machine_id: String,
+    #[clap(help = "Session ID, as shown by list-console-sessions")]
+    pub session_id: Uuid,
+    #[clap(
+        long,
+        default_value = "/var/log/consoles/recordings",
+        help = "ssh-console's session_recordings_path"
+    )]
+    pub recordings_path: PathBuf,
+    #[clap(long, default_value = "1.0", help = "Playback speed multiplier")]
+    pub speed: f64,
+    #[clap(
+        long,
+        default_value = "2.0",
+        help = "Limit pauses between events to this many seconds"
+    )]
+    pub max_idle: f64,
+    #[clap(
+        long,
+        help = "Highlight what the user typed, in addition to the console output"
+    )]
+    pub show_input: bool,
+}
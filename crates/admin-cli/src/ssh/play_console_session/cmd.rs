@@ -0,0 +1,83 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io::Write;
+use std::time::Duration;
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult};
+use ssh_console::session_recording::{EventKind, list_sessions, read_recording};
+
+use super::args::Args;
+
+/// Play back a recorded ssh-console session to stdout, with its original timing.
+pub async fn play_console_session(args: Args) -> CarbideCliResult<()> {
+    if args.speed <= 0.0 {
+        return Err(CarbideCliError::GenericError(
+            "--speed must be greater than 0".to_string(),
+        ));
+    }
+
+    let session = list_sessions(&args.recordings_path, &args.machine_id)
+        .map_err(|e| CarbideCliError::GenericError(e.to_string()))?
+        .into_iter()
+        .find(|s| s.session.session_id == args.session_id)
+        .ok_or_else(|| {
+            CarbideCliError::GenericError(format!(
+                "No recorded session {} for machine {}",
+                args.session_id, args.machine_id
+            ))
+        })?;
+    let (header, events) = read_recording(&session.recording_path(&args.recordings_path))
+        .map_err(|e| CarbideCliError::GenericError(e.to_string()))?;
+
+    eprintln!(
+        "--- Session {} on {} ({}x{}), started {} by {} ---",
+        session.session.session_id,
+        session.session.machine_id,
+        header.width,
+        header.height,
+        session.started_at.to_rfc3339(),
+        session.session.principal,
+    );
+
+    let mut stdout = std::io::stdout();
+    let mut last_time = 0.0;
+    for event in events {
+        let delay = ((event.time - last_time) / args.speed).clamp(0.0, args.max_idle.max(0.0));
+        last_time = event.time;
+        if delay > 0.0 {
+            tokio::time::sleep(Duration::from_secs_f64(delay)).await;
+        }
+
+        match event.kind {
+            EventKind::Output => write!(stdout, "{}", event.data)?,
+            EventKind::Input if args.show_input => {
+                write!(stdout, "\x1b[7m{}\x1b[0m", event.data.escape_debug())?
+            }
+            EventKind::Marker => write!(stdout, "\r\n--- {} ---\r\n", event.data)?,
+            EventKind::Input | EventKind::Resize => {}
+        }
+        stdout.flush()?;
+    }
+
+    match session.ended_at {
+        Some(ended_at) => eprintln!("\r\n--- Session ended {} ---", ended_at.to_rfc3339()),
+        None => eprintln!("\r\n--- Session did not end (still running, or ssh-console exited) ---"),
+    }
+
+    Ok(())
+}
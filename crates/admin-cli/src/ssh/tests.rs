@@ -143,3 +143,59 @@ fn parse_missing_credentials_fails() {
     let result = Cmd::try_parse_from(["ssh", "get-rshim-status", "192.168.1.100:443"]);
     assert!(result.is_err(), "should fail without username and password");
 }
+
+// parse_list_console_sessions ensures list-console-sessions
+// parses with the default recordings path.
+#[test]
+fn parse_list_console_sessions() {
+    let cmd = Cmd::try_parse_from(["ssh", "list-console-sessions", "fm100htmachine"])
+        .expect("should parse list-console-sessions");
+
+    match cmd {
+        Cmd::ListConsoleSessions(args) => {
+            assert_eq!(args.machine_id, "fm100htmachine");
+            assert_eq!(
+                args.recordings_path.to_str(),
+                Some("/var/log/consoles/recordings")
+            );
+        }
+        _ => panic!("expected ListConsoleSessions variant"),
+    }
+}
+
+// parse_play_console_session ensures play-console-session
+// parses a session ID and playback options.
+#[test]
+fn parse_play_console_session() {
+    let cmd = Cmd::try_parse_from([
+        "ssh",
+        "play-console-session",
+        "fm100htmachine",
+        "d40ad750-b925-4b34-b25a-d7f94458cc9e",
+        "--speed",
+        "4",
+        "--show-input",
+    ])
+    .expect("should parse play-console-session");
+
+    match cmd {
+        Cmd::PlayConsoleSession(args) => {
+            assert_eq!(
+                args.session_id.to_string(),
+                "d40ad750-b925-4b34-b25a-d7f94458cc9e"
+            );
+            assert_eq!(args.speed, 4.0);
+            assert_eq!(args.max_idle, 2.0);
+            assert!(args.show_input);
+        }
+        _ => panic!("expected PlayConsoleSession variant"),
+    }
+
+    let result = Cmd::try_parse_from([
+        "ssh",
+        "play-console-session",
+        "fm100htmachine",
+        "not-a-uuid",
+    ]);
+    assert!(result.is_err(), "should fail with an invalid session ID");
+}
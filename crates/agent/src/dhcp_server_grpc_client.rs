@@ -21,7 +21,8 @@ pub mod proto {
 
 use carbide_utils::models::dhcp::{
     DhcpConfig as ModelDhcpConfig, HostConfig as ModelHostConfig,
-    InterfaceInfo as ModelInterfaceInfo,
+    InterfaceInfo as ModelInterfaceInfo, InterfaceIpv6Info as ModelInterfaceIpv6Info,
+    RouterAdvertisementConfig as ModelRouterAdvertisementConfig,
 };
 use carbide_uuid::machine::MachineInterfaceId;
 use proto::dhcp_server_control_client::DhcpServerControlClient;
@@ -47,6 +48,22 @@ impl From<ModelDhcpConfig> for proto::DhcpConfig {
                 .collect(),
             carbide_provisioning_server_ipv4: c.carbide_provisioning_server_ipv4.to_string(),
             carbide_dhcp_server: c.carbide_dhcp_server.to_string(),
+            carbide_nameservers_v6: c
+                .carbide_nameservers_v6
+                .iter()
+                .map(|ip| ip.to_string())
+                .collect(),
+            router_advertisement: c.router_advertisement.map(Into::into),
+        }
+    }
+}
+
+impl From<ModelRouterAdvertisementConfig> for proto::RouterAdvertisementConfig {
+    fn from(r: ModelRouterAdvertisementConfig) -> Self {
+        proto::RouterAdvertisementConfig {
+            interval_secs: r.interval_secs,
+            router_lifetime_secs: r.router_lifetime_secs.into(),
+            slaac: r.slaac,
         }
     }
 }
@@ -60,6 +77,17 @@ impl From<ModelInterfaceInfo> for proto::InterfaceInfo {
             fqdn: i.fqdn,
             booturl: i.booturl,
             mtu: i.mtu,
+            ipv6: i.ipv6.map(Into::into),
+        }
+    }
+}
+
+impl From<ModelInterfaceIpv6Info> for proto::InterfaceIpv6Info {
+    fn from(i: ModelInterfaceIpv6Info) -> Self {
+        proto::InterfaceIpv6Info {
+            address: i.address.to_string(),
+            prefix: i.prefix,
+            gateway: i.gateway.map(|g| g.to_string()),
         }
     }
 }
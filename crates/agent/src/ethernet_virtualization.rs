@@ -3040,6 +3040,8 @@ mod tests {
             rebinding_time_secs: 432000,
             carbide_api_url: None,
             carbide_dhcp_server: Ipv4Addr::from([10, 217, 5, 39]),
+            carbide_nameservers_v6: vec![],
+            router_advertisement: None,
         };
 
         let mut network_config = rpc::ManagedHostNetworkConfigResponse {
@@ -3228,6 +3230,8 @@ mod tests {
             rebinding_time_secs: 432000,
             carbide_api_url: None,
             carbide_dhcp_server: Ipv4Addr::from([10, 217, 5, 39]),
+            carbide_nameservers_v6: vec![],
+            router_advertisement: None,
         };
         let dhcp_contents = super::read_limited(g.path())?;
         assert!(dhcp_contents.contains("vlan196"));
@@ -0,0 +1,3 @@
+-- Allows replaying machine state transitions since a certain point in time,
+-- which is used by the Watch*States RPCs to resume a stream.
+CREATE INDEX IF NOT EXISTS machine_state_history_timestamp_idx ON machine_state_history (timestamp);
@@ -0,0 +1,35 @@
+-- Transactional outbox for state changes. Entries are written in the same
+-- transaction as the state transition, and are removed by the outbox relay
+-- once they have been delivered to the hook named in `hook`.
+CREATE TABLE state_change_outbox(
+    id BIGSERIAL PRIMARY KEY,
+    controller VARCHAR(64) NOT NULL,
+    hook VARCHAR(64) NOT NULL,
+    object_id VARCHAR(256) NOT NULL,
+    state JSONB NOT NULL,
+    state_version VARCHAR(64) NOT NULL,
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_error TEXT
+);
+
+-- Used to find the oldest pending entry for each object and hook,
+-- which preserves the delivery order per object
+CREATE INDEX idx_state_change_outbox_head ON state_change_outbox(controller, hook, object_id, id);
+
+-- Entries which could not be delivered within the configured amount of attempts
+CREATE TABLE state_change_outbox_dead_letters(
+    id BIGINT PRIMARY KEY,
+    controller VARCHAR(64) NOT NULL,
+    hook VARCHAR(64) NOT NULL,
+    object_id VARCHAR(256) NOT NULL,
+    state JSONB NOT NULL,
+    state_version VARCHAR(64) NOT NULL,
+    created TIMESTAMPTZ NOT NULL,
+    attempts INTEGER NOT NULL,
+    last_error TEXT,
+    failed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_state_change_outbox_dead_letters_controller ON state_change_outbox_dead_letters(controller, hook);
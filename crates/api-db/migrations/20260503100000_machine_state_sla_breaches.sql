@@ -0,0 +1,28 @@
+-- Records each time a host stayed in a state for longer than the SLA of that state.
+-- At most one breach is recorded each time a host enters a state.
+CREATE TABLE machine_state_sla_breaches (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    machine_id VARCHAR(256) NOT NULL,
+    state jsonb NOT NULL,
+    state_version VARCHAR(64) NOT NULL,
+    state_name VARCHAR(64) NOT NULL,
+    substate_name VARCHAR(64) NOT NULL,
+    sla_secs BIGINT NOT NULL,
+    entered_at TIMESTAMPTZ NOT NULL,
+    detected_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (machine_id, state_version)
+);
+
+CREATE OR REPLACE FUNCTION machine_state_sla_breaches_keep_limit()
+RETURNS TRIGGER AS
+$body$
+BEGIN
+    DELETE FROM machine_state_sla_breaches WHERE machine_id=NEW.machine_id AND id NOT IN (SELECT id from machine_state_sla_breaches where machine_id=NEW.machine_id ORDER BY id DESC LIMIT 250);
+    RETURN NULL;
+END;
+$body$
+LANGUAGE plpgsql;
+
+CREATE TRIGGER t_machine_state_sla_breaches_keep_limit
+  AFTER INSERT ON machine_state_sla_breaches
+  FOR EACH ROW EXECUTE PROCEDURE machine_state_sla_breaches_keep_limit();
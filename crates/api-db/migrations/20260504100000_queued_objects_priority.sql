@@ -0,0 +1,34 @@
+-- Introduces priority classes for all queued object tables
+-- 0 = tenant impacting, 1 = operator requested, 2 = background
+
+ALTER TABLE machine_state_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 2,
+    ADD COLUMN queued_at timestamptz NOT NULL DEFAULT NOW();
+
+ALTER TABLE network_segments_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 2,
+    ADD COLUMN queued_at timestamptz NOT NULL DEFAULT NOW();
+
+ALTER TABLE ib_partition_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 2,
+    ADD COLUMN queued_at timestamptz NOT NULL DEFAULT NOW();
+
+ALTER TABLE dpa_interfaces_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 2,
+    ADD COLUMN queued_at timestamptz NOT NULL DEFAULT NOW();
+
+ALTER TABLE power_shelf_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 2,
+    ADD COLUMN queued_at timestamptz NOT NULL DEFAULT NOW();
+
+ALTER TABLE switch_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 2,
+    ADD COLUMN queued_at timestamptz NOT NULL DEFAULT NOW();
+
+ALTER TABLE rack_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 2,
+    ADD COLUMN queued_at timestamptz NOT NULL DEFAULT NOW();
+
+ALTER TABLE attestation_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 2,
+    ADD COLUMN queued_at timestamptz NOT NULL DEFAULT NOW();
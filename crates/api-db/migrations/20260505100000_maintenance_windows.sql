@@ -0,0 +1,20 @@
+-- Maintenance windows restrict disruptive actions like firmware updates or
+-- reprovisioning on hosts, racks and switches to a certain time range.
+CREATE TABLE maintenance_windows (
+    id uuid PRIMARY KEY,
+    name VARCHAR(256) NOT NULL,
+    description VARCHAR(1024) NOT NULL DEFAULT '',
+    labels jsonb NOT NULL DEFAULT '{}',
+    start_time TIMESTAMPTZ NOT NULL,
+    end_time TIMESTAMPTZ NOT NULL,
+    -- Serialized MaintenanceWindowScope
+    scope jsonb NOT NULL,
+    -- Serialized list of MaintenanceAction. An empty list allows all actions.
+    allowed_actions jsonb NOT NULL DEFAULT '[]',
+    created_by TEXT NULL,
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    cancelled TIMESTAMPTZ NULL,
+    CHECK (start_time < end_time)
+);
+
+CREATE INDEX maintenance_windows_end_time_idx ON maintenance_windows (end_time) WHERE cancelled IS NULL;
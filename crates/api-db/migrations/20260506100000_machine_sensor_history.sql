@@ -0,0 +1,25 @@
+-- Raw sensor readings of machines. Kept for a few hours.
+CREATE TABLE machine_sensor_samples (
+    machine_id VARCHAR(256) NOT NULL,
+    sensor TEXT NOT NULL,
+    name TEXT NOT NULL,
+    unit TEXT NOT NULL,
+    value DOUBLE PRECISION NOT NULL,
+    time TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_machine_sensor_samples_machine_id_time ON machine_sensor_samples (machine_id, time);
+
+-- Sensor readings of machines downsampled into 5 minute buckets. Kept for weeks.
+CREATE TABLE machine_sensor_rollups (
+    machine_id VARCHAR(256) NOT NULL,
+    sensor TEXT NOT NULL,
+    bucket TIMESTAMPTZ NOT NULL,
+    name TEXT NOT NULL,
+    unit TEXT NOT NULL,
+    min DOUBLE PRECISION NOT NULL,
+    max DOUBLE PRECISION NOT NULL,
+    sum DOUBLE PRECISION NOT NULL,
+    count BIGINT NOT NULL,
+    PRIMARY KEY (machine_id, sensor, bucket)
+);
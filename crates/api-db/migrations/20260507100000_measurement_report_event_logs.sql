@@ -0,0 +1,10 @@
+--- measurement_report_event_logs
+---
+--- The binary TCG event log a machine sent along with the quote
+--- that produced a measurement report. Used to replay the log
+--- against the report values and to diff it against earlier logs.
+CREATE TABLE measurement_report_event_logs (
+    report_id uuid PRIMARY KEY REFERENCES measurement_reports ON DELETE CASCADE,
+    event_log bytea NOT NULL,
+    ts timestamp with time zone DEFAULT clock_timestamp()
+);
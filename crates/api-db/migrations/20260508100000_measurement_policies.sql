@@ -0,0 +1,13 @@
+--- measurement_policies
+---
+--- Event-level measurement policies. Instead of approving exact PCR
+--- values (like a bundle does), a policy approves what the event log
+--- of a report says was measured. A policy without a profile_id
+--- applies to every system profile.
+CREATE TABLE measurement_policies (
+    policy_id uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    name text NOT NULL UNIQUE,
+    profile_id uuid REFERENCES measurement_system_profiles,
+    rules jsonb NOT NULL,
+    ts timestamp with time zone DEFAULT clock_timestamp()
+);
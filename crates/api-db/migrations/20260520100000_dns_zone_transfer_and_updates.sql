@@ -0,0 +1,27 @@
+--- dns_zone_transfer_and_updates
+---
+--- Zone transfer (AXFR) and RFC 2136 dynamic update policy per domain,
+--- mirrored 1:1 into the matching PowerDNS metadata kinds.
+ALTER TABLE domain_metadata
+    ADD COLUMN tsig_allow_axfr TEXT[] NOT NULL DEFAULT ARRAY[]::TEXT[],
+    ADD COLUMN allow_dnsupdate_from TEXT[] NOT NULL DEFAULT ARRAY[]::TEXT[],
+    ADD COLUMN tsig_allow_dnsupdate TEXT[] NOT NULL DEFAULT ARRAY[]::TEXT[],
+    ADD COLUMN also_notify TEXT[] NOT NULL DEFAULT ARRAY[]::TEXT[];
+
+--- The last SOA serial that secondaries were sent a NOTIFY for.
+ALTER TABLE domains ADD COLUMN notified_serial BIGINT;
+
+--- Records that are not derived from machine interfaces or instances,
+--- e.g. ones written by dynamic updates. Served next to the dns_records view.
+CREATE TABLE dns_resource_records (
+    id uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    domain_id uuid NOT NULL REFERENCES domains(id),
+    q_name VARCHAR NOT NULL,
+    q_type VARCHAR(10) NOT NULL,
+    ttl INTEGER NOT NULL DEFAULT 300,
+    content TEXT NOT NULL,
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (domain_id, q_name, q_type, content)
+);
+
+CREATE INDEX dns_resource_records_q_name_idx ON dns_resource_records (q_name);
@@ -0,0 +1,20 @@
+--- tenant_dns_records
+---
+--- DNS records tenants create in the domains of their VPC's network
+--- segments. They are only served to queries coming from the VPC's
+--- address space and are never part of a zone transfer.
+CREATE TABLE tenant_dns_records (
+    id uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_organization_id VARCHAR NOT NULL,
+    vpc_id uuid NOT NULL REFERENCES vpcs(id),
+    domain_id uuid NOT NULL REFERENCES domains(id),
+    q_name VARCHAR NOT NULL,
+    q_type VARCHAR(10) NOT NULL,
+    ttl INTEGER NOT NULL DEFAULT 300,
+    content TEXT NOT NULL,
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (vpc_id, q_name, q_type, content)
+);
+
+CREATE INDEX tenant_dns_records_q_name_idx ON tenant_dns_records (q_name);
+CREATE INDEX tenant_dns_records_tenant_organization_id_idx ON tenant_dns_records (tenant_organization_id);
@@ -0,0 +1,39 @@
+-- The Watch*States RPCs resume streams and tail the state history tables by
+-- the (txid, id) cursor of the records. txid is the ID of the transaction
+-- which wrote the record. Records of transactions which are older than the
+-- oldest transaction that is still in progress never change anymore, so
+-- reading them in (txid, id) order never skips a record which commits late.
+-- This supersedes the timestamp-only index.
+DROP INDEX IF EXISTS machine_state_history_timestamp_idx;
+ALTER TABLE machine_state_history
+    ADD COLUMN txid xid8 NOT NULL DEFAULT pg_current_xact_id();
+ALTER TABLE rack_state_history
+    ADD COLUMN txid xid8 NOT NULL DEFAULT pg_current_xact_id();
+ALTER TABLE switch_state_history
+    ADD COLUMN txid xid8 NOT NULL DEFAULT pg_current_xact_id();
+CREATE INDEX IF NOT EXISTS machine_state_history_cursor_idx
+    ON machine_state_history (txid, id);
+CREATE INDEX IF NOT EXISTS rack_state_history_cursor_idx
+    ON rack_state_history (txid, id);
+CREATE INDEX IF NOT EXISTS switch_state_history_cursor_idx
+    ON switch_state_history (txid, id);
+
+-- Wake up the state history tailers of all carbide-api instances whenever
+-- a state transition is recorded, so that watch streams do not depend on
+-- which instance processed the transition.
+CREATE OR REPLACE FUNCTION notify_state_history_written() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM pg_notify('state_history_written', TG_TABLE_NAME);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER machine_state_history_written
+    AFTER INSERT ON machine_state_history
+    FOR EACH STATEMENT EXECUTE FUNCTION notify_state_history_written();
+CREATE TRIGGER rack_state_history_written
+    AFTER INSERT ON rack_state_history
+    FOR EACH STATEMENT EXECUTE FUNCTION notify_state_history_written();
+CREATE TRIGGER switch_state_history_written
+    AFTER INSERT ON switch_state_history
+    FOR EACH STATEMENT EXECUTE FUNCTION notify_state_history_written();
@@ -18,6 +18,7 @@
 use std::str::FromStr;
 
 use carbide_uuid::domain::DomainId;
+use carbide_uuid::vpc::VpcId;
 use chrono::{DateTime, Utc};
 use hickory_proto::rr::Name;
 use model::dns::{Domain, NewDomain, SoaSnapshot};
@@ -50,6 +51,7 @@ pub struct DbDomain {
     pub deleted: Option<DateTime<Utc>>,
     pub soa: sqlx::types::Json<Option<dns_record::SoaRecord>>,
     pub domain_metadata_id: Option<i32>,
+    pub notified_serial: Option<i64>,
 }
 
 impl From<DbDomain> for Domain {
@@ -62,6 +64,7 @@ impl From<DbDomain> for Domain {
             deleted: db.deleted,
             soa: db.soa.0.map(SoaSnapshot),
             metadata: None,
+            notified_serial: db.notified_serial.map(|serial| serial as u32),
         }
     }
 }
@@ -214,6 +217,55 @@ pub async fn update(value: &mut Domain, txn: &mut PgConnection) -> Result<Domain
         .map_err(|e| DatabaseError::query(query, e))
 }
 
+/// Finds the domains of the network segments in a VPC.
+pub async fn find_by_vpc(
+    txn: impl DbReader<'_>,
+    vpc_id: VpcId,
+) -> Result<Vec<Domain>, DatabaseError> {
+    let query = r#"
+        SELECT * FROM domains
+        WHERE deleted IS NULL AND id IN (
+            SELECT subdomain_id FROM network_segments
+            WHERE vpc_id = $1 AND deleted IS NULL
+        )"#;
+    sqlx::query_as::<_, DbDomain>(query)
+        .bind(vpc_id)
+        .fetch_all(txn)
+        .await
+        .map(|domains| domains.into_iter().map(Domain::from).collect())
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Finds the domains whose SOA serial changed since secondaries were last
+/// sent a NOTIFY for them.
+pub async fn find_updated_primaries(txn: impl DbReader<'_>) -> Result<Vec<Domain>, DatabaseError> {
+    let query = r#"
+        SELECT * FROM domains
+        WHERE deleted IS NULL
+          AND soa->>'serial' IS NOT NULL
+          AND notified_serial IS DISTINCT FROM (soa->>'serial')::bigint"#;
+    sqlx::query_as::<_, DbDomain>(query)
+        .fetch_all(txn)
+        .await
+        .map(|domains| domains.into_iter().map(Domain::from).collect())
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+pub async fn set_notified_serial(
+    txn: &mut PgConnection,
+    id: DomainId,
+    serial: u32,
+) -> Result<(), DatabaseError> {
+    let query = "UPDATE domains SET notified_serial=$1 WHERE id=$2";
+    sqlx::query(query)
+        .bind(serial as i64)
+        .bind(id)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
 #[cfg(test)]
 #[test]
 fn test_generate_domain_serial_format() {
@@ -15,53 +15,127 @@
  * limitations under the License.
  */
 
+use carbide_uuid::domain::DomainId;
 use model::dns::metadata::DomainMetadata;
 use sqlx::postgres::PgRow;
 use sqlx::{Error, FromRow, Row};
 
 use crate::DatabaseError;
+use crate::db_read::DbReader;
 
 #[derive(Debug, Clone, Default)]
 pub struct DbMetadata {
     allow_axfr_from: Vec<String>,
+    tsig_allow_axfr: Vec<String>,
+    allow_dnsupdate_from: Vec<String>,
+    tsig_allow_dnsupdate: Vec<String>,
+    also_notify: Vec<String>,
 }
 
 impl<'r> FromRow<'r, PgRow> for DbMetadata {
     fn from_row(row: &'r PgRow) -> Result<Self, Error> {
         Ok(DbMetadata {
             allow_axfr_from: row.try_get("allow_axfr_from")?,
+            tsig_allow_axfr: row.try_get("tsig_allow_axfr")?,
+            allow_dnsupdate_from: row.try_get("allow_dnsupdate_from")?,
+            tsig_allow_dnsupdate: row.try_get("tsig_allow_dnsupdate")?,
+            also_notify: row.try_get("also_notify")?,
         })
     }
 }
 
 pub async fn metadata_for_domain(
-    txn: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    txn: impl DbReader<'_>,
     domain_name: &str,
 ) -> Result<DbMetadata, DatabaseError> {
     let domain_name = crate::dns::normalize_domain(domain_name);
 
     let query = "SELECT m.* FROM domain_metadata m JOIN domains d ON m.id = d.domain_metadata_id WHERE d.name = $1";
-    let metadata: DbMetadata = sqlx::query_as(query)
+    let metadata: Option<DbMetadata> = sqlx::query_as(query)
         .bind(domain_name)
-        .fetch_one(&mut **txn)
+        .fetch_optional(txn)
         .await
         .map_err(|e| DatabaseError::query(query, e))?;
-    Ok(metadata)
+    // Domains created before metadata moved to its own table may not have a row
+    Ok(metadata.unwrap_or_default())
+}
+
+/// Overwrites the metadata of a domain, creating the metadata row if the
+/// domain doesn't have one yet.
+pub async fn update_for_domain(
+    txn: &mut sqlx::PgConnection,
+    domain_id: DomainId,
+    metadata: &DomainMetadata,
+) -> Result<(), DatabaseError> {
+    let metadata = DbMetadata::from(metadata.clone());
+
+    let query = r#"
+        UPDATE domain_metadata m
+        SET allow_axfr_from = $2, tsig_allow_axfr = $3, allow_dnsupdate_from = $4,
+            tsig_allow_dnsupdate = $5, also_notify = $6
+        FROM domains d
+        WHERE d.domain_metadata_id = m.id AND d.id = $1"#;
+    let result = sqlx::query(query)
+        .bind(domain_id)
+        .bind(&metadata.allow_axfr_from)
+        .bind(&metadata.tsig_allow_axfr)
+        .bind(&metadata.allow_dnsupdate_from)
+        .bind(&metadata.tsig_allow_dnsupdate)
+        .bind(&metadata.also_notify)
+        .execute(&mut *txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    if result.rows_affected() > 0 {
+        return Ok(());
+    }
+
+    let metadata_id = metadata.persist(&mut *txn).await?;
+    let query = "UPDATE domains SET domain_metadata_id = $1 WHERE id = $2";
+    sqlx::query(query)
+        .bind(metadata_id)
+        .bind(domain_id)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
 }
 
 impl From<DbMetadata> for DomainMetadata {
     fn from(metadata: DbMetadata) -> Self {
         DomainMetadata {
             allow_axfr_from: metadata.allow_axfr_from,
+            tsig_allow_axfr: metadata.tsig_allow_axfr,
+            allow_dnsupdate_from: metadata.allow_dnsupdate_from,
+            tsig_allow_dnsupdate: metadata.tsig_allow_dnsupdate,
+            also_notify: metadata.also_notify,
+        }
+    }
+}
+
+impl From<DomainMetadata> for DbMetadata {
+    fn from(metadata: DomainMetadata) -> Self {
+        DbMetadata {
+            allow_axfr_from: metadata.allow_axfr_from,
+            tsig_allow_axfr: metadata.tsig_allow_axfr,
+            allow_dnsupdate_from: metadata.allow_dnsupdate_from,
+            tsig_allow_dnsupdate: metadata.tsig_allow_dnsupdate,
+            also_notify: metadata.also_notify,
         }
     }
 }
 
 impl DbMetadata {
     pub async fn persist(&self, txn: &mut sqlx::PgConnection) -> Result<i32, DatabaseError> {
-        let query = "INSERT INTO domain_metadata (allow_axfr_from) VALUES ($1) RETURNING id";
+        let query = r#"
+            INSERT INTO domain_metadata
+                (allow_axfr_from, tsig_allow_axfr, allow_dnsupdate_from, tsig_allow_dnsupdate, also_notify)
+            VALUES ($1, $2, $3, $4, $5) RETURNING id"#;
         let row: (i32,) = sqlx::query_as(query)
             .bind(&self.allow_axfr_from)
+            .bind(&self.tsig_allow_axfr)
+            .bind(&self.allow_dnsupdate_from)
+            .bind(&self.tsig_allow_dnsupdate)
+            .bind(&self.also_notify)
             .fetch_one(txn)
             .await
             .map_err(|e| DatabaseError::query(query, e))?;
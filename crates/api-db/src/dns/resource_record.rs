@@ -14,16 +14,31 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use std::net::IpAddr;
-
 use carbide_uuid::domain::DomainId;
 use dns_record::SoaRecord;
+use model::dns::ResourceRecord;
 use sqlx::postgres::PgRow;
-use sqlx::{Error, FromRow, Row};
+use sqlx::{Error, FromRow, PgConnection, Row};
 
 use crate::DatabaseError;
 use crate::db_read::DbReader;
 
+/// All records served for a domain: the ones derived from machine interfaces
+/// (the `dns_records` view, which stores addresses as `inet`) and the ones
+/// stored in `dns_resource_records`, e.g. written by RFC 2136 dynamic updates.
+// TODO: Configurable defaults for TTL
+const ALL_RECORDS: &str = r#"(
+    SELECT q_name,
+           host(resource_record) AS resource_record,
+           domain_id,
+           COALESCE(ttl, 300) AS ttl,
+           COALESCE(q_type, CASE WHEN family(resource_record) = 6 THEN 'AAAA' ELSE 'A' END) AS q_type
+    FROM dns_records
+    UNION ALL
+    SELECT q_name, content AS resource_record, domain_id, ttl, q_type
+    FROM dns_resource_records
+) dr"#;
+
 #[derive(Debug, Clone)]
 pub struct DbResourceRecord {
     pub q_type: String,
@@ -56,10 +71,7 @@ impl<'r> FromRow<'r, PgRow> for DbSoaRecord {
 
 impl<'r> FromRow<'r, PgRow> for DbResourceRecord {
     fn from_row(row: &'r PgRow) -> Result<Self, Error> {
-        // Stored as IP address in the database
-        let record: String = row
-            .try_get("resource_record")
-            .map(|i: IpAddr| i.to_string())?;
+        let record: String = row.try_get("resource_record")?;
         let q_name: String = row.try_get("q_name")?;
         let q_type: String = row.try_get("q_type")?;
         let ttl: i32 = row.try_get("ttl")?;
@@ -92,42 +104,36 @@ pub async fn find_record(
     txn: impl DbReader<'_>,
     query_name: &str,
 ) -> Result<Vec<DbResourceRecord>, DatabaseError> {
-    // TODO: Configurable defaults for TTL
-    let query = r#"
-    SELECT
-     q_name,
-     resource_record,
-     domain_id,
-     COALESCE(ttl, 300) as ttl,
-     COALESCE(q_type, CASE WHEN family(resource_record) = 6 THEN 'AAAA' ELSE 'A' END) as q_type
-     from dns_records WHERE q_name=$1"#;
+    let query = format!(
+        "SELECT q_name, resource_record, domain_id, ttl, q_type FROM {ALL_RECORDS} WHERE q_name=$1"
+    );
 
     tracing::info!("Looking up record using query_name: {}", query_name);
-    let result = sqlx::query_as::<_, DbResourceRecord>(query)
+    let result = sqlx::query_as::<_, DbResourceRecord>(&query)
         .bind(query_name)
         .fetch_all(txn)
         .await
-        .map_err(|e| DatabaseError::query(query, e))?;
+        .map_err(|e| DatabaseError::query(&query, e))?;
 
     Ok(result)
 }
 pub async fn get_all_records_all_domains(
     txn: impl DbReader<'_>,
 ) -> Result<Vec<DbResourceRecord>, DatabaseError> {
-    let query = r#"
-        SELECT dr.q_name, dr.resource_record, dr.domain_id,
-               COALESCE(dr.ttl, 300) as ttl,
-               COALESCE(dr.q_type, CASE WHEN family(dr.resource_record) = 6 THEN 'AAAA' ELSE 'A' END) as q_type
-        FROM dns_records dr
+    let query = format!(
+        r#"
+        SELECT dr.q_name, dr.resource_record, dr.domain_id, dr.ttl, dr.q_type
+        FROM {ALL_RECORDS}
         JOIN domains d ON d.id = dr.domain_id
         WHERE d.deleted IS NULL
         ORDER BY dr.q_name
-    "#;
+    "#
+    );
 
-    sqlx::query_as::<_, DbResourceRecord>(query)
+    sqlx::query_as::<_, DbResourceRecord>(&query)
         .fetch_all(txn)
         .await
-        .map_err(|e| DatabaseError::query(query, e))
+        .map_err(|e| DatabaseError::query(&query, e))
 }
 
 pub async fn get_all_records(
@@ -135,18 +141,102 @@ pub async fn get_all_records(
     query_name: &str,
 ) -> Result<Vec<DbResourceRecord>, DatabaseError> {
     let domain_name = crate::dns::normalize_domain(query_name);
-    let query = r#"
-        SELECT dr.q_name, dr.resource_record, dr.domain_id,
-               COALESCE(dr.ttl, 300) as ttl,
-               COALESCE(dr.q_type, CASE WHEN family(dr.resource_record) = 6 THEN 'AAAA' ELSE 'A' END) as q_type
-        FROM dns_records dr
+    let query = format!(
+        r#"
+        SELECT dr.q_name, dr.resource_record, dr.domain_id, dr.ttl, dr.q_type
+        FROM {ALL_RECORDS}
         JOIN domains d ON d.id = dr.domain_id
         WHERE d.name = $1 AND d.deleted IS NULL
-    "#;
+        ORDER BY dr.q_name, dr.q_type
+    "#
+    );
 
-    sqlx::query_as::<_, DbResourceRecord>(query)
+    sqlx::query_as::<_, DbResourceRecord>(&query)
         .bind(domain_name)
         .fetch_all(txn)
         .await
-        .map_err(|e| DatabaseError::query(query, e))
+        .map_err(|e| DatabaseError::query(&query, e))
+}
+
+/// Whether `q_name`/`q_type` is served from the `dns_records` view. Those
+/// records follow machine interfaces and instances and can't be changed
+/// through `dns_resource_records`.
+pub async fn is_derived_record(
+    txn: impl DbReader<'_>,
+    query_name: &str,
+    query_type: &str,
+) -> Result<bool, DatabaseError> {
+    const QUERY: &str = r#"
+        SELECT EXISTS (
+            SELECT 1 FROM dns_records
+            WHERE q_name = $1
+              AND COALESCE(q_type, CASE WHEN family(resource_record) = 6 THEN 'AAAA' ELSE 'A' END) = $2
+        )"#;
+    let (exists,): (bool,) = sqlx::query_as(QUERY)
+        .bind(query_name)
+        .bind(query_type)
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::query(QUERY, e))?;
+    Ok(exists)
+}
+
+/// Replaces all stored records of type `query_type` at `query_name` with
+/// `records`. An empty `records` deletes the record set.
+pub async fn replace_record_set(
+    txn: &mut PgConnection,
+    domain_id: DomainId,
+    query_name: &str,
+    query_type: &str,
+    records: &[ResourceRecord],
+) -> Result<(), DatabaseError> {
+    const QUERY: &str =
+        "DELETE FROM dns_resource_records WHERE domain_id = $1 AND q_name = $2 AND q_type = $3";
+    sqlx::query(QUERY)
+        .bind(domain_id)
+        .bind(query_name)
+        .bind(query_type)
+        .execute(&mut *txn)
+        .await
+        .map_err(|e| DatabaseError::query(QUERY, e))?;
+
+    for record in records {
+        add_record(&mut *txn, domain_id, record).await?;
+    }
+    Ok(())
+}
+
+/// Stores a single record. Adding a record that already exists only updates
+/// its TTL.
+pub async fn add_record(
+    txn: &mut PgConnection,
+    domain_id: DomainId,
+    record: &ResourceRecord,
+) -> Result<(), DatabaseError> {
+    const QUERY: &str = r#"
+        INSERT INTO dns_resource_records (domain_id, q_name, q_type, ttl, content)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (domain_id, q_name, q_type, content) DO UPDATE SET ttl = EXCLUDED.ttl"#;
+    sqlx::query(QUERY)
+        .bind(domain_id)
+        .bind(&record.q_name)
+        .bind(&record.q_type)
+        .bind(record.ttl as i32)
+        .bind(&record.content)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(QUERY, e))?;
+    Ok(())
+}
+
+/// Whether any record is served for `query_name`, either derived from a
+/// machine interface or stored in `dns_resource_records`.
+pub async fn name_exists(txn: impl DbReader<'_>, query_name: &str) -> Result<bool, DatabaseError> {
+    let query = format!("SELECT EXISTS (SELECT 1 FROM {ALL_RECORDS} WHERE q_name = $1)");
+    let (exists,): (bool,) = sqlx::query_as(&query)
+        .bind(query_name)
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))?;
+    Ok(exists)
 }
@@ -0,0 +1,179 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::domain::{DomainId, TenantDnsRecordId};
+use carbide_uuid::vpc::VpcId;
+use chrono::{DateTime, Utc};
+use model::dns::{NewTenantDnsRecord, TenantDnsRecord, TenantDnsRecordSearchFilter};
+use sqlx::{FromRow, PgConnection};
+
+use super::resource_record::DbResourceRecord;
+use crate::DatabaseError;
+use crate::db_read::DbReader;
+
+#[derive(Clone, Debug, FromRow)]
+pub struct DbTenantDnsRecord {
+    pub id: TenantDnsRecordId,
+    pub vpc_id: VpcId,
+    pub tenant_organization_id: String,
+    pub domain_id: DomainId,
+    pub q_name: String,
+    pub q_type: String,
+    pub ttl: i32,
+    pub content: String,
+    pub created: DateTime<Utc>,
+}
+
+impl From<DbTenantDnsRecord> for TenantDnsRecord {
+    fn from(db: DbTenantDnsRecord) -> Self {
+        TenantDnsRecord {
+            id: db.id,
+            vpc_id: db.vpc_id,
+            tenant_organization_id: db.tenant_organization_id,
+            domain_id: db.domain_id,
+            q_name: db.q_name,
+            q_type: db.q_type,
+            ttl: db.ttl as u32,
+            content: db.content,
+            created: db.created,
+        }
+    }
+}
+
+/// Serializes record creation per tenant for the rest of the transaction, so
+/// concurrent requests can't exceed the tenant's quota.
+pub async fn lock_tenant(
+    txn: &mut PgConnection,
+    tenant_organization_id: &str,
+) -> Result<(), DatabaseError> {
+    let query = "SELECT pg_advisory_xact_lock(hashtextextended($1::text, 0))";
+    sqlx::query(query)
+        .bind(format!("tenant_dns_records.{tenant_organization_id}"))
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+pub async fn count_for_tenant(
+    txn: impl DbReader<'_>,
+    tenant_organization_id: &str,
+) -> Result<i64, DatabaseError> {
+    let query = "SELECT COUNT(*) FROM tenant_dns_records WHERE tenant_organization_id = $1";
+    let (count,): (i64,) = sqlx::query_as(query)
+        .bind(tenant_organization_id)
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(count)
+}
+
+pub async fn persist(
+    txn: &mut PgConnection,
+    value: &NewTenantDnsRecord,
+    tenant_organization_id: &str,
+    domain_id: DomainId,
+) -> Result<TenantDnsRecord, DatabaseError> {
+    let query = r#"
+        INSERT INTO tenant_dns_records
+            (tenant_organization_id, vpc_id, domain_id, q_name, q_type, ttl, content)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING *"#;
+    match sqlx::query_as::<_, DbTenantDnsRecord>(query)
+        .bind(tenant_organization_id)
+        .bind(value.vpc_id)
+        .bind(domain_id)
+        .bind(&value.q_name)
+        .bind(value.q_type.to_string())
+        .bind(value.ttl as i32)
+        .bind(&value.content)
+        .fetch_one(txn)
+        .await
+    {
+        Ok(record) => Ok(record.into()),
+        Err(sqlx::Error::Database(db_err)) if db_err.is_unique_violation() => {
+            Err(DatabaseError::AlreadyFoundError {
+                kind: "tenant_dns_record",
+                id: format!("{} {} {}", value.q_name, value.q_type, value.content),
+            })
+        }
+        Err(e) => Err(DatabaseError::query(query, e)),
+    }
+}
+
+pub async fn find(
+    txn: impl DbReader<'_>,
+    filter: &TenantDnsRecordSearchFilter,
+) -> Result<Vec<TenantDnsRecord>, DatabaseError> {
+    let query = r#"
+        SELECT * FROM tenant_dns_records
+        WHERE ($1::uuid IS NULL OR vpc_id = $1)
+          AND ($2::varchar IS NULL OR tenant_organization_id = $2)
+          AND ($3::varchar IS NULL OR q_name = $3)
+        ORDER BY q_name, q_type, content"#;
+    sqlx::query_as::<_, DbTenantDnsRecord>(query)
+        .bind(filter.vpc_id)
+        .bind(&filter.tenant_organization_id)
+        .bind(&filter.q_name)
+        .fetch_all(txn)
+        .await
+        .map(|records| records.into_iter().map(TenantDnsRecord::from).collect())
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+pub async fn delete(
+    txn: &mut PgConnection,
+    id: TenantDnsRecordId,
+) -> Result<Option<TenantDnsRecord>, DatabaseError> {
+    let query = "DELETE FROM tenant_dns_records WHERE id = $1 RETURNING *";
+    sqlx::query_as::<_, DbTenantDnsRecord>(query)
+        .bind(id)
+        .fetch_optional(txn)
+        .await
+        .map(|record| record.map(TenantDnsRecord::from))
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Deletes the records of a VPC, e.g. when the VPC is deleted.
+pub async fn delete_for_vpc(txn: &mut PgConnection, vpc_id: VpcId) -> Result<(), DatabaseError> {
+    let query = "DELETE FROM tenant_dns_records WHERE vpc_id = $1";
+    sqlx::query(query)
+        .bind(vpc_id)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+/// Finds the records tenants of `vpc_id` created for `query_name`.
+pub async fn find_for_vpc(
+    txn: impl DbReader<'_>,
+    query_name: &str,
+    vpc_id: VpcId,
+) -> Result<Vec<DbResourceRecord>, DatabaseError> {
+    let query = r#"
+        SELECT t.q_name, t.content AS resource_record, t.domain_id, t.ttl, t.q_type
+        FROM tenant_dns_records t
+        JOIN vpcs v ON v.id = t.vpc_id AND v.deleted IS NULL
+        WHERE t.q_name = $1 AND t.vpc_id = $2"#;
+    sqlx::query_as::<_, DbResourceRecord>(query)
+        .bind(query_name)
+        .bind(vpc_id)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
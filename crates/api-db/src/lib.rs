@@ -52,11 +52,13 @@ pub mod machine;
 pub mod machine_boot_override;
 pub mod machine_interface;
 pub mod machine_interface_address;
+pub mod machine_state_sla_breach;
 pub mod machine_topology;
 pub mod machine_validation;
 pub mod machine_validation_config;
 pub mod machine_validation_result;
 pub mod machine_validation_suites;
+pub mod maintenance_window;
 pub mod managed_host;
 pub mod measured_boot;
 pub mod migrations;
@@ -77,8 +79,10 @@ pub mod rack_firmware;
 pub mod redfish_actions;
 pub mod resource_pool;
 pub mod route_servers;
+pub mod sensor_history;
 pub mod site_exploration_report;
 pub mod sku;
+pub mod state_change_outbox;
 pub mod state_history;
 pub mod switch;
 pub mod tenant;
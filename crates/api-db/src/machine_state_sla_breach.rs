@@ -0,0 +1,90 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Records of hosts which stayed in a state for longer than its SLA
+
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
+use config_version::ConfigVersion;
+use model::machine::ManagedHostState;
+use sqlx::{FromRow, PgConnection};
+
+use crate::{DatabaseError, DatabaseResult};
+
+/// A state in which a host stayed for longer than the SLA of the state
+#[derive(Debug, Clone, FromRow)]
+pub struct MachineStateSlaBreach {
+    pub machine_id: String,
+    /// The state, serialized as JSON
+    pub state: String,
+    /// The version of the state. Identifies when the host entered the state.
+    pub state_version: ConfigVersion,
+    pub state_name: String,
+    pub substate_name: String,
+    /// The SLA that was exceeded, in seconds
+    pub sla_secs: i64,
+    /// When the host entered the state
+    pub entered_at: DateTime<Utc>,
+    /// When the state controller detected the breach
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Records that a host exceeded the SLA of its current state.
+///
+/// Returns `false` if the breach had already been recorded for this state version.
+pub async fn record(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+    state: &ManagedHostState,
+    state_version: ConfigVersion,
+    state_name: &str,
+    substate_name: &str,
+    sla: std::time::Duration,
+) -> DatabaseResult<bool> {
+    let query = "INSERT INTO machine_state_sla_breaches
+            (machine_id, state, state_version, state_name, substate_name, sla_secs, entered_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (machine_id, state_version) DO NOTHING";
+    let result = sqlx::query(query)
+        .bind(machine_id.to_string())
+        .bind(sqlx::types::Json(state))
+        .bind(state_version)
+        .bind(state_name)
+        .bind(substate_name)
+        .bind(sla.as_secs() as i64)
+        .bind(state_version.timestamp())
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Returns the recorded SLA breaches of a host, starting with the oldest
+pub async fn find_by_machine_id(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+) -> DatabaseResult<Vec<MachineStateSlaBreach>> {
+    let query = "SELECT machine_id, state::TEXT, state_version, state_name, substate_name,
+            sla_secs, entered_at, detected_at
+        FROM machine_state_sla_breaches
+        WHERE machine_id = $1
+        ORDER BY id";
+    sqlx::query_as(query)
+        .bind(machine_id.to_string())
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
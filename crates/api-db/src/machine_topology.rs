@@ -222,6 +222,23 @@ pub async fn find_machine_id_by_bmc_ip(
         .map_err(|e| DatabaseError::query(query, e))
 }
 
+/// Returns the managed host state name (the `state` tag of the machine's controller state, e.g.
+/// `assigned` or `ready`) of the machine whose BMC has the given IP address.
+pub async fn find_machine_state_by_bmc_ip(
+    txn: impl DbReader<'_>,
+    address: &str,
+) -> Result<Option<String>, DatabaseError> {
+    let query = "SELECT m.controller_state->>'state' FROM machine_topologies mt
+        INNER JOIN machines m ON m.id = mt.machine_id
+        WHERE mt.topology->'bmc_info'->>'ip' = $1";
+    sqlx::query_scalar::<_, Option<String>>(query)
+        .bind(address)
+        .fetch_optional(txn)
+        .await
+        .map(Option::flatten)
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
 pub async fn find_machine_id_by_bmc_mac(
     txn: &mut PgConnection,
     mac_address: mac_address::MacAddress,
@@ -0,0 +1,120 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Maintenance windows for hosts, racks and switches
+
+use carbide_uuid::maintenance_window::MaintenanceWindowId;
+use chrono::{DateTime, Utc};
+use model::maintenance_window::{MaintenanceWindow, NewMaintenanceWindow};
+use sqlx::PgConnection;
+
+use crate::{DatabaseError, DatabaseResult};
+
+pub async fn create(
+    txn: &mut PgConnection,
+    window: &NewMaintenanceWindow,
+) -> DatabaseResult<MaintenanceWindow> {
+    let query = "INSERT INTO maintenance_windows
+            (id, name, description, labels, start_time, end_time, scope, allowed_actions, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING *";
+    sqlx::query_as(query)
+        .bind(MaintenanceWindowId::new())
+        .bind(&window.metadata.name)
+        .bind(&window.metadata.description)
+        .bind(sqlx::types::Json(&window.metadata.labels))
+        .bind(window.start_time)
+        .bind(window.end_time)
+        .bind(sqlx::types::Json(&window.scope))
+        .bind(sqlx::types::Json(&window.allowed_actions))
+        .bind(&window.created_by)
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Returns maintenance windows ordered by their start time.
+///
+/// Unless `include_inactive` is set, windows which have ended or were cancelled are omitted.
+pub async fn find(
+    txn: &mut PgConnection,
+    include_inactive: bool,
+) -> DatabaseResult<Vec<MaintenanceWindow>> {
+    let query = "SELECT * FROM maintenance_windows
+        WHERE $1 OR (cancelled IS NULL AND end_time > NOW())
+        ORDER BY start_time, id";
+    sqlx::query_as(query)
+        .bind(include_inactive)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+pub async fn find_by_id(
+    txn: &mut PgConnection,
+    id: &MaintenanceWindowId,
+) -> DatabaseResult<MaintenanceWindow> {
+    let query = "SELECT * FROM maintenance_windows WHERE id = $1";
+    sqlx::query_as(query)
+        .bind(id)
+        .fetch_one(txn)
+        .await
+        .map_err(|err: sqlx::Error| match err {
+            sqlx::Error::RowNotFound => DatabaseError::NotFoundError {
+                kind: "maintenance_window",
+                id: id.to_string(),
+            },
+            _ => DatabaseError::query(query, err),
+        })
+}
+
+/// Returns the windows which have not ended or been cancelled at time `now`.
+/// These are the windows which can affect whether an action may be performed.
+pub async fn find_relevant(
+    txn: &mut PgConnection,
+    now: DateTime<Utc>,
+) -> DatabaseResult<Vec<MaintenanceWindow>> {
+    let query = "SELECT * FROM maintenance_windows
+        WHERE cancelled IS NULL AND end_time > $1
+        ORDER BY start_time, id";
+    sqlx::query_as(query)
+        .bind(now)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Cancels a maintenance window. Cancelling a window a second time keeps the
+/// original cancellation time.
+pub async fn cancel(
+    txn: &mut PgConnection,
+    id: &MaintenanceWindowId,
+) -> DatabaseResult<MaintenanceWindow> {
+    let query = "UPDATE maintenance_windows SET cancelled = COALESCE(cancelled, NOW())
+        WHERE id = $1
+        RETURNING *";
+    sqlx::query_as(query)
+        .bind(id)
+        .fetch_one(txn)
+        .await
+        .map_err(|err: sqlx::Error| match err {
+            sqlx::Error::RowNotFound => DatabaseError::NotFoundError {
+                kind: "maintenance_window",
+                id: id.to_string(),
+            },
+            _ => DatabaseError::query(query, err),
+        })
+}
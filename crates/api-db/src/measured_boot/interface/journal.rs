@@ -134,3 +134,17 @@ pub async fn get_measurement_journal_records_for_machine_id(
         .await
         .map_err(|e| e.with_op_name("get_measurement_journal_records_for_machine_id"))
 }
+
+/// get_latest_measurement_journal_records_for_state returns the latest
+/// journal record of every machine whose latest record is in `state`.
+pub async fn get_latest_measurement_journal_records_for_state(
+    txn: &mut PgConnection,
+    state: MeasurementMachineState,
+) -> Result<Vec<MeasurementJournalRecord>, DatabaseError> {
+    let query = "select * from (select distinct on (machine_id) * from measurement_journal order by machine_id,ts desc) latest where state = $1";
+    sqlx::query_as(query)
+        .bind(state)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::new("get_latest_measurement_journal_records_for_state", e))
+}
@@ -24,6 +24,7 @@
  *  - `common`: Generic functions leveraged by all interfaces.
  *  - `journal`: Measurement journals.
  *  - `machine`: Mock machines (will eventually go away).
+ *  - `policy`: Event-level measurement policies.
  *  - `profile`: System profiles.
  *  - `report`: Machine measurement reports.
  *  - `site`: Site management.
@@ -33,6 +34,7 @@ pub mod bundle;
 pub mod common;
 pub mod journal;
 pub mod machine;
+pub mod policy;
 pub mod profile;
 pub mod report;
 pub mod site;
@@ -0,0 +1,112 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+/*!
+ *  Code for working the measurement_policies table in the database.
+ */
+
+use carbide_uuid::measured_boot::{MeasurementPolicyId, MeasurementSystemProfileId};
+use chrono::Utc;
+use measured_boot::policy::{MeasurementPolicy, PolicyRule};
+use sqlx::PgConnection;
+use sqlx::types::Json;
+
+use crate::DatabaseError;
+
+/// MeasurementPolicyRow is a single row from the measurement_policies
+/// table, with the rules stored as JSON.
+#[derive(Debug, sqlx::FromRow)]
+struct MeasurementPolicyRow {
+    policy_id: MeasurementPolicyId,
+    name: String,
+    profile_id: Option<MeasurementSystemProfileId>,
+    rules: Json<Vec<PolicyRule>>,
+    ts: chrono::DateTime<Utc>,
+}
+
+impl From<MeasurementPolicyRow> for MeasurementPolicy {
+    fn from(row: MeasurementPolicyRow) -> Self {
+        Self {
+            policy_id: row.policy_id,
+            name: row.name,
+            profile_id: row.profile_id,
+            rules: row.rules.0,
+            ts: row.ts,
+        }
+    }
+}
+
+/// insert_measurement_policy inserts a new policy.
+pub async fn insert_measurement_policy(
+    txn: &mut PgConnection,
+    name: &str,
+    profile_id: Option<MeasurementSystemProfileId>,
+    rules: &[PolicyRule],
+) -> Result<MeasurementPolicy, DatabaseError> {
+    let query =
+        "insert into measurement_policies(name, profile_id, rules) values($1, $2, $3) returning *";
+    sqlx::query_as::<_, MeasurementPolicyRow>(query)
+        .bind(name)
+        .bind(profile_id)
+        .bind(Json(rules))
+        .fetch_one(txn)
+        .await
+        .map(Into::into)
+        .map_err(|e| DatabaseError::new("insert_measurement_policy", e))
+}
+
+/// delete_policy_for_id deletes a policy, returning
+/// the policy that was deleted (if it existed).
+pub async fn delete_policy_for_id(
+    txn: &mut PgConnection,
+    policy_id: MeasurementPolicyId,
+) -> Result<Option<MeasurementPolicy>, DatabaseError> {
+    let query = "delete from measurement_policies where policy_id = $1 returning *";
+    sqlx::query_as::<_, MeasurementPolicyRow>(query)
+        .bind(policy_id)
+        .fetch_optional(txn)
+        .await
+        .map(|row| row.map(Into::into))
+        .map_err(|e| DatabaseError::new("delete_policy_for_id", e))
+}
+
+/// get_measurement_policies returns all policies.
+pub async fn get_measurement_policies(
+    txn: &mut PgConnection,
+) -> Result<Vec<MeasurementPolicy>, DatabaseError> {
+    let query = "select * from measurement_policies order by name";
+    sqlx::query_as::<_, MeasurementPolicyRow>(query)
+        .fetch_all(txn)
+        .await
+        .map(|rows| rows.into_iter().map(Into::into).collect())
+        .map_err(|e| DatabaseError::new("get_measurement_policies", e))
+}
+
+/// get_measurement_policies_for_profile_id returns the policies that
+/// apply to `profile_id`, which includes the policies that apply to
+/// every profile.
+pub async fn get_measurement_policies_for_profile_id(
+    txn: &mut PgConnection,
+    profile_id: MeasurementSystemProfileId,
+) -> Result<Vec<MeasurementPolicy>, DatabaseError> {
+    let query = "select * from measurement_policies where profile_id is null or profile_id = $1 order by name";
+    sqlx::query_as::<_, MeasurementPolicyRow>(query)
+        .bind(profile_id)
+        .fetch_all(txn)
+        .await
+        .map(|rows| rows.into_iter().map(Into::into).collect())
+        .map_err(|e| DatabaseError::new("get_measurement_policies_for_profile_id", e))
+}
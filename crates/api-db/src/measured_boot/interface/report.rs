@@ -245,6 +245,61 @@ pub(crate) async fn update_report_values_tstamp(
         .map_err(|e| DatabaseError::new("update_report_values_tstamp", e))
 }
 
+/// upsert_report_event_log stores the binary event log that came
+/// with the quote for a report, replacing any previous log (reports
+/// get reused when a machine reports the same values again).
+pub async fn upsert_report_event_log(
+    txn: &mut PgConnection,
+    report_id: MeasurementReportId,
+    event_log: &[u8],
+) -> Result<(), DatabaseError> {
+    let query = "INSERT INTO measurement_report_event_logs (report_id, event_log) VALUES ($1, $2) \
+        ON CONFLICT (report_id) DO UPDATE SET event_log = EXCLUDED.event_log, ts = clock_timestamp()";
+    sqlx::query(query)
+        .bind(report_id)
+        .bind(event_log)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::new("upsert_report_event_log", e))?;
+    Ok(())
+}
+
+/// get_report_event_log returns the binary event log stored
+/// for a report, if the machine sent one.
+pub async fn get_report_event_log(
+    txn: &mut PgConnection,
+    report_id: MeasurementReportId,
+) -> Result<Option<Vec<u8>>, DatabaseError> {
+    let query = "SELECT event_log FROM measurement_report_event_logs WHERE report_id = $1";
+    sqlx::query_scalar(query)
+        .bind(report_id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::new("get_report_event_log", e))
+}
+
+/// get_reference_report_event_log returns the event log of the latest
+/// report for `machine_id` (other than `report_id`) that was measured
+/// against an active bundle, which is what a new event log gets diffed
+/// against by default.
+pub async fn get_reference_report_event_log(
+    txn: &mut PgConnection,
+    machine_id: MachineId,
+    report_id: MeasurementReportId,
+) -> Result<Option<(MeasurementReportId, Vec<u8>)>, DatabaseError> {
+    let query = "SELECT l.report_id, l.event_log FROM measurement_report_event_logs l \
+        JOIN measurement_reports r ON r.report_id = l.report_id \
+        WHERE r.machine_id = $1 AND r.report_id != $2 \
+        AND EXISTS (SELECT 1 FROM measurement_journal j WHERE j.report_id = r.report_id AND j.state = 'measured') \
+        ORDER BY r.ts DESC LIMIT 1";
+    sqlx::query_as(query)
+        .bind(machine_id)
+        .bind(report_id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::new("get_reference_report_event_log", e))
+}
+
 /// get_all_measurement_report_value_records returns all
 /// MeasurementReportValueRecord instances in the database. This leverages
 /// the generic get_all_objects function since its a simple/common pattern.
@@ -22,6 +22,7 @@
  *  - `bundle`: Measurement bundles.
  *  - `journal`: Measurement journals.
  *  - `machine`: Mock machines (will eventually go away).
+ *  - `policy`: Event-level measurement policies.
  *  - `profile`: System profiles.
  *  - `report`: Machine measurement reports.
  *  - `site`: Site management.
@@ -31,6 +32,7 @@ pub mod bundle;
 pub mod interface;
 pub mod journal;
 pub mod machine;
+pub mod policy;
 pub mod profile;
 pub mod report;
 pub mod site;
@@ -0,0 +1,198 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+/*!
+ *  Code for working with event-level measurement policies, including
+ *  approving machines whose event logs satisfy them.
+ *
+ *  A policy only ever moves a machine from PendingBundle to Measured:
+ *  a report that matches a bundle is handled by the bundle, and a
+ *  policy failure leaves the machine pending (since MeasuringFailed
+ *  means a retired or revoked bundle was matched).
+ */
+
+use carbide_uuid::measured_boot::{
+    MeasurementPolicyId, MeasurementReportId, MeasurementSystemProfileId,
+};
+use measured_boot::event_log::EventLog;
+use measured_boot::policy::{MeasurementPolicy, PolicyRule};
+use measured_boot::records::MeasurementMachineState;
+use measured_boot::replay::EventLogAnalysis;
+use sqlx::PgConnection;
+
+use crate::measured_boot::interface::journal::{
+    get_latest_measurement_journal_records_for_state, get_measurement_journal_record_by_report_id,
+};
+use crate::measured_boot::interface::policy::{
+    delete_policy_for_id, get_measurement_policies, get_measurement_policies_for_profile_id,
+    insert_measurement_policy,
+};
+use crate::measured_boot::interface::report::get_report_event_log;
+use crate::{DatabaseError, DatabaseResult};
+
+/// new creates a new policy, and then re-evaluates every machine
+/// that is currently pending a bundle, since the new policy may
+/// be all they were waiting for.
+pub async fn new(
+    txn: &mut PgConnection,
+    name: &str,
+    profile_id: Option<MeasurementSystemProfileId>,
+    rules: &[PolicyRule],
+) -> DatabaseResult<MeasurementPolicy> {
+    if rules.is_empty() {
+        return Err(DatabaseError::InvalidArgument(
+            "a measurement policy needs at least one rule".to_string(),
+        ));
+    }
+    for rule in rules {
+        rule.validate()
+            .map_err(|e| DatabaseError::InvalidArgument(e.to_string()))?;
+    }
+    let policy = insert_measurement_policy(txn, name, profile_id, rules).await?;
+    reevaluate_pending_reports(txn).await?;
+    Ok(policy)
+}
+
+/// delete_for_id deletes a policy. Machines that were already
+/// approved by the policy stay approved until they send a new
+/// measurement report.
+pub async fn delete_for_id(
+    txn: &mut PgConnection,
+    policy_id: MeasurementPolicyId,
+) -> DatabaseResult<MeasurementPolicy> {
+    delete_policy_for_id(txn, policy_id)
+        .await?
+        .ok_or(DatabaseError::NotFoundError {
+            kind: "MeasurementPolicy",
+            id: policy_id.to_string(),
+        })
+}
+
+pub async fn get_all(txn: &mut PgConnection) -> DatabaseResult<Vec<MeasurementPolicy>> {
+    get_measurement_policies(txn).await
+}
+
+pub async fn get_all_for_profile_id(
+    txn: &mut PgConnection,
+    profile_id: MeasurementSystemProfileId,
+) -> DatabaseResult<Vec<MeasurementPolicy>> {
+    get_measurement_policies_for_profile_id(txn, profile_id).await
+}
+
+/// maybe_approve_report evaluates the policies that apply to the
+/// profile of a pending report against the report's event log. If at
+/// least one policy applies, the log replays to the quoted values, and
+/// every policy passes, the journal entry for the report moves to
+/// Measured. Returns whether the report was approved.
+pub async fn maybe_approve_report(
+    txn: &mut PgConnection,
+    report_id: MeasurementReportId,
+) -> DatabaseResult<bool> {
+    let Some(journal) = get_measurement_journal_record_by_report_id(txn, report_id).await? else {
+        return Ok(false);
+    };
+    let Some(profile_id) = journal.profile_id else {
+        return Ok(false);
+    };
+    if journal.state != MeasurementMachineState::PendingBundle {
+        return Ok(false);
+    }
+    let policies = get_measurement_policies_for_profile_id(txn, profile_id).await?;
+    if policies.is_empty() {
+        return Ok(false);
+    }
+    let Some(raw) = get_report_event_log(txn, report_id).await? else {
+        return Ok(false);
+    };
+    let log = match EventLog::parse(&raw) {
+        Ok(log) => log,
+        Err(e) => {
+            tracing::warn!(%report_id, error = %e, "Stored event log failed to parse");
+            return Ok(false);
+        }
+    };
+
+    let report = crate::measured_boot::report::from_id(txn, report_id).await?;
+    let analysis = match EventLogAnalysis::analyze(&log, &report.pcr_values(), None) {
+        Ok(analysis) => analysis.with_policies(&policies, profile_id, &log),
+        Err(e) => {
+            tracing::warn!(%report_id, error = %e, "Event log replay failed");
+            return Ok(false);
+        }
+    };
+
+    // The rules only say something about the quote if
+    // the log actually replays to the quoted values.
+    if !analysis.is_consistent() {
+        tracing::warn!(
+            machine_id = %journal.machine_id,
+            %report_id,
+            mismatched = ?analysis.mismatched_pcrs(),
+            "Not evaluating measurement policies, event log does not replay to the quoted PCR values"
+        );
+        return Ok(false);
+    }
+
+    for evaluation in analysis.policies.iter() {
+        for rule in evaluation.rules.iter().filter(|rule| !rule.passed) {
+            tracing::info!(
+                machine_id = %journal.machine_id,
+                %report_id,
+                policy = %evaluation.name,
+                rule = %rule.rule,
+                "Measurement policy rule failed: {}",
+                rule.detail
+            );
+        }
+    }
+    if !analysis.policies.iter().all(|evaluation| evaluation.passed) {
+        return Ok(false);
+    }
+
+    crate::measured_boot::journal::update_measurement_journal(
+        txn,
+        report_id,
+        Some(profile_id),
+        None,
+        MeasurementMachineState::Measured,
+    )
+    .await?;
+    tracing::info!(
+        machine_id = %journal.machine_id,
+        %report_id,
+        policies = ?analysis.policies.iter().map(|evaluation| &evaluation.name).collect::<Vec<_>>(),
+        "Machine measurements approved by measurement policies"
+    );
+    Ok(true)
+}
+
+/// reevaluate_pending_reports runs maybe_approve_report for the latest
+/// report of every machine that is pending a bundle, returning the
+/// number of machines that got approved.
+pub async fn reevaluate_pending_reports(txn: &mut PgConnection) -> DatabaseResult<usize> {
+    let pending = get_latest_measurement_journal_records_for_state(
+        txn,
+        MeasurementMachineState::PendingBundle,
+    )
+    .await?;
+    let mut approved = 0;
+    for journal in pending {
+        if maybe_approve_report(txn, journal.report_id).await? {
+            approved += 1;
+        }
+    }
+    Ok(approved)
+}
@@ -27,22 +27,27 @@ use carbide_uuid::measured_boot::{
     MeasurementBundleId, MeasurementReportId, MeasurementSystemProfileId, TrustedMachineId,
 };
 use measured_boot::bundle::MeasurementBundle;
+use measured_boot::event_log::EventLog;
 use measured_boot::journal::MeasurementJournal;
 use measured_boot::pcr::{PcrRegisterValue, PcrSet, parse_pcr_index_input};
 use measured_boot::records::{
     MeasurementApprovedType, MeasurementBundleState, MeasurementMachineState,
     MeasurementReportRecord, MeasurementReportValueRecord,
 };
+use measured_boot::replay::EventLogAnalysis;
 use measured_boot::report::MeasurementReport;
 use sqlx::{PgConnection, PgTransaction};
 
 use crate::db_read::DbReader;
 use crate::measured_boot::interface::common;
 use crate::measured_boot::interface::common::pcr_register_values_to_map;
+use crate::measured_boot::interface::journal::get_measurement_journal_record_by_report_id;
 use crate::measured_boot::interface::report::{
     delete_report_for_id, delete_report_values_for_id, get_measurement_report_record_by_id,
-    get_measurement_report_values_for_report_id, insert_measurement_report_record,
+    get_measurement_report_values_for_report_id, get_reference_report_event_log,
+    get_report_event_log, insert_measurement_report_record,
     insert_measurement_report_value_records, update_report_tstamp, update_report_values_tstamp,
+    upsert_report_event_log,
 };
 use crate::measured_boot::interface::site::{
     get_approval_for_machine_id, get_approval_for_profile_id,
@@ -66,6 +71,80 @@ pub async fn from_id(
     get_measurement_report_by_id(txn, report_id).await
 }
 
+/// from_id_with_event_log returns the report for `report_id` and, if the
+/// machine sent a binary event log with the quote, replays it against the
+/// report values and diffs it against a reference log. The reference is
+/// `reference_report_id` when given, otherwise the latest measured report
+/// for the same machine. The measurement policies that apply to the
+/// report's profile are evaluated against the log as well.
+pub async fn from_id_with_event_log(
+    txn: &mut PgConnection,
+    report_id: MeasurementReportId,
+    reference_report_id: Option<MeasurementReportId>,
+) -> DatabaseResult<MeasurementReport> {
+    let mut report = get_measurement_report_by_id(txn, report_id).await?;
+    let Some(raw) = get_report_event_log(txn, report_id).await? else {
+        return Ok(report);
+    };
+    let log = match EventLog::parse(&raw) {
+        Ok(log) => log,
+        Err(e) => {
+            tracing::warn!(%report_id, error = %e, "Stored event log failed to parse");
+            return Ok(report);
+        }
+    };
+
+    let reference = match reference_report_id {
+        Some(reference_report_id) => get_report_event_log(txn, reference_report_id)
+            .await?
+            .map(|raw| (reference_report_id, raw)),
+        None => get_reference_report_event_log(txn, report.machine_id, report_id).await?,
+    };
+    let reference = reference.and_then(|(reference_report_id, raw)| {
+        EventLog::parse(&raw)
+            .inspect_err(|e| {
+                tracing::warn!(%reference_report_id, error = %e, "Reference event log failed to parse")
+            })
+            .ok()
+            .map(|log| (reference_report_id, log))
+    });
+
+    match EventLogAnalysis::analyze(
+        &log,
+        &report.pcr_values(),
+        reference.as_ref().map(|(id, log)| (*id, log)),
+    ) {
+        Ok(analysis) => {
+            // Evaluate the policies for the profile the report was
+            // matched against, so the report explains each rule.
+            let profile_id = get_measurement_journal_record_by_report_id(txn, report_id)
+                .await?
+                .and_then(|journal| journal.profile_id);
+            report.event_log = Some(match profile_id {
+                Some(profile_id) => {
+                    let policies =
+                        crate::measured_boot::policy::get_all_for_profile_id(txn, profile_id)
+                            .await?;
+                    analysis.with_policies(&policies, profile_id, &log)
+                }
+                None => analysis,
+            });
+        }
+        Err(e) => tracing::warn!(%report_id, error = %e, "Event log replay failed"),
+    }
+    Ok(report)
+}
+
+/// set_event_log stores the binary event log that came with
+/// the quote for a report.
+pub async fn set_event_log(
+    txn: &mut PgConnection,
+    report_id: MeasurementReportId,
+    event_log: &[u8],
+) -> DatabaseResult<()> {
+    upsert_report_event_log(txn, report_id, event_log).await
+}
+
 /// delete_for_id deletes a MeasurementReport and associated
 /// MeasurementReportValues, returning a fully populated instance of
 /// MeasurementReport of the data that was deleted for `report_id`.
@@ -80,6 +159,7 @@ pub async fn delete_for_id(
             machine_id: info.machine_id,
             ts: info.ts,
             values,
+            event_log: None,
         }),
         None => Err(DatabaseError::NotFoundError {
             kind: "MeasurementReport",
@@ -151,6 +231,7 @@ pub async fn create_measurement_report(
         machine_id: info.machine_id,
         ts: info.ts,
         values,
+        event_log: None,
     };
 
     let journal_data =
@@ -231,6 +312,7 @@ where
             machine_id: report_record.machine_id,
             ts: report_record.ts,
             values: values.to_vec(),
+            event_log: None,
         });
     }
     Ok(res)
@@ -250,6 +332,7 @@ pub async fn get_measurement_report_by_id(
                 machine_id: info.machine_id,
                 ts: info.ts,
                 values,
+                event_log: None,
             })
         }
         None => Err(DatabaseError::NotFoundError {
@@ -277,6 +360,7 @@ pub async fn get_measurement_reports_for_machine_id(
             machine_id: report_record.machine_id,
             ts: report_record.ts,
             values,
+            event_log: None,
         });
     }
     Ok(res)
@@ -0,0 +1,144 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Downsampled history of sensor readings of machines
+
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
+use model::sensor_history::{
+    RAW_RETENTION, ROLLUP_INTERVAL, ROLLUP_RETENTION, SensorHistoryResolution, SensorSample,
+    SensorSeries, SensorSeriesRow, group_into_series,
+};
+use sqlx::PgConnection;
+
+use crate::{DatabaseError, DatabaseResult};
+
+/// Stores raw sensor readings and merges them into the rollups of their buckets
+pub async fn record(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+    samples: &[SensorSample],
+) -> DatabaseResult<()> {
+    if samples.is_empty() {
+        return Ok(());
+    }
+
+    let sensors: Vec<&str> = samples.iter().map(|s| s.sensor.as_str()).collect();
+    let names: Vec<&str> = samples.iter().map(|s| s.name.as_str()).collect();
+    let units: Vec<&str> = samples.iter().map(|s| s.unit.as_str()).collect();
+    let values: Vec<f64> = samples.iter().map(|s| s.value).collect();
+    let times: Vec<DateTime<Utc>> = samples.iter().map(|s| s.time).collect();
+
+    let query = "INSERT INTO machine_sensor_samples (machine_id, sensor, name, unit, value, time)
+        SELECT $1, sensor, name, unit, value, time
+        FROM UNNEST($2::TEXT[], $3::TEXT[], $4::TEXT[], $5::FLOAT8[], $6::TIMESTAMPTZ[])
+            AS t(sensor, name, unit, value, time)";
+    sqlx::query(query)
+        .bind(machine_id)
+        .bind(&sensors)
+        .bind(&names)
+        .bind(&units)
+        .bind(&values)
+        .bind(&times)
+        .execute(&mut *txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    let query = "INSERT INTO machine_sensor_rollups
+            (machine_id, sensor, bucket, name, unit, min, max, sum, count)
+        SELECT $1, sensor, date_bin($7, time, TIMESTAMPTZ '2000-01-01') AS bucket,
+            MAX(name), MAX(unit), MIN(value), MAX(value), SUM(value), COUNT(*)
+        FROM UNNEST($2::TEXT[], $3::TEXT[], $4::TEXT[], $5::FLOAT8[], $6::TIMESTAMPTZ[])
+            AS t(sensor, name, unit, value, time)
+        GROUP BY sensor, bucket
+        ON CONFLICT (machine_id, sensor, bucket) DO UPDATE SET
+            name = EXCLUDED.name,
+            unit = EXCLUDED.unit,
+            min = LEAST(machine_sensor_rollups.min, EXCLUDED.min),
+            max = GREATEST(machine_sensor_rollups.max, EXCLUDED.max),
+            sum = machine_sensor_rollups.sum + EXCLUDED.sum,
+            count = machine_sensor_rollups.count + EXCLUDED.count";
+    sqlx::query(query)
+        .bind(machine_id)
+        .bind(&sensors)
+        .bind(&names)
+        .bind(&units)
+        .bind(&values)
+        .bind(&times)
+        .bind(ROLLUP_INTERVAL)
+        .execute(&mut *txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    // Readings and rollups which are out of retention are removed together
+    // with new readings of the same machine
+    let query = "DELETE FROM machine_sensor_samples WHERE machine_id = $1 AND time < $2";
+    sqlx::query(query)
+        .bind(machine_id)
+        .bind(Utc::now() - RAW_RETENTION)
+        .execute(&mut *txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    let query = "DELETE FROM machine_sensor_rollups WHERE machine_id = $1 AND bucket < $2";
+    sqlx::query(query)
+        .bind(machine_id)
+        .bind(Utc::now() - ROLLUP_RETENTION)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(())
+}
+
+/// Returns the series of the given sensors of a machine, or of all its sensors if
+/// `sensors` is empty, between `start_time` and `end_time`
+pub async fn find(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+    sensors: &[String],
+    start_time: DateTime<Utc>,
+    end_time: DateTime<Utc>,
+    resolution: SensorHistoryResolution,
+) -> DatabaseResult<Vec<SensorSeries>> {
+    let query = match resolution {
+        SensorHistoryResolution::Raw => {
+            "SELECT sensor, name, unit, time, value AS min, value AS max, value AS avg
+            FROM machine_sensor_samples
+            WHERE machine_id = $1 AND (cardinality($2::TEXT[]) = 0 OR sensor = ANY($2))
+                AND time >= $3 AND time <= $4
+            ORDER BY sensor, time"
+        }
+        SensorHistoryResolution::Rollup5m => {
+            "SELECT sensor, name, unit, bucket AS time, min, max, sum / count AS avg
+            FROM machine_sensor_rollups
+            WHERE machine_id = $1 AND (cardinality($2::TEXT[]) = 0 OR sensor = ANY($2))
+                AND bucket >= $3 AND bucket <= $4
+            ORDER BY sensor, bucket"
+        }
+    };
+    let rows: Vec<SensorSeriesRow> = sqlx::query_as(query)
+        .bind(machine_id)
+        .bind(sensors)
+        .bind(start_time)
+        .bind(end_time)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(group_into_series(rows))
+}
@@ -0,0 +1,211 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Transactional outbox for state changes.
+//!
+//! Entries are written in the same transaction that persists a state transition,
+//! and are deleted once they have been delivered. Entries which can not be
+//! delivered get moved into the dead-letter table.
+
+use chrono::{DateTime, Utc};
+use config_version::ConfigVersion;
+use sqlx::{FromRow, PgConnection};
+
+use crate::{DatabaseError, DatabaseResult};
+
+/// A state change that is waiting to be delivered to a hook
+#[derive(Debug, Clone, FromRow)]
+pub struct StateChangeOutboxEntry {
+    pub id: i64,
+    /// The name of the state controller which emitted the state change
+    pub controller: String,
+    /// The name of the hook that the state change needs to be delivered to
+    pub hook: String,
+    pub object_id: String,
+    /// The new state of the object, serialized as JSON
+    pub state: String,
+    pub state_version: ConfigVersion,
+    pub created: DateTime<Utc>,
+    /// How often delivery has already been attempted
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+}
+
+/// A state change that could not be delivered
+#[derive(Debug, Clone, FromRow)]
+pub struct StateChangeDeadLetter {
+    pub id: i64,
+    pub controller: String,
+    pub hook: String,
+    pub object_id: String,
+    pub state: String,
+    pub state_version: ConfigVersion,
+    pub created: DateTime<Utc>,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub failed_at: DateTime<Utc>,
+}
+
+/// Stores one outbox entry for each hook in `hooks`
+pub async fn enqueue(
+    txn: &mut PgConnection,
+    controller: &str,
+    hooks: &[&str],
+    object_id: &impl std::fmt::Display,
+    state: &serde_json::Value,
+    state_version: ConfigVersion,
+) -> DatabaseResult<()> {
+    if hooks.is_empty() {
+        return Ok(());
+    }
+
+    let query =
+        "INSERT INTO state_change_outbox (controller, hook, object_id, state, state_version)
+        SELECT $1, hook, $3, $4, $5 FROM UNNEST($2::VARCHAR[]) AS hook";
+    sqlx::query(query)
+        .bind(controller)
+        .bind(hooks)
+        .bind(object_id.to_string())
+        .bind(sqlx::types::Json(state))
+        .bind(state_version)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+/// Returns the entries for the given hooks which are due for delivery, starting
+/// with the oldest.
+///
+/// Only the oldest pending entry for each object and hook is returned. Newer
+/// entries for the same object are held back until it is delivered or dead-lettered,
+/// which guarantees that each hook observes the transitions of an object in order.
+///
+/// Entries are read in the order of their ID until `limit` deliverable entries are
+/// found. Whether an entry is the oldest one of its object is looked up using
+/// `idx_state_change_outbox_head`, so the outbox is not grouped as a whole.
+pub async fn find_deliverable(
+    txn: &mut PgConnection,
+    controller: &str,
+    hooks: &[&str],
+    limit: u32,
+) -> DatabaseResult<Vec<StateChangeOutboxEntry>> {
+    let query = "SELECT id, controller, hook, object_id, state::TEXT, state_version, created,
+            attempts, next_attempt_at, last_error
+        FROM state_change_outbox entry
+        WHERE controller = $1 AND hook = ANY($2) AND next_attempt_at <= NOW()
+            AND NOT EXISTS (
+                SELECT 1 FROM state_change_outbox older
+                WHERE older.controller = entry.controller
+                    AND older.hook = entry.hook
+                    AND older.object_id = entry.object_id
+                    AND older.id < entry.id
+            )
+        ORDER BY id
+        LIMIT $3";
+    sqlx::query_as(query)
+        .bind(controller)
+        .bind(hooks)
+        .bind(limit as i64)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Removes an entry which has been delivered
+pub async fn delete(txn: &mut PgConnection, id: i64) -> DatabaseResult<()> {
+    let query = "DELETE FROM state_change_outbox WHERE id = $1";
+    sqlx::query(query)
+        .bind(id)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+/// Records a failed delivery attempt, and schedules the next attempt
+pub async fn record_failure(
+    txn: &mut PgConnection,
+    id: i64,
+    error: &str,
+    next_attempt_at: DateTime<Utc>,
+) -> DatabaseResult<()> {
+    let query = "UPDATE state_change_outbox
+        SET attempts = attempts + 1, last_error = $2, next_attempt_at = $3
+        WHERE id = $1";
+    sqlx::query(query)
+        .bind(id)
+        .bind(error)
+        .bind(next_attempt_at)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+/// Moves an entry which can not be delivered into the dead-letter table
+pub async fn move_to_dead_letters(
+    txn: &mut PgConnection,
+    id: i64,
+    error: &str,
+) -> DatabaseResult<()> {
+    let query = "WITH failed AS (
+            DELETE FROM state_change_outbox WHERE id = $1 RETURNING *
+        )
+        INSERT INTO state_change_outbox_dead_letters
+            (id, controller, hook, object_id, state, state_version, created, attempts, last_error)
+        SELECT id, controller, hook, object_id, state, state_version, created, attempts + 1, $2
+        FROM failed";
+    sqlx::query(query)
+        .bind(id)
+        .bind(error)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+/// Returns the number of entries which are waiting for delivery
+pub async fn count_pending(txn: &mut PgConnection, controller: &str) -> DatabaseResult<i64> {
+    let query = "SELECT COUNT(*) FROM state_change_outbox WHERE controller = $1";
+    sqlx::query_scalar(query)
+        .bind(controller)
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Returns the dead-lettered entries of a controller, starting with the oldest
+pub async fn find_dead_letters(
+    txn: &mut PgConnection,
+    controller: &str,
+    limit: u32,
+) -> DatabaseResult<Vec<StateChangeDeadLetter>> {
+    let query = "SELECT id, controller, hook, object_id, state::TEXT, state_version, created,
+            attempts, last_error, failed_at
+        FROM state_change_outbox_dead_letters
+        WHERE controller = $1
+        ORDER BY id
+        LIMIT $2";
+    sqlx::query_as(query)
+        .bind(controller)
+        .bind(limit as i64)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
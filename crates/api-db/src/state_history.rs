@@ -17,7 +17,7 @@
 
 use chrono::{DateTime, Utc};
 use config_version::ConfigVersion;
-use model::state_history::StateHistoryRecord;
+use model::state_history::{StateHistoryCursor, StateHistoryRecord};
 use serde::Serialize;
 use sqlx::postgres::PgRow;
 use sqlx::{Encode, FromRow, PgConnection, Postgres, Row, Type};
@@ -43,6 +43,41 @@ impl<'r> FromRow<'r, PgRow> for DbStateHistoryRecord {
     }
 }
 
+/// A state history record together with the object it belongs to and its
+/// position in the table
+#[derive(Debug, Clone)]
+pub struct StateHistoryEntry {
+    pub cursor: StateHistoryCursor,
+    pub object_id: String,
+    pub record: StateHistoryRecord,
+}
+
+impl<'r> FromRow<'r, PgRow> for StateHistoryEntry {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        // xid8 has no sqlx mapping. It is read as text and is a plain u64.
+        let txid: String = row.try_get("txid")?;
+        let txid = txid.parse().map_err(|e| sqlx::Error::ColumnDecode {
+            index: "txid".to_string(),
+            source: Box::new(e),
+        })?;
+        let record = DbStateHistoryRecord::from_row(row)?;
+        Ok(Self {
+            cursor: StateHistoryCursor {
+                txid,
+                id: row.try_get("id")?,
+            },
+            object_id: record.object_id.clone(),
+            record: record.into(),
+        })
+    }
+}
+
+/// Only records of transactions which are older than every transaction that
+/// is still in progress are read by cursor. Records of younger transactions
+/// could still be followed by records with a smaller cursor which are not
+/// committed yet.
+const STABLE_TXID_CONDITION: &str = "txid < pg_snapshot_xmin(pg_current_snapshot())";
+
 impl From<DbStateHistoryRecord> for StateHistoryRecord {
     fn from(record: DbStateHistoryRecord) -> Self {
         StateHistoryRecord {
@@ -145,6 +180,83 @@ pub async fn find_by_object_ids(
     Ok(histories)
 }
 
+/// The channel on which a notification is published whenever records are
+/// inserted into a state history table. The payload is the name of the table.
+pub const STATE_HISTORY_WRITTEN_CHANNEL: &str = "state_history_written";
+
+/// Retrieve the state history entries which are positioned after `after`,
+/// ordered by their [`StateHistoryCursor`].
+///
+/// If `after` is `None`, reading starts at the oldest record.
+/// If `ids` is not empty, only records for the given objects are returned.
+/// At most `limit` records are returned. Callers which need more records
+/// continue reading after the cursor of the last returned record.
+///
+/// Records which are written by transactions that are still in progress, or
+/// that are younger than a transaction which is still in progress, are
+/// returned once all of these transactions have finished. Long-running
+/// transactions therefore delay, but never drop, records.
+pub async fn find_after(
+    txn: &mut PgConnection,
+    table_id: StateHistoryTableId,
+    ids: &[impl std::fmt::Display],
+    after: Option<&StateHistoryCursor>,
+    limit: u32,
+) -> DatabaseResult<Vec<StateHistoryEntry>> {
+    let object_id_column = table_id.object_id_column();
+    let mut qb = sqlx::QueryBuilder::new("SELECT txid::TEXT AS txid, id, ");
+    qb.push(object_id_column);
+    qb.push("::TEXT AS object_id, state::TEXT, state_version, timestamp FROM ");
+    qb.push(table_id.sql_table());
+    qb.push(" WHERE ");
+    qb.push(STABLE_TXID_CONDITION);
+
+    if let Some(after) = after {
+        qb.push(" AND (txid, id) > (");
+        qb.push_bind(after.txid.to_string());
+        qb.push("::xid8, ");
+        qb.push_bind(after.id);
+        qb.push(")");
+    }
+
+    if !ids.is_empty() {
+        qb.push(" AND ");
+        qb.push(object_id_column);
+        qb.push("::TEXT IN (");
+        let mut separated = qb.separated(", ");
+        for id in ids {
+            separated.push_bind(id.to_string());
+        }
+        qb.push(")");
+    }
+    qb.push(" ORDER BY txid, id LIMIT ");
+    qb.push_bind(limit as i64);
+
+    qb.build_query_as()
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query("find_state_history_after", e))
+}
+
+/// Returns the cursor of the newest record in a state history table which
+/// [`find_after`] would return, or `None` if there is no such record.
+pub async fn latest_cursor(
+    txn: &mut PgConnection,
+    table_id: StateHistoryTableId,
+) -> DatabaseResult<Option<StateHistoryCursor>> {
+    let query = format!(
+        "SELECT txid::TEXT AS txid, id, {}::TEXT AS object_id, state::TEXT, state_version, timestamp
+        FROM {} WHERE {STABLE_TXID_CONDITION} ORDER BY txid DESC, id DESC LIMIT 1",
+        table_id.object_id_column(),
+        table_id.sql_table()
+    );
+    let result: Option<StateHistoryEntry> = sqlx::query_as(&query)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))?;
+    Ok(result.map(|result| result.cursor))
+}
+
 /// Retrieve state history for a single object.
 pub async fn for_object(
     txn: &mut PgConnection,
@@ -890,6 +890,8 @@ where
                 dpus_in_nic_mode,
                 dpu_firmware_versions: None,
                 dpu_agent_version: None,
+                host_bmc_scenario: None,
+                dpu_bmc_scenario: None,
             }),
         )]),
         carbide_api_url: format!("https://{}:{}", api_addr.ip(), api_addr.port()),
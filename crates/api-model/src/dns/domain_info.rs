@@ -50,10 +50,11 @@ impl From<super::Domain> for DomainInfo {
         DomainInfo {
             id: domain.id,
             zone: domain.name + ".",
-            kind: "native".to_string(),
+            // Served as a primary so PowerDNS NOTIFYs secondaries on serial changes
+            kind: "master".to_string(),
             serial: soa.0.serial,
             last_check: None,
-            notified_serial: None,
+            notified_serial: domain.notified_serial,
             masters: vec![],
         }
     }
@@ -34,6 +34,18 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "SCREAMING-KEBAB-CASE")]
 pub struct DomainMetadata {
     pub allow_axfr_from: Vec<String>,
+    /// Names of the TSIG keys a secondary may sign AXFR requests with.
+    #[serde(default)]
+    pub tsig_allow_axfr: Vec<String>,
+    /// IP addresses or CIDR ranges allowed to send RFC 2136 dynamic updates.
+    #[serde(default)]
+    pub allow_dnsupdate_from: Vec<String>,
+    /// Names of the TSIG keys dynamic updates must be signed with.
+    #[serde(default)]
+    pub tsig_allow_dnsupdate: Vec<String>,
+    /// Additional secondaries to NOTIFY when the zone serial changes.
+    #[serde(default)]
+    pub also_notify: Vec<String>,
 }
 
 impl DomainMetadata {
@@ -44,12 +56,22 @@ impl DomainMetadata {
     pub fn allow_axfr_from(&self) -> &Vec<String> {
         &self.allow_axfr_from
     }
+
+    /// Whether dynamic updates are enabled for the domain. Updates are only
+    /// accepted when at least one TSIG key is allowed to sign them.
+    pub fn allows_dnsupdate(&self) -> bool {
+        !self.tsig_allow_dnsupdate.is_empty()
+    }
 }
 
 impl From<rpc::protos::dns::Metadata> for DomainMetadata {
     fn from(metadata: rpc::protos::dns::Metadata) -> Self {
         DomainMetadata {
             allow_axfr_from: metadata.allow_axfr_from,
+            tsig_allow_axfr: metadata.tsig_allow_axfr,
+            allow_dnsupdate_from: metadata.allow_dnsupdate_from,
+            tsig_allow_dnsupdate: metadata.tsig_allow_dnsupdate,
+            also_notify: metadata.also_notify,
         }
     }
 }
@@ -58,6 +80,10 @@ impl From<DomainMetadata> for rpc::protos::dns::Metadata {
     fn from(metadata: DomainMetadata) -> Self {
         rpc::protos::dns::Metadata {
             allow_axfr_from: vec![metadata.allow_axfr_from.join(",")],
+            tsig_allow_axfr: metadata.tsig_allow_axfr,
+            allow_dnsupdate_from: metadata.allow_dnsupdate_from,
+            tsig_allow_dnsupdate: metadata.tsig_allow_dnsupdate,
+            also_notify: metadata.also_notify,
         }
     }
 }
@@ -24,11 +24,13 @@ pub mod domain_info;
 pub mod metadata;
 pub mod resource_record;
 pub mod snapshot;
+pub mod tenant_record;
 
 pub use domain_info::DomainInfo;
 pub use metadata::DomainMetadata;
 pub use resource_record::ResourceRecord;
 pub use snapshot::SoaSnapshot;
+pub use tenant_record::{NewTenantDnsRecord, TenantDnsRecord, TenantDnsRecordSearchFilter};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Domain {
@@ -39,6 +41,8 @@ pub struct Domain {
     pub deleted: Option<DateTime<Utc>>,
     pub soa: Option<SoaSnapshot>,
     pub metadata: Option<DomainMetadata>,
+    /// The SOA serial that secondaries were last sent a NOTIFY for.
+    pub notified_serial: Option<u32>,
 }
 
 impl Domain {
@@ -166,6 +170,7 @@ impl TryFrom<rpc::protos::dns::Domain> for Domain {
             deleted,
             soa,
             metadata,
+            notified_serial: None,
         })
     }
 }
@@ -0,0 +1,345 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! DNS records tenants manage inside the domains of their VPCs.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use ::rpc::errors::RpcDataConversionError;
+use carbide_uuid::domain::{DomainId, TenantDnsRecordId};
+use carbide_uuid::vpc::VpcId;
+use chrono::{DateTime, Utc};
+use dns_record::DnsResourceRecordType;
+use serde::{Deserialize, Serialize};
+
+/// The record types tenants are allowed to create.
+pub const TENANT_RECORD_TYPES: &[DnsResourceRecordType] = &[
+    DnsResourceRecordType::A,
+    DnsResourceRecordType::AAAA,
+    DnsResourceRecordType::CNAME,
+    DnsResourceRecordType::SRV,
+    DnsResourceRecordType::TXT,
+];
+
+pub const DEFAULT_TENANT_RECORD_TTL: u32 = 300;
+pub const MAX_TENANT_RECORD_TTL: u32 = 86400;
+/// The longest TXT value that fits in a single character-string.
+pub const MAX_TXT_LENGTH: usize = 255;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TenantDnsRecord {
+    pub id: TenantDnsRecordId,
+    pub vpc_id: VpcId,
+    pub tenant_organization_id: String,
+    pub domain_id: DomainId,
+    pub q_name: String,
+    pub q_type: String,
+    pub ttl: u32,
+    pub content: String,
+    pub created: DateTime<Utc>,
+}
+
+impl From<TenantDnsRecord> for rpc::protos::dns::TenantDnsRecord {
+    fn from(record: TenantDnsRecord) -> Self {
+        rpc::protos::dns::TenantDnsRecord {
+            id: Some(record.id),
+            vpc_id: Some(record.vpc_id),
+            tenant_organization_id: record.tenant_organization_id,
+            domain_id: Some(record.domain_id),
+            qname: record.q_name,
+            qtype: record.q_type,
+            ttl: record.ttl,
+            content: record.content,
+            created: Some(record.created.into()),
+        }
+    }
+}
+
+/// A validated request to create a [`TenantDnsRecord`].
+///
+/// `q_name` and any hostnames in `content` are lowercase FQDNs with a
+/// trailing dot, like the names in the `dns_records` view. Checks that need
+/// the database (the VPC's domains and prefixes, conflicts and quotas) are up
+/// to the caller.
+#[derive(Clone, Debug)]
+pub struct NewTenantDnsRecord {
+    pub vpc_id: VpcId,
+    pub q_name: String,
+    pub q_type: DnsResourceRecordType,
+    pub ttl: u32,
+    pub content: String,
+}
+
+impl NewTenantDnsRecord {
+    /// The address of an A or AAAA record.
+    pub fn address(&self) -> Option<IpAddr> {
+        match self.q_type {
+            DnsResourceRecordType::A | DnsResourceRecordType::AAAA => self.content.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Whether the record is strictly below `domain`. Records can't be
+    /// created at the apex, which holds the SOA.
+    pub fn is_below(&self, domain: &str) -> bool {
+        let domain = fqdn(domain);
+        self.q_name.len() > domain.len()
+            && self.q_name.ends_with(&domain)
+            && self.q_name[..self.q_name.len() - domain.len()].ends_with('.')
+    }
+}
+
+impl TryFrom<rpc::protos::dns::TenantDnsRecordCreationRequest> for NewTenantDnsRecord {
+    type Error = RpcDataConversionError;
+
+    fn try_from(
+        request: rpc::protos::dns::TenantDnsRecordCreationRequest,
+    ) -> Result<Self, Self::Error> {
+        let vpc_id = request
+            .vpc_id
+            .ok_or(RpcDataConversionError::MissingArgument("vpc_id"))?;
+
+        let q_type = DnsResourceRecordType::try_from(request.qtype.to_uppercase())
+            .map_err(RpcDataConversionError::InvalidDnsResourceRecordType)?;
+        if !TENANT_RECORD_TYPES.contains(&q_type) {
+            return Err(RpcDataConversionError::InvalidDnsResourceRecordType(
+                format!("{q_type} records can't be created by tenants"),
+            ));
+        }
+
+        let q_name = hostname(&request.qname)?;
+
+        let ttl = request.ttl.unwrap_or(DEFAULT_TENANT_RECORD_TTL);
+        if ttl == 0 || ttl > MAX_TENANT_RECORD_TTL {
+            return Err(RpcDataConversionError::InvalidArgument(format!(
+                "ttl must be between 1 and {MAX_TENANT_RECORD_TTL}"
+            )));
+        }
+
+        let content = request.content.trim();
+        let content = match q_type {
+            DnsResourceRecordType::A => content
+                .parse::<Ipv4Addr>()
+                .map_err(|_| RpcDataConversionError::InvalidIpAddress(content.to_string()))?
+                .to_string(),
+            DnsResourceRecordType::AAAA => content
+                .parse::<Ipv6Addr>()
+                .map_err(|_| RpcDataConversionError::InvalidIpAddress(content.to_string()))?
+                .to_string(),
+            DnsResourceRecordType::CNAME => {
+                let target = hostname(content)?;
+                if target == q_name {
+                    return Err(RpcDataConversionError::InvalidArgument(format!(
+                        "CNAME {q_name} points to itself"
+                    )));
+                }
+                target
+            }
+            DnsResourceRecordType::SRV => srv_content(content)?,
+            _ => txt_content(content)?,
+        };
+
+        Ok(NewTenantDnsRecord {
+            vpc_id,
+            q_name,
+            q_type,
+            ttl,
+            content,
+        })
+    }
+}
+
+/// Filter for listing [`TenantDnsRecord`]s. Unset fields match everything.
+#[derive(Clone, Debug, Default)]
+pub struct TenantDnsRecordSearchFilter {
+    pub vpc_id: Option<VpcId>,
+    pub tenant_organization_id: Option<String>,
+    pub q_name: Option<String>,
+}
+
+impl TryFrom<rpc::protos::dns::TenantDnsRecordSearchQuery> for TenantDnsRecordSearchFilter {
+    type Error = RpcDataConversionError;
+
+    fn try_from(query: rpc::protos::dns::TenantDnsRecordSearchQuery) -> Result<Self, Self::Error> {
+        Ok(TenantDnsRecordSearchFilter {
+            vpc_id: query.vpc_id,
+            tenant_organization_id: query.tenant_organization_id,
+            q_name: query.qname.as_deref().map(hostname).transpose()?,
+        })
+    }
+}
+
+fn fqdn(name: &str) -> String {
+    format!("{}.", name.trim_end_matches('.').to_lowercase())
+}
+
+/// Validates `name` as a hostname and returns it as a lowercase FQDN.
+/// Underscores are allowed so SRV owner names like `_ldap._tcp` work.
+fn hostname(name: &str) -> Result<String, RpcDataConversionError> {
+    let invalid = |reason: &str| {
+        RpcDataConversionError::InvalidArgument(format!("Invalid DNS name '{name}': {reason}"))
+    };
+
+    let name = fqdn(name.trim());
+    if name == "." {
+        return Err(invalid("name is empty"));
+    }
+    if name.len() > 254 {
+        return Err(invalid("name is longer than 253 characters"));
+    }
+    for label in name.trim_end_matches('.').split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(invalid("labels must be between 1 and 63 characters"));
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            return Err(invalid("labels can't start or end with '-'"));
+        }
+        if !label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(invalid(
+                "labels may only contain letters, digits, '-' and '_'",
+            ));
+        }
+    }
+    Ok(name)
+}
+
+/// Validates `<priority> <weight> <port> <target>` and normalizes the target.
+fn srv_content(content: &str) -> Result<String, RpcDataConversionError> {
+    let invalid = || {
+        RpcDataConversionError::InvalidArgument(format!(
+            "Invalid SRV record '{content}': expected '<priority> <weight> <port> <target>'"
+        ))
+    };
+
+    let fields: Vec<&str> = content.split_whitespace().collect();
+    let [priority, weight, port, target] = fields[..] else {
+        return Err(invalid());
+    };
+    let priority: u16 = priority.parse().map_err(|_| invalid())?;
+    let weight: u16 = weight.parse().map_err(|_| invalid())?;
+    let port: u16 = port.parse().map_err(|_| invalid())?;
+    // A target of "." means the service is decidedly not available
+    let target = if target == "." {
+        target.to_string()
+    } else {
+        hostname(target)?
+    };
+    Ok(format!("{priority} {weight} {port} {target}"))
+}
+
+/// Quotes a TXT value unless it already is quoted, as PowerDNS expects TXT
+/// content in zone file format.
+fn txt_content(content: &str) -> Result<String, RpcDataConversionError> {
+    let value = match content.strip_prefix('"').and_then(|c| c.strip_suffix('"')) {
+        Some(quoted) => quoted.to_string(),
+        None => content.replace('\\', "\\\\").replace('"', "\\\""),
+    };
+    if value.is_empty() || value.len() > MAX_TXT_LENGTH {
+        return Err(RpcDataConversionError::InvalidArgument(format!(
+            "TXT records must be between 1 and {MAX_TXT_LENGTH} characters"
+        )));
+    }
+    if value.chars().any(|c| c.is_control()) {
+        return Err(RpcDataConversionError::InvalidArgument(
+            "TXT records can't contain control characters".to_string(),
+        ));
+    }
+    Ok(format!("\"{value}\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(
+        qname: &str,
+        qtype: &str,
+        content: &str,
+    ) -> rpc::protos::dns::TenantDnsRecordCreationRequest {
+        rpc::protos::dns::TenantDnsRecordCreationRequest {
+            vpc_id: Some(VpcId::new()),
+            qname: qname.to_string(),
+            qtype: qtype.to_string(),
+            ttl: None,
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_normalizes_names() {
+        let record = NewTenantDnsRecord::try_from(request(
+            "DB.Example.com",
+            "cname",
+            "Primary.example.com.",
+        ))
+        .unwrap();
+        assert_eq!(record.q_name, "db.example.com.");
+        assert_eq!(record.q_type, DnsResourceRecordType::CNAME);
+        assert_eq!(record.content, "primary.example.com.");
+        assert_eq!(record.ttl, DEFAULT_TENANT_RECORD_TTL);
+        assert!(record.is_below("example.com"));
+        assert!(!record.is_below("b.example.com"));
+        assert!(!record.is_below("db.example.com"));
+    }
+
+    #[test]
+    fn test_rejects_invalid_records() {
+        for (qname, qtype, content) in [
+            ("a.example.com", "MX", "10 mail.example.com"),
+            ("a.example.com", "A", "2001:db8::1"),
+            ("a.example.com", "AAAA", "192.0.2.1"),
+            ("-a.example.com", "A", "192.0.2.1"),
+            ("a b.example.com", "A", "192.0.2.1"),
+            ("a.example.com", "CNAME", "a.example.com"),
+            ("_ldap._tcp.example.com", "SRV", "0 5 389"),
+            (
+                "_ldap._tcp.example.com",
+                "SRV",
+                "0 5 70000 ldap.example.com",
+            ),
+            ("a.example.com", "TXT", ""),
+        ] {
+            assert!(
+                NewTenantDnsRecord::try_from(request(qname, qtype, content)).is_err(),
+                "{qtype} {qname} {content} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_formats_srv_and_txt() {
+        let srv = NewTenantDnsRecord::try_from(request(
+            "_ldap._tcp.example.com",
+            "SRV",
+            "0  5 389 LDAP.example.com",
+        ))
+        .unwrap();
+        assert_eq!(srv.content, "0 5 389 ldap.example.com.");
+
+        let txt =
+            NewTenantDnsRecord::try_from(request("a.example.com", "TXT", "say \"hi\"")).unwrap();
+        assert_eq!(txt.content, r#""say \"hi\"""#);
+
+        let quoted =
+            NewTenantDnsRecord::try_from(request("a.example.com", "TXT", "\"v=spf1 -all\""))
+                .unwrap();
+        assert_eq!(quoted.content, "\"v=spf1 -all\"");
+    }
+}
@@ -67,6 +67,7 @@ pub mod machine_boot_override;
 pub mod machine_interface_address;
 pub mod machine_update_module;
 pub mod machine_validation;
+pub mod maintenance_window;
 pub mod metadata;
 pub mod network_devices;
 pub mod network_prefix;
@@ -86,6 +87,7 @@ pub mod rack_type;
 pub mod redfish;
 pub mod resource_pool;
 pub mod route_server;
+pub mod sensor_history;
 pub mod site_explorer;
 pub mod sku;
 pub mod state_history;
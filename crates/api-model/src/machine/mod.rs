@@ -79,6 +79,7 @@ pub mod machine_id;
 pub mod machine_search_config;
 pub mod network;
 pub mod nvlink;
+pub mod state_timeline;
 pub mod topology;
 pub mod upgrade_policy;
 
@@ -2675,6 +2676,162 @@ pub fn get_action_for_dpu_state(
     })
 }
 
+/// Returns the names of the state and substate of a `ManagedHostState`.
+///
+/// The names are used as attributes on state controller metrics, and as keys for
+/// configured state SLAs. The 2nd value is empty if the state has no substate.
+pub fn state_names(state: &ManagedHostState) -> (&'static str, &'static str) {
+    fn dpuinit_state_name(dpu_state: &DpuInitState) -> &'static str {
+        match dpu_state {
+            DpuInitState::InstallDpuOs { .. } => "installdpuos",
+            DpuInitState::Init => "init",
+            DpuInitState::WaitingForNetworkInstall => "waitingfornetworkinstall",
+            DpuInitState::WaitingForNetworkConfig => "waitingfornetworkconfig",
+            DpuInitState::WaitingForPlatformConfiguration => "waitingforplatformconfiguration",
+            DpuInitState::PollingBiosSetup => "pollingbiossetup",
+            DpuInitState::WaitingForPlatformPowercycle { .. } => "waitingforplatformpowercycle",
+            DpuInitState::DpfStates { .. } => "dpfstates",
+        }
+    }
+
+    fn machine_state_name(machine_state: &MachineState) -> &'static str {
+        match machine_state {
+            MachineState::Init => "init",
+            MachineState::WaitingForPlatformConfiguration { .. } => {
+                "waitingforplatformconfiguration"
+            }
+            MachineState::WaitingForBiosJob { .. } => "waitingforbiosjob",
+            MachineState::PollingBiosSetup => "pollingbiossetup",
+            MachineState::SetBootOrder { .. } => "setbootorder",
+            MachineState::UefiSetup { .. } => "uefisetup",
+            MachineState::WaitingForDiscovery => "waitingfordiscovery",
+            MachineState::Discovered { .. } => "discovered",
+            MachineState::WaitingForLockdown { .. } => "waitingforlockdown",
+            MachineState::EnableIpmiOverLan => "enableipmioverlan",
+            MachineState::Measuring { .. } => "machinestatemeasuring",
+        }
+    }
+
+    fn discovering_state_name(discovering_state: &DpuDiscoveringState) -> &'static str {
+        match discovering_state {
+            DpuDiscoveringState::Initializing => "dpuinitializing",
+            DpuDiscoveringState::Configuring => "dpuconfiguring",
+            DpuDiscoveringState::DisableSecureBoot { .. } => "disablesecureboot",
+            DpuDiscoveringState::EnableSecureBoot { .. } => "enablesecureboot",
+            DpuDiscoveringState::SetUefiHttpBoot => "setuefihttpboot",
+            DpuDiscoveringState::RebootAllDPUS => "rebootalldpus",
+            DpuDiscoveringState::EnableRshim => "enablershim",
+        }
+    }
+
+    fn instance_state_name(instance_state: &InstanceState) -> &'static str {
+        match instance_state {
+            InstanceState::Init => "init",
+            InstanceState::WaitingForNetworkSegmentToBeReady => "waitingfornetworksegmenttobeready",
+            InstanceState::WaitingForNetworkConfig => "waitingfornetworkconfig",
+            InstanceState::WaitingForStorageConfig => "waitingforstorageconfig",
+            InstanceState::WaitingForExtensionServicesConfig => "waitingforextensionservicesconfig",
+            InstanceState::WaitingForRebootToReady => "waitingforreboottoready",
+            InstanceState::Ready => "ready",
+            InstanceState::BootingWithDiscoveryImage { .. } => "bootingwithdiscoveryimage",
+            InstanceState::SwitchToAdminNetwork => "switchtoadminnetwork",
+            InstanceState::WaitingForNetworkReconfig => "waitingfornetworkreconfig",
+            InstanceState::DPUReprovision { .. } => "dpureprovisioning",
+            InstanceState::Failed { .. } => "failed",
+            InstanceState::HostReprovision { .. } => "hostreprovisioning",
+            InstanceState::NetworkConfigUpdate { .. } => "networkconfigupdate",
+            InstanceState::WaitingForDpusToUp => "waitingfordpustoup",
+            InstanceState::HostPlatformConfiguration { .. } => "hostplatformconfiguration",
+            InstanceState::DpaProvisioning => "dpaprovisioning",
+            InstanceState::WaitingForDpaToBeReady => "waitingfordpatobeready",
+        }
+    }
+
+    fn measuring_state_name(measuring_state: &MeasuringState) -> &'static str {
+        match measuring_state {
+            MeasuringState::WaitingForMeasurements => "waitingformeasurements",
+            MeasuringState::PendingBundle => "pendingbundle",
+        }
+    }
+
+    fn cleanup_state_name(cleanup_state: &CleanupState) -> &'static str {
+        match cleanup_state {
+            CleanupState::Init => "init",
+            CleanupState::SecureEraseBoss { .. } => "secureeraseboss",
+            CleanupState::HostCleanup { .. } => "hostcleanup",
+            CleanupState::CreateBossVolume { .. } => "createbossvolume",
+            CleanupState::DisableBIOSBMCLockdown => "disablebmclockdown",
+        }
+    }
+
+    fn machine_validation_state_name(validation_state: &MachineValidatingState) -> &'static str {
+        match validation_state {
+            MachineValidatingState::MachineValidating { .. } => "machinevalidating",
+            MachineValidatingState::RebootHost { .. } => "reboothost",
+        }
+    }
+    match state {
+        ManagedHostState::DpuDiscoveringState { dpu_states } => {
+            // Min state indicates the least processed DPU. The state machine is blocked
+            // becasue of this.
+            let dpu_state = dpu_states.states.values().min();
+            let Some(dpu_state) = dpu_state else {
+                return ("unknown", "dpu");
+            };
+            ("dpudiscovering", discovering_state_name(dpu_state))
+        }
+        ManagedHostState::DPUInit { dpu_states } => {
+            // Min state indicates the least processed DPU. The state machine is blocked
+            // becasue of this.
+            let dpu_state = dpu_states.states.values().min();
+            let Some(dpu_state) = dpu_state else {
+                return ("unknown", "dpu");
+            };
+            ("dpunotready", dpuinit_state_name(dpu_state))
+        }
+        ManagedHostState::HostInit { machine_state } => {
+            ("hostnotready", machine_state_name(machine_state))
+        }
+        ManagedHostState::Ready => ("ready", ""),
+        ManagedHostState::Assigned { instance_state } => {
+            ("assigned", instance_state_name(instance_state))
+        }
+        ManagedHostState::WaitingForCleanup { cleanup_state } => {
+            ("waitingforcleanup", cleanup_state_name(cleanup_state))
+        }
+        ManagedHostState::Created => ("created", ""),
+        ManagedHostState::ForceDeletion => ("forcedeletion", ""),
+        ManagedHostState::Failed { .. } => ("failed", ""),
+        ManagedHostState::DPUReprovision { .. } => ("reprovisioning", ""),
+        ManagedHostState::HostReprovision { .. } => ("hostreprovisioning", ""),
+        ManagedHostState::Measuring { measuring_state } => {
+            ("measuring", measuring_state_name(measuring_state))
+        }
+        ManagedHostState::PostAssignedMeasuring { measuring_state } => (
+            "postassignedmeasuring",
+            measuring_state_name(measuring_state),
+        ),
+        ManagedHostState::BomValidating {
+            bom_validating_state,
+        } => match bom_validating_state {
+            BomValidating::MatchingSku(_) => ("bomvalidating", "matchingsku"),
+            BomValidating::UpdatingInventory(_) => ("bomvalidating", "updatinginventory"),
+            BomValidating::VerifyingSku(_) => ("bomvalidating", "verifyingsku"),
+            BomValidating::SkuVerificationFailed(_) => ("bomvalidating", "skuverificationfailed"),
+            BomValidating::WaitingForSkuAssignment(_) => {
+                ("bomvalidating", "waitingforskuassignment")
+            }
+            BomValidating::SkuMissing(_) => ("bomvalidating", "skumissing"),
+        },
+        ManagedHostState::Validation { validation_state } => match validation_state {
+            ValidationState::MachineValidation { machine_validation } => (
+                "validation",
+                machine_validation_state_name(machine_validation),
+            ),
+        },
+    }
+}
+
 /// Returns the SLA for the current state.
 ///
 /// If any alert in `aggregate_health` carries the `ExcludeFromStateMachineSla` classification,
@@ -2705,13 +2862,29 @@ pub fn state_sla(
         .to_std()
         .unwrap_or(std::time::Duration::from_secs(60 * 60 * 24));
 
+    match state_sla_duration(state, sla_config) {
+        Some(sla) => StateSla::with_sla(sla, time_in_state),
+        None => StateSla::no_sla(),
+    }
+}
+
+/// Returns the maximum time that a host is expected to spend in `state`,
+/// or `None` if no SLA applies to the state
+pub fn state_sla_duration(
+    state: &ManagedHostState,
+    sla_config: &slas::MachineSlaConfig,
+) -> Option<std::time::Duration> {
+    if let Some(sla) = sla_config.state_override(state) {
+        return Some(sla);
+    }
+
     match state {
         ManagedHostState::DpuDiscoveringState { dpu_states } => {
             // Min state indicates the least processed DPU. The state machine is blocked
             // because of this.
             let dpu_state = dpu_states.states.values().min();
             let Some(dpu_state) = dpu_state else {
-                return StateSla::no_sla();
+                return None;
             };
 
             match dpu_state {
@@ -2721,9 +2894,7 @@ pub fn state_sla(
                 | DpuDiscoveringState::DisableSecureBoot { .. }
                 | DpuDiscoveringState::SetUefiHttpBoot
                 | DpuDiscoveringState::RebootAllDPUS
-                | DpuDiscoveringState::EnableRshim => {
-                    StateSla::with_sla(slas::DPUDISCOVERING, time_in_state)
-                }
+                | DpuDiscoveringState::EnableRshim => Some(slas::DPUDISCOVERING),
             }
         }
         ManagedHostState::DPUInit { dpu_states } => {
@@ -2731,96 +2902,79 @@ pub fn state_sla(
             // because of this.
             let dpu_state = dpu_states.states.values().min();
             let Some(dpu_state) = dpu_state else {
-                return StateSla::no_sla();
+                return None;
             };
 
             // Init has no SLA since starting discovery requires a manual action
             match dpu_state {
-                DpuInitState::Init => StateSla::no_sla(),
-                _ => StateSla::with_sla(slas::DPUINIT_NOTINIT, time_in_state),
+                DpuInitState::Init => None,
+                _ => Some(slas::DPUINIT_NOTINIT),
             }
         }
         ManagedHostState::HostInit { machine_state } => match machine_state {
-            MachineState::Init => StateSla::no_sla(),
-            _ => StateSla::with_sla(slas::HOST_INIT, time_in_state),
+            MachineState::Init => None,
+            _ => Some(slas::HOST_INIT),
         },
-        ManagedHostState::Ready => StateSla::no_sla(),
+        ManagedHostState::Ready => None,
         ManagedHostState::Assigned { instance_state } => match instance_state {
-            InstanceState::Ready => StateSla::no_sla(),
+            InstanceState::Ready => None,
             InstanceState::BootingWithDiscoveryImage { retry } => {
                 if retry.count > 1 {
-                    StateSla::with_sla(std::time::Duration::ZERO, time_in_state)
+                    Some(std::time::Duration::ZERO)
                 } else {
-                    StateSla::with_sla(
-                        sla_config.assigned_booting_with_discovery_image,
-                        time_in_state,
-                    )
+                    Some(sla_config.assigned_booting_with_discovery_image)
                 }
             }
             InstanceState::HostPlatformConfiguration { .. } => {
-                StateSla::with_sla(slas::ASSIGNED_HOST_PLATFORM_CONFIGURATION, time_in_state)
+                Some(slas::ASSIGNED_HOST_PLATFORM_CONFIGURATION)
             }
-            _ => StateSla::with_sla(slas::ASSIGNED, time_in_state),
+            _ => Some(slas::ASSIGNED),
         },
-        ManagedHostState::WaitingForCleanup { .. } => {
-            StateSla::with_sla(slas::WAITING_FOR_CLEANUP, time_in_state)
-        }
-        ManagedHostState::Created => StateSla::with_sla(slas::CREATED, time_in_state),
-        ManagedHostState::ForceDeletion => StateSla::with_sla(slas::FORCE_DELETION, time_in_state),
-        ManagedHostState::Failed { .. } => {
-            StateSla::with_sla(std::time::Duration::ZERO, time_in_state)
-        }
-        ManagedHostState::DPUReprovision { .. } => {
-            StateSla::with_sla(slas::DPU_REPROVISION, time_in_state)
-        }
+        ManagedHostState::WaitingForCleanup { .. } => Some(slas::WAITING_FOR_CLEANUP),
+        ManagedHostState::Created => Some(slas::CREATED),
+        ManagedHostState::ForceDeletion => Some(slas::FORCE_DELETION),
+        ManagedHostState::Failed { .. } => Some(std::time::Duration::ZERO),
+        ManagedHostState::DPUReprovision { .. } => Some(slas::DPU_REPROVISION),
         ManagedHostState::HostReprovision { .. } => {
             // Multiple types of firmware may need to be updated, and in some cases it can take a while.
             // This SHOULD be enough based on current observed behavior, but may need to be extended.
-            StateSla::with_sla(slas::HOST_REPROVISION, time_in_state)
+            Some(slas::HOST_REPROVISION)
         }
         ManagedHostState::Measuring { measuring_state } => match measuring_state {
             // The API shouldn't be waiting for measurements for long. As soon
             // as it transitions into this state, Scout should get an Action::Measure
             // action, and it should pretty quickly send measurements in (~seconds).
-            MeasuringState::WaitingForMeasurements => {
-                StateSla::with_sla(slas::MEASUREMENT_WAIT_FOR_MEASUREMENT, time_in_state)
-            }
+            MeasuringState::WaitingForMeasurements => Some(slas::MEASUREMENT_WAIT_FOR_MEASUREMENT),
             // If the machine is waiting for a matching bundle, this could
             // take a bit, since it means either auto-bundle generation OR
             // manual bundle generation needs to happen. In the case of new
             // turn ups, this could take hours or even days (e.g. if new gear
             // is sitting there).
-            MeasuringState::PendingBundle => StateSla::no_sla(),
+            MeasuringState::PendingBundle => None,
         },
         ManagedHostState::PostAssignedMeasuring { measuring_state } => match measuring_state {
             // The API shouldn't be waiting for measurements for long. As soon
             // as it transitions into this state, Scout should get an Action::Measure
             // action, and it should pretty quickly send measurements in (~seconds).
-            MeasuringState::WaitingForMeasurements => {
-                StateSla::with_sla(slas::MEASUREMENT_WAIT_FOR_MEASUREMENT, time_in_state)
-            }
+            MeasuringState::WaitingForMeasurements => Some(slas::MEASUREMENT_WAIT_FOR_MEASUREMENT),
             // If the machine is waiting for a matching bundle, this could
             // take a bit, since it means either auto-bundle generation OR
             // manual bundle generation needs to happen. In the case of new
             // turn ups, this could take hours or even days (e.g. if new gear
             // is sitting there).
-            MeasuringState::PendingBundle => StateSla::no_sla(),
+            MeasuringState::PendingBundle => None,
         },
         ManagedHostState::BomValidating {
             bom_validating_state,
         } => match bom_validating_state {
-            BomValidating::SkuVerificationFailed(_bom_validating_context) => StateSla::no_sla(),
-            BomValidating::WaitingForSkuAssignment(_bom_validating_context) => StateSla::no_sla(),
-            _ => StateSla::with_sla(slas::BOM_VALIDATION, time_in_state),
+            BomValidating::SkuVerificationFailed(_bom_validating_context) => None,
+            BomValidating::WaitingForSkuAssignment(_bom_validating_context) => None,
+            _ => Some(slas::BOM_VALIDATION),
         },
         ManagedHostState::Validation { validation_state } => match validation_state {
             ValidationState::MachineValidation { machine_validation } => match machine_validation {
-                MachineValidatingState::MachineValidating { .. } => {
-                    StateSla::with_sla(slas::VALIDATION, time_in_state)
-                }
-                MachineValidatingState::RebootHost { .. } => {
-                    StateSla::with_sla(slas::VALIDATION, time_in_state)
-                }
+                MachineValidatingState::MachineValidating { .. } => Some(slas::VALIDATION),
+                MachineValidatingState::RebootHost { .. } => Some(slas::VALIDATION),
             },
         },
     }
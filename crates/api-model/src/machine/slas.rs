@@ -17,8 +17,15 @@
 
 //! SLAs for Machine State Machine Controller
 
+use std::collections::HashMap;
 use std::time::Duration;
 
+use super::ManagedHostState;
+
+/// Source of the health report which is attached to hosts that exceed the SLA
+/// of their current state
+pub const HEALTH_REPORT_SOURCE: &str = "state-sla";
+
 pub const DPUDISCOVERING: Duration = Duration::from_secs(30 * 60);
 
 // DPUInit any substate other than INIT
@@ -56,6 +63,10 @@ pub const VALIDATION: Duration = Duration::from_secs(30 * 60);
 pub struct MachineSlaConfig {
     /// SLA for the Assigned/BootingWithDiscoveryImage state.
     pub assigned_booting_with_discovery_image: Duration,
+    /// SLAs which replace the built-in SLA for a state.
+    /// Keys are either `state` or `state/substate`, using the names
+    /// returned by [`super::state_names`].
+    pub state_overrides: HashMap<String, Duration>,
 }
 
 impl Default for MachineSlaConfig {
@@ -74,6 +85,33 @@ impl MachineSlaConfig {
             // Set to 1.1 * failure_retry_time so the SLA fires
             // shortly after the retry would have triggered.
             assigned_booting_with_discovery_image: failure_retry_time * 11 / 10,
+            state_overrides: HashMap::new(),
+        }
+    }
+
+    pub fn with_state_overrides(
+        mut self,
+        overrides: impl IntoIterator<Item = (String, Duration)>,
+    ) -> Self {
+        self.state_overrides.extend(overrides);
+        self
+    }
+
+    /// Returns the configured SLA for a state, if there is one.
+    /// An SLA for the substate takes precedence over an SLA for the whole state.
+    pub fn state_override(&self, state: &ManagedHostState) -> Option<Duration> {
+        if self.state_overrides.is_empty() {
+            return None;
+        }
+
+        let (state_name, substate_name) = super::state_names(state);
+        if !substate_name.is_empty()
+            && let Some(sla) = self
+                .state_overrides
+                .get(&format!("{state_name}/{substate_name}"))
+        {
+            return Some(*sla);
         }
+        self.state_overrides.get(state_name).copied()
     }
 }
@@ -0,0 +1,198 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Timeline of the states a host went through, with dwell times and state SLAs
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use config_version::ConfigVersion;
+
+use super::slas::MachineSlaConfig;
+use super::{ManagedHostState, state_names, state_sla_duration};
+use crate::state_history::StateHistoryRecord;
+
+/// A state that a host entered, and how long it stayed in the state
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateTimelineEntry {
+    /// The state that was entered, serialized as JSON
+    pub state: String,
+    pub state_name: &'static str,
+    pub substate_name: &'static str,
+    pub state_version: ConfigVersion,
+    pub entered_at: DateTime<Utc>,
+    /// When the host left the state. `None` for the current state.
+    pub left_at: Option<DateTime<Utc>>,
+    /// How long the host stayed in the state, or stayed in it so far
+    pub dwell_time: std::time::Duration,
+    pub sla: Option<std::time::Duration>,
+    pub sla_exceeded: bool,
+    /// When the state controller detected that the SLA was exceeded
+    pub breach_detected_at: Option<DateTime<Utc>>,
+}
+
+/// Builds the timeline of a host from its state history, starting by the oldest state.
+///
+/// `breaches` maps the version strings of states for which an SLA breach was recorded
+/// to the time the breach was detected. The dwell time of the last state is
+/// measured up to `now`.
+pub fn build_state_timeline(
+    records: Vec<StateHistoryRecord>,
+    breaches: &HashMap<String, DateTime<Utc>>,
+    sla_config: &MachineSlaConfig,
+    now: DateTime<Utc>,
+) -> Vec<StateTimelineEntry> {
+    let entered_at: Vec<DateTime<Utc>> = records
+        .iter()
+        .map(|record| record.state_version.timestamp())
+        .collect();
+
+    records
+        .into_iter()
+        .enumerate()
+        .map(|(idx, record)| {
+            let left_at = entered_at.get(idx + 1).copied();
+            let entered_at = entered_at[idx];
+            let dwell_time = (left_at.unwrap_or(now) - entered_at)
+                .to_std()
+                .unwrap_or_default();
+
+            // History records of states which no longer exist can not be parsed.
+            // They are still shown, but without names and SLA.
+            let state = serde_json::from_str::<ManagedHostState>(&record.state).ok();
+            let (state_name, substate_name) = state.as_ref().map(state_names).unwrap_or_default();
+            let sla = state
+                .as_ref()
+                .and_then(|state| state_sla_duration(state, sla_config));
+            let breach_detected_at = breaches
+                .get(&record.state_version.version_string())
+                .copied();
+
+            StateTimelineEntry {
+                state: record.state,
+                state_name,
+                substate_name,
+                state_version: record.state_version,
+                entered_at,
+                left_at,
+                dwell_time,
+                sla,
+                sla_exceeded: breach_detected_at.is_some()
+                    || sla.is_some_and(|sla| dwell_time > sla),
+                breach_detected_at,
+            }
+        })
+        .collect()
+}
+
+impl From<StateTimelineEntry> for rpc::forge::ManagedHostStateTimelineEntry {
+    fn from(value: StateTimelineEntry) -> Self {
+        rpc::forge::ManagedHostStateTimelineEntry {
+            state: value.state,
+            state_name: value.state_name.to_string(),
+            substate_name: value.substate_name.to_string(),
+            version: value.state_version.version_string(),
+            entered_at: Some(value.entered_at.into()),
+            left_at: value.left_at.map(Into::into),
+            dwell_time: Some(value.dwell_time.into()),
+            sla: value.sla.map(Into::into),
+            sla_exceeded: value.sla_exceeded,
+            breach_detected_at: value.breach_detected_at.map(Into::into),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn record(state: &str, state_version: ConfigVersion) -> StateHistoryRecord {
+        StateHistoryRecord {
+            state: state.to_string(),
+            state_version,
+            time: None,
+        }
+    }
+
+    #[test]
+    fn test_build_state_timeline() {
+        let created = serde_json::to_string(&ManagedHostState::Created).unwrap();
+        let ready = serde_json::to_string(&ManagedHostState::Ready).unwrap();
+        let v1 = ConfigVersion::initial();
+        let v2 = v1.increment();
+        let v3 = v2.increment();
+        let now = v3.timestamp() + chrono::Duration::minutes(10);
+        let breach_detected_at = v2.timestamp();
+
+        let sla_config = MachineSlaConfig::new(Duration::from_secs(1800))
+            .with_state_overrides([("ready".to_string(), Duration::from_secs(300))]);
+        let timeline = build_state_timeline(
+            vec![
+                record(&created, v1),
+                record(&ready, v2),
+                record(r#"{"state": "nolongerexists"}"#, v3),
+            ],
+            &HashMap::from([(v1.version_string(), breach_detected_at)]),
+            &sla_config,
+            now,
+        );
+
+        assert_eq!(timeline.len(), 3);
+        assert_eq!(timeline[0].state_name, "created");
+        assert_eq!(timeline[0].left_at, Some(v2.timestamp()));
+        assert!(timeline[0].sla_exceeded);
+        assert_eq!(timeline[0].breach_detected_at, Some(breach_detected_at));
+
+        assert_eq!(timeline[1].state_name, "ready");
+        assert_eq!(timeline[1].sla, Some(Duration::from_secs(300)));
+        assert_eq!(
+            timeline[1].dwell_time,
+            (v3.timestamp() - v2.timestamp()).to_std().unwrap()
+        );
+        assert!(!timeline[1].sla_exceeded);
+
+        assert_eq!(timeline[2].state_name, "");
+        assert_eq!(timeline[2].sla, None);
+        assert_eq!(timeline[2].left_at, None);
+        assert_eq!(timeline[2].dwell_time, Duration::from_secs(600));
+    }
+
+    #[test]
+    fn test_state_sla_override() {
+        let sla_config = MachineSlaConfig::new(Duration::from_secs(1800)).with_state_overrides([
+            ("ready".to_string(), Duration::from_secs(300)),
+            ("created".to_string(), Duration::from_secs(60)),
+        ]);
+        assert_eq!(
+            state_sla_duration(&ManagedHostState::Ready, &sla_config),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(
+            state_sla_duration(&ManagedHostState::Created, &sla_config),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            state_sla_duration(
+                &ManagedHostState::Created,
+                &MachineSlaConfig::new(Duration::from_secs(1800))
+            ),
+            Some(crate::machine::slas::CREATED)
+        );
+    }
+}
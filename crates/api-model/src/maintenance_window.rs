@@ -0,0 +1,526 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Maintenance windows restrict disruptive actions on hosts, racks and switches
+//! to a certain time range.
+
+use std::collections::HashMap;
+
+use carbide_uuid::machine::MachineId;
+use carbide_uuid::maintenance_window::MaintenanceWindowId;
+use carbide_uuid::rack::RackId;
+use carbide_uuid::switch::SwitchId;
+use chrono::{DateTime, Utc};
+use rpc::errors::RpcDataConversionError;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, Row};
+
+use crate::metadata::Metadata;
+
+/// An action which can be performed on objects during a maintenance window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MaintenanceAction {
+    FirmwareUpdate,
+    Reprovision,
+}
+
+impl std::fmt::Display for MaintenanceAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MaintenanceAction::FirmwareUpdate => write!(f, "firmware update"),
+            MaintenanceAction::Reprovision => write!(f, "reprovision"),
+        }
+    }
+}
+
+impl From<rpc::forge::MaintenanceWindowAction> for MaintenanceAction {
+    fn from(action: rpc::forge::MaintenanceWindowAction) -> Self {
+        match action {
+            rpc::forge::MaintenanceWindowAction::FirmwareUpdate => {
+                MaintenanceAction::FirmwareUpdate
+            }
+            rpc::forge::MaintenanceWindowAction::Reprovision => MaintenanceAction::Reprovision,
+        }
+    }
+}
+
+impl From<MaintenanceAction> for rpc::forge::MaintenanceWindowAction {
+    fn from(action: MaintenanceAction) -> Self {
+        match action {
+            MaintenanceAction::FirmwareUpdate => {
+                rpc::forge::MaintenanceWindowAction::FirmwareUpdate
+            }
+            MaintenanceAction::Reprovision => rpc::forge::MaintenanceWindowAction::Reprovision,
+        }
+    }
+}
+
+/// Selects the objects which are covered by a maintenance window.
+/// An object is covered if it matches any of the criteria.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceWindowScope {
+    #[serde(default)]
+    pub machine_ids: Vec<MachineId>,
+    /// Covers the racks themselves as well as all hosts and switches in the racks
+    #[serde(default)]
+    pub rack_ids: Vec<RackId>,
+    #[serde(default)]
+    pub switch_ids: Vec<SwitchId>,
+    /// Covers hosts with any of these SKUs
+    #[serde(default)]
+    pub skus: Vec<String>,
+    /// Covers objects which carry all of these labels.
+    /// Labels with an empty value match any value.
+    #[serde(default)]
+    pub label_selector: HashMap<String, String>,
+}
+
+/// Describes an object for which it should be checked whether it is covered
+/// by a maintenance window
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaintenanceTarget<'a> {
+    pub machine_id: Option<&'a MachineId>,
+    pub rack_id: Option<&'a RackId>,
+    pub switch_id: Option<&'a SwitchId>,
+    pub sku: Option<&'a str>,
+    pub labels: Option<&'a HashMap<String, String>>,
+}
+
+impl MaintenanceWindowScope {
+    pub fn is_empty(&self) -> bool {
+        self.machine_ids.is_empty()
+            && self.rack_ids.is_empty()
+            && self.switch_ids.is_empty()
+            && self.skus.is_empty()
+            && self.label_selector.is_empty()
+    }
+
+    /// Returns whether the object described by `target` is covered by the scope
+    pub fn covers(&self, target: &MaintenanceTarget<'_>) -> bool {
+        if target
+            .machine_id
+            .is_some_and(|id| self.machine_ids.contains(id))
+            || target.rack_id.is_some_and(|id| self.rack_ids.contains(id))
+            || target
+                .switch_id
+                .is_some_and(|id| self.switch_ids.contains(id))
+            || target
+                .sku
+                .is_some_and(|sku| self.skus.iter().any(|s| s == sku))
+        {
+            return true;
+        }
+
+        match target.labels {
+            Some(labels) if !self.label_selector.is_empty() => {
+                self.label_selector.iter().all(|(key, value)| {
+                    labels
+                        .get(key)
+                        .is_some_and(|v| value.is_empty() || v == value)
+                })
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<rpc::forge::MaintenanceWindowScope> for MaintenanceWindowScope {
+    fn from(scope: rpc::forge::MaintenanceWindowScope) -> Self {
+        MaintenanceWindowScope {
+            machine_ids: scope.machine_ids,
+            rack_ids: scope.rack_ids,
+            switch_ids: scope.switch_ids,
+            skus: scope.skus,
+            label_selector: scope
+                .label_selector
+                .into_iter()
+                .map(|label| (label.key, label.value.unwrap_or_default()))
+                .collect(),
+        }
+    }
+}
+
+impl From<MaintenanceWindowScope> for rpc::forge::MaintenanceWindowScope {
+    fn from(scope: MaintenanceWindowScope) -> Self {
+        let mut label_selector: Vec<rpc::forge::Label> = scope
+            .label_selector
+            .into_iter()
+            .map(|(key, value)| rpc::forge::Label {
+                key,
+                value: if value.is_empty() { None } else { Some(value) },
+            })
+            .collect();
+        label_selector.sort_by(|a, b| a.key.cmp(&b.key));
+
+        rpc::forge::MaintenanceWindowScope {
+            machine_ids: scope.machine_ids,
+            rack_ids: scope.rack_ids,
+            switch_ids: scope.switch_ids,
+            skus: scope.skus,
+            label_selector,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MaintenanceWindow {
+    pub id: MaintenanceWindowId,
+    pub metadata: Metadata,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub scope: MaintenanceWindowScope,
+    /// The actions which may be performed during the window.
+    /// An empty list allows all actions.
+    pub allowed_actions: Vec<MaintenanceAction>,
+    pub created_by: Option<String>,
+    pub created: DateTime<Utc>,
+    pub cancelled: Option<DateTime<Utc>>,
+}
+
+impl MaintenanceWindow {
+    /// Returns whether the window is in effect at time `now`
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.cancelled.is_none() && self.start_time <= now && now < self.end_time
+    }
+
+    /// Returns whether the window has neither ended nor been cancelled at time `now`
+    pub fn is_relevant_at(&self, now: DateTime<Utc>) -> bool {
+        self.cancelled.is_none() && now < self.end_time
+    }
+
+    pub fn allows(&self, action: MaintenanceAction) -> bool {
+        self.allowed_actions.is_empty() || self.allowed_actions.contains(&action)
+    }
+}
+
+/// Whether an action may be performed on an object, as determined by maintenance windows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaintenanceGate {
+    /// No maintenance window which allows the action covers the object.
+    /// Whether the action is performed is up to the regular policies.
+    Unrestricted,
+    /// A maintenance window which allows the action is currently active
+    Open(MaintenanceWindowId),
+    /// The object is only covered by maintenance windows which have not started yet.
+    /// The action should be deferred until the window starts.
+    Deferred { next_start: DateTime<Utc> },
+}
+
+/// Determines whether `action` may be performed on `target` at time `now`
+/// given a set of maintenance windows
+pub fn maintenance_gate(
+    windows: &[MaintenanceWindow],
+    action: MaintenanceAction,
+    target: &MaintenanceTarget<'_>,
+    now: DateTime<Utc>,
+) -> MaintenanceGate {
+    let mut next_start: Option<DateTime<Utc>> = None;
+    for window in windows
+        .iter()
+        .filter(|w| w.is_relevant_at(now) && w.allows(action) && w.scope.covers(target))
+    {
+        if window.is_active_at(now) {
+            return MaintenanceGate::Open(window.id);
+        }
+        next_start = Some(match next_start {
+            Some(start) => start.min(window.start_time),
+            None => window.start_time,
+        });
+    }
+
+    match next_start {
+        Some(next_start) => MaintenanceGate::Deferred { next_start },
+        None => MaintenanceGate::Unrestricted,
+    }
+}
+
+/// A maintenance window which should get created
+#[derive(Debug, Clone)]
+pub struct NewMaintenanceWindow {
+    pub metadata: Metadata,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub scope: MaintenanceWindowScope,
+    pub allowed_actions: Vec<MaintenanceAction>,
+    pub created_by: Option<String>,
+}
+
+impl TryFrom<(rpc::forge::CreateMaintenanceWindowRequest, Option<String>)>
+    for NewMaintenanceWindow
+{
+    type Error = RpcDataConversionError;
+
+    fn try_from(
+        value: (rpc::forge::CreateMaintenanceWindowRequest, Option<String>),
+    ) -> Result<Self, Self::Error> {
+        let (request, created_by) = value;
+
+        let metadata = Metadata::try_from(request.metadata.unwrap_or_default())?;
+        metadata
+            .validate(false)
+            .map_err(|e| RpcDataConversionError::InvalidArgument(e.to_string()))?;
+
+        let start_time = request
+            .start_time
+            .ok_or(RpcDataConversionError::MissingArgument("start_time"))?;
+        let start_time = DateTime::<Utc>::try_from(start_time)
+            .map_err(|_| RpcDataConversionError::InvalidTimestamp(start_time.to_string()))?;
+        let end_time = request
+            .end_time
+            .ok_or(RpcDataConversionError::MissingArgument("end_time"))?;
+        let end_time = DateTime::<Utc>::try_from(end_time)
+            .map_err(|_| RpcDataConversionError::InvalidTimestamp(end_time.to_string()))?;
+        if start_time >= end_time {
+            return Err(RpcDataConversionError::InvalidArgument(
+                "start_time must precede end_time".to_string(),
+            ));
+        }
+        if end_time <= Utc::now() {
+            return Err(RpcDataConversionError::InvalidArgument(
+                "end_time occurs in the past".to_string(),
+            ));
+        }
+
+        let scope = MaintenanceWindowScope::from(
+            request
+                .scope
+                .ok_or(RpcDataConversionError::MissingArgument("scope"))?,
+        );
+        if scope.is_empty() {
+            return Err(RpcDataConversionError::InvalidArgument(
+                "scope must select at least one object".to_string(),
+            ));
+        }
+
+        let mut allowed_actions = Vec::new();
+        for action in request.allowed_actions {
+            let action = rpc::forge::MaintenanceWindowAction::try_from(action).map_err(|_| {
+                RpcDataConversionError::InvalidValue(
+                    "MaintenanceWindowAction".to_string(),
+                    action.to_string(),
+                )
+            })?;
+            let action = MaintenanceAction::from(action);
+            if !allowed_actions.contains(&action) {
+                allowed_actions.push(action);
+            }
+        }
+
+        Ok(NewMaintenanceWindow {
+            metadata,
+            start_time,
+            end_time,
+            scope,
+            allowed_actions,
+            created_by,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for MaintenanceWindow {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let labels: sqlx::types::Json<HashMap<String, String>> = row.try_get("labels")?;
+        let scope: sqlx::types::Json<MaintenanceWindowScope> = row.try_get("scope")?;
+        let allowed_actions: sqlx::types::Json<Vec<MaintenanceAction>> =
+            row.try_get("allowed_actions")?;
+
+        Ok(MaintenanceWindow {
+            id: row.try_get("id")?,
+            metadata: Metadata {
+                name: row.try_get("name")?,
+                description: row.try_get("description")?,
+                labels: labels.0,
+            },
+            start_time: row.try_get("start_time")?,
+            end_time: row.try_get("end_time")?,
+            scope: scope.0,
+            allowed_actions: allowed_actions.0,
+            created_by: row.try_get("created_by")?,
+            created: row.try_get("created")?,
+            cancelled: row.try_get("cancelled")?,
+        })
+    }
+}
+
+impl From<MaintenanceWindow> for rpc::forge::MaintenanceWindow {
+    fn from(window: MaintenanceWindow) -> Self {
+        rpc::forge::MaintenanceWindow {
+            id: Some(window.id),
+            metadata: Some(window.metadata.into()),
+            start_time: Some(window.start_time.into()),
+            end_time: Some(window.end_time.into()),
+            scope: Some(window.scope.into()),
+            allowed_actions: window
+                .allowed_actions
+                .into_iter()
+                .map(|action| rpc::forge::MaintenanceWindowAction::from(action) as i32)
+                .collect(),
+            created_by: window.created_by,
+            created: Some(window.created.into()),
+            cancelled: window.cancelled.map(Into::into),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_uuid::switch::{SwitchIdSource, SwitchType};
+    use chrono::Duration;
+
+    use super::*;
+
+    fn window(
+        start_offset: Duration,
+        end_offset: Duration,
+        scope: MaintenanceWindowScope,
+        allowed_actions: Vec<MaintenanceAction>,
+        now: DateTime<Utc>,
+    ) -> MaintenanceWindow {
+        MaintenanceWindow {
+            id: MaintenanceWindowId::new(),
+            metadata: Metadata::default(),
+            start_time: now + start_offset,
+            end_time: now + end_offset,
+            scope,
+            allowed_actions,
+            created_by: None,
+            created: now,
+            cancelled: None,
+        }
+    }
+
+    #[test]
+    fn test_scope_covers() {
+        let rack_id = RackId::new("rack-1");
+        let switch_id = SwitchId::new(SwitchIdSource::Tpm, [0; 32], SwitchType::NvLink);
+        let scope = MaintenanceWindowScope {
+            rack_ids: vec![rack_id.clone()],
+            skus: vec!["sku1".to_string()],
+            label_selector: HashMap::from([
+                ("pool".to_string(), "a".to_string()),
+                ("canary".to_string(), "".to_string()),
+            ]),
+            ..Default::default()
+        };
+
+        // Switches in a covered rack are covered
+        assert!(scope.covers(&MaintenanceTarget {
+            switch_id: Some(&switch_id),
+            rack_id: Some(&rack_id),
+            ..Default::default()
+        }));
+        assert!(!scope.covers(&MaintenanceTarget {
+            switch_id: Some(&switch_id),
+            ..Default::default()
+        }));
+        assert!(scope.covers(&MaintenanceTarget {
+            sku: Some("sku1"),
+            ..Default::default()
+        }));
+
+        // All labels of the selector need to match
+        let labels = HashMap::from([
+            ("pool".to_string(), "a".to_string()),
+            ("canary".to_string(), "yes".to_string()),
+        ]);
+        assert!(scope.covers(&MaintenanceTarget {
+            labels: Some(&labels),
+            ..Default::default()
+        }));
+        let labels = HashMap::from([("pool".to_string(), "a".to_string())]);
+        assert!(!scope.covers(&MaintenanceTarget {
+            labels: Some(&labels),
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn test_maintenance_gate() {
+        let now = Utc::now();
+        let sku_scope = MaintenanceWindowScope {
+            skus: vec!["sku1".to_string()],
+            ..Default::default()
+        };
+        let target = MaintenanceTarget {
+            sku: Some("sku1"),
+            ..Default::default()
+        };
+
+        let future = window(
+            Duration::hours(1),
+            Duration::hours(2),
+            sku_scope.clone(),
+            vec![MaintenanceAction::FirmwareUpdate],
+            now,
+        );
+        let later = window(
+            Duration::hours(3),
+            Duration::hours(4),
+            sku_scope.clone(),
+            vec![],
+            now,
+        );
+        let active = window(
+            Duration::hours(-1),
+            Duration::hours(1),
+            sku_scope.clone(),
+            vec![MaintenanceAction::Reprovision],
+            now,
+        );
+        let expired = window(
+            Duration::hours(-2),
+            Duration::hours(-1),
+            sku_scope,
+            vec![],
+            now,
+        );
+        let windows = vec![future.clone(), later, active.clone(), expired];
+
+        assert_eq!(
+            maintenance_gate(&windows, MaintenanceAction::FirmwareUpdate, &target, now),
+            MaintenanceGate::Deferred {
+                next_start: future.start_time
+            }
+        );
+        assert_eq!(
+            maintenance_gate(&windows, MaintenanceAction::Reprovision, &target, now),
+            MaintenanceGate::Open(active.id)
+        );
+        assert_eq!(
+            maintenance_gate(
+                &windows,
+                MaintenanceAction::Reprovision,
+                &MaintenanceTarget {
+                    sku: Some("sku2"),
+                    ..Default::default()
+                },
+                now
+            ),
+            MaintenanceGate::Unrestricted
+        );
+
+        // Cancelled windows are ignored
+        let mut cancelled = active;
+        cancelled.cancelled = Some(now);
+        assert_eq!(
+            maintenance_gate(&[cancelled], MaintenanceAction::Reprovision, &target, now),
+            MaintenanceGate::Unrestricted
+        );
+    }
+}
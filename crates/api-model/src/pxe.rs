@@ -16,12 +16,13 @@
  */
 
 use carbide_uuid::machine::MachineInterfaceId;
-use rpc::forge::MachineArchitecture;
+use rpc::forge::{BootScriptFormat, MachineArchitecture};
 
 pub struct PxeInstructionRequest {
     pub interface_id: MachineInterfaceId,
     pub arch: MachineArchitecture,
     pub product: Option<String>,
+    pub script_format: BootScriptFormat,
 }
 
 impl TryFrom<rpc::forge::PxeInstructionRequest> for PxeInstructionRequest {
@@ -43,10 +44,17 @@ impl TryFrom<rpc::forge::PxeInstructionRequest> for PxeInstructionRequest {
 
         let product = value.product;
 
+        let script_format = BootScriptFormat::try_from(value.script_format).map_err(|_| {
+            rpc::errors::RpcDataConversionError::InvalidArgument(
+                "Unknown boot script format received.".to_string(),
+            )
+        })?;
+
         Ok(PxeInstructionRequest {
             interface_id,
             arch,
             product,
+            script_format,
         })
     }
 }
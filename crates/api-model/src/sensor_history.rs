@@ -0,0 +1,232 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! History of sensor readings of machines.
+//!
+//! Raw readings are kept for [RAW_RETENTION]. They are also downsampled into
+//! [ROLLUP_INTERVAL] rollups which are kept for [ROLLUP_RETENTION].
+
+use chrono::{DateTime, TimeDelta, Utc};
+use rpc::errors::RpcDataConversionError;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, Row};
+
+/// How long raw sensor readings are kept
+pub const RAW_RETENTION: TimeDelta = TimeDelta::hours(6);
+
+/// The size of the buckets raw readings are downsampled into
+pub const ROLLUP_INTERVAL: TimeDelta = TimeDelta::minutes(5);
+
+/// How long downsampled sensor readings are kept
+pub const ROLLUP_RETENTION: TimeDelta = TimeDelta::weeks(4);
+
+/// A single reading of a sensor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SensorSample {
+    pub sensor: String,
+    pub name: String,
+    pub unit: String,
+    pub value: f64,
+    pub time: DateTime<Utc>,
+}
+
+impl TryFrom<rpc::forge::SensorSample> for SensorSample {
+    type Error = RpcDataConversionError;
+
+    fn try_from(sample: rpc::forge::SensorSample) -> Result<Self, Self::Error> {
+        if sample.sensor.is_empty() {
+            return Err(RpcDataConversionError::MissingArgument("sensor"));
+        }
+        let time = sample
+            .time
+            .ok_or(RpcDataConversionError::MissingArgument("time"))?;
+        let time = DateTime::<Utc>::try_from(time)
+            .map_err(|_| RpcDataConversionError::InvalidTimestamp(time.to_string()))?;
+
+        Ok(Self {
+            sensor: sample.sensor,
+            name: sample.name,
+            unit: sample.unit,
+            value: sample.value,
+            time,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SensorHistoryResolution {
+    Raw,
+    Rollup5m,
+}
+
+impl SensorHistoryResolution {
+    /// Picks raw readings as long as they still cover the start of the range
+    pub fn for_range(start_time: DateTime<Utc>, now: DateTime<Utc>) -> Self {
+        if start_time >= now - RAW_RETENTION {
+            Self::Raw
+        } else {
+            Self::Rollup5m
+        }
+    }
+}
+
+impl From<SensorHistoryResolution> for rpc::forge::SensorHistoryResolution {
+    fn from(resolution: SensorHistoryResolution) -> Self {
+        match resolution {
+            SensorHistoryResolution::Raw => rpc::forge::SensorHistoryResolution::Raw,
+            SensorHistoryResolution::Rollup5m => rpc::forge::SensorHistoryResolution::Rollup5m,
+        }
+    }
+}
+
+/// A point of a sensor series. For raw readings `min`, `max` and `avg` are the same value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SensorSeriesPoint {
+    pub time: DateTime<Utc>,
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
+}
+
+/// The readings of a single sensor, starting by the oldest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SensorSeries {
+    pub sensor: String,
+    pub name: String,
+    pub unit: String,
+    pub points: Vec<SensorSeriesPoint>,
+}
+
+impl From<SensorSeries> for rpc::forge::SensorSeries {
+    fn from(series: SensorSeries) -> Self {
+        rpc::forge::SensorSeries {
+            sensor: series.sensor,
+            name: series.name,
+            unit: series.unit,
+            points: series
+                .points
+                .into_iter()
+                .map(|point| rpc::forge::SensorSeriesPoint {
+                    time: Some(point.time.into()),
+                    min: point.min,
+                    max: point.max,
+                    avg: point.avg,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A row of the raw or rollup sensor history tables
+#[derive(Debug, Clone)]
+pub struct SensorSeriesRow {
+    pub sensor: String,
+    pub name: String,
+    pub unit: String,
+    pub point: SensorSeriesPoint,
+}
+
+impl<'r> FromRow<'r, PgRow> for SensorSeriesRow {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            sensor: row.try_get("sensor")?,
+            name: row.try_get("name")?,
+            unit: row.try_get("unit")?,
+            point: SensorSeriesPoint {
+                time: row.try_get("time")?,
+                min: row.try_get("min")?,
+                max: row.try_get("max")?,
+                avg: row.try_get("avg")?,
+            },
+        })
+    }
+}
+
+/// Groups rows which are ordered by sensor and time into series
+pub fn group_into_series(rows: impl IntoIterator<Item = SensorSeriesRow>) -> Vec<SensorSeries> {
+    let mut series: Vec<SensorSeries> = Vec::new();
+    for row in rows {
+        match series.last_mut() {
+            Some(current) if current.sensor == row.sensor => current.points.push(row.point),
+            _ => series.push(SensorSeries {
+                sensor: row.sensor,
+                name: row.name,
+                unit: row.unit,
+                points: vec![row.point],
+            }),
+        }
+    }
+    series
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolution_depends_on_raw_retention() {
+        let now = Utc::now();
+        assert_eq!(
+            SensorHistoryResolution::for_range(now - TimeDelta::hours(1), now),
+            SensorHistoryResolution::Raw
+        );
+        assert_eq!(
+            SensorHistoryResolution::for_range(now - TimeDelta::days(2), now),
+            SensorHistoryResolution::Rollup5m
+        );
+    }
+
+    #[test]
+    fn rows_are_grouped_by_sensor() {
+        let now = Utc::now();
+        let row = |sensor: &str, value: f64| SensorSeriesRow {
+            sensor: sensor.to_string(),
+            name: "CPU0_Temp".to_string(),
+            unit: "celsius".to_string(),
+            point: SensorSeriesPoint {
+                time: now,
+                min: value,
+                max: value,
+                avg: value,
+            },
+        };
+
+        let series = group_into_series(vec![
+            row("cpu0", 40.0),
+            row("cpu0", 41.0),
+            row("cpu1", 50.0),
+        ]);
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].sensor, "cpu0");
+        assert_eq!(series[0].points.len(), 2);
+        assert_eq!(series[1].points[0].avg, 50.0);
+    }
+
+    #[test]
+    fn sample_requires_time() {
+        let sample = rpc::forge::SensorSample {
+            sensor: "cpu0".to_string(),
+            name: "CPU0_Temp".to_string(),
+            unit: "celsius".to_string(),
+            value: 40.0,
+            time: None,
+        };
+        assert!(SensorSample::try_from(sample).is_err());
+    }
+}
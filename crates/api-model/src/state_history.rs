@@ -15,6 +15,9 @@
  * limitations under the License.
  */
 
+use std::fmt;
+use std::str::FromStr;
+
 use chrono::{DateTime, Utc};
 use config_version::ConfigVersion;
 use serde::{Deserialize, Serialize};
@@ -36,6 +39,45 @@ pub struct StateHistoryRecord {
     pub time: Option<DateTime<Utc>>,
 }
 
+/// Position of a record in a state history table.
+///
+/// State history records are totally ordered by `(txid, id)`, where `txid` is
+/// the ID of the transaction that wrote the record and `id` the primary key of
+/// the record. Unlike timestamps or primary keys alone, transaction IDs of
+/// records which become visible later are never smaller than the ones of
+/// records which have already been read, as long as only records of
+/// transactions older than all in-progress transactions are read.
+///
+/// The string format is `<txid>/<id>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct StateHistoryCursor {
+    pub txid: u64,
+    pub id: i64,
+}
+
+impl fmt::Display for StateHistoryCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.txid, self.id)
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid state history cursor: {0}")]
+pub struct StateHistoryCursorParseError(String);
+
+impl FromStr for StateHistoryCursor {
+    type Err = StateHistoryCursorParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || StateHistoryCursorParseError(s.to_string());
+        let (txid, id) = s.split_once('/').ok_or_else(err)?;
+        Ok(Self {
+            txid: txid.parse().map_err(|_| err())?,
+            id: id.parse().map_err(|_| err())?,
+        })
+    }
+}
+
 impl<'r> FromRow<'r, PgRow> for StateHistoryRecord {
     fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
         let time = match row.try_get("timestamp") {
@@ -94,3 +136,46 @@ impl From<StateHistoryRecord> for ::rpc::forge::NetworkSegmentStateHistory {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_history_cursor_roundtrip() {
+        let cursor = StateHistoryCursor {
+            txid: 4_294_967_301,
+            id: 12,
+        };
+        let serialized = cursor.to_string();
+        assert_eq!(serialized, "4294967301/12");
+        assert_eq!(serialized.parse::<StateHistoryCursor>().unwrap(), cursor);
+    }
+
+    #[test]
+    fn test_state_history_cursor_order() {
+        let cursor = |txid, id| StateHistoryCursor { txid, id };
+        // Records of older transactions come first, even if their ID is larger
+        assert!(cursor(10, 20) < cursor(11, 5));
+        assert!(cursor(10, 5) < cursor(10, 6));
+    }
+
+    #[test]
+    fn test_state_history_cursor_invalid() {
+        for invalid in [
+            "",
+            "1234",
+            "1234/",
+            "/12",
+            "abc/12",
+            "1234/12/3",
+            "-1/12",
+            "1700000000123456/V12-T1700000000123456/rack-1",
+        ] {
+            assert!(
+                invalid.parse::<StateHistoryCursor>().is_err(),
+                "{invalid} should not parse"
+            );
+        }
+    }
+}
@@ -21,13 +21,18 @@ use std::panic::Location;
 use std::pin::Pin;
 use std::sync::{Arc, OnceLock};
 
+use ::rpc::WatchStream;
 pub use ::rpc::forge as rpc;
 use ::rpc::forge::{RemoveSkuRequest, SkuIdList};
 use ::rpc::protos::dns::{
     CreateDomainRequest, DnsResourceRecordLookupRequest, DnsResourceRecordLookupResponse, Domain,
-    DomainDeletionRequest, DomainDeletionResult, DomainList, DomainMetadataRequest,
+    DomainDeletionRequest, DomainDeletionResult, DomainInfo, DomainList, DomainMetadataRequest,
     DomainMetadataResponse, DomainSearchQuery, GetAllDomainsRequest, GetAllDomainsResponse,
-    UpdateDomainRequest,
+    GetAllRecordsForDomainRequest, GetAllRecordsForDomainResponse, GetDomainInfoRequest,
+    GetUpdatedPrimaryDomainsRequest, SetDomainNotifiedRequest, SetDomainNotifiedResponse,
+    TenantDnsRecord, TenantDnsRecordCreationRequest, TenantDnsRecordDeletionRequest,
+    TenantDnsRecordDeletionResult, TenantDnsRecordList, TenantDnsRecordSearchQuery,
+    UpdateDnsRecordsRequest, UpdateDnsRecordsResponse, UpdateDomainRequest,
 };
 use ::rpc::protos::{measured_boot as measured_boot_pb, mlx_device as mlx_device_pb};
 use carbide_ib_fabric::ib::IBFabricManager;
@@ -57,7 +62,9 @@ use crate::ethernet_virtualization::EthVirtData;
 use crate::logging::log_limiter::LogLimiter;
 use crate::rack::bms_client::BmsDsxExchangeHandle;
 use crate::scout_stream::ConnectionRegistry;
+use crate::state_change_watch::StateChangeWatchers;
 use crate::state_controller::controller::Enqueuer;
+use crate::state_controller::machine::dry_run::MachineStateDryRunner;
 use crate::state_controller::machine::io::MachineStateControllerIO;
 use crate::{CarbideError, CarbideResult};
 
@@ -83,6 +90,8 @@ pub struct Api {
     pub(crate) metric_emitter: ApiMetricsEmitter,
     pub(crate) component_manager: Option<component_manager::component_manager::ComponentManager>,
     pub(crate) bms_client: OnceLock<Arc<BmsDsxExchangeHandle>>,
+    pub(crate) machine_state_dry_runner: OnceLock<MachineStateDryRunner>,
+    pub(crate) state_change_watchers: StateChangeWatchers,
 }
 
 pub(crate) type ScoutStreamType =
@@ -91,6 +100,10 @@ pub(crate) type ScoutStreamType =
 #[tonic::async_trait]
 impl Forge for Api {
     type ScoutStreamStream = ScoutStreamType;
+    type WatchManagedHostStatesStream = WatchStream<rpc::ManagedHostStateChange>;
+    type WatchInstanceStatesStream = WatchStream<rpc::InstanceStateChange>;
+    type WatchRackStatesStream = WatchStream<rpc::RackStateChange>;
+    type WatchSwitchStatesStream = WatchStream<rpc::SwitchStateChange>;
 
     async fn version(
         &self,
@@ -622,6 +635,62 @@ impl Forge for Api {
         crate::handlers::dns::get_all_domains(self, request).await
     }
 
+    async fn get_domain_info(
+        &self,
+        request: Request<GetDomainInfoRequest>,
+    ) -> Result<Response<DomainInfo>, Status> {
+        crate::handlers::dns::get_domain_info(self, request).await
+    }
+
+    async fn get_all_records_for_domain(
+        &self,
+        request: Request<GetAllRecordsForDomainRequest>,
+    ) -> Result<Response<GetAllRecordsForDomainResponse>, Status> {
+        crate::handlers::dns::get_all_records_for_domain(self, request).await
+    }
+
+    async fn update_dns_records(
+        &self,
+        request: Request<UpdateDnsRecordsRequest>,
+    ) -> Result<Response<UpdateDnsRecordsResponse>, Status> {
+        crate::handlers::dns::update_dns_records(self, request).await
+    }
+
+    async fn get_updated_primary_domains(
+        &self,
+        request: Request<GetUpdatedPrimaryDomainsRequest>,
+    ) -> Result<Response<GetAllDomainsResponse>, Status> {
+        crate::handlers::dns::get_updated_primary_domains(self, request).await
+    }
+
+    async fn set_domain_notified(
+        &self,
+        request: Request<SetDomainNotifiedRequest>,
+    ) -> Result<Response<SetDomainNotifiedResponse>, Status> {
+        crate::handlers::dns::set_domain_notified(self, request).await
+    }
+
+    async fn create_tenant_dns_record(
+        &self,
+        request: Request<TenantDnsRecordCreationRequest>,
+    ) -> Result<Response<TenantDnsRecord>, Status> {
+        crate::handlers::tenant_dns_record::create(self, request).await
+    }
+
+    async fn find_tenant_dns_records(
+        &self,
+        request: Request<TenantDnsRecordSearchQuery>,
+    ) -> Result<Response<TenantDnsRecordList>, Status> {
+        crate::handlers::tenant_dns_record::find(self, request).await
+    }
+
+    async fn delete_tenant_dns_record(
+        &self,
+        request: Request<TenantDnsRecordDeletionRequest>,
+    ) -> Result<Response<TenantDnsRecordDeletionResult>, Status> {
+        crate::handlers::tenant_dns_record::delete(self, request).await
+    }
+
     async fn lookup_record(
         &self,
         request: Request<DnsResourceRecordLookupRequest>,
@@ -810,6 +879,20 @@ impl Forge for Api {
         crate::handlers::machine::find_machine_state_histories(self, request).await
     }
 
+    async fn get_managed_host_state_timeline(
+        &self,
+        request: Request<rpc::ManagedHostStateTimelineRequest>,
+    ) -> Result<Response<rpc::ManagedHostStateTimeline>, Status> {
+        crate::handlers::machine::get_managed_host_state_timeline(self, request).await
+    }
+
+    async fn explain_machine_next_step(
+        &self,
+        request: Request<rpc::ExplainMachineNextStepRequest>,
+    ) -> Result<Response<rpc::ExplainMachineNextStepResponse>, Status> {
+        crate::handlers::machine::explain_machine_next_step(self, request).await
+    }
+
     async fn find_power_shelf_state_histories(
         &self,
         request: Request<rpc::PowerShelfStateHistoriesRequest>,
@@ -838,6 +921,20 @@ impl Forge for Api {
         crate::handlers::machine::find_machine_health_histories(self, request).await
     }
 
+    async fn record_machine_sensor_samples(
+        &self,
+        request: Request<rpc::RecordMachineSensorSamplesRequest>,
+    ) -> Result<Response<()>, Status> {
+        crate::handlers::sensor_history::record_machine_sensor_samples(self, request).await
+    }
+
+    async fn find_machine_sensor_history(
+        &self,
+        request: Request<rpc::MachineSensorHistoryRequest>,
+    ) -> Result<Response<rpc::MachineSensorHistory>, Status> {
+        crate::handlers::sensor_history::find_machine_sensor_history(self, request).await
+    }
+
     async fn assign_static_address(
         &self,
         request: Request<rpc::AssignStaticAddressRequest>,
@@ -1934,6 +2031,27 @@ impl Forge for Api {
         crate::handlers::measured_boot::find_closest_bundle_match(self, request).await
     }
 
+    async fn create_measurement_policy(
+        &self,
+        request: Request<measured_boot_pb::CreateMeasurementPolicyRequest>,
+    ) -> Result<Response<measured_boot_pb::CreateMeasurementPolicyResponse>, Status> {
+        crate::handlers::measured_boot::create_policy(self, request).await
+    }
+
+    async fn delete_measurement_policy(
+        &self,
+        request: Request<measured_boot_pb::DeleteMeasurementPolicyRequest>,
+    ) -> Result<Response<measured_boot_pb::DeleteMeasurementPolicyResponse>, Status> {
+        crate::handlers::measured_boot::delete_policy(self, request).await
+    }
+
+    async fn show_measurement_policies(
+        &self,
+        request: Request<measured_boot_pb::ShowMeasurementPoliciesRequest>,
+    ) -> Result<Response<measured_boot_pb::ShowMeasurementPoliciesResponse>, Status> {
+        crate::handlers::measured_boot::show_policies(self, request).await
+    }
+
     async fn delete_measurement_journal(
         &self,
         request: Request<measured_boot_pb::DeleteMeasurementJournalRequest>,
@@ -2755,6 +2873,27 @@ impl Forge for Api {
         crate::handlers::firmware::set_firmware_update_time_window(self, request).await
     }
 
+    async fn create_maintenance_window(
+        &self,
+        request: Request<rpc::CreateMaintenanceWindowRequest>,
+    ) -> Result<Response<rpc::MaintenanceWindow>, Status> {
+        crate::handlers::maintenance_window::create_maintenance_window(self, request).await
+    }
+
+    async fn find_maintenance_windows(
+        &self,
+        request: Request<rpc::FindMaintenanceWindowsRequest>,
+    ) -> Result<Response<rpc::MaintenanceWindowList>, Status> {
+        crate::handlers::maintenance_window::find_maintenance_windows(self, request).await
+    }
+
+    async fn cancel_maintenance_window(
+        &self,
+        request: Request<rpc::CancelMaintenanceWindowRequest>,
+    ) -> Result<Response<rpc::MaintenanceWindow>, Status> {
+        crate::handlers::maintenance_window::cancel_maintenance_window(self, request).await
+    }
+
     async fn list_host_firmware(
         &self,
         request: Request<rpc::ListHostFirmwareRequest>,
@@ -3064,6 +3203,36 @@ impl Forge for Api {
         crate::handlers::scout_stream::ping(self, request).await
     }
 
+    // The watch_*_states RPCs stream state transitions of the respective objects.
+    // Clients can resume a stream by passing the last version they received.
+    async fn watch_managed_host_states(
+        &self,
+        request: Request<rpc::WatchManagedHostStatesRequest>,
+    ) -> Result<Response<Self::WatchManagedHostStatesStream>, Status> {
+        crate::handlers::state_watch::watch_managed_host_states(self, request).await
+    }
+
+    async fn watch_instance_states(
+        &self,
+        request: Request<rpc::WatchInstanceStatesRequest>,
+    ) -> Result<Response<Self::WatchInstanceStatesStream>, Status> {
+        crate::handlers::state_watch::watch_instance_states(self, request).await
+    }
+
+    async fn watch_rack_states(
+        &self,
+        request: Request<rpc::WatchRackStatesRequest>,
+    ) -> Result<Response<Self::WatchRackStatesStream>, Status> {
+        crate::handlers::state_watch::watch_rack_states(self, request).await
+    }
+
+    async fn watch_switch_states(
+        &self,
+        request: Request<rpc::WatchSwitchStatesRequest>,
+    ) -> Result<Response<Self::WatchSwitchStatesStream>, Status> {
+        crate::handlers::state_watch::watch_switch_states(self, request).await
+    }
+
     async fn mlx_admin_profile_sync(
         &self,
         request: Request<mlx_device_pb::MlxAdminProfileSyncRequest>,
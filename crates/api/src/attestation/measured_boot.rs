@@ -20,6 +20,8 @@ use std::fs::File;
 use std::io::Write;
 use std::process::Command;
 
+use ::measured_boot::event_log::{EventLog, looks_like_event_log};
+use ::measured_boot::replay::EventLogAnalysis;
 use byteorder::{BigEndian, ByteOrder};
 use carbide_uuid::machine::MachineId;
 use carbide_uuid::measured_boot::MeasurementReportId;
@@ -278,13 +280,61 @@ fn extract_cred_secret(creds: &[u8]) -> CarbideResult<(Vec<u8>, Vec<u8>)> {
 pub fn event_log_to_string(event_log: &Option<Vec<u8>>) -> String {
     event_log
         .as_ref()
-        .map(|log_utf8| {
-            String::from_utf8(log_utf8.to_vec())
+        .map(|log| {
+            // Newer scout builds send the binary TCG event log, older
+            // ones send the text output of tpm2_eventlog.
+            if looks_like_event_log(log) {
+                return match EventLog::parse(log) {
+                    Ok(parsed) => parsed
+                        .events
+                        .iter()
+                        .map(|event| {
+                            format!(
+                                "[{}] pcr={} {}: {}",
+                                event.index,
+                                event.pcr_index,
+                                event.event_type,
+                                event.description()
+                            )
+                        })
+                        .collect::<Vec<String>>()
+                        .join("\n"),
+                    Err(e) => format!("<event log failed to parse: {e}>"),
+                };
+            }
+            String::from_utf8(log.to_vec())
                 .unwrap_or(String::from("<event log failed utf8 conversion>"))
         })
         .unwrap_or(String::from("<event log empty>"))
 }
 
+/// log_event_log_analysis logs the outcome of replaying a machine's event
+/// log: PCRs the log doesn't replay to (which means the log can't be trusted
+/// to explain the quote), and the events that changed since the reference log.
+pub fn log_event_log_analysis(machine_id: &MachineId, analysis: &EventLogAnalysis) {
+    let mismatched = analysis.mismatched_pcrs();
+    if !mismatched.is_empty() {
+        tracing::warn!(
+            %machine_id,
+            ?mismatched,
+            "Event log does not replay to the quoted PCR values"
+        );
+    }
+    if let Some(reference_report_id) = analysis.reference_report_id {
+        for diff in analysis.diffs.iter() {
+            tracing::info!(
+                %machine_id,
+                %reference_report_id,
+                pcr_register = diff.pcr_register,
+                change = ?diff.change,
+                event_type = %diff.event_type,
+                "Event log change: {}",
+                diff.description
+            );
+        }
+    }
+}
+
 #[cfg_attr(not(feature = "linux-build"), allow(unused_variables))]
 pub async fn compare_pub_key_against_cert(
     txn: &mut PgConnection,
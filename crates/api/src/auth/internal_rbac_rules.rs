@@ -182,6 +182,14 @@ impl InternalRBACRules {
         x.perm("LookupRecordLegacy", vec![Dns]);
         x.perm("GetAllDomainMetadata", vec![Dns]);
         x.perm("GetAllDomains", vec![Dns]);
+        x.perm("GetDomainInfo", vec![Dns]);
+        x.perm("GetAllRecordsForDomain", vec![Dns]);
+        x.perm("UpdateDnsRecords", vec![Dns]);
+        x.perm("GetUpdatedPrimaryDomains", vec![Dns]);
+        x.perm("SetDomainNotified", vec![Dns]);
+        x.perm("CreateTenantDnsRecord", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("FindTenantDnsRecords", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("DeleteTenantDnsRecord", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("InvokeInstancePower", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("ForgeAgentControl", vec![Machineatron, Scout]);
         x.perm("DiscoverMachine", vec![Anonymous]);
@@ -225,7 +233,11 @@ impl InternalRBACRules {
         x.perm("FindConnectedDevicesByDpuMachineIds", vec![ForgeAdminCLI]);
         x.perm("FindMachineIdsByBmcIps", vec![ForgeAdminCLI, Rla]);
         x.perm("FindMachineHealthHistories", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("RecordMachineSensorSamples", vec![Health]);
+        x.perm("FindMachineSensorHistory", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("FindMachineStateHistories", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("GetManagedHostStateTimeline", vec![ForgeAdminCLI]);
+        x.perm("ExplainMachineNextStep", vec![ForgeAdminCLI]);
         x.perm("IdentifyUuid", vec![ForgeAdminCLI]);
         x.perm("IdentifyMac", vec![ForgeAdminCLI]);
         x.perm("IdentifySerial", vec![ForgeAdminCLI, Machineatron, Rla]);
@@ -345,6 +357,9 @@ impl InternalRBACRules {
         x.perm("ListMeasurementBundles", vec![ForgeAdminCLI]);
         x.perm("ListMeasurementBundleMachines", vec![ForgeAdminCLI]);
         x.perm("FindClosestBundleMatch", vec![ForgeAdminCLI]);
+        x.perm("CreateMeasurementPolicy", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("DeleteMeasurementPolicy", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("ShowMeasurementPolicies", vec![ForgeAdminCLI]);
         x.perm("DeleteMeasurementJournal", vec![ForgeAdminCLI]);
         x.perm("ShowMeasurementJournal", vec![ForgeAdminCLI]);
         x.perm("ShowMeasurementJournals", vec![ForgeAdminCLI]);
@@ -430,15 +445,18 @@ impl InternalRBACRules {
         x.perm("GetOperatingSystem", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("UpdateOperatingSystem", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("DeleteOperatingSystem", vec![ForgeAdminCLI, SiteAgent]);
-        x.perm("FindOperatingSystemIds", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm(
+            "FindOperatingSystemIds",
+            vec![ForgeAdminCLI, SiteAgent, Pxe],
+        );
         x.perm("FindOperatingSystemsByIds", vec![ForgeAdminCLI, SiteAgent]);
         x.perm(
             "GetOperatingSystemCachableIpxeTemplateArtifacts",
-            vec![ForgeAdminCLI],
+            vec![ForgeAdminCLI, Pxe],
         );
         x.perm(
             "UpdateOperatingSystemCachableIpxeTemplateArtifacts",
-            vec![ForgeAdminCLI],
+            vec![ForgeAdminCLI, Pxe],
         );
         x.perm("GetIpxeTemplate", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("ListIpxeTemplates", vec![ForgeAdminCLI, SiteAgent]);
@@ -595,6 +613,9 @@ impl InternalRBACRules {
         x.perm("CreateBmcUser", vec![ForgeAdminCLI]);
         x.perm("DeleteBmcUser", vec![ForgeAdminCLI]);
         x.perm("SetFirmwareUpdateTimeWindow", vec![ForgeAdminCLI, Rla]);
+        x.perm("CreateMaintenanceWindow", vec![ForgeAdminCLI]);
+        x.perm("FindMaintenanceWindows", vec![ForgeAdminCLI]);
+        x.perm("CancelMaintenanceWindow", vec![ForgeAdminCLI]);
         x.perm("ListHostFirmware", vec![ForgeAdminCLI, Rla]);
         x.perm("EnableInfiniteBoot", vec![ForgeAdminCLI]);
         x.perm("IsInfiniteBootEnabled", vec![ForgeAdminCLI]);
@@ -763,6 +784,10 @@ impl InternalRBACRules {
         x.perm("ScoutStreamShowConnections", vec![ForgeAdminCLI]);
         x.perm("ScoutStreamDisconnect", vec![ForgeAdminCLI]);
         x.perm("ScoutStreamPing", vec![ForgeAdminCLI]);
+        x.perm("WatchManagedHostStates", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("WatchInstanceStates", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("WatchRackStates", vec![ForgeAdminCLI, SiteAgent, Rla]);
+        x.perm("WatchSwitchStates", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("MlxAdminProfileSync", vec![ForgeAdminCLI]);
         x.perm("MlxAdminProfileShow", vec![ForgeAdminCLI]);
         x.perm("MlxAdminProfileCompare", vec![ForgeAdminCLI]);
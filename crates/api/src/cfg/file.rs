@@ -46,6 +46,7 @@ use model::firmware::{
     AgentUpgradePolicyChoice, Firmware, FirmwareComponent, FirmwareComponentType, FirmwareEntry,
 };
 use model::machine::HostHealthConfig;
+use model::machine::slas::MachineSlaConfig;
 use model::network_security_group::NetworkSecurityGroupRule;
 use model::network_segment::NetworkDefinition;
 use model::resource_pool::define::ResourcePoolDef;
@@ -53,7 +54,9 @@ use model::tenant::identity_config::SigningAlgorithm;
 use regex::Regex;
 use serde::{Deserialize, Deserializer, Serialize};
 
-use crate::state_controller::config::IterationConfig;
+use crate::state_controller::config::{
+    IterationConfig, OutboxRelayConfig, PriorityLaneConfig, PriorityLanesConfig,
+};
 
 static BF2_NIC: &str = "24.47.2682";
 static BF2_BMC: &str = "BF-25.10-20";
@@ -551,6 +554,15 @@ pub struct CarbideConfig {
     #[serde(default)]
     pub arm_pxe_boot_url_override: Option<String>,
 
+    /// Selects UEFI HTTP Boot (signed shim/grub or UKI images served by
+    /// carbide-pxe) instead of iPXE chainloading, per host or per SKU.
+    #[serde(default)]
+    pub http_boot: HttpBootConfig,
+
+    /// Quotas for the DNS records tenants create in their VPC domains.
+    #[serde(default)]
+    pub tenant_dns_records: TenantDnsRecordsConfig,
+
     /// Alternate API URL for external hosts that cannot resolve
     /// https://carbide-pxe.forge. This be an IP (e.g., "https://10.0.0.1:1079"),
     /// or an externally resolvable hostname (e.g.,
@@ -632,6 +644,106 @@ pub struct CarbideConfig {
     pub config_ctx: Option<Figment>,
 }
 
+/// How a host gets from its UEFI firmware to the discovery image.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BootMethod {
+    /// UEFI HTTP Boot fetches `ipxe.efi`, which then chainloads the iPXE
+    /// script from carbide-pxe.
+    #[default]
+    Ipxe,
+    /// UEFI HTTP Boot fetches a signed boot loader directly, so hosts with
+    /// Secure Boot enabled never run iPXE.
+    HttpBoot,
+}
+
+/// The image a host configured for [`BootMethod::HttpBoot`] is pointed at.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HttpBootLoader {
+    /// The signed shim, which loads grub from the same directory. grub then
+    /// fetches its `grub.cfg` from carbide-pxe.
+    #[default]
+    ShimGrub,
+    /// A signed unified kernel image, which carries its own command line.
+    Uki,
+}
+
+/// UEFI HTTP Boot configuration.
+///
+/// ```toml
+/// [http_boot]
+/// base_url = "http://carbide-pxe.forge:8080/api/v0/http-boot"
+/// default_method = "ipxe"
+///
+/// [http_boot.skus]
+/// "PowerEdge-R760" = "http_boot"
+///
+/// [http_boot.hosts]
+/// "fm100htjsaledfasinabqqer70e2ua5ksqj4kfjii0v0a90vulps48c1h7g" = "ipxe"
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct HttpBootConfig {
+    /// carbide-pxe URL under which the HTTP Boot images are served. HTTP Boot
+    /// is disabled for all hosts if this is not set.
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Boot method for hosts that have neither a host nor a SKU entry.
+    #[serde(default)]
+    pub default_method: BootMethod,
+    /// Boot method per SKU ID. Takes precedence over `default_method`.
+    #[serde(default)]
+    pub skus: HashMap<String, BootMethod>,
+    /// Boot method per host machine ID. Takes precedence over `skus`.
+    #[serde(default)]
+    pub hosts: HashMap<String, BootMethod>,
+    /// The image handed out to hosts using HTTP Boot.
+    #[serde(default)]
+    pub loader: HttpBootLoader,
+}
+
+/// Quotas for tenant-managed DNS records.
+///
+/// ```toml
+/// [tenant_dns_records]
+/// default_quota = 100
+///
+/// [tenant_dns_records.quotas]
+/// "tenant-org-with-many-services" = 1000
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct TenantDnsRecordsConfig {
+    /// Maximum number of records per tenant organization without a `quotas`
+    /// entry. 0 disables tenant-managed records for those tenants.
+    #[serde(default = "TenantDnsRecordsConfig::default_quota")]
+    pub default_quota: u32,
+    /// Maximum number of records per tenant organization ID.
+    #[serde(default)]
+    pub quotas: HashMap<String, u32>,
+}
+
+impl TenantDnsRecordsConfig {
+    fn default_quota() -> u32 {
+        100
+    }
+
+    pub fn quota(&self, tenant_organization_id: &str) -> u32 {
+        self.quotas
+            .get(tenant_organization_id)
+            .copied()
+            .unwrap_or(self.default_quota)
+    }
+}
+
+impl Default for TenantDnsRecordsConfig {
+    fn default() -> Self {
+        Self {
+            default_quota: Self::default_quota(),
+            quotas: HashMap::new(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
 pub enum BgpLeafSessionPassword {
     /// Use a defined site-wide password.
@@ -1229,9 +1341,26 @@ pub struct MachineStateControllerConfig {
         serialize_with = "as_duration"
     )]
     pub uefi_boot_wait: Duration,
+    /// SLAs which replace the built-in maximum time that a host is expected
+    /// to spend in a state. Hosts which exceed the SLA get a health alert.
+    #[serde(default)]
+    pub state_slas: Vec<MachineStateSlaConfig>,
+    /// Whether hosts which exceed a built-in SLA get a health alert.
+    /// SLAs which are configured in `state_slas` always raise alerts.
+    #[serde(default)]
+    pub builtin_state_sla_alerts: bool,
 }
 
 impl MachineStateControllerConfig {
+    /// Returns the SLAs which are evaluated by the machine state controller
+    pub fn sla_config(&self) -> MachineSlaConfig {
+        MachineSlaConfig::new(self.failure_retry_time).with_state_overrides(
+            self.state_slas
+                .iter()
+                .map(|state_sla| (state_sla.key(), state_sla.sla)),
+        )
+    }
+
     pub fn dpu_wait_time_default() -> Duration {
         Duration::minutes(5)
     }
@@ -1268,6 +1397,36 @@ impl Default for MachineStateControllerConfig {
             scout_reporting_timeout: MachineStateControllerConfig::scout_reporting_timeout_default(
             ),
             uefi_boot_wait: MachineStateControllerConfig::uefi_boot_wait_default(),
+            state_slas: Vec::new(),
+            builtin_state_sla_alerts: false,
+        }
+    }
+}
+
+/// The SLA for a single state of the machine state machine
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MachineStateSlaConfig {
+    /// The name of the state, as used in the `state` attribute of state controller metrics.
+    /// E.g. `dpunotready`
+    pub state: String,
+    /// The name of the substate, as used in the `substate` attribute of state controller
+    /// metrics. E.g. `waitingfornetworkconfig`. If absent, the SLA applies to all
+    /// substates which don't have their own SLA configured.
+    #[serde(default)]
+    pub substate: Option<String>,
+    /// The maximum time a host is expected to stay in the state
+    #[serde(
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub sla: std::time::Duration,
+}
+
+impl MachineStateSlaConfig {
+    fn key(&self) -> String {
+        match &self.substate {
+            Some(substate) => format!("{}/{}", self.state, substate),
+            None => self.state.clone(),
         }
     }
 }
@@ -1430,6 +1589,11 @@ pub struct StateControllerConfig {
         serialize_with = "as_std_duration"
     )]
     pub metric_hold_time: std::time::Duration,
+
+    /// Configures how the processing capacity is shared between queued objects
+    /// of different priority classes
+    #[serde(default)]
+    pub priority_lanes: StateControllerPriorityLanesConfig,
 }
 
 impl StateControllerConfig {
@@ -1472,6 +1636,90 @@ impl Default for StateControllerConfig {
             max_concurrency: Self::max_concurrency_default(),
             metric_emission_interval: Self::metric_emission_interval(),
             metric_hold_time: Self::metric_hold_time(),
+            priority_lanes: StateControllerPriorityLanesConfig::default(),
+        }
+    }
+}
+
+/// Settings for a single priority class of state controller queued objects
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct StateControllerPriorityLaneConfig {
+    /// The share of dispatch slots that objects of this class receive, relative
+    /// to the weights of the other classes.
+    /// With a weight of 0, objects of this class only get dispatched if no
+    /// objects of other classes are waiting.
+    pub weight: u32,
+
+    /// The maximum amount of objects of this class that are processed concurrently.
+    /// If not set, only `max_concurrency` of the state controller applies.
+    #[serde(default)]
+    pub max_concurrency: Option<usize>,
+}
+
+/// Settings for all priority classes of state controller queued objects
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct StateControllerPriorityLanesConfig {
+    /// Objects whose state handling impacts tenants, e.g. assigned hosts
+    #[serde(default = "StateControllerPriorityLanesConfig::tenant_impacting_default")]
+    pub tenant_impacting: StateControllerPriorityLaneConfig,
+
+    /// Objects which have explicitly been enqueued, e.g. due to an API request
+    #[serde(default = "StateControllerPriorityLanesConfig::operator_requested_default")]
+    pub operator_requested: StateControllerPriorityLaneConfig,
+
+    /// Objects which are periodically enqueued by the state controller
+    #[serde(default = "StateControllerPriorityLanesConfig::background_default")]
+    pub background: StateControllerPriorityLaneConfig,
+}
+
+impl StateControllerPriorityLanesConfig {
+    pub const fn tenant_impacting_default() -> StateControllerPriorityLaneConfig {
+        StateControllerPriorityLaneConfig {
+            weight: 6,
+            max_concurrency: None,
+        }
+    }
+
+    pub const fn operator_requested_default() -> StateControllerPriorityLaneConfig {
+        StateControllerPriorityLaneConfig {
+            weight: 3,
+            max_concurrency: None,
+        }
+    }
+
+    pub const fn background_default() -> StateControllerPriorityLaneConfig {
+        StateControllerPriorityLaneConfig {
+            weight: 1,
+            max_concurrency: None,
+        }
+    }
+}
+
+impl Default for StateControllerPriorityLanesConfig {
+    fn default() -> Self {
+        Self {
+            tenant_impacting: Self::tenant_impacting_default(),
+            operator_requested: Self::operator_requested_default(),
+            background: Self::background_default(),
+        }
+    }
+}
+
+impl From<&StateControllerPriorityLaneConfig> for PriorityLaneConfig {
+    fn from(config: &StateControllerPriorityLaneConfig) -> Self {
+        PriorityLaneConfig {
+            weight: config.weight,
+            max_concurrency: config.max_concurrency,
+        }
+    }
+}
+
+impl From<&StateControllerPriorityLanesConfig> for PriorityLanesConfig {
+    fn from(config: &StateControllerPriorityLanesConfig) -> Self {
+        PriorityLanesConfig {
+            tenant_impacting: (&config.tenant_impacting).into(),
+            operator_requested: (&config.operator_requested).into(),
+            background: (&config.background).into(),
         }
     }
 }
@@ -1486,6 +1734,7 @@ impl From<&StateControllerConfig> for IterationConfig {
             processor_log_interval: config.processor_log_interval,
             metric_emission_interval: config.metric_emission_interval,
             metric_hold_time: config.metric_hold_time,
+            priority_lanes: (&config.priority_lanes).into(),
         }
     }
 }
@@ -2485,6 +2734,31 @@ pub struct DsxExchangeEventBusConfig {
     #[serde(default = "DsxExchangeEventBusConfig::default_queue_capacity")]
     pub queue_capacity: usize,
 
+    /// Publish `ManagedHostState` transitions through the transactional state change
+    /// outbox instead of the in-memory queue. Transitions are retried until the broker
+    /// accepts them or `max_delivery_attempts` is reached, and are then moved into the
+    /// dead-letter table. Defaults to false.
+    ///
+    /// The outbox holds every transition that has not been published yet, so it grows
+    /// for as long as the broker is unreachable. Dead letters are not removed
+    /// automatically.
+    #[serde(default = "DsxExchangeEventBusConfig::default_durable_delivery")]
+    pub durable_delivery: bool,
+
+    /// How often publishing a state change is attempted before it is moved into
+    /// the dead-letter table. Only used with `durable_delivery`. Defaults to 10.
+    #[serde(default = "DsxExchangeEventBusConfig::default_max_delivery_attempts")]
+    pub max_delivery_attempts: u32,
+
+    /// Upper bound for the exponential backoff between publish attempts.
+    /// Only used with `durable_delivery`. Defaults to 5 minutes.
+    #[serde(
+        default = "DsxExchangeEventBusConfig::default_max_retry_backoff",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub max_retry_backoff: std::time::Duration,
+
     #[serde(default)]
     pub auth: MqttAuthConfig,
 }
@@ -2497,6 +2771,28 @@ impl DsxExchangeEventBusConfig {
     pub const fn default_queue_capacity() -> usize {
         1024
     }
+
+    pub const fn default_durable_delivery() -> bool {
+        false
+    }
+
+    pub const fn default_max_delivery_attempts() -> u32 {
+        10
+    }
+
+    pub const fn default_max_retry_backoff() -> std::time::Duration {
+        std::time::Duration::from_secs(5 * 60)
+    }
+}
+
+impl From<&DsxExchangeEventBusConfig> for OutboxRelayConfig {
+    fn from(config: &DsxExchangeEventBusConfig) -> Self {
+        OutboxRelayConfig {
+            max_attempts: config.max_delivery_attempts,
+            max_retry_backoff: config.max_retry_backoff,
+            ..Default::default()
+        }
+    }
 }
 
 /// MachineValidation related configuration
@@ -2674,6 +2970,7 @@ mod tests {
                 processor_log_interval: std::time::Duration::from_secs(60),
                 metric_emission_interval: std::time::Duration::from_secs(60),
                 metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                priority_lanes: StateControllerPriorityLanesConfig::default(),
             },
             dpu_wait_time: Duration::minutes(20),
             power_down_wait: Duration::seconds(10),
@@ -2681,6 +2978,12 @@ mod tests {
             dpu_up_threshold: Duration::weeks(1),
             scout_reporting_timeout: Duration::minutes(5),
             uefi_boot_wait: Duration::minutes(5),
+            state_slas: vec![MachineStateSlaConfig {
+                state: "dpunotready".to_string(),
+                substate: Some("waitingfornetworkconfig".to_string()),
+                sla: std::time::Duration::from_secs(45 * 60),
+            }],
+            builtin_state_sla_alerts: false,
         };
 
         let config_str = serde_json::to_string(&input).unwrap();
@@ -2701,7 +3004,8 @@ mod tests {
     fn deserialize_machine_controller_config() {
         let config = r#"{"dpu_wait_time": "20m","power_down_wait":"10s",
         "failure_retry_time":"1h30m", "dpu_up_threshold": "1w",
-        "controller": {"iteration_time": "33s", "max_object_handling_time": "63s", "max_concurrency": 13}}"#;
+        "controller": {"iteration_time": "33s", "max_object_handling_time": "63s", "max_concurrency": 13},
+        "state_slas": [{"state": "dpunotready", "sla": "1h"}]}"#;
         let config: MachineStateControllerConfig = serde_json::from_str(config).unwrap();
 
         assert_eq!(
@@ -2716,6 +3020,7 @@ mod tests {
                         processor_log_interval: std::time::Duration::from_secs(60),
                         metric_emission_interval: std::time::Duration::from_secs(60),
                         metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                        priority_lanes: StateControllerPriorityLanesConfig::default(),
                     }
                 },
                 dpu_wait_time: Duration::minutes(20),
@@ -2724,6 +3029,12 @@ mod tests {
                 dpu_up_threshold: Duration::weeks(1),
                 scout_reporting_timeout: Duration::minutes(5),
                 uefi_boot_wait: Duration::minutes(5),
+                state_slas: vec![MachineStateSlaConfig {
+                    state: "dpunotready".to_string(),
+                    substate: None,
+                    sla: std::time::Duration::from_secs(60 * 60),
+                }],
+                builtin_state_sla_alerts: false,
             }
         );
     }
@@ -2744,6 +3055,8 @@ mod tests {
                 dpu_up_threshold: Duration::weeks(1),
                 scout_reporting_timeout: Duration::minutes(5),
                 uefi_boot_wait: Duration::minutes(5),
+                state_slas: vec![],
+                builtin_state_sla_alerts: false,
             }
         );
     }
@@ -2766,6 +3079,7 @@ mod tests {
                         processor_log_interval: std::time::Duration::from_secs(60),
                         metric_emission_interval: std::time::Duration::from_secs(60),
                         metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                        priority_lanes: StateControllerPriorityLanesConfig::default(),
                     }
                 },
                 network_segment_drain_time: Duration::minutes(21),
@@ -2787,7 +3101,7 @@ mod tests {
         let config_str = serde_json::to_string(&input).unwrap();
         assert_eq!(
             config_str,
-            r#"{"iteration_time":"30s","max_object_handling_time":"180s","max_concurrency":10,"processor_dispatch_interval":"2s","processor_log_interval":"60s","metric_emission_interval":"60s","metric_hold_time":"300s"}"#
+            r#"{"iteration_time":"30s","max_object_handling_time":"180s","max_concurrency":10,"processor_dispatch_interval":"2s","processor_log_interval":"60s","metric_emission_interval":"60s","metric_hold_time":"300s","priority_lanes":{"tenant_impacting":{"weight":6,"max_concurrency":null},"operator_requested":{"weight":3,"max_concurrency":null},"background":{"weight":1,"max_concurrency":null}}}"#
         );
         let config: StateControllerConfig = serde_json::from_str(&config_str).unwrap();
         assert_eq!(config, input);
@@ -2803,11 +3117,25 @@ mod tests {
             processor_log_interval: std::time::Duration::from_secs(60),
             metric_emission_interval: std::time::Duration::from_secs(60),
             metric_hold_time: std::time::Duration::from_secs(5 * 60),
+            priority_lanes: StateControllerPriorityLanesConfig {
+                tenant_impacting: StateControllerPriorityLaneConfig {
+                    weight: 10,
+                    max_concurrency: None,
+                },
+                operator_requested: StateControllerPriorityLaneConfig {
+                    weight: 5,
+                    max_concurrency: Some(4),
+                },
+                background: StateControllerPriorityLaneConfig {
+                    weight: 0,
+                    max_concurrency: Some(2),
+                },
+            },
         };
         let config_str = serde_json::to_string(&input).unwrap();
         assert_eq!(
             config_str,
-            r#"{"iteration_time":"11s","max_object_handling_time":"22s","max_concurrency":33,"processor_dispatch_interval":"2s","processor_log_interval":"60s","metric_emission_interval":"60s","metric_hold_time":"300s"}"#
+            r#"{"iteration_time":"11s","max_object_handling_time":"22s","max_concurrency":33,"processor_dispatch_interval":"2s","processor_log_interval":"60s","metric_emission_interval":"60s","metric_hold_time":"300s","priority_lanes":{"tenant_impacting":{"weight":10,"max_concurrency":null},"operator_requested":{"weight":5,"max_concurrency":4},"background":{"weight":0,"max_concurrency":2}}}"#
         );
         let config: StateControllerConfig = serde_json::from_str(&config_str).unwrap();
         assert_eq!(config, input);
@@ -2968,6 +3296,8 @@ mod tests {
                 rotate_switch_nvos_credentials: Arc::new(false.into()),
                 force_dpu_nic_mode: Arc::new(false.into()),
                 explore_mode: SiteExplorerExploreMode::LibRedfish,
+                bmc_event_subscriptions: false,
+                bmc_event_min_interval: std::time::Duration::from_secs(10),
             }
         );
         assert_eq!(
@@ -2981,6 +3311,7 @@ mod tests {
                     processor_log_interval: std::time::Duration::from_secs(60),
                     metric_emission_interval: std::time::Duration::from_secs(60),
                     metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                    priority_lanes: StateControllerPriorityLanesConfig::default(),
                 },
                 dpu_wait_time: Duration::minutes(7),
                 power_down_wait: Duration::seconds(17),
@@ -2988,6 +3319,8 @@ mod tests {
                 dpu_up_threshold: Duration::minutes(77),
                 scout_reporting_timeout: Duration::minutes(5),
                 uefi_boot_wait: Duration::minutes(5),
+                state_slas: vec![],
+                builtin_state_sla_alerts: false,
             }
         );
         assert_eq!(
@@ -3002,6 +3335,7 @@ mod tests {
                     processor_log_interval: std::time::Duration::from_secs(60),
                     metric_emission_interval: std::time::Duration::from_secs(60),
                     metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                    priority_lanes: StateControllerPriorityLanesConfig::default(),
                 },
             }
         );
@@ -3016,6 +3350,7 @@ mod tests {
                     processor_log_interval: std::time::Duration::from_secs(60),
                     metric_emission_interval: std::time::Duration::from_secs(60),
                     metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                    priority_lanes: StateControllerPriorityLanesConfig::default(),
                 },
             }
         );
@@ -3141,6 +3476,8 @@ mod tests {
                 rotate_switch_nvos_credentials: Arc::new(false.into()),
                 force_dpu_nic_mode: Arc::new(false.into()),
                 explore_mode: SiteExplorerExploreMode::LibRedfish,
+                bmc_event_subscriptions: false,
+                bmc_event_min_interval: std::time::Duration::from_secs(10),
             }
         );
 
@@ -3165,6 +3502,7 @@ mod tests {
                     processor_log_interval: std::time::Duration::from_secs(60),
                     metric_emission_interval: std::time::Duration::from_secs(60),
                     metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                    priority_lanes: StateControllerPriorityLanesConfig::default(),
                 },
                 dpu_wait_time: Duration::minutes(3),
                 power_down_wait: Duration::seconds(13),
@@ -3172,6 +3510,8 @@ mod tests {
                 dpu_up_threshold: Duration::minutes(33),
                 scout_reporting_timeout: Duration::minutes(20),
                 uefi_boot_wait: Duration::minutes(5),
+                state_slas: vec![],
+                builtin_state_sla_alerts: false,
             }
         );
         assert_eq!(
@@ -3186,6 +3526,7 @@ mod tests {
                     processor_log_interval: std::time::Duration::from_secs(60),
                     metric_emission_interval: std::time::Duration::from_secs(60),
                     metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                    priority_lanes: StateControllerPriorityLanesConfig::default(),
                 },
             }
         );
@@ -3200,6 +3541,7 @@ mod tests {
                     processor_log_interval: std::time::Duration::from_secs(60),
                     metric_emission_interval: std::time::Duration::from_secs(60),
                     metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                    priority_lanes: StateControllerPriorityLanesConfig::default(),
                 },
             }
         );
@@ -3450,6 +3792,8 @@ mod tests {
                 rotate_switch_nvos_credentials: Arc::new(false.into()),
                 force_dpu_nic_mode: Arc::new(false.into()),
                 explore_mode: SiteExplorerExploreMode::LibRedfish,
+                bmc_event_subscriptions: false,
+                bmc_event_min_interval: std::time::Duration::from_secs(10),
             }
         );
 
@@ -3474,6 +3818,7 @@ mod tests {
                     processor_log_interval: std::time::Duration::from_secs(60),
                     metric_emission_interval: std::time::Duration::from_secs(60),
                     metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                    priority_lanes: StateControllerPriorityLanesConfig::default(),
                 },
                 dpu_wait_time: Duration::minutes(7),
                 power_down_wait: Duration::seconds(17),
@@ -3481,6 +3826,8 @@ mod tests {
                 dpu_up_threshold: Duration::minutes(77),
                 scout_reporting_timeout: Duration::minutes(20),
                 uefi_boot_wait: Duration::minutes(5),
+                state_slas: vec![],
+                builtin_state_sla_alerts: false,
             }
         );
         assert_eq!(
@@ -3495,6 +3842,7 @@ mod tests {
                     processor_log_interval: std::time::Duration::from_secs(60),
                     metric_emission_interval: std::time::Duration::from_secs(60),
                     metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                    priority_lanes: StateControllerPriorityLanesConfig::default(),
                 },
             }
         );
@@ -3509,6 +3857,7 @@ mod tests {
                     processor_log_interval: std::time::Duration::from_secs(60),
                     metric_emission_interval: std::time::Duration::from_secs(60),
                     metric_hold_time: std::time::Duration::from_secs(5 * 60),
+                    priority_lanes: StateControllerPriorityLanesConfig::default(),
                 },
             }
         );
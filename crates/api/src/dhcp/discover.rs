@@ -19,17 +19,21 @@ use std::str::FromStr;
 
 use ::rpc::forge as rpc;
 use carbide_network::ip::{IdentifyAddressFamily, IpAddressFamily};
+use carbide_utils::models::arch::CpuArchitecture;
+use carbide_uuid::machine::{MachineId, MachineInterfaceId};
 use db::dhcp_entry::DhcpEntry;
 use db::{self, expected_machine, machine_interface};
 use mac_address::MacAddress;
 use model::dpa_interface::DpaInterface;
 use model::expected_machine::ExpectedHostNic;
+use model::machine::machine_search_config::MachineSearchConfig;
 use model::network_segment::AllocationStrategy;
 use sqlx::PgConnection;
 use tonic::{Request, Response};
 
-use crate::CarbideError;
 use crate::api::Api;
+use crate::cfg::file::BootMethod;
+use crate::{CarbideError, http_boot};
 
 // MTU for both the underlay and overlay networks on
 // the E/W Fabric
@@ -161,6 +165,36 @@ async fn handle_dhcp_from_dpa(
     handle_underlay_from_dpa(txn, &mut dpa_if, macaddr, relay_address).await
 }
 
+/// Returns the boot URL for a UEFI HTTP Boot client whose host is configured
+/// for [`BootMethod::HttpBoot`]. `None` leaves the DHCP server on its default
+/// iPXE boot file.
+async fn http_boot_url(
+    api: &Api,
+    txn: &mut PgConnection,
+    machine_id: Option<&MachineId>,
+    interface_id: MachineInterfaceId,
+    arch: CpuArchitecture,
+) -> Result<Option<String>, CarbideError> {
+    let config = &api.runtime_config.http_boot;
+    if config.base_url.is_none() {
+        return Ok(None);
+    }
+
+    let hw_sku = match machine_id {
+        Some(machine_id) => {
+            db::machine::find_one(&mut *txn, machine_id, MachineSearchConfig::default())
+                .await?
+                .and_then(|machine| machine.hw_sku)
+        }
+        None => None,
+    };
+
+    if http_boot::boot_method(config, machine_id, hw_sku.as_deref()) != BootMethod::HttpBoot {
+        return Ok(None);
+    }
+    Ok(http_boot::boot_url(config, arch, interface_id))
+}
+
 pub async fn discover_dhcp(
     api: &Api,
     request: Request<rpc::DhcpDiscovery>,
@@ -334,6 +368,10 @@ pub async fn discover_dhcp(
         }
     }
 
+    let http_boot_arch = vendor_string
+        .as_deref()
+        .and_then(http_boot::http_client_arch);
+
     // Save vendor string, this is allowed to fail due to dhcp happening more than once on the same machine/vendor string
     if let Some(vendor) = vendor_string {
         let res = db::dhcp_entry::persist(
@@ -358,7 +396,7 @@ pub async fn discover_dhcp(
 
     let mut txn = api.txn_begin().await?;
 
-    let record: rpc::DhcpRecord = db::dhcp_record::find_by_mac_address(
+    let mut record: rpc::DhcpRecord = db::dhcp_record::find_by_mac_address(
         &mut txn,
         &parsed_mac,
         &machine_interface.segment_id,
@@ -367,6 +405,17 @@ pub async fn discover_dhcp(
     .await?
     .into();
 
+    if let Some(arch) = http_boot_arch {
+        record.booturl = http_boot_url(
+            api,
+            &mut txn,
+            machine_interface.machine_id.as_ref(),
+            machine_interface.id,
+            arch,
+        )
+        .await?;
+    }
+
     txn.commit().await?;
     Ok(Response::new(record))
 }
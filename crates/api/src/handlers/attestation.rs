@@ -189,6 +189,22 @@ pub(crate) async fn attest_quote(
                 ),
             })?;
 
+    // If scout sent the binary event log, keep it with the report so
+    // it can be replayed and diffed later on, and log what changed. If
+    // no bundle matched, measurement policies may still approve it.
+    if let Some(event_log) = request.event_log.as_deref()
+        && ::measured_boot::event_log::looks_like_event_log(event_log)
+    {
+        db::measured_boot::report::set_event_log(&mut txn, report.report_id, event_log).await?;
+        let analyzed =
+            db::measured_boot::report::from_id_with_event_log(&mut txn, report.report_id, None)
+                .await?;
+        if let Some(analysis) = analyzed.event_log.as_ref() {
+            crate::attestation::log_event_log_analysis(&machine_id, analysis);
+        }
+        db::measured_boot::policy::maybe_approve_report(&mut txn, report.report_id).await?;
+    }
+
     // if the attestation was successful and enabled, we can now vend the certs
     // - get attestation result
     // - if enabled and not successful, send response without certs
@@ -15,7 +15,8 @@
  * limitations under the License.
  */
 use ::rpc::protos;
-use db::dns::resource_record;
+use carbide_uuid::vpc::VpcId;
+use db::dns::{resource_record, tenant_record};
 use dns_record::constants::*;
 use dns_record::{DnsResourceRecordReply, DnsResourceRecordType};
 use tonic::{Request, Response, Status};
@@ -60,10 +61,17 @@ async fn lookup_soa_record(
 }
 
 /// Returns ALL record types (A, AAAA, CNAME, etc.) - PowerDNS filters to requested type
-async fn lookup_records_by_qname(
-    txn: impl DbReader<'_>,
+///
+/// Records tenants created in their VPC domains are only included for queries
+/// carbide-dns received on the resolver listener of their VPC.
+async fn lookup_records_by_qname<DB>(
+    txn: &mut DB,
     query_name: &str,
-) -> Result<Vec<DnsResourceRecordReply>, tonic::Status> {
+    vpc_id: Option<VpcId>,
+) -> Result<Vec<DnsResourceRecordReply>, tonic::Status>
+where
+    for<'db> &'db mut DB: DbReader<'db>,
+{
     tracing::debug!("Looking up records for {}", query_name);
 
     // dns_records view expects trailing dots (FQDN format)
@@ -73,7 +81,7 @@ async fn lookup_records_by_qname(
         query_name.to_string()
     };
 
-    let result = resource_record::find_record(txn, &qname_with_dot)
+    let mut result = resource_record::find_record(&mut *txn, &qname_with_dot)
         .await
         .map_err(CarbideError::from)?
         .into_iter()
@@ -83,6 +91,19 @@ async fn lookup_records_by_qname(
         })
         .collect::<Vec<_>>();
 
+    if let Some(vpc_id) = vpc_id {
+        let tenant_records =
+            tenant_record::find_for_vpc(txn, &qname_with_dot.to_lowercase(), vpc_id)
+                .await
+                .map_err(CarbideError::from)?
+                .into_iter()
+                .map(|db_record| {
+                    let model_record: model::dns::ResourceRecord = db_record.into();
+                    DnsResourceRecordReply::from(model_record)
+                });
+        result.extend(tenant_records);
+    }
+
     Ok(result)
 }
 
@@ -116,6 +137,7 @@ async fn lookup_records_by_qname(
 async fn lookup_any_record<DB>(
     txn: &mut DB,
     query_name: &str,
+    vpc_id: Option<VpcId>,
 ) -> Result<Vec<DnsResourceRecordReply>, Status>
 where
     for<'db> &'db mut DB: DbReader<'db>,
@@ -126,7 +148,7 @@ where
 
     // 1. Look up all matching records from dns_records view
     //    (Returns A, AAAA, CNAME, etc. - PowerDNS will filter if needed)
-    let records = lookup_records_by_qname(&mut *txn, query_name).await?;
+    let records = lookup_records_by_qname(&mut *txn, query_name, vpc_id).await?;
     dns_records.extend(records);
 
     // 2. If this query is for a domain we're authoritative for, also include SOA
@@ -246,15 +268,237 @@ pub async fn get_all_domain_metadata(
         id: metadata_request.domain.clone(),
     })?;
 
-    let proto_metadata = domain
-        .metadata
-        .as_ref()
-        .map(|m| protos::dns::Metadata::from(m.clone()));
+    let metadata = match domain.metadata.clone() {
+        Some(metadata) => metadata,
+        None => {
+            db::dns::domain_metadata::metadata_for_domain(&api.database_connection, &domain.name)
+                .await?
+                .into()
+        }
+    };
 
     Ok(Response::new(protos::dns::DomainMetadataResponse {
-        result: proto_metadata,
+        result: Some(protos::dns::Metadata::from(metadata)),
+    }))
+}
+
+async fn find_domain(
+    txn: impl DbReader<'_>,
+    name: &str,
+) -> Result<model::dns::Domain, CarbideError> {
+    let domain_name = db::dns::normalize_domain(name);
+    db::dns::domain::find_by_name(txn, &domain_name)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| CarbideError::NotFoundError {
+            kind: "domain",
+            id: name.to_string(),
+        })
+}
+
+pub async fn get_domain_info(
+    api: &Api,
+    request: Request<protos::dns::GetDomainInfoRequest>,
+) -> Result<Response<protos::dns::DomainInfo>, Status> {
+    log_request_data(&request);
+
+    let domain = find_domain(&api.database_connection, &request.into_inner().name).await?;
+
+    Ok(Response::new(model::dns::DomainInfo::from(domain).into()))
+}
+
+/// Returns the SOA of a domain followed by all of its records, which is what
+/// PowerDNS needs to answer AXFR. IXFR requests are answered with the same
+/// full transfer.
+pub async fn get_all_records_for_domain(
+    api: &Api,
+    request: Request<protos::dns::GetAllRecordsForDomainRequest>,
+) -> Result<Response<protos::dns::GetAllRecordsForDomainResponse>, Status> {
+    log_request_data(&request);
+
+    let mut txn = api.db_reader();
+    let domain = find_domain(&mut txn, &request.into_inner().name).await?;
+
+    let mut records = vec![lookup_soa_record(&mut txn, &format!("{}.", domain.name)).await?];
+    records.extend(
+        resource_record::get_all_records(&mut txn, &domain.name)
+            .await
+            .map_err(CarbideError::from)?
+            .into_iter()
+            .map(|db_record| {
+                let model_record: model::dns::ResourceRecord = db_record.into();
+                DnsResourceRecordReply::from(model_record)
+            }),
+    );
+
+    Ok(Response::new(protos::dns::GetAllRecordsForDomainResponse {
+        result: records.into_iter().map(Into::into).collect(),
     }))
 }
+
+/// Checks that an RFC 2136 update may change `qname`/`qtype` in `domain` and
+/// returns the normalized owner name, or `None` if the change should be skipped.
+async fn dns_update_target(
+    txn: &mut sqlx::PgConnection,
+    domain: &model::dns::Domain,
+    qname: &str,
+    qtype: &str,
+) -> Result<Option<String>, CarbideError> {
+    let qname = format!("{}.", db::dns::normalize_domain(qname));
+    let in_domain =
+        qname == format!("{}.", domain.name) || qname.ends_with(&format!(".{}.", domain.name));
+    if !in_domain {
+        return Err(CarbideError::InvalidArgument(format!(
+            "{qname} is not in domain {}",
+            domain.name
+        )));
+    }
+
+    match DnsResourceRecordType::try_from(qtype) {
+        // Carbide owns the SOA and bumps its serial once per update
+        Ok(DnsResourceRecordType::SOA) => return Ok(None),
+        Ok(DnsResourceRecordType::ANY) | Err(_) => {
+            return Err(CarbideError::InvalidArgument(format!(
+                "unsupported record type {qtype} in DNS update"
+            )));
+        }
+        Ok(_) => {}
+    }
+
+    if resource_record::is_derived_record(&mut *txn, &qname, qtype).await? {
+        return Err(CarbideError::InvalidArgument(format!(
+            "{qtype} records of {qname} are managed by carbide and can't be updated"
+        )));
+    }
+
+    Ok(Some(qname))
+}
+
+fn update_record(
+    qname: &str,
+    record: protos::dns::DnsResourceRecord,
+) -> model::dns::ResourceRecord {
+    model::dns::ResourceRecord {
+        q_type: record.qtype,
+        q_name: qname.to_string(),
+        ttl: record.ttl,
+        content: record.content,
+        domain_id: None,
+    }
+}
+
+/// Applies the changes of an RFC 2136 dynamic update that PowerDNS already
+/// authenticated, and bumps the SOA serial of the domain once.
+pub async fn update_dns_records(
+    api: &Api,
+    request: Request<protos::dns::UpdateDnsRecordsRequest>,
+) -> Result<Response<protos::dns::UpdateDnsRecordsResponse>, Status> {
+    use protos::dns::dns_record_change::Change;
+
+    log_request_data(&request);
+
+    let req = request.into_inner();
+    let mut txn = api.txn_begin().await?;
+
+    let mut domain = find_domain(&mut txn, &req.domain).await?;
+    let metadata: model::dns::DomainMetadata =
+        db::dns::domain_metadata::metadata_for_domain(&mut txn, &domain.name)
+            .await?
+            .into();
+    if !metadata.allows_dnsupdate() {
+        return Err(CarbideError::InvalidArgument(format!(
+            "dynamic updates are not enabled for domain {}",
+            domain.name
+        ))
+        .into());
+    }
+
+    for change in req.changes {
+        match change
+            .change
+            .ok_or(CarbideError::MissingArgument("change"))?
+        {
+            Change::Replace(set) => {
+                let Some(qname) =
+                    dns_update_target(&mut txn, &domain, &set.qname, &set.qtype).await?
+                else {
+                    continue;
+                };
+                if let Some(record) = set.records.iter().find(|r| r.qtype != set.qtype) {
+                    return Err(CarbideError::InvalidArgument(format!(
+                        "{} record in {} record set of {qname}",
+                        record.qtype, set.qtype
+                    ))
+                    .into());
+                }
+                let records = set
+                    .records
+                    .into_iter()
+                    .map(|r| update_record(&qname, r))
+                    .collect::<Vec<_>>();
+                resource_record::replace_record_set(
+                    &mut txn, domain.id, &qname, &set.qtype, &records,
+                )
+                .await?;
+            }
+            Change::Add(record) => {
+                let Some(qname) =
+                    dns_update_target(&mut txn, &domain, &record.qname, &record.qtype).await?
+                else {
+                    continue;
+                };
+                resource_record::add_record(&mut txn, domain.id, &update_record(&qname, record))
+                    .await?;
+            }
+        }
+    }
+
+    domain.ensure_soa_and_increment();
+    let domain = db::dns::domain::update(&mut domain, &mut txn).await?;
+
+    txn.commit().await?;
+
+    let serial = domain.soa.map(|soa| soa.0.serial).unwrap_or_default();
+    tracing::info!(domain = %domain.name, serial, "Applied DNS update");
+
+    Ok(Response::new(protos::dns::UpdateDnsRecordsResponse {
+        serial,
+    }))
+}
+
+pub async fn get_updated_primary_domains(
+    api: &Api,
+    request: Request<protos::dns::GetUpdatedPrimaryDomainsRequest>,
+) -> Result<Response<protos::dns::GetAllDomainsResponse>, Status> {
+    log_request_data(&request);
+
+    let domains = db::dns::domain::find_updated_primaries(&api.database_connection).await?;
+
+    Ok(Response::new(protos::dns::GetAllDomainsResponse {
+        result: domains
+            .into_iter()
+            .map(model::dns::DomainInfo::from)
+            .map(protos::dns::DomainInfo::from)
+            .collect(),
+    }))
+}
+
+pub async fn set_domain_notified(
+    api: &Api,
+    request: Request<protos::dns::SetDomainNotifiedRequest>,
+) -> Result<Response<protos::dns::SetDomainNotifiedResponse>, Status> {
+    log_request_data(&request);
+
+    let req = request.into_inner();
+    let id = req.id.ok_or(CarbideError::MissingArgument("id"))?;
+
+    let mut txn = api.txn_begin().await?;
+    db::dns::domain::set_notified_serial(&mut txn, id, req.serial).await?;
+    txn.commit().await?;
+
+    Ok(Response::new(protos::dns::SetDomainNotifiedResponse {}))
+}
 pub async fn lookup_record(
     api: &Api,
     request: Request<protos::dns::DnsResourceRecordLookupRequest>,
@@ -271,6 +515,11 @@ pub async fn lookup_record(
         "Processing DNS lookup request"
     );
 
+    // Set by carbide-dns, which is the only caller allowed to look up records,
+    // from the listener the query arrived on. The client address and EDNS
+    // client subnet are not used since clients and resolvers can forge them.
+    let vpc_id = lookup_request.vpc_id;
+
     let rrtype = DnsResourceRecordType::try_from(lookup_request.qtype)
         .map_err(|e| CarbideError::InvalidArgument(format!("Invalid qtype supplied: {}", e)))?;
 
@@ -284,7 +533,7 @@ pub async fn lookup_record(
         DnsResourceRecordType::ANY => {
             // Return ALL records for this qname
             let normalized = db::dns::normalize_domain(&qname);
-            lookup_any_record(&mut api.db_reader(), &normalized).await?
+            lookup_any_record(&mut api.db_reader(), &normalized, vpc_id).await?
         }
         DnsResourceRecordType::SOA => {
             // SOA queries: only return SOA record for the domain
@@ -294,7 +543,7 @@ pub async fn lookup_record(
         }
         _ => {
             // For all other types (A, AAAA, MX, CNAME, etc.):
-            lookup_records_by_qname(&api.database_connection, &qname).await?
+            lookup_records_by_qname(&mut api.db_reader(), &qname, vpc_id).await?
         }
     };
 
@@ -410,6 +659,7 @@ pub async fn lookup_record_legacy_compat(
         local: None,
         remote: None,
         real_remote: None,
+        vpc_id: None,
     };
 
     // Call the new handler
@@ -20,7 +20,7 @@ use ::rpc::protos::dns::{
 };
 use db::dns::domain;
 use db::{self, ObjectColumnFilter};
-use model::dns::NewDomain;
+use model::dns::{DomainMetadata, NewDomain};
 use tonic::{Request, Response, Status};
 
 use crate::CarbideError;
@@ -73,7 +73,13 @@ pub(crate) async fn update(
 
     domain.increment_serial();
 
-    let updated_domain = domain::update(&mut domain, &mut txn).await?;
+    let mut updated_domain = domain::update(&mut domain, &mut txn).await?;
+
+    if let Some(metadata) = domain_proto.metadata {
+        let metadata = DomainMetadata::from(metadata);
+        db::dns::domain_metadata::update_for_domain(&mut txn, updated_domain.id, &metadata).await?;
+        updated_domain.metadata = Some(metadata);
+    }
 
     txn.commit().await?;
 
@@ -43,10 +43,10 @@ use model::network_segment::NetworkSegmentSearchConfig;
 use tonic::{Request, Response, Status};
 
 use crate::api::{Api, log_machine_id, log_request_data};
-use crate::cfg::file::VpcIsolationBehaviorType;
+use crate::cfg::file::{BootMethod, VpcIsolationBehaviorType};
 use crate::handlers::extension_service;
 use crate::handlers::utils::convert_and_log_machine_id;
-use crate::{CarbideError, cfg, ethernet_virtualization};
+use crate::{CarbideError, cfg, ethernet_virtualization, http_boot};
 
 /// vxlan48 is special HBN single vxlan device. It handles networking between machines on the
 /// same subnet. It handles the encapsulation into VXLAN and VNI for cross-host comms.
@@ -193,7 +193,7 @@ pub(crate) async fn get_managed_host_network_config_inner(
         api.runtime_config.arm_pxe_boot_url_override.clone()
     };
 
-    let (admin_interface_rpc, host_interface_id) = ethernet_virtualization::admin_network(
+    let (mut admin_interface_rpc, host_interface_id) = ethernet_virtualization::admin_network(
         &mut txn,
         &snapshot.host_snapshot.id,
         &dpu_snapshot.id,
@@ -203,6 +203,24 @@ pub(crate) async fn get_managed_host_network_config_inner(
     )
     .await?;
 
+    // Hosts configured for HTTP Boot get their signed boot loader URL from the
+    // DPU's DHCP server instead of the iPXE one.
+    if let Some(arch) = snapshot
+        .host_snapshot
+        .hardware_info
+        .as_ref()
+        .map(|h| h.machine_type)
+        && http_boot::boot_method(
+            &api.runtime_config.http_boot,
+            Some(&snapshot.host_snapshot.id),
+            snapshot.host_snapshot.hw_sku.as_deref(),
+        ) == BootMethod::HttpBoot
+        && let Some(url) =
+            http_boot::boot_url(&api.runtime_config.http_boot, arch, host_interface_id)
+    {
+        admin_interface_rpc.booturl = Some(url);
+    }
+
     // If admin network is in use and is fnn, use admin network's vpc_vni.
     let mut vpc_vni = if use_admin_network && admin_interface_rpc.vpc_vni != 0 {
         Some(admin_interface_rpc.vpc_vni)
@@ -28,6 +28,7 @@ use itertools::Itertools;
 use libredfish::SystemPowerControl;
 use model::hardware_info::MachineNvLinkInfo;
 use model::machine::machine_search_config::MachineSearchConfig;
+use model::machine::state_timeline::build_state_timeline;
 use model::machine::{LoadSnapshotOptions, Machine, ManagedHostState, ManagedHostStateSnapshot};
 use model::metadata::Metadata;
 use tonic::{Request, Response, Status};
@@ -36,6 +37,8 @@ use crate::CarbideError;
 use crate::api::{Api, log_machine_id, log_request_data};
 use crate::auth::AuthContext;
 use crate::handlers::utils::convert_and_log_machine_id;
+use crate::state_controller::dry_run::DryRunOutcome;
+use crate::state_controller::machine::dry_run::MachineDryRunReport;
 
 pub(crate) async fn find_machine_ids(
     api: &Api,
@@ -113,11 +116,7 @@ pub(crate) async fn find_machines_by_ids(
 
     txn.commit().await?;
 
-    let sla_config = model::machine::slas::MachineSlaConfig::new(
-        api.runtime_config
-            .machine_state_controller
-            .failure_retry_time,
-    );
+    let sla_config = api.runtime_config.machine_state_controller.sla_config();
     Ok(Response::new(snapshot_map_to_rpc_machines(
         snapshots,
         &sla_config,
@@ -169,6 +168,133 @@ pub(crate) async fn find_machine_state_histories(
     Ok(Response::new(response))
 }
 
+pub(crate) async fn get_managed_host_state_timeline(
+    api: &Api,
+    request: Request<rpc::ManagedHostStateTimelineRequest>,
+) -> Result<Response<rpc::ManagedHostStateTimeline>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+
+    let machine_id = convert_and_log_machine_id(request.machine_id.as_ref())?;
+    if machine_id.machine_type().is_dpu() {
+        return Err(CarbideError::InvalidArgument(
+            "state timelines are only available for hosts".to_string(),
+        )
+        .into());
+    }
+
+    let mut txn = api.txn_begin().await?;
+
+    let records = db::state_history::for_object(
+        &mut txn,
+        db::state_history::StateHistoryTableId::Machine,
+        &machine_id,
+    )
+    .await?;
+    let breaches: HashMap<String, chrono::DateTime<chrono::Utc>> =
+        db::machine_state_sla_breach::find_by_machine_id(&mut txn, &machine_id)
+            .await?
+            .into_iter()
+            .map(|breach| (breach.state_version.version_string(), breach.detected_at))
+            .collect();
+
+    txn.commit().await?;
+
+    if records.is_empty() {
+        return Err(CarbideError::NotFoundError {
+            kind: "machine",
+            id: machine_id.to_string(),
+        }
+        .into());
+    }
+
+    let sla_config = api.runtime_config.machine_state_controller.sla_config();
+    let entries = build_state_timeline(records, &breaches, &sla_config, chrono::Utc::now());
+
+    Ok(Response::new(rpc::ManagedHostStateTimeline {
+        entries: entries.into_iter().map(Into::into).collect(),
+    }))
+}
+
+pub(crate) async fn explain_machine_next_step(
+    api: &Api,
+    request: Request<rpc::ExplainMachineNextStepRequest>,
+) -> Result<Response<rpc::ExplainMachineNextStepResponse>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+
+    let machine_id = convert_and_log_machine_id(request.machine_id.as_ref())?;
+    if machine_id.machine_type().is_dpu() {
+        return Err(CarbideError::InvalidArgument(
+            "the next step can only be explained for hosts".to_string(),
+        )
+        .into());
+    }
+
+    let dry_runner = api.machine_state_dry_runner.get().ok_or_else(|| {
+        CarbideError::UnavailableError(
+            "the machine state controller is not running on this instance".to_string(),
+        )
+    })?;
+
+    let MachineDryRunReport {
+        report,
+        side_effects,
+    } = dry_runner
+        .explain_next_step(&machine_id)
+        .await
+        .map_err(|e| CarbideError::Internal {
+            message: format!("dry run of the state handler failed: {e}"),
+        })?
+        .ok_or_else(|| CarbideError::NotFoundError {
+            kind: "machine",
+            id: machine_id.to_string(),
+        })?;
+
+    let to_json = |state: &ManagedHostState| {
+        serde_json::to_string(state).map_err(|e| CarbideError::Internal {
+            message: format!("failed to serialize state: {e}"),
+        })
+    };
+
+    let mut response = rpc::ExplainMachineNextStepResponse {
+        current_state: to_json(&report.current_state.value)?,
+        current_version: report.current_state.version.version_string(),
+        time_in_state_above_sla: report.time_in_state_above_sla,
+        source_ref: report.source_ref.map(|l| l.to_string()),
+        db_writes: report.db_writes,
+        transaction_discarded: report.transaction_discarded,
+        side_effects: side_effects
+            .into_iter()
+            .map(|effect| rpc::MachineNextStepSideEffect {
+                target: effect.target.to_string(),
+                host: effect.host,
+                operation: effect.operation.to_string(),
+                details: effect.details,
+            })
+            .collect(),
+        ..Default::default()
+    };
+    match report.outcome {
+        DryRunOutcome::Transition { next_state } => {
+            response.set_kind(rpc::MachineNextStepKind::Transition);
+            response.next_state = Some(to_json(&next_state)?);
+        }
+        DryRunOutcome::Wait { reason } => {
+            response.set_kind(rpc::MachineNextStepKind::Wait);
+            response.wait_reason = Some(reason);
+        }
+        DryRunOutcome::DoNothing => response.set_kind(rpc::MachineNextStepKind::DoNothing),
+        DryRunOutcome::Deleted => response.set_kind(rpc::MachineNextStepKind::Deleted),
+        DryRunOutcome::Error { error } => {
+            response.set_kind(rpc::MachineNextStepKind::Error);
+            response.error = Some(error);
+        }
+    }
+
+    Ok(Response::new(response))
+}
+
 pub(crate) async fn find_machine_health_histories(
     api: &Api,
     request: Request<rpc::MachineHealthHistoriesRequest>,
@@ -0,0 +1,88 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::forge as rpc;
+use model::maintenance_window::NewMaintenanceWindow;
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data};
+use crate::auth::AuthContext;
+
+pub(crate) async fn create_maintenance_window(
+    api: &Api,
+    request: Request<rpc::CreateMaintenanceWindowRequest>,
+) -> Result<Response<rpc::MaintenanceWindow>, Status> {
+    log_request_data(&request);
+
+    let created_by = request
+        .extensions()
+        .get::<AuthContext>()
+        .and_then(|ctx| ctx.get_external_user_name())
+        .map(String::from);
+
+    let new_window = NewMaintenanceWindow::try_from((request.into_inner(), created_by))
+        .map_err(CarbideError::from)?;
+
+    let mut txn = api.txn_begin().await?;
+    let window = db::maintenance_window::create(&mut txn, &new_window).await?;
+    txn.commit().await?;
+
+    tracing::info!(
+        maintenance_window_id = %window.id,
+        "Created maintenance window from {} to {}",
+        window.start_time,
+        window.end_time
+    );
+
+    Ok(Response::new(window.into()))
+}
+
+pub(crate) async fn find_maintenance_windows(
+    api: &Api,
+    request: Request<rpc::FindMaintenanceWindowsRequest>,
+) -> Result<Response<rpc::MaintenanceWindowList>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+
+    let mut txn = api.txn_begin().await?;
+    let windows = db::maintenance_window::find(&mut txn, request.include_inactive).await?;
+    txn.commit().await?;
+
+    Ok(Response::new(rpc::MaintenanceWindowList {
+        windows: windows.into_iter().map(Into::into).collect(),
+    }))
+}
+
+pub(crate) async fn cancel_maintenance_window(
+    api: &Api,
+    request: Request<rpc::CancelMaintenanceWindowRequest>,
+) -> Result<Response<rpc::MaintenanceWindow>, Status> {
+    log_request_data(&request);
+    let id = request
+        .into_inner()
+        .id
+        .ok_or(CarbideError::MissingArgument("id"))?;
+
+    let mut txn = api.txn_begin().await?;
+    let window = db::maintenance_window::cancel(&mut txn, &id).await?;
+    txn.commit().await?;
+
+    tracing::info!(maintenance_window_id = %window.id, "Cancelled maintenance window");
+
+    Ok(Response::new(window.into()))
+}
@@ -23,7 +23,7 @@ use sqlx::PgConnection;
 use tonic::{Request, Response, Status};
 
 use crate::api::Api;
-use crate::measured_boot::rpc::{bundle, journal, machine, profile, report, site};
+use crate::measured_boot::rpc::{bundle, journal, machine, policy, profile, report, site};
 use crate::{CarbideError, attestation as attest};
 
 pub(crate) async fn create_attest_key_bind_challenge(
@@ -292,6 +292,33 @@ pub async fn find_closest_bundle_match(
         .map(Response::new)
 }
 
+pub async fn create_policy(
+    api: &Api,
+    request: Request<pb::CreateMeasurementPolicyRequest>,
+) -> Result<Response<pb::CreateMeasurementPolicyResponse>, Status> {
+    policy::handle_create_measurement_policy(api, request.into_inner())
+        .await
+        .map(Response::new)
+}
+
+pub async fn delete_policy(
+    api: &Api,
+    request: Request<pb::DeleteMeasurementPolicyRequest>,
+) -> Result<Response<pb::DeleteMeasurementPolicyResponse>, Status> {
+    policy::handle_delete_measurement_policy(api, request.into_inner())
+        .await
+        .map(Response::new)
+}
+
+pub async fn show_policies(
+    api: &Api,
+    request: Request<pb::ShowMeasurementPoliciesRequest>,
+) -> Result<Response<pb::ShowMeasurementPoliciesResponse>, Status> {
+    policy::handle_show_measurement_policies(api, request.into_inner())
+        .await
+        .map(Response::new)
+}
+
 pub async fn delete_journal(
     api: &Api,
     request: Request<pb::DeleteMeasurementJournalRequest>,
@@ -53,6 +53,7 @@ pub mod machine_interface_address;
 pub mod machine_quarantine;
 pub mod machine_scout;
 pub mod machine_validation;
+pub mod maintenance_window;
 pub mod managed_host;
 pub mod measured_boot;
 pub mod mlx_admin;
@@ -70,11 +71,14 @@ pub mod redfish;
 pub mod resource_pool;
 pub mod route_server;
 pub mod scout_stream;
+pub mod sensor_history;
 pub mod site_explorer;
 pub mod sku;
+pub mod state_watch;
 pub mod switch;
 mod switch_artifacts;
 pub mod tenant;
+pub mod tenant_dns_record;
 pub mod tenant_identity_config;
 pub mod tenant_keyset;
 pub mod tpm_ca;
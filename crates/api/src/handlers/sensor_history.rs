@@ -0,0 +1,108 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::forge as rpc;
+use chrono::Utc;
+use model::sensor_history::{RAW_RETENTION, SensorHistoryResolution, SensorSample};
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data};
+use crate::handlers::utils::convert_and_log_machine_id;
+
+/// Upper bound for the amount of readings which can be recorded in a single request
+const MAX_SAMPLES_PER_REQUEST: usize = 20_000;
+
+pub(crate) async fn record_machine_sensor_samples(
+    api: &Api,
+    request: Request<rpc::RecordMachineSensorSamplesRequest>,
+) -> Result<Response<()>, Status> {
+    // The samples are not logged, since requests carry a lot of them
+    let request = request.into_inner();
+    let machine_id = convert_and_log_machine_id(request.machine_id.as_ref())?;
+
+    if request.samples.len() > MAX_SAMPLES_PER_REQUEST {
+        return Err(CarbideError::InvalidArgument(format!(
+            "no more than {MAX_SAMPLES_PER_REQUEST} samples can be recorded at once"
+        ))
+        .into());
+    }
+    let samples = request
+        .samples
+        .into_iter()
+        .map(SensorSample::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(CarbideError::from)?;
+
+    let mut txn = api.txn_begin().await?;
+    db::sensor_history::record(&mut txn, &machine_id, &samples).await?;
+    txn.commit().await?;
+
+    Ok(Response::new(()))
+}
+
+pub(crate) async fn find_machine_sensor_history(
+    api: &Api,
+    request: Request<rpc::MachineSensorHistoryRequest>,
+) -> Result<Response<rpc::MachineSensorHistory>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+    let machine_id = convert_and_log_machine_id(request.machine_id.as_ref())?;
+
+    let now = Utc::now();
+    let end_time = request
+        .end_time
+        .map(chrono::DateTime::<chrono::Utc>::try_from)
+        .transpose()
+        .map_err(|_| CarbideError::InvalidArgument("Invalid end_time timestamp".to_string()))?
+        .unwrap_or(now);
+    let start_time = request
+        .start_time
+        .map(chrono::DateTime::<chrono::Utc>::try_from)
+        .transpose()
+        .map_err(|_| CarbideError::InvalidArgument("Invalid start_time timestamp".to_string()))?
+        .unwrap_or(end_time - RAW_RETENTION);
+    if start_time > end_time {
+        return Err(CarbideError::InvalidArgument(
+            "start_time must not be after end_time".to_string(),
+        )
+        .into());
+    }
+
+    let resolution = match request.resolution() {
+        rpc::SensorHistoryResolution::Auto => SensorHistoryResolution::for_range(start_time, now),
+        rpc::SensorHistoryResolution::Raw => SensorHistoryResolution::Raw,
+        rpc::SensorHistoryResolution::Rollup5m => SensorHistoryResolution::Rollup5m,
+    };
+
+    let mut txn = api.txn_begin().await?;
+    let series = db::sensor_history::find(
+        &mut txn,
+        &machine_id,
+        &request.sensors,
+        start_time,
+        end_time,
+        resolution,
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(Response::new(rpc::MachineSensorHistory {
+        resolution: rpc::SensorHistoryResolution::from(resolution).into(),
+        series: series.into_iter().map(Into::into).collect(),
+    }))
+}
@@ -0,0 +1,586 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Handlers for the `Watch*States` RPCs
+//!
+//! Each stream first replays the state history after `resume_from_cursor` (if
+//! requested) and then forwards the live transitions that are read from the
+//! state history tables by [`crate::state_change_watch::StateChangeWatchers`].
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use ::rpc::WatchStream;
+use ::rpc::forge as rpc;
+use carbide_uuid::instance::InstanceId;
+use carbide_uuid::machine::MachineId;
+use config_version::ConfigVersion;
+use db::ObjectColumnFilter;
+use db::state_history::{StateHistoryEntry, StateHistoryTableId};
+use model::machine::{InstanceState, ManagedHostState};
+use model::state_history::StateHistoryCursor;
+use serde::Serialize;
+use tokio::sync::{Mutex, broadcast, mpsc};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data};
+use crate::state_change_watch::StateChange;
+
+/// The maximum amount of state history records that are replayed when a stream is resumed.
+/// Clients which are further behind need to reload the full state of the objects.
+const MAX_REPLAYED_RECORDS: u32 = 10_000;
+
+/// The amount of messages that are buffered between the forwarding task and the gRPC stream
+const STREAM_BUFFER_SIZE: usize = 64;
+
+pub(crate) async fn watch_managed_host_states(
+    api: &Api,
+    request: Request<rpc::WatchManagedHostStatesRequest>,
+) -> Result<Response<WatchStream<rpc::ManagedHostStateChange>>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+    check_id_count(api, request.machine_ids.len())?;
+    let resume_from = parse_resume_cursor(request.resume_from_cursor.as_deref())?;
+
+    // Subscribe before loading the history, so that no transition can slip
+    // through between both steps
+    let receiver = api
+        .state_change_watchers
+        .subscribe_machines()
+        .await
+        .map_err(CarbideError::from)?;
+    let replayed = load_replay(
+        api,
+        StateHistoryTableId::Machine,
+        &request.machine_ids,
+        resume_from.as_ref(),
+    )
+    .await?;
+    let replayed_until = known_until(resume_from, &replayed);
+
+    let filter: HashSet<MachineId> = request.machine_ids.into_iter().collect();
+    let replayed = replayed
+        .into_iter()
+        .filter_map(|entry| {
+            let machine_id = parse_object_id::<MachineId>(&entry.object_id)?;
+            Some(rpc::ManagedHostStateChange {
+                machine_id: Some(machine_id),
+                state: Some(entry.record.into()),
+                replayed: true,
+                cursor: entry.cursor.to_string(),
+            })
+        })
+        .collect();
+
+    Ok(Response::new(spawn_watch_stream(
+        receiver,
+        replayed,
+        replayed_until,
+        move |change| {
+            let change =
+                filter_matches(&filter, &change.object_id).then(|| rpc::ManagedHostStateChange {
+                    machine_id: Some(change.object_id),
+                    state: Some(live_record(
+                        &change.new_state,
+                        change.new_version,
+                        change.timestamp,
+                    )),
+                    replayed: false,
+                    cursor: change.cursor.to_string(),
+                });
+            std::future::ready(change)
+        },
+    )))
+}
+
+pub(crate) async fn watch_instance_states(
+    api: &Api,
+    request: Request<rpc::WatchInstanceStatesRequest>,
+) -> Result<Response<WatchStream<rpc::InstanceStateChange>>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+    check_id_count(api, request.instance_ids.len())?;
+    let resume_from = parse_resume_cursor(request.resume_from_cursor.as_deref())?;
+
+    // Instances do not have a state machine of their own. Their state is part
+    // of the ManagedHostState of the Machine they are running on.
+    let mut resolver = InstanceResolver::new(api.database_connection.clone());
+    if !request.instance_ids.is_empty() {
+        resolver.restrict_to(api, &request.instance_ids).await?;
+    }
+    let machine_ids: Vec<MachineId> = resolver.instances_by_machine.keys().copied().collect();
+
+    let receiver = api
+        .state_change_watchers
+        .subscribe_machines()
+        .await
+        .map_err(CarbideError::from)?;
+    let replayed_entries = load_replay(
+        api,
+        StateHistoryTableId::Machine,
+        &machine_ids,
+        resume_from.as_ref(),
+    )
+    .await?;
+    let replayed_until = known_until(resume_from, &replayed_entries);
+
+    let mut replayed = Vec::with_capacity(replayed_entries.len());
+    for entry in replayed_entries {
+        let Some(machine_id) = parse_object_id::<MachineId>(&entry.object_id) else {
+            continue;
+        };
+        let Ok(state) = serde_json::from_str::<ManagedHostState>(&entry.record.state) else {
+            continue;
+        };
+        let Some(change) = resolver
+            .state_change(
+                machine_id,
+                &state,
+                entry.record.state_version,
+                entry.record.time,
+                &entry.cursor,
+                true,
+            )
+            .await
+        else {
+            continue;
+        };
+        replayed.push(change);
+    }
+
+    let resolver = Arc::new(Mutex::new(resolver));
+    Ok(Response::new(spawn_watch_stream(
+        receiver,
+        replayed,
+        replayed_until,
+        move |change| {
+            let resolver = resolver.clone();
+            async move {
+                resolver
+                    .lock()
+                    .await
+                    .state_change(
+                        change.object_id,
+                        &change.new_state,
+                        change.new_version,
+                        Some(change.timestamp),
+                        &change.cursor,
+                        false,
+                    )
+                    .await
+            }
+        },
+    )))
+}
+
+pub(crate) async fn watch_rack_states(
+    api: &Api,
+    request: Request<rpc::WatchRackStatesRequest>,
+) -> Result<Response<WatchStream<rpc::RackStateChange>>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+    check_id_count(api, request.rack_ids.len())?;
+    let resume_from = parse_resume_cursor(request.resume_from_cursor.as_deref())?;
+
+    let receiver = api
+        .state_change_watchers
+        .subscribe_racks()
+        .await
+        .map_err(CarbideError::from)?;
+    let replayed = load_replay(
+        api,
+        StateHistoryTableId::Rack,
+        &request.rack_ids,
+        resume_from.as_ref(),
+    )
+    .await?;
+    let replayed_until = known_until(resume_from, &replayed);
+
+    let filter: HashSet<_> = request.rack_ids.into_iter().collect();
+    let replayed = replayed
+        .into_iter()
+        .filter_map(|entry| {
+            let rack_id = parse_object_id(&entry.object_id)?;
+            Some(rpc::RackStateChange {
+                rack_id: Some(rack_id),
+                state: Some(entry.record.into()),
+                replayed: true,
+                cursor: entry.cursor.to_string(),
+            })
+        })
+        .collect();
+
+    Ok(Response::new(spawn_watch_stream(
+        receiver,
+        replayed,
+        replayed_until,
+        move |change| {
+            let change = filter_matches(&filter, &change.object_id).then(|| rpc::RackStateChange {
+                state: Some(live_record(
+                    &change.new_state,
+                    change.new_version,
+                    change.timestamp,
+                )),
+                rack_id: Some(change.object_id),
+                replayed: false,
+                cursor: change.cursor.to_string(),
+            });
+            std::future::ready(change)
+        },
+    )))
+}
+
+pub(crate) async fn watch_switch_states(
+    api: &Api,
+    request: Request<rpc::WatchSwitchStatesRequest>,
+) -> Result<Response<WatchStream<rpc::SwitchStateChange>>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+    check_id_count(api, request.switch_ids.len())?;
+    let resume_from = parse_resume_cursor(request.resume_from_cursor.as_deref())?;
+
+    let receiver = api
+        .state_change_watchers
+        .subscribe_switches()
+        .await
+        .map_err(CarbideError::from)?;
+    let replayed = load_replay(
+        api,
+        StateHistoryTableId::Switch,
+        &request.switch_ids,
+        resume_from.as_ref(),
+    )
+    .await?;
+    let replayed_until = known_until(resume_from, &replayed);
+
+    let filter: HashSet<_> = request.switch_ids.into_iter().collect();
+    let replayed = replayed
+        .into_iter()
+        .filter_map(|entry| {
+            let switch_id = parse_object_id(&entry.object_id)?;
+            Some(rpc::SwitchStateChange {
+                switch_id: Some(switch_id),
+                state: Some(entry.record.into()),
+                replayed: true,
+                cursor: entry.cursor.to_string(),
+            })
+        })
+        .collect();
+
+    Ok(Response::new(spawn_watch_stream(
+        receiver,
+        replayed,
+        replayed_until,
+        move |change| {
+            let change =
+                filter_matches(&filter, &change.object_id).then(|| rpc::SwitchStateChange {
+                    switch_id: Some(change.object_id),
+                    state: Some(live_record(
+                        &change.new_state,
+                        change.new_version,
+                        change.timestamp,
+                    )),
+                    replayed: false,
+                    cursor: change.cursor.to_string(),
+                });
+            std::future::ready(change)
+        },
+    )))
+}
+
+fn check_id_count(api: &Api, num_ids: usize) -> Result<(), CarbideError> {
+    let max_find_by_ids = api.runtime_config.max_find_by_ids as usize;
+    if num_ids > max_find_by_ids {
+        return Err(CarbideError::InvalidArgument(format!(
+            "no more than {max_find_by_ids} IDs can be accepted"
+        )));
+    }
+    Ok(())
+}
+
+fn parse_resume_cursor(cursor: Option<&str>) -> Result<Option<StateHistoryCursor>, CarbideError> {
+    cursor
+        .map(|cursor| {
+            cursor.parse::<StateHistoryCursor>().map_err(|e| {
+                CarbideError::InvalidArgument(format!("Invalid resume_from_cursor: {e}"))
+            })
+        })
+        .transpose()
+}
+
+fn parse_object_id<Id: FromStr>(object_id: &str) -> Option<Id> {
+    let id = Id::from_str(object_id).ok();
+    if id.is_none() {
+        tracing::warn!(
+            object_id,
+            "Skipping state history record with invalid object ID"
+        );
+    }
+    id
+}
+
+fn filter_matches<Id: std::hash::Hash + Eq>(filter: &HashSet<Id>, object_id: &Id) -> bool {
+    filter.is_empty() || filter.contains(object_id)
+}
+
+/// Loads all state history records which have been recorded after `resume_from`
+///
+/// Fails instead of returning a partial history if more than
+/// [`MAX_REPLAYED_RECORDS`] records would need to be replayed.
+async fn load_replay(
+    api: &Api,
+    table_id: StateHistoryTableId,
+    object_ids: &[impl std::fmt::Display],
+    resume_from: Option<&StateHistoryCursor>,
+) -> Result<Vec<StateHistoryEntry>, CarbideError> {
+    let Some(resume_from) = resume_from else {
+        return Ok(Vec::new());
+    };
+
+    let mut txn = api.txn_begin().await?;
+    let entries = db::state_history::find_after(
+        &mut txn,
+        table_id,
+        object_ids,
+        Some(resume_from),
+        MAX_REPLAYED_RECORDS + 1,
+    )
+    .await?;
+    txn.commit().await?;
+
+    if entries.len() > MAX_REPLAYED_RECORDS as usize {
+        return Err(CarbideError::FailedPrecondition(format!(
+            "More than {MAX_REPLAYED_RECORDS} state changes have been recorded after cursor {resume_from}. \
+            Reload the current state and watch without resume_from_cursor."
+        )));
+    }
+
+    Ok(entries)
+}
+
+/// The cursor up to which the client knows all state changes once `replayed`
+/// has been sent
+fn known_until(
+    resume_from: Option<StateHistoryCursor>,
+    replayed: &[StateHistoryEntry],
+) -> Option<StateHistoryCursor> {
+    replayed.last().map(|entry| entry.cursor).or(resume_from)
+}
+
+fn live_record<S: Serialize>(
+    state: &S,
+    version: ConfigVersion,
+    time: chrono::DateTime<chrono::Utc>,
+) -> rpc::StateHistoryRecord {
+    rpc::StateHistoryRecord {
+        state: serde_json::to_string(state).unwrap_or_default(),
+        version: version.version_string(),
+        time: Some(time.into()),
+    }
+}
+
+/// Maps the state transitions of Machines to the Instances running on them.
+///
+/// Instances are created before their Machine enters `Assigned`, and deleted
+/// in the transaction in which it leaves `Assigned`. The Instance of a Machine
+/// is therefore looked up once when the Machine is first seen in `Assigned`,
+/// and forgotten once it leaves `Assigned`.
+struct InstanceResolver {
+    pool: sqlx::PgPool,
+    /// Whether only the Instances in `instances_by_machine` are of interest
+    restricted: bool,
+    instances_by_machine: HashMap<MachineId, InstanceId>,
+}
+
+impl InstanceResolver {
+    fn new(pool: sqlx::PgPool) -> Self {
+        Self {
+            pool,
+            restricted: false,
+            instances_by_machine: HashMap::new(),
+        }
+    }
+
+    /// Only resolves the given Instances, which must exist already
+    async fn restrict_to(
+        &mut self,
+        api: &Api,
+        instance_ids: &[InstanceId],
+    ) -> Result<(), CarbideError> {
+        let instances = db::instance::find(
+            &api.database_connection,
+            ObjectColumnFilter::List(db::instance::IdColumn, instance_ids),
+        )
+        .await?;
+        if instances.is_empty() {
+            return Err(CarbideError::NotFoundError {
+                kind: "instance",
+                id: format!("{instance_ids:?}"),
+            });
+        }
+        self.restricted = true;
+        self.instances_by_machine = instances
+            .into_iter()
+            .map(|instance| (instance.machine_id, instance.id))
+            .collect();
+        Ok(())
+    }
+
+    /// Returns the Instance state change for a transition of `machine_id`
+    /// into `state`, if the Machine runs an Instance of interest
+    async fn state_change(
+        &mut self,
+        machine_id: MachineId,
+        state: &ManagedHostState,
+        version: ConfigVersion,
+        time: Option<chrono::DateTime<chrono::Utc>>,
+        cursor: &StateHistoryCursor,
+        replayed: bool,
+    ) -> Option<rpc::InstanceStateChange> {
+        let ManagedHostState::Assigned { instance_state } = state else {
+            self.instances_by_machine.remove(&machine_id);
+            return None;
+        };
+        let instance_id = self.resolve(&machine_id).await?;
+        Some(instance_state_change(
+            instance_id,
+            machine_id,
+            instance_state,
+            version,
+            time,
+            cursor,
+            replayed,
+        ))
+    }
+
+    async fn resolve(&mut self, machine_id: &MachineId) -> Option<InstanceId> {
+        if let Some(instance_id) = self.instances_by_machine.get(machine_id) {
+            return Some(*instance_id);
+        }
+        if self.restricted {
+            return None;
+        }
+
+        let mut conn = match self.pool.acquire().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                tracing::warn!(error = %e, %machine_id, "Failed to acquire database connection");
+                return None;
+            }
+        };
+        match db::instance::find_id_by_machine_id(&mut conn, machine_id).await {
+            Ok(instance_id) => {
+                if let Some(instance_id) = instance_id {
+                    self.instances_by_machine.insert(*machine_id, instance_id);
+                }
+                instance_id
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, %machine_id, "Failed to look up instance for machine");
+                None
+            }
+        }
+    }
+}
+
+fn instance_state_change(
+    instance_id: InstanceId,
+    machine_id: MachineId,
+    instance_state: &InstanceState,
+    version: ConfigVersion,
+    time: Option<chrono::DateTime<chrono::Utc>>,
+    cursor: &StateHistoryCursor,
+    replayed: bool,
+) -> rpc::InstanceStateChange {
+    rpc::InstanceStateChange {
+        instance_id: Some(instance_id),
+        machine_id: Some(machine_id),
+        state: Some(rpc::StateHistoryRecord {
+            state: serde_json::to_string(instance_state).unwrap_or_default(),
+            version: version.version_string(),
+            time: Some(time.unwrap_or_else(|| version.timestamp()).into()),
+        }),
+        replayed,
+        cursor: cursor.to_string(),
+    }
+}
+
+/// Spawns a task that sends the `replayed` messages, followed by all live
+/// changes that `convert` maps to a message.
+///
+/// Live changes at or before `replayed_until` are already known to the client
+/// and are skipped. If the client can not keep up with the rate of changes, the stream
+/// is terminated with a `DATA_LOSS` status.
+fn spawn_watch_stream<Id, S, T, F, Fut>(
+    mut receiver: broadcast::Receiver<StateChange<Id, S>>,
+    replayed: Vec<T>,
+    replayed_until: Option<StateHistoryCursor>,
+    mut convert: F,
+) -> WatchStream<T>
+where
+    Id: Clone + Send + 'static,
+    S: Clone + Send + 'static,
+    T: Send + 'static,
+    F: FnMut(StateChange<Id, S>) -> Fut + Send + 'static,
+    Fut: Future<Output = Option<T>> + Send,
+{
+    let (sender, stream_receiver) = mpsc::channel(STREAM_BUFFER_SIZE);
+
+    tokio::spawn(async move {
+        for message in replayed {
+            if sender.send(Ok(message)).await.is_err() {
+                return;
+            }
+        }
+
+        loop {
+            let change = tokio::select! {
+                _ = sender.closed() => return,
+                change = receiver.recv() => change,
+            };
+
+            let change = match change {
+                Ok(change) => change,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    let _ = sender
+                        .send(Err(Status::data_loss(format!(
+                            "Stream fell behind by {skipped} state changes. Resume from the last received cursor."
+                        ))))
+                        .await;
+                    return;
+                }
+                Err(broadcast::error::RecvError::Closed) => return,
+            };
+
+            if replayed_until.is_some_and(|replayed_until| change.cursor <= replayed_until) {
+                continue;
+            }
+
+            if let Some(message) = convert(change).await
+                && sender.send(Ok(message)).await.is_err()
+            {
+                return;
+            }
+        }
+    });
+
+    Box::pin(ReceiverStream::new(stream_receiver))
+}
@@ -0,0 +1,179 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::protos;
+use db::ObjectColumnFilter;
+use db::dns::tenant_record;
+use dns_record::DnsResourceRecordType;
+use model::dns::{NewTenantDnsRecord, TenantDnsRecordSearchFilter};
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data};
+
+pub async fn create(
+    api: &Api,
+    request: Request<protos::dns::TenantDnsRecordCreationRequest>,
+) -> Result<Response<protos::dns::TenantDnsRecord>, Status> {
+    log_request_data(&request);
+
+    let new_record = NewTenantDnsRecord::try_from(request.into_inner())?;
+
+    let mut txn = api.txn_begin().await?;
+
+    let vpc = db::vpc::find_by(
+        &mut txn,
+        ObjectColumnFilter::One(db::vpc::IdColumn, &new_record.vpc_id),
+    )
+    .await?
+    .pop()
+    .ok_or_else(|| CarbideError::NotFoundError {
+        kind: "vpc",
+        id: new_record.vpc_id.to_string(),
+    })?;
+    let tenant_organization_id = vpc.tenant_organization_id;
+
+    let quota = api
+        .runtime_config
+        .tenant_dns_records
+        .quota(&tenant_organization_id);
+    if quota == 0 {
+        return Err(CarbideError::FailedPrecondition(format!(
+            "DNS records are disabled for tenant {tenant_organization_id}"
+        ))
+        .into());
+    }
+
+    // Records go into the most specific domain of the VPC's network segments
+    let domain = db::dns::domain::find_by_vpc(&mut txn, vpc.id)
+        .await?
+        .into_iter()
+        .filter(|domain| new_record.is_below(&domain.name))
+        .max_by_key(|domain| domain.name.len())
+        .ok_or_else(|| {
+            CarbideError::InvalidArgument(format!(
+                "{} is not inside the domain of any network segment of VPC {}",
+                new_record.q_name, vpc.id
+            ))
+        })?;
+
+    if let Some(address) = new_record.address() {
+        let vpc_prefixes = db::vpc_prefix::find_by_vpc(&mut txn, vpc.id).await?;
+        let segment_prefixes = db::network_prefix::find_by_vpc(&mut txn, vpc.id).await?;
+        let in_vpc = vpc_prefixes
+            .iter()
+            .map(|p| p.config.prefix)
+            .chain(segment_prefixes.iter().map(|p| p.prefix))
+            .any(|prefix| prefix.contains(address));
+        if !in_vpc {
+            return Err(CarbideError::InvalidArgument(format!(
+                "{address} is not inside any prefix of VPC {}",
+                vpc.id
+            ))
+            .into());
+        }
+    }
+
+    // Serialize the checks below with other requests of the same tenant
+    tenant_record::lock_tenant(&mut txn, &tenant_organization_id).await?;
+
+    // Names of machines and instances, and records managed through dynamic
+    // updates, are served to everyone and can't be shadowed.
+    if db::dns::resource_record::name_exists(&mut txn, &new_record.q_name).await? {
+        return Err(CarbideError::AlreadyFoundError {
+            kind: "dns_record",
+            id: new_record.q_name,
+        }
+        .into());
+    }
+
+    // A CNAME can't coexist with any other record of the same name
+    let existing = tenant_record::find(
+        &mut txn,
+        &TenantDnsRecordSearchFilter {
+            vpc_id: Some(vpc.id),
+            q_name: Some(new_record.q_name.clone()),
+            ..Default::default()
+        },
+    )
+    .await?;
+    let cname = DnsResourceRecordType::CNAME.to_string();
+    if (new_record.q_type == DnsResourceRecordType::CNAME && !existing.is_empty())
+        || existing.iter().any(|record| record.q_type == cname)
+    {
+        return Err(CarbideError::InvalidArgument(format!(
+            "{} can't have both a CNAME and other records",
+            new_record.q_name
+        ))
+        .into());
+    }
+
+    let count = tenant_record::count_for_tenant(&mut txn, &tenant_organization_id).await?;
+    if count >= quota as i64 {
+        return Err(CarbideError::ResourceExhausted(format!(
+            "tenant {tenant_organization_id} already has {count} DNS records (quota: {quota})"
+        ))
+        .into());
+    }
+
+    let record =
+        tenant_record::persist(&mut txn, &new_record, &tenant_organization_id, domain.id).await?;
+
+    txn.commit().await?;
+
+    Ok(Response::new(record.into()))
+}
+
+pub async fn find(
+    api: &Api,
+    request: Request<protos::dns::TenantDnsRecordSearchQuery>,
+) -> Result<Response<protos::dns::TenantDnsRecordList>, Status> {
+    log_request_data(&request);
+
+    let filter = TenantDnsRecordSearchFilter::try_from(request.into_inner())?;
+
+    let records = tenant_record::find(&api.database_connection, &filter).await?;
+
+    Ok(Response::new(protos::dns::TenantDnsRecordList {
+        records: records.into_iter().map(Into::into).collect(),
+    }))
+}
+
+pub async fn delete(
+    api: &Api,
+    request: Request<protos::dns::TenantDnsRecordDeletionRequest>,
+) -> Result<Response<protos::dns::TenantDnsRecordDeletionResult>, Status> {
+    log_request_data(&request);
+
+    let id = request
+        .into_inner()
+        .id
+        .ok_or(CarbideError::MissingArgument("id"))?;
+
+    let mut txn = api.txn_begin().await?;
+
+    tenant_record::delete(&mut txn, id)
+        .await?
+        .ok_or_else(|| CarbideError::NotFoundError {
+            kind: "tenant_dns_record",
+            id: id.to_string(),
+        })?;
+
+    txn.commit().await?;
+
+    Ok(Response::new(protos::dns::TenantDnsRecordDeletionResult {}))
+}
@@ -403,6 +403,9 @@ pub(crate) async fn delete(
     // Delete associated VPC peerings
     db::vpc_peering::delete_by_vpc_id(&mut txn, vpc_id).await?;
 
+    // Delete the DNS records the tenant created in the VPC's domains
+    db::dns::tenant_record::delete_for_vpc(&mut txn, vpc_id).await?;
+
     txn.commit().await?;
 
     Ok(Response::new(rpc::VpcDeletionResult {}))
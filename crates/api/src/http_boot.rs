@@ -0,0 +1,169 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Selection of UEFI HTTP Boot vs iPXE chainloading.
+//!
+//! Every host already fetches its first boot loader through UEFI HTTP Boot; what
+//! differs is the URL handed out in the DHCP reply. Hosts using iPXE get
+//! `ipxe.efi`, which then fetches an iPXE script from carbide-pxe. Hosts using
+//! HTTP Boot get a signed shim (which loads grub and a generated `grub.cfg`) or
+//! a signed UKI, so they can boot with Secure Boot enabled.
+
+use carbide_utils::models::arch::CpuArchitecture;
+use carbide_uuid::machine::{MachineId, MachineInterfaceId};
+
+use crate::cfg::file::{BootMethod, HttpBootConfig, HttpBootLoader};
+
+/// Returns the boot method for a host. A host entry wins over a SKU entry,
+/// which wins over the default.
+pub fn boot_method(
+    config: &HttpBootConfig,
+    host_machine_id: Option<&MachineId>,
+    hw_sku: Option<&str>,
+) -> BootMethod {
+    if let Some(method) = host_machine_id.and_then(|id| config.hosts.get(&id.to_string())) {
+        return *method;
+    }
+    if let Some(method) = hw_sku.and_then(|sku| config.skus.get(sku)) {
+        return *method;
+    }
+    config.default_method
+}
+
+/// Returns the HTTP Boot URL for an interface, or `None` if HTTP Boot is not
+/// configured or the architecture has no signed images.
+///
+/// The interface ID is part of the path so that grub, which can't read the
+/// iPXE-specific DHCP options, can still identify itself when it fetches
+/// `grub.cfg` from the same directory.
+pub fn boot_url(
+    config: &HttpBootConfig,
+    arch: CpuArchitecture,
+    interface_id: MachineInterfaceId,
+) -> Option<String> {
+    let base_url = config.base_url.as_deref()?.trim_end_matches('/');
+    let (arch, file) = match (arch, config.loader) {
+        (CpuArchitecture::X86_64, HttpBootLoader::ShimGrub) => ("x86_64", "shimx64.efi"),
+        (CpuArchitecture::Aarch64, HttpBootLoader::ShimGrub) => ("aarch64", "shimaa64.efi"),
+        (CpuArchitecture::X86_64, HttpBootLoader::Uki) => ("x86_64", "uki.efi"),
+        (CpuArchitecture::Aarch64, HttpBootLoader::Uki) => ("aarch64", "uki.efi"),
+        (CpuArchitecture::Unknown, _) => return None,
+    };
+    Some(format!("{base_url}/{interface_id}/{arch}/{file}"))
+}
+
+/// Extracts the client architecture from an HTTP Boot vendor class
+/// (`HTTPClient:Arch:00016:UNDI:003001`). Returns `None` for any other vendor
+/// class, including PXE clients.
+pub fn http_client_arch(vendor_class: &str) -> Option<CpuArchitecture> {
+    let mut parts = vendor_class.split(':');
+    if parts.next() != Some("HTTPClient") || parts.next() != Some("Arch") {
+        return None;
+    }
+    // RFC 4578 / IANA processor architecture types for HTTP Boot
+    match parts.next()?.parse::<u16>().ok()? {
+        16 => Some(CpuArchitecture::X86_64),
+        19 => Some(CpuArchitecture::Aarch64),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn config() -> HttpBootConfig {
+        HttpBootConfig {
+            base_url: Some("http://carbide-pxe.forge:8080/api/v0/http-boot/".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_boot_method_precedence() {
+        let host =
+            MachineId::from_str("fm100htjsaledfasinabqqer70e2ua5ksqj4kfjii0v0a90vulps48c1h7g")
+                .parse()
+                .unwrap();
+        let mut config = config();
+        assert_eq!(
+            boot_method(&config, Some(&host), Some("sku-a")),
+            BootMethod::Ipxe
+        );
+
+        config.skus = HashMap::from([("sku-a".to_string(), BootMethod::HttpBoot)]);
+        assert_eq!(
+            boot_method(&config, Some(&host), Some("sku-a")),
+            BootMethod::HttpBoot
+        );
+        assert_eq!(
+            boot_method(&config, Some(&host), Some("sku-b")),
+            BootMethod::Ipxe
+        );
+
+        config.hosts = HashMap::from([(host.to_string(), BootMethod::Ipxe)]);
+        assert_eq!(
+            boot_method(&config, Some(&host), Some("sku-a")),
+            BootMethod::Ipxe
+        );
+    }
+
+    #[test]
+    fn test_boot_url() {
+        let interface_id = MachineInterfaceId::nil();
+        let mut config = config();
+        assert_eq!(
+            boot_url(&config, CpuArchitecture::X86_64, interface_id).as_deref(),
+            Some(
+                "http://carbide-pxe.forge:8080/api/v0/http-boot/00000000-0000-0000-0000-000000000000/x86_64/shimx64.efi"
+            )
+        );
+        config.loader = HttpBootLoader::Uki;
+        assert_eq!(
+            boot_url(&config, CpuArchitecture::Aarch64, interface_id).as_deref(),
+            Some(
+                "http://carbide-pxe.forge:8080/api/v0/http-boot/00000000-0000-0000-0000-000000000000/aarch64/uki.efi"
+            )
+        );
+        assert_eq!(
+            boot_url(&config, CpuArchitecture::Unknown, interface_id),
+            None
+        );
+        config.base_url = None;
+        assert_eq!(
+            boot_url(&config, CpuArchitecture::X86_64, interface_id),
+            None
+        );
+    }
+
+    #[test]
+    fn test_http_client_arch() {
+        assert_eq!(
+            http_client_arch("HTTPClient:Arch:00016:UNDI:003001"),
+            Some(CpuArchitecture::X86_64)
+        );
+        assert_eq!(
+            http_client_arch("HTTPClient:Arch:00019:UNDI:003000"),
+            Some(CpuArchitecture::Aarch64)
+        );
+        assert_eq!(http_client_arch("PXEClient:Arch:00007:UNDI:003000"), None);
+        assert_eq!(http_client_arch("HTTPClient"), None);
+    }
+}
@@ -124,6 +124,13 @@ pub struct InstructionGenerator {
 }
 
 impl InstructionGenerator {
+    fn serialize(&self, script_format: rpc::BootScriptFormat) -> String {
+        match script_format {
+            rpc::BootScriptFormat::Ipxe => self.serialize_pxe_instructions(),
+            rpc::BootScriptFormat::Grub => self.serialize_grub_instructions(),
+        }
+    }
+
     fn serialize_pxe_instructions(&self) -> String {
         match &self.initrd {
             Some(initrd) => {
@@ -148,6 +155,48 @@ boot ||
             }
         }
     }
+
+    /// grub variable names can't contain `-`, so the iPXE variables set by the
+    /// carbide-pxe template are renamed. The command line is quoted because it
+    /// may contain `;`, which grub treats as a command separator.
+    fn serialize_grub_instructions(&self) -> String {
+        let kernel = grub_variables(&self.kernel);
+        let command_line = grub_variables(&self.command_line);
+        match &self.initrd {
+            Some(initrd) => format!(
+                r#"
+linux {kernel} "{command_line}"
+initrd {}
+boot
+"#,
+                grub_variables(initrd)
+            ),
+            None => format!(
+                r#"
+linux {kernel} "{command_line}"
+boot
+"#
+            ),
+        }
+    }
+}
+
+fn grub_variables(s: &str) -> String {
+    s.replace("${base-url}", "${base_url}")
+        .replace("${cloudinit-url}", "${cloudinit_url}")
+}
+
+/// Converts the fixed `echo`/`sleep`/`exit` scripts below to the requested
+/// format. grub has the same commands but no `||`.
+fn message_instructions(script: String, script_format: rpc::BootScriptFormat) -> String {
+    match script_format {
+        rpc::BootScriptFormat::Ipxe => script,
+        rpc::BootScriptFormat::Grub => script
+            .lines()
+            .map(|line| line.trim_end().trim_end_matches("||").trim_end())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
 }
 
 impl PxeInstructions {
@@ -157,6 +206,7 @@ impl PxeInstructions {
         mac_address: MacAddress,
         console: &str,
         machine_type: MachineType,
+        script_format: rpc::BootScriptFormat,
     ) -> String {
         tracing::info!(
             "machine_type: {machine_type}; machine interface ID: {machine_interface_id}; mac address: {mac_address}"
@@ -186,7 +236,7 @@ impl PxeInstructions {
                     initrd: None,
                 }
             }
-        }.serialize_pxe_instructions()
+        }.serialize(script_format)
     }
 
     /// Render an IpxeScript definition using the template-based renderer
@@ -232,11 +282,13 @@ impl PxeInstructions {
         txn: &mut PgConnection,
         target: PxeInstructionRequest,
     ) -> Result<String, CarbideError> {
+        let script_format = target.script_format;
+
         let error_instructions = |machine_id: MachineId,
                                   interface_id: MachineInterfaceId,
                                   state: &ManagedHostState|
          -> String {
-            format!(
+            let script = format!(
                 r#"
 echo Machine ID: {machine_id}
 echo Interface ID: {interface_id}
@@ -245,14 +297,29 @@ echo Could not continue boot due to invalid state ||
 sleep 5 ||
 exit ||
 "#
-            )
+            );
+            message_instructions(script, script_format)
+        };
+
+        // Tenant and override scripts are written for iPXE and can't be run by
+        // grub, so hosts using HTTP Boot get an explanation instead.
+        let unsupported_instructions = |interface_id: MachineInterfaceId, what: &str| -> String {
+            let script = format!(
+                r#"
+echo Interface ID: {interface_id}
+echo {what} requires iPXE, but this host uses UEFI HTTP Boot ||
+sleep 5 ||
+exit ||
+"#
+            );
+            message_instructions(script, script_format)
         };
 
         let exit_instructions = |machine_id: MachineId,
                                  interface_id: MachineInterfaceId,
                                  state: &ManagedHostState|
          -> String {
-            format!(
+            let script = format!(
                 r#"
 echo Machine ID: {machine_id}
 echo Interface ID: {interface_id}
@@ -261,7 +328,8 @@ echo This state assumes an OS is provisioned and will exit into the OS in 5 seco
 sleep 5 ||
 exit ||
 "#
-            )
+            );
+            message_instructions(script, script_format)
         };
 
         static UNKNOWN_HOST_INSTRUCTIONS: &str = r#"
@@ -271,7 +339,7 @@ exit ||
         "#;
 
         let mut console = "ttyS0";
-        let mut qcow_imager_url = "chain ${base-url}/internal/x86_64/qcow-imager.efi loglevel=7 console=tty0 pci=realloc=off ";
+        let mut qcow_imager_kernel = "${base-url}/internal/x86_64/qcow-imager.efi";
         let interface = db::machine_interface::find_one(&mut *txn, target.interface_id).await?;
 
         // This custom pxe is different from a customer instance of pxe. It is more for testing one off
@@ -282,6 +350,12 @@ exit ||
             db::machine_boot_override::find_optional(&mut *txn, target.interface_id).await?
             && let Some(custom_pxe) = machine_boot_override.custom_pxe
         {
+            if script_format != rpc::BootScriptFormat::Ipxe {
+                return Ok(unsupported_instructions(
+                    target.interface_id,
+                    "The custom PXE boot override",
+                ));
+            }
             return Ok(custom_pxe);
         }
 
@@ -298,6 +372,7 @@ exit ||
                         interface.mac_address,
                         console,
                         MachineType::Dpu,
+                        script_format,
                     ));
                 } else {
                     tracing::warn!(
@@ -323,7 +398,10 @@ exit ||
                 // This only happens if someone powered on a host manually before we ingested it,
                 // which is unlikely but possible.
                 tracing::info!(interface = ?interface, "Request for PXE instructions for unknown interface, skipping PXE boot");
-                return Ok(UNKNOWN_HOST_INSTRUCTIONS.to_string());
+                return Ok(message_instructions(
+                    UNKNOWN_HOST_INSTRUCTIONS.to_string(),
+                    script_format,
+                ));
             };
 
             let (machine_type, console) = match target.arch {
@@ -343,6 +421,7 @@ exit ||
                 interface.mac_address,
                 console,
                 machine_type,
+                script_format,
             ));
         };
 
@@ -375,6 +454,7 @@ exit ||
                     interface.mac_address,
                     console,
                     machine.id.machine_type(),
+                    script_format,
                 ));
             }
 
@@ -392,6 +472,7 @@ exit ||
                                 interface.mac_address,
                                 console,
                                 machine.id.machine_type(),
+                                script_format,
                             ));
                         }
                         _ => {
@@ -415,7 +496,7 @@ exit ||
 
         if target.arch == rpc::MachineArchitecture::Arm {
             console = "ttyAMA0";
-            qcow_imager_url = "chain ${base-url}/internal/aarch64/qcow-imager.efi loglevel=7 console=tty0 pci=realloc=off ";
+            qcow_imager_kernel = "${base-url}/internal/aarch64/qcow-imager.efi";
         } else if let Some(hardware_info) = machine.hardware_info.as_ref()
             && let Some(dmi_info) = hardware_info.dmi_data.as_ref()
             && (dmi_info.sys_vendor == "Lenovo" || dmi_info.sys_vendor == "Supermicro")
@@ -463,6 +544,7 @@ exit ||
                 interface.mac_address,
                 console,
                 machine.id.machine_type(),
+                script_format,
             ),
             ManagedHostState::Assigned { instance_state } => match instance_state {
                 InstanceState::Ready => {
@@ -488,6 +570,15 @@ exit ||
                         }
 
                         match instance.config.os.variant {
+                            model::os::OperatingSystemVariant::Ipxe(_)
+                            | model::os::OperatingSystemVariant::OperatingSystemId(_)
+                                if script_format != rpc::BootScriptFormat::Ipxe =>
+                            {
+                                unsupported_instructions(
+                                    target.interface_id,
+                                    "The instance's iPXE operating system",
+                                )
+                            }
                             model::os::OperatingSystemVariant::Ipxe(ipxe) => {
                                 let mut tenant_ipxe = ipxe.ipxe_script;
                                 let vendor_serial_console = format!(" console={console}");
@@ -525,41 +616,49 @@ exit ||
                                         machine.current_state(),
                                     )
                                 } else {
-                                    let mut qcow_imaging_ipxe = format!(
-                                        "{} console={},115200 image_url={} image_sha={}",
-                                        qcow_imager_url,
+                                    let mut qcow_imaging_args = format!(
+                                        "loglevel=7 console=tty0 pci=realloc=off  console={},115200 image_url={} image_sha={}",
                                         console,
                                         os_image.attributes.source_url,
                                         os_image.attributes.digest
                                     );
                                     if let Some(x) = os_image.attributes.auth_token {
-                                        qcow_imaging_ipxe +=
+                                        qcow_imaging_args +=
                                             format!(" image_auth_token={x}").as_str();
                                     }
                                     if let Some(x) = os_image.attributes.auth_type {
-                                        qcow_imaging_ipxe +=
+                                        qcow_imaging_args +=
                                             format!(" image_auth_type={x}").as_str();
                                     }
                                     if let Some(x) = os_image.attributes.rootfs_id {
-                                        qcow_imaging_ipxe += format!(" rootfs_uuid={x}").as_str();
+                                        qcow_imaging_args += format!(" rootfs_uuid={x}").as_str();
                                     }
                                     if let Some(x) = os_image.attributes.rootfs_label {
-                                        qcow_imaging_ipxe += format!(" rootfs_label={x}").as_str();
+                                        qcow_imaging_args += format!(" rootfs_label={x}").as_str();
                                     }
                                     if let Some(x) = os_image.attributes.boot_disk {
-                                        qcow_imaging_ipxe += format!(" image_disk={x}").as_str();
+                                        qcow_imaging_args += format!(" image_disk={x}").as_str();
                                     }
                                     if let Some(x) = os_image.attributes.bootfs_id {
-                                        qcow_imaging_ipxe += format!(" bootfs_uuid={x}").as_str();
+                                        qcow_imaging_args += format!(" bootfs_uuid={x}").as_str();
                                     }
                                     if let Some(x) = os_image.attributes.efifs_id {
-                                        qcow_imaging_ipxe += format!(" efifs_uuid={x}").as_str();
+                                        qcow_imaging_args += format!(" efifs_uuid={x}").as_str();
                                     }
                                     if instance.config.os.user_data.is_some() {
-                                        qcow_imaging_ipxe += " ds=nocloud-net;s=${cloudinit-url}";
+                                        qcow_imaging_args += " ds=nocloud-net;s=${cloudinit-url}";
+                                    }
+                                    match script_format {
+                                        rpc::BootScriptFormat::Ipxe => format!(
+                                            "chain {qcow_imager_kernel} {qcow_imaging_args}\r\nboot"
+                                        ),
+                                        rpc::BootScriptFormat::Grub => InstructionGenerator {
+                                            kernel: qcow_imager_kernel.to_string(),
+                                            command_line: qcow_imaging_args,
+                                            initrd: None,
+                                        }
+                                        .serialize_grub_instructions(),
                                     }
-                                    qcow_imaging_ipxe += "\r\nboot";
-                                    qcow_imaging_ipxe
                                 }
                             }
                         }
@@ -575,6 +674,7 @@ exit ||
                         interface.mac_address,
                         console,
                         machine.id.machine_type(),
+                        script_format,
                     )
                 }
 
@@ -589,6 +689,7 @@ exit ||
                 interface.mac_address,
                 console,
                 machine.id.machine_type(),
+                script_format,
             ),
             x => error_instructions(machine_id, target.interface_id, x),
         };
@@ -601,6 +702,8 @@ exit ||
 mod tests {
     use mac_address::MacAddress;
 
+    use super::*;
+
     #[test]
     /// test_formatted_mac_for_instruction_generator makes sure the MAC address
     /// does what we want/expect as part of how we pass it to the instruction
@@ -610,4 +713,34 @@ mod tests {
         let mac_address: MacAddress = "aa:bb:cc:dd:ee:ff".parse().unwrap();
         assert_eq!("AA:BB:CC:DD:EE:FF".to_string(), format!("{mac_address}"));
     }
+
+    #[test]
+    fn test_grub_instructions() {
+        let generator = InstructionGenerator {
+            kernel: "${base-url}/internal/aarch64/carbide.efi".to_string(),
+            command_line: "bfks=${cloudinit-url}/user-data ds=nocloud-net;s=x".to_string(),
+            initrd: Some("${base-url}/internal/aarch64/carbide.root".to_string()),
+        };
+        assert_eq!(
+            generator.serialize(rpc::BootScriptFormat::Grub),
+            r#"
+linux ${base_url}/internal/aarch64/carbide.efi "bfks=${cloudinit_url}/user-data ds=nocloud-net;s=x"
+initrd ${base_url}/internal/aarch64/carbide.root
+boot
+"#
+        );
+    }
+
+    #[test]
+    fn test_message_instructions() {
+        let script = "\necho hello ||\nsleep 5 ||\nexit ||\n".to_string();
+        assert_eq!(
+            message_instructions(script.clone(), rpc::BootScriptFormat::Ipxe),
+            script
+        );
+        assert_eq!(
+            message_instructions(script, rpc::BootScriptFormat::Grub),
+            "\necho hello\nsleep 5\nexit"
+        );
+    }
 }
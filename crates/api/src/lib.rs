@@ -47,6 +47,7 @@ mod dynamic_settings;
 mod errors;
 mod ethernet_virtualization;
 mod handlers;
+mod http_boot;
 mod instance;
 mod ipxe;
 mod listener;
@@ -62,6 +63,7 @@ mod redfish;
 mod run;
 mod scout_stream;
 mod setup;
+mod state_change_watch;
 mod state_controller;
 mod storage;
 #[cfg(test)]
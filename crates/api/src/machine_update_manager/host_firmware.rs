@@ -23,9 +23,13 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use async_trait::async_trait;
 use carbide_firmware::FirmwareConfig;
 use carbide_uuid::machine::MachineId;
+use chrono::Utc;
 use db::{self, desired_firmware};
 use model::machine::ManagedHostStateSnapshot;
 use model::machine_update_module::HOST_FW_UPDATE_HEALTH_REPORT_SOURCE;
+use model::maintenance_window::{
+    MaintenanceAction, MaintenanceGate, MaintenanceTarget, MaintenanceWindow, maintenance_gate,
+};
 use opentelemetry::metrics::Meter;
 use sqlx::PgConnection;
 use tokio::sync::Mutex;
@@ -57,7 +61,7 @@ impl MachineUpdateModule for HostFirmwareUpdate {
         txn: &mut PgConnection,
         available_updates: i32,
         updating_host_machines: &HashSet<MachineId>,
-        _snapshots: &HashMap<MachineId, ManagedHostStateSnapshot>,
+        snapshots: &HashMap<MachineId, ManagedHostStateSnapshot>,
     ) -> CarbideResult<HashSet<MachineId>> {
         if let Ok(mut firmware_dir_last_read) = self.firmware_dir_last_read.try_lock() {
             let firmware_dir_mod_time = self.firmware_config.config_update_time();
@@ -76,7 +80,11 @@ impl MachineUpdateModule for HostFirmwareUpdate {
             }
         }
 
-        let machine_updates = self.check_for_updates(txn, available_updates).await?;
+        let windows = db::maintenance_window::find_relevant(txn, Utc::now()).await?;
+        let deferred = deferred_by_maintenance_windows(&windows, snapshots);
+        let machine_updates = self
+            .check_for_updates(txn, available_updates, &deferred)
+            .await?;
         let mut updates_started = HashSet::default();
         self.metrics
             .pending_firmware_updates
@@ -174,6 +182,7 @@ impl HostFirmwareUpdate {
         &self,
         txn: &mut PgConnection,
         mut available_updates: i32,
+        deferred: &HashSet<MachineId>,
     ) -> CarbideResult<Vec<MachineId>> {
         let mut machines = vec![];
         if available_updates == 0 {
@@ -190,6 +199,10 @@ impl HostFirmwareUpdate {
             if available_updates == 0 {
                 return Ok(machines);
             };
+            if deferred.contains(&update_needed.id) {
+                // The host is covered by a maintenance window which has not started yet
+                continue;
+            }
             if self
                 .config
                 .firmware_global
@@ -207,6 +220,39 @@ impl HostFirmwareUpdate {
     }
 }
 
+/// Returns the hosts which are only covered by maintenance windows that have not
+/// started yet, and whose firmware updates therefore need to be deferred.
+///
+/// An active maintenance window never makes a host eligible for an update by itself.
+fn deferred_by_maintenance_windows(
+    windows: &[MaintenanceWindow],
+    snapshots: &HashMap<MachineId, ManagedHostStateSnapshot>,
+) -> HashSet<MachineId> {
+    if windows.is_empty() {
+        return HashSet::default();
+    }
+
+    let now = Utc::now();
+    snapshots
+        .iter()
+        .filter(|(machine_id, snapshot)| {
+            let host = &snapshot.host_snapshot;
+            let target = MaintenanceTarget {
+                machine_id: Some(machine_id),
+                rack_id: host.rack_id.as_ref(),
+                sku: host.hw_sku.as_deref(),
+                labels: Some(&host.metadata.labels),
+                ..Default::default()
+            };
+            matches!(
+                maintenance_gate(windows, MaintenanceAction::FirmwareUpdate, &target, now),
+                MaintenanceGate::Deferred { .. }
+            )
+        })
+        .map(|(machine_id, _)| *machine_id)
+        .collect()
+}
+
 impl fmt::Display for HostFirmwareUpdate {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "HostFirmwareUpdate")
@@ -0,0 +1,103 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+/*!
+ * gRPC handlers for measurement policy related API calls.
+ */
+
+use ::measured_boot::policy::PolicyRule;
+use rpc::errors::RpcDataConversionError;
+use rpc::protos::measured_boot::{
+    CreateMeasurementPolicyRequest, CreateMeasurementPolicyResponse,
+    DeleteMeasurementPolicyRequest, DeleteMeasurementPolicyResponse,
+    ShowMeasurementPoliciesRequest, ShowMeasurementPoliciesResponse,
+};
+use tonic::Status;
+
+use crate::api::Api;
+use crate::errors::CarbideError;
+
+/// handle_create_measurement_policy handles the CreateMeasurementPolicy
+/// API endpoint. Machines that are pending a bundle get re-evaluated
+/// against the new policy as part of creating it.
+pub async fn handle_create_measurement_policy(
+    api: &Api,
+    req: CreateMeasurementPolicyRequest,
+) -> Result<CreateMeasurementPolicyResponse, Status> {
+    if req.name.is_empty() {
+        return Err(CarbideError::MissingArgument("name").into());
+    }
+    let rules = req
+        .rules
+        .into_iter()
+        .map(PolicyRule::try_from)
+        .collect::<Result<Vec<PolicyRule>, RpcDataConversionError>>()
+        .map_err(CarbideError::from)?;
+
+    let mut txn = api.txn_begin().await?;
+    let policy = db::measured_boot::policy::new(&mut txn, &req.name, req.profile_id, &rules)
+        .await
+        .map_err(CarbideError::from)?;
+
+    txn.commit().await?;
+    Ok(CreateMeasurementPolicyResponse {
+        policy: Some(policy.into()),
+    })
+}
+
+/// handle_delete_measurement_policy handles the DeleteMeasurementPolicy
+/// API endpoint.
+pub async fn handle_delete_measurement_policy(
+    api: &Api,
+    req: DeleteMeasurementPolicyRequest,
+) -> Result<DeleteMeasurementPolicyResponse, Status> {
+    let mut txn = api.txn_begin().await?;
+    let policy = db::measured_boot::policy::delete_for_id(
+        &mut txn,
+        req.policy_id
+            .ok_or(CarbideError::MissingArgument("policy_id"))?,
+    )
+    .await
+    .map_err(CarbideError::from)?;
+
+    txn.commit().await?;
+    Ok(DeleteMeasurementPolicyResponse {
+        policy: Some(policy.into()),
+    })
+}
+
+/// handle_show_measurement_policies handles the ShowMeasurementPolicies
+/// API endpoint.
+pub async fn handle_show_measurement_policies(
+    api: &Api,
+    req: ShowMeasurementPoliciesRequest,
+) -> Result<ShowMeasurementPoliciesResponse, Status> {
+    let mut txn = api.txn_begin().await?;
+    let policies = match req.profile_id {
+        Some(profile_id) => {
+            db::measured_boot::policy::get_all_for_profile_id(&mut txn, profile_id).await
+        }
+        None => db::measured_boot::policy::get_all(&mut txn).await,
+    }
+    .map_err(|e| CarbideError::Internal {
+        message: format!("failed to read policies: {e}"),
+    })?;
+
+    txn.commit().await?;
+    Ok(ShowMeasurementPoliciesResponse {
+        policies: policies.into_iter().map(Into::into).collect(),
+    })
+}
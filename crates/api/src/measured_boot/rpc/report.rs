@@ -172,10 +172,11 @@ pub async fn handle_show_measurement_report_for_id(
     let mut txn = api.txn_begin().await?;
     let result = Ok(ShowMeasurementReportForIdResponse {
         report: Some(
-            db::measured_boot::report::from_id(
+            db::measured_boot::report::from_id_with_event_log(
                 &mut txn,
                 req.report_id
                     .ok_or(CarbideError::MissingArgument("report_id"))?,
+                req.reference_report_id,
             )
             .await
             .map_err(|e| CarbideError::Internal {
@@ -22,9 +22,13 @@
 use std::str::FromStr;
 
 use carbide_uuid::machine::MachineId;
+use measured_boot::event_log::{EventLog, EventType, HashAlgorithm};
 use measured_boot::machine::CandidateMachine;
+use measured_boot::pcr::PcrRegisterValue;
+use measured_boot::replay::replay_pcrs;
 use model::hardware_info::HardwareInfo;
 use model::machine::{CURRENT_STATE_MODEL_VERSION, ManagedHostState};
+use sha2::{Digest, Sha256};
 use sqlx::PgConnection;
 
 pub fn load_topology_json(path: &str) -> HardwareInfo {
@@ -58,3 +62,89 @@ pub async fn create_test_machine(
     assert_eq!(machine_id, machine.machine_id);
     Ok(machine)
 }
+
+// Builders for synthetic crypto-agile (SHA-256 only) TCG event logs.
+
+// EFI_GLOBAL_VARIABLE and EFI_IMAGE_SECURITY_DATABASE_GUID, as
+// they are laid out in memory.
+pub const EFI_GLOBAL_VARIABLE: [u8; 16] = [
+    0x61, 0xdf, 0xe4, 0x8b, 0xca, 0x93, 0xd2, 0x11, 0xaa, 0x0d, 0x00, 0xe0, 0x98, 0x03, 0x2b, 0x8c,
+];
+pub const EFI_IMAGE_SECURITY_DATABASE: [u8; 16] = [
+    0xcb, 0xb2, 0x19, 0xd7, 0x3a, 0x3d, 0x96, 0x45, 0xa3, 0xbc, 0xda, 0xd0, 0x0e, 0x67, 0x65, 0x6f,
+];
+
+fn utf16(text: &str) -> Vec<u8> {
+    text.encode_utf16()
+        .flat_map(|unit| unit.to_le_bytes())
+        .collect()
+}
+
+pub fn spec_id_header() -> Vec<u8> {
+    let mut data = b"Spec ID Event03\0".to_vec();
+    data.extend(0u32.to_le_bytes()); // platformClass
+    data.extend([0, 2, 0, 2]); // minor, major, errata, uintnSize
+    data.extend(1u32.to_le_bytes()); // numberOfAlgorithms
+    data.extend(0x000bu16.to_le_bytes()); // TPM_ALG_SHA256
+    data.extend(32u16.to_le_bytes());
+    data.push(0); // vendorInfoSize
+
+    let mut event = Vec::new();
+    event.extend(0u32.to_le_bytes());
+    event.extend(EventType::NO_ACTION.0.to_le_bytes());
+    event.extend([0u8; 20]);
+    event.extend((data.len() as u32).to_le_bytes());
+    event.extend(data);
+    event
+}
+
+// event builds a TCG_PCR_EVENT2 whose SHA-256 digest is the
+// hash of its data, which is good enough for replay purposes.
+pub fn event(pcr_index: u32, event_type: EventType, data: &[u8]) -> Vec<u8> {
+    let mut event = Vec::new();
+    event.extend(pcr_index.to_le_bytes());
+    event.extend(event_type.0.to_le_bytes());
+    event.extend(1u32.to_le_bytes());
+    event.extend(0x000bu16.to_le_bytes());
+    event.extend(Sha256::digest(data));
+    event.extend((data.len() as u32).to_le_bytes());
+    event.extend(data);
+    event
+}
+
+pub fn variable(guid: [u8; 16], name: &str, value: &[u8]) -> Vec<u8> {
+    let mut data = guid.to_vec();
+    data.extend((name.encode_utf16().count() as u64).to_le_bytes());
+    data.extend((value.len() as u64).to_le_bytes());
+    data.extend(utf16(name));
+    data.extend(value);
+    data
+}
+
+pub fn image(path: &str) -> Vec<u8> {
+    let mut file_path = utf16(path);
+    file_path.extend([0, 0]);
+    let mut device_path = vec![0x04, 0x04];
+    device_path.extend(((file_path.len() + 4) as u16).to_le_bytes());
+    device_path.extend(file_path);
+    device_path.extend([0x7f, 0xff, 0x04, 0x00]);
+
+    let mut data = Vec::new();
+    data.extend(0x1000u64.to_le_bytes()); // ImageLocationInMemory
+    data.extend(0x2000u64.to_le_bytes()); // ImageLengthInMemory
+    data.extend(0u64.to_le_bytes()); // ImageLinkTimeAddress
+    data.extend((device_path.len() as u64).to_le_bytes());
+    data.extend(device_path);
+    data
+}
+
+pub fn quoted_values(log: &EventLog) -> Vec<PcrRegisterValue> {
+    replay_pcrs(log, HashAlgorithm::Sha256)
+        .unwrap()
+        .into_iter()
+        .map(|(pcr_register, value)| PcrRegisterValue {
+            pcr_register: pcr_register as i16,
+            sha_any: hex::encode(value),
+        })
+        .collect()
+}
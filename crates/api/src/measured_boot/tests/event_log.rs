@@ -0,0 +1,203 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! tests/event_log.rs
+//!
+//! Event logs:
+//! [x] test_parse_crypto_agile_log: Make sure events and their data decode.
+//! [x] test_parse_truncated_log: Make sure a truncated log is rejected.
+//! [x] test_replay_against_quote: Make sure replay matches (and catches) quotes.
+//! [x] test_diff_event_logs: Make sure changed and added events are reported.
+//! [x] test_report_event_log: Make sure stored event logs show up on reports.
+
+#[cfg(test)]
+mod tests {
+    use measured_boot::event_log::{
+        EfiImageType, EventDetail, EventLog, EventType, HashAlgorithm, VariableKind,
+    };
+    use measured_boot::pcr::PcrRegisterValue;
+    use measured_boot::replay::{EventChange, EventLogAnalysis, diff_event_logs};
+
+    use crate::measured_boot::tests::common::{
+        EFI_GLOBAL_VARIABLE, EFI_IMAGE_SECURITY_DATABASE, create_test_machine, event, image,
+        load_topology_json, quoted_values, spec_id_header, variable,
+    };
+
+    fn build_log(db: &[u8], extra_driver: bool) -> Vec<u8> {
+        let mut log = spec_id_header();
+        log.extend(event(
+            7,
+            EventType::EFI_VARIABLE_DRIVER_CONFIG,
+            &variable(EFI_GLOBAL_VARIABLE, "SecureBoot", &[1]),
+        ));
+        log.extend(event(
+            7,
+            EventType::EFI_VARIABLE_DRIVER_CONFIG,
+            &variable(EFI_IMAGE_SECURITY_DATABASE, "db", db),
+        ));
+        log.extend(event(
+            2,
+            EventType::EFI_BOOT_SERVICES_DRIVER,
+            &image("\\EFI\\nic.efi"),
+        ));
+        if extra_driver {
+            log.extend(event(
+                2,
+                EventType::EFI_BOOT_SERVICES_DRIVER,
+                &image("\\EFI\\extra.efi"),
+            ));
+        }
+        log.extend(event(
+            1,
+            EventType::EFI_VARIABLE_BOOT,
+            &variable(EFI_GLOBAL_VARIABLE, "Boot0001", b"pxe"),
+        ));
+        for pcr_index in 0..8 {
+            log.extend(event(pcr_index, EventType::SEPARATOR, &[0, 0, 0, 0]));
+        }
+        log
+    }
+
+    #[test]
+    fn test_parse_crypto_agile_log() {
+        let log = EventLog::parse(&build_log(b"cert-a", false)).unwrap();
+        assert_eq!(log.algorithms(), vec![HashAlgorithm::Sha256]);
+        assert_eq!(log.events.len(), 1 + 4 + 8);
+        assert_eq!(log.measurements().count(), 4 + 8);
+
+        match log.events[2].detail() {
+            EventDetail::EfiVariable { name, kind, .. } => {
+                assert_eq!(name, "db");
+                assert_eq!(kind, VariableKind::SecureBoot);
+            }
+            other => panic!("unexpected detail: {other:?}"),
+        }
+        match log.events[3].detail() {
+            EventDetail::EfiImage {
+                image_type,
+                device_path,
+                length,
+            } => {
+                assert_eq!(image_type, EfiImageType::Driver);
+                assert_eq!(device_path.as_deref(), Some("\\EFI\\nic.efi"));
+                assert_eq!(length, 0x2000);
+            }
+            other => panic!("unexpected detail: {other:?}"),
+        }
+        assert_eq!(log.events[4].description(), "boot variable Boot0001");
+        assert_eq!(log.events[5].description(), "separator");
+    }
+
+    #[test]
+    fn test_parse_truncated_log() {
+        let raw = build_log(b"cert-a", false);
+        assert!(EventLog::parse(&raw[..raw.len() - 3]).is_err());
+    }
+
+    #[test]
+    fn test_replay_against_quote() {
+        let log = EventLog::parse(&build_log(b"cert-a", false)).unwrap();
+        let mut quoted = quoted_values(&log);
+        // PCR 10 isn't in the log, so it can't be judged either way.
+        quoted.push(PcrRegisterValue {
+            pcr_register: 10,
+            sha_any: hex::encode([0xabu8; 32]),
+        });
+
+        let analysis = EventLogAnalysis::analyze(&log, &quoted, None).unwrap();
+        assert_eq!(analysis.algorithm, HashAlgorithm::Sha256);
+        assert!(analysis.is_consistent());
+        assert!(analysis.diffs.is_empty());
+
+        // A quote taken after a different db was measured
+        // no longer matches what this log replays to.
+        let other = EventLog::parse(&build_log(b"cert-b", false)).unwrap();
+        let analysis = EventLogAnalysis::analyze(&log, &quoted_values(&other), None).unwrap();
+        assert_eq!(analysis.mismatched_pcrs(), vec![7]);
+    }
+
+    #[test]
+    fn test_diff_event_logs() {
+        let reference = EventLog::parse(&build_log(b"cert-a", false)).unwrap();
+        let current = EventLog::parse(&build_log(b"cert-b", true)).unwrap();
+
+        let diffs = diff_event_logs(&reference, &current, HashAlgorithm::Sha256);
+        assert_eq!(diffs.len(), 2);
+
+        assert_eq!(diffs[0].pcr_register, 2);
+        assert_eq!(diffs[0].change, EventChange::Added);
+        assert_eq!(diffs[0].description, "EFI driver \\EFI\\extra.efi");
+        assert!(diffs[0].reference_digest.is_none());
+
+        assert_eq!(diffs[1].pcr_register, 7);
+        assert_eq!(diffs[1].change, EventChange::Changed);
+        assert_eq!(diffs[1].description, "Secure Boot variable db");
+        assert!(diffs[1].reference_digest.is_some());
+        assert_ne!(diffs[1].reference_digest, diffs[1].digest);
+
+        assert!(diff_event_logs(&reference, &reference, HashAlgorithm::Sha256).is_empty());
+    }
+
+    #[crate::sqlx_test]
+    pub async fn test_report_event_log(
+        pool: sqlx::PgPool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut txn = pool.begin().await?;
+        let machine = create_test_machine(
+            &mut txn,
+            "fm100hseddco33hvlofuqvg543p6p9aj60g76q5cq491g9m9tgtf2dk0530",
+            &load_topology_json("dell_r750.json"),
+        )
+        .await?;
+
+        let reference_raw = build_log(b"cert-a", false);
+        let reference_values = quoted_values(&EventLog::parse(&reference_raw)?);
+        let reference =
+            db::measured_boot::report::new(&mut txn, machine.machine_id, &reference_values).await?;
+        db::measured_boot::report::set_event_log(&mut txn, reference.report_id, &reference_raw)
+            .await?;
+
+        let current_raw = build_log(b"cert-b", false);
+        let current_values = quoted_values(&EventLog::parse(&current_raw)?);
+        let current =
+            db::measured_boot::report::new(&mut txn, machine.machine_id, &current_values).await?;
+        db::measured_boot::report::set_event_log(&mut txn, current.report_id, &current_raw).await?;
+
+        // Reports without an explicit ask don't carry the analysis.
+        let plain = db::measured_boot::report::from_id(&mut txn, current.report_id).await?;
+        assert!(plain.event_log.is_none());
+
+        let shown = db::measured_boot::report::from_id_with_event_log(
+            &mut txn,
+            current.report_id,
+            Some(reference.report_id),
+        )
+        .await?;
+        let analysis = shown.event_log.expect("event log analysis");
+        assert!(analysis.is_consistent());
+        assert_eq!(analysis.reference_report_id, Some(reference.report_id));
+        assert_eq!(analysis.diffs.len(), 1);
+        assert_eq!(analysis.diffs[0].change, EventChange::Changed);
+
+        // And it survives the trip through the API types.
+        let pb: rpc::protos::measured_boot::MeasurementReportPb = shown.clone().into();
+        let round_tripped = measured_boot::report::MeasurementReport::try_from(pb)?;
+        assert_eq!(round_tripped.event_log, shown.event_log);
+
+        Ok(())
+    }
+}
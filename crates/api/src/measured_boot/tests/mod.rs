@@ -19,9 +19,11 @@
 //! Measured boot unit testing module.
 
 pub mod common;
+mod event_log;
 mod integration;
 mod journal;
 mod metrics;
+mod policy;
 mod profile;
 mod report;
 mod rpc;
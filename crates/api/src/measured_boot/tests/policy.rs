@@ -0,0 +1,275 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! tests/policy.rs
+//!
+//! Measurement policies:
+//! [x] test_policy_rules: Make sure each rule kind passes and fails as expected.
+//! [x] test_policy_validation: Make sure broken rules are rejected.
+//! [x] test_policy_approves_pending_machine: Make sure a passing policy approves a pending machine.
+//! [x] test_policy_failure_stays_pending: Make sure a failing policy leaves the machine pending.
+
+#[cfg(test)]
+mod tests {
+    use measured_boot::event_log::{EventLog, EventType, HashAlgorithm};
+    use measured_boot::policy::PolicyRule;
+    use measured_boot::records::MeasurementMachineState;
+    use sha2::{Digest, Sha256};
+
+    use crate::measured_boot::tests::common::{
+        EFI_GLOBAL_VARIABLE, EFI_IMAGE_SECURITY_DATABASE, create_test_machine, event, image,
+        load_topology_json, quoted_values, spec_id_header, variable,
+    };
+
+    const SHIM: &[u8] = b"shim-15.8";
+    const GRUB: &[u8] = b"grub-2.12";
+    const DB: &[u8] = b"cert-a";
+
+    fn build_log(grub: &[u8], cmdline: &str) -> Vec<u8> {
+        let mut log = spec_id_header();
+        log.extend(event(
+            7,
+            EventType::EFI_VARIABLE_DRIVER_CONFIG,
+            &variable(EFI_GLOBAL_VARIABLE, "SecureBoot", &[1]),
+        ));
+        log.extend(event(
+            7,
+            EventType::EFI_VARIABLE_DRIVER_CONFIG,
+            &variable(EFI_IMAGE_SECURITY_DATABASE, "db", DB),
+        ));
+        for pcr_index in 0..8 {
+            log.extend(event(pcr_index, EventType::SEPARATOR, &[0, 0, 0, 0]));
+        }
+        // The digests of the applications are the hash of the load
+        // event, so stand-in the binary with the load event data.
+        log.extend(event(
+            4,
+            EventType::EFI_BOOT_SERVICES_APPLICATION,
+            &[image("\\EFI\\BOOT\\BOOTX64.EFI"), SHIM.to_vec()].concat(),
+        ));
+        log.extend(event(
+            4,
+            EventType::EFI_BOOT_SERVICES_APPLICATION,
+            &[image("\\EFI\\ubuntu\\grubx64.efi"), grub.to_vec()].concat(),
+        ));
+        log.extend(event(
+            8,
+            EventType::IPL,
+            format!("kernel_cmdline: {cmdline}\0").as_bytes(),
+        ));
+        log
+    }
+
+    fn application_digest(path: &str, binary: &[u8]) -> String {
+        hex::encode(Sha256::digest([image(path), binary.to_vec()].concat()))
+    }
+
+    fn allowed_rules() -> Vec<PolicyRule> {
+        vec![
+            PolicyRule::BootloaderDigests {
+                allowed_digests: vec![
+                    application_digest("\\EFI\\BOOT\\BOOTX64.EFI", SHIM),
+                    application_digest("\\EFI\\ubuntu\\grubx64.efi", GRUB),
+                ],
+            },
+            PolicyRule::SecureBootVariable {
+                variable: "db".to_string(),
+                allowed_digests: vec![hex::encode(Sha256::digest(variable(
+                    EFI_IMAGE_SECURITY_DATABASE,
+                    "db",
+                    DB,
+                )))],
+            },
+            PolicyRule::KernelCommandLine {
+                allowed_patterns: vec![r"root=UUID=[0-9a-f-]+ ro( quiet)?".to_string()],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_policy_rules() {
+        let rules = allowed_rules();
+        let good = EventLog::parse(&build_log(GRUB, "root=UUID=1234-abcd ro quiet")).unwrap();
+        for rule in rules.iter() {
+            let result = rule.evaluate(&good, HashAlgorithm::Sha256);
+            assert!(result.passed, "{}: {}", result.rule, result.detail);
+        }
+
+        // An unknown grub build is called out by path.
+        let bad = EventLog::parse(&build_log(
+            b"grub-evil",
+            "root=UUID=1234-abcd ro init=/bin/sh",
+        ))
+        .unwrap();
+        let result = rules[0].evaluate(&bad, HashAlgorithm::Sha256);
+        assert!(!result.passed);
+        assert!(result.detail.contains("grubx64.efi"), "{}", result.detail);
+
+        // The db didn't change, so that rule still passes.
+        assert!(rules[1].evaluate(&bad, HashAlgorithm::Sha256).passed);
+
+        // Patterns have to match the whole command line.
+        let result = rules[2].evaluate(&bad, HashAlgorithm::Sha256);
+        assert!(!result.passed);
+        assert!(result.detail.contains("init=/bin/sh"), "{}", result.detail);
+
+        // A variable that was never measured can't pass.
+        let missing = PolicyRule::SecureBootVariable {
+            variable: "dbx".to_string(),
+            allowed_digests: vec!["00".to_string()],
+        };
+        let result = missing.evaluate(&good, HashAlgorithm::Sha256);
+        assert!(!result.passed);
+        assert_eq!(result.detail, "dbx was not measured");
+    }
+
+    #[test]
+    fn test_policy_validation() {
+        assert!(allowed_rules().iter().all(|rule| rule.validate().is_ok()));
+        assert!(
+            PolicyRule::BootloaderDigests {
+                allowed_digests: vec!["not-hex".to_string()]
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(
+            PolicyRule::BootloaderDigests {
+                allowed_digests: vec![]
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(
+            PolicyRule::KernelCommandLine {
+                allowed_patterns: vec!["root=(".to_string()]
+            }
+            .validate()
+            .is_err()
+        );
+    }
+
+    #[crate::sqlx_test]
+    pub async fn test_policy_approves_pending_machine(
+        pool: sqlx::PgPool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut txn = pool.begin().await?;
+        let machine = create_test_machine(
+            &mut txn,
+            "fm100hseddco33hvlofuqvg543p6p9aj60g76q5cq491g9m9tgtf2dk0530",
+            &load_topology_json("dell_r750.json"),
+        )
+        .await?;
+
+        let raw = build_log(GRUB, "root=UUID=1234-abcd ro");
+        let values = quoted_values(&EventLog::parse(&raw)?);
+        let report = db::measured_boot::report::new(&mut txn, machine.machine_id, &values).await?;
+        db::measured_boot::report::set_event_log(&mut txn, report.report_id, &raw).await?;
+
+        // No policies yet, so nothing changes.
+        assert!(
+            !db::measured_boot::policy::maybe_approve_report(&mut txn, report.report_id).await?
+        );
+        let journal =
+            db::measured_boot::journal::get_journal_for_report_id(&mut txn, report.report_id)
+                .await?;
+        assert_eq!(journal.state, MeasurementMachineState::PendingBundle);
+
+        // Creating a policy re-evaluates the pending machine.
+        let policy = db::measured_boot::policy::new(
+            &mut txn,
+            "ubuntu-24.04",
+            journal.profile_id,
+            &allowed_rules(),
+        )
+        .await?;
+        let journal =
+            db::measured_boot::journal::get_latest_for_machine_id(&mut txn, machine.machine_id)
+                .await?
+                .expect("latest journal");
+        assert_eq!(journal.report_id, report.report_id);
+        assert_eq!(journal.state, MeasurementMachineState::Measured);
+        assert!(journal.bundle_id.is_none());
+
+        // The report explains which rules passed.
+        let shown =
+            db::measured_boot::report::from_id_with_event_log(&mut txn, report.report_id, None)
+                .await?;
+        let analysis = shown.event_log.expect("event log analysis");
+        assert_eq!(analysis.policies.len(), 1);
+        assert_eq!(analysis.policies[0].policy_id, policy.policy_id);
+        assert!(analysis.policies[0].passed);
+        assert_eq!(analysis.policies[0].rules.len(), 3);
+
+        // And it survives the trip through the API types.
+        let pb: rpc::protos::measured_boot::MeasurementReportPb = shown.clone().into();
+        let round_tripped = measured_boot::report::MeasurementReport::try_from(pb)?;
+        assert_eq!(round_tripped.event_log, shown.event_log);
+
+        Ok(())
+    }
+
+    #[crate::sqlx_test]
+    pub async fn test_policy_failure_stays_pending(
+        pool: sqlx::PgPool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut txn = pool.begin().await?;
+        let machine = create_test_machine(
+            &mut txn,
+            "fm100hseddco33hvlofuqvg543p6p9aj60g76q5cq491g9m9tgtf2dk0530",
+            &load_topology_json("dell_r750.json"),
+        )
+        .await?;
+
+        db::measured_boot::policy::new(&mut txn, "ubuntu-24.04", None, &allowed_rules()).await?;
+
+        let raw = build_log(b"grub-evil", "root=UUID=1234-abcd ro");
+        let values = quoted_values(&EventLog::parse(&raw)?);
+        let report = db::measured_boot::report::new(&mut txn, machine.machine_id, &values).await?;
+        db::measured_boot::report::set_event_log(&mut txn, report.report_id, &raw).await?;
+        assert!(
+            !db::measured_boot::policy::maybe_approve_report(&mut txn, report.report_id).await?
+        );
+
+        let journal =
+            db::measured_boot::journal::get_journal_for_report_id(&mut txn, report.report_id)
+                .await?;
+        assert_eq!(journal.state, MeasurementMachineState::PendingBundle);
+
+        let shown =
+            db::measured_boot::report::from_id_with_event_log(&mut txn, report.report_id, None)
+                .await?;
+        let analysis = shown.event_log.expect("event log analysis");
+        assert!(!analysis.policies[0].passed);
+        let failed: Vec<&str> = analysis.policies[0]
+            .rules
+            .iter()
+            .filter(|rule| !rule.passed)
+            .map(|rule| rule.rule.as_str())
+            .collect();
+        assert_eq!(failed, vec!["bootloader digests (2 allowed)"]);
+
+        // A log that doesn't replay to the quote is never approved,
+        // no matter what the rules say.
+        let raw = build_log(GRUB, "root=UUID=1234-abcd ro");
+        db::measured_boot::report::set_event_log(&mut txn, report.report_id, &raw).await?;
+        assert!(
+            !db::measured_boot::policy::maybe_approve_report(&mut txn, report.report_id).await?
+        );
+
+        Ok(())
+    }
+}
@@ -423,6 +423,7 @@ mod tests {
         // Now lets do a basic show for the report.
         let req = mbrpc::ShowMeasurementReportForIdRequest {
             report_id: report.report_id,
+            reference_report_id: None,
         };
         let resp = report::handle_show_measurement_report_for_id(api, req).await?;
         assert!(resp.report.is_some());
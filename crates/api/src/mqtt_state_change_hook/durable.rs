@@ -0,0 +1,185 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Durable MQTT hook, which publishes state changes from the transactional
+//! state change outbox.
+
+use std::time::Duration;
+
+use carbide_uuid::machine::MachineId;
+use model::machine::ManagedHostState;
+use opentelemetry::metrics::Meter;
+use tokio::time::timeout;
+
+use crate::mqtt_state_change_hook::hook::{MqttPublisher, MqttStateChangeHook};
+use crate::mqtt_state_change_hook::message::ManagedHostStateChangeMessage;
+use crate::mqtt_state_change_hook::metrics::MqttHookMetrics;
+use crate::state_controller::state_change_outbox::{
+    DeliveryError, DurableStateChangeHook, OutboxStateChange,
+};
+
+/// MQTT hook that publishes `ManagedHostState` changes to the MQTT broker
+/// with at-least-once delivery.
+///
+/// Publishes the same messages as [`MqttStateChangeHook`]. Failed publish
+/// attempts are retried by the outbox relay, so no state change is dropped
+/// while the broker is unavailable.
+pub struct DurableMqttStateChangeHook<P> {
+    client: P,
+    publish_timeout: Duration,
+    metrics: MqttHookMetrics,
+}
+
+impl<P: MqttPublisher> DurableMqttStateChangeHook<P> {
+    /// Name under which the hook stores its outbox entries
+    pub const NAME: &'static str = "dsx_exchange_event_bus";
+
+    pub fn new(client: P, publish_timeout: Duration, meter: &Meter) -> Self {
+        Self {
+            client,
+            publish_timeout,
+            metrics: MqttHookMetrics::without_queue(meter, "managed_host"),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<P: MqttPublisher> DurableStateChangeHook<MachineId, ManagedHostState>
+    for DurableMqttStateChangeHook<P>
+{
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    async fn deliver(
+        &self,
+        change: &OutboxStateChange<MachineId, ManagedHostState>,
+    ) -> Result<(), DeliveryError> {
+        let message = ManagedHostStateChangeMessage {
+            machine_id: &change.object_id,
+            managed_host_state: &change.new_state,
+            timestamp: change.timestamp,
+        };
+        let payload = message.to_json_bytes().map_err(|e| {
+            self.metrics.record_serialization_error();
+            DeliveryError::Permanent(format!("Failed to serialize state change message: {e}"))
+        })?;
+        let topic = MqttStateChangeHook::build_topic(&change.object_id);
+
+        match timeout(self.publish_timeout, self.client.publish(&topic, payload)).await {
+            Ok(Ok(())) => {
+                tracing::debug!(%topic, attempt = change.attempt, "Published state change to MQTT");
+                self.metrics.record_success();
+                Ok(())
+            }
+            Ok(Err(e)) => {
+                self.metrics.record_publish_error();
+                Err(DeliveryError::Transient(format!(
+                    "Failed to publish state change to MQTT: {e}"
+                )))
+            }
+            Err(_) => {
+                self.metrics.record_timeout();
+                Err(DeliveryError::Transient(
+                    "MQTT publish timed out".to_string(),
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use config_version::ConfigVersion;
+    use mqttea::MqtteaClientError;
+
+    use super::*;
+
+    fn test_machine_id() -> MachineId {
+        use carbide_uuid::machine::{MachineIdSource, MachineType};
+        MachineId::new(
+            MachineIdSource::ProductBoardChassisSerial,
+            [0; 32],
+            MachineType::Host,
+        )
+    }
+
+    fn make_change(id: MachineId) -> OutboxStateChange<MachineId, ManagedHostState> {
+        OutboxStateChange {
+            object_id: id,
+            new_state: ManagedHostState::Ready,
+            new_version: ConfigVersion::initial(),
+            timestamp: chrono::Utc::now(),
+            attempt: 1,
+        }
+    }
+
+    /// Publisher that records published messages, or hangs if `hang` is set
+    #[derive(Default)]
+    struct RecordingPublisher {
+        hang: bool,
+        published: Mutex<Vec<(String, Vec<u8>)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl MqttPublisher for RecordingPublisher {
+        async fn publish(&self, topic: &str, payload: Vec<u8>) -> Result<(), MqtteaClientError> {
+            if self.hang {
+                std::future::pending::<()>().await;
+            }
+            self.published
+                .lock()
+                .unwrap()
+                .push((topic.to_string(), payload));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_state_change_is_published() {
+        let hook = DurableMqttStateChangeHook::new(
+            RecordingPublisher::default(),
+            Duration::from_secs(1),
+            &opentelemetry::global::meter("test"),
+        );
+
+        let id = test_machine_id();
+        hook.deliver(&make_change(id)).await.unwrap();
+
+        let published = hook.client.published.lock().unwrap();
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].0, format!("nico/v1/machine/{id}/state"));
+        let parsed: serde_json::Value = serde_json::from_slice(&published[0].1).unwrap();
+        assert_eq!(parsed["managed_host_state"]["state"], "ready");
+    }
+
+    #[tokio::test]
+    async fn test_publish_timeout_is_transient() {
+        let hook = DurableMqttStateChangeHook::new(
+            RecordingPublisher {
+                hang: true,
+                ..Default::default()
+            },
+            Duration::from_millis(1),
+            &opentelemetry::global::meter("test"),
+        );
+
+        let result = hook.deliver(&make_change(test_machine_id())).await;
+        assert!(matches!(result, Err(DeliveryError::Transient(_))));
+    }
+}
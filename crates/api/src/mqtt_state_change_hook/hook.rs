@@ -109,7 +109,7 @@ impl MqttStateChangeHook {
         }
     }
 
-    fn build_topic(machine_id: &MachineId) -> String {
+    pub(super) fn build_topic(machine_id: &MachineId) -> String {
         format!("{}/{}/state", TOPIC_PREFIX, machine_id)
     }
 }
@@ -212,6 +212,7 @@ mod tests {
             object_id: id,
             previous_state: None,
             new_state: state,
+            new_version: config_version::ConfigVersion::initial(),
             timestamp: chrono::Utc::now(),
         }
     }
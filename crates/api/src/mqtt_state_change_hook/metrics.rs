@@ -57,6 +57,11 @@ impl MqttHookMetrics {
             })
             .build();
 
+        Self::without_queue(meter, component)
+    }
+
+    /// Create metrics instruments for a publisher which does not use a queue.
+    pub fn without_queue(meter: &Meter, component: &'static str) -> Self {
         let publish_count = meter
             .u64_counter("carbide_dsx_event_bus_publish_count")
             .with_description("Total number of MQTT publish attempts")
@@ -20,6 +20,7 @@
 //! This module implements the AsyncAPI specification defined in `carbide.yaml`,
 //! publishing state changes to `nico/v1/machine/{machineId}/state` over MQTT 3.1.1.
 
+pub mod durable;
 pub mod hook;
 pub mod message;
 pub mod metrics;
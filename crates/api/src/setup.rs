@@ -73,26 +73,36 @@ use crate::logging::service_health_metrics::{
 };
 use crate::machine_update_manager::MachineUpdateManager;
 use crate::measured_boot::metrics_collector::MeasuredBootMetricsCollector;
+use crate::mqtt_state_change_hook::durable::DurableMqttStateChangeHook;
 use crate::mqtt_state_change_hook::hook::MqttStateChangeHook;
 use crate::rack::bms_client::BmsDsxExchangeHandle;
 use crate::scout_stream::ConnectionRegistry;
+use crate::state_change_watch::StateChangeWatchers;
 use crate::state_controller::common_services::CommonStateHandlerServices;
 use crate::state_controller::controller::{Enqueuer, StateController};
 use crate::state_controller::dpa_interface::handler::DpaInterfaceStateHandler;
 use crate::state_controller::dpa_interface::io::DpaInterfaceStateControllerIO;
 use crate::state_controller::ib_partition::handler::IBPartitionStateHandler;
 use crate::state_controller::ib_partition::io::IBPartitionStateControllerIO;
+use crate::state_controller::machine::dry_run::MachineStateDryRunner;
 use crate::state_controller::machine::handler::MachineStateHandlerBuilder;
 use crate::state_controller::machine::io::MachineStateControllerIO;
+use crate::state_controller::machine::middleware::machine_state_handler_middlewares;
+use crate::state_controller::maintenance_window::{
+    RackMaintenanceWindowMiddleware, SwitchMaintenanceWindowMiddleware,
+};
 use crate::state_controller::network_segment::handler::NetworkSegmentStateHandler;
 use crate::state_controller::network_segment::io::NetworkSegmentStateControllerIO;
+use crate::state_controller::network_segment::middleware::NetworkSegmentMetricsMiddleware;
 use crate::state_controller::power_shelf::handler::PowerShelfStateHandler;
 use crate::state_controller::power_shelf::io::PowerShelfStateControllerIO;
+use crate::state_controller::power_shelf::middleware::PowerShelfDeletionMiddleware;
 use crate::state_controller::rack::handler::RackStateHandler;
 use crate::state_controller::rack::io::RackStateControllerIO;
 use crate::state_controller::spdm::handler::SpdmAttestationStateHandler;
 use crate::state_controller::spdm::io::SpdmStateControllerIO;
 use crate::state_controller::state_change_emitter::StateChangeEmitterBuilder;
+use crate::state_controller::state_handler_middleware::MiddlewareChain;
 use crate::state_controller::switch::handler::SwitchStateHandler;
 use crate::state_controller::switch::io::SwitchStateControllerIO;
 use crate::{attestation, db_init, ethernet_virtualization, listener};
@@ -613,10 +623,12 @@ pub async fn start_api(
         nmxm_pool: shared_nmxm_pool,
         work_lock_manager_handle,
         dpf_sdk: dpf_sdk.clone(),
-        machine_state_handler_enqueuer: Enqueuer::new(db_pool),
+        machine_state_handler_enqueuer: Enqueuer::new(db_pool.clone()),
         metric_emitter: ApiMetricsEmitter::new(&meter),
         component_manager,
         bms_client: std::sync::OnceLock::new(),
+        machine_state_dry_runner: std::sync::OnceLock::new(),
+        state_change_watchers: StateChangeWatchers::new(db_pool.clone(), cancel_token.clone()),
     });
 
     if carbide_config.listen_only {
@@ -820,8 +832,13 @@ pub async fn initialize_and_start_controllers(
             && config.enabled
         {
             let options = {
-                let defaults =
-                    mqttea::client::ClientOptions::default().with_qos(mqttea::QoS::AtMostOnce);
+                // Durable delivery requires the broker to acknowledge publishes
+                let qos = if config.durable_delivery {
+                    mqttea::QoS::AtLeastOnce
+                } else {
+                    mqttea::QoS::AtMostOnce
+                };
+                let defaults = mqttea::client::ClientOptions::default().with_qos(qos);
 
                 if let Some(provider) = crate::auth::mqtt_auth::build_credentials_provider(
                     &config.auth,
@@ -874,14 +891,24 @@ pub async fn initialize_and_start_controllers(
                 .set(bms_client)
                 .map_err(|_| eyre::eyre!("BMS DSX Exchange handle already initialized"))?;
 
-            emitter_builder = emitter_builder.hook(Box::new(MqttStateChangeHook::new(
-                client,
-                join_set,
-                config.publish_timeout,
-                config.queue_capacity,
-                &meter,
-                cancel_token.clone(),
-            )));
+            emitter_builder = if config.durable_delivery {
+                emitter_builder
+                    .durable_hook(Arc::new(DurableMqttStateChangeHook::new(
+                        client,
+                        config.publish_timeout,
+                        &meter,
+                    )))
+                    .outbox_relay_config(config.into())
+            } else {
+                emitter_builder.hook(Box::new(MqttStateChangeHook::new(
+                    client,
+                    join_set,
+                    config.publish_timeout,
+                    config.queue_capacity,
+                    &meter,
+                    cancel_token.clone(),
+                )))
+            };
         }
 
         emitter_builder.build()
@@ -925,6 +952,80 @@ pub async fn initialize_and_start_controllers(
         .to_string_lossy()
         .to_string();
 
+    let machine_state_handler = Arc::new(
+        MachineStateHandlerBuilder::builder()
+            .dpu_up_threshold(carbide_config.machine_state_controller.dpu_up_threshold)
+            .dpu_nic_firmware_reprovision_update_enabled(
+                carbide_config
+                    .dpu_config
+                    .dpu_nic_firmware_reprovision_update_enabled,
+            )
+            .dpu_enable_secure_boot(carbide_config.dpu_config.dpu_enable_secure_boot)
+            .dpu_wait_time(carbide_config.machine_state_controller.dpu_wait_time)
+            .power_down_wait(carbide_config.machine_state_controller.power_down_wait)
+            .failure_retry_time(carbide_config.machine_state_controller.failure_retry_time)
+            .scout_reporting_timeout(
+                carbide_config
+                    .machine_state_controller
+                    .scout_reporting_timeout,
+            )
+            .uefi_boot_wait(carbide_config.machine_state_controller.uefi_boot_wait)
+            .hardware_models(carbide_config.get_firmware_config())
+            .firmware_downloader(&downloader)
+            .attestation_enabled(carbide_config.attestation_enabled)
+            .upload_limiter(upload_limiter.clone())
+            .machine_validation_config(carbide_config.machine_validation_config.clone())
+            .common_pools(common_pools.clone())
+            .bom_validation(carbide_config.bom_validation)
+            .no_firmware_update_reset_retries(carbide_config.firmware_global.no_reset_retries)
+            .instance_autoreboot_period(
+                carbide_config
+                    .machine_updater
+                    .instance_autoreboot_period
+                    .clone(),
+            )
+            .credential_reader(api_service.credential_manager.clone())
+            .power_options_config(carbide_config.power_manager_options.clone().into())
+            .dpf_sdk(dpf_sdk.clone())
+            .meter(&meter)
+            .build(),
+    );
+    let machine_state_controller_io = Arc::new(MachineStateControllerIO {
+        host_health: HostHealthConfig {
+            hardware_health_reports: carbide_config.host_health.hardware_health_reports,
+            dpu_agent_version_staleness_threshold: carbide_config
+                .host_health
+                .dpu_agent_version_staleness_threshold,
+            prevent_allocations_on_stale_dpu_agent_version: carbide_config
+                .host_health
+                .prevent_allocations_on_stale_dpu_agent_version,
+            prevent_allocations_on_scout_heartbeat_timeout: carbide_config
+                .host_health
+                .prevent_allocations_on_scout_heartbeat_timeout,
+            suppress_external_alerting_on_scout_heartbeat_timeout: carbide_config
+                .host_health
+                .suppress_external_alerting_on_scout_heartbeat_timeout,
+        },
+        sla_config: carbide_config.machine_state_controller.sla_config(),
+    });
+    let machine_middlewares = machine_state_handler_middlewares();
+    api_service
+        .machine_state_dry_runner
+        .set(MachineStateDryRunner::new(
+            Arc::new(MiddlewareChain::new(
+                machine_state_handler.clone(),
+                machine_middlewares.clone(),
+                None,
+            )),
+            machine_state_controller_io.clone(),
+            handler_services.clone(),
+            carbide_config
+                .machine_state_controller
+                .controller
+                .max_object_handling_time,
+        ))
+        .map_err(|_| eyre::eyre!("Machine state dry runner already initialized"))?;
+
     // handles need to be stored in a variable
     // If they are assigned to _ then the destructor will be immediately called
     StateController::<MachineStateControllerIO>::builder()
@@ -933,63 +1034,9 @@ pub async fn initialize_and_start_controllers(
         .processor_id(state_controller_id.clone())
         .services(handler_services.clone())
         .iteration_config((&carbide_config.machine_state_controller.controller).into())
-        .state_handler(Arc::new(
-            MachineStateHandlerBuilder::builder()
-                .dpu_up_threshold(carbide_config.machine_state_controller.dpu_up_threshold)
-                .dpu_nic_firmware_reprovision_update_enabled(
-                    carbide_config
-                        .dpu_config
-                        .dpu_nic_firmware_reprovision_update_enabled,
-                )
-                .dpu_enable_secure_boot(carbide_config.dpu_config.dpu_enable_secure_boot)
-                .dpu_wait_time(carbide_config.machine_state_controller.dpu_wait_time)
-                .power_down_wait(carbide_config.machine_state_controller.power_down_wait)
-                .failure_retry_time(carbide_config.machine_state_controller.failure_retry_time)
-                .scout_reporting_timeout(
-                    carbide_config
-                        .machine_state_controller
-                        .scout_reporting_timeout,
-                )
-                .uefi_boot_wait(carbide_config.machine_state_controller.uefi_boot_wait)
-                .hardware_models(carbide_config.get_firmware_config())
-                .firmware_downloader(&downloader)
-                .attestation_enabled(carbide_config.attestation_enabled)
-                .upload_limiter(upload_limiter.clone())
-                .machine_validation_config(carbide_config.machine_validation_config.clone())
-                .common_pools(common_pools.clone())
-                .bom_validation(carbide_config.bom_validation)
-                .no_firmware_update_reset_retries(carbide_config.firmware_global.no_reset_retries)
-                .instance_autoreboot_period(
-                    carbide_config
-                        .machine_updater
-                        .instance_autoreboot_period
-                        .clone(),
-                )
-                .credential_reader(api_service.credential_manager.clone())
-                .power_options_config(carbide_config.power_manager_options.clone().into())
-                .dpf_sdk(dpf_sdk.clone())
-                .build(),
-        ))
-        .io(Arc::new(MachineStateControllerIO {
-            host_health: HostHealthConfig {
-                hardware_health_reports: carbide_config.host_health.hardware_health_reports,
-                dpu_agent_version_staleness_threshold: carbide_config
-                    .host_health
-                    .dpu_agent_version_staleness_threshold,
-                prevent_allocations_on_stale_dpu_agent_version: carbide_config
-                    .host_health
-                    .prevent_allocations_on_stale_dpu_agent_version,
-                prevent_allocations_on_scout_heartbeat_timeout: carbide_config
-                    .host_health
-                    .prevent_allocations_on_scout_heartbeat_timeout,
-                suppress_external_alerting_on_scout_heartbeat_timeout: carbide_config
-                    .host_health
-                    .suppress_external_alerting_on_scout_heartbeat_timeout,
-            },
-            sla_config: model::machine::slas::MachineSlaConfig::new(
-                carbide_config.machine_state_controller.failure_retry_time,
-            ),
-        }))
+        .state_handler(machine_state_handler)
+        .middlewares(machine_middlewares)
+        .io(machine_state_controller_io)
         .state_change_emitter(state_change_emitter)
         .build_and_spawn(join_set, cancel_token.clone())
         .expect("Unable to build MachineStateController");
@@ -1011,6 +1058,7 @@ pub async fn initialize_and_start_controllers(
             sc_pool_vlan_id,
             sc_pool_vni,
         )))
+        .middleware(Arc::new(NetworkSegmentMetricsMiddleware::default()))
         .build_and_spawn(join_set, cancel_token.clone())
         .expect("Unable to build NetworkSegmentController");
 
@@ -1067,6 +1115,7 @@ pub async fn initialize_and_start_controllers(
         .services(handler_services.clone())
         .iteration_config((&carbide_config.power_shelf_state_controller.controller).into())
         .state_handler(Arc::new(PowerShelfStateHandler::default()))
+        .middleware(Arc::new(PowerShelfDeletionMiddleware::default()))
         .build_and_spawn(join_set, cancel_token.clone())
         .expect("Unable to build PowerShelfStateController");
 
@@ -1076,6 +1125,7 @@ pub async fn initialize_and_start_controllers(
         .processor_id(state_controller_id.clone())
         .services(handler_services.clone())
         .state_handler(Arc::new(RackStateHandler::default()))
+        .middleware(Arc::new(RackMaintenanceWindowMiddleware::default()))
         .build_and_spawn(join_set, cancel_token.clone())
         .expect("Unable to build RackStateController");
 
@@ -1086,6 +1136,7 @@ pub async fn initialize_and_start_controllers(
         .services(handler_services.clone())
         .iteration_config((&carbide_config.switch_state_controller.controller).into())
         .state_handler(Arc::new(SwitchStateHandler::default()))
+        .middleware(Arc::new(SwitchMaintenanceWindowMiddleware::default()))
         .build_and_spawn(join_set, cancel_token.clone())
         .expect("Unable to build SwitchStateController");
 
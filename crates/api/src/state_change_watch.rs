@@ -0,0 +1,340 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Fan-out of state transitions to the `Watch*States` RPCs.
+//!
+//! The state controllers distribute objects across all carbide-api instances,
+//! so a transition is usually not processed by the instance that serves a watch
+//! stream. Therefore every instance tails the state history tables, which are
+//! written by all state controllers, and broadcasts new records to its local
+//! subscribers.
+//!
+//! Tailing starts with the first subscription. A trigger publishes a notification
+//! on [`STATE_HISTORY_WRITTEN_CHANNEL`] whenever state history is written, which
+//! wakes up the tailers of all instances. The tables are additionally polled in
+//! case a notification gets lost.
+//!
+//! Records are read in the order of their [`StateHistoryCursor`]. Only records
+//! of transactions which are older than every transaction still in progress are
+//! read, so no record with a smaller cursor can show up later. Every tailer
+//! therefore only needs to remember the cursor of the last record it published.
+
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use carbide_uuid::machine::MachineId;
+use carbide_uuid::rack::RackId;
+use carbide_uuid::switch::SwitchId;
+use chrono::{DateTime, Utc};
+use config_version::ConfigVersion;
+use db::DatabaseError;
+use db::state_history::{STATE_HISTORY_WRITTEN_CHANNEL, StateHistoryEntry, StateHistoryTableId};
+use model::machine::ManagedHostState;
+use model::rack::RackState;
+use model::state_history::StateHistoryCursor;
+use model::switch::SwitchControllerState;
+use serde::de::DeserializeOwned;
+use sqlx::PgPool;
+use sqlx::postgres::PgListener;
+use tokio::sync::{OnceCell, broadcast};
+use tokio_util::sync::CancellationToken;
+
+/// The amount of events which are buffered per subscriber. Subscribers which fall
+/// further behind get disconnected and need to resume the stream.
+const WATCH_CHANNEL_CAPACITY: usize = 1024;
+
+/// The interval in which the state history tables are read if no notification arrives
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The minimum delay between reading the state history tables. Notifications
+/// which arrive in the meantime are handled by a single read.
+const MIN_POLL_DELAY: Duration = Duration::from_millis(100);
+
+/// The amount of records which are read from a state history table at once
+const TAIL_BATCH_SIZE: u32 = 1000;
+
+/// A state transition, as delivered to the subscribers of a [`StateChangeFeed`]
+#[derive(Debug, Clone)]
+pub struct StateChange<Id, S> {
+    /// The ID of the object that changed state
+    pub object_id: Id,
+    /// The state that was entered
+    pub new_state: S,
+    /// The version that was persisted together with `new_state`
+    pub new_version: ConfigVersion,
+    /// The time when the state was entered
+    pub timestamp: DateTime<Utc>,
+    /// The position of the transition in the state history table
+    pub cursor: StateHistoryCursor,
+}
+
+/// Broadcasts the transitions of one object type to any number of subscribers
+pub struct StateChangeFeed<Id, S> {
+    sender: broadcast::Sender<StateChange<Id, S>>,
+}
+
+impl<Id, S> Clone for StateChangeFeed<Id, S> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl<Id: Clone, S: Clone> StateChangeFeed<Id, S> {
+    fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    fn publish(&self, change: StateChange<Id, S>) {
+        // An error here only means that nobody is subscribed
+        let _ = self.sender.send(change);
+    }
+}
+
+/// Feeds for all object types which can be watched
+#[derive(Clone)]
+pub struct StateChangeWatchers {
+    pub machines: StateChangeFeed<MachineId, ManagedHostState>,
+    pub racks: StateChangeFeed<RackId, RackState>,
+    pub switches: StateChangeFeed<SwitchId, SwitchControllerState>,
+    pool: PgPool,
+    cancel_token: CancellationToken,
+    tailing: Arc<OnceCell<()>>,
+}
+
+impl StateChangeWatchers {
+    /// Creates the feeds. Tailing the state history tables using `pool` starts
+    /// with the first subscription, and stops once `cancel_token` is cancelled.
+    pub fn new(pool: PgPool, cancel_token: CancellationToken) -> Self {
+        Self {
+            machines: StateChangeFeed::new(WATCH_CHANNEL_CAPACITY),
+            racks: StateChangeFeed::new(WATCH_CHANNEL_CAPACITY),
+            switches: StateChangeFeed::new(WATCH_CHANNEL_CAPACITY),
+            pool,
+            cancel_token,
+            tailing: Arc::new(OnceCell::new()),
+        }
+    }
+
+    pub async fn subscribe_machines(
+        &self,
+    ) -> Result<broadcast::Receiver<StateChange<MachineId, ManagedHostState>>, DatabaseError> {
+        let receiver = self.machines.sender.subscribe();
+        self.ensure_tailing().await?;
+        Ok(receiver)
+    }
+
+    pub async fn subscribe_racks(
+        &self,
+    ) -> Result<broadcast::Receiver<StateChange<RackId, RackState>>, DatabaseError> {
+        let receiver = self.racks.sender.subscribe();
+        self.ensure_tailing().await?;
+        Ok(receiver)
+    }
+
+    pub async fn subscribe_switches(
+        &self,
+    ) -> Result<broadcast::Receiver<StateChange<SwitchId, SwitchControllerState>>, DatabaseError>
+    {
+        let receiver = self.switches.sender.subscribe();
+        self.ensure_tailing().await?;
+        Ok(receiver)
+    }
+
+    /// Starts tailing the state history tables, unless this already happened.
+    ///
+    /// Returns once the position from which on records get published is
+    /// determined. Every transition that is committed after this call returns
+    /// will be published.
+    async fn ensure_tailing(&self) -> Result<(), DatabaseError> {
+        self.tailing
+            .get_or_try_init(|| async {
+                let mut conn = self.pool.acquire().await.map_err(DatabaseError::acquire)?;
+                let machines = HistoryTailer::start(
+                    &mut conn,
+                    StateHistoryTableId::Machine,
+                    self.machines.clone(),
+                )
+                .await?;
+                let racks =
+                    HistoryTailer::start(&mut conn, StateHistoryTableId::Rack, self.racks.clone())
+                        .await?;
+                let switches = HistoryTailer::start(
+                    &mut conn,
+                    StateHistoryTableId::Switch,
+                    self.switches.clone(),
+                )
+                .await?;
+
+                tokio::spawn(tail_state_history(
+                    self.pool.clone(),
+                    self.cancel_token.clone(),
+                    machines,
+                    racks,
+                    switches,
+                ));
+                Ok::<(), DatabaseError>(())
+            })
+            .await
+            .copied()
+    }
+}
+
+async fn tail_state_history(
+    pool: PgPool,
+    cancel_token: CancellationToken,
+    mut machines: HistoryTailer<MachineId, ManagedHostState>,
+    mut racks: HistoryTailer<RackId, RackState>,
+    mut switches: HistoryTailer<SwitchId, SwitchControllerState>,
+) {
+    let mut listener: Option<PgListener> = None;
+    loop {
+        if listener.is_none() {
+            listener = listen(&pool)
+                .await
+                .inspect_err(|e| {
+                    tracing::warn!(error = %e, "Failed to listen for state history notifications")
+                })
+                .ok();
+        }
+
+        for result in [
+            machines.poll(&pool).await,
+            racks.poll(&pool).await,
+            switches.poll(&pool).await,
+        ] {
+            if let Err(e) = result {
+                tracing::warn!(error = %e, "Failed to read state history");
+            }
+        }
+
+        let notified = async {
+            match listener.as_mut() {
+                Some(listener) => listener.recv().await.map(|_| ()),
+                None => std::future::pending().await,
+            }
+        };
+        let received = tokio::select! {
+            _ = cancel_token.cancelled() => return,
+            received = notified => received,
+            _ = tokio::time::sleep(POLL_INTERVAL) => Ok(()),
+        };
+        if let Err(e) = received {
+            tracing::warn!(error = %e, "Failed to receive state history notification");
+            listener = None;
+        }
+
+        tokio::select! {
+            _ = cancel_token.cancelled() => return,
+            _ = tokio::time::sleep(MIN_POLL_DELAY) => {},
+        }
+    }
+}
+
+async fn listen(pool: &PgPool) -> Result<PgListener, sqlx::Error> {
+    let mut listener = PgListener::connect_with(pool).await?;
+    listener.listen(STATE_HISTORY_WRITTEN_CHANNEL).await?;
+    Ok(listener)
+}
+
+/// Publishes the records of one state history table
+struct HistoryTailer<Id, S> {
+    table_id: StateHistoryTableId,
+    feed: StateChangeFeed<Id, S>,
+    /// The cursor of the newest record that has been published. Records up to
+    /// and including this one were written before tailing started, or have
+    /// already been published.
+    position: Option<StateHistoryCursor>,
+}
+
+impl<Id, S> HistoryTailer<Id, S>
+where
+    Id: FromStr + Clone,
+    S: DeserializeOwned + Clone,
+{
+    async fn start(
+        conn: &mut sqlx::PgConnection,
+        table_id: StateHistoryTableId,
+        feed: StateChangeFeed<Id, S>,
+    ) -> Result<Self, DatabaseError> {
+        let position = db::state_history::latest_cursor(conn, table_id).await?;
+        Ok(Self {
+            table_id,
+            feed,
+            position,
+        })
+    }
+
+    async fn poll(&mut self, pool: &PgPool) -> Result<(), DatabaseError> {
+        let mut conn = pool.acquire().await.map_err(DatabaseError::acquire)?;
+
+        loop {
+            let entries = db::state_history::find_after(
+                &mut conn,
+                self.table_id,
+                &[] as &[String],
+                self.position.as_ref(),
+                TAIL_BATCH_SIZE,
+            )
+            .await?;
+            let done = entries.len() < TAIL_BATCH_SIZE as usize;
+
+            for entry in entries {
+                self.position = Some(entry.cursor);
+                self.publish(entry);
+            }
+
+            if done {
+                return Ok(());
+            }
+        }
+    }
+
+    fn publish(&self, entry: StateHistoryEntry) {
+        let Ok(object_id) = Id::from_str(&entry.object_id) else {
+            tracing::warn!(
+                object_id = %entry.object_id,
+                table = self.table_id.sql_table(),
+                "Skipping state history record with invalid object ID"
+            );
+            return;
+        };
+        let new_state = match serde_json::from_str::<S>(&entry.record.state) {
+            Ok(state) => state,
+            Err(e) => {
+                tracing::warn!(
+                    error = %e,
+                    object_id = %entry.object_id,
+                    table = self.table_id.sql_table(),
+                    "Skipping state history record with invalid state"
+                );
+                return;
+            }
+        };
+        let new_version = entry.record.state_version;
+        self.feed.publish(StateChange {
+            object_id,
+            new_state,
+            new_version,
+            timestamp: entry.record.time.unwrap_or_else(|| new_version.timestamp()),
+            cursor: entry.cursor,
+        });
+    }
+}
@@ -0,0 +1,223 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Dry-run execution of the Machine state handler
+//!
+//! Answers the question "what would the state controller do to this host right
+//! now?" by running the state handler against the current state of the host with
+//! services that can not change anything:
+//! - Redfish operations that would change the state of a BMC are recorded instead
+//!   of being executed. Reads are forwarded to the BMC.
+//! - IPMI operations are recorded instead of being executed.
+//! - The database is accessed via connections whose transactions are read-only.
+//!   Writes which the state handler performs directly instead of enqueueing them in the
+//!   [`DbWriteBatch`](crate::state_controller::db_write_batch::DbWriteBatch) fail, and
+//!   the failure is reported as the outcome of the dry run.
+
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use carbide_ipmi::IPMITool;
+use carbide_redfish::libredfish::recording::{RecordedRedfishCall, RecordingRedfishClientPool};
+use carbide_uuid::machine::MachineId;
+use forge_secrets::credentials::CredentialKey;
+use model::machine::{ManagedHostState, ManagedHostStateSnapshot};
+use sqlx::PgPool;
+use sqlx::postgres::PgPoolOptions;
+
+use crate::state_controller::common_services::CommonStateHandlerServices;
+use crate::state_controller::dry_run::{self, DryRunReport};
+use crate::state_controller::machine::context::MachineStateHandlerContextObjects;
+use crate::state_controller::machine::io::MachineStateControllerIO;
+use crate::state_controller::state_handler::{StateHandler, StateHandlerError};
+
+/// The maximum amount of connections the read-only pool will open
+const MAX_READ_ONLY_CONNECTIONS: u32 = 4;
+
+/// A side effect on an external system that the state handler attempted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedSideEffect {
+    /// The system the side effect targets, e.g. `redfish`
+    pub target: &'static str,
+    /// The BMC the side effect was targeted at
+    pub host: String,
+    pub operation: &'static str,
+    pub details: String,
+}
+
+impl From<RecordedRedfishCall> for RecordedSideEffect {
+    fn from(call: RecordedRedfishCall) -> Self {
+        Self {
+            target: "redfish",
+            host: call.host,
+            operation: call.operation,
+            details: call.details,
+        }
+    }
+}
+
+/// The result of a dry run of the Machine state handler
+#[derive(Debug)]
+pub struct MachineDryRunReport {
+    pub report: DryRunReport<ManagedHostState>,
+    /// Redfish and IPMI operations the state handler attempted, in the order they were called
+    pub side_effects: Vec<RecordedSideEffect>,
+}
+
+/// Runs the Machine state handler for a single host without applying its outcome
+pub struct MachineStateDryRunner {
+    handler: Arc<
+        dyn StateHandler<
+                State = ManagedHostStateSnapshot,
+                ControllerState = ManagedHostState,
+                ContextObjects = MachineStateHandlerContextObjects,
+                ObjectId = MachineId,
+            >,
+    >,
+    io: Arc<MachineStateControllerIO>,
+    services: Arc<CommonStateHandlerServices>,
+    read_only_pool: PgPool,
+    max_object_handling_time: Duration,
+}
+
+impl MachineStateDryRunner {
+    /// Creates a dry runner which uses the same handler, IO and services as the
+    /// Machine state controller
+    pub fn new(
+        handler: Arc<
+            dyn StateHandler<
+                    State = ManagedHostStateSnapshot,
+                    ControllerState = ManagedHostState,
+                    ContextObjects = MachineStateHandlerContextObjects,
+                    ObjectId = MachineId,
+                >,
+        >,
+        io: Arc<MachineStateControllerIO>,
+        services: Arc<CommonStateHandlerServices>,
+        max_object_handling_time: Duration,
+    ) -> Self {
+        let connect_options = services
+            .db_pool
+            .connect_options()
+            .as_ref()
+            .clone()
+            .options([("default_transaction_read_only", "on")]);
+        let read_only_pool = PgPoolOptions::new()
+            .max_connections(MAX_READ_ONLY_CONNECTIONS)
+            .connect_lazy_with(connect_options);
+
+        Self {
+            handler,
+            io,
+            services,
+            read_only_pool,
+            max_object_handling_time,
+        }
+    }
+
+    /// Runs the state handler for the host and reports what it would do.
+    ///
+    /// Returns `Ok(None)` if the host does not exist.
+    pub async fn explain_next_step(
+        &self,
+        machine_id: &MachineId,
+    ) -> Result<Option<MachineDryRunReport>, StateHandlerError> {
+        let redfish_client_pool = Arc::new(RecordingRedfishClientPool::new(
+            self.services.redfish_client_pool.clone(),
+        ));
+        let ipmi_tool = Arc::new(RecordingIpmiTool::default());
+
+        let mut services = CommonStateHandlerServices {
+            db_pool: self.read_only_pool.clone(),
+            db_reader: self.read_only_pool.clone().into(),
+            redfish_client_pool: redfish_client_pool.clone(),
+            ipmi_tool: ipmi_tool.clone(),
+            ..self.services.as_ref().clone()
+        };
+
+        let Some(report) = dry_run::dry_run(
+            self.io.as_ref(),
+            self.handler.as_ref(),
+            machine_id,
+            &self.read_only_pool,
+            &mut services,
+            self.max_object_handling_time,
+        )
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let mut side_effects: Vec<RecordedSideEffect> = redfish_client_pool
+            .recorded_calls()
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        side_effects.extend(ipmi_tool.calls.lock().unwrap().drain(..));
+
+        Ok(Some(MachineDryRunReport {
+            report,
+            side_effects,
+        }))
+    }
+}
+
+/// An [`IPMITool`] which records operations instead of executing them
+#[derive(Default)]
+struct RecordingIpmiTool {
+    calls: Mutex<Vec<RecordedSideEffect>>,
+}
+
+impl RecordingIpmiTool {
+    fn record(&self, bmc_ip: IpAddr, operation: &'static str, details: String) {
+        self.calls.lock().unwrap().push(RecordedSideEffect {
+            target: "ipmi",
+            host: bmc_ip.to_string(),
+            operation,
+            details,
+        });
+    }
+}
+
+#[async_trait]
+impl IPMITool for RecordingIpmiTool {
+    async fn bmc_cold_reset(
+        &self,
+        bmc_ip: IpAddr,
+        _credential_key: &CredentialKey,
+    ) -> Result<(), eyre::Report> {
+        self.record(bmc_ip, "bmc_cold_reset", String::new());
+        Ok(())
+    }
+
+    async fn restart(
+        &self,
+        machine_id: &MachineId,
+        bmc_ip: IpAddr,
+        legacy_boot: bool,
+        _credential_key: &CredentialKey,
+    ) -> Result<(), eyre::Report> {
+        self.record(
+            bmc_ip,
+            "restart",
+            format!("machine_id: {machine_id}, legacy_boot: {legacy_boot}"),
+        );
+        Ok(())
+    }
+}
@@ -60,6 +60,7 @@ use model::instance::status::extension_service::{
 use model::machine::LockdownMode::{self, Enable};
 use model::machine::infiniband::{IbConfigNotSyncedReason, ib_config_synced};
 use model::machine::nvlink::nvlink_config_synced;
+use model::machine::slas;
 use model::machine::{
     BiosConfigInfo, BiosConfigState, BomValidating, BomValidatingContext, CleanupState,
     CreateBossVolumeContext, CreateBossVolumeState, DpuDiscoveringState, DpuInitNextStateResolver,
@@ -76,6 +77,7 @@ use model::machine::{
 use model::power_manager::PowerHandlingOutcome;
 use model::resource_pool::common::CommonPools;
 use model::site_explorer::ExploredEndpoint;
+use opentelemetry::metrics::{Counter, Meter};
 use sku::{handle_bom_validation_requested, handle_bom_validation_state};
 use sqlx::PgConnection;
 use tokio::fs::File;
@@ -94,6 +96,7 @@ use crate::redfish::{
 };
 use crate::state_controller::common_services::CommonStateHandlerServices;
 use crate::state_controller::machine::context::MachineStateHandlerContextObjects;
+use crate::state_controller::machine::metrics;
 use crate::state_controller::machine::{
     MeasuringOutcome, get_measuring_prerequisites, handle_measuring_state,
 };
@@ -113,7 +116,9 @@ use helpers::{
 use rpc::forge_agent_control_response::FileArtifact;
 
 use crate::state_controller::db_write_batch::DbWriteBatch;
-use crate::state_controller::machine::write_ops::MachineWriteOp;
+use crate::state_controller::machine::write_ops::{
+    MachineWriteOp, StateSlaUpdate, StateSlaWriteOp,
+};
 
 // We can't use http::StatusCode because libredfish has a newer version
 const NOT_FOUND: u16 = 404;
@@ -202,6 +207,7 @@ pub struct MachineStateHandler {
     host_upgrade: Arc<HostUpgradeState>,
     power_options_config: PowerOptionConfig,
     enable_secure_boot: bool,
+    state_sla_errors_counter: Option<Counter<u64>>,
 }
 
 pub struct MachineStateHandlerBuilder {
@@ -224,6 +230,7 @@ pub struct MachineStateHandlerBuilder {
     enable_secure_boot: bool,
     hgx_bmc_gpu_reboot_delay: chrono::Duration,
     dpf_sdk: Option<Arc<dyn DpfOperations>>,
+    state_sla_errors_counter: Option<Counter<u64>>,
 }
 
 impl MachineStateHandlerBuilder {
@@ -261,9 +268,17 @@ impl MachineStateHandlerBuilder {
             enable_secure_boot: false,
             hgx_bmc_gpu_reboot_delay: chrono::Duration::seconds(30),
             dpf_sdk: None,
+            state_sla_errors_counter: None,
         }
     }
 
+    /// Registers the metrics which are updated by the writes of the handler.
+    /// This should be the meter of the machine state controller.
+    pub fn meter(mut self, meter: &Meter) -> Self {
+        self.state_sla_errors_counter = Some(metrics::state_sla_errors_counter(meter));
+        self
+    }
+
     pub fn dpf_sdk(mut self, dpf_sdk: Option<Arc<dyn DpfOperations>>) -> Self {
         self.dpf_sdk = dpf_sdk;
         self
@@ -439,6 +454,7 @@ impl MachineStateHandler {
             host_upgrade,
             power_options_config: builder.power_options_config,
             enable_secure_boot: builder.enable_secure_boot,
+            state_sla_errors_counter: builder.state_sla_errors_counter,
         }
     }
 
@@ -560,6 +576,109 @@ impl MachineStateHandler {
             })
     }
 
+    /// Raises a health alert and records a breach if the host stays in its current
+    /// state for longer than the SLA of the state, and clears the alert once the
+    /// host is within the SLA again.
+    ///
+    /// Alerts are only raised for SLAs which are configured in `state_slas`,
+    /// unless alerts for the built-in SLAs are enabled. The changes are applied
+    /// together with the state handling outcome, but failing to apply them does
+    /// not affect it.
+    fn handle_state_sla(
+        &self,
+        mh_snapshot: &ManagedHostStateSnapshot,
+        ctx: &mut StateHandlerContext<'_, MachineStateHandlerContextObjects>,
+    ) {
+        let host_machine_id = &mh_snapshot.host_snapshot.id;
+        let state = &mh_snapshot.host_snapshot.state;
+        let controller_config = &ctx.services.site_config.machine_state_controller;
+        let sla_config = controller_config.sla_config();
+        let state_sla = model::machine::state_sla(
+            host_machine_id,
+            &state.value,
+            &state.version,
+            &mh_snapshot.aggregate_health,
+            &sla_config,
+        );
+        let alerts_enabled = controller_config.builtin_state_sla_alerts
+            || sla_config.state_override(&state.value).is_some();
+        let existing_alert = mh_snapshot
+            .host_snapshot
+            .health_reports
+            .merges
+            .get(slas::HEALTH_REPORT_SOURCE)
+            .and_then(|report| report.alerts.first());
+
+        let Some(sla) = state_sla
+            .sla
+            .filter(|_| state_sla.time_in_state_above_sla && alerts_enabled)
+        else {
+            if existing_alert.is_some() {
+                ctx.pending_db_writes.push(StateSlaWriteOp {
+                    update: StateSlaUpdate::ClearAlert {
+                        machine_id: *host_machine_id,
+                    },
+                    errors_counter: self.state_sla_errors_counter.clone(),
+                });
+                tracing::info!(
+                    host_machine_id = %host_machine_id,
+                    "Host is within state SLA again, removing health alert"
+                );
+            }
+            return;
+        };
+
+        let (state_name, substate_name) = model::machine::state_names(&state.value);
+        let full_state_name = match substate_name.is_empty() {
+            true => state_name.to_string(),
+            false => format!("{state_name}/{substate_name}"),
+        };
+        ctx.metrics.host_with_state_sla_breach =
+            Some((host_machine_id.to_string(), full_state_name.clone()));
+
+        // The time the SLA was exceeded identifies the breach. The alert only
+        // needs to be updated if the host entered a new state since it was raised.
+        let entered_at = state.version.timestamp();
+        let sla_delta = Duration::from_std(sla).unwrap_or(Duration::MAX);
+        let breached_at = entered_at
+            .checked_add_signed(sla_delta)
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+        if existing_alert.is_some_and(|alert| alert.in_alert_since == Some(breached_at)) {
+            return;
+        }
+
+        let message = format!(
+            "Host entered state {full_state_name} at {entered_at} and exceeded the SLA of {}",
+            config_version::format_duration(sla_delta)
+        );
+        let health_report = HealthReport::state_sla_exceeded(
+            slas::HEALTH_REPORT_SOURCE.to_string(),
+            full_state_name.clone(),
+            message,
+            breached_at,
+        );
+        ctx.pending_db_writes.push(StateSlaWriteOp {
+            update: StateSlaUpdate::RaiseAlert {
+                machine_id: *host_machine_id,
+                health_report,
+                state: state.value.clone(),
+                state_version: state.version,
+                state_name: state_name.to_string(),
+                substate_name: substate_name.to_string(),
+                sla,
+            },
+            errors_counter: self.state_sla_errors_counter.clone(),
+        });
+
+        tracing::warn!(
+            host_machine_id = %host_machine_id,
+            state = full_state_name,
+            %entered_at,
+            sla = config_version::format_duration(sla_delta),
+            "Host exceeded state SLA, adding health alert"
+        );
+    }
+
     async fn clear_dpu_reprovision(
         mh_snaphost: &ManagedHostStateSnapshot,
         txn: &mut PgConnection,
@@ -2157,6 +2276,7 @@ impl StateHandler for MachineStateHandler {
 
         self.record_metrics(mh_snapshot, ctx);
         self.record_health_history(mh_snapshot, ctx);
+        self.handle_state_sla(mh_snapshot, ctx);
 
         // Handles power options based on the host's state and configuration settings.
         let PowerHandlingOutcome {
@@ -17,6 +17,8 @@
 
 //! State Controller IO implementation for Machines
 
+use std::collections::HashSet;
+
 use carbide_uuid::machine::MachineId;
 use config_version::{ConfigVersion, Versioned};
 use db::{self, DatabaseError};
@@ -24,15 +26,16 @@ use model::StateSla;
 use model::controller_outcome::PersistentStateHandlerOutcome;
 use model::machine::machine_search_config::MachineSearchConfig;
 use model::machine::slas::MachineSlaConfig;
-use model::machine::{
-    self, DpuDiscoveringState, DpuInitState, HostHealthConfig, MachineValidatingState,
-    ManagedHostState, ManagedHostStateSnapshot, MeasuringState, ValidationState,
-};
+use model::machine::{self, HostHealthConfig, ManagedHostState, ManagedHostStateSnapshot};
 use sqlx::PgConnection;
 
 use crate::state_controller::io::StateControllerIO;
 use crate::state_controller::machine::context::MachineStateHandlerContextObjects;
 use crate::state_controller::machine::metrics::MachineMetricsEmitter;
+use crate::state_controller::queue_priority::QueuePriority;
+
+/// Serialized names of the [`ManagedHostState`]s in which a host impacts a tenant
+const TENANT_IMPACTING_STATES: [&str; 2] = ["assigned", "waitingforcleanup"];
 
 /// State Controller IO implementation for Machines
 #[derive(Default, Debug)]
@@ -68,6 +71,51 @@ impl StateControllerIO for MachineStateControllerIO {
         .await?)
     }
 
+    async fn list_objects_with_priority(
+        &self,
+        txn: &mut PgConnection,
+    ) -> Result<Vec<(Self::ObjectId, QueuePriority)>, DatabaseError> {
+        let machine_ids = self.list_objects(txn).await?;
+
+        // Hosts which are used by tenants or which are being cleaned up after
+        // tenant usage are handled before hosts which are e.g. still being ingested
+        let mut tenant_impacting = HashSet::new();
+        for state in TENANT_IMPACTING_STATES {
+            tenant_impacting.extend(
+                db::machine::find_machine_ids(
+                    &mut *txn,
+                    MachineSearchConfig {
+                        include_predicted_host: true,
+                        controller_state: Some(state.to_string()),
+                        ..Default::default()
+                    },
+                )
+                .await?,
+            );
+        }
+
+        Ok(machine_ids
+            .into_iter()
+            .map(|machine_id| {
+                let priority = if tenant_impacting.contains(&machine_id) {
+                    QueuePriority::TenantImpacting
+                } else {
+                    QueuePriority::Background
+                };
+                (machine_id, priority)
+            })
+            .collect())
+    }
+
+    fn queue_priority(&self, state: &Self::ControllerState) -> QueuePriority {
+        match state {
+            ManagedHostState::Assigned { .. } | ManagedHostState::WaitingForCleanup { .. } => {
+                QueuePriority::TenantImpacting
+            }
+            _ => QueuePriority::Background,
+        }
+    }
+
     /// Loads a state snapshot from the database
     async fn load_object_state(
         &self,
@@ -153,167 +201,7 @@ impl StateControllerIO for MachineStateControllerIO {
     }
 
     fn metric_state_names(state: &ManagedHostState) -> (&'static str, &'static str) {
-        use model::machine::{CleanupState, InstanceState, MachineState};
-
-        fn dpuinit_state_name(dpu_state: &DpuInitState) -> &'static str {
-            match dpu_state {
-                DpuInitState::InstallDpuOs { .. } => "installdpuos",
-                DpuInitState::Init => "init",
-                DpuInitState::WaitingForNetworkInstall => "waitingfornetworkinstall",
-                DpuInitState::WaitingForNetworkConfig => "waitingfornetworkconfig",
-                DpuInitState::WaitingForPlatformConfiguration => "waitingforplatformconfiguration",
-                DpuInitState::PollingBiosSetup => "pollingbiossetup",
-                DpuInitState::WaitingForPlatformPowercycle { .. } => "waitingforplatformpowercycle",
-                DpuInitState::DpfStates { .. } => "dpfstates",
-            }
-        }
-
-        fn machine_state_name(machine_state: &MachineState) -> &'static str {
-            match machine_state {
-                MachineState::Init => "init",
-                MachineState::WaitingForPlatformConfiguration { .. } => {
-                    "waitingforplatformconfiguration"
-                }
-                MachineState::WaitingForBiosJob { .. } => "waitingforbiosjob",
-                MachineState::PollingBiosSetup => "pollingbiossetup",
-                MachineState::SetBootOrder { .. } => "setbootorder",
-                MachineState::UefiSetup { .. } => "uefisetup",
-                MachineState::WaitingForDiscovery => "waitingfordiscovery",
-                MachineState::Discovered { .. } => "discovered",
-                MachineState::WaitingForLockdown { .. } => "waitingforlockdown",
-                MachineState::EnableIpmiOverLan => "enableipmioverlan",
-                MachineState::Measuring { .. } => "machinestatemeasuring",
-            }
-        }
-
-        fn discovering_state_name(discovering_state: &DpuDiscoveringState) -> &'static str {
-            match discovering_state {
-                DpuDiscoveringState::Initializing => "dpuinitializing",
-                DpuDiscoveringState::Configuring => "dpuconfiguring",
-                DpuDiscoveringState::DisableSecureBoot { .. } => "disablesecureboot",
-                DpuDiscoveringState::EnableSecureBoot { .. } => "enablesecureboot",
-                DpuDiscoveringState::SetUefiHttpBoot => "setuefihttpboot",
-                DpuDiscoveringState::RebootAllDPUS => "rebootalldpus",
-                DpuDiscoveringState::EnableRshim => "enablershim",
-            }
-        }
-
-        fn instance_state_name(instance_state: &InstanceState) -> &'static str {
-            match instance_state {
-                InstanceState::Init => "init",
-                InstanceState::WaitingForNetworkSegmentToBeReady => {
-                    "waitingfornetworksegmenttobeready"
-                }
-                InstanceState::WaitingForNetworkConfig => "waitingfornetworkconfig",
-                InstanceState::WaitingForStorageConfig => "waitingforstorageconfig",
-                InstanceState::WaitingForExtensionServicesConfig => {
-                    "waitingforextensionservicesconfig"
-                }
-                InstanceState::WaitingForRebootToReady => "waitingforreboottoready",
-                InstanceState::Ready => "ready",
-                InstanceState::BootingWithDiscoveryImage { .. } => "bootingwithdiscoveryimage",
-                InstanceState::SwitchToAdminNetwork => "switchtoadminnetwork",
-                InstanceState::WaitingForNetworkReconfig => "waitingfornetworkreconfig",
-                InstanceState::DPUReprovision { .. } => "dpureprovisioning",
-                InstanceState::Failed { .. } => "failed",
-                InstanceState::HostReprovision { .. } => "hostreprovisioning",
-                InstanceState::NetworkConfigUpdate { .. } => "networkconfigupdate",
-                InstanceState::WaitingForDpusToUp => "waitingfordpustoup",
-                InstanceState::HostPlatformConfiguration { .. } => "hostplatformconfiguration",
-                InstanceState::DpaProvisioning => "dpaprovisioning",
-                InstanceState::WaitingForDpaToBeReady => "waitingfordpatobeready",
-            }
-        }
-
-        fn measuring_state_name(measuring_state: &MeasuringState) -> &'static str {
-            match measuring_state {
-                MeasuringState::WaitingForMeasurements => "waitingformeasurements",
-                MeasuringState::PendingBundle => "pendingbundle",
-            }
-        }
-
-        fn cleanup_state_name(cleanup_state: &CleanupState) -> &'static str {
-            match cleanup_state {
-                CleanupState::Init => "init",
-                CleanupState::SecureEraseBoss { .. } => "secureeraseboss",
-                CleanupState::HostCleanup { .. } => "hostcleanup",
-                CleanupState::CreateBossVolume { .. } => "createbossvolume",
-                CleanupState::DisableBIOSBMCLockdown => "disablebmclockdown",
-            }
-        }
-
-        fn machine_validation_state_name(
-            validation_state: &MachineValidatingState,
-        ) -> &'static str {
-            match validation_state {
-                MachineValidatingState::MachineValidating { .. } => "machinevalidating",
-                MachineValidatingState::RebootHost { .. } => "reboothost",
-            }
-        }
-        match state {
-            ManagedHostState::DpuDiscoveringState { dpu_states } => {
-                // Min state indicates the least processed DPU. The state machine is blocked
-                // becasue of this.
-                let dpu_state = dpu_states.states.values().min();
-                let Some(dpu_state) = dpu_state else {
-                    return ("unknown", "dpu");
-                };
-                ("dpudiscovering", discovering_state_name(dpu_state))
-            }
-            ManagedHostState::DPUInit { dpu_states } => {
-                // Min state indicates the least processed DPU. The state machine is blocked
-                // becasue of this.
-                let dpu_state = dpu_states.states.values().min();
-                let Some(dpu_state) = dpu_state else {
-                    return ("unknown", "dpu");
-                };
-                ("dpunotready", dpuinit_state_name(dpu_state))
-            }
-            ManagedHostState::HostInit { machine_state } => {
-                ("hostnotready", machine_state_name(machine_state))
-            }
-            ManagedHostState::Ready => ("ready", ""),
-            ManagedHostState::Assigned { instance_state } => {
-                ("assigned", instance_state_name(instance_state))
-            }
-            ManagedHostState::WaitingForCleanup { cleanup_state } => {
-                ("waitingforcleanup", cleanup_state_name(cleanup_state))
-            }
-            ManagedHostState::Created => ("created", ""),
-            ManagedHostState::ForceDeletion => ("forcedeletion", ""),
-            ManagedHostState::Failed { .. } => ("failed", ""),
-            ManagedHostState::DPUReprovision { .. } => ("reprovisioning", ""),
-            ManagedHostState::HostReprovision { .. } => ("hostreprovisioning", ""),
-            ManagedHostState::Measuring { measuring_state } => {
-                ("measuring", measuring_state_name(measuring_state))
-            }
-            ManagedHostState::PostAssignedMeasuring { measuring_state } => (
-                "postassignedmeasuring",
-                measuring_state_name(measuring_state),
-            ),
-            ManagedHostState::BomValidating {
-                bom_validating_state,
-            } => match bom_validating_state {
-                machine::BomValidating::MatchingSku(_) => ("bomvalidating", "matchingsku"),
-                machine::BomValidating::UpdatingInventory(_) => {
-                    ("bomvalidating", "updatinginventory")
-                }
-                machine::BomValidating::VerifyingSku(_) => ("bomvalidating", "verifyingsku"),
-                machine::BomValidating::SkuVerificationFailed(_) => {
-                    ("bomvalidating", "skuverificationfailed")
-                }
-                machine::BomValidating::WaitingForSkuAssignment(_) => {
-                    ("bomvalidating", "waitingforskuassignment")
-                }
-                machine::BomValidating::SkuMissing(_) => ("bomvalidating", "skumissing"),
-            },
-            ManagedHostState::Validation { validation_state } => match validation_state {
-                ValidationState::MachineValidation { machine_validation } => (
-                    "validation",
-                    machine_validation_state_name(machine_validation),
-                ),
-            },
-        }
+        machine::state_names(state)
     }
 
     fn state_sla(
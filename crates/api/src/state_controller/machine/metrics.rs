@@ -23,7 +23,7 @@ use ::carbide_utils::metrics::SharedMetricsHolder;
 use model::hardware_info::MachineInventorySoftwareComponent;
 use model::tenant::TenantOrganizationId;
 use opentelemetry::KeyValue;
-use opentelemetry::metrics::{Histogram, Meter};
+use opentelemetry::metrics::{Counter, Histogram, Meter};
 
 use crate::state_controller::metrics::MetricsEmitter;
 
@@ -67,6 +67,8 @@ pub struct MachineMetrics {
     pub last_machine_validation_list: HashMap<(String, String), i32>,
     /// Machine ID if this host has a scout heartbeat timeout
     pub host_with_scout_heartbeat_timeout: Option<String>,
+    /// Machine ID and state name if this host exceeded the SLA of its current state
+    pub host_with_state_sla_breach: Option<(String, String)>,
 }
 
 #[derive(Debug, Default)]
@@ -101,12 +103,23 @@ pub struct MachineStateControllerIterationMetrics {
     pub hosts_with_bios_password_set: usize,
     pub last_machine_validation_list: HashMap<(String, String), i32>,
     pub hosts_with_scout_heartbeat_timeout: HashSet<String>,
+    /// The set of hosts (by machine_id and state) which exceeded the SLA of their current state
+    pub hosts_with_state_sla_breach: HashSet<(String, String)>,
 }
 
 #[derive(Copy, Clone, Hash, PartialEq, Eq, Debug)]
 pub struct IsInUseByTenant(bool);
 
 #[derive(Debug)]
+/// Creates the counter for state SLA bookkeeping failures, which are otherwise
+/// only logged. It is registered on the meter of the machine state controller.
+pub fn state_sla_errors_counter(meter: &Meter) -> Counter<u64> {
+    meter
+        .u64_counter("carbide_machines_state_sla_errors_count")
+        .with_description("The amount of times the state SLA alert of a host could not be updated")
+        .build()
+}
+
 pub struct MachineMetricsEmitter {
     machine_reboot_attempts_in_booting_with_discovery_image: Histogram<u64>,
     machine_reboot_attempts_in_failed_during_discovery: Histogram<u64>,
@@ -605,6 +618,30 @@ impl MetricsEmitter for MachineMetricsEmitter {
                 })
                 .build()
         };
+        {
+            let metrics = shared_metrics.clone();
+            meter
+                .u64_observable_gauge("carbide_hosts_with_state_sla_breach")
+                .with_description("Hosts which exceeded the SLA of their current state")
+                .with_callback(move |observer| {
+                    metrics.if_available(|metrics, attrs| {
+                        for (machine_id, state) in &metrics.hosts_with_state_sla_breach {
+                            observer.observe(
+                                1u64,
+                                &[
+                                    attrs,
+                                    &[
+                                        KeyValue::new("host_machine_id", machine_id.clone()),
+                                        KeyValue::new("state", state.clone()),
+                                    ],
+                                ]
+                                .concat(),
+                            );
+                        }
+                    })
+                })
+                .build()
+        };
         {
             let metrics = shared_metrics;
             meter
@@ -670,6 +707,12 @@ impl MetricsEmitter for MachineMetricsEmitter {
                 .insert(machine_id.clone());
         }
 
+        if let Some(breach) = &object_metrics.host_with_state_sla_breach {
+            iteration_metrics
+                .hosts_with_state_sla_breach
+                .insert(breach.clone());
+        }
+
         if let Some(tenant) = object_metrics.in_use_by_tenant.as_ref() {
             *iteration_metrics
                 .gpus_in_use_by_tenant
@@ -823,6 +866,7 @@ mod tests {
                 sku: None,
                 sku_device_type: None,
                 host_with_scout_heartbeat_timeout: None,
+                host_with_state_sla_breach: None,
             },
             MachineMetrics {
                 num_gpus: 2,
@@ -879,6 +923,7 @@ mod tests {
                 sku: Some("SkuA".to_string()),
                 sku_device_type: Some("DeviceTypeA".to_string()),
                 host_with_scout_heartbeat_timeout: Some("machine_b".to_string()),
+                host_with_state_sla_breach: None,
             },
             MachineMetrics {
                 num_gpus: 3,
@@ -911,6 +956,7 @@ mod tests {
                 sku: Some("SkuA".to_string()),
                 sku_device_type: Some("DeviceTypeA".to_string()),
                 host_with_scout_heartbeat_timeout: None,
+                host_with_state_sla_breach: None,
             },
             MachineMetrics {
                 num_gpus: 1,
@@ -953,6 +999,10 @@ mod tests {
                 sku: Some("SkuB".to_string()),
                 sku_device_type: Some("DeviceTypeA".to_string()),
                 host_with_scout_heartbeat_timeout: Some("machine_d".to_string()),
+                host_with_state_sla_breach: Some((
+                    "machine_d".to_string(),
+                    "dpunotready/waitingfornetworkconfig".to_string(),
+                )),
             },
             MachineMetrics {
                 num_gpus: 2,
@@ -1019,6 +1069,7 @@ mod tests {
                 sku: Some("SkuC".to_string()),
                 sku_device_type: Some("DeviceTypeC".to_string()),
                 host_with_scout_heartbeat_timeout: None,
+                host_with_state_sla_breach: None,
             },
             MachineMetrics {
                 num_gpus: 3,
@@ -1072,6 +1123,7 @@ mod tests {
                 sku: Some("SkuC".to_string()),
                 sku_device_type: Some("DeviceTypeC".to_string()),
                 host_with_scout_heartbeat_timeout: Some("machine_f".to_string()),
+                host_with_state_sla_breach: None,
             },
         ];
 
@@ -1115,6 +1167,13 @@ mod tests {
                 "machine_f".to_string(),
             ])
         );
+        assert_eq!(
+            iteration_metrics.hosts_with_state_sla_breach,
+            HashSet::from_iter([(
+                "machine_d".to_string(),
+                "dpunotready/waitingfornetworkconfig".to_string()
+            )])
+        );
         assert_eq!(iteration_metrics.gpus_usable, 3);
         assert_eq!(iteration_metrics.gpus_total, 11);
         assert_eq!(iteration_metrics.dpus_up, 6);
@@ -0,0 +1,45 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! State handler middlewares of the Machine state controller
+//!
+//! Health alerts which prevent host state changes and health overrides which
+//! block recovery reboots are enforced by the state handler itself, at the
+//! points where it would change the host. Metrics, health history and state
+//! SLAs are therefore still recorded for those hosts.
+
+use std::sync::Arc;
+
+use carbide_uuid::machine::MachineId;
+use model::machine::{ManagedHostState, ManagedHostStateSnapshot};
+
+use crate::state_controller::machine::context::MachineStateHandlerContextObjects;
+use crate::state_controller::maintenance_window::MachineMaintenanceWindowMiddleware;
+use crate::state_controller::state_handler_middleware::DynStateHandlerMiddleware;
+
+/// A middleware of the Machine state controller
+pub type DynMachineStateHandlerMiddleware = DynStateHandlerMiddleware<
+    MachineId,
+    ManagedHostStateSnapshot,
+    ManagedHostState,
+    MachineStateHandlerContextObjects,
+>;
+
+/// Returns the middlewares of the Machine state controller, in the order in which they run.
+pub fn machine_state_handler_middlewares() -> Vec<DynMachineStateHandlerMiddleware> {
+    vec![Arc::new(MachineMaintenanceWindowMiddleware::default())]
+}
@@ -31,9 +31,11 @@ use model::machine::{
 use super::state_handler::StateHandlerError;
 
 pub mod context;
+pub mod dry_run;
 pub mod handler;
 pub mod io;
 pub mod metrics;
+pub mod middleware;
 pub mod write_ops;
 
 /// Fields of span that should be logged for each message.
@@ -5,8 +5,11 @@ use carbide_uuid::machine::MachineId;
 use chrono::{DateTime, Utc};
 use config_version::ConfigVersion;
 use health_report::{HealthReport, HealthReportApplyMode};
-use model::machine::{MachineLastRebootRequested, MachineLastRebootRequestedMode};
-use sqlx::PgTransaction;
+use model::machine::{
+    MachineLastRebootRequested, MachineLastRebootRequestedMode, ManagedHostState, slas,
+};
+use opentelemetry::metrics::Counter;
+use sqlx::{Acquire, PgTransaction};
 
 use crate::state_controller::db_write_batch::WriteOp;
 use crate::state_controller::state_handler::StateHandlerError;
@@ -200,4 +203,196 @@ impl WriteOp for MachineWriteOp {
         };
         Ok(())
     }
+
+    fn description(&self) -> String {
+        use MachineWriteOp::*;
+        match self {
+            UpdateRebootRequestedTime {
+                machine_id,
+                mode,
+                time,
+            } => format!("Update reboot requested time of {machine_id} to {time} ({mode:?})"),
+            PersistMachineHealthHistory { machine_id, .. } => {
+                format!("Persist health history of {machine_id}")
+            }
+            ResetHostReprovisioningRequest {
+                machine_id,
+                clear_reset,
+            } => format!(
+                "Reset host reprovisioning request of {machine_id} (clear_reset: {clear_reset})"
+            ),
+            UpdateDpuReprovisionStartTime { machine_id, time } => {
+                format!("Update DPU reprovision start time of {machine_id} to {time}")
+            }
+            UpdateHostReprovisionStartTime { machine_id, time } => {
+                format!("Update host reprovision start time of {machine_id} to {time}")
+            }
+            ClearFailureDetails { machine_id } => {
+                format!("Clear failure details of {machine_id}")
+            }
+            UpdateRestartVerificationStatus {
+                machine_id,
+                verified,
+                attempts,
+                ..
+            } => format!(
+                "Update restart verification status of {machine_id} (verified: {verified:?}, attempts: {attempts})"
+            ),
+            UpdateFirmwareVersionByBmcAddress {
+                bmc_address,
+                bmc_version,
+                bios_version,
+            } => format!(
+                "Update firmware versions of BMC {bmc_address} (BMC: {bmc_version}, BIOS: {bios_version})"
+            ),
+            SetTopologyUpdateNeeded { machine_id, value } => {
+                format!("Set topology update needed of {machine_id} to {value}")
+            }
+            SetCustomPxeRebootRequested {
+                machine_id,
+                requested,
+            } => format!("Set custom PXE reboot requested of {machine_id} to {requested}"),
+            InsertMachineHealthReport {
+                machine_id,
+                mode,
+                health_report,
+            } => format!(
+                "Insert health report from {} for {machine_id} ({mode:?})",
+                health_report.source
+            ),
+            ReExploreIfVersionMatches { address, version } => {
+                format!("Re-explore endpoint {address} if its version is {version}")
+            }
+            UseCustomIpxeOnNextBoot {
+                machine_id,
+                boot_with_custom_ipxe,
+            } => format!(
+                "Set use custom iPXE on next boot of {machine_id} to {boot_with_custom_ipxe}"
+            ),
+        }
+    }
+}
+
+/// Updates the state SLA health alert of a host, and records SLA breaches.
+///
+/// SLA bookkeeping must not prevent a host from making progress. The writes are
+/// therefore applied in a savepoint, and failures are logged and counted
+/// instead of failing the other writes of the batch.
+#[derive(Debug)]
+pub struct StateSlaWriteOp {
+    pub update: StateSlaUpdate,
+    /// Counts the failures to apply `update`
+    pub errors_counter: Option<Counter<u64>>,
+}
+
+#[derive(Debug)]
+pub enum StateSlaUpdate {
+    /// The host is within the SLA of its state again
+    ClearAlert { machine_id: MachineId },
+    /// The host exceeded the SLA of its state
+    RaiseAlert {
+        machine_id: MachineId,
+        health_report: HealthReport,
+        state: ManagedHostState,
+        state_version: ConfigVersion,
+        state_name: String,
+        substate_name: String,
+        sla: std::time::Duration,
+    },
+}
+
+impl StateSlaUpdate {
+    fn machine_id(&self) -> &MachineId {
+        match self {
+            StateSlaUpdate::ClearAlert { machine_id }
+            | StateSlaUpdate::RaiseAlert { machine_id, .. } => machine_id,
+        }
+    }
+
+    async fn apply_in(self, txn: &mut sqlx::PgConnection) -> Result<(), StateHandlerError> {
+        match self {
+            StateSlaUpdate::ClearAlert { machine_id } => {
+                db::machine::remove_health_report(
+                    txn,
+                    &machine_id,
+                    HealthReportApplyMode::Merge,
+                    slas::HEALTH_REPORT_SOURCE,
+                )
+                .await?;
+            }
+            StateSlaUpdate::RaiseAlert {
+                machine_id,
+                health_report,
+                state,
+                state_version,
+                state_name,
+                substate_name,
+                sla,
+            } => {
+                db::machine::insert_health_report(
+                    txn,
+                    &machine_id,
+                    HealthReportApplyMode::Merge,
+                    &health_report,
+                    false,
+                )
+                .await?;
+                db::machine_state_sla_breach::record(
+                    txn,
+                    &machine_id,
+                    &state,
+                    state_version,
+                    &state_name,
+                    &substate_name,
+                    sla,
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl WriteOp for StateSlaWriteOp {
+    async fn apply<'a, 't: 'a>(
+        self: Box<Self>,
+        txn: &'a mut PgTransaction<'t>,
+    ) -> Result<(), StateHandlerError> {
+        let machine_id = *self.update.machine_id();
+        let mut savepoint = txn.begin().await?;
+        match self.update.apply_in(&mut savepoint).await {
+            Ok(()) => savepoint.commit().await?,
+            Err(e) => {
+                if let Some(errors_counter) = &self.errors_counter {
+                    errors_counter.add(1, &[]);
+                }
+                tracing::error!(
+                    host_machine_id = %machine_id,
+                    error = %e,
+                    "Failed to update state SLA alert"
+                );
+                savepoint.rollback().await?;
+            }
+        }
+        Ok(())
+    }
+
+    fn description(&self) -> String {
+        match &self.update {
+            StateSlaUpdate::ClearAlert { machine_id } => {
+                format!("Remove state SLA alert of {machine_id}")
+            }
+            StateSlaUpdate::RaiseAlert {
+                machine_id,
+                state_name,
+                substate_name,
+                sla,
+                ..
+            } => format!(
+                "Add state SLA alert for {machine_id} and record breach of the {}s SLA of {state_name}/{substate_name}",
+                sla.as_secs()
+            ),
+        }
+    }
 }
@@ -0,0 +1,204 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! State handler middlewares which defer disruptive transitions of hosts, racks
+//! and switches until a maintenance window which covers them starts.
+
+use carbide_uuid::machine::MachineId;
+use carbide_uuid::rack::RackId;
+use carbide_uuid::switch::SwitchId;
+use chrono::Utc;
+use model::machine::{ManagedHostState, ManagedHostStateSnapshot};
+use model::maintenance_window::{
+    MaintenanceAction, MaintenanceGate, MaintenanceTarget, maintenance_gate,
+};
+use model::rack::{Rack, RackState};
+use model::switch::{Switch, SwitchControllerState};
+
+use crate::state_controller::common_services::CommonStateHandlerServices;
+use crate::state_controller::machine::context::MachineStateHandlerContextObjects;
+use crate::state_controller::rack::context::RackStateHandlerContextObjects;
+use crate::state_controller::state_handler::{
+    StateHandlerContext, StateHandlerError, StateHandlerOutcome,
+};
+use crate::state_controller::state_handler_middleware::{
+    MiddlewareDecision, StateHandlerMiddleware,
+};
+use crate::state_controller::switch::context::SwitchStateHandlerContextObjects;
+
+/// Checks whether `action` may be performed on `target` now.
+/// Returns the outcome to use instead of the state handler if it needs to be deferred.
+async fn check_maintenance_windows<S>(
+    services: &CommonStateHandlerServices,
+    action: MaintenanceAction,
+    target: &MaintenanceTarget<'_>,
+) -> Result<MiddlewareDecision<S>, StateHandlerError> {
+    let now = Utc::now();
+    let mut conn = services.db_pool.acquire().await?;
+    let windows = db::maintenance_window::find_relevant(&mut conn, now).await?;
+
+    Ok(match maintenance_gate(&windows, action, target, now) {
+        MaintenanceGate::Unrestricted | MaintenanceGate::Open(_) => MiddlewareDecision::Proceed,
+        MaintenanceGate::Deferred { next_start } => {
+            MiddlewareDecision::Intercept(StateHandlerOutcome::wait(format!(
+                "{action} is deferred until the maintenance window starting at {next_start}"
+            )))
+        }
+    })
+}
+
+/// Defers host firmware updates, DPU reprovisioning and on-demand machine
+/// validation of hosts in `Ready`
+#[derive(Debug, Default, Clone)]
+pub struct MachineMaintenanceWindowMiddleware {}
+
+#[async_trait::async_trait]
+impl StateHandlerMiddleware for MachineMaintenanceWindowMiddleware {
+    type ObjectId = MachineId;
+    type State = ManagedHostStateSnapshot;
+    type ControllerState = ManagedHostState;
+    type ContextObjects = MachineStateHandlerContextObjects;
+
+    fn name(&self) -> &'static str {
+        "maintenance_window"
+    }
+
+    async fn before_handle(
+        &self,
+        host_machine_id: &MachineId,
+        state: &mut ManagedHostStateSnapshot,
+        controller_state: &ManagedHostState,
+        ctx: &mut StateHandlerContext<MachineStateHandlerContextObjects>,
+    ) -> Result<MiddlewareDecision<ManagedHostState>, StateHandlerError> {
+        // Instance creation takes precedence over maintenance in the state handler,
+        // and is not deferred.
+        if !matches!(controller_state, ManagedHostState::Ready) || state.instance.is_some() {
+            return Ok(MiddlewareDecision::Proceed);
+        }
+        let host = &state.host_snapshot;
+        // Reprovisioning requests of the rack firmware upgrade are already gated
+        // by the maintenance windows of the rack.
+        let action = match host.host_reprovision_requested.as_ref() {
+            Some(req) if req.initiator.starts_with("rack-") => {
+                return Ok(MiddlewareDecision::Proceed);
+            }
+            Some(_) => MaintenanceAction::FirmwareUpdate,
+            // On-demand machine validation reboots the host just like a reprovisioning
+            None if state
+                .dpu_snapshots
+                .iter()
+                .any(|dpu| dpu.reprovision_requested.is_some())
+                || host.on_demand_machine_validation_request == Some(true) =>
+            {
+                MaintenanceAction::Reprovision
+            }
+            None => return Ok(MiddlewareDecision::Proceed),
+        };
+
+        let target = MaintenanceTarget {
+            machine_id: Some(host_machine_id),
+            rack_id: host.rack_id.as_ref(),
+            sku: host.hw_sku.as_deref(),
+            labels: Some(&host.metadata.labels),
+            ..Default::default()
+        };
+        check_maintenance_windows(ctx.services, action, &target).await
+    }
+}
+
+/// Defers rack reprovisioning and on-demand maintenance of racks in `Ready`
+#[derive(Debug, Default, Clone)]
+pub struct RackMaintenanceWindowMiddleware {}
+
+#[async_trait::async_trait]
+impl StateHandlerMiddleware for RackMaintenanceWindowMiddleware {
+    type ObjectId = RackId;
+    type State = Rack;
+    type ControllerState = RackState;
+    type ContextObjects = RackStateHandlerContextObjects;
+
+    fn name(&self) -> &'static str {
+        "maintenance_window"
+    }
+
+    async fn before_handle(
+        &self,
+        rack_id: &RackId,
+        state: &mut Rack,
+        controller_state: &RackState,
+        ctx: &mut StateHandlerContext<RackStateHandlerContextObjects>,
+    ) -> Result<MiddlewareDecision<RackState>, StateHandlerError> {
+        // Topology changes take precedence over maintenance in the state handler,
+        // and are not deferred.
+        if !matches!(controller_state, RackState::Ready) || state.config.topology_changed {
+            return Ok(MiddlewareDecision::Proceed);
+        }
+        let action = if state.config.reprovision_requested {
+            MaintenanceAction::Reprovision
+        } else if state.config.maintenance_requested.is_some() {
+            MaintenanceAction::FirmwareUpdate
+        } else {
+            return Ok(MiddlewareDecision::Proceed);
+        };
+
+        let target = MaintenanceTarget {
+            rack_id: Some(rack_id),
+            labels: Some(&state.metadata.labels),
+            ..Default::default()
+        };
+        check_maintenance_windows(ctx.services, action, &target).await
+    }
+}
+
+/// Defers reprovisioning of switches in `Ready`
+#[derive(Debug, Default, Clone)]
+pub struct SwitchMaintenanceWindowMiddleware {}
+
+#[async_trait::async_trait]
+impl StateHandlerMiddleware for SwitchMaintenanceWindowMiddleware {
+    type ObjectId = SwitchId;
+    type State = Switch;
+    type ControllerState = SwitchControllerState;
+    type ContextObjects = SwitchStateHandlerContextObjects;
+
+    fn name(&self) -> &'static str {
+        "maintenance_window"
+    }
+
+    async fn before_handle(
+        &self,
+        switch_id: &SwitchId,
+        state: &mut Switch,
+        controller_state: &SwitchControllerState,
+        ctx: &mut StateHandlerContext<SwitchStateHandlerContextObjects>,
+    ) -> Result<MiddlewareDecision<SwitchControllerState>, StateHandlerError> {
+        if !matches!(controller_state, SwitchControllerState::Ready)
+            || state.is_marked_as_deleted()
+            || state.switch_reprovisioning_requested.is_none()
+        {
+            return Ok(MiddlewareDecision::Proceed);
+        }
+
+        let target = MaintenanceTarget {
+            switch_id: Some(switch_id),
+            rack_id: state.rack_id.as_ref(),
+            labels: Some(&state.metadata.labels),
+            ..Default::default()
+        };
+        check_maintenance_windows(ctx.services, MaintenanceAction::Reprovision, &target).await
+    }
+}
@@ -19,6 +19,7 @@ pub mod common_services;
 pub mod dpa_interface;
 pub mod ib_partition;
 pub mod machine;
+pub mod maintenance_window;
 pub mod network_segment;
 pub mod power_shelf;
 pub mod rack;
@@ -26,5 +27,6 @@ pub mod spdm;
 pub mod switch;
 
 pub use ::state_controller::{
-    config, controller, db_write_batch, io, metrics, state_change_emitter, state_handler,
+    config, controller, db_write_batch, dry_run, io, metrics, queue_priority, state_change_emitter,
+    state_change_outbox, state_handler, state_handler_middleware,
 };
@@ -0,0 +1,76 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! State handler middlewares for Network Segments
+
+use carbide_uuid::network::NetworkSegmentId;
+use model::network_segment::{NetworkSegment, NetworkSegmentControllerState, NetworkSegmentType};
+
+use crate::state_controller::network_segment::context::NetworkSegmentStateHandlerContextObjects;
+use crate::state_controller::state_handler::{StateHandlerContext, StateHandlerError};
+use crate::state_controller::state_handler_middleware::{
+    MiddlewareDecision, StateHandlerMiddleware,
+};
+
+/// Records the IP allocation metrics of network segments, irrespective of their state
+#[derive(Debug, Default, Clone)]
+pub struct NetworkSegmentMetricsMiddleware {}
+
+#[async_trait::async_trait]
+impl StateHandlerMiddleware for NetworkSegmentMetricsMiddleware {
+    type ObjectId = NetworkSegmentId;
+    type State = NetworkSegment;
+    type ControllerState = NetworkSegmentControllerState;
+    type ContextObjects = NetworkSegmentStateHandlerContextObjects;
+
+    fn name(&self) -> &'static str {
+        "metrics"
+    }
+
+    async fn before_handle(
+        &self,
+        _segment_id: &NetworkSegmentId,
+        state: &mut NetworkSegment,
+        _controller_state: &NetworkSegmentControllerState,
+        ctx: &mut StateHandlerContext<NetworkSegmentStateHandlerContextObjects>,
+    ) -> Result<MiddlewareDecision<NetworkSegmentControllerState>, StateHandlerError> {
+        // If there are no prefixes return.
+        // Also, we don't want to put out stats for Tenant segments, as they are not under our control.
+        if state.prefixes.is_empty() || state.segment_type == NetworkSegmentType::Tenant {
+            return Ok(MiddlewareDecision::Proceed);
+        }
+
+        // The code below assumes that we have only one prefix of type IPV4
+        ctx.metrics.available_ips = state.prefixes[0].num_free_ips as usize;
+        ctx.metrics.reserved_ips = state.prefixes[0].num_reserved as usize;
+        ctx.metrics.seg_name = state.name.clone();
+
+        ctx.metrics.seg_type = state.segment_type.to_string();
+        ctx.metrics.seg_id = state.id.to_string();
+        ctx.metrics.prefix = state.prefixes[0].prefix.to_string();
+
+        let total = state.prefixes[0].prefix.size();
+
+        let total_cnt: u32 = match total {
+            ipnetwork::NetworkSize::V4(nf) => nf,
+            ipnetwork::NetworkSize::V6(_n128) => 0,
+        };
+        ctx.metrics.total_ips = total_cnt as usize;
+
+        Ok(MiddlewareDecision::Proceed)
+    }
+}
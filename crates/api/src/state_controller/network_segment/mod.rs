@@ -21,3 +21,4 @@ pub mod context;
 pub mod handler;
 pub mod io;
 pub mod metrics;
+pub mod middleware;
@@ -37,7 +37,7 @@ impl StateHandler for PowerShelfStateHandler {
     async fn handle_object_state(
         &self,
         power_shelf_id: &PowerShelfId,
-        state: &mut PowerShelf,
+        _state: &mut PowerShelf,
         controller_state: &Self::ControllerState,
         ctx: &mut StateHandlerContext<Self::ContextObjects>,
     ) -> Result<StateHandlerOutcome<PowerShelfControllerState>, StateHandlerError> {
@@ -87,34 +87,24 @@ impl StateHandler for PowerShelfStateHandler {
                 Ok(StateHandlerOutcome::deleted().with_txn(txn))
             }
 
+            // Power shelves which are marked as deleted are moved into `Deleting`
+            // by the `PowerShelfDeletionMiddleware`
             PowerShelfControllerState::Ready => {
                 tracing::info!("PowerShelf is ready");
-                if state.is_marked_as_deleted() {
-                    Ok(StateHandlerOutcome::transition(
-                        PowerShelfControllerState::Deleting,
-                    ))
-                } else {
-                    // TODO: Implement PowerShelf monitoring logic
-                    // This would typically involve:
-                    // 1. Checking PowerShelf health status
-                    // 2. Updating PowerShelf status
-                    // 3. Monitoring power consumption and efficiency
+                // TODO: Implement PowerShelf monitoring logic
+                // This would typically involve:
+                // 1. Checking PowerShelf health status
+                // 2. Updating PowerShelf status
+                // 3. Monitoring power consumption and efficiency
 
-                    // For now, just do nothing
-                    Ok(StateHandlerOutcome::do_nothing())
-                }
+                // For now, just do nothing
+                Ok(StateHandlerOutcome::do_nothing())
             }
 
             PowerShelfControllerState::Error { .. } => {
                 tracing::info!("PowerShelf is in error state");
-                if state.is_marked_as_deleted() {
-                    Ok(StateHandlerOutcome::transition(
-                        PowerShelfControllerState::Deleting,
-                    ))
-                } else {
-                    // If PowerShelf is in error state, keep it there for manual intervention
-                    Ok(StateHandlerOutcome::do_nothing())
-                }
+                // If PowerShelf is in error state, keep it there for manual intervention
+                Ok(StateHandlerOutcome::do_nothing())
             }
         }
     }
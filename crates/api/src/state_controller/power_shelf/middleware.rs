@@ -0,0 +1,67 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! State handler middlewares for Power Shelves
+
+use carbide_uuid::power_shelf::PowerShelfId;
+use model::power_shelf::{PowerShelf, PowerShelfControllerState};
+
+use crate::state_controller::power_shelf::context::PowerShelfStateHandlerContextObjects;
+use crate::state_controller::state_handler::{
+    StateHandlerContext, StateHandlerError, StateHandlerOutcome,
+};
+use crate::state_controller::state_handler_middleware::{
+    MiddlewareDecision, StateHandlerMiddleware,
+};
+
+/// Moves power shelves which are marked as deleted into `Deleting` once they
+/// settled in `Ready` or `Error`
+#[derive(Debug, Default, Clone)]
+pub struct PowerShelfDeletionMiddleware {}
+
+#[async_trait::async_trait]
+impl StateHandlerMiddleware for PowerShelfDeletionMiddleware {
+    type ObjectId = PowerShelfId;
+    type State = PowerShelf;
+    type ControllerState = PowerShelfControllerState;
+    type ContextObjects = PowerShelfStateHandlerContextObjects;
+
+    fn name(&self) -> &'static str {
+        "deletion"
+    }
+
+    async fn before_handle(
+        &self,
+        _power_shelf_id: &PowerShelfId,
+        state: &mut PowerShelf,
+        controller_state: &PowerShelfControllerState,
+        _ctx: &mut StateHandlerContext<PowerShelfStateHandlerContextObjects>,
+    ) -> Result<MiddlewareDecision<PowerShelfControllerState>, StateHandlerError> {
+        if !state.is_marked_as_deleted()
+            || !matches!(
+                controller_state,
+                PowerShelfControllerState::Ready | PowerShelfControllerState::Error { .. }
+            )
+        {
+            return Ok(MiddlewareDecision::Proceed);
+        }
+
+        Ok(MiddlewareDecision::Intercept(
+            StateHandlerOutcome::transition(PowerShelfControllerState::Deleting),
+        ))
+    }
+}
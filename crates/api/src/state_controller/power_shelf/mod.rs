@@ -20,3 +20,4 @@
 pub mod context;
 pub mod handler;
 pub mod io;
+pub mod middleware;
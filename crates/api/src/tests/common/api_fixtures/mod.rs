@@ -112,23 +112,30 @@ use crate::logging::level_filter::ActiveLevel;
 use crate::logging::log_limiter::LogLimiter;
 use crate::rack::rms_client::test_support::RmsSim;
 use crate::scout_stream;
+use crate::state_change_watch::StateChangeWatchers;
 use crate::state_controller::common_services::CommonStateHandlerServices;
 use crate::state_controller::controller::{Enqueuer, StateController};
 use crate::state_controller::ib_partition::handler::IBPartitionStateHandler;
 use crate::state_controller::ib_partition::io::IBPartitionStateControllerIO;
+use crate::state_controller::machine::dry_run::MachineStateDryRunner;
 use crate::state_controller::machine::handler::{
     MachineStateHandler, MachineStateHandlerBuilder, PowerOptionConfig, ReachabilityParams,
 };
 use crate::state_controller::machine::io::MachineStateControllerIO;
+use crate::state_controller::machine::middleware::machine_state_handler_middlewares;
+use crate::state_controller::maintenance_window::SwitchMaintenanceWindowMiddleware;
 use crate::state_controller::network_segment::handler::NetworkSegmentStateHandler;
 use crate::state_controller::network_segment::io::NetworkSegmentStateControllerIO;
+use crate::state_controller::network_segment::middleware::NetworkSegmentMetricsMiddleware;
 use crate::state_controller::power_shelf::handler::PowerShelfStateHandler;
 use crate::state_controller::power_shelf::io::PowerShelfStateControllerIO;
+use crate::state_controller::power_shelf::middleware::PowerShelfDeletionMiddleware;
 use crate::state_controller::spdm::handler::SpdmAttestationStateHandler;
 use crate::state_controller::spdm::io::SpdmStateControllerIO;
 use crate::state_controller::state_handler::{
     StateHandler, StateHandlerContext, StateHandlerError, StateHandlerOutcome,
 };
+use crate::state_controller::state_handler_middleware::MiddlewareChain;
 use crate::state_controller::switch::handler::SwitchStateHandler;
 use crate::state_controller::switch::io::SwitchStateControllerIO;
 use crate::tests::common::api_fixtures::endpoint_explorer::MockEndpointExplorer;
@@ -1131,6 +1138,8 @@ pub fn get_config() -> CarbideConfig {
             controller: StateControllerConfig::default(),
             scout_reporting_timeout: Duration::weeks(52),
             uefi_boot_wait: Duration::seconds(0),
+            state_slas: vec![],
+            builtin_state_sla_alerts: false,
         },
         network_segment_state_controller: NetworkSegmentStateControllerConfig {
             network_segment_drain_time: Duration::seconds(2),
@@ -1239,6 +1248,8 @@ pub fn get_config() -> CarbideConfig {
         external_api_url: None,
         external_pxe_url: None,
         external_static_pxe_url: None,
+        http_boot: crate::cfg::file::HttpBootConfig::default(),
+        tenant_dns_records: crate::cfg::file::TenantDnsRecordsConfig::default(),
         supernic_firmware_profiles: HashMap::default(),
         component_manager: None,
         initial_objects_file: None,
@@ -1528,6 +1539,8 @@ pub async fn create_test_env_with_overrides(
         metric_emitter: ApiMetricsEmitter::new(&test_meter.meter()),
         component_manager: None,
         bms_client: std::sync::OnceLock::new(),
+        machine_state_dry_runner: std::sync::OnceLock::new(),
+        state_change_watchers: StateChangeWatchers::new(db_pool.clone(), cancel_token.clone()),
     });
 
     let attestation_enabled = config.attestation_enabled;
@@ -1557,6 +1570,7 @@ pub async fn create_test_env_with_overrides(
                 )
                 .power_options_config(power_options)
                 .dpf_sdk(dpf_sdk)
+                .meter(&test_meter.meter())
                 .build(),
         )),
     };
@@ -1584,18 +1598,35 @@ pub async fn create_test_env_with_overrides(
 
     let state_controller_id = uuid::Uuid::new_v4().to_string();
 
+    let machine_state_controller_io = Arc::new(MachineStateControllerIO {
+        host_health: config.host_health,
+        sla_config: config.machine_state_controller.sla_config(),
+    });
+    let machine_middlewares = machine_state_handler_middlewares();
+    api.machine_state_dry_runner
+        .set(MachineStateDryRunner::new(
+            Arc::new(MiddlewareChain::new(
+                Arc::new(machine_swap.clone()),
+                machine_middlewares.clone(),
+                None,
+            )),
+            machine_state_controller_io.clone(),
+            handler_services.clone(),
+            config
+                .machine_state_controller
+                .controller
+                .max_object_handling_time,
+        ))
+        .unwrap_or_else(|_| panic!("Machine state dry runner already initialized"));
+
     let machine_controller = StateController::<MachineStateControllerIO>::builder()
         .database(db_pool.clone(), work_lock_manager_handle.clone())
         .meter("carbide_machines", test_meter.meter())
         .processor_id(state_controller_id.clone())
         .services(handler_services.clone())
         .state_handler(Arc::new(machine_swap.clone()))
-        .io(Arc::new(MachineStateControllerIO {
-            host_health: config.host_health,
-            sla_config: model::machine::slas::MachineSlaConfig::new(
-                config.machine_state_controller.failure_retry_time,
-            ),
-        }))
+        .middlewares(machine_middlewares)
+        .io(machine_state_controller_io)
         .build_for_manual_iterations(cancel_token.clone())
         .expect("Unable to build state controller");
 
@@ -1632,30 +1663,33 @@ pub async fn create_test_env_with_overrides(
         ))),
     };
 
-    let mut network_controller = StateController::builder()
+    let mut network_controller = StateController::<NetworkSegmentStateControllerIO>::builder()
         .database(db_pool.clone(), work_lock_manager_handle.clone())
         .meter("carbide_machines", test_meter.meter())
         .processor_id(state_controller_id.clone())
         .services(handler_services.clone())
         .state_handler(Arc::new(network_swap.clone()))
+        .middleware(Arc::new(NetworkSegmentMetricsMiddleware::default()))
         .build_for_manual_iterations(cancel_token.clone())
         .expect("Unable to build state controller");
 
-    let power_shelf_controller = StateController::builder()
+    let power_shelf_controller = StateController::<PowerShelfStateControllerIO>::builder()
         .database(db_pool.clone(), work_lock_manager_handle.clone())
         .meter("carbide_power_shelves", test_meter.meter())
         .processor_id(state_controller_id.clone())
         .services(handler_services.clone())
         .state_handler(Arc::new(PowerShelfStateHandler::default()))
+        .middleware(Arc::new(PowerShelfDeletionMiddleware::default()))
         .build_for_manual_iterations(cancel_token.clone())
         .expect("Unable to build PowerShelfStateController");
 
-    let switch_controller = StateController::builder()
+    let switch_controller = StateController::<SwitchStateControllerIO>::builder()
         .database(db_pool.clone(), work_lock_manager_handle.clone())
         .meter("carbide_switches", test_meter.meter())
         .processor_id(state_controller_id.clone())
         .services(handler_services.clone())
         .state_handler(Arc::new(SwitchStateHandler::default()))
+        .middleware(Arc::new(SwitchMaintenanceWindowMiddleware::default()))
         .build_for_manual_iterations(cancel_token.clone())
         .expect("Unable to build state controller");
 
@@ -1697,6 +1731,8 @@ pub async fn create_test_env_with_overrides(
             force_dpu_nic_mode: Arc::new(false.into()),
             // Tests use MockEndpointExplorer. So this doesn't affect anything.
             explore_mode: SiteExplorerExploreMode::NvRedfish,
+            bmc_event_subscriptions: false,
+            bmc_event_min_interval: std::time::Duration::from_secs(10),
         },
         test_meter.meter(),
         Arc::new(fake_endpoint_explorer.clone()),
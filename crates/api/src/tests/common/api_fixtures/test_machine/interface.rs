@@ -39,6 +39,7 @@ impl TestMachineInterface {
                 arch: arch as i32,
                 interface_id: Some(self.id),
                 product: None,
+                script_format: rpc::forge::BootScriptFormat::Ipxe as i32,
             }))
             .await
             .unwrap()
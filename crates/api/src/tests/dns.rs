@@ -69,6 +69,7 @@ async fn test_dns(pool: sqlx::PgPool) {
                 remote: None,
                 qtype: "A".to_string(),
                 real_remote: None,
+                vpc_id: None,
             },
         ))
         .await
@@ -94,6 +95,7 @@ async fn test_dns(pool: sqlx::PgPool) {
                 remote: None,
                 qname: fqdn2 + ".",
                 real_remote: None,
+                vpc_id: None,
             },
         ))
         .await
@@ -137,6 +139,7 @@ async fn test_dns(pool: sqlx::PgPool) {
                     remote: None,
                     qtype: "A".to_string(),
                     real_remote: None,
+                    vpc_id: None,
                 },
             ))
             .await
@@ -167,6 +170,7 @@ async fn test_dns(pool: sqlx::PgPool) {
                     remote: None,
                     qtype: "A".to_string(),
                     real_remote: None,
+                    vpc_id: None,
                 },
             ))
             .await
@@ -200,6 +204,7 @@ async fn test_dns(pool: sqlx::PgPool) {
                 remote: None,
                 qtype: "A".to_string(),
                 real_remote: None,
+                vpc_id: None,
             },
         ))
         .await
@@ -221,6 +226,7 @@ async fn test_dns(pool: sqlx::PgPool) {
                     remote: None,
                     qtype: "A".to_string(),
                     real_remote: None,
+                    vpc_id: None,
                 },
             ))
             .await
@@ -232,6 +238,230 @@ async fn test_dns(pool: sqlx::PgPool) {
     }
 }
 
+fn dns_record(qname: &str, qtype: &str, content: &str) -> rpc::protos::dns::DnsResourceRecord {
+    rpc::protos::dns::DnsResourceRecord {
+        qname: qname.to_string(),
+        qtype: qtype.to_string(),
+        ttl: 60,
+        content: content.to_string(),
+        domain_id: None,
+        scope_mask: None,
+        auth: None,
+    }
+}
+
+fn add_change(record: rpc::protos::dns::DnsResourceRecord) -> rpc::protos::dns::DnsRecordChange {
+    rpc::protos::dns::DnsRecordChange {
+        change: Some(rpc::protos::dns::dns_record_change::Change::Add(record)),
+    }
+}
+
+#[crate::sqlx_test]
+async fn test_dns_update_and_zone_transfer(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    env.create_vpc_and_tenant_segment().await;
+    let api = &env.api;
+
+    let interface = api
+        .discover_dhcp(DhcpDiscovery::builder("FF:FF:FF:FF:FF:FF", "192.0.2.1").tonic_request())
+        .await
+        .unwrap()
+        .into_inner();
+    let derived_qname = format!("{}.", interface.fqdn);
+
+    let update = |changes: Vec<rpc::protos::dns::DnsRecordChange>| {
+        tonic::Request::new(rpc::protos::dns::UpdateDnsRecordsRequest {
+            domain: format!("{DOMAIN_NAME}."),
+            changes,
+        })
+    };
+    let www = format!("www.{DOMAIN_NAME}.");
+
+    // Updates are refused until a TSIG key is allowed to sign them
+    let status = api
+        .update_dns_records(update(vec![add_change(dns_record(
+            &www,
+            "A",
+            "192.0.2.80",
+        ))]))
+        .await
+        .expect_err("update without TSIG-ALLOW-DNSUPDATE should fail");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    let domain = api
+        .find_domain(tonic::Request::new(rpc::protos::dns::DomainSearchQuery {
+            id: None,
+            name: Some(DOMAIN_NAME.to_string()),
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .domains
+        .remove(0);
+    let serial_before = api
+        .get_domain_info(tonic::Request::new(
+            rpc::protos::dns::GetDomainInfoRequest {
+                name: format!("{DOMAIN_NAME}."),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .serial;
+    api.update_domain(tonic::Request::new(rpc::protos::dns::UpdateDomainRequest {
+        domain: Some(rpc::protos::dns::Domain {
+            metadata: Some(rpc::protos::dns::Metadata {
+                tsig_allow_axfr: vec!["corp-xfr".to_string()],
+                tsig_allow_dnsupdate: vec!["corp-update".to_string()],
+                ..Default::default()
+            }),
+            ..domain
+        }),
+    }))
+    .await
+    .unwrap();
+
+    let metadata = api
+        .get_all_domain_metadata(tonic::Request::new(
+            rpc::protos::dns::DomainMetadataRequest {
+                domain: DOMAIN_NAME.to_string(),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .result
+        .unwrap();
+    assert_eq!(metadata.tsig_allow_dnsupdate, vec!["corp-update"]);
+
+    // Records derived from machine interfaces can't be updated
+    let status = api
+        .update_dns_records(update(vec![add_change(dns_record(
+            &derived_qname,
+            "A",
+            "192.0.2.99",
+        ))]))
+        .await
+        .expect_err("updating a derived record should fail");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    // Names outside of the domain are refused
+    let status = api
+        .update_dns_records(update(vec![add_change(dns_record(
+            "www.example.com.",
+            "A",
+            "192.0.2.99",
+        ))]))
+        .await
+        .expect_err("updating a record in another domain should fail");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    let response = api
+        .update_dns_records(update(vec![
+            add_change(dns_record(&www, "A", "192.0.2.80")),
+            add_change(dns_record(&www, "TXT", "\"hello\"")),
+            // Carbide keeps the SOA itself
+            add_change(dns_record(&format!("{DOMAIN_NAME}."), "SOA", "ignored")),
+        ]))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(response.serial > serial_before as u32);
+
+    let lookup = api
+        .lookup_record(tonic::Request::new(
+            rpc::protos::dns::DnsResourceRecordLookupRequest {
+                qname: www.clone(),
+                zone_id: "-1".to_string(),
+                local: None,
+                remote: None,
+                qtype: "ANY".to_string(),
+                real_remote: None,
+                vpc_id: None,
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    let mut types = lookup
+        .records
+        .iter()
+        .map(|r| r.qtype.as_str())
+        .collect::<Vec<_>>();
+    types.sort();
+    assert_eq!(types, vec!["A", "TXT"]);
+
+    // Replacing with an empty set deletes the records
+    api.update_dns_records(update(vec![rpc::protos::dns::DnsRecordChange {
+        change: Some(rpc::protos::dns::dns_record_change::Change::Replace(
+            rpc::protos::dns::DnsRecordSet {
+                qname: www.clone(),
+                qtype: "TXT".to_string(),
+                records: vec![],
+            },
+        )),
+    }]))
+    .await
+    .unwrap();
+
+    // AXFR: the SOA comes first, followed by derived and stored records
+    let transfer = api
+        .get_all_records_for_domain(tonic::Request::new(
+            rpc::protos::dns::GetAllRecordsForDomainRequest {
+                name: format!("{DOMAIN_NAME}."),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .result;
+    assert_eq!(transfer[0].qtype, "SOA");
+    assert!(
+        transfer
+            .iter()
+            .any(|r| r.qname == derived_qname && r.qtype == "A")
+    );
+    assert!(
+        transfer
+            .iter()
+            .any(|r| r.qname == www && r.qtype == "A" && r.content == "192.0.2.80")
+    );
+    assert!(!transfer.iter().any(|r| r.qname == www && r.qtype == "TXT"));
+
+    // NOTIFY: the domain is reported until its current serial was notified
+    let updated = api
+        .get_updated_primary_domains(tonic::Request::new(
+            rpc::protos::dns::GetUpdatedPrimaryDomainsRequest {},
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .result;
+    let info = updated
+        .iter()
+        .find(|d| d.zone == format!("{DOMAIN_NAME}."))
+        .unwrap();
+    assert_eq!(info.kind, "master");
+
+    api.set_domain_notified(tonic::Request::new(
+        rpc::protos::dns::SetDomainNotifiedRequest {
+            id: info.id,
+            serial: info.serial as u32,
+        },
+    ))
+    .await
+    .unwrap();
+    let updated = api
+        .get_updated_primary_domains(tonic::Request::new(
+            rpc::protos::dns::GetUpdatedPrimaryDomainsRequest {},
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .result;
+    assert!(!updated.iter().any(|d| d.zone == format!("{DOMAIN_NAME}.")));
+}
+
 // test_dns_aaaa verifies that IPv6 addresses in the machine_interface_addresses
 // table produce AAAA DNS records (not A records) in the dns_records view.
 #[crate::sqlx_test]
@@ -279,6 +509,7 @@ async fn test_dns_aaaa(pool: sqlx::PgPool) {
                 remote: None,
                 qtype: "ANY".to_string(),
                 real_remote: None,
+                vpc_id: None,
             },
         ))
         .await
@@ -321,6 +552,7 @@ async fn test_dns_aaaa(pool: sqlx::PgPool) {
                 remote: None,
                 qtype: "ANY".to_string(),
                 real_remote: None,
+                vpc_id: None,
             },
         ))
         .await
@@ -946,6 +946,7 @@ async fn test_instance_dns_resolution(_: PgPoolOptions, options: PgConnectOption
                 remote: None,
                 qtype: "A".to_string(),
                 real_remote: None,
+                vpc_id: None,
             },
         ))
         .await
@@ -1005,6 +1006,7 @@ async fn test_instance_null_hostname(_: PgPoolOptions, options: PgConnectOptions
                 remote: None,
                 qtype: "A".to_string(),
                 real_remote: None,
+                vpc_id: None,
             },
         ))
         .await
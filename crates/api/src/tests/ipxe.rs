@@ -70,6 +70,7 @@ async fn get_pxe_instructions(
             arch: arch as i32,
             interface_id: Some(interface_id),
             product,
+            script_format: rpc::forge::BootScriptFormat::Ipxe as i32,
         }))
         .await
         .unwrap()
@@ -366,6 +367,63 @@ async fn test_pxe_host(pool: sqlx::PgPool) {
     assert!(instructions.pxe_script.contains("x86_64/scout.efi"));
 }
 
+#[crate::sqlx_test]
+async fn test_grub_instructions_for_http_boot_host(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let (host_id, _dpu_id) = common::api_fixtures::create_managed_host(&env).await.into();
+    let mut txn = env
+        .pool
+        .begin()
+        .await
+        .expect("Unable to create transaction on database pool");
+    let host_interface_id = db::machine_interface::find_by_machine_ids(&mut txn, &[host_id])
+        .await
+        .unwrap()[&host_id][0]
+        .id;
+    txn.commit().await.unwrap();
+    move_machine_to_needed_state(
+        host_id,
+        &ManagedHostState::HostInit {
+            machine_state: MachineState::WaitingForDiscovery,
+        },
+        &env.pool,
+    )
+    .await;
+
+    let grub_instructions = |interface_id| {
+        env.api
+            .get_pxe_instructions(tonic::Request::new(rpc::forge::PxeInstructionRequest {
+                arch: rpc::forge::MachineArchitecture::X86 as i32,
+                interface_id: Some(interface_id),
+                product: None,
+                script_format: rpc::forge::BootScriptFormat::Grub as i32,
+            }))
+            .map(|response| response.unwrap().into_inner().pxe_script)
+    };
+
+    let script = grub_instructions(host_interface_id).await;
+    assert!(
+        script.contains("linux ${base_url}/internal/x86_64/scout.efi \""),
+        "Actual script: {script}"
+    );
+    assert!(!script.contains("||"), "Actual script: {script}");
+
+    move_machine_to_needed_state(
+        host_id,
+        &ManagedHostState::Assigned {
+            instance_state: model::machine::InstanceState::WaitingForNetworkSegmentToBeReady,
+        },
+        &env.pool,
+    )
+    .await;
+    let script = grub_instructions(host_interface_id).await;
+    assert!(
+        script.contains("Could not continue boot due to invalid state"),
+        "Actual script: {script}"
+    );
+    assert!(!script.contains("||"), "Actual script: {script}");
+}
+
 #[crate::sqlx_test]
 async fn test_pxe_instance(pool: sqlx::PgPool) {
     let env = create_test_env(pool).await;
@@ -0,0 +1,64 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use common::api_fixtures::{create_managed_host, create_test_env};
+use rpc::forge::forge_server::Forge;
+use rpc::forge::{ExplainMachineNextStepRequest, MachineNextStepKind};
+
+use crate::tests::common;
+
+#[crate::sqlx_test]
+async fn test_explain_machine_next_step_does_not_apply_outcome(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let (host_machine_id, dpu_machine_id) = create_managed_host(&env).await.into();
+
+    let machine_before = env.find_machine(host_machine_id).await.remove(0);
+
+    let response = env
+        .api
+        .explain_machine_next_step(tonic::Request::new(ExplainMachineNextStepRequest {
+            machine_id: Some(host_machine_id),
+        }))
+        .await?
+        .into_inner();
+
+    assert_ne!(response.kind(), MachineNextStepKind::Unspecified);
+    assert_eq!(response.current_version, machine_before.state_version);
+    let current_state: serde_json::Value = serde_json::from_str(&response.current_state)?;
+    assert_eq!(current_state["state"], "ready");
+    if response.kind() == MachineNextStepKind::Transition {
+        assert!(response.next_state.is_some());
+    }
+    assert!(response.source_ref.is_some() || response.kind() == MachineNextStepKind::Error);
+
+    // Nothing the handler intended to do has been applied
+    let machine_after = env.find_machine(host_machine_id).await.remove(0);
+    assert_eq!(machine_after.state, machine_before.state);
+    assert_eq!(machine_after.state_version, machine_before.state_version);
+
+    let err = env
+        .api
+        .explain_machine_next_step(tonic::Request::new(ExplainMachineNextStepRequest {
+            machine_id: Some(dpu_machine_id),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    Ok(())
+}
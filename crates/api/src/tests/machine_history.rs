@@ -277,6 +277,86 @@ async fn test_old_machine_state_history(
     Ok(())
 }
 
+#[crate::sqlx_test]
+async fn test_managed_host_state_timeline(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let (host_machine_id, dpu_machine_id) = create_managed_host(&env).await.into();
+
+    let mut txn = env.pool.begin().await?;
+    let machine = db::machine::find_one(
+        txn.as_mut(),
+        &host_machine_id,
+        model::machine::machine_search_config::MachineSearchConfig::default(),
+    )
+    .await?
+    .unwrap();
+    let history = db::state_history::for_object(
+        &mut txn,
+        db::state_history::StateHistoryTableId::Machine,
+        &host_machine_id,
+    )
+    .await?;
+    let breached_version = history[0].state_version;
+    for expect_recorded in [true, false] {
+        let recorded = db::machine_state_sla_breach::record(
+            &mut txn,
+            &host_machine_id,
+            &ManagedHostState::Created,
+            breached_version,
+            "created",
+            "",
+            std::time::Duration::from_secs(60),
+        )
+        .await?;
+        assert_eq!(recorded, expect_recorded);
+    }
+    txn.commit().await?;
+
+    let timeline = env
+        .api
+        .get_managed_host_state_timeline(tonic::Request::new(
+            rpc::forge::ManagedHostStateTimelineRequest {
+                machine_id: Some(host_machine_id),
+            },
+        ))
+        .await?
+        .into_inner();
+
+    assert_eq!(timeline.entries.len(), history.len());
+    let first = &timeline.entries[0];
+    assert_eq!(first.state_name, "created");
+    assert_eq!(first.version, breached_version.version_string());
+    assert!(first.left_at.is_some());
+    assert!(first.sla.is_some());
+    assert!(first.sla_exceeded);
+    assert!(first.breach_detected_at.is_some());
+    assert!(
+        timeline.entries[1..]
+            .iter()
+            .all(|entry| entry.breach_detected_at.is_none())
+    );
+
+    let last = timeline.entries.last().unwrap();
+    assert_eq!(last.state_name, "ready");
+    assert_eq!(last.version, machine.state.version.version_string());
+    assert!(last.left_at.is_none());
+
+    let err = env
+        .api
+        .get_managed_host_state_timeline(tonic::Request::new(
+            rpc::forge::ManagedHostStateTimelineRequest {
+                machine_id: Some(dpu_machine_id),
+            },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    Ok(())
+}
+
 fn json_history(history: &[StateHistoryRecord]) -> serde_json::Result<Vec<serde_json::Value>> {
     // // Check that version numbers are always incrementing by 1
     if !history.is_empty() {
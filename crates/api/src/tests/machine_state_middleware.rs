@@ -0,0 +1,136 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests for the middlewares of the Machine state controller
+
+use std::sync::Arc;
+
+use health_report::{
+    HealthAlertClassification, HealthProbeAlert, HealthReport, HealthReportApplyMode,
+};
+use model::machine::{FailureCause, FailureDetails, FailureSource, ManagedHostState};
+
+use crate::state_controller::db_write_batch::DbWriteBatch;
+use crate::state_controller::machine::context::MachineStateHandlerContextObjects;
+use crate::state_controller::machine::metrics::MachineMetrics;
+use crate::state_controller::machine::middleware::machine_state_handler_middlewares;
+use crate::state_controller::state_handler::{StateHandler, StateHandlerContext};
+use crate::state_controller::state_handler_middleware::MiddlewareChain;
+use crate::tests::common::api_fixtures::{
+    TestEnvOverrides, create_managed_host, create_test_env_with_overrides, get_config,
+    send_health_report_entry,
+};
+
+/// Hosts whose health prevents state changes and recovery reboots are held by
+/// the state handler, which still records their metrics, health and SLAs.
+#[crate::sqlx_test]
+async fn test_health_blocked_host_still_records_metrics_and_sla(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = get_config();
+    config.machine_state_controller.builtin_state_sla_alerts = true;
+    let env = create_test_env_with_overrides(pool, TestEnvOverrides::with_config(config)).await;
+    let mh = create_managed_host(&env).await;
+
+    // Failed has a built-in SLA of 0, which is exceeded right away
+    let mut txn = env.db_txn().await;
+    db::machine::update_state(
+        &mut txn,
+        &mh.id,
+        &ManagedHostState::Failed {
+            details: FailureDetails {
+                cause: FailureCause::NoError,
+                failed_at: chrono::Utc::now(),
+                source: FailureSource::NoError,
+            },
+            machine_id: mh.id,
+            retry_count: 1,
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    let held = HealthReport {
+        alerts: vec![HealthProbeAlert {
+            id: "Maintenance".parse()?,
+            target: None,
+            in_alert_since: None,
+            message: "Held by operator".to_string(),
+            tenant_message: None,
+            classifications: vec![
+                HealthAlertClassification::prevent_host_state_changes(),
+                HealthAlertClassification::stop_reboot_for_automatic_recovery_from_state_machine(),
+            ],
+        }],
+        ..HealthReport::empty("operator-hold".to_string())
+    };
+    send_health_report_entry(&env, &mh.id, (held, HealthReportApplyMode::Merge)).await;
+
+    let mut txn = env.db_txn().await;
+    let mut snapshot = mh.snapshot(&mut txn).await;
+    txn.commit().await?;
+    let controller_state = snapshot.managed_state.clone();
+
+    let chain = MiddlewareChain::new(
+        Arc::new(env.machine_state_handler.clone()),
+        machine_state_handler_middlewares(),
+        None,
+    );
+    let mut write_batch = DbWriteBatch::new();
+    let mut services = env.state_handler_services();
+    let mut metrics = MachineMetrics::default();
+    let mut ctx = StateHandlerContext::<MachineStateHandlerContextObjects> {
+        services: &mut services,
+        metrics: &mut metrics,
+        pending_db_writes: &mut write_batch,
+    };
+    // Whether the host may move on is up to the state handler; what matters
+    // here is that it got invoked.
+    let _ = chain
+        .handle_object_state(&mh.id, &mut snapshot, &controller_state, &mut ctx)
+        .await;
+
+    assert_eq!(metrics.machine_id, mh.id.to_string());
+    assert!(
+        metrics
+            .health_alert_classifications
+            .contains(&HealthAlertClassification::prevent_host_state_changes())
+    );
+    assert!(metrics.host_with_state_sla_breach.is_some());
+    let writes = write_batch.descriptions();
+    assert!(
+        writes
+            .iter()
+            .any(|write| write.starts_with("Persist health history")),
+        "{writes:?}"
+    );
+    assert!(
+        writes
+            .iter()
+            .any(|write| write.starts_with("Add state SLA alert")),
+        "{writes:?}"
+    );
+    // The reboot itself is suppressed by the health override
+    assert!(
+        !writes
+            .iter()
+            .any(|write| write.starts_with("Update reboot requested time")),
+        "{writes:?}"
+    );
+
+    Ok(())
+}
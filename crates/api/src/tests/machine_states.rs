@@ -32,7 +32,7 @@ use common::api_fixtures::{
     TestEnv, TestManagedHost, create_managed_host, create_managed_host_with_config,
     create_test_env, create_test_env_with_overrides, get_config,
 };
-use health_report::HealthReport;
+use health_report::{HealthReport, HealthReportApplyMode};
 use ipnetwork::IpNetwork;
 use measured_boot::bundle::MeasurementBundle;
 use measured_boot::pcr::PcrRegisterValue;
@@ -44,7 +44,7 @@ use model::machine::health_override::HARDWARE_HEALTH_OVERRIDE_PREFIX;
 use model::machine::{
     DpuInitState, DpuReprovisionStates, FailureCause, FailureDetails, FailureSource, InstanceState,
     LockdownMode, MachineState, MachineValidatingState, ManagedHostState, MeasuringState,
-    ValidationState,
+    ValidationState, slas,
 };
 use rpc::forge::forge_server::Forge;
 use rpc::forge::{HealthReportEntry, InsertMachineHealthReportRequest, TpmCaCert, TpmCaCertId};
@@ -53,6 +53,7 @@ use rpc::machine_discovery::AttestKeyInfo;
 use rpc::{DiscoveryData, DiscoveryInfo};
 use tonic::{Code, Request};
 
+use crate::cfg::file::MachineStateSlaConfig;
 use crate::handlers::measured_boot::rpc_forge::MachineDiscoveryInfo;
 use crate::state_controller::db_write_batch::DbWriteBatch;
 use crate::state_controller::machine::context::MachineStateHandlerContextObjects;
@@ -840,6 +841,7 @@ async fn test_failed_state_host_discovery_recovery(pool: sqlx::PgPool) {
             arch: rpc::forge::MachineArchitecture::X86 as i32,
             interface_id: Some(host.interfaces[0].id),
             product: None,
+            script_format: rpc::forge::BootScriptFormat::Ipxe as i32,
         }))
         .await
         .unwrap()
@@ -1131,6 +1133,126 @@ async fn test_state_sla(pool: sqlx::PgPool) {
     assert_eq!(sla.sla.unwrap(), std::time::Duration::from_secs(0).into());
 }
 
+#[crate::sqlx_test]
+async fn test_state_sla_alert(pool: sqlx::PgPool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = get_config();
+    config.machine_state_controller.state_slas = vec![MachineStateSlaConfig {
+        state: "ready".to_string(),
+        substate: None,
+        sla: std::time::Duration::ZERO,
+    }];
+    let env = create_test_env_with_overrides(pool, TestEnvOverrides::with_config(config)).await;
+    let mh = create_managed_host(&env).await;
+    env.run_machine_state_controller_iteration().await;
+
+    let mut txn = env.db_txn().await;
+    let snapshot = mh.snapshot(&mut txn).await;
+    assert!(
+        snapshot
+            .host_snapshot
+            .health_reports
+            .merges
+            .contains_key(slas::HEALTH_REPORT_SOURCE)
+    );
+    let breaches = db::machine_state_sla_breach::find_by_machine_id(&mut txn, &mh.id).await?;
+    assert_eq!(breaches.len(), 1);
+    assert_eq!(breaches[0].state_name, "ready");
+
+    // Explaining the next step reports the alert, but does not raise it
+    db::machine::remove_health_report(
+        &mut txn,
+        &mh.id,
+        HealthReportApplyMode::Merge,
+        slas::HEALTH_REPORT_SOURCE,
+    )
+    .await?;
+    txn.commit().await?;
+
+    let response = env
+        .api
+        .explain_machine_next_step(Request::new(rpc::forge::ExplainMachineNextStepRequest {
+            machine_id: Some(mh.id),
+        }))
+        .await?
+        .into_inner();
+    assert!(
+        response
+            .db_writes
+            .iter()
+            .any(|write| write.contains("state SLA alert"))
+    );
+    let mut txn = env.db_txn().await;
+    let snapshot = mh.snapshot(&mut txn).await;
+    assert!(
+        !snapshot
+            .host_snapshot
+            .health_reports
+            .merges
+            .contains_key(slas::HEALTH_REPORT_SOURCE)
+    );
+    txn.commit().await?;
+
+    env.run_machine_state_controller_iteration().await;
+    let mut txn = env.db_txn().await;
+    let snapshot = mh.snapshot(&mut txn).await;
+    assert!(
+        snapshot
+            .host_snapshot
+            .health_reports
+            .merges
+            .contains_key(slas::HEALTH_REPORT_SOURCE)
+    );
+    // The breach of the same state is only recorded once
+    let breaches = db::machine_state_sla_breach::find_by_machine_id(&mut txn, &mh.id).await?;
+    assert_eq!(breaches.len(), 1);
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_builtin_state_sla_alerts_are_opt_in(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let mh = create_managed_host(&env).await;
+
+    // Failed has a built-in SLA of 0, which is exceeded right away
+    let mut txn = env.db_txn().await;
+    db::machine::update_state(
+        &mut txn,
+        &mh.id,
+        &ManagedHostState::Failed {
+            details: FailureDetails {
+                cause: FailureCause::NoError,
+                failed_at: chrono::Utc::now(),
+                source: FailureSource::NoError,
+            },
+            machine_id: mh.id,
+            retry_count: 1,
+        },
+    )
+    .await?;
+    txn.commit().await?;
+    let machine = mh.host().rpc_machine().await;
+    assert!(machine.state_sla.unwrap().time_in_state_above_sla);
+
+    let response = env
+        .api
+        .explain_machine_next_step(Request::new(rpc::forge::ExplainMachineNextStepRequest {
+            machine_id: Some(mh.id),
+        }))
+        .await?
+        .into_inner();
+    assert!(
+        !response
+            .db_writes
+            .iter()
+            .any(|write| write.contains("state SLA alert"))
+    );
+
+    Ok(())
+}
+
 /// test_measurement_failed_state_transition is used to test the state
 /// machine changes surrounding measured boot, more specifically, making
 /// sure the handle_measuring_state function works as expected, in terms
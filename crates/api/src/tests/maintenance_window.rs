@@ -0,0 +1,364 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use carbide_uuid::machine::MachineId;
+use chrono::{Duration, Utc};
+use model::machine::ManagedHostState;
+use rpc::forge as rpcf;
+use rpc::forge::dpu_reprovisioning_request::Mode;
+use rpc::forge::forge_server::Forge;
+
+use crate::machine_update_manager::MachineUpdateManager;
+use crate::tests::common::api_fixtures::{
+    TestEnvOverrides, create_managed_host, create_test_env, create_test_env_with_overrides,
+    get_config,
+};
+
+fn create_request(
+    start_offset: Duration,
+    end_offset: Duration,
+    scope: rpcf::MaintenanceWindowScope,
+) -> tonic::Request<rpcf::CreateMaintenanceWindowRequest> {
+    let now = Utc::now();
+    tonic::Request::new(rpcf::CreateMaintenanceWindowRequest {
+        metadata: Some(rpcf::Metadata {
+            name: "window".to_string(),
+            description: "".to_string(),
+            labels: vec![],
+        }),
+        start_time: Some((now + start_offset).into()),
+        end_time: Some((now + end_offset).into()),
+        scope: Some(scope),
+        allowed_actions: vec![rpcf::MaintenanceWindowAction::FirmwareUpdate as i32],
+    })
+}
+
+fn sku_scope(sku: &str) -> rpcf::MaintenanceWindowScope {
+    rpcf::MaintenanceWindowScope {
+        skus: vec![sku.to_string()],
+        ..Default::default()
+    }
+}
+
+fn machine_scope(machine_id: MachineId) -> rpcf::MaintenanceWindowScope {
+    rpcf::MaintenanceWindowScope {
+        machine_ids: vec![machine_id],
+        ..Default::default()
+    }
+}
+
+#[crate::sqlx_test]
+async fn test_create_list_cancel_maintenance_window(
+    db_pool: sqlx::PgPool,
+) -> Result<(), eyre::Report> {
+    let env = create_test_env(db_pool.clone()).await;
+
+    let window = env
+        .api
+        .create_maintenance_window(create_request(
+            Duration::hours(1),
+            Duration::hours(2),
+            sku_scope("sku1"),
+        ))
+        .await?
+        .into_inner();
+    let window_id = window.id.unwrap();
+    assert_eq!(window.metadata.unwrap().name, "window");
+    assert_eq!(window.scope.unwrap().skus, vec!["sku1".to_string()]);
+    assert!(window.cancelled.is_none());
+
+    let windows = env
+        .api
+        .find_maintenance_windows(tonic::Request::new(rpcf::FindMaintenanceWindowsRequest {
+            include_inactive: false,
+        }))
+        .await?
+        .into_inner()
+        .windows;
+    assert_eq!(windows.len(), 1);
+    assert_eq!(windows[0].id, Some(window_id));
+
+    let cancelled = env
+        .api
+        .cancel_maintenance_window(tonic::Request::new(rpcf::CancelMaintenanceWindowRequest {
+            id: Some(window_id),
+        }))
+        .await?
+        .into_inner();
+    assert!(cancelled.cancelled.is_some());
+
+    // Cancelled windows are only listed on request, and no longer affect any actions
+    let windows = env
+        .api
+        .find_maintenance_windows(tonic::Request::new(rpcf::FindMaintenanceWindowsRequest {
+            include_inactive: false,
+        }))
+        .await?
+        .into_inner()
+        .windows;
+    assert!(windows.is_empty());
+    let windows = env
+        .api
+        .find_maintenance_windows(tonic::Request::new(rpcf::FindMaintenanceWindowsRequest {
+            include_inactive: true,
+        }))
+        .await?
+        .into_inner()
+        .windows;
+    assert_eq!(windows.len(), 1);
+
+    let mut txn = db_pool.begin().await?;
+    assert!(
+        db::maintenance_window::find_relevant(&mut txn, Utc::now())
+            .await?
+            .is_empty()
+    );
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_create_invalid_maintenance_window(db_pool: sqlx::PgPool) -> Result<(), eyre::Report> {
+    let env = create_test_env(db_pool.clone()).await;
+
+    // End before start
+    let err = env
+        .api
+        .create_maintenance_window(create_request(
+            Duration::hours(2),
+            Duration::hours(1),
+            sku_scope("sku1"),
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    // Window in the past
+    let err = env
+        .api
+        .create_maintenance_window(create_request(
+            Duration::hours(-2),
+            Duration::hours(-1),
+            sku_scope("sku1"),
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    // Empty scope
+    let err = env
+        .api
+        .create_maintenance_window(create_request(
+            Duration::hours(1),
+            Duration::hours(2),
+            rpcf::MaintenanceWindowScope::default(),
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_cancel_unknown_maintenance_window(db_pool: sqlx::PgPool) -> Result<(), eyre::Report> {
+    let env = create_test_env(db_pool.clone()).await;
+
+    let err = env
+        .api
+        .cancel_maintenance_window(tonic::Request::new(rpcf::CancelMaintenanceWindowRequest {
+            id: Some(carbide_uuid::maintenance_window::MaintenanceWindowId::new()),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_host_firmware_update_deferred_until_maintenance_window(
+    db_pool: sqlx::PgPool,
+) -> Result<(), eyre::Report> {
+    let env = create_test_env(db_pool.clone()).await;
+    let mh = create_managed_host(&env).await;
+
+    let window = env
+        .api
+        .create_maintenance_window(create_request(
+            Duration::hours(1),
+            Duration::hours(2),
+            machine_scope(mh.id),
+        ))
+        .await?
+        .into_inner();
+
+    let update_manager = MachineUpdateManager::new(
+        env.pool.clone(),
+        env.config.clone(),
+        env.test_meter.meter(),
+        env.api.work_lock_manager_handle.clone(),
+    );
+
+    // The host is outdated, but the window which covers it has not started yet
+    update_manager.run_single_iteration().await?;
+    let mut txn = env.pool.begin().await?;
+    let host = mh.host().db_machine(&mut txn).await;
+    assert!(host.host_reprovision_requested.is_none());
+    txn.commit().await?;
+
+    env.api
+        .cancel_maintenance_window(tonic::Request::new(rpcf::CancelMaintenanceWindowRequest {
+            id: window.id,
+        }))
+        .await?;
+
+    update_manager.run_single_iteration().await?;
+    let mut txn = env.pool.begin().await?;
+    let host = mh.host().db_machine(&mut txn).await;
+    assert!(host.host_reprovision_requested.is_some());
+    txn.commit().await?;
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_open_maintenance_window_does_not_bypass_explicit_update_start(
+    db_pool: sqlx::PgPool,
+) -> Result<(), eyre::Report> {
+    let mut config = get_config();
+    config
+        .host_models
+        .get_mut("1")
+        .unwrap()
+        .explicit_start_needed = true;
+    let env =
+        create_test_env_with_overrides(db_pool.clone(), TestEnvOverrides::with_config(config))
+            .await;
+    let mh = create_managed_host(&env).await;
+
+    env.api
+        .create_maintenance_window(create_request(
+            Duration::hours(-1),
+            Duration::hours(1),
+            machine_scope(mh.id),
+        ))
+        .await?;
+
+    let update_manager = MachineUpdateManager::new(
+        env.pool.clone(),
+        env.config.clone(),
+        env.test_meter.meter(),
+        env.api.work_lock_manager_handle.clone(),
+    );
+
+    // The window only allows updates, it does not start them
+    update_manager.run_single_iteration().await?;
+    let mut txn = env.pool.begin().await?;
+    let host = mh.host().db_machine(&mut txn).await;
+    assert!(host.host_reprovision_requested.is_none());
+    txn.commit().await?;
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_dpu_reprovisioning_deferred_until_maintenance_window(
+    db_pool: sqlx::PgPool,
+) -> Result<(), eyre::Report> {
+    let env = create_test_env(db_pool.clone()).await;
+    let mh = create_managed_host(&env).await;
+
+    let mut request = create_request(Duration::hours(1), Duration::hours(2), machine_scope(mh.id));
+    request.get_mut().allowed_actions = vec![rpcf::MaintenanceWindowAction::Reprovision as i32];
+    let window = env
+        .api
+        .create_maintenance_window(request)
+        .await?
+        .into_inner();
+
+    mh.mark_machine_for_updates().await;
+    mh.dpu().trigger_dpu_reprovisioning(Mode::Set, true).await;
+
+    env.run_machine_state_controller_iteration().await;
+    let mut txn = env.pool.begin().await?;
+    let host = mh.host().db_machine(&mut txn).await;
+    assert_eq!(host.current_state(), &ManagedHostState::Ready);
+    let dpu = mh.dpu().db_machine(&mut txn).await;
+    assert!(dpu.reprovision_requested.is_some());
+    txn.commit().await?;
+
+    env.api
+        .cancel_maintenance_window(tonic::Request::new(rpcf::CancelMaintenanceWindowRequest {
+            id: window.id,
+        }))
+        .await?;
+
+    env.run_machine_state_controller_iteration().await;
+    let mut txn = env.pool.begin().await?;
+    let host = mh.host().db_machine(&mut txn).await;
+    assert!(
+        matches!(
+            host.current_state(),
+            ManagedHostState::DPUReprovision { .. }
+        ),
+        "Unexpected state {:?}",
+        host.current_state()
+    );
+    txn.commit().await?;
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_rack_level_host_reprovisioning_is_not_deferred_by_host_windows(
+    db_pool: sqlx::PgPool,
+) -> Result<(), eyre::Report> {
+    let env = create_test_env(db_pool.clone()).await;
+    let mh = create_managed_host(&env).await;
+
+    env.api
+        .create_maintenance_window(create_request(
+            Duration::hours(1),
+            Duration::hours(2),
+            machine_scope(mh.id),
+        ))
+        .await?;
+
+    let mut txn = env.pool.begin().await?;
+    db::host_machine_update::trigger_host_reprovisioning_request(
+        &mut txn,
+        "rack-firmware-upgrade",
+        &mh.id,
+    )
+    .await?;
+    txn.commit().await?;
+
+    env.run_machine_state_controller_iteration().await;
+    let mut txn = env.pool.begin().await?;
+    let host = mh.host().db_machine(&mut txn).await;
+    assert!(
+        matches!(
+            host.current_state(),
+            ManagedHostState::HostReprovision { .. }
+        ),
+        "Unexpected state {:?}",
+        host.current_state()
+    );
+    txn.commit().await?;
+
+    Ok(())
+}
@@ -65,6 +65,7 @@ mod machine_boot_override;
 mod machine_creator;
 mod machine_dhcp;
 mod machine_discovery;
+mod machine_dry_run;
 mod machine_find;
 mod machine_health;
 mod machine_history;
@@ -73,11 +74,13 @@ mod machine_interfaces;
 mod machine_metadata;
 mod machine_network;
 mod machine_power;
+mod machine_state_middleware;
 mod machine_states;
 mod machine_topology;
 pub mod machine_update_manager;
 mod machine_validation;
 mod maintenance;
+mod maintenance_window;
 #[cfg(feature = "linux-build")]
 mod measured_boot;
 mod mqtt_state_change_hook;
@@ -103,11 +106,13 @@ mod rack_state_controller;
 mod redfish_actions;
 mod resource_pool;
 mod route_servers;
+mod sensor_history;
 mod service_health_metrics;
 mod set_primary_dpu;
 mod site_explorer;
 mod sku;
 mod spdm;
+mod state_watch;
 mod static_address_management;
 mod storage;
 mod switch;
@@ -115,6 +120,7 @@ mod switch_find;
 mod switch_health;
 mod switch_metadata;
 mod switch_state_controller;
+mod tenant_dns_record;
 mod tenant_keyset_find;
 mod tenants;
 mod tpm_ca;
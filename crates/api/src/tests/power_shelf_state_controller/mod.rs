@@ -27,6 +27,7 @@ use crate::state_controller::config::IterationConfig;
 use crate::state_controller::controller::StateController;
 use crate::state_controller::power_shelf::handler::PowerShelfStateHandler;
 use crate::state_controller::power_shelf::io::PowerShelfStateControllerIO;
+use crate::state_controller::power_shelf::middleware::PowerShelfDeletionMiddleware;
 use crate::tests::common;
 use crate::tests::common::api_fixtures::create_test_env;
 mod fixtures;
@@ -137,6 +138,7 @@ async fn test_power_shelf_deletion_with_state_controller(
         .processor_id(uuid::Uuid::new_v4().to_string())
         .services(handler_services.clone())
         .state_handler(power_shelf_handler.clone())
+        .middleware(Arc::new(PowerShelfDeletionMiddleware::default()))
         .build_for_manual_iterations(cancel_token.clone())
         .unwrap();
 
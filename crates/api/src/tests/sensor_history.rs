@@ -0,0 +1,133 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use chrono::{Duration, DurationRound, Utc};
+use rpc::forge as rpcf;
+use rpc::forge::forge_server::Forge;
+
+use crate::tests::common::api_fixtures::{create_managed_host, create_test_env};
+
+fn sample(sensor: &str, value: f64, time: chrono::DateTime<Utc>) -> rpcf::SensorSample {
+    rpcf::SensorSample {
+        sensor: sensor.to_string(),
+        name: "CPU0_Temp".to_string(),
+        unit: "celsius".to_string(),
+        value,
+        time: Some(time.into()),
+    }
+}
+
+#[crate::sqlx_test]
+async fn test_record_and_find_sensor_history(db_pool: sqlx::PgPool) -> Result<(), eyre::Report> {
+    let env = create_test_env(db_pool.clone()).await;
+    let host_machine_id = create_managed_host(&env).await.id;
+
+    // All samples are placed into the same 5 minute bucket
+    let bucket = Utc::now().duration_trunc(Duration::minutes(5))? - Duration::minutes(10);
+    env.api
+        .record_machine_sensor_samples(tonic::Request::new(
+            rpcf::RecordMachineSensorSamplesRequest {
+                machine_id: Some(host_machine_id),
+                samples: vec![
+                    sample("cpu0", 40.0, bucket + Duration::seconds(10)),
+                    sample("cpu0", 50.0, bucket + Duration::seconds(70)),
+                    sample("cpu1", 30.0, bucket + Duration::seconds(10)),
+                ],
+            },
+        ))
+        .await?;
+    env.api
+        .record_machine_sensor_samples(tonic::Request::new(
+            rpcf::RecordMachineSensorSamplesRequest {
+                machine_id: Some(host_machine_id),
+                samples: vec![sample("cpu0", 60.0, bucket + Duration::seconds(130))],
+            },
+        ))
+        .await?;
+
+    let history = env
+        .api
+        .find_machine_sensor_history(tonic::Request::new(rpcf::MachineSensorHistoryRequest {
+            machine_id: Some(host_machine_id),
+            sensors: vec![],
+            start_time: None,
+            end_time: None,
+            resolution: rpcf::SensorHistoryResolution::Auto.into(),
+        }))
+        .await?
+        .into_inner();
+    assert_eq!(history.resolution(), rpcf::SensorHistoryResolution::Raw);
+    assert_eq!(history.series.len(), 2);
+    assert_eq!(history.series[0].sensor, "cpu0");
+    assert_eq!(
+        history.series[0]
+            .points
+            .iter()
+            .map(|p| p.avg)
+            .collect::<Vec<_>>(),
+        vec![40.0, 50.0, 60.0]
+    );
+
+    let history = env
+        .api
+        .find_machine_sensor_history(tonic::Request::new(rpcf::MachineSensorHistoryRequest {
+            machine_id: Some(host_machine_id),
+            sensors: vec!["cpu0".to_string()],
+            start_time: Some((Utc::now() - Duration::days(2)).into()),
+            end_time: None,
+            resolution: rpcf::SensorHistoryResolution::Auto.into(),
+        }))
+        .await?
+        .into_inner();
+    assert_eq!(
+        history.resolution(),
+        rpcf::SensorHistoryResolution::Rollup5m
+    );
+    assert_eq!(history.series.len(), 1);
+    let points = &history.series[0].points;
+    assert_eq!(points.len(), 1);
+    assert_eq!(points[0].time, Some(bucket.into()));
+    assert_eq!(points[0].min, 40.0);
+    assert_eq!(points[0].max, 60.0);
+    assert_eq!(points[0].avg, 50.0);
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_record_sensor_samples_requires_time(
+    db_pool: sqlx::PgPool,
+) -> Result<(), eyre::Report> {
+    let env = create_test_env(db_pool.clone()).await;
+    let host_machine_id = create_managed_host(&env).await.id;
+
+    let mut invalid = sample("cpu0", 40.0, Utc::now());
+    invalid.time = None;
+    let err = env
+        .api
+        .record_machine_sensor_samples(tonic::Request::new(
+            rpcf::RecordMachineSensorSamplesRequest {
+                machine_id: Some(host_machine_id),
+                samples: vec![invalid],
+            },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    Ok(())
+}
@@ -991,6 +991,8 @@ async fn test_site_explorer_audit_exploration_results(
         force_dpu_nic_mode: Arc::new(false.into()),
         // Tests use MockEndpointExplorer. So this doesn't affect anything.
         explore_mode: SiteExplorerExploreMode::NvRedfish,
+        bmc_event_subscriptions: false,
+        bmc_event_min_interval: std::time::Duration::from_secs(10),
     };
     let test_meter = TestMeter::default();
     let explorer = SiteExplorer::new(
@@ -0,0 +1,603 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::time::Duration;
+
+use carbide_uuid::rack::RackId;
+use carbide_uuid::switch::SwitchId;
+use common::api_fixtures::{create_managed_host, create_test_env};
+use config_version::ConfigVersion;
+use db::state_history::{StateHistoryEntry, StateHistoryTableId};
+use model::machine::{InstanceState, ManagedHostState};
+use model::rack::RackState;
+use model::switch::SwitchControllerState;
+use rpc::forge::forge_server::Forge;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::tests::common;
+
+const STREAM_TIMEOUT: Duration = Duration::from_secs(5);
+
+async fn next_change<T>(
+    stream: &mut (impl Stream<Item = Result<T, tonic::Status>> + Unpin),
+) -> Result<T, Box<dyn std::error::Error>> {
+    Ok(tokio::time::timeout(STREAM_TIMEOUT, stream.next())
+        .await?
+        .ok_or("stream ended")??)
+}
+
+/// Loads the whole history of a table, in the order in which it is replayed
+async fn load_history(
+    pool: &sqlx::PgPool,
+    table_id: StateHistoryTableId,
+    ids: &[impl std::fmt::Display],
+) -> Result<Vec<StateHistoryEntry>, Box<dyn std::error::Error>> {
+    let mut txn = pool.begin().await?;
+    let history = db::state_history::find_after(&mut txn, table_id, ids, None, 1000).await?;
+    txn.commit().await?;
+    Ok(history)
+}
+
+/// Writes a state history record, the way the state controller of any
+/// carbide-api instance would do it
+async fn record_transition<ID, S>(
+    pool: &sqlx::PgPool,
+    table_id: StateHistoryTableId,
+    object_id: &ID,
+    state: &S,
+    version: ConfigVersion,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    ID: std::fmt::Display + Sync,
+    for<'q> &'q ID: sqlx::Encode<'q, sqlx::Postgres> + sqlx::Type<sqlx::Postgres>,
+    S: serde::Serialize + Sync,
+{
+    let mut txn = pool.begin().await?;
+    db::state_history::persist(&mut txn, table_id, object_id, state, version).await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_watch_managed_host_states_resume(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let (host_machine_id, _dpu_machine_id) = create_managed_host(&env).await.into();
+
+    let history = load_history(&env.pool, StateHistoryTableId::Machine, &[host_machine_id]).await?;
+    assert!(history.len() > 3);
+
+    // Resume from a record in the middle of the history. Everything after it
+    // must be replayed in order.
+    let resume_idx = history.len() - 3;
+    let mut stream = env
+        .api
+        .watch_managed_host_states(tonic::Request::new(
+            rpc::forge::WatchManagedHostStatesRequest {
+                machine_ids: vec![host_machine_id],
+                resume_from_cursor: Some(history[resume_idx].cursor.to_string()),
+            },
+        ))
+        .await?
+        .into_inner();
+
+    for expected in &history[resume_idx + 1..] {
+        let change = next_change(&mut stream).await?;
+        assert!(change.replayed);
+        assert_eq!(change.machine_id, Some(host_machine_id));
+        assert_eq!(change.cursor, expected.cursor.to_string());
+        let state = change.state.unwrap();
+        assert_eq!(
+            state.version,
+            expected.record.state_version.version_string()
+        );
+        assert_eq!(state.state, expected.record.state);
+    }
+
+    // Transitions which are recorded afterwards are streamed live, no matter
+    // which carbide-api instance recorded them
+    let live_version = history.last().unwrap().record.state_version.increment();
+    record_transition(
+        &env.pool,
+        StateHistoryTableId::Machine,
+        &host_machine_id,
+        &ManagedHostState::Ready,
+        live_version,
+    )
+    .await?;
+
+    let change = next_change(&mut stream).await?;
+    assert!(!change.replayed);
+    let state = change.state.unwrap();
+    assert_eq!(state.version, live_version.version_string());
+    assert_eq!(
+        serde_json::from_str::<ManagedHostState>(&state.state)?,
+        ManagedHostState::Ready
+    );
+
+    // The cursor of the live change points at the record that was written
+    let history = load_history(&env.pool, StateHistoryTableId::Machine, &[host_machine_id]).await?;
+    assert_eq!(change.cursor, history.last().unwrap().cursor.to_string());
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_watch_managed_host_states_filters_machines(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let (host_machine_id, dpu_machine_id) = create_managed_host(&env).await.into();
+
+    let mut stream = env
+        .api
+        .watch_managed_host_states(tonic::Request::new(
+            rpc::forge::WatchManagedHostStatesRequest {
+                machine_ids: vec![host_machine_id],
+                resume_from_cursor: None,
+            },
+        ))
+        .await?
+        .into_inner();
+
+    for machine_id in [&dpu_machine_id, &host_machine_id] {
+        record_transition(
+            &env.pool,
+            StateHistoryTableId::Machine,
+            machine_id,
+            &ManagedHostState::Ready,
+            ConfigVersion::new(1000),
+        )
+        .await?;
+    }
+
+    let change = next_change(&mut stream).await?;
+    assert_eq!(change.machine_id, Some(host_machine_id));
+    assert!(!change.replayed);
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_watch_managed_host_states_invalid_cursor(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+
+    for cursor in ["not-a-cursor", "V1-T1700000000000000"] {
+        let err = env
+            .api
+            .watch_managed_host_states(tonic::Request::new(
+                rpc::forge::WatchManagedHostStatesRequest {
+                    machine_ids: vec![],
+                    resume_from_cursor: Some(cursor.to_string()),
+                },
+            ))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    }
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_watch_instance_states(pool: sqlx::PgPool) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let mh = create_managed_host(&env).await;
+    let (instance, _) = mh
+        .instance_builer(&env)
+        .single_interface_network_config(segment_id)
+        .build_and_return()
+        .await;
+    let host_machine_id = mh.id;
+
+    // Resume from the last record before the Instance was created. All
+    // instance states which have been entered since are replayed.
+    let history = load_history(&env.pool, StateHistoryTableId::Machine, &[host_machine_id]).await?;
+    let first_assigned = history
+        .iter()
+        .position(|entry| {
+            matches!(
+                serde_json::from_str(&entry.record.state),
+                Ok(ManagedHostState::Assigned { .. })
+            )
+        })
+        .expect("Machine should have entered Assigned");
+
+    let mut stream = env
+        .api
+        .watch_instance_states(tonic::Request::new(
+            rpc::forge::WatchInstanceStatesRequest {
+                instance_ids: vec![instance.id],
+                resume_from_cursor: Some(history[first_assigned - 1].cursor.to_string()),
+            },
+        ))
+        .await?
+        .into_inner();
+
+    for entry in &history[first_assigned..] {
+        let change = next_change(&mut stream).await?;
+        assert!(change.replayed);
+        assert_eq!(change.instance_id, Some(instance.id));
+        assert_eq!(change.machine_id, Some(host_machine_id));
+        assert_eq!(change.cursor, entry.cursor.to_string());
+    }
+
+    // Host transitions which do not carry an instance state are not streamed
+    let version = history.last().unwrap().record.state_version;
+    record_transition(
+        &env.pool,
+        StateHistoryTableId::Machine,
+        &host_machine_id,
+        &ManagedHostState::Ready,
+        version.increment(),
+    )
+    .await?;
+    let live_version = version.increment().increment();
+    record_transition(
+        &env.pool,
+        StateHistoryTableId::Machine,
+        &host_machine_id,
+        &ManagedHostState::Assigned {
+            instance_state: InstanceState::Ready,
+        },
+        live_version,
+    )
+    .await?;
+
+    let change = next_change(&mut stream).await?;
+    assert!(!change.replayed);
+    assert_eq!(change.instance_id, Some(instance.id));
+    let state = change.state.unwrap();
+    assert_eq!(state.version, live_version.version_string());
+    assert_eq!(
+        serde_json::from_str::<InstanceState>(&state.state)?,
+        InstanceState::Ready
+    );
+
+    Ok(())
+}
+
+/// Instances which are created after the stream was opened are resolved once
+/// their Machine enters the Assigned state
+#[crate::sqlx_test]
+async fn test_watch_instance_states_created_after_subscribing(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let mh = create_managed_host(&env).await;
+
+    let mut stream = env
+        .api
+        .watch_instance_states(tonic::Request::new(
+            rpc::forge::WatchInstanceStatesRequest {
+                instance_ids: vec![],
+                resume_from_cursor: None,
+            },
+        ))
+        .await?
+        .into_inner();
+
+    let (instance, _) = mh
+        .instance_builer(&env)
+        .single_interface_network_config(segment_id)
+        .build_and_return()
+        .await;
+
+    let change = next_change(&mut stream).await?;
+    assert!(!change.replayed);
+    assert_eq!(change.instance_id, Some(instance.id));
+    assert_eq!(change.machine_id, Some(mh.id));
+
+    while let Ok(Some(change)) =
+        tokio::time::timeout(Duration::from_millis(500), stream.next()).await
+    {
+        assert_eq!(change?.instance_id, Some(instance.id));
+    }
+
+    // The host leaves Assigned in the transaction which deletes the instance.
+    // Later transitions of the host must not be reported for the old instance.
+    let version = load_history(&env.pool, StateHistoryTableId::Machine, &[mh.id])
+        .await?
+        .last()
+        .unwrap()
+        .record
+        .state_version;
+    let mut txn = env.pool.begin().await?;
+    db::instance::delete(instance.id, &mut txn).await?;
+    db::state_history::persist(
+        &mut txn,
+        StateHistoryTableId::Machine,
+        &mh.id,
+        &ManagedHostState::Ready,
+        version.increment(),
+    )
+    .await?;
+    txn.commit().await?;
+    record_transition(
+        &env.pool,
+        StateHistoryTableId::Machine,
+        &mh.id,
+        &ManagedHostState::Assigned {
+            instance_state: InstanceState::Ready,
+        },
+        version.increment().increment(),
+    )
+    .await?;
+    assert!(
+        tokio::time::timeout(Duration::from_millis(500), stream.next())
+            .await
+            .is_err()
+    );
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_watch_rack_states(pool: sqlx::PgPool) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let rack_id = RackId::new(uuid::Uuid::new_v4().to_string());
+    let other_rack_id = RackId::new(uuid::Uuid::new_v4().to_string());
+
+    let version = ConfigVersion::initial();
+    for state in [RackState::Created, RackState::Discovering] {
+        record_transition(
+            &env.pool,
+            StateHistoryTableId::Rack,
+            &rack_id,
+            &state,
+            version,
+        )
+        .await?;
+    }
+    let history = load_history(&env.pool, StateHistoryTableId::Rack, &[&rack_id]).await?;
+    assert_eq!(history.len(), 2);
+
+    let mut stream = env
+        .api
+        .watch_rack_states(tonic::Request::new(rpc::forge::WatchRackStatesRequest {
+            rack_ids: vec![rack_id.clone()],
+            resume_from_cursor: Some(history[0].cursor.to_string()),
+        }))
+        .await?
+        .into_inner();
+
+    let change = next_change(&mut stream).await?;
+    assert!(change.replayed);
+    assert_eq!(change.rack_id, Some(rack_id.clone()));
+    assert_eq!(change.cursor, history[1].cursor.to_string());
+
+    for id in [&other_rack_id, &rack_id] {
+        record_transition(
+            &env.pool,
+            StateHistoryTableId::Rack,
+            id,
+            &RackState::Ready,
+            version.increment(),
+        )
+        .await?;
+    }
+    let change = next_change(&mut stream).await?;
+    assert!(!change.replayed);
+    assert_eq!(change.rack_id, Some(rack_id));
+    assert_eq!(
+        serde_json::from_str::<RackState>(&change.state.unwrap().state)?,
+        RackState::Ready
+    );
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_watch_switch_states(pool: sqlx::PgPool) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let switch_id = SwitchId::from(uuid::Uuid::new_v4());
+
+    let mut stream = env
+        .api
+        .watch_switch_states(tonic::Request::new(rpc::forge::WatchSwitchStatesRequest {
+            switch_ids: vec![],
+            resume_from_cursor: None,
+        }))
+        .await?
+        .into_inner();
+
+    let version = ConfigVersion::initial();
+    for (state, version) in [
+        (SwitchControllerState::Created, version),
+        (SwitchControllerState::Ready, version.increment()),
+    ] {
+        record_transition(
+            &env.pool,
+            StateHistoryTableId::Switch,
+            &switch_id,
+            &state,
+            version,
+        )
+        .await?;
+    }
+
+    for expected in [SwitchControllerState::Created, SwitchControllerState::Ready] {
+        let change = next_change(&mut stream).await?;
+        assert!(!change.replayed);
+        assert_eq!(change.switch_id, Some(switch_id));
+        assert_eq!(
+            serde_json::from_str::<SwitchControllerState>(&change.state.unwrap().state)?,
+            expected
+        );
+    }
+
+    Ok(())
+}
+
+/// Records which are written in the same transaction share their transaction ID.
+/// Resuming after one of them must neither repeat nor skip the others.
+#[crate::sqlx_test]
+async fn test_watch_resume_within_transaction(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let rack_ids: Vec<RackId> = (0..3)
+        .map(|_| RackId::new(uuid::Uuid::new_v4().to_string()))
+        .collect();
+
+    let mut txn = env.pool.begin().await?;
+    for rack_id in &rack_ids {
+        db::state_history::persist(
+            &mut txn,
+            StateHistoryTableId::Rack,
+            rack_id,
+            &RackState::Created,
+            ConfigVersion::initial(),
+        )
+        .await?;
+    }
+    txn.commit().await?;
+
+    let history = load_history(&env.pool, StateHistoryTableId::Rack, &rack_ids).await?;
+    assert_eq!(history.len(), 3);
+    assert!(
+        history
+            .iter()
+            .all(|entry| entry.cursor.txid == history[0].cursor.txid)
+    );
+
+    let mut stream = env
+        .api
+        .watch_rack_states(tonic::Request::new(rpc::forge::WatchRackStatesRequest {
+            rack_ids: vec![],
+            resume_from_cursor: Some(history[0].cursor.to_string()),
+        }))
+        .await?
+        .into_inner();
+    for entry in &history[1..] {
+        let change = next_change(&mut stream).await?;
+        assert!(change.replayed);
+        assert_eq!(change.cursor, entry.cursor.to_string());
+    }
+    assert!(
+        tokio::time::timeout(Duration::from_millis(500), stream.next())
+            .await
+            .is_err()
+    );
+
+    Ok(())
+}
+
+/// A transaction which records a transition and commits after a younger
+/// transaction must neither be skipped nor be streamed out of order
+#[crate::sqlx_test]
+async fn test_watch_late_commit(pool: sqlx::PgPool) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let early_rack_id = RackId::new(uuid::Uuid::new_v4().to_string());
+    let late_rack_id = RackId::new(uuid::Uuid::new_v4().to_string());
+
+    let mut stream = env
+        .api
+        .watch_rack_states(tonic::Request::new(rpc::forge::WatchRackStatesRequest {
+            rack_ids: vec![],
+            resume_from_cursor: None,
+        }))
+        .await?
+        .into_inner();
+
+    let mut slow_txn = env.pool.begin().await?;
+    db::state_history::persist(
+        &mut slow_txn,
+        StateHistoryTableId::Rack,
+        &early_rack_id,
+        &RackState::Created,
+        ConfigVersion::initial(),
+    )
+    .await?;
+    record_transition(
+        &env.pool,
+        StateHistoryTableId::Rack,
+        &late_rack_id,
+        &RackState::Created,
+        ConfigVersion::initial(),
+    )
+    .await?;
+
+    // Nothing is streamed while an older transaction is still in progress
+    assert!(
+        tokio::time::timeout(Duration::from_millis(500), stream.next())
+            .await
+            .is_err()
+    );
+    slow_txn.commit().await?;
+
+    let history = load_history(
+        &env.pool,
+        StateHistoryTableId::Rack,
+        &[&early_rack_id, &late_rack_id],
+    )
+    .await?;
+    assert_eq!(history.len(), 2);
+    for (expected_rack_id, entry) in [&early_rack_id, &late_rack_id].into_iter().zip(&history) {
+        let change = next_change(&mut stream).await?;
+        assert!(!change.replayed);
+        assert_eq!(change.rack_id.as_ref(), Some(expected_rack_id));
+        assert_eq!(change.cursor, entry.cursor.to_string());
+    }
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_watch_resume_too_far_behind(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let switch_id = SwitchId::from(uuid::Uuid::new_v4());
+
+    record_transition(
+        &env.pool,
+        StateHistoryTableId::Switch,
+        &switch_id,
+        &SwitchControllerState::Created,
+        ConfigVersion::initial(),
+    )
+    .await?;
+    let history = load_history(&env.pool, StateHistoryTableId::Switch, &[switch_id]).await?;
+
+    sqlx::query(
+        "INSERT INTO switch_state_history (switch_id, state, state_version, timestamp)
+        SELECT $1, '{\"state\": \"ready\"}', 'V' || n || '-T1', NOW() + n * INTERVAL '1 microsecond'
+        FROM generate_series(2, 10002) AS n",
+    )
+    .bind(switch_id.to_string())
+    .execute(&env.pool)
+    .await?;
+
+    let err = env
+        .api
+        .watch_switch_states(tonic::Request::new(rpc::forge::WatchSwitchStatesRequest {
+            switch_ids: vec![switch_id],
+            resume_from_cursor: Some(history[0].cursor.to_string()),
+        }))
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+
+    Ok(())
+}
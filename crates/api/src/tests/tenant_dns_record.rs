@@ -0,0 +1,327 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::vpc::VpcId;
+use rpc::forge::forge_server::Forge;
+use rpc::protos::dns::{
+    DnsResourceRecordLookupRequest, TenantDnsRecordCreationRequest, TenantDnsRecordDeletionRequest,
+    TenantDnsRecordSearchQuery,
+};
+
+use crate::tests::common::api_fixtures::network_segment::{
+    FIXTURE_TENANT_NETWORK_SEGMENT_GATEWAYS, create_tenant_network_segment,
+};
+use crate::tests::common::api_fixtures::{
+    TestEnv, TestEnvOverrides, create_test_env, create_test_env_with_overrides, get_config,
+};
+use crate::tests::common::rpc_builder::{DhcpDiscovery, VpcCreationRequest};
+
+const TENANT_ORG: &str = "2829bbe3-c169-4cd9-8b2a-19a8b1618a93";
+
+async fn test_vpc_id(env: &TestEnv) -> VpcId {
+    env.api
+        .find_vpc_ids(tonic::Request::new(rpc::forge::VpcSearchFilter {
+            name: Some("test vpc 1".to_string()),
+            tenant_org_id: None,
+            label: None,
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .vpc_ids
+        .remove(0)
+}
+
+fn record(
+    vpc_id: VpcId,
+    qname: &str,
+    qtype: &str,
+    content: &str,
+) -> tonic::Request<TenantDnsRecordCreationRequest> {
+    tonic::Request::new(TenantDnsRecordCreationRequest {
+        vpc_id: Some(vpc_id),
+        qname: qname.to_string(),
+        qtype: qtype.to_string(),
+        ttl: None,
+        content: content.to_string(),
+    })
+}
+
+fn lookup(
+    qname: &str,
+    vpc_id: Option<VpcId>,
+    remote: &str,
+    real_remote: Option<&str>,
+) -> tonic::Request<DnsResourceRecordLookupRequest> {
+    tonic::Request::new(DnsResourceRecordLookupRequest {
+        qtype: "ANY".to_string(),
+        qname: qname.to_string(),
+        zone_id: "-1".to_string(),
+        local: None,
+        remote: Some(remote.to_string()),
+        real_remote: real_remote.map(str::to_string),
+        vpc_id,
+    })
+}
+
+async fn lookup_contents(
+    env: &TestEnv,
+    request: tonic::Request<DnsResourceRecordLookupRequest>,
+) -> Vec<String> {
+    env.api
+        .lookup_record(request)
+        .await
+        .unwrap()
+        .into_inner()
+        .records
+        .into_iter()
+        .map(|record| record.content)
+        .collect()
+}
+
+#[crate::sqlx_test]
+async fn test_tenant_dns_records(pool: sqlx::PgPool) {
+    let env = {
+        let mut config = get_config();
+        config
+            .tenant_dns_records
+            .quotas
+            .insert(TENANT_ORG.to_string(), 3);
+        create_test_env_with_overrides(pool, TestEnvOverrides::with_config(config)).await
+    };
+    env.create_vpc_and_tenant_segment().await;
+    let api = &env.api;
+    let vpc_id = test_vpc_id(&env).await;
+
+    let db_record = api
+        .create_tenant_dns_record(record(vpc_id, "DB.dwrt1.com", "A", "192.0.4.10"))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(db_record.qname, "db.dwrt1.com.");
+    assert_eq!(db_record.tenant_organization_id, TENANT_ORG);
+    assert_eq!(db_record.ttl, 300);
+
+    // Only queries received on the VPC's resolver listener see the record
+    let records = api
+        .lookup_record(lookup("db.dwrt1.com.", Some(vpc_id), "10.0.0.53", None))
+        .await
+        .unwrap()
+        .into_inner()
+        .records;
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].content, "192.0.4.10");
+    assert_eq!(records[0].scope_mask, None);
+
+    let records = lookup_contents(&env, lookup("db.dwrt1.com.", None, "192.0.4.20", None)).await;
+    assert!(
+        records.is_empty(),
+        "record visible outside the VPC listener"
+    );
+
+    // Addresses and names must be inside the VPC
+    let status = api
+        .create_tenant_dns_record(record(vpc_id, "web.dwrt1.com", "A", "10.0.0.1"))
+        .await
+        .expect_err("address outside the VPC prefixes should be rejected");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    let status = api
+        .create_tenant_dns_record(record(vpc_id, "web.example.com", "A", "192.0.4.11"))
+        .await
+        .expect_err("name outside the VPC domains should be rejected");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    let status = api
+        .create_tenant_dns_record(record(vpc_id, "db.dwrt1.com", "MX", "10 mail.dwrt1.com"))
+        .await
+        .expect_err("MX records should be rejected");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    // Names derived from machine interfaces can't be shadowed
+    let interface = api
+        .discover_dhcp(DhcpDiscovery::builder("FF:FF:FF:FF:FF:FF", "192.0.2.1").tonic_request())
+        .await
+        .unwrap()
+        .into_inner();
+    let status = api
+        .create_tenant_dns_record(record(vpc_id, &interface.fqdn, "A", "192.0.4.11"))
+        .await
+        .expect_err("derived names should be rejected");
+    assert_eq!(status.code(), tonic::Code::AlreadyExists);
+
+    // A CNAME excludes all other records of the same name
+    api.create_tenant_dns_record(record(vpc_id, "www.dwrt1.com", "CNAME", "db.dwrt1.com"))
+        .await
+        .unwrap();
+    let status = api
+        .create_tenant_dns_record(record(vpc_id, "www.dwrt1.com", "TXT", "hello"))
+        .await
+        .expect_err("records next to a CNAME should be rejected");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    api.create_tenant_dns_record(record(
+        vpc_id,
+        "_postgres._tcp.dwrt1.com",
+        "SRV",
+        "0 5 5432 db.dwrt1.com",
+    ))
+    .await
+    .unwrap();
+
+    let status = api
+        .create_tenant_dns_record(record(vpc_id, "db.dwrt1.com", "TXT", "primary"))
+        .await
+        .expect_err("records beyond the quota should be rejected");
+    assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+
+    // Tenant records are not part of zone transfers
+    let zone = api
+        .get_all_records_for_domain(tonic::Request::new(
+            rpc::protos::dns::GetAllRecordsForDomainRequest {
+                name: "dwrt1.com.".to_string(),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(zone.result.iter().all(|r| r.qname != "db.dwrt1.com."));
+
+    let records = api
+        .find_tenant_dns_records(tonic::Request::new(TenantDnsRecordSearchQuery {
+            vpc_id: Some(vpc_id),
+            tenant_organization_id: None,
+            qname: None,
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .records;
+    assert_eq!(records.len(), 3);
+
+    api.delete_tenant_dns_record(tonic::Request::new(TenantDnsRecordDeletionRequest {
+        id: db_record.id,
+    }))
+    .await
+    .unwrap();
+    let status = api
+        .delete_tenant_dns_record(tonic::Request::new(TenantDnsRecordDeletionRequest {
+            id: db_record.id,
+        }))
+        .await
+        .expect_err("deleting a record twice should fail");
+    assert_eq!(status.code(), tonic::Code::NotFound);
+
+    // Deleting frees quota
+    api.create_tenant_dns_record(record(vpc_id, "db.dwrt1.com", "TXT", "primary"))
+        .await
+        .unwrap();
+}
+
+/// VPCs can use the same address space, so the query source can't tell which
+/// VPC's records to serve.
+#[crate::sqlx_test]
+async fn test_tenant_dns_records_overlapping_vpcs(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    env.create_vpc_and_tenant_segment().await;
+    let vpc_1 = test_vpc_id(&env).await;
+    let vpc_2 = env
+        .api
+        .create_vpc(VpcCreationRequest::builder("test vpc 2", TENANT_ORG).tonic_request())
+        .await
+        .unwrap()
+        .into_inner()
+        .id
+        .unwrap();
+    create_tenant_network_segment(
+        &env.api,
+        Some(vpc_2),
+        FIXTURE_TENANT_NETWORK_SEGMENT_GATEWAYS[1],
+        "TENANT_2",
+        true,
+    )
+    .await;
+    env.run_network_segment_controller_iteration().await;
+    env.run_network_segment_controller_iteration().await;
+
+    env.api
+        .create_tenant_dns_record(record(vpc_1, "db.dwrt1.com", "A", "192.0.4.10"))
+        .await
+        .unwrap();
+    env.api
+        .create_tenant_dns_record(record(vpc_2, "db.dwrt1.com", "A", "192.1.4.10"))
+        .await
+        .unwrap();
+
+    // The same client, e.g. an address both VPCs use, sees the records of the
+    // VPC whose listener it queried
+    for (vpc_id, expected) in [(vpc_1, "192.0.4.10"), (vpc_2, "192.1.4.10")] {
+        for real_remote in [None, Some("192.0.4.0/24"), Some("192.1.4.0/24")] {
+            let records = lookup_contents(
+                &env,
+                lookup("db.dwrt1.com.", Some(vpc_id), "192.0.4.20", real_remote),
+            )
+            .await;
+            assert_eq!(records, [expected], "{vpc_id} with subnet {real_remote:?}");
+        }
+    }
+}
+
+/// The client address and EDNS client subnet are controlled by the client or
+/// its resolver and must not grant access to a VPC's records.
+#[crate::sqlx_test]
+async fn test_tenant_dns_records_spoofed_client_subnet(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    env.create_vpc_and_tenant_segment().await;
+    let vpc_id = test_vpc_id(&env).await;
+    let other_vpc_id = env
+        .api
+        .create_vpc(VpcCreationRequest::builder("test vpc 2", TENANT_ORG).tonic_request())
+        .await
+        .unwrap()
+        .into_inner()
+        .id
+        .unwrap();
+
+    env.api
+        .create_tenant_dns_record(record(vpc_id, "db.dwrt1.com", "A", "192.0.4.10"))
+        .await
+        .unwrap();
+
+    for (listener, remote, real_remote) in [
+        (None, "192.0.4.20", None),
+        (None, "10.0.0.53", Some("192.0.4.0/24")),
+        (None, "10.0.0.53", Some("192.0.4.20/32")),
+        (Some(other_vpc_id), "192.0.4.20", Some("192.0.4.0/24")),
+    ] {
+        let records =
+            lookup_contents(&env, lookup("db.dwrt1.com.", listener, remote, real_remote)).await;
+        assert!(
+            records.is_empty(),
+            "record visible to {remote} with subnet {real_remote:?} on {listener:?}"
+        );
+    }
+
+    // Behind a resolver which strips the client subnet, the listener still
+    // identifies the VPC
+    let records = lookup_contents(
+        &env,
+        lookup("db.dwrt1.com.", Some(vpc_id), "10.0.0.53", None),
+    )
+    .await;
+    assert_eq!(records, ["192.0.4.10"]);
+}
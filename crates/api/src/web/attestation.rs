@@ -104,6 +104,7 @@ pub async fn show_attestation_results(
     // get report
     let request = tonic::Request::new(mbprotos::ShowMeasurementReportForIdRequest {
         report_id: Some(latest_journal.report_id),
+        reference_report_id: None,
     });
     let report = match state.show_measurement_report_for_id(request).await {
         Ok(resp) => match resp.into_inner().report {
@@ -20,10 +20,12 @@ use std::sync::Arc;
 
 use askama::Template;
 use axum::Json;
-use axum::extract::{Path as AxumPath, State as AxumState};
+use axum::extract::{Path as AxumPath, Query, State as AxumState};
 use axum::response::{Html, IntoResponse, Response};
 use carbide_uuid::machine::MachineId;
 use hyper::http::StatusCode;
+use rpc::forge::forge_server::Forge;
+use serde::Deserialize;
 
 use super::health::{HealthHistoryRecord, HealthHistoryTable, fetch_health_history};
 use crate::api::Api;
@@ -33,21 +35,121 @@ use crate::api::Api;
 struct MachineHealth {
     id: String,
     history: HealthHistoryTable,
+    sensor_hours: i64,
+    sensors: Vec<SensorSummary>,
+}
+
+/// Default time range of the sensor history shown on the health history page
+const DEFAULT_SENSOR_HISTORY_HOURS: i64 = 6;
+
+#[derive(Deserialize, Debug)]
+pub struct SensorHistoryParams {
+    hours: Option<i64>,
+}
+
+impl SensorHistoryParams {
+    fn hours(&self) -> i64 {
+        self.hours
+            .unwrap_or(DEFAULT_SENSOR_HISTORY_HOURS)
+            .clamp(1, model::sensor_history::ROLLUP_RETENTION.num_hours())
+    }
+}
+
+/// Width and height of the sensor sparklines
+const SPARKLINE_WIDTH: f64 = 160.0;
+const SPARKLINE_HEIGHT: f64 = 24.0;
+
+/// Summary of the readings of a sensor in the displayed time range
+struct SensorSummary {
+    sensor: String,
+    name: String,
+    unit: String,
+    latest: String,
+    min: String,
+    avg: String,
+    max: String,
+    /// Points of an SVG polyline which shows the average readings over time
+    sparkline: String,
+}
+
+impl From<rpc::forge::SensorSeries> for SensorSummary {
+    fn from(series: rpc::forge::SensorSeries) -> Self {
+        let min = series
+            .points
+            .iter()
+            .map(|p| p.min)
+            .fold(f64::INFINITY, f64::min);
+        let max = series
+            .points
+            .iter()
+            .map(|p| p.max)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let avg = series.points.iter().map(|p| p.avg).sum::<f64>() / series.points.len() as f64;
+        let latest = series.points.last().map(|p| p.avg);
+
+        let x_step = SPARKLINE_WIDTH / series.points.len().saturating_sub(1).max(1) as f64;
+        let y_range = (max - min).max(f64::EPSILON);
+        let sparkline = series
+            .points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let x = i as f64 * x_step;
+                let y = SPARKLINE_HEIGHT - (p.avg - min) / y_range * SPARKLINE_HEIGHT;
+                format!("{x:.1},{y:.1}")
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let fmt = |value: f64| {
+            if value.is_finite() {
+                format!("{value:.2}")
+            } else {
+                String::new()
+            }
+        };
+        Self {
+            sensor: series.sensor,
+            name: series.name,
+            unit: series.unit,
+            latest: latest.map(fmt).unwrap_or_default(),
+            min: fmt(min),
+            avg: fmt(avg),
+            max: fmt(max),
+            sparkline,
+        }
+    }
 }
 
 /// Show the health history for a certain Machine
 pub async fn show_health_history(
     AxumState(state): AxumState<Arc<Api>>,
     AxumPath(machine_id): AxumPath<String>,
+    Query(params): Query<SensorHistoryParams>,
 ) -> Response {
     let (machine_id, records) = match fetch_health_records(&state, &machine_id).await {
         Ok((id, records)) => (id, records),
         Err((code, msg)) => return (code, msg).into_response(),
     };
 
+    let sensor_hours = params.hours();
+    let sensors = match fetch_sensor_history(&state, &machine_id, sensor_hours).await {
+        Ok(history) => history
+            .series
+            .into_iter()
+            .map(SensorSummary::from)
+            .collect(),
+        Err(err) => {
+            tracing::error!(%err, %machine_id, "find_machine_sensor_history");
+            return (StatusCode::INTERNAL_SERVER_ERROR, String::new()).into_response();
+        }
+    };
+
     let display = MachineHealth {
         id: machine_id.to_string(),
         history: HealthHistoryTable { records },
+        sensor_hours,
+        sensors,
     };
 
     (StatusCode::OK, Html(display.render().unwrap())).into_response()
@@ -88,3 +190,41 @@ pub async fn fetch_health_records(
 
     Ok((machine_id, health_records))
 }
+
+/// Sensor readings of a Machine in JSON format
+pub async fn show_sensor_history_json(
+    AxumState(state): AxumState<Arc<Api>>,
+    AxumPath(machine_id): AxumPath<String>,
+    Query(params): Query<SensorHistoryParams>,
+) -> Response {
+    let Ok(machine_id) = MachineId::from_str(&machine_id) else {
+        return (StatusCode::BAD_REQUEST, "invalid machine id").into_response();
+    };
+
+    match fetch_sensor_history(&state, &machine_id, params.hours()).await {
+        Ok(history) => (StatusCode::OK, Json(history)).into_response(),
+        Err(err) => {
+            tracing::error!(%err, %machine_id, "find_machine_sensor_history");
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new()).into_response()
+        }
+    }
+}
+
+async fn fetch_sensor_history(
+    api: &Api,
+    machine_id: &MachineId,
+    hours: i64,
+) -> Result<rpc::forge::MachineSensorHistory, tonic::Status> {
+    let start_time = chrono::Utc::now() - chrono::TimeDelta::hours(hours);
+    api.find_machine_sensor_history(tonic::Request::new(
+        rpc::forge::MachineSensorHistoryRequest {
+            machine_id: Some(*machine_id),
+            sensors: Vec::new(),
+            start_time: Some(start_time.into()),
+            end_time: None,
+            resolution: rpc::forge::SensorHistoryResolution::Auto.into(),
+        },
+    ))
+    .await
+    .map(|response| response.into_inner())
+}
@@ -524,6 +524,10 @@ pub fn routes(api: Arc<Api>) -> eyre::Result<NormalizePath<Router>> {
                 "/machine/{machine_id}/health-history.json",
                 get(health_history::show_health_history_json),
             )
+            .route(
+                "/machine/{machine_id}/sensor-history.json",
+                get(health_history::show_sensor_history_json),
+            )
             .route(
                 "/machine/{machine_id}/state-history",
                 get(state_history::show_machine_state_history),
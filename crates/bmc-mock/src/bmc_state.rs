@@ -21,6 +21,7 @@ use crate::redfish;
 use crate::redfish::account_service::AccountServiceState;
 use crate::redfish::chassis::ChassisState;
 use crate::redfish::computer_system::SystemState;
+use crate::redfish::event_service::{EventServiceState, ResourceEvent};
 use crate::redfish::manager::ManagerState;
 use crate::redfish::update_service::UpdateServiceState;
 
@@ -35,6 +36,7 @@ pub struct BmcState {
     pub chassis_state: Arc<ChassisState>,
     pub update_service_state: Arc<UpdateServiceState>,
     pub account_service_state: Arc<AccountServiceState>,
+    pub event_service_state: Arc<EventServiceState>,
     pub injected_bugs: Arc<InjectedBugs>,
     pub callbacks: Option<Arc<dyn crate::Callbacks>>,
 }
@@ -50,6 +52,12 @@ impl BmcState {
         match event {
             BmcEvent::PowerOn => {
                 self.complete_all_bios_jobs();
+                for system in self.system_state.systems() {
+                    self.event_service_state.publish(
+                        ResourceEvent::PoweredOn,
+                        &redfish::computer_system::resource(system.id()).odata_id,
+                    );
+                }
             }
             BmcEvent::BootCompleted => {
                 self.system_state.on_boot_completed();
@@ -15,22 +15,28 @@
  * limitations under the License.
  */
 
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use arc_swap::ArcSwap;
-use axum::http::StatusCode;
+use axum::http::{Method, StatusCode};
+use axum_server::tls_rustls::RustlsConfig;
 use duration_str::deserialize_option_duration;
 use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
 
 use crate::redfish;
+use crate::scenario::{AppliedFaults, Fault, Scenario, ScenarioRun};
 
 #[derive(Clone, Default)]
 pub struct InjectedBugs {
     all_dpu_lost_on_host: Arc<AtomicBool>,
     long_response: Arc<ArcSwap<Option<LongResponse>>>,
     http_error: Arc<Mutex<Option<HttpErrorRule>>>,
+    scenario: Arc<Mutex<Option<ScenarioRun>>>,
+    tls_config: Arc<Mutex<Option<RustlsConfig>>>,
 }
 
 #[derive(Deserialize, Serialize, Default)]
@@ -81,6 +87,66 @@ impl InjectedBugs {
         *self.http_error.lock().unwrap() = args.http_error;
     }
 
+    pub fn start_scenario(&self, scenario: Scenario) {
+        *self.scenario.lock().unwrap() = Some(ScenarioRun::start(scenario, Instant::now()));
+    }
+
+    pub fn stop_scenario(&self) {
+        *self.scenario.lock().unwrap() = None;
+    }
+
+    pub fn scenario_status(&self) -> serde_json::Value {
+        self.scenario
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|run| run.status(Instant::now()))
+            .unwrap_or_default()
+    }
+
+    /// Gives scenarios access to the TLS configuration of the server
+    /// this mock is served from, so certificates can be rotated.
+    pub fn attach_tls_config(&self, config: RustlsConfig) {
+        *self.tls_config.lock().unwrap() = Some(config);
+    }
+
+    /// Evaluates the running scenario, if any, for a request.
+    pub fn scenario_faults(&self, method: &Method, path: &str) -> AppliedFaults {
+        let evaluation = match self.scenario.lock().unwrap().as_mut() {
+            Some(run) => run.evaluate(Instant::now(), method, path),
+            None => return AppliedFaults::default(),
+        };
+        for fault in evaluation.activated {
+            if let Fault::TlsCertRotation { cert_dir } = fault {
+                self.rotate_tls_cert(&cert_dir);
+            }
+        }
+        evaluation.faults
+    }
+
+    fn rotate_tls_cert(&self, cert_dir: &Path) {
+        let Some(config) = self.tls_config.lock().unwrap().clone() else {
+            tracing::warn!("Cannot rotate TLS certificate: mock is not attached to a TLS server");
+            return;
+        };
+        // tls::server_config falls back to REPO_ROOT when the certificate
+        // is missing, which is not what a scenario asks for.
+        if !cert_dir.join("tls.crt").exists() {
+            tracing::warn!(
+                ?cert_dir,
+                "Cannot rotate TLS certificate: tls.crt not found"
+            );
+            return;
+        }
+        match crate::tls::server_config(Some(cert_dir)) {
+            Ok(server_config) => {
+                tracing::warn!(?cert_dir, "Rotating TLS certificate");
+                config.reload_from_config(Arc::new(server_config));
+            }
+            Err(err) => tracing::warn!(?cert_dir, "Cannot rotate TLS certificate: {err}"),
+        }
+    }
+
     pub fn all_dpu_lost_on_host(&self) -> Option<AllDpuLostOnHost> {
         self.all_dpu_lost_on_host
             .load(Ordering::Relaxed)
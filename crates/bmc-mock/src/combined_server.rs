@@ -53,6 +53,7 @@ impl ListenerOrAddress {
 pub struct CombinedServer {
     join_handle: Option<JoinHandle<std::io::Result<()>>>,
     axum_handle: axum_server::Handle,
+    tls_config: RustlsConfig,
     pub address: SocketAddr,
 }
 
@@ -77,6 +78,7 @@ impl CombinedServer {
     ) -> Self {
         let config = RustlsConfig::from_config(Arc::new(server_config));
 
+        let tls_config = config.clone();
         let axum_handle = axum_server::Handle::new();
 
         let (addr, server) = match listener_or_address {
@@ -117,10 +119,17 @@ impl CombinedServer {
         Self {
             axum_handle,
             join_handle: Some(join_handle),
+            tls_config,
             address: addr,
         }
     }
 
+    /// Handle to the TLS configuration in use, which can be reloaded
+    /// while the server is running.
+    pub fn tls_config(&self) -> RustlsConfig {
+        self.tls_config.clone()
+    }
+
     pub async fn stop(&mut self) -> std::io::Result<()> {
         if let Some(join_handle) = self.join_handle.take() {
             self.axum_handle.shutdown();
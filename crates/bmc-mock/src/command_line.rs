@@ -18,6 +18,7 @@ use std::path::PathBuf;
 use std::str::FromStr;
 
 use clap::Parser;
+use url::Url;
 
 #[derive(Clone, Parser, Debug)]
 pub struct IpRouterPair {
@@ -55,9 +56,31 @@ pub struct Args {
 
     #[clap(
         long,
-        help = "An ip_address and .tar.gz file pair (comma separated).\nThe file is an archive of redfish data when the request is forwarded to a specific IP address.\nRepeat for different machines"
+        help = "An ip_address and .tar.gz file pair (comma separated).\nThe file is an archive of redfish data when the request is forwarded to a specific IP address.\nA .jsonl file is replayed as a recording instead (see --replay).\nRepeat for different machines"
     )]
     pub ip_router: Option<Vec<IpRouterPair>>,
+
+    #[clap(
+        long,
+        conflicts_with = "targz",
+        help = "Path to a recording (.jsonl) made with --record-upstream to replay statefully"
+    )]
+    pub replay: Option<std::path::PathBuf>,
+
+    #[clap(
+        long,
+        requires = "record_to",
+        conflicts_with_all = ["targz", "replay", "ip_router"],
+        help = "Instead of mocking, proxy every request to this BMC (e.g. https://10.0.0.5) and record the exchanges"
+    )]
+    pub record_upstream: Option<Url>,
+
+    #[clap(
+        long,
+        requires = "record_upstream",
+        help = "Path of the recording (.jsonl) to append exchanges to when using --record-upstream"
+    )]
+    pub record_to: Option<std::path::PathBuf>,
 }
 
 pub fn parse_args() -> Args {
@@ -33,7 +33,10 @@ mod json;
 mod machine_info;
 mod middleware_router;
 mod mock_machine_router;
+pub mod recording;
 mod redfish;
+mod replay_router;
+pub mod scenario;
 pub mod test_support;
 pub mod tls;
 
@@ -45,6 +48,7 @@ pub use machine_info::{
 pub use mock_machine_router::{
     BmcCommand, SetSystemPowerError, SetSystemPowerResult, machine_router,
 };
+pub use replay_router::replay_router;
 
 pub const DUMMY_FACTORY_USERNAME: &str = "root";
 pub const DUMMY_FACTORY_PASSWORD: &str = "factory_password";
@@ -15,6 +15,7 @@
  * limitations under the License.
  */
 mod command_line;
+mod record_proxy;
 mod tar_router;
 
 use std::collections::HashMap;
@@ -24,6 +25,7 @@ use std::process::Command;
 use std::sync::Arc;
 
 use axum::Router;
+use bmc_mock::recording::RecordingWriter;
 use bmc_mock::{
     BmcCommand, Callbacks, DpuMachineInfo, HostHardwareType, HostMachineInfo, ListenerOrAddress,
     MachineInfo, MockPowerState, SetSystemPowerError, SystemPowerControl,
@@ -67,23 +69,45 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = command_line::parse_args();
     if let Some(ip_routers) = args.ip_router {
         for ip_router in ip_routers {
-            info!(
-                "Using archive {} for {}",
-                ip_router.targz.to_string_lossy(),
-                ip_router.ip_address
-            );
-            let r = tar_router::tar_router(
-                TarGzOption::Disk(&ip_router.targz),
-                Some(&mut tar_router_entries),
-            )
-            .unwrap();
+            let r = if ip_router
+                .targz
+                .extension()
+                .is_some_and(|ext| ext == "jsonl")
+            {
+                info!(
+                    "Replaying recording {} for {}",
+                    ip_router.targz.to_string_lossy(),
+                    ip_router.ip_address
+                );
+                bmc_mock::replay_router(bmc_mock::recording::load(&ip_router.targz)?)
+            } else {
+                info!(
+                    "Using archive {} for {}",
+                    ip_router.targz.to_string_lossy(),
+                    ip_router.ip_address
+                );
+                tar_router::tar_router(
+                    TarGzOption::Disk(&ip_router.targz),
+                    Some(&mut tar_router_entries),
+                )
+                .unwrap()
+            };
             routers_by_ip.insert(ip_router.ip_address, r);
         }
     }
 
     let listen_addr = args.port.map(|p| SocketAddr::from(([0, 0, 0, 0], p)));
     info!("Using cert_path: {:?}", args.cert_path);
-    let router = if let Some(tar_path) = args.targz {
+    let router = if let (Some(upstream), Some(record_to)) = (args.record_upstream, args.record_to) {
+        info!("Recording {upstream} to {}", record_to.to_string_lossy());
+        record_proxy::record_router(upstream, RecordingWriter::create(&record_to)?)?
+    } else if let Some(recording) = args.replay {
+        info!(
+            "Replaying recording {} as default",
+            recording.to_string_lossy()
+        );
+        bmc_mock::replay_router(bmc_mock::recording::load(&recording)?)
+    } else if let Some(tar_path) = args.targz {
         info!("Using archive {} as default", tar_path.to_string_lossy());
         tar_router::tar_router(TarGzOption::Disk(&tar_path), Some(&mut tar_router_entries)).unwrap()
     } else {
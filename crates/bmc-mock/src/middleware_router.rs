@@ -20,6 +20,7 @@ use std::sync::Arc;
 use axum::Router;
 use axum::body::Body;
 use axum::extract::{Request, State};
+use axum::http::header::LOCATION;
 use axum::response::{IntoResponse, Response};
 use axum::routing::any;
 use tracing::instrument;
@@ -27,11 +28,13 @@ use tracing::instrument;
 use crate::Callbacks;
 use crate::bug::InjectedBugs;
 use crate::http::call_router_with_new_request;
+use crate::redfish::event_service::{EventServiceState, ResourceEvent};
 
 pub fn append(
     mat_host_id: String,
     router: Router,
     injected_bugs: Arc<InjectedBugs>,
+    event_service: Arc<EventServiceState>,
     callbacks: Arc<dyn Callbacks>,
 ) -> Router {
     Router::new()
@@ -40,6 +43,7 @@ pub fn append(
             mat_host_id,
             inner: router,
             injected_bugs,
+            event_service,
             callbacks,
         })
 }
@@ -47,8 +51,25 @@ pub fn append(
 #[instrument(skip_all, fields(mat_host_id = %state.mat_host_id))]
 async fn process(State(mut state): State<Middleware>, request: Request<Body>) -> Response {
     let is_safe = request.method().is_safe();
+    let http_method = request.method().clone();
+    let faults = state
+        .injected_bugs
+        .scenario_faults(request.method(), request.uri().path());
     let method = request.method().to_string();
     let path = request.uri().path().to_string();
+    if let Some(delay) = faults.delay() {
+        tracing::warn!(method, path, "Scenario delays request for {delay:?}");
+        tokio::time::sleep(delay).await;
+    }
+    if let Some(response) = faults.short_circuit(request.method(), request.headers()) {
+        tracing::warn!(
+            method,
+            path,
+            status = response.status().to_string(),
+            "Scenario fault answered request",
+        );
+        return response;
+    }
     if let Some(delay) = state.injected_bugs.long_response(&path) {
         tracing::warn!(
             method,
@@ -61,12 +82,20 @@ async fn process(State(mut state): State<Middleware>, request: Request<Body>) ->
         tracing::warn!(method, path, %status, "Injected HTTP error for request",);
         return status.into_response();
     }
-    let response = state.call_inner_router(request).await;
+    let mut response = state.call_inner_router(request).await;
+    if !faults.is_empty() {
+        response = faults.rewrite(response).await;
+    }
     if !response.status().is_success() {
         tracing::warn!(method, path, status = response.status().to_string());
     }
     if !is_safe && response.status().is_success() {
         state.callbacks.state_refresh_indication();
+        if let Some((event, origin)) =
+            ResourceEvent::for_request(&http_method, &path, response.headers().get(LOCATION))
+        {
+            state.event_service.publish(event, &origin);
+        }
     }
     response
 }
@@ -76,6 +105,7 @@ struct Middleware {
     mat_host_id: String,
     inner: Router,
     injected_bugs: Arc<InjectedBugs>,
+    event_service: Arc<EventServiceState>,
     callbacks: Arc<dyn Callbacks>,
 }
 
@@ -27,7 +27,9 @@ use crate::auth_router::Authorizer;
 use crate::bmc_state::BmcState;
 use crate::bug::InjectedBugs;
 use crate::json::JsonExt;
+use crate::redfish::event_service::EventServiceState;
 use crate::redfish::manager::ManagerState;
+use crate::scenario::Scenario;
 use crate::{Callbacks, MachineInfo, SystemPowerControl, auth_router, middleware_router, redfish};
 
 #[derive(Debug)]
@@ -83,12 +85,19 @@ pub fn machine_router(
             "/InjectedBugs",
             get(get_injected_bugs).post(post_injected_bugs),
         )
+        .route(
+            "/InjectedBugs/Scenario",
+            get(get_scenario)
+                .post(post_scenario)
+                .delete(delete_scenario),
+        )
         .add_routes(crate::redfish::service_root::add_routes)
         .add_routes(crate::redfish::chassis::add_routes)
         .add_routes(crate::redfish::manager::add_routes)
         .add_routes(crate::redfish::update_service::add_routes)
         .add_routes(crate::redfish::task_service::add_routes)
         .add_routes(crate::redfish::account_service::add_routes)
+        .add_routes(crate::redfish::event_service::add_routes)
         .add_routes(|routes| crate::redfish::computer_system::add_routes(routes, bmc_vendor))
         .add_routes(crate::ipmi::add_routes);
     let router = match &machine_info {
@@ -111,6 +120,7 @@ pub fn machine_router(
         crate::redfish::account_service::AccountServiceState::new(factory_default_account),
     );
     let injected_bugs = Arc::new(InjectedBugs::default());
+    let event_service_state = Arc::new(EventServiceState::default());
     let state = BmcState {
         bmc_vendor,
         bmc_product,
@@ -121,6 +131,7 @@ pub fn machine_router(
         chassis_state,
         update_service_state,
         account_service_state,
+        event_service_state: event_service_state.clone(),
         injected_bugs: injected_bugs.clone(),
         callbacks: Some(callbacks.clone()),
     };
@@ -135,7 +146,13 @@ pub fn machine_router(
             }
         }),
         Box::new(move |router| {
-            middleware_router::append(mat_host_id, router, injected_bugs, callbacks)
+            middleware_router::append(
+                mat_host_id,
+                router,
+                injected_bugs,
+                event_service_state,
+                callbacks,
+            )
         }),
     ] as [Box<dyn FnOnce(axum::Router) -> axum::Router>; _])
         .into_iter()
@@ -159,3 +176,25 @@ async fn post_injected_bugs(
             serde_json::json!({"error": format!("{err:?}")}).into_response(StatusCode::BAD_REQUEST)
         })
 }
+
+async fn get_scenario(State(state): State<BmcState>) -> Response {
+    state.injected_bugs.scenario_status().into_ok_response()
+}
+
+/// Starts a scenario given as YAML or JSON, replacing the running one.
+async fn post_scenario(State(state): State<BmcState>, body: String) -> Response {
+    match Scenario::from_yaml(&body) {
+        Ok(scenario) => {
+            state.injected_bugs.start_scenario(scenario);
+            state.injected_bugs.scenario_status().into_ok_response()
+        }
+        Err(err) => {
+            serde_json::json!({"error": err.to_string()}).into_response(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn delete_scenario(State(state): State<BmcState>) -> Response {
+    state.injected_bugs.stop_scenario();
+    crate::http::ok_no_content()
+}
@@ -0,0 +1,384 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Recording proxy: forwards every request to a real BMC and appends
+//! the exchange to a recording, which can later be replayed with
+//! `--replay` or `--ip-router <ip>,<recording>.jsonl`.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use axum::Router;
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use bmc_mock::recording::{
+    RECORDED_HEADERS, RecordedExchange, RecordingWriter, normalize_uri, redact,
+    redact_response_body,
+};
+use bytes::{Bytes, BytesMut};
+use futures::StreamExt;
+use url::Url;
+
+// Larger bodies, like firmware images, are streamed through without
+// being recorded, instead of being buffered in memory.
+const MAX_RECORDED_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+#[derive(Clone)]
+struct RecordProxy {
+    upstream: Url,
+    client: reqwest::Client,
+    writer: Arc<RecordingWriter>,
+    started: Instant,
+}
+
+/// Create a router that proxies to `upstream` and records to `writer`.
+pub fn record_router(upstream: Url, writer: RecordingWriter) -> eyre::Result<Router> {
+    // BMCs pretty much always have self-signed certificates.
+    let client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .build()?;
+    Ok(Router::new().fallback(forward).with_state(RecordProxy {
+        upstream,
+        client,
+        writer: Arc::new(writer),
+        started: Instant::now(),
+    }))
+}
+
+async fn forward(State(proxy): State<RecordProxy>, request: Request<Body>) -> Response {
+    match proxy.forward(request).await {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::error!("Recording proxy failed: {e:#}");
+            (StatusCode::BAD_GATEWAY, format!("bmc-mock: {e:#}")).into_response()
+        }
+    }
+}
+
+impl RecordProxy {
+    async fn forward(&self, request: Request<Body>) -> eyre::Result<Response> {
+        let (parts, body) = request.into_parts();
+        let uri = normalize_uri(parts.uri.path(), parts.uri.query());
+        let (upstream_body, request_body) = match content_length(&parts.headers) {
+            Some(length) if length <= MAX_RECORDED_BODY_SIZE => {
+                let body = axum::body::to_bytes(body, MAX_RECORDED_BODY_SIZE).await?;
+                let request_body = serde_json::from_slice(&body).ok().map(|mut value| {
+                    redact(&mut value);
+                    value
+                });
+                (reqwest::Body::from(body), request_body)
+            }
+            _ => (reqwest::Body::wrap_stream(body.into_data_stream()), None),
+        };
+
+        let mut headers = parts.headers.clone();
+        for name in [header::HOST, header::CONTENT_LENGTH] {
+            headers.remove(name);
+        }
+        headers.remove("forwarded");
+        let upstream_response = self
+            .client
+            .request(parts.method.clone(), self.upstream.join(&uri)?)
+            .headers(headers)
+            .body(upstream_body)
+            .send()
+            .await?;
+        let status = upstream_response.status();
+        let response_headers = upstream_response.headers().clone();
+        let (response_body, recorded_body) = bounded_body(upstream_response).await?;
+
+        let exchange = RecordedExchange {
+            offset_ms: self.started.elapsed().as_millis() as u64,
+            method: parts.method.to_string(),
+            uri,
+            request_body,
+            status: status.as_u16(),
+            headers: recorded_headers(&response_headers),
+            response_body: recorded_body
+                .map(|body| redact_response_body(String::from_utf8_lossy(&body).into_owned()))
+                .unwrap_or_default(),
+        };
+        tracing::info!(
+            method = exchange.method,
+            uri = exchange.uri,
+            status = exchange.status,
+            "Recorded exchange"
+        );
+        self.writer.append(&exchange)?;
+
+        let mut response = Response::new(response_body);
+        *response.status_mut() = status;
+        for (name, value) in response_headers.iter() {
+            if name != header::TRANSFER_ENCODING && name != header::CONTENT_LENGTH {
+                response.headers_mut().append(name, value.clone());
+            }
+        }
+        Ok(response)
+    }
+}
+
+fn content_length(headers: &HeaderMap) -> Option<usize> {
+    headers
+        .get(header::CONTENT_LENGTH)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+}
+
+/// bounded_body reads an upstream response body into memory, as long as
+/// it is small enough to be recorded. Larger bodies are streamed through
+/// to the client and are not recorded.
+async fn bounded_body(response: reqwest::Response) -> eyre::Result<(Body, Option<Bytes>)> {
+    if content_length(response.headers()).is_some_and(|length| length > MAX_RECORDED_BODY_SIZE) {
+        tracing::warn!(url = %response.url(), "Not recording large response body");
+        return Ok((Body::from_stream(response.bytes_stream()), None));
+    }
+
+    let mut stream = response.bytes_stream();
+    let mut buffered = BytesMut::new();
+    while let Some(chunk) = stream.next().await {
+        buffered.extend_from_slice(&chunk?);
+        if buffered.len() > MAX_RECORDED_BODY_SIZE {
+            tracing::warn!("Not recording large response body");
+            let head =
+                futures::stream::once(async move { Ok::<_, reqwest::Error>(buffered.freeze()) });
+            return Ok((Body::from_stream(head.chain(stream)), None));
+        }
+    }
+    let body = buffered.freeze();
+    Ok((Body::from(body.clone()), Some(body)))
+}
+
+/// recorded_headers keeps the headers worth replaying. Absolute
+/// locations are made relative, since the replaying mock won't be
+/// at the address of the recorded BMC.
+fn recorded_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+    RECORDED_HEADERS
+        .iter()
+        .filter_map(|name| {
+            let value = headers.get(*name)?.to_str().ok()?;
+            let value = match Url::parse(value) {
+                Ok(url) if *name == "location" => normalize_uri(url.path(), url.query()),
+                _ => value.to_string(),
+            };
+            Some((name.to_string(), value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use axum::http::Method;
+    use axum::routing::{get, post};
+    use http_body_util::BodyExt;
+    use serde_json::json;
+    use tower::Service;
+
+    use super::*;
+
+    const TASK: &str = "/redfish/v1/TaskService/Tasks/JID_1";
+    const UPDATE: &str = "/redfish/v1/UpdateService/Actions/UpdateService.SimpleUpdate";
+    const FIRMWARE: &str = "/redfish/v1/UpdateService/FirmwareInventory/BIOS";
+    const ACCOUNT: &str = "/redfish/v1/AccountService/Accounts/2";
+    const SESSIONS: &str = "/redfish/v1/SessionService/Sessions";
+
+    /// A BMC which updates its firmware through a task that completes
+    /// after being polled twice.
+    #[derive(Default)]
+    struct FakeBmc {
+        updated: bool,
+        task_polls: usize,
+    }
+
+    /// upstream serves a [`FakeBmc`] on a local port.
+    async fn upstream() -> Url {
+        let bmc = Arc::new(Mutex::new(FakeBmc::default()));
+        let router = Router::new()
+            .route(
+                FIRMWARE,
+                get(|State(bmc): State<Arc<Mutex<FakeBmc>>>| async move {
+                    let version = if bmc.lock().unwrap().updated {
+                        "2.0"
+                    } else {
+                        "1.0"
+                    };
+                    axum::Json(json!({ "Version": version }))
+                }),
+            )
+            .route(
+                UPDATE,
+                post(
+                    |State(bmc): State<Arc<Mutex<FakeBmc>>>, image: Body| async move {
+                        axum::body::to_bytes(image, usize::MAX).await.unwrap();
+                        bmc.lock().unwrap().updated = true;
+                        (
+                            StatusCode::ACCEPTED,
+                            [(header::LOCATION, format!("https://10.0.0.1{TASK}"))],
+                        )
+                    },
+                ),
+            )
+            .route(
+                TASK,
+                get(|State(bmc): State<Arc<Mutex<FakeBmc>>>| async move {
+                    let mut bmc = bmc.lock().unwrap();
+                    bmc.task_polls += 1;
+                    let state = match bmc.task_polls {
+                        1 => "Running",
+                        _ => "Completed",
+                    };
+                    axum::Json(
+                        json!({ "TaskState": state, "PercentComplete": bmc.task_polls * 50 }),
+                    )
+                }),
+            )
+            .route(
+                ACCOUNT,
+                get(|| async {
+                    axum::Json(
+                        json!({ "UserName": "root", "Password": "hunter2", "Locked": false }),
+                    )
+                }),
+            )
+            .route(
+                SESSIONS,
+                post(|| async {
+                    axum::Json(json!({ "UserName": "root", "Password": null, "Token": "s3cr3t" }))
+                }),
+            )
+            .with_state(bmc);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        Url::parse(&format!("http://{address}")).unwrap()
+    }
+
+    async fn call(router: &mut Router, method: Method, uri: &str, body: &str) -> (u16, String) {
+        let request = axum::http::Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::CONTENT_LENGTH, body.len())
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = router.call(request).await.unwrap();
+        let status = response.status().as_u16();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    fn field(body: &str, name: &str) -> serde_json::Value {
+        serde_json::from_str::<serde_json::Value>(body).unwrap()[name].clone()
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("bmc-mock-round-trip-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        // Record a firmware update, polled in order.
+        let mut recorder =
+            record_router(upstream().await, RecordingWriter::create(&path).unwrap()).unwrap();
+        let login = r#"{"UserName": "root", "Password": "hunter2"}"#;
+        let (status, body) = call(&mut recorder, Method::POST, SESSIONS, login).await;
+        assert_eq!(status, 200);
+        // The client still gets the real response.
+        assert_eq!(field(&body, "Token"), "s3cr3t");
+        call(&mut recorder, Method::GET, ACCOUNT, "").await;
+        call(&mut recorder, Method::GET, FIRMWARE, "").await;
+        call(&mut recorder, Method::POST, UPDATE, "{}").await;
+        call(&mut recorder, Method::GET, TASK, "").await;
+        call(&mut recorder, Method::GET, TASK, "").await;
+        call(&mut recorder, Method::GET, FIRMWARE, "").await;
+
+        let recording = std::fs::read_to_string(&path).unwrap();
+        assert!(!recording.contains("hunter2"), "{recording}");
+        assert!(!recording.contains("s3cr3t"), "{recording}");
+        let exchanges = bmc_mock::recording::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(exchanges.len(), 7);
+        assert_eq!(
+            exchanges[0].request_body.as_ref().unwrap()["Password"],
+            "<redacted>"
+        );
+        assert_eq!(
+            field(&exchanges[0].response_body, "Password"),
+            serde_json::Value::Null
+        );
+        assert_eq!(field(&exchanges[1].response_body, "Password"), "<redacted>");
+        assert_eq!(field(&exchanges[1].response_body, "Locked"), false);
+        assert_eq!(exchanges[3].headers["location"], TASK);
+
+        // Replay it with the firmware and the task polled out of order.
+        let mut replayer = bmc_mock::replay_router(exchanges);
+        let version = |body: String| field(&body, "Version");
+        let task_state = |body: String| field(&body, "TaskState");
+        assert_eq!(
+            version(call(&mut replayer, Method::GET, FIRMWARE, "").await.1),
+            "1.0"
+        );
+        assert_eq!(call(&mut replayer, Method::POST, UPDATE, "{}").await.0, 202);
+        assert_eq!(
+            version(call(&mut replayer, Method::GET, FIRMWARE, "").await.1),
+            "2.0"
+        );
+        assert_eq!(
+            task_state(call(&mut replayer, Method::GET, TASK, "").await.1),
+            "Running"
+        );
+        assert_eq!(
+            version(call(&mut replayer, Method::GET, FIRMWARE, "").await.1),
+            "2.0"
+        );
+        assert_eq!(
+            task_state(call(&mut replayer, Method::GET, TASK, "").await.1),
+            "Completed"
+        );
+        assert_eq!(
+            task_state(call(&mut replayer, Method::GET, TASK, "").await.1),
+            "Completed"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_large_bodies_are_not_recorded() {
+        let path =
+            std::env::temp_dir().join(format!("bmc-mock-large-body-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut recorder =
+            record_router(upstream().await, RecordingWriter::create(&path).unwrap()).unwrap();
+        let image = format!("{{\"Image\": \"{}\"}}", "0".repeat(MAX_RECORDED_BODY_SIZE));
+        assert_eq!(
+            call(&mut recorder, Method::POST, UPDATE, &image).await.0,
+            202
+        );
+
+        let exchanges = bmc_mock::recording::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(exchanges.len(), 1);
+        assert_eq!(exchanges[0].status, 202);
+        assert_eq!(exchanges[0].request_body, None);
+    }
+}
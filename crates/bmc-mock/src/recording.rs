@@ -0,0 +1,170 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Recordings of live Redfish traffic.
+//!
+//! A recording is a JSON Lines file with one [`RecordedExchange`] per
+//! line, in the order the BMC answered them. They are made by running
+//! bmc-mock with `--record-upstream` in front of a real BMC, and are
+//! replayed by [`crate::replay_router`].
+
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+use eyre::Context;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Response headers worth keeping: the rest are either connection
+/// specific or differ between every response.
+pub const RECORDED_HEADERS: &[&str] = &[
+    "content-type",
+    "etag",
+    "location",
+    "odata-version",
+    "retry-after",
+];
+
+/// Body fields whose values are never written to a recording. Besides
+/// request bodies, this covers the accounts and sessions reported by
+/// AccountService and SessionService.
+const REDACTED_FIELDS: &[&str] = &[
+    "Password",
+    "password",
+    "Token",
+    "token",
+    "X-Auth-Token",
+    "AuthToken",
+];
+const REDACTED: &str = "<redacted>";
+
+/// A single request/response pair captured from a real BMC.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecordedExchange {
+    /// Milliseconds since the recording started.
+    #[serde(default)]
+    pub offset_ms: u64,
+    pub method: String,
+    /// Path and query of the request, without a trailing slash.
+    pub uri: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<Value>,
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub response_body: String,
+}
+
+impl RecordedExchange {
+    pub fn is_get(&self) -> bool {
+        self.method == "GET" || self.method == "HEAD"
+    }
+}
+
+/// normalize_uri strips the trailing slash (like the NormalizePathLayer
+/// in front of every router does) so recorded and replayed URIs match.
+pub fn normalize_uri(path: &str, query: Option<&str>) -> String {
+    let path = match path.trim_end_matches('/') {
+        "" => "/",
+        path => path,
+    };
+    match query {
+        Some(query) if !query.is_empty() => format!("{path}?{query}"),
+        _ => path.to_string(),
+    }
+}
+
+/// redact replaces credentials in a request or response body, at any
+/// depth. Unset (null) credentials are kept as they are.
+pub fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if REDACTED_FIELDS.contains(&key.as_str()) {
+                    if !value.is_null() {
+                        *value = Value::String(REDACTED.to_string());
+                    }
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+/// redact_response_body redacts a JSON response body. Bodies without
+/// credentials, and bodies which aren't JSON, are kept verbatim.
+pub fn redact_response_body(body: String) -> String {
+    let Ok(mut value) = serde_json::from_str::<Value>(&body) else {
+        return body;
+    };
+    let original = value.clone();
+    redact(&mut value);
+    if value == original {
+        body
+    } else {
+        value.to_string()
+    }
+}
+
+/// load reads a recording from disk.
+pub fn load(path: &Path) -> eyre::Result<Vec<RecordedExchange>> {
+    let file = File::open(path).wrap_err(format!("cannot read recording: {path:?}"))?;
+    BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(line) if line.trim().is_empty()))
+        .map(|(index, line)| {
+            let line = line.wrap_err(format!("cannot read recording: {path:?}"))?;
+            serde_json::from_str(&line)
+                .wrap_err(format!("{path:?} line {}: invalid exchange", index + 1))
+        })
+        .collect()
+}
+
+/// RecordingWriter appends exchanges to a recording as they happen, so
+/// an interrupted capture still leaves a usable file behind.
+#[derive(Debug)]
+pub struct RecordingWriter {
+    file: Mutex<File>,
+}
+
+impl RecordingWriter {
+    pub fn create(path: &Path) -> eyre::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .wrap_err(format!("cannot open recording: {path:?}"))?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+
+    pub fn append(&self, exchange: &RecordedExchange) -> eyre::Result<()> {
+        let mut line = serde_json::to_string(exchange)?;
+        line.push('\n');
+        self.file.lock().unwrap().write_all(line.as_bytes())?;
+        Ok(())
+    }
+}
@@ -208,6 +208,10 @@ impl SystemState {
             .find_map(|system| system.resolve_current_boot_selection())
     }
 
+    pub fn id(&self) -> &str {
+        &self.config.id
+    }
+
     pub fn on_boot_completed(&self) {
         self.systems.iter().for_each(|s| s.on_boot_completed())
     }
@@ -540,7 +544,13 @@ async fn post_reset_system(
     // while issuing a redfish call, and MachineStateMachine is blocked waiting for the row lock
     // to be released.
     match callbacks.set_power_state(reset_type) {
-        Ok(_) => json!({}).into_ok_response(),
+        Ok(_) => {
+            state.event_service_state.publish(
+                redfish::event_service::ResourceEvent::for_reset(reset_type),
+                &resource(&system_id).odata_id,
+            );
+            json!({}).into_ok_response()
+        }
         Err(SetSystemPowerError::BadRequest(_)) => StatusCode::BAD_REQUEST.into_response(),
         Err(SetSystemPowerError::CommandSendError(_)) => {
             StatusCode::INTERNAL_SERVER_ERROR.into_response()
@@ -0,0 +1,574 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::borrow::Cow;
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use axum::extract::{Path, State};
+use axum::http::header::LOCATION;
+use axum::http::{HeaderValue, Method, StatusCode};
+use axum::response::sse::{Event as SseEvent, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde_json::json;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::bmc_state::BmcState;
+use crate::json::JsonExt;
+use crate::{SystemPowerControl, http, redfish};
+
+const SSE_PATH: &str = "/redfish/v1/EventService/SSE";
+const SUBMIT_TEST_EVENT_PATH: &str =
+    "/redfish/v1/EventService/Actions/EventService.SubmitTestEvent";
+const RESOURCE_EVENT_REGISTRY: &str = "ResourceEvent.1.3";
+/// Events buffered for each SSE client before it starts lagging.
+const SSE_BUFFER: usize = 256;
+
+pub fn resource() -> redfish::Resource<'static> {
+    redfish::Resource {
+        odata_id: Cow::Borrowed("/redfish/v1/EventService"),
+        odata_type: Cow::Borrowed("#EventService.v1_10_0.EventService"),
+        id: Cow::Borrowed("EventService"),
+        name: Cow::Borrowed("Event Service"),
+    }
+}
+
+const SUBSCRIPTIONS_COLLECTION_RESOURCE: redfish::Collection<'static> = redfish::Collection {
+    odata_id: Cow::Borrowed("/redfish/v1/EventService/Subscriptions"),
+    odata_type: Cow::Borrowed("#EventDestinationCollection.EventDestinationCollection"),
+    name: Cow::Borrowed("Event Subscriptions Collection"),
+};
+
+pub fn subscription_resource(id: impl Display) -> redfish::Resource<'static> {
+    redfish::Resource {
+        odata_id: Cow::Owned(format!(
+            "{}/{id}",
+            SUBSCRIPTIONS_COLLECTION_RESOURCE.odata_id
+        )),
+        odata_type: Cow::Borrowed("#EventDestination.v1_13_0.EventDestination"),
+        id: Cow::Owned(id.to_string()),
+        name: Cow::Borrowed("Event Subscription"),
+    }
+}
+
+pub fn add_routes(r: Router<BmcState>) -> Router<BmcState> {
+    r.route(&resource().odata_id, get(get_root))
+        .route(
+            &SUBSCRIPTIONS_COLLECTION_RESOURCE.odata_id,
+            get(get_subscriptions).post(create_subscription),
+        )
+        .route(
+            format!(
+                "{}/{{subscription_id}}",
+                SUBSCRIPTIONS_COLLECTION_RESOURCE.odata_id
+            )
+            .as_str(),
+            get(get_subscription).delete(delete_subscription),
+        )
+        .route(SSE_PATH, get(get_sse))
+        .route(SUBMIT_TEST_EVENT_PATH, post(submit_test_event))
+}
+
+/// Messages of the DMTF ResourceEvent registry that bmc-mock emits.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ResourceEvent {
+    Created,
+    Removed,
+    Changed,
+    PoweredOn,
+    PoweredOff,
+    PowerStateChanged(SystemPowerControl),
+}
+
+impl ResourceEvent {
+    fn message_key(&self) -> &'static str {
+        match self {
+            Self::Created => "ResourceCreated",
+            Self::Removed => "ResourceRemoved",
+            Self::Changed => "ResourceChanged",
+            Self::PoweredOn => "ResourcePoweredOn",
+            Self::PoweredOff => "ResourcePoweredOff",
+            Self::PowerStateChanged(_) => "ResourcePowerStateChanged",
+        }
+    }
+
+    /// Value of the deprecated `EventType` property, which older clients
+    /// still filter on.
+    fn event_type(&self) -> &'static str {
+        match self {
+            Self::Created => "ResourceAdded",
+            Self::Removed => "ResourceRemoved",
+            Self::Changed => "ResourceUpdated",
+            Self::PoweredOn | Self::PoweredOff | Self::PowerStateChanged(_) => "StatusChange",
+        }
+    }
+
+    fn message(&self, origin: &str) -> (String, Vec<String>) {
+        match self {
+            Self::Created => ("The resource has been created successfully.".into(), vec![]),
+            Self::Removed => ("The resource has been removed successfully.".into(), vec![]),
+            Self::Changed => (
+                "One or more resource properties have changed.".into(),
+                vec![],
+            ),
+            Self::PoweredOn => (
+                format!("The resource '{origin}' has powered on."),
+                vec![origin.to_string()],
+            ),
+            Self::PoweredOff => (
+                format!("The resource '{origin}' has powered off."),
+                vec![origin.to_string()],
+            ),
+            Self::PowerStateChanged(reset_type) => (
+                format!(
+                    "The power state of resource '{origin}' has changed to type '{reset_type:?}'."
+                ),
+                vec![origin.to_string(), format!("{reset_type:?}")],
+            ),
+        }
+    }
+
+    /// Event announcing the effect of a power request.
+    pub fn for_reset(reset_type: SystemPowerControl) -> Self {
+        type C = SystemPowerControl;
+        match reset_type {
+            C::On | C::ForceOn => Self::PoweredOn,
+            C::GracefulShutdown | C::ForceOff => Self::PoweredOff,
+            other => Self::PowerStateChanged(other),
+        }
+    }
+
+    /// Event announcing the effect of a successful modifying request, if
+    /// it has one. Actions announce their own events.
+    pub fn for_request(
+        method: &Method,
+        path: &str,
+        location: Option<&HeaderValue>,
+    ) -> Option<(Self, String)> {
+        if path.contains("/Actions/") || path.starts_with(resource().odata_id.as_ref()) {
+            return None;
+        }
+        if !path.starts_with("/redfish/v1") {
+            return None;
+        }
+        match *method {
+            Method::POST => Some(match location.and_then(|location| location.to_str().ok()) {
+                Some(location) => (Self::Created, location.to_string()),
+                None => (Self::Changed, path.to_string()),
+            }),
+            Method::DELETE => Some((Self::Removed, path.to_string())),
+            Method::PATCH | Method::PUT => Some((Self::Changed, path.to_string())),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Subscription {
+    id: String,
+    destination: String,
+    context: String,
+    protocol: String,
+    registry_prefixes: Vec<String>,
+}
+
+impl Subscription {
+    fn wants(&self, message_id: &str) -> bool {
+        self.registry_prefixes.is_empty()
+            || self
+                .registry_prefixes
+                .iter()
+                .any(|prefix| message_id.starts_with(prefix.as_str()))
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        json!({
+            "Destination": self.destination,
+            "Context": self.context,
+            "Protocol": self.protocol,
+            "RegistryPrefixes": self.registry_prefixes,
+            "EventFormatType": "Event",
+            "SubscriptionType": "RedfishEvent",
+        })
+        .patch(subscription_resource(&self.id))
+    }
+}
+
+/// Redfish EventService of a mock BMC: fans out events to SSE clients
+/// and to push subscriptions.
+#[derive(Debug)]
+pub struct EventServiceState {
+    sender: broadcast::Sender<serde_json::Value>,
+    next_event_id: AtomicU64,
+    next_subscription_id: AtomicU64,
+    subscriptions: Mutex<Vec<Subscription>>,
+}
+
+impl Default for EventServiceState {
+    fn default() -> Self {
+        Self {
+            sender: broadcast::channel(SSE_BUFFER).0,
+            next_event_id: AtomicU64::new(1),
+            next_subscription_id: AtomicU64::new(1),
+            subscriptions: Mutex::default(),
+        }
+    }
+}
+
+impl EventServiceState {
+    pub fn subscribe(&self) -> broadcast::Receiver<serde_json::Value> {
+        self.sender.subscribe()
+    }
+
+    pub fn publish(&self, event: ResourceEvent, origin: &str) {
+        let id = self
+            .next_event_id
+            .fetch_add(1, Ordering::Relaxed)
+            .to_string();
+        let (message, message_args) = event.message(origin);
+        let message_id = format!("{RESOURCE_EVENT_REGISTRY}.{}", event.message_key());
+        let payload = json!({
+            "@odata.type": "#Event.v1_7_0.Event",
+            "Id": id,
+            "Name": "Event",
+            "Events": [{
+                "EventId": id,
+                "EventTimestamp": chrono::Utc::now().to_rfc3339(),
+                "EventType": event.event_type(),
+                "MessageId": message_id,
+                "Message": message,
+                "MessageArgs": message_args,
+                "MessageSeverity": "OK",
+                "OriginOfCondition": { "@odata.id": origin },
+            }],
+        });
+        tracing::debug!(message_id, origin, "Publishing Redfish event");
+
+        let subscriptions = self.subscriptions.lock().expect("mutex poisoned").clone();
+        for subscription in subscriptions
+            .into_iter()
+            .filter(|subscription| subscription.wants(&message_id))
+        {
+            let payload = payload
+                .clone()
+                .patch(json!({ "Context": subscription.context }));
+            tokio::spawn(deliver(subscription.destination, payload));
+        }
+        // Nobody listening on SSE is not an error.
+        let _ = self.sender.send(payload);
+    }
+
+    fn add_subscription(&self, mut subscription: Subscription) -> Subscription {
+        subscription.id = self
+            .next_subscription_id
+            .fetch_add(1, Ordering::Relaxed)
+            .to_string();
+        self.subscriptions
+            .lock()
+            .expect("mutex poisoned")
+            .push(subscription.clone());
+        subscription
+    }
+
+    fn find_subscription(&self, id: &str) -> Option<Subscription> {
+        self.subscriptions
+            .lock()
+            .expect("mutex poisoned")
+            .iter()
+            .find(|subscription| subscription.id == id)
+            .cloned()
+    }
+
+    fn remove_subscription(&self, id: &str) -> bool {
+        let mut subscriptions = self.subscriptions.lock().expect("mutex poisoned");
+        let count = subscriptions.len();
+        subscriptions.retain(|subscription| subscription.id != id);
+        subscriptions.len() != count
+    }
+}
+
+async fn deliver(destination: String, payload: serde_json::Value) {
+    let client = match reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .build()
+    {
+        Ok(client) => client,
+        Err(err) => {
+            tracing::warn!("Cannot create client to deliver Redfish event: {err}");
+            return;
+        }
+    };
+    if let Err(err) = client
+        .post(&destination)
+        .json(&payload)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+    {
+        tracing::warn!(destination, "Redfish event delivery failed: {err}");
+    }
+}
+
+async fn get_root() -> Response {
+    json!({
+        "ServiceEnabled": true,
+        "DeliveryRetryAttempts": 0,
+        "DeliveryRetryIntervalSeconds": 0,
+        "EventFormatTypes": ["Event"],
+        "RegistryPrefixes": ["ResourceEvent"],
+        "ServerSentEventUri": SSE_PATH,
+        "SSEFilterPropertiesSupported": {
+            "EventFormatType": false,
+            "MessageId": false,
+            "RegistryPrefix": false,
+            "ResourceType": false,
+        },
+        "Actions": {
+            "#EventService.SubmitTestEvent": {
+                "target": SUBMIT_TEST_EVENT_PATH,
+            },
+        },
+    })
+    .patch(resource())
+    .patch(SUBSCRIPTIONS_COLLECTION_RESOURCE.nav_property("Subscriptions"))
+    .into_ok_response()
+}
+
+async fn get_subscriptions(State(state): State<BmcState>) -> Response {
+    let members = state
+        .event_service_state
+        .subscriptions
+        .lock()
+        .expect("mutex poisoned")
+        .iter()
+        .map(|subscription| subscription_resource(&subscription.id).entity_ref())
+        .collect::<Vec<_>>();
+    SUBSCRIPTIONS_COLLECTION_RESOURCE
+        .with_members(&members)
+        .into_ok_response()
+}
+
+async fn create_subscription(
+    State(state): State<BmcState>,
+    Json(request): Json<serde_json::Value>,
+) -> Response {
+    let Some(destination) = request.get("Destination").and_then(|v| v.as_str()) else {
+        return json!("Destination is a required property").into_response(StatusCode::BAD_REQUEST);
+    };
+    let str_field = |name: &str| {
+        request
+            .get(name)
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+    };
+    let registry_prefixes = request
+        .get("RegistryPrefixes")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default();
+    let subscription = state.event_service_state.add_subscription(Subscription {
+        id: String::new(),
+        destination: destination.to_string(),
+        context: str_field("Context").unwrap_or_default(),
+        protocol: str_field("Protocol").unwrap_or_else(|| "Redfish".to_string()),
+        registry_prefixes,
+    });
+    let location = subscription_resource(&subscription.id).odata_id;
+    let mut response = subscription.to_json().into_response(StatusCode::CREATED);
+    if let Ok(location) = HeaderValue::from_str(&location) {
+        response.headers_mut().insert(LOCATION, location);
+    }
+    response
+}
+
+async fn get_subscription(
+    State(state): State<BmcState>,
+    Path(subscription_id): Path<String>,
+) -> Response {
+    state
+        .event_service_state
+        .find_subscription(&subscription_id)
+        .map(|subscription| subscription.to_json().into_ok_response())
+        .unwrap_or_else(http::not_found)
+}
+
+async fn delete_subscription(
+    State(state): State<BmcState>,
+    Path(subscription_id): Path<String>,
+) -> Response {
+    if state
+        .event_service_state
+        .remove_subscription(&subscription_id)
+    {
+        http::ok_no_content()
+    } else {
+        http::not_found()
+    }
+}
+
+async fn get_sse(State(state): State<BmcState>) -> Response {
+    let receiver = state.event_service_state.subscribe();
+    let stream = futures::stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(payload) => {
+                    let event = SseEvent::default()
+                        .id(payload["Id"].as_str().unwrap_or_default())
+                        .data(payload.to_string());
+                    return Some((Ok::<_, Infallible>(event), receiver));
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("SSE client is too slow, {skipped} events dropped");
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+async fn submit_test_event(State(state): State<BmcState>) -> Response {
+    state
+        .event_service_state
+        .publish(ResourceEvent::Changed, &resource().odata_id);
+    http::ok_no_content()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::Router;
+    use axum::body::Body;
+    use axum::http::header::{CONTENT_TYPE, LOCATION};
+    use axum::http::{Method, Request, StatusCode};
+    use axum::response::Response;
+    use http_body_util::BodyExt;
+    use serde_json::{Value, json};
+    use tower::Service;
+
+    use crate::*;
+
+    #[derive(Debug)]
+    struct TestCallbacks {}
+
+    impl Callbacks for TestCallbacks {
+        fn get_power_state(&self) -> MockPowerState {
+            MockPowerState::On
+        }
+        fn send_power_command(&self, _: SystemPowerControl) -> Result<(), SetSystemPowerError> {
+            Ok(())
+        }
+        fn state_refresh_indication(&self) {}
+    }
+
+    fn test_host_mock() -> Router {
+        crate::machine_router(
+            MachineInfo::Host(HostMachineInfo::new(
+                HostHardwareType::DellPowerEdgeR750,
+                vec![DpuMachineInfo::default()],
+            )),
+            Arc::new(TestCallbacks {}),
+            String::default(),
+            false,
+        )
+        .0
+    }
+
+    async fn call(router: &mut Router, method: Method, uri: &str, body: Option<Value>) -> Response {
+        let request = Request::builder().method(method).uri(uri);
+        let request = match body {
+            Some(body) => request
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => request.body(Body::empty()),
+        };
+        router.call(request.unwrap()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_subscriptions() {
+        let mut bmc_mock = test_host_mock();
+        let subscriptions = "/redfish/v1/EventService/Subscriptions";
+
+        let response = call(
+            &mut bmc_mock,
+            Method::POST,
+            subscriptions,
+            Some(json!({"Destination": "https://127.0.0.1:1/events", "Context": "test"})),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let location = response.headers()[LOCATION].to_str().unwrap().to_string();
+
+        let response = call(&mut bmc_mock, Method::GET, &location, None).await;
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let subscription: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(subscription["Context"], "test");
+        assert_eq!(subscription["Protocol"], "Redfish");
+
+        let response = call(&mut bmc_mock, Method::DELETE, &location, None).await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = call(&mut bmc_mock, Method::GET, &location, None).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_sse_reports_power_state_changes() {
+        let mut bmc_mock = test_host_mock();
+        let response = call(
+            &mut bmc_mock,
+            Method::GET,
+            "/redfish/v1/EventService/SSE",
+            None,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let mut events = response.into_body();
+
+        let response = call(
+            &mut bmc_mock,
+            Method::POST,
+            "/redfish/v1/Systems/System.Embedded.1/Actions/ComputerSystem.Reset",
+            Some(json!({"ResetType": "ForceRestart"})),
+        )
+        .await;
+        assert!(response.status().is_success());
+
+        let frame = events.frame().await.unwrap().unwrap().into_data().unwrap();
+        let frame = String::from_utf8(frame.to_vec()).unwrap();
+        let data = frame
+            .lines()
+            .find_map(|line| line.strip_prefix("data: "))
+            .expect("SSE frame carries data");
+        let event: Value = serde_json::from_str(data).unwrap();
+        assert_eq!(
+            event["Events"][0]["MessageId"],
+            "ResourceEvent.1.3.ResourcePowerStateChanged"
+        );
+        assert_eq!(
+            event["Events"][0]["OriginOfCondition"]["@odata.id"],
+            "/redfish/v1/Systems/System.Embedded.1"
+        );
+    }
+}
@@ -0,0 +1,268 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Stateful replay of a [recording](crate::recording).
+//!
+//! The recording is treated as a timeline. Each resource (method and
+//! URI) keeps its own cursor, so every request is answered with the
+//! next recorded response for the same resource and polling a task
+//! walks through the recorded task states, no matter how polls of
+//! different resources interleave. A GET never moves past a recorded
+//! action (POST/PATCH/PUT/DELETE) the client hasn't sent yet: until it
+//! does, GETs keep returning the state from before the action. Once the
+//! action is sent, states recorded before it are skipped. Once the
+//! recorded responses for a resource run out, the last one keeps being
+//! served.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::Router;
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::{HeaderName, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+
+use crate::recording::{RecordedExchange, normalize_uri};
+
+/// Create a router that replays `exchanges`.
+pub fn replay_router(exchanges: Vec<RecordedExchange>) -> Router {
+    Router::new()
+        .fallback(replay)
+        .with_state(Arc::new(Mutex::new(Replay::new(exchanges))))
+}
+
+#[derive(Debug)]
+struct Replay {
+    exchanges: Vec<RecordedExchange>,
+    // Per method and URI, the index of the first exchange that hasn't
+    // been replayed yet.
+    cursors: HashMap<(String, String), usize>,
+    // Index of the exchange after the latest replayed action. Exchanges
+    // before it describe states the client has already moved past.
+    floor: usize,
+    // Index of the first action after `floor`. GETs recorded after it
+    // describe states the client hasn't caused yet.
+    horizon: usize,
+}
+
+impl Replay {
+    fn new(exchanges: Vec<RecordedExchange>) -> Self {
+        let mut replay = Self {
+            exchanges,
+            cursors: HashMap::new(),
+            floor: 0,
+            horizon: 0,
+        };
+        replay.horizon = replay.next_action(0);
+        replay
+    }
+
+    fn next_action(&self, from: usize) -> usize {
+        self.exchanges[from..]
+            .iter()
+            .position(|exchange| !exchange.is_get())
+            .map_or(self.exchanges.len(), |offset| from + offset)
+    }
+
+    fn select(&mut self, method: &str, uri: &str) -> Option<&RecordedExchange> {
+        let matches =
+            |exchange: &RecordedExchange| exchange.method == method && exchange.uri == uri;
+        let is_get = method == "GET" || method == "HEAD";
+        let cursor = self
+            .cursors
+            .entry((method.to_string(), uri.to_string()))
+            .or_default();
+
+        let (start, end) = if is_get {
+            ((*cursor).max(self.floor), self.horizon)
+        } else {
+            (*cursor, self.exchanges.len())
+        };
+        let ahead = self
+            .exchanges
+            .get(start..end)
+            .and_then(|exchanges| exchanges.iter().position(matches));
+        if let Some(offset) = ahead {
+            let index = start + offset;
+            *cursor = index + 1;
+            if !is_get {
+                self.floor = self.floor.max(index + 1);
+                self.horizon = self.next_action(self.floor);
+            }
+            return self.exchanges.get(index);
+        }
+
+        // Nothing left ahead: serve the latest state the client can have
+        // caused so far, or for resources only recorded later, the
+        // first recording.
+        let index = self.exchanges[..self.horizon]
+            .iter()
+            .rposition(matches)
+            .or_else(|| self.exchanges.iter().position(matches))?;
+        self.exchanges.get(index)
+    }
+}
+
+async fn replay(State(replay): State<Arc<Mutex<Replay>>>, request: Request<Body>) -> Response {
+    let method = request.method().to_string();
+    let uri = normalize_uri(request.uri().path(), request.uri().query());
+    let Some(exchange) = replay.lock().unwrap().select(&method, &uri).cloned() else {
+        tracing::warn!(method, uri, "Request is not in the recording");
+        return (
+            StatusCode::NOT_FOUND,
+            format!("bmc-mock: {method} {uri} is not in the recording"),
+        )
+            .into_response();
+    };
+
+    let mut response = Response::new(Body::from(exchange.response_body));
+    *response.status_mut() =
+        StatusCode::from_u16(exchange.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    for (name, value) in exchange.headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::try_from(name.as_str()),
+            HeaderValue::try_from(value.as_str()),
+        ) {
+            response.headers_mut().insert(name, value);
+        }
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use axum::body::Body;
+    use axum::http::{Method, Request};
+    use http_body_util::BodyExt;
+    use tower::Service;
+
+    use super::*;
+
+    const TASK: &str = "/redfish/v1/TaskService/Tasks/JID_1";
+    const UPDATE: &str = "/redfish/v1/UpdateService/Actions/UpdateService.SimpleUpdate";
+    const FIRMWARE: &str = "/redfish/v1/UpdateService/FirmwareInventory/BIOS";
+    const OTHER_TASK: &str = "/redfish/v1/TaskService/Tasks/JID_2";
+
+    fn exchange(method: &str, uri: &str, status: u16, body: &str) -> RecordedExchange {
+        RecordedExchange {
+            offset_ms: 0,
+            method: method.to_string(),
+            uri: uri.to_string(),
+            request_body: None,
+            status,
+            headers: BTreeMap::new(),
+            response_body: body.to_string(),
+        }
+    }
+
+    async fn call(router: &mut Router, method: Method, uri: &str) -> (u16, String) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+        let response = router.call(request).await.unwrap();
+        let status = response.status().as_u16();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_replay_timeline() {
+        let mut update = exchange("POST", UPDATE, 202, "");
+        update
+            .headers
+            .insert("location".to_string(), TASK.to_string());
+        let mut router = replay_router(vec![
+            exchange("GET", FIRMWARE, 200, "1.0"),
+            update,
+            exchange("GET", TASK, 200, "Running"),
+            exchange("GET", TASK, 200, "Completed"),
+            exchange("GET", FIRMWARE, 200, "2.0"),
+        ]);
+
+        // Until the update is sent, the old firmware keeps being reported.
+        assert_eq!(call(&mut router, Method::GET, FIRMWARE).await.1, "1.0");
+        assert_eq!(call(&mut router, Method::GET, FIRMWARE).await.1, "1.0");
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(UPDATE)
+            .body(Body::empty())
+            .unwrap();
+        let response = router.call(request).await.unwrap();
+        assert_eq!(response.status().as_u16(), 202);
+        assert_eq!(response.headers()["location"], TASK);
+
+        // The task progresses, and then stays completed.
+        assert_eq!(call(&mut router, Method::GET, TASK).await.1, "Running");
+        assert_eq!(call(&mut router, Method::GET, TASK).await.1, "Completed");
+        assert_eq!(call(&mut router, Method::GET, TASK).await.1, "Completed");
+        assert_eq!(call(&mut router, Method::GET, FIRMWARE).await.1, "2.0");
+
+        // Trailing slashes don't matter, unknown resources are not found.
+        assert_eq!(
+            call(&mut router, Method::GET, &format!("{FIRMWARE}/"))
+                .await
+                .1,
+            "2.0"
+        );
+        assert_eq!(
+            call(&mut router, Method::GET, "/redfish/v1/Nope").await.0,
+            404
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replay_out_of_order_polling() {
+        let mut router = replay_router(vec![
+            exchange("GET", TASK, 200, "Running"),
+            exchange("GET", TASK, 200, "Running 50%"),
+            exchange("GET", OTHER_TASK, 200, "Running"),
+            exchange("GET", TASK, 200, "Completed"),
+            exchange("GET", OTHER_TASK, 200, "Completed"),
+            exchange("GET", FIRMWARE, 200, "1.0"),
+            exchange("POST", UPDATE, 202, ""),
+            exchange("GET", FIRMWARE, 200, "2.0"),
+        ]);
+
+        // Polling resources recorded later doesn't skip the states of
+        // resources recorded earlier.
+        assert_eq!(call(&mut router, Method::GET, FIRMWARE).await.1, "1.0");
+        assert_eq!(
+            call(&mut router, Method::GET, OTHER_TASK).await.1,
+            "Running"
+        );
+        assert_eq!(
+            call(&mut router, Method::GET, OTHER_TASK).await.1,
+            "Completed"
+        );
+        assert_eq!(call(&mut router, Method::GET, TASK).await.1, "Running");
+        assert_eq!(call(&mut router, Method::GET, TASK).await.1, "Running 50%");
+        assert_eq!(call(&mut router, Method::GET, TASK).await.1, "Completed");
+        assert_eq!(call(&mut router, Method::GET, TASK).await.1, "Completed");
+
+        // The action moves the timeline, repeating it replays the last response.
+        assert_eq!(call(&mut router, Method::POST, UPDATE).await.0, 202);
+        assert_eq!(call(&mut router, Method::GET, FIRMWARE).await.1, "2.0");
+        assert_eq!(call(&mut router, Method::POST, UPDATE).await.0, 202);
+        assert_eq!(call(&mut router, Method::GET, FIRMWARE).await.1, "2.0");
+    }
+}
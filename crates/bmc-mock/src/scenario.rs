@@ -0,0 +1,844 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Scriptable fault-injection scenarios.
+//!
+//! A scenario is an ordered list of steps loaded from YAML (or JSON).
+//! Each step optionally waits for a matching request, then for its
+//! `after` delay, and then activates a [`Fault`]. The fault stays in
+//! effect for `duration` or until it has affected `times` requests,
+//! whichever comes first; with neither set it lasts until the scenario
+//! is cleared.
+//!
+//! BMC reboot in the middle of a firmware update:
+//!
+//! ```yaml
+//! name: bmc-reboot-mid-update
+//! steps:
+//!   - on_request: { method: POST, path: "/redfish/v1/UpdateService/Actions/.*" }
+//!     fault: { kind: task_stuck, percent_complete: 40 }
+//!     duration: 30s
+//!   - after: 30s
+//!     fault: { kind: bmc_reboot }
+//!     duration: 2m
+//!   - after: 2m
+//!     fault: { kind: http_error, path: "/redfish/v1/TaskService/Tasks/.*", status: 404 }
+//! ```
+//!
+//! Steps are evaluated lazily when requests arrive, which is the only
+//! point where a fault can be observed anyway.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::http::header::{CONTENT_LENGTH, ETAG, IF_MATCH, WWW_AUTHENTICATE};
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode};
+use axum::response::{IntoResponse, Response};
+use duration_str::{deserialize_duration, deserialize_option_duration};
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::json;
+use tokio::time::Instant;
+
+use crate::json::JsonExt;
+
+/// Requests under this prefix control the mock itself and are never
+/// affected by a scenario.
+const CONTROL_PATH_PREFIX: &str = "/InjectedBugs";
+const SYSTEMS_PATH_PREFIX: &str = "/redfish/v1/Systems/";
+const SYSTEM_RESET_SUFFIX: &str = "/Actions/ComputerSystem.Reset";
+const TASKS_PATH_PREFIX: &str = "/redfish/v1/TaskService/Tasks/";
+const STALE_ETAG: HeaderValue = HeaderValue::from_static("W/\"bmc-mock-stale\"");
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to read scenario file {0}: {1}")]
+    Read(PathBuf, #[source] std::io::Error),
+    #[error("failed to parse scenario: {0}")]
+    Parse(#[from] serde_yaml::Error),
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    #[serde(default)]
+    pub name: String,
+    pub steps: Vec<Step>,
+}
+
+impl Scenario {
+    pub fn from_yaml(s: &str) -> Result<Self, Error> {
+        Ok(serde_yaml::from_str(s)?)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, Error> {
+        let s = std::fs::read_to_string(path).map_err(|e| Error::Read(path.to_path_buf(), e))?;
+        Self::from_yaml(&s)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Step {
+    /// Do not start the `after` delay until a matching request is
+    /// seen. The matching request itself is not affected by this step.
+    #[serde(default)]
+    pub on_request: Option<RequestMatch>,
+    /// Delay since the previous step was activated (or since the
+    /// scenario was started or the trigger request was seen).
+    #[serde(default, deserialize_with = "deserialize_option_duration")]
+    pub after: Option<Duration>,
+    #[serde(default, deserialize_with = "deserialize_option_duration")]
+    pub duration: Option<Duration>,
+    /// Number of requests the fault affects before it expires.
+    #[serde(default)]
+    pub times: Option<usize>,
+    pub fault: Fault,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RequestMatch {
+    #[serde(default)]
+    pub method: Option<String>,
+    pub path: PathPattern,
+}
+
+impl RequestMatch {
+    fn matches(&self, method: &Method, path: &str) -> bool {
+        method_matches(self.method.as_deref(), method) && self.path.is_match(path)
+    }
+}
+
+/// Regular expression that has to match the whole request path.
+#[derive(Clone, Debug)]
+pub struct PathPattern {
+    source: String,
+    regex: Regex,
+}
+
+impl PathPattern {
+    pub fn new(source: impl Into<String>) -> Result<Self, regex::Error> {
+        let source = source.into();
+        let regex = Regex::new(&format!("^(?:{source})$"))?;
+        Ok(Self { source, regex })
+    }
+
+    pub fn is_match(&self, path: &str) -> bool {
+        self.regex.is_match(path)
+    }
+}
+
+impl Serialize for PathPattern {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+impl<'de> Deserialize<'de> for PathPattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let source = String::deserialize(deserializer)?;
+        Self::new(source).map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Fault {
+    /// BMC is rebooting: every request fails with 503.
+    BmcReboot,
+    /// Sessions were dropped by the BMC: every authenticated request
+    /// fails with 401.
+    InvalidateSessions,
+    /// GETs of `path` advertise an ETag that conditional writes are
+    /// then rejected for with 412.
+    StaleEtag { path: PathPattern },
+    /// Systems report `power_state`; reset requests are accepted and
+    /// ignored.
+    PowerStateStuck { power_state: String },
+    /// Every task reports Running at `percent_complete`.
+    TaskStuck {
+        #[serde(default = "default_stuck_percent")]
+        percent_complete: u8,
+    },
+    /// `LinkStatus` of matching interfaces alternates between LinkDown
+    /// and LinkUp every `period`.
+    FlappingInterface {
+        path: PathPattern,
+        #[serde(deserialize_with = "deserialize_duration")]
+        period: Duration,
+    },
+    /// Reloads the server certificate from `cert_dir` on activation.
+    TlsCertRotation { cert_dir: PathBuf },
+    HttpError {
+        path: PathPattern,
+        #[serde(default)]
+        method: Option<String>,
+        status: u16,
+    },
+    Delay {
+        #[serde(default)]
+        path: Option<PathPattern>,
+        #[serde(deserialize_with = "deserialize_duration")]
+        delay: Duration,
+    },
+}
+
+fn default_stuck_percent() -> u8 {
+    99
+}
+
+impl Fault {
+    /// Faults that act once when activated instead of on requests.
+    fn is_one_shot(&self) -> bool {
+        matches!(self, Self::TlsCertRotation { .. })
+    }
+
+    fn rewrites_response(&self) -> bool {
+        matches!(
+            self,
+            Self::StaleEtag { .. }
+                | Self::PowerStateStuck { .. }
+                | Self::TaskStuck { .. }
+                | Self::FlappingInterface { .. }
+        )
+    }
+
+    fn applies_to(&self, method: &Method, path: &str) -> bool {
+        match self {
+            Self::BmcReboot => true,
+            Self::InvalidateSessions => !is_service_root(path),
+            Self::StaleEtag { path: pattern } => pattern.is_match(path),
+            Self::PowerStateStuck { .. } => is_system_path(path),
+            Self::TaskStuck { .. } => method == Method::GET && path.starts_with(TASKS_PATH_PREFIX),
+            Self::FlappingInterface { path: pattern, .. } => {
+                method == Method::GET && pattern.is_match(path)
+            }
+            Self::TlsCertRotation { .. } => false,
+            Self::HttpError {
+                path: pattern,
+                method: expected,
+                ..
+            } => method_matches(expected.as_deref(), method) && pattern.is_match(path),
+            Self::Delay { path: pattern, .. } => pattern.as_ref().is_none_or(|p| p.is_match(path)),
+        }
+    }
+}
+
+fn method_matches(expected: Option<&str>, method: &Method) -> bool {
+    expected.is_none_or(|m| m.eq_ignore_ascii_case(method.as_str()))
+}
+
+fn is_service_root(path: &str) -> bool {
+    path.trim_end_matches('/') == "/redfish/v1"
+}
+
+/// `/redfish/v1/Systems/{id}` or its reset action.
+fn is_system_path(path: &str) -> bool {
+    path.strip_prefix(SYSTEMS_PATH_PREFIX)
+        .map(|rest| rest.strip_suffix(SYSTEM_RESET_SUFFIX).unwrap_or(rest))
+        .is_some_and(|id| !id.is_empty() && !id.contains('/'))
+}
+
+/// A scenario being played against a mock BMC.
+#[derive(Debug)]
+pub struct ScenarioRun {
+    scenario: Scenario,
+    started: Instant,
+    /// Index of the next step to activate.
+    next_step: usize,
+    /// When the `after` delay of the next step started counting. None
+    /// while the step waits for its trigger request.
+    next_reference: Option<Instant>,
+    active: Vec<ActiveFault>,
+}
+
+#[derive(Debug)]
+struct ActiveFault {
+    step: usize,
+    fault: Fault,
+    activated: Instant,
+    expires: Option<Instant>,
+    remaining: Option<usize>,
+}
+
+impl ActiveFault {
+    fn is_live(&self, now: Instant) -> bool {
+        self.expires.is_none_or(|expires| now < expires) && self.remaining != Some(0)
+    }
+}
+
+/// Result of evaluating a scenario for one request.
+#[derive(Debug, Default)]
+pub struct Evaluation {
+    /// One-shot faults activated by this evaluation.
+    pub activated: Vec<Fault>,
+    pub faults: AppliedFaults,
+}
+
+impl ScenarioRun {
+    pub fn start(scenario: Scenario, now: Instant) -> Self {
+        tracing::info!(name = scenario.name, "Starting fault-injection scenario");
+        let next_reference = Self::reference_for(scenario.steps.first(), now);
+        Self {
+            scenario,
+            started: now,
+            next_step: 0,
+            next_reference,
+            active: vec![],
+        }
+    }
+
+    fn reference_for(step: Option<&Step>, now: Instant) -> Option<Instant> {
+        step.filter(|step| step.on_request.is_none()).map(|_| now)
+    }
+
+    /// Activates the steps that are due, then returns the faults that
+    /// apply to this request.
+    pub fn evaluate(&mut self, now: Instant, method: &Method, path: &str) -> Evaluation {
+        if path.starts_with(CONTROL_PATH_PREFIX) {
+            return Evaluation::default();
+        }
+        let activated = self.activate_due_steps(now);
+        self.active
+            .retain(|f| f.expires.is_none_or(|expires| now < expires) && f.remaining != Some(0));
+
+        let mut faults = vec![];
+        for active in self.active.iter_mut() {
+            if !active.fault.applies_to(method, path) {
+                continue;
+            }
+            if let Some(remaining) = active.remaining.as_mut() {
+                *remaining -= 1;
+            }
+            faults.push(AppliedFault {
+                fault: active.fault.clone(),
+                activated: active.activated,
+                evaluated: now,
+            });
+        }
+
+        if self.next_reference.is_none()
+            && let Some(step) = self.scenario.steps.get(self.next_step)
+            && step
+                .on_request
+                .as_ref()
+                .is_some_and(|m| m.matches(method, path))
+        {
+            tracing::info!(
+                name = self.scenario.name,
+                step = self.next_step,
+                %method,
+                path,
+                "Scenario step triggered"
+            );
+            self.next_reference = Some(now);
+        }
+
+        Evaluation {
+            activated,
+            faults: AppliedFaults { faults },
+        }
+    }
+
+    fn activate_due_steps(&mut self, now: Instant) -> Vec<Fault> {
+        let mut one_shots = vec![];
+        while let Some(step) = self.scenario.steps.get(self.next_step)
+            && let Some(reference) = self.next_reference
+        {
+            let at = reference + step.after.unwrap_or_default();
+            if at > now {
+                break;
+            }
+            tracing::warn!(
+                name = self.scenario.name,
+                step = self.next_step,
+                fault = ?step.fault,
+                "Scenario fault activated"
+            );
+            if step.fault.is_one_shot() {
+                one_shots.push(step.fault.clone());
+            } else {
+                self.active.push(ActiveFault {
+                    step: self.next_step,
+                    fault: step.fault.clone(),
+                    activated: at,
+                    expires: step.duration.map(|d| at + d),
+                    remaining: step.times,
+                });
+            }
+            self.next_step += 1;
+            self.next_reference = Self::reference_for(self.scenario.steps.get(self.next_step), at);
+        }
+        one_shots
+    }
+
+    pub fn status(&self, now: Instant) -> serde_json::Value {
+        let active = self
+            .active
+            .iter()
+            .filter(|f| f.is_live(now))
+            .map(|f| json!({"step": f.step, "fault": f.fault, "remaining": f.remaining}))
+            .collect::<Vec<_>>();
+        let waiting_for_trigger =
+            self.next_step < self.scenario.steps.len() && self.next_reference.is_none();
+        json!({
+            "name": self.scenario.name,
+            "elapsed": format!("{:?}", now - self.started),
+            "next_step": self.next_step,
+            "waiting_for_trigger": waiting_for_trigger,
+            "active": active,
+            "scenario": self.scenario,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct AppliedFault {
+    fault: Fault,
+    activated: Instant,
+    /// When the request was seen.
+    evaluated: Instant,
+}
+
+/// Faults that apply to a single request.
+#[derive(Debug, Default)]
+pub struct AppliedFaults {
+    faults: Vec<AppliedFault>,
+}
+
+impl AppliedFaults {
+    pub fn is_empty(&self) -> bool {
+        self.faults.is_empty()
+    }
+
+    pub fn delay(&self) -> Option<Duration> {
+        self.faults
+            .iter()
+            .filter_map(|f| match &f.fault {
+                Fault::Delay { delay, .. } => Some(*delay),
+                _ => None,
+            })
+            .max()
+    }
+
+    /// Response that replaces the one of the inner router, if any.
+    pub fn short_circuit(&self, method: &Method, headers: &HeaderMap) -> Option<Response> {
+        self.faults.iter().find_map(|f| match &f.fault {
+            Fault::BmcReboot => Some(StatusCode::SERVICE_UNAVAILABLE.into_response()),
+            Fault::InvalidateSessions => Some(
+                (
+                    StatusCode::UNAUTHORIZED,
+                    [(WWW_AUTHENTICATE, "Basic realm=\"bmc-mock\"")],
+                )
+                    .into_response(),
+            ),
+            Fault::HttpError { status, .. } => Some(
+                StatusCode::from_u16(*status)
+                    .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+                    .into_response(),
+            ),
+            Fault::StaleEtag { .. } if !method.is_safe() && headers.contains_key(IF_MATCH) => Some(
+                json!({"error": "ETag does not match the current resource"})
+                    .into_response(StatusCode::PRECONDITION_FAILED),
+            ),
+            Fault::PowerStateStuck { .. } if *method == Method::POST => {
+                Some(StatusCode::NO_CONTENT.into_response())
+            }
+            _ => None,
+        })
+    }
+
+    /// Applies the faults that alter successful responses.
+    pub async fn rewrite(&self, response: Response) -> Response {
+        if !response.status().is_success()
+            || !self.faults.iter().any(|f| f.fault.rewrites_response())
+        {
+            return response;
+        }
+        let (mut parts, body) = response.into_parts();
+        let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                tracing::warn!("Cannot read response body to inject faults: {err}");
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+        };
+        let Ok(mut value) = serde_json::from_slice::<serde_json::Value>(&bytes) else {
+            return Response::from_parts(parts, Body::from(bytes));
+        };
+        for f in &self.faults {
+            value = match &f.fault {
+                Fault::PowerStateStuck { power_state } => {
+                    value.patch(json!({ "PowerState": power_state }))
+                }
+                Fault::TaskStuck { percent_complete } => value.patch(json!({
+                    "PercentComplete": percent_complete,
+                    "TaskState": "Running",
+                    "TaskStatus": "OK",
+                })),
+                Fault::FlappingInterface { period, .. } => {
+                    let flips = (f.evaluated - f.activated)
+                        .as_millis()
+                        .checked_div(period.as_millis())
+                        .unwrap_or(0);
+                    let link_status = if flips % 2 == 0 { "LinkDown" } else { "LinkUp" };
+                    value.patch(json!({ "LinkStatus": link_status }))
+                }
+                Fault::StaleEtag { .. } => {
+                    parts.headers.insert(ETAG, STALE_ETAG);
+                    value
+                }
+                _ => value,
+            };
+        }
+        parts.headers.remove(CONTENT_LENGTH);
+        Response::from_parts(parts, Body::from(value.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use axum::http::header::{ETAG, IF_MATCH};
+    use axum::http::{HeaderMap, HeaderValue, Method, StatusCode};
+    use axum::response::IntoResponse;
+    use serde_json::{Value, json};
+    use tokio::time::Instant;
+
+    use super::{Fault, STALE_ETAG, Scenario, ScenarioRun};
+
+    const SCENARIO: &str = r#"
+name: reboot-after-reset
+steps:
+  - on_request: { method: POST, path: "/redfish/v1/Systems/[^/]+/Actions/ComputerSystem.Reset" }
+    after: 10s
+    fault: { kind: bmc_reboot }
+    times: 2
+  - fault: { kind: task_stuck }
+    duration: 1m
+"#;
+
+    const TASK: &str = "/redfish/v1/TaskService/Tasks/JID_1";
+    const BIOS_SETTINGS: &str = "/redfish/v1/Systems/System.Embedded.1/Bios/Settings";
+    const INTERFACE: &str =
+        "/redfish/v1/Managers/iDRAC.Embedded.1/EthernetInterfaces/NIC.Embedded.1";
+
+    fn start(yaml: &str) -> (ScenarioRun, Instant) {
+        let start = Instant::now();
+        (
+            ScenarioRun::start(Scenario::from_yaml(yaml).unwrap(), start),
+            start,
+        )
+    }
+
+    /// Evaluates the scenario for a GET at `now` and rewrites `body` like
+    /// the mock would rewrite the response of the inner router.
+    async fn get(
+        run: &mut ScenarioRun,
+        now: Instant,
+        path: &str,
+        body: Value,
+    ) -> (HeaderMap, Value) {
+        let faults = run.evaluate(now, &Method::GET, path).faults;
+        let response = faults.rewrite(axum::Json(body).into_response()).await;
+        let headers = response.headers().clone();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (headers, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    fn status(run: &mut ScenarioRun, now: Instant, method: Method, path: &str) -> StatusCode {
+        run.evaluate(now, &method, path)
+            .faults
+            .short_circuit(&method, &HeaderMap::new())
+            .map(|response| response.status())
+            .unwrap_or(StatusCode::OK)
+    }
+
+    #[test]
+    fn steps_wait_for_trigger_and_expire() {
+        let start = Instant::now();
+        let mut run = ScenarioRun::start(Scenario::from_yaml(SCENARIO).unwrap(), start);
+        let at = |secs| start + Duration::from_secs(secs);
+
+        assert_eq!(
+            status(&mut run, at(0), Method::GET, "/redfish/v1"),
+            StatusCode::OK
+        );
+        // The trigger request itself is not affected.
+        let reset = "/redfish/v1/Systems/System.Embedded.1/Actions/ComputerSystem.Reset";
+        assert_eq!(status(&mut run, at(5), Method::POST, reset), StatusCode::OK);
+        assert_eq!(
+            status(&mut run, at(14), Method::GET, "/redfish/v1"),
+            StatusCode::OK
+        );
+
+        // Reboot is activated 10s after the trigger and lasts two requests.
+        for _ in 0..2 {
+            assert_eq!(
+                status(&mut run, at(15), Method::GET, "/redfish/v1"),
+                StatusCode::SERVICE_UNAVAILABLE
+            );
+        }
+        // Control paths are never affected.
+        assert_eq!(
+            status(&mut run, at(15), Method::GET, "/InjectedBugs/Scenario"),
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(&mut run, at(16), Method::GET, "/redfish/v1"),
+            StatusCode::OK
+        );
+
+        // Task is stuck from the reboot on, for a minute.
+        let task = "/redfish/v1/TaskService/Tasks/0";
+        assert_eq!(
+            run.evaluate(at(16), &Method::GET, task).faults.faults.len(),
+            1
+        );
+        assert!(run.evaluate(at(76), &Method::GET, task).faults.is_empty());
+    }
+
+    #[test]
+    fn invalid_path_pattern_is_rejected() {
+        let err = Scenario::from_yaml(
+            "steps: [{ fault: { kind: http_error, path: \"(\", status: 500 } }]",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("regex"), "{err}");
+    }
+
+    #[test]
+    fn timed_steps_activate_in_order() {
+        let (mut run, start) = start(
+            r#"
+steps:
+  - after: 5s
+    fault: { kind: bmc_reboot }
+    duration: 5s
+  - after: 10s
+    fault: { kind: http_error, path: "/redfish/v1/Systems/.*", status: 404 }
+  - after: 1s
+    fault: { kind: invalidate_sessions }
+"#,
+        );
+        let at = |secs| start + Duration::from_secs(secs);
+        let system = "/redfish/v1/Systems/System.Embedded.1";
+
+        assert_eq!(status(&mut run, at(4), Method::GET, system), StatusCode::OK);
+        assert_eq!(
+            status(&mut run, at(5), Method::GET, system),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        // The reboot is over, the next step counts from its activation.
+        assert_eq!(
+            status(&mut run, at(10), Method::GET, system),
+            StatusCode::OK
+        );
+        assert_eq!(run.status(at(10))["next_step"], 1);
+
+        // Steps that became due at once are activated in order, and the
+        // earliest active fault answers the request.
+        assert_eq!(
+            status(&mut run, at(30), Method::GET, system),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            status(&mut run, at(30), Method::GET, "/redfish/v1/Managers"),
+            StatusCode::UNAUTHORIZED
+        );
+        // Sessions are never needed for the service root.
+        assert_eq!(
+            status(&mut run, at(30), Method::GET, "/redfish/v1/"),
+            StatusCode::OK
+        );
+        let status = run.status(at(30));
+        assert_eq!(status["next_step"], 3);
+        assert_eq!(status["waiting_for_trigger"], false);
+        let active_steps = status["active"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["step"].as_u64().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(active_steps, vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn stale_etag_rejects_conditional_writes() {
+        let (mut run, start) = start(&format!(
+            "steps: [{{ fault: {{ kind: stale_etag, path: \"{BIOS_SETTINGS}\" }} }}]"
+        ));
+
+        let (headers, _) = get(&mut run, start, BIOS_SETTINGS, json!({"Attributes": {}})).await;
+        assert_eq!(headers[ETAG], STALE_ETAG);
+        // Other resources keep their ETags.
+        let (headers, _) = get(&mut run, start, TASK, json!({"TaskState": "Running"})).await;
+        assert!(!headers.contains_key(ETAG));
+
+        let mut if_match = HeaderMap::new();
+        if_match.insert(IF_MATCH, HeaderValue::from_static("W/\"bmc-mock-stale\""));
+        let rejected = run
+            .evaluate(start, &Method::PATCH, BIOS_SETTINGS)
+            .faults
+            .short_circuit(&Method::PATCH, &if_match)
+            .unwrap();
+        assert_eq!(rejected.status(), StatusCode::PRECONDITION_FAILED);
+        // Unconditional writes and reads go through.
+        assert!(
+            run.evaluate(start, &Method::PATCH, BIOS_SETTINGS)
+                .faults
+                .short_circuit(&Method::PATCH, &HeaderMap::new())
+                .is_none()
+        );
+        assert!(
+            run.evaluate(start, &Method::GET, BIOS_SETTINGS)
+                .faults
+                .short_circuit(&Method::GET, &if_match)
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn stuck_task_reports_99_percent() {
+        let (mut run, start) = start("steps: [{ fault: { kind: task_stuck } }]");
+
+        let completed = json!({"TaskState": "Completed", "TaskStatus": "OK", "PercentComplete": 100, "Id": "JID_1"});
+        let (_, task) = get(&mut run, start, TASK, completed.clone()).await;
+        assert_eq!(task["TaskState"], "Running");
+        assert_eq!(task["PercentComplete"], 99);
+        assert_eq!(task["Id"], "JID_1");
+
+        // Only task GETs are affected.
+        assert!(run.evaluate(start, &Method::DELETE, TASK).faults.is_empty());
+        let (_, system) = get(&mut run, start, "/redfish/v1/Systems/1", completed).await;
+        assert_eq!(system["TaskState"], "Completed");
+    }
+
+    #[tokio::test]
+    async fn flapping_interface_alternates_every_period() {
+        let (mut run, start) = start(&format!(
+            "steps: [{{ after: 1s, fault: {{ kind: flapping_interface, path: \"{INTERFACE}\", period: 10s }} }}]"
+        ));
+        let at = |millis| start + Duration::from_millis(millis);
+        let up = json!({"LinkStatus": "LinkUp", "Id": "NIC.Embedded.1"});
+
+        let mut link_status = async |millis| {
+            get(&mut run, at(millis), INTERFACE, up.clone()).await.1["LinkStatus"].clone()
+        };
+        // Not yet active.
+        assert_eq!(link_status(500).await, "LinkUp");
+        // The period counts from the activation of the step.
+        assert_eq!(link_status(1_000).await, "LinkDown");
+        assert_eq!(link_status(10_999).await, "LinkDown");
+        assert_eq!(link_status(11_000).await, "LinkUp");
+        assert_eq!(link_status(20_999).await, "LinkUp");
+        assert_eq!(link_status(21_000).await, "LinkDown");
+
+        let (_, other) = get(&mut run, at(21_000), "/redfish/v1/Managers/1", up).await;
+        assert_eq!(other["LinkStatus"], "LinkUp");
+    }
+
+    #[test]
+    fn tls_cert_rotation_happens_once_on_activation() {
+        let (mut run, start) = start(
+            "steps: [{ after: 2s, fault: { kind: tls_cert_rotation, cert_dir: /tmp/certs } }]",
+        );
+        let at = |secs| start + Duration::from_secs(secs);
+
+        assert!(
+            run.evaluate(at(1), &Method::GET, "/redfish/v1")
+                .activated
+                .is_empty()
+        );
+        let evaluation = run.evaluate(at(2), &Method::GET, "/redfish/v1");
+        assert!(matches!(
+            evaluation.activated.as_slice(),
+            [Fault::TlsCertRotation { cert_dir }] if cert_dir.to_str() == Some("/tmp/certs")
+        ));
+        // Rotation doesn't affect requests, and is not repeated.
+        assert!(evaluation.faults.is_empty());
+        let evaluation = run.evaluate(at(60), &Method::GET, "/redfish/v1");
+        assert!(evaluation.activated.is_empty());
+        assert!(evaluation.faults.is_empty());
+        assert!(run.status(at(60))["active"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn scenario_file_keeps_step_order() {
+        // The example from the module documentation.
+        let yaml = r#"
+name: bmc-reboot-mid-update
+steps:
+  - on_request: { method: POST, path: "/redfish/v1/UpdateService/Actions/.*" }
+    fault: { kind: task_stuck, percent_complete: 40 }
+    duration: 30s
+  - after: 30s
+    fault: { kind: bmc_reboot }
+    duration: 2m
+  - after: 2m
+    fault: { kind: http_error, path: "/redfish/v1/TaskService/Tasks/.*", status: 404 }
+"#;
+        let path =
+            std::env::temp_dir().join(format!("bmc-mock-scenario-{}.yaml", std::process::id()));
+        std::fs::write(&path, yaml).unwrap();
+        let scenario = Scenario::from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(scenario.name, "bmc-reboot-mid-update");
+        assert!(matches!(
+            scenario.steps.as_slice(),
+            [
+                super::Step {
+                    fault: Fault::TaskStuck {
+                        percent_complete: 40
+                    },
+                    ..
+                },
+                super::Step {
+                    fault: Fault::BmcReboot,
+                    ..
+                },
+                super::Step {
+                    fault: Fault::HttpError { status: 404, .. },
+                    ..
+                },
+            ]
+        ));
+        assert!(scenario.steps[0].on_request.is_some());
+        assert_eq!(scenario.steps[1].after, Some(Duration::from_secs(30)));
+        assert_eq!(scenario.steps[1].duration, Some(Duration::from_secs(120)));
+
+        // The trigger starts the timeline: the first step is waiting for it.
+        let start = Instant::now();
+        let mut run = ScenarioRun::start(scenario, start);
+        assert_eq!(run.status(start)["waiting_for_trigger"], true);
+        let update = "/redfish/v1/UpdateService/Actions/UpdateService.SimpleUpdate";
+        run.evaluate(start, &Method::POST, update);
+        let faults = run.evaluate(start, &Method::GET, TASK).faults;
+        assert_eq!(faults.faults.len(), 1);
+
+        let err = Scenario::from_file(&path).unwrap_err();
+        assert!(matches!(err, super::Error::Read(..)), "{err}");
+        let err = Scenario::from_yaml("steps: [{ fault: { kind: nope } }]").unwrap_err();
+        assert!(matches!(err, super::Error::Parse(_)), "{err}");
+    }
+}
@@ -222,6 +222,54 @@ impl FromStr for AclEntry {
     }
 }
 
+/// A verb and path pattern using the same syntax as an [`AclEntry`], but without an allow/deny
+/// action. Used by the request policy engine to scope rules and rate limits to a set of requests.
+///
+/// Examples:
+///
+/// - `PATCH /redfish/v1/Systems/*/Bios/Settings`
+/// - `/redfish/v1/**`: Any method on anything under /redfish/v1/
+#[derive(Clone)]
+pub struct RequestPattern(AclEntry);
+
+impl RequestPattern {
+    /// Returns whether this pattern matches `method` and `path`, with the same semantics as an ACL
+    /// entry.
+    pub fn matches(&self, method: &http::Method, path: &str) -> bool {
+        self.0.matches(method, path)
+    }
+}
+
+impl FromStr for RequestPattern {
+    type Err = AclPathParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        if input.trim_start().starts_with('!') {
+            return Err(AclPathParseError {
+                orig: input.to_string(),
+                err: "Request patterns cannot be negated with '!'".to_string(),
+            });
+        }
+        input.parse().map(Self)
+    }
+}
+
+impl<'de> Deserialize<'de> for RequestPattern {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+
+impl Display for RequestPattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
 /// The authorization decision produced by a matching ACL entry.
 #[derive(Copy, Clone)]
 enum AclAction {
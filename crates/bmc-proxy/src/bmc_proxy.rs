@@ -37,6 +37,7 @@ use tokio_util::sync::CancellationToken;
 use tower_http::add_extension::AddExtensionLayer;
 
 use crate::config::{AuthConfig, TlsConfig};
+use crate::policy::{PolicyEngine, PolicyRequest};
 
 const TLS_REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
 const MAX_BODY_SIZE: usize = 8 * 1024 * 1024; // 8MiB body size limit (matches nginx ingress controller defaults)
@@ -70,6 +71,7 @@ struct BmcProxyState {
     meter: Meter,
     pg_pool: PgPool,
     credential_manager: Arc<dyn CredentialManager>,
+    policy: Arc<PolicyEngine>,
 }
 
 impl BmcProxyState {
@@ -118,11 +120,14 @@ pub async fn start(
         .await
         .map_err(BmcProxyError::Listen)?;
 
+    let policy = Arc::new(PolicyEngine::new(config.policy.clone(), &meter));
+
     let state = BmcProxyState {
         config,
         pg_pool,
         credential_manager,
         meter,
+        policy,
     };
 
     let app = Router::new()
@@ -437,6 +442,17 @@ async fn proxy_request(
     if !state.allows(&request) {
         return Ok(error_response((StatusCode::FORBIDDEN, "Forbidden").into()));
     }
+    let principals = request
+        .extensions()
+        .get::<AuthContext<()>>()
+        .map(|auth_context| {
+            auth_context
+                .principals
+                .iter()
+                .map(Principal::as_identifier)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
     let (parts, body) = request.into_parts();
     let target_ip = forwarded_host_ip(&parts.headers)
         .ok_or_else(|| {
@@ -450,6 +466,28 @@ async fn proxy_request(
         })?
         .map_err(|e| error_response((StatusCode::BAD_REQUEST, e.to_string()).into()))?;
 
+    // The body is needed to evaluate the request policy, so read it before doing anything else
+    let body = axum::body::to_bytes(body, MAX_BODY_SIZE)
+        .await
+        .map_err(|e| error_response((StatusCode::BAD_REQUEST, e.to_string()).into()))?;
+
+    let policy_decision = state
+        .policy
+        .evaluate(
+            &PolicyRequest {
+                principals: &principals,
+                method: &parts.method,
+                path: parts.uri.path(),
+                bmc_ip: target_ip,
+                body: &body,
+            },
+            &state.pg_pool,
+        )
+        .await;
+    if let Some(rejection) = policy_decision.rejection() {
+        return Ok(error_response(rejection.into()));
+    }
+
     let bmc_mac_address = db::machine_interface::find_by_ip(&state.pg_pool, target_ip)
         .await
         .map_err(|e| error_response((StatusCode::BAD_GATEWAY, e.to_string()).into()))?
@@ -481,10 +519,6 @@ async fn proxy_request(
 
     copy_request_headers(&parts.headers, &mut bmc_client_info.header_map);
 
-    let body = axum::body::to_bytes(body, MAX_BODY_SIZE)
-        .await
-        .map_err(|e| error_response((StatusCode::BAD_REQUEST, e.to_string()).into()))?;
-
     let Credentials::UsernamePassword { username, password } = bmc_client_info.credentials;
 
     let mut upstream_uri_parts = bmc_client_info.base_upstream_uri.into_parts();
@@ -9,6 +9,7 @@ use figment::providers::{Env, Format, Toml};
 use serde::{Deserialize, Serialize};
 
 use crate::acl::AclConfig;
+use crate::policy::PolicyConfig;
 
 #[derive(thiserror::Error, Debug)]
 pub enum ConfigError {
@@ -40,6 +41,8 @@ pub struct Config {
     pub tls: TlsConfig,
     pub auth: AuthConfig,
     pub bmc_proxy: Option<HostPortPair>,
+    #[serde(default)]
+    pub policy: PolicyConfig,
 }
 
 struct Defaults;
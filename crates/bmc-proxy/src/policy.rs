@@ -0,0 +1,756 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Method- and body-aware request policy for carbide-bmc-proxy.
+//!
+//! Policies are evaluated after the per-principal ACLs in [`crate::acl`] have allowed a request,
+//! and can further restrict it based on the JSON request body, the state of the machine whose BMC
+//! is being accessed, and per-principal, per-BMC rate limits. In `audit` mode every decision is
+//! logged but requests are always forwarded, which allows new rules to be tried out safely.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use db::DatabaseError;
+use http::{Method, StatusCode};
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Meter};
+use serde::Deserialize;
+use serde_json::Value;
+use sqlx::PgPool;
+
+use crate::acl::RequestPattern;
+
+/// Once this many rate limit buckets are tracked, buckets which have fully refilled are dropped.
+const MAX_RATE_LIMIT_BUCKETS: usize = 10_000;
+
+/// Request policy configuration, the `[policy]` section of the config file.
+#[derive(Clone, Default, Deserialize)]
+pub struct PolicyConfig {
+    /// Whether decisions are enforced or only logged
+    #[serde(default)]
+    pub mode: PolicyMode,
+    /// Ordered list of rules. The first matching rule decides whether a request is allowed.
+    /// Requests which match no rule are allowed (the ACLs have already been applied.)
+    #[serde(default)]
+    pub rules: Vec<PolicyRule>,
+    /// Rate limits, applied to every request which is allowed by the rules.
+    #[serde(default)]
+    pub rate_limits: Vec<RateLimitConfig>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyMode {
+    /// Reject requests denied by a rule or a rate limit
+    #[default]
+    Enforce,
+    /// Log every decision, but forward requests regardless of the outcome
+    Audit,
+}
+
+impl PolicyMode {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Enforce => "enforce",
+            Self::Audit => "audit",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyAction {
+    Allow,
+    Deny,
+}
+
+/// A policy rule. All of the configured conditions must match for the rule to apply.
+#[derive(Clone, Deserialize)]
+pub struct PolicyRule {
+    /// Name of the rule, used in logs, metrics and error responses
+    pub name: String,
+    pub action: PolicyAction,
+    /// Principals the rule applies to. If empty, the rule applies to every principal.
+    #[serde(default)]
+    pub principals: Vec<String>,
+    /// Method and path pattern, in ACL syntax. If unset, the rule applies to every request.
+    #[serde(default)]
+    pub request: Option<RequestPattern>,
+    /// Conditions on the JSON request body
+    #[serde(default)]
+    pub body: Vec<BodyCondition>,
+    /// Managed host states (for example `assigned`) the machine owning the target BMC must be in.
+    /// Compared case-insensitively. BMCs which don't belong to a known machine never match.
+    #[serde(default)]
+    pub machine_states: Vec<String>,
+}
+
+/// A condition on the JSON request body. `pointer` is a JSON pointer (RFC 6901) into the body,
+/// for example `/ResetType`.
+///
+/// If the body is not valid JSON, the condition can't be evaluated: deny rules treat it as
+/// matching, allow rules as not matching.
+#[derive(Clone, Deserialize)]
+#[serde(untagged)]
+pub enum BodyCondition {
+    /// Matches if the value at `pointer` is equal to one of `one_of`
+    OneOf { pointer: String, one_of: Vec<Value> },
+    /// Matches if the value at `pointer` is an object with any key not listed in `allowed_keys`
+    DisallowedKeys {
+        pointer: String,
+        allowed_keys: Vec<String>,
+    },
+}
+
+impl BodyCondition {
+    /// Returns `None` if there is no JSON body to evaluate the condition against.
+    fn matches(&self, body: Option<&Value>) -> Option<bool> {
+        let body = body?;
+        Some(match self {
+            Self::OneOf { pointer, one_of } => body
+                .pointer(pointer)
+                .is_some_and(|value| one_of.contains(value)),
+            Self::DisallowedKeys {
+                pointer,
+                allowed_keys,
+            } => body
+                .pointer(pointer)
+                .and_then(Value::as_object)
+                .is_some_and(|object| object.keys().any(|key| !allowed_keys.contains(key))),
+        })
+    }
+}
+
+/// Limits each principal to `max_requests` requests per `period_secs` against a single BMC.
+#[derive(Clone, Deserialize)]
+pub struct RateLimitConfig {
+    /// Name of the rate limit, used in logs, metrics and error responses
+    pub name: String,
+    /// Principals the limit applies to. If empty, the limit applies to every principal.
+    #[serde(default)]
+    pub principals: Vec<String>,
+    /// Method and path pattern, in ACL syntax. If unset, the limit applies to every request.
+    #[serde(default)]
+    pub request: Option<RequestPattern>,
+    pub max_requests: u32,
+    pub period_secs: u64,
+}
+
+impl RateLimitConfig {
+    /// Returns the principal of `request` which this limit is accounted against, if it applies.
+    fn principal_for<'a>(&self, request: &'a PolicyRequest<'_>) -> Option<&'a str> {
+        if let Some(pattern) = &self.request
+            && !pattern.matches(request.method, request.path)
+        {
+            return None;
+        }
+        request
+            .principals
+            .iter()
+            .find(|principal| self.principals.is_empty() || self.principals.contains(principal))
+            .map(String::as_str)
+    }
+}
+
+/// The parts of a proxied request policies are evaluated against.
+pub struct PolicyRequest<'a> {
+    /// Identifiers of all authenticated principals of the request
+    pub principals: &'a [String],
+    pub method: &'a Method,
+    pub path: &'a str,
+    pub bmc_ip: IpAddr,
+    pub body: &'a [u8],
+}
+
+/// Looks up the managed host state of the machine owning a BMC.
+pub trait MachineStateSource {
+    fn machine_state(
+        &self,
+        bmc_ip: IpAddr,
+    ) -> impl Future<Output = Result<Option<String>, DatabaseError>> + Send;
+}
+
+impl MachineStateSource for PgPool {
+    async fn machine_state(&self, bmc_ip: IpAddr) -> Result<Option<String>, DatabaseError> {
+        db::machine_topology::find_machine_state_by_bmc_ip(self, &bmc_ip.to_string()).await
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// The request is allowed, either by the named rule or because no rule matched
+    Allow {
+        rule: Option<String>,
+    },
+    Deny {
+        rule: String,
+        reason: Option<String>,
+    },
+    RateLimited {
+        limit: String,
+    },
+}
+
+impl Verdict {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Allow { .. } => "allow",
+            Self::Deny { .. } => "deny",
+            Self::RateLimited { .. } => "rate_limited",
+        }
+    }
+
+    fn name(&self) -> Option<&str> {
+        match self {
+            Self::Allow { rule } => rule.as_deref(),
+            Self::Deny { rule, .. } => Some(rule),
+            Self::RateLimited { limit } => Some(limit),
+        }
+    }
+}
+
+pub struct PolicyDecision {
+    pub verdict: Verdict,
+    pub mode: PolicyMode,
+}
+
+impl PolicyDecision {
+    /// Returns the status and message to reject the request with, or `None` if it should be
+    /// forwarded. Requests are never rejected in audit mode.
+    pub fn rejection(&self) -> Option<(StatusCode, String)> {
+        if self.mode == PolicyMode::Audit {
+            return None;
+        }
+        match &self.verdict {
+            Verdict::Allow { .. } => None,
+            Verdict::Deny { rule, .. } => Some((
+                StatusCode::FORBIDDEN,
+                format!("Forbidden by policy rule {rule}"),
+            )),
+            Verdict::RateLimited { limit } => Some((
+                StatusCode::TOO_MANY_REQUESTS,
+                format!("Rate limit {limit} exceeded"),
+            )),
+        }
+    }
+}
+
+pub struct PolicyEngine {
+    config: PolicyConfig,
+    rate_limiter: RateLimiter,
+    decision_counter: Counter<u64>,
+}
+
+impl PolicyEngine {
+    pub fn new(config: PolicyConfig, meter: &Meter) -> Self {
+        let decision_counter = meter
+            .u64_counter("carbide-bmc-proxy.policy.decisions")
+            .with_description("The amount of requests evaluated by the request policy, by outcome")
+            .build();
+        Self {
+            rate_limiter: RateLimiter::new(config.rate_limits.clone()),
+            config,
+            decision_counter,
+        }
+    }
+
+    /// Evaluates the policy for `request`, logging and counting the decision.
+    pub async fn evaluate(
+        &self,
+        request: &PolicyRequest<'_>,
+        machine_states: &impl MachineStateSource,
+    ) -> PolicyDecision {
+        let mut verdict = self.evaluate_rules(request, machine_states).await;
+        if matches!(verdict, Verdict::Allow { .. })
+            && let Some(limit) = self.rate_limiter.check(request, Instant::now())
+        {
+            verdict = Verdict::RateLimited {
+                limit: limit.to_string(),
+            };
+        }
+
+        let decision = PolicyDecision {
+            verdict,
+            mode: self.config.mode,
+        };
+        self.record(request, &decision);
+        decision
+    }
+
+    async fn evaluate_rules(
+        &self,
+        request: &PolicyRequest<'_>,
+        machine_states: &impl MachineStateSource,
+    ) -> Verdict {
+        let body = if self.config.rules.iter().any(|rule| !rule.body.is_empty()) {
+            serde_json::from_slice::<Value>(request.body).ok()
+        } else {
+            None
+        };
+        let mut machine_state: Option<Option<String>> = None;
+
+        for rule in &self.config.rules {
+            if !rule.principals.is_empty()
+                && !request
+                    .principals
+                    .iter()
+                    .any(|principal| rule.principals.contains(principal))
+            {
+                continue;
+            }
+            if let Some(pattern) = &rule.request
+                && !pattern.matches(request.method, request.path)
+            {
+                continue;
+            }
+
+            let fail_closed = rule.action == PolicyAction::Deny;
+            if !rule
+                .body
+                .iter()
+                .all(|condition| condition.matches(body.as_ref()).unwrap_or(fail_closed))
+            {
+                continue;
+            }
+
+            if !rule.machine_states.is_empty() {
+                if machine_state.is_none() {
+                    match machine_states.machine_state(request.bmc_ip).await {
+                        Ok(state) => machine_state = Some(state),
+                        Err(error) => {
+                            // Without the machine state we can't tell whether the rule applies
+                            return Verdict::Deny {
+                                rule: rule.name.clone(),
+                                reason: Some(format!("Error looking up machine state: {error}")),
+                            };
+                        }
+                    }
+                }
+                let Some(Some(state)) = &machine_state else {
+                    continue;
+                };
+                if !rule
+                    .machine_states
+                    .iter()
+                    .any(|s| s.eq_ignore_ascii_case(state))
+                {
+                    continue;
+                }
+            }
+
+            return match rule.action {
+                PolicyAction::Allow => Verdict::Allow {
+                    rule: Some(rule.name.clone()),
+                },
+                PolicyAction::Deny => Verdict::Deny {
+                    rule: rule.name.clone(),
+                    reason: None,
+                },
+            };
+        }
+
+        Verdict::Allow { rule: None }
+    }
+
+    fn record(&self, request: &PolicyRequest<'_>, decision: &PolicyDecision) {
+        let verdict = &decision.verdict;
+        self.decision_counter.add(
+            1,
+            &[
+                KeyValue::new("decision", verdict.as_str()),
+                KeyValue::new("rule", verdict.name().unwrap_or("").to_string()),
+                KeyValue::new("mode", decision.mode.as_str()),
+            ],
+        );
+
+        let reason = match verdict {
+            Verdict::Deny {
+                reason: Some(reason),
+                ..
+            } => reason.as_str(),
+            _ => "",
+        };
+        // Allowed requests are only interesting while auditing, everything else is always logged
+        if matches!(verdict, Verdict::Allow { .. }) && decision.mode == PolicyMode::Enforce {
+            tracing::debug!(
+                decision = verdict.as_str(),
+                rule = verdict.name(),
+                mode = decision.mode.as_str(),
+                principals = ?request.principals,
+                method = %request.method,
+                path = request.path,
+                bmc_ip = %request.bmc_ip,
+                "BMC proxy policy decision"
+            );
+        } else {
+            tracing::info!(
+                decision = verdict.as_str(),
+                rule = verdict.name(),
+                reason,
+                mode = decision.mode.as_str(),
+                principals = ?request.principals,
+                method = %request.method,
+                path = request.path,
+                bmc_ip = %request.bmc_ip,
+                "BMC proxy policy decision"
+            );
+        }
+    }
+}
+
+/// Token bucket rate limiter, with one bucket per rate limit, principal and BMC.
+struct RateLimiter {
+    limits: Vec<RateLimitConfig>,
+    buckets: Mutex<HashMap<(usize, String, IpAddr), TokenBucket>>,
+}
+
+struct TokenBucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn refill(&mut self, limit: &RateLimitConfig, now: Instant) {
+        let capacity = f64::from(limit.max_requests);
+        let rate = capacity / limit.period_secs as f64;
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(capacity);
+        self.updated_at = now;
+    }
+
+    fn is_full(&self, limit: &RateLimitConfig) -> bool {
+        self.tokens >= f64::from(limit.max_requests)
+    }
+}
+
+impl RateLimiter {
+    fn new(limits: Vec<RateLimitConfig>) -> Self {
+        Self {
+            limits,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Takes a token from every rate limit which applies to `request`, returning the name of the
+    /// first limit which has been exhausted.
+    fn check(&self, request: &PolicyRequest<'_>, now: Instant) -> Option<&str> {
+        if self.limits.is_empty() {
+            return None;
+        }
+        let mut buckets = self.buckets.lock().expect("rate limiter mutex poisoned");
+
+        if buckets.len() >= MAX_RATE_LIMIT_BUCKETS {
+            buckets.retain(|(index, _, _), bucket| {
+                let limit = &self.limits[*index];
+                bucket.refill(limit, now);
+                !bucket.is_full(limit)
+            });
+        }
+
+        for (index, limit) in self.limits.iter().enumerate() {
+            let Some(principal) = limit.principal_for(request) else {
+                continue;
+            };
+            let bucket = buckets
+                .entry((index, principal.to_string(), request.bmc_ip))
+                .or_insert_with(|| TokenBucket {
+                    tokens: f64::from(limit.max_requests),
+                    updated_at: now,
+                });
+            bucket.refill(limit, now);
+            if bucket.tokens < 1.0 {
+                return Some(&limit.name);
+            }
+            bucket.tokens -= 1.0;
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+    use std::time::Duration;
+
+    use figment::providers::{Format, Toml};
+
+    use super::*;
+
+    struct MockMachineStates(HashMap<IpAddr, String>);
+
+    impl MachineStateSource for MockMachineStates {
+        async fn machine_state(&self, bmc_ip: IpAddr) -> Result<Option<String>, DatabaseError> {
+            Ok(self.0.get(&bmc_ip).cloned())
+        }
+    }
+
+    const ASSIGNED_BMC: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+    const READY_BMC: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+
+    fn machine_states() -> MockMachineStates {
+        MockMachineStates(HashMap::from([
+            (ASSIGNED_BMC, "assigned".to_string()),
+            (READY_BMC, "ready".to_string()),
+        ]))
+    }
+
+    fn policy_engine(config_str: &str) -> PolicyEngine {
+        #[derive(Deserialize)]
+        struct MockConfig {
+            policy: PolicyConfig,
+        }
+
+        let config: MockConfig = figment::Figment::new()
+            .merge(Toml::string(config_str))
+            .extract()
+            .expect("Mock config didn't parse");
+        PolicyEngine::new(config.policy, &opentelemetry::global::meter("test"))
+    }
+
+    fn request<'a>(
+        principals: &'a [String],
+        method: &'a Method,
+        path: &'a str,
+        bmc_ip: IpAddr,
+        body: &'a str,
+    ) -> PolicyRequest<'a> {
+        PolicyRequest {
+            principals,
+            method,
+            path,
+            bmc_ip,
+            body: body.as_bytes(),
+        }
+    }
+
+    const CONFIG: &str = r#"
+        [[policy.rules]]
+        name = "bios-attribute-allowlist"
+        action = "deny"
+        request = "PATCH /redfish/v1/Systems/*/Bios/Settings"
+        body = [{ pointer = "/Attributes", allowed_keys = ["BootMode", "SriovEnable"] }]
+
+        [[policy.rules]]
+        name = "no-force-off-while-assigned"
+        action = "deny"
+        principals = ["spiffe-service-id/dps"]
+        request = "POST /redfish/v1/Systems/*/Actions/ComputerSystem.Reset"
+        body = [{ pointer = "/ResetType", one_of = ["ForceOff", "ForceRestart"] }]
+        machine_states = ["Assigned"]
+    "#;
+
+    #[tokio::test]
+    async fn test_body_key_allowlist() {
+        let engine = policy_engine(CONFIG);
+        let principals = vec!["spiffe-service-id/dps".to_string()];
+        let path = "/redfish/v1/Systems/1/Bios/Settings";
+
+        let allowed = request(
+            &principals,
+            &Method::PATCH,
+            path,
+            READY_BMC,
+            r#"{"Attributes": {"BootMode": "Uefi"}}"#,
+        );
+        let decision = engine.evaluate(&allowed, &machine_states()).await;
+        assert_eq!(decision.verdict, Verdict::Allow { rule: None });
+        assert!(decision.rejection().is_none());
+
+        let denied = request(
+            &principals,
+            &Method::PATCH,
+            path,
+            READY_BMC,
+            r#"{"Attributes": {"BootMode": "Uefi", "SecureBoot": "Disabled"}}"#,
+        );
+        let decision = engine.evaluate(&denied, &machine_states()).await;
+        assert_eq!(
+            decision.verdict,
+            Verdict::Deny {
+                rule: "bios-attribute-allowlist".to_string(),
+                reason: None
+            }
+        );
+        assert_eq!(decision.rejection().unwrap().0, StatusCode::FORBIDDEN);
+
+        // Bodies which aren't JSON can't be checked, so deny rules fail closed
+        let garbage = request(&principals, &Method::PATCH, path, READY_BMC, "not json");
+        let decision = engine.evaluate(&garbage, &machine_states()).await;
+        assert!(matches!(decision.verdict, Verdict::Deny { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_body_value_and_machine_state() {
+        let engine = policy_engine(CONFIG);
+        let dps = vec!["spiffe-service-id/dps".to_string()];
+        let other = vec!["spiffe-service-id/carbide-api".to_string()];
+        let path = "/redfish/v1/Systems/1/Actions/ComputerSystem.Reset";
+        let force_off = r#"{"ResetType": "ForceOff"}"#;
+
+        let evaluate = async |principals: &[String], bmc_ip: IpAddr, body: &str| {
+            engine
+                .evaluate(
+                    &request(principals, &Method::POST, path, bmc_ip, body),
+                    &machine_states(),
+                )
+                .await
+                .verdict
+        };
+
+        assert!(matches!(
+            evaluate(&dps, ASSIGNED_BMC, force_off).await,
+            Verdict::Deny { .. }
+        ));
+        assert!(matches!(
+            evaluate(&dps, ASSIGNED_BMC, r#"{"ResetType": "On"}"#).await,
+            Verdict::Allow { .. }
+        ));
+        assert!(matches!(
+            evaluate(&dps, READY_BMC, force_off).await,
+            Verdict::Allow { .. }
+        ));
+        assert!(matches!(
+            evaluate(&other, ASSIGNED_BMC, force_off).await,
+            Verdict::Allow { .. }
+        ));
+        // BMCs which don't belong to a known machine never match a machine state
+        assert!(matches!(
+            evaluate(&dps, IpAddr::V4(Ipv4Addr::new(10, 0, 0, 3)), force_off).await,
+            Verdict::Allow { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_first_matching_rule_wins() {
+        let engine = policy_engine(
+            r#"
+            [[policy.rules]]
+            name = "allow-reads"
+            action = "allow"
+            request = "GET /redfish/v1/**"
+
+            [[policy.rules]]
+            name = "deny-everything-else"
+            action = "deny"
+            "#,
+        );
+        let principals = vec!["spiffe-service-id/dps".to_string()];
+
+        let decision = engine
+            .evaluate(
+                &request(
+                    &principals,
+                    &Method::GET,
+                    "/redfish/v1/Systems",
+                    READY_BMC,
+                    "",
+                ),
+                &machine_states(),
+            )
+            .await;
+        assert_eq!(
+            decision.verdict,
+            Verdict::Allow {
+                rule: Some("allow-reads".to_string())
+            }
+        );
+
+        let decision = engine
+            .evaluate(
+                &request(
+                    &principals,
+                    &Method::POST,
+                    "/redfish/v1/Systems",
+                    READY_BMC,
+                    "",
+                ),
+                &machine_states(),
+            )
+            .await;
+        assert!(matches!(decision.verdict, Verdict::Deny { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_audit_mode_never_rejects() {
+        let engine = policy_engine(
+            r#"
+            [policy]
+            mode = "audit"
+
+            [[policy.rules]]
+            name = "deny-everything"
+            action = "deny"
+            "#,
+        );
+        let principals = vec!["spiffe-service-id/dps".to_string()];
+
+        let decision = engine
+            .evaluate(
+                &request(&principals, &Method::GET, "/redfish/v1", READY_BMC, ""),
+                &machine_states(),
+            )
+            .await;
+        assert!(matches!(decision.verdict, Verdict::Deny { .. }));
+        assert!(decision.rejection().is_none());
+    }
+
+    #[test]
+    fn test_rate_limit_per_principal_and_bmc() {
+        let engine = policy_engine(
+            r#"
+            [[policy.rate_limits]]
+            name = "resets"
+            request = "POST /redfish/v1/Systems/*/Actions/**"
+            max_requests = 2
+            period_secs = 60
+            "#,
+        );
+        let limiter = &engine.rate_limiter;
+        let dps = vec!["spiffe-service-id/dps".to_string()];
+        let other = vec!["spiffe-service-id/carbide-api".to_string()];
+        let path = "/redfish/v1/Systems/1/Actions/ComputerSystem.Reset";
+        let now = Instant::now();
+
+        let dps_request = request(&dps, &Method::POST, path, READY_BMC, "");
+        assert_eq!(limiter.check(&dps_request, now), None);
+        assert_eq!(limiter.check(&dps_request, now), None);
+        assert_eq!(limiter.check(&dps_request, now), Some("resets"));
+
+        // Other principals, other BMCs and other requests have their own budget
+        let other_request = request(&other, &Method::POST, path, READY_BMC, "");
+        assert_eq!(limiter.check(&other_request, now), None);
+        let other_bmc_request = request(&dps, &Method::POST, path, ASSIGNED_BMC, "");
+        assert_eq!(limiter.check(&other_bmc_request, now), None);
+        let read_request = request(&dps, &Method::GET, path, READY_BMC, "");
+        assert_eq!(limiter.check(&read_request, now), None);
+
+        // Tokens refill over the period
+        assert_eq!(
+            limiter.check(&dps_request, now + Duration::from_secs(30)),
+            None
+        );
+        assert_eq!(
+            limiter.check(&dps_request, now + Duration::from_secs(30)),
+            Some("resets")
+        );
+    }
+}
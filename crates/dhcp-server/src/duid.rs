@@ -0,0 +1,136 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! DHCP Unique Identifiers (RFC 8415 section 11).
+//!
+//! The API identifies machine interfaces by MAC address, so a DHCPv6 client's DUID has to be
+//! mapped back to the MAC of the interface it is soliciting on. DUID-LLT and DUID-LL carry it
+//! directly; DUID-EN and DUID-UUID do not, and rely on the relay supplying the Client
+//! Link-Layer Address option (RFC 6939) instead.
+use std::fmt;
+use std::net::Ipv4Addr;
+
+const DUID_TYPE_LLT: u16 = 1;
+const DUID_TYPE_LL: u16 = 3;
+const DUID_TYPE_UUID: u16 = 4;
+
+/// IANA hardware type for Ethernet.
+pub const HTYPE_ETHERNET: u16 = 1;
+
+const MAC_ADDRESS_LEN: usize = 6;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Duid(Vec<u8>);
+
+impl Duid {
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn duid_type(&self) -> Option<u16> {
+        let bytes: [u8; 2] = self.0.get(..2)?.try_into().ok()?;
+        Some(u16::from_be_bytes(bytes))
+    }
+
+    /// The Ethernet address embedded in a DUID-LLT or DUID-LL, if any.
+    pub fn mac_address(&self) -> Option<&[u8]> {
+        let (header, address) = match self.duid_type()? {
+            // type(2) + hardware type(2) + time(4) + link-layer address
+            DUID_TYPE_LLT => self.0.get(2..)?.split_at_checked(6)?,
+            // type(2) + hardware type(2) + link-layer address
+            DUID_TYPE_LL => self.0.get(2..)?.split_at_checked(2)?,
+            _ => return None,
+        };
+        let htype = u16::from_be_bytes(header.get(..2)?.try_into().ok()?);
+        (htype == HTYPE_ETHERNET && address.len() == MAC_ADDRESS_LEN).then_some(address)
+    }
+
+    /// The DUID this server identifies itself with.
+    ///
+    /// All controller replicas share `carbide_dhcp_server`, just as they share the v4 Server
+    /// Identifier, so a client that got an Advertise from one replica can send its Request to
+    /// any of them.
+    pub fn for_server(carbide_dhcp_server: Ipv4Addr) -> Self {
+        let mut bytes = Vec::with_capacity(18);
+        bytes.extend_from_slice(&DUID_TYPE_UUID.to_be_bytes());
+        bytes.extend_from_slice(&[0; 12]);
+        bytes.extend_from_slice(&carbide_dhcp_server.octets());
+        Self(bytes)
+    }
+}
+
+impl fmt::Display for Duid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex = self
+            .0
+            .iter()
+            .map(|x| format!("{x:02x}"))
+            .collect::<Vec<String>>()
+            .join(":");
+        f.write_str(&hex)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::Ipv4Addr;
+
+    use super::Duid;
+
+    #[test]
+    fn mac_address_from_duid_llt_and_ll() {
+        let mac = [0xb8, 0x3f, 0xd2, 0x90, 0x9a, 0x12];
+
+        let mut llt = vec![0, 1, 0, 1, 0x2a, 0x3b, 0x4c, 0x5d];
+        llt.extend_from_slice(&mac);
+        assert_eq!(Duid::from_bytes(&llt).mac_address(), Some(&mac[..]));
+
+        let mut ll = vec![0, 3, 0, 1];
+        ll.extend_from_slice(&mac);
+        assert_eq!(Duid::from_bytes(&ll).mac_address(), Some(&mac[..]));
+    }
+
+    #[test]
+    fn mac_address_absent_for_other_duids() {
+        // DUID-EN
+        let en = Duid::from_bytes(&[0, 2, 0, 0, 0x16, 0x1f, 1, 2, 3, 4]);
+        assert_eq!(en.mac_address(), None);
+
+        // DUID-LL with a non-Ethernet hardware type
+        let infiniband = Duid::from_bytes(&[0, 3, 0, 32, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(infiniband.mac_address(), None);
+
+        // Truncated DUID-LLT
+        let truncated = Duid::from_bytes(&[0, 1, 0, 1, 0x2a]);
+        assert_eq!(truncated.mac_address(), None);
+        assert_eq!(Duid::from_bytes(&[0]).duid_type(), None);
+    }
+
+    #[test]
+    fn server_duid_is_stable() {
+        let address = Ipv4Addr::new(10, 217, 126, 16);
+        let duid = Duid::for_server(address);
+        assert_eq!(duid, Duid::for_server(address));
+        assert_eq!(duid.duid_type(), Some(4));
+        assert_eq!(duid.as_bytes().len(), 18);
+        assert!(duid.to_string().ends_with("0a:d9:7e:10"));
+    }
+}
@@ -15,7 +15,7 @@
  * limitations under the License.
  */
 use std::io;
-use std::net::{AddrParseError, Ipv4Addr};
+use std::net::{AddrParseError, Ipv4Addr, Ipv6Addr};
 use std::str::Utf8Error;
 
 use dhcproto::v4::relay::RelayCode;
@@ -80,4 +80,19 @@ pub enum DhcpError {
 
     #[error("Multiple interfaces are provided, but only 1 is supported: {0}")]
     MultipleInterfacesProvidedOneSupported(usize),
+
+    #[error("Missing DHCPv6 Option: {0:?}")]
+    MissingOptionV6(dhcproto::v6::OptionCode),
+
+    #[error("Unhandled DHCPv6 Message Type: {0:?}")]
+    UnhandledMessageTypeV6(dhcproto::v6::MessageType),
+
+    #[error("DHCPv6 Decline message received for IP: {0}, duid: {1}")]
+    DhcpV6DeclineMessage(String, String),
+
+    #[error("Non relayed DHCPv6 packet received from {0}. Dropping!")]
+    NonRelayedPacketV6(Ipv6Addr),
+
+    #[error("Could not map DUID {0} to a MAC address")]
+    UnmappableDuid(String),
 }
@@ -20,6 +20,8 @@ use std::net::SocketAddr;
 use carbide_utils::models::dhcp::{
     DhcpConfig as ModelDhcpConfig, DhcpTimestamps, DhcpTimestampsFilePath,
     HostConfig as ModelHostConfig, InterfaceInfo as ModelInterfaceInfo,
+    InterfaceIpv6Info as ModelInterfaceIpv6Info,
+    RouterAdvertisementConfig as ModelRouterAdvertisementConfig,
 };
 use carbide_uuid::machine::MachineInterfaceId;
 use tokio::sync::mpsc;
@@ -76,6 +78,32 @@ impl TryFrom<proto::DhcpConfig> for ModelDhcpConfig {
                 .collect::<Result<Vec<_>, _>>()?,
             carbide_provisioning_server_ipv4: c.carbide_provisioning_server_ipv4.parse()?,
             carbide_dhcp_server: c.carbide_dhcp_server.parse()?,
+            carbide_nameservers_v6: c
+                .carbide_nameservers_v6
+                .iter()
+                .map(|s| s.parse())
+                .collect::<Result<Vec<_>, _>>()?,
+            router_advertisement: c
+                .router_advertisement
+                .map(ModelRouterAdvertisementConfig::try_from)
+                .transpose()?,
+        })
+    }
+}
+
+impl TryFrom<proto::RouterAdvertisementConfig> for ModelRouterAdvertisementConfig {
+    type Error = DhcpError;
+
+    fn try_from(r: proto::RouterAdvertisementConfig) -> Result<Self, Self::Error> {
+        Ok(ModelRouterAdvertisementConfig {
+            interval_secs: r.interval_secs,
+            router_lifetime_secs: r.router_lifetime_secs.try_into().map_err(|_| {
+                DhcpError::InvalidInput(format!(
+                    "router_lifetime_secs out of range: {}",
+                    r.router_lifetime_secs
+                ))
+            })?,
+            slaac: r.slaac,
         })
     }
 }
@@ -91,6 +119,19 @@ impl TryFrom<proto::InterfaceInfo> for ModelInterfaceInfo {
             fqdn: i.fqdn,
             booturl: i.booturl,
             mtu: i.mtu,
+            ipv6: i.ipv6.map(ModelInterfaceIpv6Info::try_from).transpose()?,
+        })
+    }
+}
+
+impl TryFrom<proto::InterfaceIpv6Info> for ModelInterfaceIpv6Info {
+    type Error = DhcpError;
+
+    fn try_from(i: proto::InterfaceIpv6Info) -> Result<Self, Self::Error> {
+        Ok(ModelInterfaceIpv6Info {
+            address: i.address.parse()?,
+            prefix: i.prefix,
+            gateway: i.gateway.map(|g| g.parse()).transpose()?,
         })
     }
 }
@@ -17,21 +17,27 @@
 
 mod cache;
 mod command_line;
+mod duid;
 mod errors;
 mod grpc_server;
 mod modes;
 mod packet_handler;
+mod packet_handler_v6;
+mod router_advertisement;
 mod rpc;
 mod util;
 mod vendor_class;
 
 use std::error::Error;
-use std::net::SocketAddr;
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
 use std::sync::Arc;
 
 use ::rpc::forge::{DhcpDiscovery, DhcpRecord};
 use cache::CacheEntry;
-use carbide_utils::models::dhcp::{DhcpConfig, DhcpTimestamps, DhcpTimestampsFilePath, HostConfig};
+use carbide_utils::models::dhcp::{
+    DhcpConfig, DhcpTimestamps, DhcpTimestampsFilePath, HostConfig, InterfaceIpv6Info,
+    RouterAdvertisementConfig,
+};
 use chrono::Utc;
 use command_line::{Args, ServerMode};
 use errors::DhcpError;
@@ -48,7 +54,7 @@ use tracing::level_filters::LevelFilter;
 use tracing_subscriber::EnvFilter;
 use tracing_subscriber::prelude::*;
 
-use crate::util::get_socket;
+use crate::util::{get_dhcpv6_socket, get_socket};
 
 pub struct Server {
     socket: Arc<UdpSocket>,
@@ -100,6 +106,33 @@ async fn run_dhcp_server(args: Args, cancel_token: CancellationToken) {
     // Create a new socket for each interface.
     // In case of Controller, there will be only 1 interface.
     for interface in args.interfaces {
+        join_handles.push(tokio::spawn(run_dhcpv6_server(
+            interface.clone(),
+            Arc::new(get_mode(&args.mode)),
+            config__.clone(),
+            dhcp_timestamps.clone(),
+            rate_limiter_.clone(),
+            cancel_token.clone(),
+        )));
+
+        // In Dpu mode we are the host's first hop, so we also tell it to use DHCPv6.
+        if let ServerMode::Dpu = args.mode
+            && let Some(ra_config) = &config__.dhcp_config.router_advertisement
+            && let Some(interface_info) = config__
+                .host_config
+                .as_ref()
+                .and_then(|x| x.host_ip_addresses.get(&interface))
+            && let Some(ipv6) = &interface_info.ipv6
+        {
+            join_handles.push(tokio::spawn(run_router_advertisements(
+                interface.clone(),
+                ra_config.clone(),
+                ipv6.clone(),
+                interface_info.mtu.unwrap_or(1500),
+                cancel_token.clone(),
+            )));
+        }
+
         let config_ = config__.clone();
         let args_mode = args.mode.clone();
         let dhcp_timestamps_ = dhcp_timestamps.clone();
@@ -205,6 +238,107 @@ async fn run_dhcp_server(args: Args, cancel_token: CancellationToken) {
     futures::future::join_all(join_handles).await;
 }
 
+/// DHCPv6 counterpart of the per-interface receive loop in `run_dhcp_server`.
+async fn run_dhcpv6_server(
+    interface: String,
+    handler: Arc<Box<dyn DhcpMode>>,
+    config: Config,
+    dhcp_timestamps: Arc<Mutex<DhcpTimestamps>>,
+    rate_limiter: Arc<tokio::sync::Semaphore>,
+    cancel: CancellationToken,
+) {
+    let listen_address = SocketAddr::new(
+        IpAddr::from(Ipv6Addr::UNSPECIFIED),
+        packet_handler_v6::DHCPV6_SERVER_PORT,
+    );
+
+    let mut socket = Arc::new(get_dhcpv6_socket(listen_address, interface.clone()).await);
+    tracing::info!(
+        "Listening on {:?} on interface: {}, mode: {:?}",
+        listen_address,
+        interface,
+        handler
+    );
+
+    let machine_cache_ = Arc::new(Mutex::new(LruCache::new(
+        std::num::NonZeroUsize::new(cache::MACHINE_CACHE_SIZE).unwrap(),
+    )));
+
+    loop {
+        let mut buf = [0; 1500];
+        tokio::select! {
+            _ = cancel.cancelled() => {
+                tracing::info!(
+                    "DHCPv6 server on interface {} received cancellation, shutting down",
+                    interface
+                );
+                break;
+            }
+            result = socket.recv_from(&mut buf) => {
+                let (len, addr) = match result {
+                    Ok((len, addr)) => (len, addr),
+                    Err(err) => {
+                        tracing::error!("Socket recv failed with error: {err}");
+                        drop(socket);
+                        tracing::info!("Recreating the socket on {listen_address}, {interface}");
+                        socket =
+                            Arc::new(get_dhcpv6_socket(listen_address, interface.clone()).await);
+                        continue;
+                    }
+                };
+
+                let Ok(permit) = rate_limiter.clone().try_acquire_owned() else {
+                    // drop packet.
+                    tracing::error!("Dropping packet because of rate limiting.");
+                    continue;
+                };
+
+                // Not a valid packet.
+                if len < packet_handler_v6::MINIMUM_DHCPV6_PKT_SIZE {
+                    tracing::error!("Dropping packet because it is smaller than min length.");
+                    continue;
+                }
+
+                let config = config.clone();
+                let mut machine_cache = machine_cache_.clone();
+                let iface = interface.clone();
+                let handler_ = handler.clone();
+                let dhcp_timestamps = dhcp_timestamps.clone();
+                let socket = socket.clone();
+
+                tokio::spawn(async move {
+                    process_v6(
+                        addr,
+                        socket,
+                        &buf[..len],
+                        config,
+                        &**handler_,
+                        &iface,
+                        &mut machine_cache,
+                        dhcp_timestamps,
+                    )
+                    .await;
+                    drop(permit);
+                });
+            }
+        }
+    }
+}
+
+async fn run_router_advertisements(
+    interface: String,
+    ra_config: RouterAdvertisementConfig,
+    ipv6: InterfaceIpv6Info,
+    mtu: u32,
+    cancel: CancellationToken,
+) {
+    if let Err(err) =
+        router_advertisement::run(interface.clone(), ra_config, ipv6, mtu, cancel).await
+    {
+        tracing::error!("Router Advertisements on interface {interface} stopped: {err}");
+    }
+}
+
 /// Initialises the tracing subscriber with per-crate log-level overrides.
 fn setup_tracing() -> Result<(), Box<dyn Error>> {
     let env_filter = EnvFilter::builder()
@@ -614,8 +748,53 @@ async fn process(
         }
     }
 
+    record_dhcp_timestamp(config.host_config, dhcp_timestamps).await;
+}
+
+#[tracing::instrument(skip_all)]
+#[allow(clippy::too_many_arguments)]
+async fn process_v6(
+    addr: SocketAddr,
+    socket: Arc<UdpSocket>,
+    buf: &[u8],
+    config: Config,
+    handler: &dyn DhcpMode,
+    circuit_id: &str, // interface name
+    machine_cache: &mut Arc<Mutex<LruCache<String, CacheEntry>>>,
+    dhcp_timestamps: Arc<Mutex<DhcpTimestamps>>,
+) {
+    tracing::info!("Received DHCPv6 packet [{}] from {}", buf[0], addr);
+
+    let packet = match packet_handler_v6::process_packet(
+        buf,
+        addr,
+        &config,
+        circuit_id,
+        handler,
+        machine_cache,
+    )
+    .await
+    {
+        Ok(packet) => packet,
+        Err(err) => {
+            tracing::error!("Dropping packet because of error: {}", err);
+            return;
+        }
+    };
+
+    if let Err(err) = packet.send(socket).await {
+        tracing::error!("Packet sending failed because of error: {}", err);
+    }
+
+    record_dhcp_timestamp(config.host_config, dhcp_timestamps).await;
+}
+
+async fn record_dhcp_timestamp(
+    host_config: Option<HostConfig>,
+    dhcp_timestamps: Arc<Mutex<DhcpTimestamps>>,
+) {
     // Tell forge-dpu-agent that an IP has been requested for this interface.
-    if let Some(host_config) = config.host_config {
+    if let Some(host_config) = host_config {
         let mut dhcp_timestamps = dhcp_timestamps.lock().await;
         dhcp_timestamps.add_timestamp(host_config.host_interface_id, Utc::now().to_rfc3339());
         if let Err(e) = dhcp_timestamps.write() {
@@ -14,7 +14,9 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use carbide_utils::models::dhcp::InterfaceInfo;
+use std::net::IpAddr;
+
+use carbide_utils::models::dhcp::{InterfaceInfo, InterfaceIpv6Info};
 use carbide_uuid::machine::MachineInterfaceId;
 use lru::LruCache;
 use rpc::forge::{DhcpDiscovery, DhcpRecord};
@@ -24,6 +26,7 @@ use super::DhcpMode;
 use crate::cache::CacheEntry;
 use crate::errors::DhcpError;
 use crate::packet_handler::DecodedPacket;
+use crate::packet_handler_v6::DecodedPacketV6;
 use crate::{Config, HostConfig};
 
 #[derive(Debug)]
@@ -47,6 +50,19 @@ fn from_host_conf(value: &InterfaceInfo, interface_id: MachineInterfaceId) -> Dh
     }
 }
 
+fn from_host_conf_v6(
+    value: &InterfaceInfo,
+    ipv6: &InterfaceIpv6Info,
+    interface_id: MachineInterfaceId,
+) -> DhcpRecord {
+    DhcpRecord {
+        address: ipv6.address.to_string(),
+        prefix: ipv6.prefix.clone(),
+        gateway: ipv6.gateway.map(|x| x.to_string()),
+        ..from_host_conf(value, interface_id)
+    }
+}
+
 #[async_trait]
 impl DhcpMode for Dpu {
     async fn discover_dhcp(
@@ -77,6 +93,18 @@ impl DhcpMode for Dpu {
             ));
         };
 
+        // DHCPv6 requests are reported with an IPv6 relay address.
+        if let Ok(IpAddr::V6(_)) = discovery_request.relay_address.parse::<IpAddr>() {
+            let ipv6 = ip_details.ipv6.as_ref().ok_or_else(|| {
+                DhcpError::MissingArgument(format!("Could not find IPv6 details for {circuit_id}"))
+            })?;
+            return Ok(from_host_conf_v6(
+                ip_details,
+                ipv6,
+                host_config.host_interface_id,
+            ));
+        }
+
         Ok(from_host_conf(ip_details, host_config.host_interface_id))
     }
 
@@ -85,6 +113,10 @@ impl DhcpMode for Dpu {
         Some(circuit_id.to_string())
     }
 
+    fn get_circuit_id_v6(&self, _packet: &DecodedPacketV6, circuit_id: &str) -> Option<String> {
+        Some(circuit_id.to_string())
+    }
+
     fn should_be_relayed(&self) -> bool {
         false
     }
@@ -26,6 +26,7 @@ use crate::Config;
 use crate::cache::CacheEntry;
 use crate::errors::DhcpError;
 use crate::packet_handler::{DecodedPacket, Packet};
+use crate::packet_handler_v6::DecodedPacketV6;
 
 pub mod controller;
 pub mod dpu;
@@ -47,6 +48,10 @@ pub trait DhcpMode: Send + Sync + std::fmt::Debug {
     fn get_circuit_id(&self, packet: &DecodedPacket, _circuit_id: &str) -> Option<String> {
         packet.get_circuit_id()
     }
+    /// Get circuit id for a DHCPv6 packet. Relays carry it in the Interface-ID option.
+    fn get_circuit_id_v6(&self, packet: &DecodedPacketV6, _circuit_id: &str) -> Option<String> {
+        packet.get_interface_id()
+    }
     /// Should be relayed? A controller mode will accept on relayed packet, while dpu with relay
     /// mode will never get a relayed packet.
     fn should_be_relayed(&self) -> bool {
@@ -0,0 +1,697 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! DHCPv6 (RFC 8415) handling for stateful IA_NA address assignment.
+//!
+//! Addresses come from the same place as DHCPv4 leases: Controller mode asks the API, which
+//! picks the address family from the relay's link-address, and Dpu mode reads the IPv6 side of
+//! the host config pushed by dpu-agent.
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+
+use dhcproto::v6::{
+    DhcpOption, DhcpOptions, IAAddr, IANA, Message, MessageType, OptionCode, RelayMessage,
+    RelayMessageData, Status, StatusCode,
+};
+use dhcproto::{Decodable, Decoder, Encodable, Encoder};
+use ipnetwork::IpNetwork;
+use lru::LruCache;
+use rpc::forge::{DhcpDiscovery, DhcpRecord};
+use tokio::net::UdpSocket;
+use tokio::sync::Mutex;
+
+use crate::cache::CacheEntry;
+use crate::duid::{Duid, HTYPE_ETHERNET};
+use crate::errors::DhcpError;
+use crate::{Config, DhcpMode, util};
+
+pub const DHCPV6_SERVER_PORT: u16 = 547;
+
+/// All_DHCP_Relay_Agents_and_Servers (RFC 8415 section 7.1).
+pub const ALL_DHCP_RELAY_AGENTS_AND_SERVERS: Ipv6Addr = Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 1, 2);
+
+/// msg-type(1) + transaction-id(3)
+pub const MINIMUM_DHCPV6_PKT_SIZE: usize = 4;
+
+const MSG_TYPE_RELAY_FORW: u8 = 12;
+
+/// Client Link-Layer Address option (RFC 6939).
+const OPTION_CLIENT_LINKLAYER_ADDR: u16 = 79;
+
+/// A client message along with the Relay-forward messages it arrived in, outermost first.
+pub struct DecodedPacketV6 {
+    message: Message,
+    relays: Vec<RelayMessage>,
+}
+
+impl DecodedPacketV6 {
+    fn decode(buf: &[u8]) -> Result<Self, DhcpError> {
+        if buf[0] != MSG_TYPE_RELAY_FORW {
+            return Ok(Self {
+                message: Message::decode(&mut Decoder::new(buf))?,
+                relays: vec![],
+            });
+        }
+
+        let mut relays = vec![];
+        let mut relay = RelayMessage::decode(&mut Decoder::new(buf))?;
+        loop {
+            let Some(DhcpOption::RelayMsg(inner)) = relay.opts().get(OptionCode::RelayMsg) else {
+                return Err(DhcpError::MissingOptionV6(OptionCode::RelayMsg));
+            };
+            let inner = inner.clone();
+            relays.push(relay);
+            match inner {
+                RelayMessageData::Message(message) => return Ok(Self { message, relays }),
+                RelayMessageData::Relay(next) => relay = next,
+            }
+        }
+    }
+
+    fn is_relayed(&self, peer: Ipv6Addr) -> Result<(), DhcpError> {
+        if self.relays.is_empty() {
+            return Err(DhcpError::NonRelayedPacketV6(peer));
+        }
+        Ok(())
+    }
+
+    fn client_id(&self) -> Option<Duid> {
+        match self.message.opts().get(OptionCode::ClientId) {
+            Some(DhcpOption::ClientId(duid)) => Some(Duid::from_bytes(duid)),
+            _ => None,
+        }
+    }
+
+    fn server_id(&self) -> Option<Duid> {
+        match self.message.opts().get(OptionCode::ServerId) {
+            Some(DhcpOption::ServerId(duid)) => Some(Duid::from_bytes(duid)),
+            _ => None,
+        }
+    }
+
+    fn is_this_for_us(&self, server_duid: &Duid) -> Result<(), DhcpError> {
+        match self.server_id() {
+            Some(duid) if &duid != server_duid => Err(DhcpError::NotMyPacket(duid.to_string())),
+            // No identifier sent by client. It can be for us
+            _ => Ok(()),
+        }
+    }
+
+    /// The relay closest to the client is the last one. Its link-address identifies the
+    /// client's link, unless it left it unspecified and an outer relay filled one in.
+    fn get_link_address(&self) -> Option<Ipv6Addr> {
+        self.relays
+            .iter()
+            .rev()
+            .map(|relay| relay.link_addr())
+            .find(|addr| !addr.is_unspecified())
+    }
+
+    pub fn get_interface_id(&self) -> Option<String> {
+        match self.relays.last()?.opts().get(OptionCode::InterfaceId) {
+            Some(DhcpOption::InterfaceId(x)) => util::u8_to_hex_string(x).ok(),
+            _ => None,
+        }
+    }
+
+    /// Client Link-Layer Address inserted by the relay closest to the client.
+    fn get_client_link_layer_address(&self) -> Option<Vec<u8>> {
+        self.relays.last()?.opts().iter().find_map(|opt| match opt {
+            DhcpOption::Unknown(unknown)
+                if u16::from(unknown.code()) == OPTION_CLIENT_LINKLAYER_ADDR =>
+            {
+                let (htype, address) = unknown.data().split_at_checked(2)?;
+                (u16::from_be_bytes([htype[0], htype[1]]) == HTYPE_ETHERNET)
+                    .then(|| address.to_vec())
+            }
+            _ => None,
+        })
+    }
+
+    /// The MAC address the API knows this client's machine interface by.
+    fn get_mac_address(&self) -> Result<String, DhcpError> {
+        if let Some(address) = self.get_client_link_layer_address() {
+            return Ok(util::u8_to_mac(&address));
+        }
+
+        let duid = self
+            .client_id()
+            .ok_or(DhcpError::MissingOptionV6(OptionCode::ClientId))?;
+        duid.mac_address()
+            .map(util::u8_to_mac)
+            .ok_or_else(|| DhcpError::UnmappableDuid(duid.to_string()))
+    }
+
+    fn ia_nas(&self) -> Vec<&IANA> {
+        self.message
+            .opts()
+            .iter()
+            .filter_map(|opt| match opt {
+                DhcpOption::IANA(ia_na) => Some(ia_na),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn has_rapid_commit(&self) -> bool {
+        self.message.opts().get(OptionCode::RapidCommit).is_some()
+    }
+
+    fn get_discovery_request(
+        &self,
+        handler: &dyn DhcpMode,
+        circuit_id: &str,
+    ) -> Result<DhcpDiscovery, DhcpError> {
+        // The API chooses the address family from the relay address, so a directly connected
+        // client (Dpu mode) is reported with the unspecified IPv6 address.
+        let relay_address = match (self.relays.is_empty(), self.get_link_address()) {
+            (true, _) => Ipv6Addr::UNSPECIFIED,
+            (false, Some(link_address)) => link_address,
+            (false, None) => {
+                return Err(DhcpError::InvalidInput(
+                    "Relay-forward message without a link-address.".to_string(),
+                ));
+            }
+        };
+
+        let mac_address = match self.get_mac_address() {
+            Ok(mac_address) => mac_address,
+            // Directly connected clients are served from the host config, which is keyed by
+            // interface rather than MAC.
+            Err(err) if self.relays.is_empty() => {
+                tracing::debug!("No MAC address for directly connected client: {err}");
+                String::new()
+            }
+            Err(err) => return Err(err),
+        };
+
+        Ok(DhcpDiscovery {
+            mac_address,
+            relay_address: relay_address.to_string(),
+            vendor_string: None,
+            link_address: None,
+            circuit_id: handler.get_circuit_id_v6(self, circuit_id),
+            remote_id: None,
+            desired_address: None,
+        })
+    }
+}
+
+pub struct PacketV6 {
+    encoded_packet: Vec<u8>,
+    pub dst_address: SocketAddr,
+}
+
+impl PacketV6 {
+    #[cfg(test)]
+    pub fn encoded_packet(&self) -> &Vec<u8> {
+        &self.encoded_packet
+    }
+
+    pub async fn send(&self, socket: Arc<UdpSocket>) -> Result<(), String> {
+        tracing::info!("Sending packet to {:?}", self.dst_address);
+        socket
+            .send_to(&self.encoded_packet, self.dst_address)
+            .await
+            .map_err(|x| x.to_string())?;
+
+        Ok(())
+    }
+}
+
+/// Handle one DHCPv6 datagram received from `peer`. Replies go back to the sender: the client
+/// itself on port 546, or the relay agent on port 547.
+pub async fn process_packet(
+    buf: &[u8],
+    peer: SocketAddr,
+    config: &Config,
+    circuit_id: &str,
+    handler: &dyn DhcpMode,
+    machine_cache: &mut Arc<Mutex<LruCache<String, CacheEntry>>>,
+) -> Result<PacketV6, DhcpError> {
+    let IpAddr::V6(peer_ip) = peer.ip() else {
+        return Err(DhcpError::InvalidInput(format!(
+            "DHCPv6 packet from non-IPv6 peer {peer}"
+        )));
+    };
+
+    let decoded_packet = DecodedPacketV6::decode(buf)?;
+    tracing::info!(packet.received=?decoded_packet.message, "Received DHCPv6 Packet");
+
+    if handler.should_be_relayed() {
+        decoded_packet.is_relayed(peer_ip)?;
+    }
+    let server_duid = Duid::for_server(config.dhcp_config.carbide_dhcp_server);
+    decoded_packet.is_this_for_us(&server_duid)?;
+
+    let msg_type = decoded_packet.message.msg_type();
+    let dhcp_response = match msg_type {
+        MessageType::Solicit
+        | MessageType::Request
+        | MessageType::Confirm
+        | MessageType::Renew
+        | MessageType::Rebind => Some(
+            handler
+                .discover_dhcp(
+                    decoded_packet.get_discovery_request(handler, circuit_id)?,
+                    config,
+                    machine_cache,
+                )
+                .await?,
+        ),
+        _ => None,
+    };
+
+    let reply = create_dhcpv6_reply_packet(&decoded_packet, dhcp_response, config, &server_duid)?;
+    tracing::info!(packet.send=?reply, "Sending DHCPv6 Packet");
+
+    let mut encoded_packet = Vec::new();
+    let mut e = Encoder::new(&mut encoded_packet);
+    match wrap_in_relay_replies(reply, &decoded_packet.relays) {
+        RelayMessageData::Message(message) => message.encode(&mut e)?,
+        RelayMessageData::Relay(relay) => relay.encode(&mut e)?,
+    }
+
+    Ok(PacketV6 {
+        encoded_packet,
+        dst_address: peer,
+    })
+}
+
+fn create_dhcpv6_reply_packet(
+    src: &DecodedPacketV6,
+    forge_response: Option<DhcpRecord>,
+    config: &Config,
+    server_duid: &Duid,
+) -> Result<Message, DhcpError> {
+    let dhcp_msg_type = src.message.msg_type();
+    let reply_message_type = match dhcp_msg_type {
+        MessageType::Solicit if src.has_rapid_commit() => MessageType::Reply,
+        MessageType::Solicit => MessageType::Advertise,
+        MessageType::Request
+        | MessageType::Confirm
+        | MessageType::Renew
+        | MessageType::Rebind
+        | MessageType::Release
+        | MessageType::InformationRequest => MessageType::Reply,
+        MessageType::Decline => {
+            return Err(DhcpError::DhcpV6DeclineMessage(
+                src.ia_nas()
+                    .iter()
+                    .flat_map(|ia_na| ia_addrs(ia_na))
+                    .map(|addr| addr.to_string())
+                    .collect::<Vec<String>>()
+                    .join(","),
+                src.client_id().map(|x| x.to_string()).unwrap_or_default(),
+            ));
+        }
+        _ => {
+            return Err(DhcpError::UnhandledMessageTypeV6(dhcp_msg_type));
+        }
+    };
+
+    // https://www.rfc-editor.org/rfc/rfc8415
+    let mut msg = Message::new_with_id(reply_message_type, src.message.xid());
+    if let Some(client_id) = src.client_id() {
+        msg.opts_mut()
+            .insert(DhcpOption::ClientId(client_id.as_bytes().to_vec()));
+    }
+    msg.opts_mut()
+        .insert(DhcpOption::ServerId(server_duid.as_bytes().to_vec()));
+    if reply_message_type == MessageType::Reply && dhcp_msg_type == MessageType::Solicit {
+        msg.opts_mut().insert(DhcpOption::RapidCommit);
+    }
+
+    if dhcp_msg_type == MessageType::Release {
+        // Leases are tied to the machine interface and expired by the API, so there is
+        // nothing to free here.
+        msg.opts_mut()
+            .insert(status_code(Status::Success, "Release received."));
+        return Ok(msg);
+    }
+
+    if !config.dhcp_config.carbide_nameservers_v6.is_empty() {
+        msg.opts_mut().insert(DhcpOption::DomainNameServers(
+            config.dhcp_config.carbide_nameservers_v6.clone(),
+        ));
+    }
+
+    let Some(forge_response) = forge_response else {
+        // Information-request: configuration only, no addresses.
+        return Ok(msg);
+    };
+    let allocated_address = get_allocated_address(&forge_response)?;
+
+    if dhcp_msg_type == MessageType::Confirm {
+        let on_link = src
+            .ia_nas()
+            .iter()
+            .flat_map(|ia_na| ia_addrs(ia_na))
+            .all(|addr| addr == allocated_address);
+        msg.opts_mut().insert(if on_link {
+            status_code(Status::Success, "All addresses are on link.")
+        } else {
+            status_code(Status::NotOnLink, "Address is not on link.")
+        });
+        return Ok(msg);
+    }
+
+    let ia_nas = src.ia_nas();
+    if ia_nas.is_empty() {
+        msg.opts_mut().insert(status_code(
+            Status::NoAddrsAvail,
+            "No IA_NA present in request.",
+        ));
+        return Ok(msg);
+    }
+
+    // A machine interface has one address per family, so only the first IA_NA is bound.
+    for (index, ia_na) in ia_nas.into_iter().enumerate() {
+        let mut ia_opts = DhcpOptions::new();
+        if index == 0 {
+            ia_opts.insert(DhcpOption::IAAddr(IAAddr {
+                addr: allocated_address,
+                preferred_life: config.dhcp_config.lease_time_secs,
+                valid_life: config.dhcp_config.lease_time_secs,
+                opts: DhcpOptions::new(),
+            }));
+            // Addresses the client still holds but which are no longer allocated to it are
+            // returned with zero lifetimes, so that the client stops using them.
+            for addr in ia_addrs(ia_na).filter(|addr| *addr != allocated_address) {
+                ia_opts.insert(DhcpOption::IAAddr(IAAddr {
+                    addr,
+                    preferred_life: 0,
+                    valid_life: 0,
+                    opts: DhcpOptions::new(),
+                }));
+            }
+        } else {
+            ia_opts.insert(status_code(
+                Status::NoAddrsAvail,
+                "Only one address is assigned per interface.",
+            ));
+        }
+
+        msg.opts_mut().insert(DhcpOption::IANA(IANA {
+            id: ia_na.id,
+            t1: config.dhcp_config.renewal_time_secs,
+            t2: config.dhcp_config.rebinding_time_secs,
+            opts: ia_opts,
+        }));
+    }
+
+    Ok(msg)
+}
+
+fn get_allocated_address(forge_response: &DhcpRecord) -> Result<Ipv6Addr, DhcpError> {
+    match forge_response.prefix.parse::<IpNetwork>() {
+        Ok(IpNetwork::V6(_)) => {}
+        Ok(IpNetwork::V4(prefix)) => {
+            return Err(DhcpError::GenericError(format!(
+                "Prefix ({prefix}) is an IPv4 network, which can not be served over DHCPv6."
+            )));
+        }
+        Err(error) => {
+            return Err(DhcpError::GenericError(format!(
+                "prefix value in deserialized protobuf is not an IP Network: {error}"
+            )));
+        }
+    }
+
+    Ok(forge_response.address.parse::<Ipv6Addr>()?)
+}
+
+fn ia_addrs(ia_na: &IANA) -> impl Iterator<Item = Ipv6Addr> + '_ {
+    ia_na.opts.iter().filter_map(|opt| match opt {
+        DhcpOption::IAAddr(ia_addr) => Some(ia_addr.addr),
+        _ => None,
+    })
+}
+
+fn status_code(status: Status, msg: &str) -> DhcpOption {
+    DhcpOption::StatusCode(StatusCode {
+        status,
+        msg: msg.to_string(),
+    })
+}
+
+/// Wrap `reply` in one Relay-reply per Relay-forward the request came through, echoing each
+/// relay's Interface-ID so it can find the link to send the reply on.
+fn wrap_in_relay_replies(reply: Message, relays: &[RelayMessage]) -> RelayMessageData {
+    let mut data = RelayMessageData::Message(reply);
+    for relay in relays.iter().rev() {
+        let mut opts = DhcpOptions::new();
+        if let Some(interface_id) = relay.opts().get(OptionCode::InterfaceId) {
+            opts.insert(interface_id.clone());
+        }
+        opts.insert(DhcpOption::RelayMsg(data));
+
+        data = RelayMessageData::Relay(RelayMessage {
+            msg_type: MessageType::RelayRepl,
+            hop_count: relay.hop_count(),
+            link_addr: relay.link_addr(),
+            peer_addr: relay.peer_addr(),
+            opts,
+        });
+    }
+    data
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+    use std::sync::Arc;
+
+    use carbide_utils::models::dhcp::DhcpConfig;
+    use dhcproto::v6::{
+        DhcpOption, DhcpOptions, IANA, Message, MessageType, OptionCode, RelayMessage,
+        RelayMessageData,
+    };
+    use dhcproto::{Decodable, Decoder, Encodable, Encoder};
+    use lru::LruCache;
+    use rpc::forge::{DhcpDiscovery, DhcpRecord};
+    use tokio::sync::Mutex;
+    use tonic::async_trait;
+
+    use crate::cache::{self, CacheEntry};
+    use crate::duid::Duid;
+    use crate::errors::DhcpError;
+    use crate::{Config, DhcpMode, packet_handler_v6};
+
+    /// Hands out a fixed IPv6 lease, and checks the discovery request the handler built.
+    #[derive(Debug)]
+    struct TestV6 {
+        relayed: bool,
+    }
+
+    #[async_trait]
+    impl DhcpMode for TestV6 {
+        async fn discover_dhcp(
+            &self,
+            discovery_request: DhcpDiscovery,
+            _config: &Config,
+            _machine_cache: &mut Arc<Mutex<LruCache<String, CacheEntry>>>,
+        ) -> Result<DhcpRecord, DhcpError> {
+            if self.relayed {
+                assert_eq!(discovery_request.relay_address, "2001:db8:0:1::1");
+                assert_eq!(discovery_request.mac_address, "b8:3f:d2:90:9a:12");
+                assert_eq!(discovery_request.circuit_id.as_deref(), Some("vlan200"));
+            } else {
+                assert_eq!(discovery_request.relay_address, "::");
+            }
+
+            Ok(DhcpRecord {
+                machine_id: None,
+                machine_interface_id: Some("0fd6e9a3-06fc-4a22-ad29-aca299677b00".parse().unwrap()),
+                segment_id: None,
+                subdomain_id: None,
+                fqdn: "seventeen-connecticut.dev3.frg.nvidia.com".to_string(),
+                mac_address: "b8:3f:d2:90:9a:12".to_string(),
+                address: "2001:db8:0:1::10".to_string(),
+                mtu: 9000,
+                prefix: "2001:db8:0:1::/64".to_string(),
+                gateway: Some("2001:db8:0:1::1".to_string()),
+                booturl: None,
+                last_invalidation_time: None,
+            })
+        }
+
+        fn should_be_relayed(&self) -> bool {
+            self.relayed
+        }
+    }
+
+    fn config() -> Config {
+        Config {
+            dhcp_config: DhcpConfig {
+                carbide_dhcp_server: Ipv4Addr::new(10, 217, 126, 16),
+                carbide_nameservers_v6: vec!["2001:db8::53".parse().unwrap()],
+                ..Default::default()
+            },
+            host_config: None,
+        }
+    }
+
+    fn client_duid() -> Vec<u8> {
+        vec![0, 3, 0, 1, 0xb8, 0x3f, 0xd2, 0x90, 0x9a, 0x12]
+    }
+
+    fn client_message(message_type: MessageType, server_id: Option<Duid>) -> Message {
+        let mut msg = Message::new_with_id(message_type, [1, 2, 3]);
+        msg.opts_mut().insert(DhcpOption::ClientId(client_duid()));
+        if let Some(server_id) = server_id {
+            msg.opts_mut()
+                .insert(DhcpOption::ServerId(server_id.as_bytes().to_vec()));
+        }
+        msg.opts_mut().insert(DhcpOption::IANA(IANA {
+            id: 7,
+            t1: 0,
+            t2: 0,
+            opts: DhcpOptions::new(),
+        }));
+        msg
+    }
+
+    fn encode(data: RelayMessageData) -> Vec<u8> {
+        let mut encoded_packet = Vec::new();
+        let mut e = Encoder::new(&mut encoded_packet);
+        match data {
+            RelayMessageData::Message(message) => message.encode(&mut e).unwrap(),
+            RelayMessageData::Relay(relay) => relay.encode(&mut e).unwrap(),
+        }
+        encoded_packet
+    }
+
+    fn relay_forward(msg: Message) -> RelayMessage {
+        let mut opts = DhcpOptions::new();
+        opts.insert(DhcpOption::InterfaceId(b"vlan200".to_vec()));
+        opts.insert(DhcpOption::RelayMsg(RelayMessageData::Message(msg)));
+        RelayMessage {
+            msg_type: MessageType::RelayForw,
+            hop_count: 0,
+            link_addr: "2001:db8:0:1::1".parse().unwrap(),
+            peer_addr: "fe80::ba3f:d2ff:fe90:9a12".parse().unwrap(),
+            opts,
+        }
+    }
+
+    async fn process(
+        buf: &[u8],
+        handler: &dyn DhcpMode,
+    ) -> Result<packet_handler_v6::PacketV6, DhcpError> {
+        let mut machine_cache = Arc::new(Mutex::new(LruCache::new(
+            std::num::NonZeroUsize::new(cache::MACHINE_CACHE_SIZE).unwrap(),
+        )));
+        let peer: SocketAddr = "[fe80::1]:547".parse().unwrap();
+        packet_handler_v6::process_packet(
+            buf,
+            peer,
+            &config(),
+            "vlan100",
+            handler,
+            &mut machine_cache,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn solicit_gets_advertise_with_address() {
+        let buf = encode(RelayMessageData::Message(client_message(
+            MessageType::Solicit,
+            None,
+        )));
+        let packet = process(&buf, &TestV6 { relayed: false }).await.unwrap();
+
+        let reply = Message::decode(&mut Decoder::new(packet.encoded_packet())).unwrap();
+        assert_eq!(reply.msg_type(), MessageType::Advertise);
+        assert_eq!(reply.xid(), [1, 2, 3]);
+        assert_eq!(
+            reply.opts().get(OptionCode::ServerId),
+            Some(&DhcpOption::ServerId(
+                Duid::for_server(Ipv4Addr::new(10, 217, 126, 16))
+                    .as_bytes()
+                    .to_vec()
+            ))
+        );
+        assert_eq!(
+            reply.opts().get(OptionCode::DomainNameServers),
+            Some(&DhcpOption::DomainNameServers(vec![
+                "2001:db8::53".parse().unwrap()
+            ]))
+        );
+
+        let Some(DhcpOption::IANA(ia_na)) = reply.opts().get(OptionCode::IANA) else {
+            panic!("IA_NA missing from Advertise");
+        };
+        assert_eq!(ia_na.id, 7);
+        let Some(DhcpOption::IAAddr(ia_addr)) = ia_na.opts.get(OptionCode::IAAddr) else {
+            panic!("IA Address missing from IA_NA");
+        };
+        assert_eq!(
+            ia_addr.addr,
+            "2001:db8:0:1::10".parse::<Ipv6Addr>().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn relayed_request_gets_relay_reply() {
+        let server_id = Duid::for_server(Ipv4Addr::new(10, 217, 126, 16));
+        let buf = encode(RelayMessageData::Relay(relay_forward(client_message(
+            MessageType::Request,
+            Some(server_id),
+        ))));
+        let packet = process(&buf, &TestV6 { relayed: true }).await.unwrap();
+
+        let relay_reply = RelayMessage::decode(&mut Decoder::new(packet.encoded_packet())).unwrap();
+        assert_eq!(relay_reply.msg_type(), MessageType::RelayRepl);
+        assert_eq!(
+            relay_reply.peer_addr(),
+            "fe80::ba3f:d2ff:fe90:9a12".parse::<Ipv6Addr>().unwrap()
+        );
+        assert_eq!(
+            relay_reply.opts().get(OptionCode::InterfaceId),
+            Some(&DhcpOption::InterfaceId(b"vlan200".to_vec()))
+        );
+        let Some(DhcpOption::RelayMsg(RelayMessageData::Message(reply))) =
+            relay_reply.opts().get(OptionCode::RelayMsg)
+        else {
+            panic!("Reply missing from Relay-reply");
+        };
+        assert_eq!(reply.msg_type(), MessageType::Reply);
+    }
+
+    #[tokio::test]
+    async fn rejects_unrelayed_and_foreign_packets() {
+        let buf = encode(RelayMessageData::Message(client_message(
+            MessageType::Solicit,
+            None,
+        )));
+        assert!(matches!(
+            process(&buf, &TestV6 { relayed: true }).await,
+            Err(DhcpError::NonRelayedPacketV6(..))
+        ));
+
+        let buf = encode(RelayMessageData::Message(client_message(
+            MessageType::Request,
+            Some(Duid::for_server(Ipv4Addr::new(10, 0, 0, 1))),
+        )));
+        assert!(matches!(
+            process(&buf, &TestV6 { relayed: false }).await,
+            Err(DhcpError::NotMyPacket(..))
+        ));
+    }
+}
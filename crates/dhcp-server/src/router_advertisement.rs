@@ -0,0 +1,233 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! ICMPv6 Router Advertisements (RFC 4861) for IPv6 host interfaces in Dpu mode.
+//!
+//! The DPU is the host's first hop, so it is also what tells the host to use DHCPv6 (Managed
+//! flag) and, optionally, to autoconfigure from the interface prefix (SLAAC).
+use std::io::Read;
+use std::net::{Ipv6Addr, SocketAddrV6};
+use std::time::{Duration, Instant};
+
+use carbide_utils::models::dhcp::{InterfaceIpv6Info, RouterAdvertisementConfig};
+use ipnetwork::Ipv6Network;
+use tokio::io::unix::AsyncFd;
+use tokio_util::sync::CancellationToken;
+
+use crate::errors::DhcpError;
+use crate::util;
+
+const ICMPV6_ROUTER_SOLICITATION: u8 = 133;
+const ICMPV6_ROUTER_ADVERTISEMENT: u8 = 134;
+
+const ND_OPT_PREFIX_INFORMATION: u8 = 3;
+const ND_OPT_MTU: u8 = 5;
+
+const RA_FLAG_MANAGED: u8 = 0x80;
+const PREFIX_FLAG_ON_LINK: u8 = 0x80;
+const PREFIX_FLAG_AUTONOMOUS: u8 = 0x40;
+
+/// SLAAC only works with 64 bit interface identifiers.
+const SLAAC_PREFIX_LEN: u8 = 64;
+
+/// MIN_DELAY_BETWEEN_RAS from RFC 4861 section 10.
+const MIN_DELAY_BETWEEN_RAS: Duration = Duration::from_secs(3);
+
+const ALL_NODES: Ipv6Addr = Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 1);
+const ALL_ROUTERS: Ipv6Addr = Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 2);
+
+/// Build a Router Advertisement for the host link described by `ipv6`. The ICMPv6 checksum is
+/// left as zero; the kernel fills it in for raw ICMPv6 sockets.
+pub fn build_router_advertisement(
+    config: &RouterAdvertisementConfig,
+    ipv6: &InterfaceIpv6Info,
+    mtu: u32,
+) -> Result<Vec<u8>, DhcpError> {
+    let prefix = ipv6.prefix.parse::<Ipv6Network>().map_err(|e| {
+        DhcpError::InvalidInput(format!("Invalid IPv6 prefix {}: {e}", ipv6.prefix))
+    })?;
+
+    // Only advertise ourselves as a default router if there is a gateway on this link.
+    let router_lifetime = if ipv6.gateway.is_some() {
+        config.router_lifetime_secs
+    } else {
+        0
+    };
+
+    let mut packet = Vec::with_capacity(56);
+    packet.extend_from_slice(&[ICMPV6_ROUTER_ADVERTISEMENT, 0, 0, 0]);
+    packet.push(64); // Cur Hop Limit
+    packet.push(RA_FLAG_MANAGED);
+    packet.extend_from_slice(&router_lifetime.to_be_bytes());
+    packet.extend_from_slice(&0u32.to_be_bytes()); // Reachable Time: unspecified
+    packet.extend_from_slice(&0u32.to_be_bytes()); // Retrans Timer: unspecified
+
+    let mut prefix_flags = PREFIX_FLAG_ON_LINK;
+    if config.slaac && prefix.prefix() == SLAAC_PREFIX_LEN {
+        prefix_flags |= PREFIX_FLAG_AUTONOMOUS;
+    }
+    let lifetime = config
+        .interval_secs
+        .saturating_mul(3)
+        .max(u32::from(config.router_lifetime_secs));
+    packet.extend_from_slice(&[ND_OPT_PREFIX_INFORMATION, 4, prefix.prefix(), prefix_flags]);
+    packet.extend_from_slice(&lifetime.to_be_bytes()); // Valid Lifetime
+    packet.extend_from_slice(&lifetime.to_be_bytes()); // Preferred Lifetime
+    packet.extend_from_slice(&0u32.to_be_bytes()); // Reserved2
+    packet.extend_from_slice(&prefix.network().octets());
+
+    packet.extend_from_slice(&[ND_OPT_MTU, 1, 0, 0]);
+    packet.extend_from_slice(&mtu.to_be_bytes());
+
+    Ok(packet)
+}
+
+fn get_icmpv6_socket(interface: &str, ifindex: u32) -> Result<socket2::Socket, DhcpError> {
+    let socket = socket2::Socket::new(
+        socket2::Domain::IPV6,
+        socket2::Type::RAW,
+        Some(socket2::Protocol::ICMPV6),
+    )?;
+    socket.set_nonblocking(true)?;
+    socket.bind_device(Some(interface.as_bytes()))?;
+    // Neighbor Discovery packets must be sent with a hop limit of 255.
+    socket.set_multicast_hops_v6(255)?;
+    socket.set_unicast_hops_v6(255)?;
+    socket.set_multicast_if_v6(ifindex)?;
+    socket.set_multicast_loop_v6(false)?;
+    socket.join_multicast_v6(&ALL_ROUTERS, ifindex)?;
+    Ok(socket)
+}
+
+/// Send unsolicited Router Advertisements every `interval_secs` and answer Router
+/// Solicitations on `interface` until `cancel` is cancelled.
+pub async fn run(
+    interface: String,
+    config: RouterAdvertisementConfig,
+    ipv6: InterfaceIpv6Info,
+    mtu: u32,
+    cancel: CancellationToken,
+) -> Result<(), DhcpError> {
+    let packet = build_router_advertisement(&config, &ipv6, mtu)?;
+    let ifindex = util::interface_index(&interface)?;
+    let socket = AsyncFd::new(get_icmpv6_socket(&interface, ifindex)?)?;
+    let destination: socket2::SockAddr = SocketAddrV6::new(ALL_NODES, 0, 0, ifindex).into();
+
+    tracing::info!(
+        "Sending Router Advertisements for {} on interface: {}",
+        ipv6.prefix,
+        interface
+    );
+
+    let mut interval =
+        tokio::time::interval(Duration::from_secs(u64::from(config.interval_secs.max(1))));
+    let mut last_sent: Option<Instant> = None;
+    let mut buf = [0; 1500];
+
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => {
+                tracing::info!(
+                    "Router Advertisements on interface {} received cancellation, shutting down",
+                    interface
+                );
+                return Ok(());
+            }
+            _ = interval.tick() => {}
+            guard = socket.readable() => {
+                let mut guard = guard?;
+                match guard.try_io(|inner| inner.get_ref().read(&mut buf)) {
+                    Ok(Ok(len)) if len > 0 && buf[0] == ICMPV6_ROUTER_SOLICITATION => {
+                        if last_sent.is_some_and(|x| x.elapsed() < MIN_DELAY_BETWEEN_RAS) {
+                            continue;
+                        }
+                    }
+                    Ok(Ok(_)) | Err(_) => continue,
+                    Ok(Err(err)) => {
+                        tracing::error!("Router Solicitation recv failed with error: {err}");
+                        continue;
+                    }
+                }
+            }
+        }
+
+        match socket.get_ref().send_to(&packet, &destination) {
+            Ok(_) => last_sent = Some(Instant::now()),
+            Err(err) => tracing::error!("Router Advertisement sending failed with error: {err}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use carbide_utils::models::dhcp::{InterfaceIpv6Info, RouterAdvertisementConfig};
+
+    use super::build_router_advertisement;
+
+    fn ipv6_info(prefix: &str) -> InterfaceIpv6Info {
+        InterfaceIpv6Info {
+            address: "2001:db8::10".parse().unwrap(),
+            prefix: prefix.to_string(),
+            gateway: Some("2001:db8::1".parse().unwrap()),
+        }
+    }
+
+    #[test]
+    fn router_advertisement_layout() {
+        let config = RouterAdvertisementConfig {
+            slaac: true,
+            ..Default::default()
+        };
+        let packet =
+            build_router_advertisement(&config, &ipv6_info("2001:db8::/64"), 9000).unwrap();
+
+        assert_eq!(packet.len(), 16 + 32 + 8);
+        assert_eq!(packet[0], 134);
+        // Managed flag
+        assert_eq!(packet[5], 0x80);
+        // Router lifetime
+        assert_eq!(u16::from_be_bytes([packet[6], packet[7]]), 1800);
+        // Prefix information: /64, on-link and autonomous
+        assert_eq!(&packet[16..20], &[3, 4, 64, 0xc0]);
+        assert_eq!(
+            &packet[32..48],
+            &"2001:db8::".parse::<std::net::Ipv6Addr>().unwrap().octets()
+        );
+        // MTU
+        assert_eq!(&packet[48..52], &[5, 1, 0, 0]);
+        assert_eq!(u32::from_be_bytes(packet[52..56].try_into().unwrap()), 9000);
+    }
+
+    #[test]
+    fn router_advertisement_without_slaac() {
+        let config = RouterAdvertisementConfig {
+            slaac: true,
+            ..Default::default()
+        };
+        // SLAAC needs a /64, so a /127 link is only advertised as on-link.
+        let packet =
+            build_router_advertisement(&config, &ipv6_info("2001:db8::/127"), 1500).unwrap();
+        assert_eq!(&packet[16..20], &[3, 4, 127, 0x80]);
+
+        let mut no_gateway = ipv6_info("2001:db8::/64");
+        no_gateway.gateway = None;
+        let packet = build_router_advertisement(&config, &no_gateway, 1500).unwrap();
+        assert_eq!(u16::from_be_bytes([packet[6], packet[7]]), 0);
+
+        assert!(build_router_advertisement(&config, &ipv6_info("not-a-prefix"), 1500).is_err());
+    }
+}
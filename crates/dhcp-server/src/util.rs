@@ -19,6 +19,7 @@ use tokio::net::UdpSocket;
 
 use crate::Config;
 use crate::errors::DhcpError;
+use crate::packet_handler_v6::ALL_DHCP_RELAY_AGENTS_AND_SERVERS;
 use crate::vendor_class::{MachineArchitecture, VendorClass};
 
 macro_rules! socket_opr {
@@ -83,12 +84,13 @@ pub fn machine_get_filename(
 }
 
 /// Create a UDP socket and set non_blocking, broadcast and other options flag on it.
+/// The socket family follows `listen_address`; IPv6 sockets are v6-only.
 pub async fn get_socket(listen_address: core::net::SocketAddr, interface: String) -> UdpSocket {
     for retry in 0..10 {
         // Create a socket2.socket. std and tokio sockets do not support advance options like
         // reuseaddr to be set.
         let socket = match socket2::Socket::new(
-            socket2::Domain::IPV4,
+            socket2::Domain::for_address(listen_address),
             socket2::Type::DGRAM,
             Some(socket2::Protocol::UDP),
         ) {
@@ -100,11 +102,16 @@ pub async fn get_socket(listen_address: core::net::SocketAddr, interface: String
             }
         };
 
+        if listen_address.is_ipv6() {
+            socket_opr!(socket, socket.set_only_v6(true), retry);
+        }
         socket_opr!(socket, socket.set_reuse_address(true), retry);
         socket_opr!(socket, socket.set_nonblocking(true), retry);
         socket_opr!(socket, socket.bind(&listen_address.into()), retry);
-        // Not for listening, but allowed for sending.
-        socket_opr!(socket, socket.set_broadcast(true), retry);
+        if listen_address.is_ipv4() {
+            // Not for listening, but allowed for sending.
+            socket_opr!(socket, socket.set_broadcast(true), retry);
+        }
 
         let mut retries_left = 10;
         while retries_left > 0 && socket.bind_device(Some(interface.as_bytes())).is_err() {
@@ -121,3 +128,30 @@ pub async fn get_socket(listen_address: core::net::SocketAddr, interface: String
     }
     panic!("Could not create socket successfully.");
 }
+
+/// Create the DHCPv6 server socket. Directly connected clients send to
+/// All_DHCP_Relay_Agents_and_Servers, so the socket joins that group on `interface`; relay
+/// agents unicast to us and need nothing extra.
+pub async fn get_dhcpv6_socket(
+    listen_address: core::net::SocketAddr,
+    interface: String,
+) -> UdpSocket {
+    let socket = get_socket(listen_address, interface.clone()).await;
+    let joined = interface_index(&interface).and_then(|ifindex| {
+        socket
+            .join_multicast_v6(&ALL_DHCP_RELAY_AGENTS_AND_SERVERS, ifindex)
+            .map_err(DhcpError::from)
+    });
+    if let Err(e) = joined {
+        tracing::error!("Could not join DHCPv6 multicast group on {interface}: {e}");
+    }
+    socket
+}
+
+/// Kernel interface index, needed to scope IPv6 link-local and multicast traffic.
+pub fn interface_index(interface: &str) -> Result<u32, DhcpError> {
+    let ifindex = std::fs::read_to_string(format!("/sys/class/net/{interface}/ifindex"))?;
+    ifindex.trim().parse().map_err(|e| {
+        DhcpError::GenericError(format!("Invalid ifindex for {interface}: {ifindex:?}: {e}"))
+    })
+}
@@ -14,8 +14,11 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::collections::BTreeMap;
+use std::net::{IpAddr, SocketAddr};
 use std::path::{Path, PathBuf};
 
+use carbide_uuid::vpc::VpcId;
 use forge_tls::client_config::ClientCert;
 use local_ip_address::local_ip;
 use rpc::forge_tls_client::ForgeClientConfig;
@@ -52,6 +55,33 @@ pub struct Config {
         deserialize_with = "deserialize_uri"
     )]
     pub otlp_endpoint: http::Uri,
+    /// TSIG keys PowerDNS verifies zone transfer and dynamic update requests
+    /// with, by key name. Domains refer to them in their TSIG metadata.
+    #[serde(default)]
+    pub tsig_keys: BTreeMap<String, TsigKey>,
+    /// The VPC each resolver listener serves, by the local address PowerDNS
+    /// received the query on. Records tenants created in their VPC domains
+    /// are only served on the listener of their VPC, which must only be
+    /// reachable from the VPC's VRF. PowerDNS caches answers by name, so a
+    /// PowerDNS instance with more than one VPC listener needs its packet
+    /// and query caches disabled.
+    #[serde(default)]
+    pub vpc_resolvers: BTreeMap<IpAddr, VpcId>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TsigKey {
+    /// e.g. `hmac-sha256`
+    pub algorithm: String,
+    /// File holding the base64 encoded secret. Read on every use so the
+    /// secret can be rotated without a restart.
+    pub secret_path: PathBuf,
+}
+
+impl TsigKey {
+    pub fn secret(&self) -> Result<String, std::io::Error> {
+        std::fs::read_to_string(&self.secret_path).map(|s| s.trim().to_string())
+    }
 }
 
 pub struct Defaults;
@@ -114,6 +144,8 @@ impl Default for Config {
             client_key_path: Defaults::client_key(),
             otlp_endpoint: Defaults::otlp_endpoint(),
             legacy_listen: None,
+            tsig_keys: BTreeMap::new(),
+            vpc_resolvers: BTreeMap::new(),
         }
     }
 }
@@ -152,6 +184,15 @@ impl Config {
         };
         ForgeClientConfig::new(forge_root_ca, Some(client_cert))
     }
+    /// The VPC whose resolver listener `local` is, as PowerDNS sends it in a
+    /// `lookup`, e.g. `192.0.2.53` or `192.0.2.53:53`.
+    pub fn vpc_resolver(&self, local: &str) -> Option<VpcId> {
+        let address = local
+            .parse::<IpAddr>()
+            .or_else(|_| local.parse::<SocketAddr>().map(|addr| addr.ip()))
+            .ok()?;
+        self.vpc_resolvers.get(&address).copied()
+    }
     pub fn load(path: &Path) -> Result<Self, ConfigError> {
         let cfg = std::fs::read_to_string(path).map_err(|error| ConfigError::CouldNotRead {
             path: path.to_string_lossy().to_string(),
@@ -20,12 +20,15 @@ use std::sync::Arc;
 use config::Config;
 use eyre::{Report, WrapErr};
 use pdns::request::PdnsRequest;
-use pdns::response::PdnsResponse;
+use pdns::response::{PdnsResponse, domain_metadata};
 use pdns::socket::PdnsSocket;
+use pdns::transaction::PdnsTransaction;
 use rpc::JsonDnsResourceRecord;
 use rpc::forge_tls_client::{ApiConfig, ForgeClientT, ForgeTlsClient};
 use rpc::protos::dns::{
-    DnsResourceRecordLookupRequest, DomainMetadataRequest, GetAllDomainsRequest,
+    DnsResourceRecordLookupRequest, DomainInfo, DomainMetadataRequest, GetAllDomainsRequest,
+    GetAllRecordsForDomainRequest, GetDomainInfoRequest, GetUpdatedPrimaryDomainsRequest,
+    SetDomainNotifiedRequest,
 };
 use serde_json::{Value, json};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
@@ -57,13 +60,14 @@ pub async fn start(config: Config) -> Result<(), eyre::Report> {
 
         if let Ok((stream, _)) = listener.accept().await {
             let client = client.clone();
+            let config = config.clone();
             let conn_id = Uuid::new_v4();
             tokio::spawn(async move {
                 let span = tracing::info_span!("connection", %conn_id);
                 let _guard = span.enter();
 
                 tracing::info!("Connection accepted");
-                if let Err(err) = handle_connection(stream, client).await {
+                if let Err(err) = handle_connection(stream, client, config).await {
                     tracing::error!(
                         error = ?err,
                         "Connection handling failed"
@@ -76,9 +80,13 @@ pub async fn start(config: Config) -> Result<(), eyre::Report> {
 async fn handle_connection(
     mut stream: UnixStream,
     client: Arc<Mutex<ForgeClientT>>,
+    config: Arc<Config>,
 ) -> Result<(), Report> {
     let (reader, mut writer) = stream.split();
 
+    // PowerDNS runs at most one transaction per backend connection
+    let mut transaction: Option<PdnsTransaction> = None;
+
     let mut reader = BufReader::new(reader);
     let mut buffer = String::new();
 
@@ -135,7 +143,7 @@ async fn handle_connection(
             }
 
             "lookup" => {
-                match handle_lookup(&req, &client).await {
+                match handle_lookup(&req, &client, &config).await {
                     Ok(response) => response,
                     Err(e) => {
                         tracing::error!(
@@ -148,6 +156,58 @@ async fn handle_connection(
                     }
                 }
             }
+            "getDomainMetadata" => or_fallback(
+                "getDomainMetadata",
+                handle_get_domain_metadata(&req, &client).await,
+                json!([]),
+            ),
+            "getDomainInfo" => or_fallback(
+                "getDomainInfo",
+                handle_get_domain_info(&req, &client).await,
+                json!(false),
+            ),
+            // Failing the transfer is better than serving an empty zone
+            "list" => or_fallback("list", handle_list(&req, &client).await, json!(false)),
+            "getTSIGKey" => or_fallback(
+                "getTSIGKey",
+                handle_get_tsig_key(&req, &config),
+                json!(false),
+            ),
+            "getUpdatedMasters" => or_fallback(
+                "getUpdatedMasters",
+                handle_get_updated_masters(&client).await,
+                json!([]),
+            ),
+            "setNotified" => or_fallback(
+                "setNotified",
+                handle_set_notified(&req, &client).await,
+                json!(false),
+            ),
+            "startTransaction" => or_fallback(
+                "startTransaction",
+                handle_start_transaction(&req, &mut transaction),
+                json!(false),
+            ),
+            "replaceRRSet" | "feedRecord" => or_fallback(
+                &req.method,
+                handle_transaction_change(&req, transaction.as_mut()),
+                json!(false),
+            ),
+            "commitTransaction" => or_fallback(
+                "commitTransaction",
+                handle_commit_transaction(&req, transaction.take(), &client).await,
+                json!(false),
+            ),
+            "abortTransaction" => {
+                if let Some(aborted) = transaction.take() {
+                    tracing::info!(
+                        trxid = aborted.id(),
+                        domain = %aborted.domain(),
+                        "Aborted DNS update transaction"
+                    );
+                }
+                PdnsResponse::new(json!(true))
+            }
             "initialize" => {
                 let span = tracing::info_span!("initialize");
                 let _guard = span.enter();
@@ -242,9 +302,9 @@ async fn handle_get_all_domain_metadata(
 
     let res = metadata
         .result
-        .into_iter()
-        .map(|x| serde_json::to_value(x).unwrap_or_default())
-        .collect::<Vec<_>>();
+        .as_ref()
+        .map(domain_metadata)
+        .unwrap_or_default();
 
     tracing::info!(
         method = "getAllDomainMetadata",
@@ -253,7 +313,7 @@ async fn handle_get_all_domain_metadata(
         "getAllDomainMetadata completed"
     );
 
-    let response = PdnsResponse::from(res);
+    let response = PdnsResponse::new(json!(res));
     tracing::trace!(
         method = "getAllDomainMetadata",
         response = ?response,
@@ -265,8 +325,13 @@ async fn handle_get_all_domain_metadata(
 async fn handle_lookup(
     req: &PdnsRequest,
     client: &Arc<Mutex<ForgeClientT>>,
+    config: &Config,
 ) -> Result<PdnsResponse, Report> {
-    let query: DnsResourceRecordLookupRequest = req.try_into()?;
+    let mut query: DnsResourceRecordLookupRequest = req.try_into()?;
+    query.vpc_id = query
+        .local
+        .as_deref()
+        .and_then(|local| config.vpc_resolver(local));
 
     // Create a dedicated span for DNS lookup with all query parameters for correlation
     // This enables correlation with PowerDNS logs via qname, qtype, remote, and timestamp
@@ -278,6 +343,7 @@ async fn handle_lookup(
         remote = %query.remote.as_deref().unwrap_or("unknown"),
         real_remote = %query.real_remote.as_deref().unwrap_or("unknown"),
         local = %query.local.as_deref().unwrap_or("unknown"),
+        vpc_id = ?query.vpc_id,
     );
     let _lookup_guard = lookup_span.enter();
 
@@ -310,6 +376,214 @@ async fn handle_lookup(
     Ok(response)
 }
 
+/// Logs a failed request and answers PowerDNS with `fallback` instead of
+/// closing the connection.
+fn or_fallback(
+    method: &str,
+    result: Result<PdnsResponse, Report>,
+    fallback: Value,
+) -> PdnsResponse {
+    result.unwrap_or_else(|e| {
+        tracing::error!(
+            method,
+            error = %e,
+            "Failed to process {method} - returning {fallback} to PowerDNS"
+        );
+        PdnsResponse::new(fallback)
+    })
+}
+
+async fn handle_get_domain_metadata(
+    req: &PdnsRequest,
+    client: &Arc<Mutex<ForgeClientT>>,
+) -> Result<PdnsResponse, Report> {
+    let query: DomainMetadataRequest = req.try_into()?;
+    let kind = req.string_parameter("kind")?;
+
+    let mut client = client.lock().await;
+    let metadata = client.get_all_domain_metadata(query).await?.into_inner();
+
+    let values = metadata
+        .result
+        .as_ref()
+        .map(domain_metadata)
+        .and_then(|mut kinds| kinds.remove(kind.as_str()))
+        .unwrap_or_default();
+
+    tracing::debug!(method = "getDomainMetadata", %kind, ?values, "getDomainMetadata completed");
+    Ok(PdnsResponse::new(json!(values)))
+}
+
+async fn handle_get_domain_info(
+    req: &PdnsRequest,
+    client: &Arc<Mutex<ForgeClientT>>,
+) -> Result<PdnsResponse, Report> {
+    let query: GetDomainInfoRequest = req.try_into()?;
+
+    let mut client = client.lock().await;
+    let domain = client.get_domain_info(query).await?.into_inner();
+
+    Ok(PdnsResponse::new(serde_json::to_value(domain)?))
+}
+
+/// Answers `list`, which PowerDNS uses to serve AXFR. PowerDNS answers IXFR
+/// requests with a full transfer as well, which RFC 1995 permits.
+async fn handle_list(
+    req: &PdnsRequest,
+    client: &Arc<Mutex<ForgeClientT>>,
+) -> Result<PdnsResponse, Report> {
+    let query: GetAllRecordsForDomainRequest = req.try_into()?;
+    let span = tracing::info_span!("list", zone = %query.name);
+    let _guard = span.enter();
+
+    let api_start = std::time::Instant::now();
+    let mut client = client.lock().await;
+    let records = client.get_all_records_for_domain(query).await?.into_inner();
+
+    tracing::info!(
+        method = "list",
+        record_count = records.result.len(),
+        duration_ms = api_start.elapsed().as_millis(),
+        "list completed"
+    );
+
+    let value = records
+        .result
+        .into_iter()
+        .map(|x| Value::from(JsonDnsResourceRecord(x)))
+        .collect::<Vec<_>>();
+    Ok(PdnsResponse::from(value))
+}
+
+fn handle_get_tsig_key(req: &PdnsRequest, config: &Config) -> Result<PdnsResponse, Report> {
+    let name = req.string_parameter("name")?;
+    let key = config
+        .tsig_keys
+        .get(name.trim_end_matches('.'))
+        .ok_or_else(|| eyre::eyre!("Unknown TSIG key {name}"))?;
+    let secret = key
+        .secret()
+        .wrap_err_with(|| format!("Failed to read secret of TSIG key {name}"))?;
+
+    Ok(PdnsResponse::new(
+        json!({"algorithm": key.algorithm, "content": secret}),
+    ))
+}
+
+async fn handle_get_updated_masters(
+    client: &Arc<Mutex<ForgeClientT>>,
+) -> Result<PdnsResponse, Report> {
+    let mut client = client.lock().await;
+    let domains = client
+        .get_updated_primary_domains(GetUpdatedPrimaryDomainsRequest {})
+        .await?
+        .into_inner();
+
+    tracing::debug!(
+        method = "getUpdatedMasters",
+        domain_count = domains.result.len(),
+        "getUpdatedMasters completed"
+    );
+
+    Ok(updated_masters(domains.result))
+}
+
+/// The `getUpdatedMasters` answer: the domains PowerDNS should send NOTIFY
+/// for. PowerDNS passes their `id` back in `setNotified`.
+fn updated_masters(domains: Vec<DomainInfo>) -> PdnsResponse {
+    let res = domains
+        .into_iter()
+        .map(|x| serde_json::to_value(x).unwrap_or_default())
+        .collect::<Vec<_>>();
+    PdnsResponse::from(res)
+}
+
+async fn handle_set_notified(
+    req: &PdnsRequest,
+    client: &Arc<Mutex<ForgeClientT>>,
+) -> Result<PdnsResponse, Report> {
+    let query: SetDomainNotifiedRequest = req.try_into()?;
+
+    let mut client = client.lock().await;
+    client.set_domain_notified(query).await?;
+
+    Ok(PdnsResponse::new(json!(true)))
+}
+
+/// Starts a transaction, unless one is still in progress. PowerDNS aborts or
+/// commits a transaction before it starts the next one, so this only happens
+/// if it lost track of the open one; replacing it would drop its changes.
+fn handle_start_transaction(
+    req: &PdnsRequest,
+    transaction: &mut Option<PdnsTransaction>,
+) -> Result<PdnsResponse, Report> {
+    if let Some(open) = transaction {
+        return Err(eyre::eyre!(
+            "Transaction {} for {} is still in progress",
+            open.id(),
+            open.domain()
+        ));
+    }
+
+    let started = PdnsTransaction::start(req)?;
+    tracing::info!(
+        trxid = started.id(),
+        domain = %started.domain(),
+        "Started DNS update transaction"
+    );
+    *transaction = Some(started);
+    Ok(PdnsResponse::new(json!(true)))
+}
+
+fn handle_transaction_change(
+    req: &PdnsRequest,
+    transaction: Option<&mut PdnsTransaction>,
+) -> Result<PdnsResponse, Report> {
+    let transaction = transaction.ok_or_else(|| eyre::eyre!("No transaction in progress"))?;
+    if !transaction.is_current(req)? {
+        return Err(eyre::eyre!(
+            "Change for a transaction other than {}",
+            transaction.id()
+        ));
+    }
+
+    match req.method.as_str() {
+        "replaceRRSet" => transaction.replace_rrset(req)?,
+        _ => transaction.feed_record(req)?,
+    }
+    Ok(PdnsResponse::new(json!(true)))
+}
+
+async fn handle_commit_transaction(
+    req: &PdnsRequest,
+    transaction: Option<PdnsTransaction>,
+    client: &Arc<Mutex<ForgeClientT>>,
+) -> Result<PdnsResponse, Report> {
+    let transaction = transaction.ok_or_else(|| eyre::eyre!("No transaction in progress"))?;
+    if !transaction.is_current(req)? {
+        return Err(eyre::eyre!(
+            "Commit for a transaction other than {}",
+            transaction.id()
+        ));
+    }
+
+    let span = tracing::info_span!(
+        "commit_transaction",
+        trxid = transaction.id(),
+        domain = %transaction.domain()
+    );
+    let _guard = span.enter();
+
+    let mut client = client.lock().await;
+    let response = client
+        .update_dns_records(transaction.into_request())
+        .await?
+        .into_inner();
+
+    tracing::info!(serial = response.serial, "Committed DNS update transaction");
+    Ok(PdnsResponse::new(json!(true)))
+}
+
 async fn send_response(
     writer: &mut tokio::net::unix::WriteHalf<'_>,
     response: PdnsResponse,
@@ -375,3 +649,169 @@ async fn send_response(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TsigKey;
+
+    fn request(method: &str, parameters: Value) -> PdnsRequest {
+        PdnsRequest {
+            method: method.to_string(),
+            parameters: serde_json::from_value(parameters).unwrap(),
+        }
+    }
+
+    fn result(response: PdnsResponse) -> Value {
+        serde_json::to_value(response).unwrap()["result"].clone()
+    }
+
+    #[test]
+    fn test_transaction_lifecycle() {
+        let mut transaction = None;
+        let start = request(
+            "startTransaction",
+            json!({"trxid": 1, "domain": "example.com"}),
+        );
+        assert_eq!(
+            result(handle_start_transaction(&start, &mut transaction).unwrap()),
+            json!(true)
+        );
+
+        // A second transaction must not replace the open one
+        let nested = request(
+            "startTransaction",
+            json!({"trxid": 2, "domain": "example.org"}),
+        );
+        assert!(handle_start_transaction(&nested, &mut transaction).is_err());
+        assert_eq!(transaction.as_ref().map(|t| t.id()), Some(1));
+
+        let feed = |trxid: i64| {
+            request(
+                "feedRecord",
+                json!({
+                    "trxid": trxid,
+                    "rr": {"qname": "www.example.com.", "qtype": "A", "ttl": 60, "content": "192.0.2.1"},
+                }),
+            )
+        };
+        assert!(handle_transaction_change(&feed(2), transaction.as_mut()).is_err());
+        assert_eq!(
+            result(handle_transaction_change(&feed(1), transaction.as_mut()).unwrap()),
+            json!(true)
+        );
+        assert_eq!(transaction.clone().unwrap().into_request().changes.len(), 1);
+
+        // Once the transaction is done, the next one can start
+        transaction = None;
+        assert!(handle_start_transaction(&nested, &mut transaction).is_ok());
+        assert_eq!(
+            transaction.as_ref().map(|t| t.domain()),
+            Some("example.org")
+        );
+    }
+
+    #[test]
+    fn test_change_without_transaction() {
+        let feed = request(
+            "feedRecord",
+            json!({
+                "trxid": 1,
+                "rr": {"qname": "www.example.com.", "qtype": "A", "ttl": 60, "content": "192.0.2.1"},
+            }),
+        );
+        assert!(handle_transaction_change(&feed, None).is_err());
+    }
+
+    #[test]
+    fn test_get_tsig_key() {
+        let secret_path = std::env::temp_dir().join(format!("carbide-dns-tsig-{}", Uuid::new_v4()));
+        std::fs::write(&secret_path, "c2VjcmV0\n").unwrap();
+        let config = Config {
+            tsig_keys: [(
+                "update-key".to_string(),
+                TsigKey {
+                    algorithm: "hmac-sha256".to_string(),
+                    secret_path: secret_path.clone(),
+                },
+            )]
+            .into(),
+            ..Default::default()
+        };
+
+        // PowerDNS asks for the key name with a trailing dot
+        for name in ["update-key", "update-key."] {
+            let response =
+                handle_get_tsig_key(&request("getTSIGKey", json!({"name": name})), &config)
+                    .unwrap();
+            assert_eq!(
+                result(response),
+                json!({"algorithm": "hmac-sha256", "content": "c2VjcmV0"})
+            );
+        }
+        assert!(
+            handle_get_tsig_key(
+                &request("getTSIGKey", json!({"name": "other-key"})),
+                &config
+            )
+            .is_err()
+        );
+
+        // A key whose secret can't be read must not be handed out
+        std::fs::remove_file(&secret_path).unwrap();
+        assert!(
+            handle_get_tsig_key(
+                &request("getTSIGKey", json!({"name": "update-key"})),
+                &config
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_updated_masters() {
+        let id = carbide_uuid::domain::DomainId::new();
+        let response = updated_masters(vec![DomainInfo {
+            id: Some(id),
+            zone: "example.com".to_string(),
+            kind: "master".to_string(),
+            serial: 2026101802,
+            last_checked: None,
+            notified_serial: Some(2026101801),
+        }]);
+
+        let domains = result(response);
+        assert_eq!(domains.as_array().map(|d| d.len()), Some(1));
+        assert_eq!(domains[0]["zone"], "example.com");
+        assert_eq!(domains[0]["kind"], "master");
+        assert_eq!(domains[0]["serial"], 2026101802);
+        assert_eq!(domains[0]["notified_serial"], 2026101801);
+
+        assert_eq!(result(updated_masters(vec![])), json!([]));
+    }
+
+    #[test]
+    fn test_vpc_resolver() {
+        let vpc_id = carbide_uuid::vpc::VpcId::new();
+        let config: Config = toml::from_str(&format!(
+            r#"
+            [vpc_resolvers]
+            "192.0.2.53" = "{vpc_id}"
+            "2001:db8::53" = "{vpc_id}"
+            "#
+        ))
+        .unwrap();
+
+        for local in [
+            "192.0.2.53",
+            "192.0.2.53:53",
+            "2001:db8::53",
+            "[2001:db8::53]:53",
+        ] {
+            assert_eq!(config.vpc_resolver(local), Some(vpc_id), "{local}");
+        }
+        for local in ["192.0.2.54", "0.0.0.0", "", "not-an-address"] {
+            assert_eq!(config.vpc_resolver(local), None, "{local}");
+        }
+    }
+}
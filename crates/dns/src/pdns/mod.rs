@@ -18,3 +18,4 @@
 pub(crate) mod request;
 pub(crate) mod response;
 pub(crate) mod socket;
+pub(crate) mod transaction;
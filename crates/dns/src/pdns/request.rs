@@ -16,6 +16,7 @@
  */
 
 use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr};
 
 use dns_record::DnsResourceRecordType;
 use serde::{Deserialize, Serialize};
@@ -145,9 +146,11 @@ impl TryFrom<&PdnsRequest> for rpc::protos::dns::DnsResourceRecordLookupRequest
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
 
+        // PowerDNS sends `real-remote`; `real_remote` is kept for older callers
         let real_remote = request
             .parameters
-            .get("real_remote")
+            .get("real-remote")
+            .or_else(|| request.parameters.get("real_remote"))
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
 
@@ -170,6 +173,247 @@ impl TryFrom<&PdnsRequest> for rpc::protos::dns::DnsResourceRecordLookupRequest
             local,
             remote,
             real_remote,
+            // Set from the listener by the caller
+            vpc_id: None,
         })
     }
 }
+
+impl PdnsRequest {
+    /// Returns the string parameter `name`, e.g. `zonename` for `list`.
+    pub fn string_parameter(&self, name: &str) -> Result<String, eyre::Report> {
+        self.parameters
+            .get(name)
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| {
+                tracing::error!(
+                    method = %self.method,
+                    parameters = ?self.parameters,
+                    "Missing or invalid '{name}' parameter"
+                );
+                eyre::eyre!("Missing or invalid '{name}' parameter")
+            })
+    }
+
+    /// Returns the integer parameter `name`, e.g. `trxid` for transactions.
+    pub fn integer_parameter(&self, name: &str) -> Result<i64, eyre::Report> {
+        self.parameters
+            .get(name)
+            .and_then(|v| v.as_i64())
+            .ok_or_else(|| {
+                tracing::error!(
+                    method = %self.method,
+                    parameters = ?self.parameters,
+                    "Missing or invalid '{name}' parameter"
+                );
+                eyre::eyre!("Missing or invalid '{name}' parameter")
+            })
+    }
+}
+
+impl TryFrom<&PdnsRequest> for rpc::protos::dns::GetDomainInfoRequest {
+    type Error = eyre::Report;
+
+    fn try_from(request: &PdnsRequest) -> Result<Self, Self::Error> {
+        Ok(rpc::protos::dns::GetDomainInfoRequest {
+            name: request.string_parameter("name")?,
+        })
+    }
+}
+
+/// Converts a `list` request, which PowerDNS sends to serve AXFR (and IXFR,
+/// which it answers with a full transfer).
+impl TryFrom<&PdnsRequest> for rpc::protos::dns::GetAllRecordsForDomainRequest {
+    type Error = eyre::Report;
+
+    fn try_from(request: &PdnsRequest) -> Result<Self, Self::Error> {
+        Ok(rpc::protos::dns::GetAllRecordsForDomainRequest {
+            name: request.string_parameter("zonename")?,
+        })
+    }
+}
+
+impl TryFrom<&PdnsRequest> for rpc::protos::dns::SetDomainNotifiedRequest {
+    type Error = eyre::Report;
+
+    fn try_from(request: &PdnsRequest) -> Result<Self, Self::Error> {
+        // The id is the one we returned in getUpdatedMasters
+        let id = request.string_parameter("id")?;
+        let id = id
+            .parse::<carbide_uuid::domain::DomainId>()
+            .map_err(|e| eyre::eyre!("Invalid domain id {id}: {e}"))?;
+        let serial = request.integer_parameter("serial")?;
+
+        Ok(rpc::protos::dns::SetDomainNotifiedRequest {
+            id: Some(id),
+            serial: u32::try_from(serial).map_err(|_| eyre::eyre!("Invalid serial {serial}"))?,
+        })
+    }
+}
+
+/// Converts a resource record as PowerDNS sends it in `replaceRRSet` and
+/// `feedRecord`, e.g. `{"qname": "www.example.com.", "qtype": "A", "ttl": 300,
+/// "content": "192.0.2.1"}`.
+pub(crate) fn resource_record(
+    value: &Value,
+) -> Result<rpc::protos::dns::DnsResourceRecord, eyre::Report> {
+    let field = |name: &str| {
+        value
+            .get(name)
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| eyre::eyre!("Missing or invalid '{name}' in resource record"))
+    };
+    let ttl = value
+        .get("ttl")
+        .and_then(|v| v.as_u64())
+        .and_then(|ttl| u32::try_from(ttl).ok())
+        .ok_or_else(|| eyre::eyre!("Missing or invalid 'ttl' in resource record"))?;
+
+    let qtype = field("qtype")?;
+    let content = field("content")?;
+    validate_content(&qtype, &content)?;
+
+    Ok(rpc::protos::dns::DnsResourceRecord {
+        qname: field("qname")?,
+        qtype,
+        ttl,
+        content,
+        domain_id: None,
+        scope_mask: None,
+        auth: None,
+    })
+}
+
+/// Rejects record content PowerDNS would not be able to serve for `qtype`,
+/// since it passes the content of RFC 2136 updates through unchecked.
+fn validate_content(qtype: &str, content: &str) -> Result<(), eyre::Report> {
+    let valid = match qtype.to_ascii_uppercase().as_str() {
+        "A" => content.parse::<Ipv4Addr>().is_ok(),
+        "AAAA" => content.parse::<Ipv6Addr>().is_ok(),
+        "CNAME" | "NS" | "PTR" => is_domain_name(content),
+        _ => true,
+    };
+    if !valid {
+        return Err(eyre::eyre!(
+            "Invalid content '{content}' for {qtype} record"
+        ));
+    }
+    Ok(())
+}
+
+/// Whether `name` is a valid host name, with or without the trailing dot.
+/// Underscores are allowed since they are common in service names.
+fn is_domain_name(name: &str) -> bool {
+    let name = name.strip_suffix('.').unwrap_or(name);
+    !name.is_empty()
+        && name.len() <= 253
+        && name.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn record(qtype: &str, content: &str) -> Value {
+        json!({"qname": "www.example.com.", "qtype": qtype, "ttl": 300, "content": content})
+    }
+
+    #[test]
+    fn test_resource_record() {
+        let rr = resource_record(&record("A", "192.0.2.1")).unwrap();
+        assert_eq!(rr.qname, "www.example.com.");
+        assert_eq!(rr.qtype, "A");
+        assert_eq!(rr.ttl, 300);
+        assert_eq!(rr.content, "192.0.2.1");
+        assert_eq!(rr.domain_id, None);
+    }
+
+    #[test]
+    fn test_resource_record_missing_fields() {
+        for field in ["qname", "qtype", "ttl", "content"] {
+            let mut rr = record("A", "192.0.2.1");
+            rr.as_object_mut().unwrap().remove(field);
+            assert!(resource_record(&rr).is_err(), "{field} is required");
+        }
+
+        let mut rr = record("A", "192.0.2.1");
+        rr["ttl"] = json!(u64::from(u32::MAX) + 1);
+        assert!(resource_record(&rr).is_err());
+        rr["ttl"] = json!("300");
+        assert!(resource_record(&rr).is_err());
+    }
+
+    #[test]
+    fn test_resource_record_content() {
+        for (qtype, content) in [
+            ("A", "192.0.2.1"),
+            ("AAAA", "2001:db8::1"),
+            ("CNAME", "host.example.com."),
+            ("NS", "ns1.example.com"),
+            ("PTR", "_ldap._tcp.example.com."),
+            ("TXT", "v=spf1 -all"),
+        ] {
+            assert!(
+                resource_record(&record(qtype, content)).is_ok(),
+                "{qtype} {content} is valid"
+            );
+        }
+
+        for (qtype, content) in [
+            ("A", "2001:db8::1"),
+            ("A", "192.0.2"),
+            ("A", "host.example.com."),
+            ("AAAA", "192.0.2.1"),
+            ("CNAME", "192.0.2.1 "),
+            ("CNAME", ""),
+            ("CNAME", "."),
+            ("NS", "ns1..example.com."),
+            ("NS", "-ns1.example.com."),
+            ("PTR", "host name.example.com."),
+        ] {
+            assert!(
+                resource_record(&record(qtype, content)).is_err(),
+                "{qtype} {content} is invalid"
+            );
+        }
+
+        let long_label = format!("{}.example.com.", "a".repeat(64));
+        assert!(resource_record(&record("CNAME", &long_label)).is_err());
+    }
+
+    #[test]
+    fn test_set_domain_notified_request() {
+        let id = carbide_uuid::domain::DomainId::new();
+        let request = PdnsRequest {
+            method: "setNotified".to_string(),
+            parameters: [
+                ("id".to_string(), json!(id.to_string())),
+                ("serial".to_string(), json!(2026101801)),
+            ]
+            .into(),
+        };
+        let query = rpc::protos::dns::SetDomainNotifiedRequest::try_from(&request).unwrap();
+        assert_eq!(query.id, Some(id));
+        assert_eq!(query.serial, 2026101801);
+
+        let mut invalid = request.clone();
+        invalid.parameters.insert("id".to_string(), json!("1"));
+        assert!(rpc::protos::dns::SetDomainNotifiedRequest::try_from(&invalid).is_err());
+
+        let mut invalid = request;
+        invalid.parameters.insert("serial".to_string(), json!(-1));
+        assert!(rpc::protos::dns::SetDomainNotifiedRequest::try_from(&invalid).is_err());
+    }
+}
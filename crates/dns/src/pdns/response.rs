@@ -15,6 +15,9 @@
  * limitations under the License.
  */
 
+use std::collections::BTreeMap;
+
+use rpc::protos::dns::Metadata;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -42,3 +45,34 @@ impl From<Vec<Value>> for PdnsResponse {
         }
     }
 }
+
+/// The PowerDNS metadata kinds of a domain, e.g. `ALLOW-AXFR-FROM`, as
+/// returned by `getAllDomainMetadata`. Kinds without values are left out.
+pub fn domain_metadata(metadata: &Metadata) -> BTreeMap<&'static str, Vec<String>> {
+    // carbide-api may send comma separated lists as a single value
+    let values = |values: &[String]| {
+        values
+            .iter()
+            .flat_map(|v| v.split(','))
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .collect::<Vec<_>>()
+    };
+
+    [
+        ("ALLOW-AXFR-FROM", values(&metadata.allow_axfr_from)),
+        ("TSIG-ALLOW-AXFR", values(&metadata.tsig_allow_axfr)),
+        (
+            "ALLOW-DNSUPDATE-FROM",
+            values(&metadata.allow_dnsupdate_from),
+        ),
+        (
+            "TSIG-ALLOW-DNSUPDATE",
+            values(&metadata.tsig_allow_dnsupdate),
+        ),
+        ("ALSO-NOTIFY", values(&metadata.also_notify)),
+    ]
+    .into_iter()
+    .filter(|(_, values)| !values.is_empty())
+    .collect()
+}
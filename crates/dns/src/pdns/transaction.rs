@@ -0,0 +1,232 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! RFC 2136 dynamic updates arrive from PowerDNS as a transaction:
+//! `startTransaction`, any number of `replaceRRSet` and `feedRecord` calls,
+//! then `commitTransaction` or `abortTransaction`. The changes are collected
+//! here and sent to carbide-api as one `UpdateDnsRecords` call on commit, so
+//! an update is applied atomically.
+
+use rpc::protos::dns::dns_record_change::Change;
+use rpc::protos::dns::{DnsRecordChange, DnsRecordSet, UpdateDnsRecordsRequest};
+use serde_json::Value;
+
+use super::request::{PdnsRequest, resource_record};
+
+#[derive(Debug, Clone)]
+pub(crate) struct PdnsTransaction {
+    id: i64,
+    domain: String,
+    changes: Vec<DnsRecordChange>,
+}
+
+impl PdnsTransaction {
+    pub fn start(request: &PdnsRequest) -> Result<Self, eyre::Report> {
+        Ok(PdnsTransaction {
+            id: request.integer_parameter("trxid")?,
+            domain: request.string_parameter("domain")?,
+            changes: vec![],
+        })
+    }
+
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// Whether `request` belongs to this transaction
+    pub fn is_current(&self, request: &PdnsRequest) -> Result<bool, eyre::Report> {
+        Ok(request.integer_parameter("trxid")? == self.id)
+    }
+
+    pub fn replace_rrset(&mut self, request: &PdnsRequest) -> Result<(), eyre::Report> {
+        let records = request
+            .parameters
+            .get("rrset")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| eyre::eyre!("Missing or invalid 'rrset' parameter"))?
+            .iter()
+            .map(resource_record)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.changes.push(DnsRecordChange {
+            change: Some(Change::Replace(DnsRecordSet {
+                qname: request.string_parameter("qname")?,
+                qtype: request.string_parameter("qtype")?,
+                records,
+            })),
+        });
+        Ok(())
+    }
+
+    pub fn feed_record(&mut self, request: &PdnsRequest) -> Result<(), eyre::Report> {
+        let record = request
+            .parameters
+            .get("rr")
+            .ok_or_else(|| eyre::eyre!("Missing 'rr' parameter"))
+            .and_then(|rr: &Value| resource_record(rr))?;
+
+        self.changes.push(DnsRecordChange {
+            change: Some(Change::Add(record)),
+        });
+        Ok(())
+    }
+
+    pub fn into_request(self) -> UpdateDnsRecordsRequest {
+        UpdateDnsRecordsRequest {
+            domain: self.domain,
+            changes: self.changes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn request(method: &str, parameters: Value) -> PdnsRequest {
+        PdnsRequest {
+            method: method.to_string(),
+            parameters: serde_json::from_value(parameters).unwrap(),
+        }
+    }
+
+    fn record(qname: &str, content: &str) -> Value {
+        json!({"qname": qname, "qtype": "A", "ttl": 300, "content": content})
+    }
+
+    #[test]
+    fn test_start() {
+        let transaction = PdnsTransaction::start(&request(
+            "startTransaction",
+            json!({"trxid": 7, "domain": "example.com", "domain_id": 1}),
+        ))
+        .unwrap();
+        assert_eq!(transaction.id(), 7);
+        assert_eq!(transaction.domain(), "example.com");
+
+        assert!(
+            PdnsTransaction::start(&request(
+                "startTransaction",
+                json!({"trxid": "7", "domain": "example.com"})
+            ))
+            .is_err()
+        );
+        assert!(PdnsTransaction::start(&request("startTransaction", json!({"trxid": 7}))).is_err());
+    }
+
+    #[test]
+    fn test_changes() {
+        let mut transaction = PdnsTransaction::start(&request(
+            "startTransaction",
+            json!({"trxid": 7, "domain": "example.com"}),
+        ))
+        .unwrap();
+
+        let replace = request(
+            "replaceRRSet",
+            json!({
+                "trxid": 7,
+                "qname": "www.example.com.",
+                "qtype": "A",
+                "rrset": [
+                    record("www.example.com.", "192.0.2.1"),
+                    record("www.example.com.", "192.0.2.2"),
+                ],
+            }),
+        );
+        assert!(transaction.is_current(&replace).unwrap());
+        transaction.replace_rrset(&replace).unwrap();
+
+        let feed = request(
+            "feedRecord",
+            json!({"trxid": 7, "rr": record("api.example.com.", "192.0.2.3")}),
+        );
+        transaction.feed_record(&feed).unwrap();
+
+        let other = request(
+            "feedRecord",
+            json!({"trxid": 8, "rr": record("a.example.com.", "192.0.2.4")}),
+        );
+        assert!(!transaction.is_current(&other).unwrap());
+
+        let update = transaction.into_request();
+        assert_eq!(update.domain, "example.com");
+        assert_eq!(update.changes.len(), 2);
+        let Some(Change::Replace(rrset)) = &update.changes[0].change else {
+            panic!("Expected a replace, got {:?}", update.changes[0]);
+        };
+        assert_eq!(rrset.qname, "www.example.com.");
+        assert_eq!(rrset.qtype, "A");
+        assert_eq!(
+            rrset
+                .records
+                .iter()
+                .map(|r| r.content.as_str())
+                .collect::<Vec<_>>(),
+            ["192.0.2.1", "192.0.2.2"]
+        );
+        let Some(Change::Add(record)) = &update.changes[1].change else {
+            panic!("Expected an add, got {:?}", update.changes[1]);
+        };
+        assert_eq!(record.qname, "api.example.com.");
+        assert_eq!(record.content, "192.0.2.3");
+    }
+
+    #[test]
+    fn test_invalid_changes_are_rejected() {
+        let mut transaction = PdnsTransaction::start(&request(
+            "startTransaction",
+            json!({"trxid": 7, "domain": "example.com"}),
+        ))
+        .unwrap();
+
+        // An empty rrset deletes the records
+        transaction
+            .replace_rrset(&request(
+                "replaceRRSet",
+                json!({"trxid": 7, "qname": "old.example.com.", "qtype": "A", "rrset": []}),
+            ))
+            .unwrap();
+        assert!(
+            transaction
+                .replace_rrset(&request(
+                    "replaceRRSet",
+                    json!({
+                        "trxid": 7,
+                        "qname": "www.example.com.",
+                        "qtype": "A",
+                        "rrset": [record("www.example.com.", "192.0.2.1"), record("www.example.com.", "not-an-ip")],
+                    }),
+                ))
+                .is_err()
+        );
+        assert!(
+            transaction
+                .feed_record(&request("feedRecord", json!({"trxid": 7})))
+                .is_err()
+        );
+
+        // Rejected changes are not applied partially
+        assert_eq!(transaction.into_request().changes.len(), 1);
+    }
+}
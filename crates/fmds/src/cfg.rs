@@ -63,6 +63,20 @@ pub struct Options {
         default_value = "169.254.169.254/30"
     )]
     pub interface_cidr: String,
+
+    /// Reject requests to the EC2 metadata tree that don't carry an IMDSv2
+    /// session token, like `http-tokens=required` on EC2.
+    #[clap(long, env = "FMDS_REQUIRE_SESSION_TOKENS", default_value = "false")]
+    pub require_session_tokens: bool,
+
+    /// IPv4 TTL and IPv6 hop limit of metadata responses. Replies are
+    /// routed through the metadata gateway, so a limit of 2 reaches the host
+    /// but not containers or VMs the host forwards traffic for, which keeps
+    /// SSRF-style requests from those from reading metadata. The limit applies
+    /// to all metadata trees. Unset by default, which keeps metadata reachable
+    /// from clients behind a routed or NAT hop.
+    #[clap(long, env = "FMDS_HOP_LIMIT")]
+    pub hop_limit: Option<u32>,
 }
 
 impl Options {
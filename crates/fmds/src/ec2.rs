@@ -0,0 +1,254 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! An EC2 instance metadata service (IMDSv2) compatible view of the data in
+//! [`FmdsState`], so cloud-init's Ec2 datasource can consume it natively.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::Router;
+use axum::extract::{Path, Request, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, put};
+use serde::Serialize;
+
+use crate::rest_server::{current_config, is_forwarded};
+use crate::state::FmdsState;
+
+/// The newest metadata API version cloud-init asks EC2 for.
+pub const EC2_API_VERSION: &str = "2021-03-23";
+pub const TOKEN_HEADER: &str = "x-aws-ec2-metadata-token";
+pub const TOKEN_TTL_HEADER: &str = "x-aws-ec2-metadata-token-ttl-seconds";
+const MAX_TOKEN_TTL_SECONDS: u64 = 21600;
+
+const HOSTNAME: &str = "hostname";
+const LOCAL_HOSTNAME: &str = "local-hostname";
+const INSTANCE_ID: &str = "instance-id";
+const LOCAL_IPV4: &str = "local-ipv4";
+const PUBLIC_IPV4: &str = "public-ipv4";
+const PLACEMENT: &str = "placement";
+const AVAILABILITY_ZONE: &str = "availability-zone";
+
+/// `PUT /latest/api/token`, which hands out IMDSv2 session tokens.
+pub fn get_token_router(state: Arc<FmdsState>) -> Router {
+    Router::new()
+        .route("/latest/api/token", put(put_token))
+        .with_state(state)
+}
+
+/// The EC2 metadata tree under [`EC2_API_VERSION`].
+pub fn get_ec2_router(state: Arc<FmdsState>) -> Router {
+    let metadata_router = Router::new()
+        .route("/meta-data", get(get_metadata_listing))
+        .route("/meta-data/", get(get_metadata_listing))
+        .route(
+            &format!("/meta-data/{PLACEMENT}"),
+            get(get_placement_listing),
+        )
+        .route(
+            &format!("/meta-data/{PLACEMENT}/"),
+            get(get_placement_listing),
+        )
+        .route(
+            &format!("/meta-data/{PLACEMENT}/{AVAILABILITY_ZONE}"),
+            get(get_availability_zone),
+        )
+        .route("/meta-data/{key}", get(get_metadata_value))
+        .route("/user-data", get(get_user_data))
+        .route(
+            "/dynamic/instance-identity/document",
+            get(get_instance_identity_document),
+        );
+
+    Router::new()
+        .nest(&format!("/{EC2_API_VERSION}"), metadata_router)
+        .with_state(state)
+}
+
+/// Checks the IMDSv2 session token of a request. Requests without one are
+/// only let through while session tokens aren't required.
+pub async fn require_session_token(
+    State(state): State<Arc<FmdsState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match request.headers().get(TOKEN_HEADER) {
+        Some(token)
+            if token
+                .to_str()
+                .is_ok_and(|token| state.session_tokens.is_valid(token)) =>
+        {
+            next.run(request).await
+        }
+        Some(_) => (
+            StatusCode::UNAUTHORIZED,
+            "invalid or expired session token".to_string(),
+        )
+            .into_response(),
+        None if state.require_session_tokens => (
+            StatusCode::UNAUTHORIZED,
+            "a session token is required".to_string(),
+        )
+            .into_response(),
+        None => next.run(request).await,
+    }
+}
+
+async fn put_token(State(state): State<Arc<FmdsState>>, headers: HeaderMap) -> Response {
+    // Like EC2, refuse tokens to requests that went through a proxy. Together
+    // with the response hop limit this keeps SSRF-style requests relayed by
+    // software on the instance from obtaining a token.
+    if is_forwarded(&headers) {
+        return (
+            StatusCode::FORBIDDEN,
+            "forwarded requests can't obtain a session token".to_string(),
+        )
+            .into_response();
+    }
+
+    let ttl = match headers
+        .get(TOKEN_TTL_HEADER)
+        .and_then(|ttl| ttl.to_str().ok())
+        .and_then(|ttl| ttl.parse::<u64>().ok())
+    {
+        Some(ttl) if (1..=MAX_TOKEN_TTL_SECONDS).contains(&ttl) => ttl,
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                format!("{TOKEN_TTL_HEADER} must be between 1 and {MAX_TOKEN_TTL_SECONDS}"),
+            )
+                .into_response();
+        }
+    };
+
+    let token = state.session_tokens.issue(Duration::from_secs(ttl));
+    ([(TOKEN_TTL_HEADER, ttl.to_string())], token).into_response()
+}
+
+async fn get_metadata_listing(State(state): State<Arc<FmdsState>>) -> (StatusCode, String) {
+    let config = match current_config(&state) {
+        Ok(config) => config,
+        Err(err) => return err,
+    };
+
+    let mut keys = vec![HOSTNAME];
+    if config.instance_id.is_some() {
+        keys.push(INSTANCE_ID);
+    }
+    keys.extend([LOCAL_HOSTNAME, LOCAL_IPV4]);
+    let placement = format!("{PLACEMENT}/");
+    if config.sitename.is_some() {
+        keys.push(&placement);
+    }
+    keys.push(PUBLIC_IPV4);
+
+    (StatusCode::OK, keys.join("\n"))
+}
+
+async fn get_metadata_value(
+    State(state): State<Arc<FmdsState>>,
+    Path(key): Path<String>,
+) -> (StatusCode, String) {
+    let config = match current_config(&state) {
+        Ok(config) => config,
+        Err(err) => return err,
+    };
+
+    match key.as_str() {
+        HOSTNAME | LOCAL_HOSTNAME => (StatusCode::OK, config.hostname.clone()),
+        LOCAL_IPV4 | PUBLIC_IPV4 => (StatusCode::OK, config.address.clone()),
+        INSTANCE_ID => match &config.instance_id {
+            Some(instance_id) => (StatusCode::OK, instance_id.to_string()),
+            None => (
+                StatusCode::NOT_FOUND,
+                "instance id not available".to_string(),
+            ),
+        },
+        _ => (
+            StatusCode::NOT_FOUND,
+            format!("metadata category not found: {key}"),
+        ),
+    }
+}
+
+async fn get_placement_listing(State(state): State<Arc<FmdsState>>) -> (StatusCode, String) {
+    match current_config(&state) {
+        Ok(config) if config.sitename.is_some() => (StatusCode::OK, AVAILABILITY_ZONE.to_string()),
+        Ok(_) => (StatusCode::NOT_FOUND, "placement not available".to_string()),
+        Err(err) => err,
+    }
+}
+
+async fn get_availability_zone(State(state): State<Arc<FmdsState>>) -> (StatusCode, String) {
+    let config = match current_config(&state) {
+        Ok(config) => config,
+        Err(err) => return err,
+    };
+
+    match &config.sitename {
+        Some(sitename) => (StatusCode::OK, sitename.clone()),
+        None => (
+            StatusCode::NOT_FOUND,
+            "availability zone not available".to_string(),
+        ),
+    }
+}
+
+async fn get_user_data(State(state): State<Arc<FmdsState>>) -> (StatusCode, String) {
+    let config = match current_config(&state) {
+        Ok(config) => config,
+        Err(err) => return err,
+    };
+
+    // EC2 answers 404 for instances without user data
+    if config.user_data.is_empty() {
+        (StatusCode::NOT_FOUND, "user data not available".to_string())
+    } else {
+        (StatusCode::OK, config.user_data.clone())
+    }
+}
+
+/// The subset of the EC2 instance identity document that FMDS knows about.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InstanceIdentityDocument {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    instance_id: Option<String>,
+    private_ip: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    availability_zone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<String>,
+}
+
+async fn get_instance_identity_document(State(state): State<Arc<FmdsState>>) -> Response {
+    let config = match current_config(&state) {
+        Ok(config) => config,
+        Err(err) => return err.into_response(),
+    };
+
+    axum::Json(InstanceIdentityDocument {
+        instance_id: config.instance_id.as_ref().map(ToString::to_string),
+        private_ip: config.address.clone(),
+        availability_zone: config.sitename.clone(),
+        region: config.sitename.clone(),
+    })
+    .into_response()
+}
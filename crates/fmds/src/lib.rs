@@ -16,8 +16,10 @@
  */
 
 pub mod cfg;
+pub mod ec2;
 pub mod grpc_server;
 pub mod nic_init;
+pub mod openstack;
 pub mod phone_home;
 pub mod rest_server;
 pub mod state;
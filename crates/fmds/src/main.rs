@@ -22,7 +22,7 @@ use clap::Parser;
 use fmds::cfg::Options;
 use fmds::grpc_server::FmdsGrpcServer;
 use fmds::nic_init;
-use fmds::rest_server::get_fmds_router;
+use fmds::rest_server::{bind_listener, get_metadata_router};
 use fmds::state::FmdsState;
 use forge_tls::client_config::ClientCert;
 use rpc::fmds::fmds_config_service_server::FmdsConfigServiceServer;
@@ -95,39 +95,35 @@ async fn main() -> eyre::Result<()> {
         }
     };
 
-    let state = Arc::new(FmdsState::new(
-        options.forge_api.clone(),
-        forge_client_config,
-    ));
+    let state = Arc::new(
+        FmdsState::new(options.forge_api.clone(), forge_client_config)
+            .with_required_session_tokens(options.require_session_tokens),
+    );
 
     // Start REST server for tenant metadata queries
     let rest_state = state.clone();
     let rest_address = options.rest_address.clone();
+    let hop_limit = options.hop_limit;
     tokio::spawn(async move {
-        // We serve metadata under both /latest and /2009-04-04 for
-        // compatibility with cloud-init, which uses the AWS EC2 instance
-        // metadata API versioned path format.
-        let router = axum::Router::new()
-            .nest("/latest", get_fmds_router(rest_state.clone()))
-            .nest("/2009-04-04", get_fmds_router(rest_state))
-            .layer(
-                TraceLayer::new_for_http()
-                    .make_span_with(|request: &Request<_>| {
-                        // Captures URI and Method automatically in every log within this span
-                        tracing::info_span!(
-                            "http-request",
-                            method = %request.method(),
-                            uri = %request.uri(),
-                        )
-                    })
-                    .on_request(DefaultOnRequest::new().level(Level::INFO))
-                    .on_response(DefaultOnResponse::new().level(Level::INFO)),
-            );
+        let router = get_metadata_router(rest_state).layer(
+            TraceLayer::new_for_http()
+                .make_span_with(|request: &Request<_>| {
+                    // Captures URI and Method automatically in every log within this span
+                    tracing::info_span!(
+                        "http-request",
+                        method = %request.method(),
+                        uri = %request.uri(),
+                    )
+                })
+                .on_request(DefaultOnRequest::new().level(Level::INFO))
+                .on_response(DefaultOnResponse::new().level(Level::INFO)),
+        );
 
         let addr: std::net::SocketAddr = rest_address.parse().expect("invalid REST address");
-        let server = axum_server::Server::bind(addr);
+        let listener = bind_listener(addr, hop_limit).expect("failed to bind REST address");
+        let server = axum_server::Server::from_tcp(listener);
 
-        tracing::info!(%addr, "REST server listening");
+        tracing::info!(%addr, ?hop_limit, "REST server listening");
         if let Err(err) = server.serve(router.into_make_service()).await {
             tracing::error!("REST server error: {err}");
         }
@@ -0,0 +1,147 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! An OpenStack metadata service compatible view of the data in
+//! [`FmdsState`], so cloud-init's OpenStack datasource can consume it
+//! natively.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::Router;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use serde::Serialize;
+
+use crate::rest_server::current_config;
+use crate::state::FmdsState;
+
+/// The only metadata version served. cloud-init uses `latest` when it
+/// doesn't find a dated version it knows.
+const OPENSTACK_VERSION: &str = "latest";
+const META_DATA_JSON: &str = "meta_data.json";
+const NETWORK_DATA_JSON: &str = "network_data.json";
+const USER_DATA: &str = "user_data";
+
+pub fn get_openstack_router(state: Arc<FmdsState>) -> Router {
+    let base = format!("/openstack/{OPENSTACK_VERSION}");
+    Router::new()
+        .route("/openstack", get(get_versions))
+        .route("/openstack/", get(get_versions))
+        .route(&base, get(get_listing))
+        .route(&format!("{base}/"), get(get_listing))
+        .route(&format!("{base}/{META_DATA_JSON}"), get(get_meta_data))
+        .route(
+            &format!("{base}/{NETWORK_DATA_JSON}"),
+            get(get_network_data),
+        )
+        .route(&format!("{base}/{USER_DATA}"), get(get_user_data))
+        .with_state(state)
+}
+
+async fn get_versions() -> (StatusCode, String) {
+    (StatusCode::OK, OPENSTACK_VERSION.to_string())
+}
+
+async fn get_listing(State(state): State<Arc<FmdsState>>) -> (StatusCode, String) {
+    let config = match current_config(&state) {
+        Ok(config) => config,
+        Err(err) => return err,
+    };
+
+    let mut files = vec![META_DATA_JSON, NETWORK_DATA_JSON];
+    if !config.user_data.is_empty() {
+        files.push(USER_DATA);
+    }
+    (StatusCode::OK, files.join("\n"))
+}
+
+/// `meta_data.json`, limited to the fields FMDS knows about. Carbide
+/// specific values go into `meta`, OpenStack's free-form instance metadata.
+#[derive(Debug, Serialize)]
+struct MetaData {
+    uuid: String,
+    name: String,
+    hostname: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    availability_zone: Option<String>,
+    meta: BTreeMap<&'static str, String>,
+}
+
+async fn get_meta_data(State(state): State<Arc<FmdsState>>) -> Response {
+    let config = match current_config(&state) {
+        Ok(config) => config,
+        Err(err) => return err.into_response(),
+    };
+
+    // cloud-init requires an instance ID. Fall back to the machine for hosts
+    // that aren't running an instance.
+    let uuid = match (&config.instance_id, &config.machine_id) {
+        (Some(instance_id), _) => instance_id.to_string(),
+        (None, Some(machine_id)) => machine_id.to_string(),
+        (None, None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                "instance id not available".to_string(),
+            )
+                .into_response();
+        }
+    };
+
+    let mut meta = BTreeMap::from([("asn", config.asn.to_string())]);
+    if let Some(machine_id) = &config.machine_id {
+        meta.insert("machine-id", machine_id.to_string());
+    }
+    if let Some(sitename) = &config.sitename {
+        meta.insert("sitename", sitename.clone());
+    }
+
+    axum::Json(MetaData {
+        uuid,
+        name: config.hostname.clone(),
+        hostname: config.hostname.clone(),
+        availability_zone: config.sitename.clone(),
+        meta,
+    })
+    .into_response()
+}
+
+/// `network_data.json`. Every link in it needs the MAC address of an
+/// instance interface, which FMDS doesn't know, so the document is empty.
+/// cloud-init then falls back to DHCP on the primary interface, just like it
+/// does with the EC2 view.
+async fn get_network_data(State(state): State<Arc<FmdsState>>) -> Response {
+    match current_config(&state) {
+        Ok(_) => axum::Json(serde_json::json!({})).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+async fn get_user_data(State(state): State<Arc<FmdsState>>) -> (StatusCode, String) {
+    let config = match current_config(&state) {
+        Ok(config) => config,
+        Err(err) => return err,
+    };
+
+    if config.user_data.is_empty() {
+        (StatusCode::NOT_FOUND, "user data not available".to_string())
+    } else {
+        (StatusCode::OK, config.user_data.clone())
+    }
+}
@@ -15,14 +15,19 @@
  * limitations under the License.
  */
 
+use std::net::{SocketAddr, TcpListener};
 use std::sync::Arc;
 
-use axum::Router;
-use axum::extract::{Path, State};
-use axum::http::StatusCode;
+use axum::extract::{Path, Request, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
 use axum::routing::{get, post};
+use axum::{Router, middleware};
+use socket2::{Domain, Protocol, Socket, Type};
 
-use crate::state::FmdsState;
+use crate::state::{FmdsConfig, FmdsState};
+use crate::{ec2, openstack};
 
 const PUBLIC_IPV4_CATEGORY: &str = "public-ipv4";
 const HOSTNAME_CATEGORY: &str = "hostname";
@@ -39,6 +44,81 @@ const INSTANCE_ID_CATEGORY: &str = "instance-id";
 const PHONE_HOME_CATEGORY: &str = "phone_home";
 const ASN_CATEGORY: &str = "asn";
 
+/// Every metadata tree FMDS serves: its own under `/latest` and
+/// `/2009-04-04`, the EC2 IMDSv2 one under [`ec2::EC2_API_VERSION`] and the
+/// OpenStack one under `/openstack`. All of them are derived from the same
+/// [`FmdsState`].
+///
+/// Session tokens only apply to the EC2 tree; the FMDS tree keeps working
+/// for existing clients that don't know about them. The OpenStack API has no
+/// tokens, so forwarded requests are refused instead.
+pub fn get_metadata_router(state: Arc<FmdsState>) -> Router {
+    let ec2_router = ec2::get_ec2_router(state.clone()).route_layer(
+        middleware::from_fn_with_state(state.clone(), ec2::require_session_token),
+    );
+    let openstack_router = openstack::get_openstack_router(state.clone())
+        .route_layer(middleware::from_fn(reject_forwarded));
+
+    // We serve metadata under both /latest and /2009-04-04 for
+    // compatibility with cloud-init, which uses the AWS EC2 instance
+    // metadata API versioned path format.
+    Router::new()
+        .nest("/latest", get_fmds_router(state.clone()))
+        .nest("/2009-04-04", get_fmds_router(state.clone()))
+        .merge(ec2_router)
+        .merge(ec2::get_token_router(state))
+        .merge(openstack_router)
+}
+
+/// Binds the REST listener. Accepted connections inherit the IPv4 TTL and
+/// IPv6 hop limit of the listening socket, so every response is sent with
+/// `hop_limit` if one is given. Both are set on IPv6 sockets, which also
+/// accept IPv4 clients as mapped addresses.
+pub fn bind_listener(addr: SocketAddr, hop_limit: Option<u32>) -> std::io::Result<TcpListener> {
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+    socket.set_reuse_address(true)?;
+    if let Some(hop_limit) = hop_limit {
+        socket.set_ttl_v4(hop_limit)?;
+        if addr.is_ipv6() {
+            socket.set_unicast_hops_v6(hop_limit)?;
+        }
+    }
+    socket.bind(&addr.into())?;
+    socket.listen(1024)?;
+    socket.set_nonblocking(true)?;
+    Ok(socket.into())
+}
+
+/// Whether a proxy relayed `headers`. Software on the host that can be
+/// tricked into relaying requests (SSRF) typically adds one of these.
+pub(crate) fn is_forwarded(headers: &HeaderMap) -> bool {
+    ["x-forwarded-for", "forwarded", "via"]
+        .iter()
+        .any(|name| headers.contains_key(*name))
+}
+
+async fn reject_forwarded(request: Request, next: Next) -> Response {
+    if is_forwarded(request.headers()) {
+        return (
+            StatusCode::FORBIDDEN,
+            "forwarded requests can't read metadata".to_string(),
+        )
+            .into_response();
+    }
+    next.run(request).await
+}
+
+/// The config last pushed by the agent, or the response to send while there
+/// is none yet.
+pub(crate) fn current_config(state: &FmdsState) -> Result<Arc<FmdsConfig>, (StatusCode, String)> {
+    state.config.load_full().ok_or_else(|| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "metadata currently unavailable".to_string(),
+        )
+    })
+}
+
 pub fn get_fmds_router(state: Arc<FmdsState>) -> Router {
     let user_data_router =
         Router::new().route(&format!("/{USER_DATA_CATEGORY}"), get(get_userdata));
@@ -406,6 +486,63 @@ mod tests {
         (status, body_str)
     }
 
+    async fn setup_metadata_server(state: Arc<FmdsState>) -> (tokio::task::JoinHandle<()>, u16) {
+        let router = get_metadata_router(state);
+
+        let addr = std::net::SocketAddr::from(([127, 0, 0, 1], 0));
+        let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+        let server_port = listener.local_addr().unwrap().port();
+        let std_listener = listener.into_std().unwrap();
+
+        let server = tokio::spawn(async move {
+            axum_server::Server::from_tcp(std_listener)
+                .serve(router.into_make_service())
+                .await
+                .unwrap();
+        });
+
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        (server, server_port)
+    }
+
+    async fn send_request(
+        port: u16,
+        method: hyper::Method,
+        path: &str,
+        headers: &[(&str, &str)],
+    ) -> (http::StatusCode, http::HeaderMap, String) {
+        let client = hyper_util::client::legacy::Client::builder(TokioExecutor::new()).build_http();
+        let mut builder = hyper::Request::builder()
+            .method(method)
+            .uri(format!("http://127.0.0.1:{port}/{path}"));
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        let request: hyper::Request<Full<Bytes>> = builder.body("".into()).unwrap();
+
+        let response = client.request(request).await.unwrap();
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body_str = std::str::from_utf8(&body).unwrap().to_string();
+
+        (status, headers, body_str)
+    }
+
+    async fn get_session_token(port: u16) -> String {
+        let (status, headers, token) = send_request(
+            port,
+            hyper::Method::PUT,
+            "latest/api/token",
+            &[(ec2::TOKEN_TTL_HEADER, "60")],
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers[ec2::TOKEN_TTL_HEADER], "60");
+        token
+    }
+
     // Metadata unavailable (empty state) test.
     #[tokio::test]
     async fn test_returns_error_when_no_config() {
@@ -668,6 +805,285 @@ mod tests {
         server.abort();
     }
 
+    // EC2 IMDSv2 compatibility tests.
+    #[tokio::test]
+    async fn test_ec2_session_tokens() {
+        let state = make_test_state();
+        state.update_config(make_test_config());
+        let (server, port) = setup_metadata_server(state).await;
+
+        let (status, _, _) = send_request(port, hyper::Method::PUT, "latest/api/token", &[]).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _, _) = send_request(
+            port,
+            hyper::Method::PUT,
+            "latest/api/token",
+            &[(ec2::TOKEN_TTL_HEADER, "21601")],
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _, _) = send_request(
+            port,
+            hyper::Method::PUT,
+            "latest/api/token",
+            &[
+                (ec2::TOKEN_TTL_HEADER, "60"),
+                ("X-Forwarded-For", "10.0.0.2"),
+            ],
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let token = get_session_token(port).await;
+
+        let path = "2021-03-23/meta-data/hostname";
+        let (status, _, body) = send_request(
+            port,
+            hyper::Method::GET,
+            path,
+            &[(ec2::TOKEN_HEADER, &token)],
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "test-host");
+
+        let (status, _, _) = send_request(
+            port,
+            hyper::Method::GET,
+            path,
+            &[(ec2::TOKEN_HEADER, "bogus")],
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        // Tokens are optional unless required
+        let (status, _, _) = send_request(port, hyper::Method::GET, path, &[]).await;
+        assert_eq!(status, StatusCode::OK);
+
+        // The FMDS tree doesn't look at tokens
+        let (status, _, body) = send_request(
+            port,
+            hyper::Method::GET,
+            "latest/meta-data/hostname",
+            &[(ec2::TOKEN_HEADER, "bogus")],
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "test-host");
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_ec2_required_session_tokens() {
+        let state = Arc::new(
+            FmdsState::new("https://api.test".to_string(), None).with_required_session_tokens(true),
+        );
+        state.update_config(make_test_config());
+        let (server, port) = setup_metadata_server(state).await;
+
+        for path in ["2021-03-23/meta-data/hostname", "2021-03-23/user-data"] {
+            let (status, _, _) = send_request(port, hyper::Method::GET, path, &[]).await;
+            assert_eq!(status, StatusCode::UNAUTHORIZED, "{path}");
+        }
+
+        let token = get_session_token(port).await;
+        let (status, _, body) = send_request(
+            port,
+            hyper::Method::GET,
+            "2021-03-23/meta-data/hostname",
+            &[(ec2::TOKEN_HEADER, &token)],
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "test-host");
+
+        // Existing clients of the FMDS tree keep working without tokens
+        for path in ["latest/meta-data/hostname", "2009-04-04/meta-data/hostname"] {
+            let (status, _, body) = send_request(port, hyper::Method::GET, path, &[]).await;
+            assert_eq!(status, StatusCode::OK, "{path}");
+            assert_eq!(body, "test-host");
+        }
+
+        // The OpenStack tree doesn't use session tokens
+        let (status, _, _) = send_request(
+            port,
+            hyper::Method::GET,
+            "openstack/latest/meta_data.json",
+            &[],
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_ec2_metadata_tree() {
+        let state = make_test_state();
+        state.update_config(make_test_config());
+        let (server, port) = setup_metadata_server(state).await;
+
+        let get = |path: &'static str| send_request(port, hyper::Method::GET, path, &[]);
+
+        let (status, _, body) = get("2021-03-23/meta-data/").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            [
+                "hostname",
+                "instance-id",
+                "local-hostname",
+                "local-ipv4",
+                "placement/",
+                "public-ipv4",
+            ]
+            .join("\n")
+        );
+
+        let (_, _, body) = get("2021-03-23/meta-data/local-ipv4").await;
+        assert_eq!(body, "10.0.0.1");
+        let (_, _, body) = get("2021-03-23/meta-data/instance-id").await;
+        assert_eq!(body, "67e55044-10b1-426f-9247-bb680e5fe0c8");
+        let (_, _, body) = get("2021-03-23/meta-data/placement/").await;
+        assert_eq!(body, "availability-zone");
+        let (_, _, body) = get("2021-03-23/meta-data/placement/availability-zone").await;
+        assert_eq!(body, "test-site");
+        let (_, _, body) = get("2021-03-23/user-data").await;
+        assert_eq!(body, "cloud-init-data");
+
+        let (status, _, body) = get("2021-03-23/dynamic/instance-identity/document").await;
+        assert_eq!(status, StatusCode::OK);
+        let document: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(
+            document,
+            serde_json::json!({
+                "instanceId": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+                "privateIp": "10.0.0.1",
+                "availabilityZone": "test-site",
+                "region": "test-site",
+            })
+        );
+
+        let (status, _, _) = get("2021-03-23/meta-data/machine-id").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        server.abort();
+    }
+
+    // OpenStack compatibility tests.
+    #[tokio::test]
+    async fn test_openstack_metadata_tree() {
+        let state = make_test_state();
+        state.update_config(make_test_config());
+        let (server, port) = setup_metadata_server(state).await;
+
+        let get = |path: &'static str| send_request(port, hyper::Method::GET, path, &[]);
+
+        let (status, _, body) = get("openstack/").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "latest");
+
+        let (_, _, body) = get("openstack/latest/").await;
+        assert_eq!(body, "meta_data.json\nnetwork_data.json\nuser_data");
+
+        let (status, _, body) = get("openstack/latest/meta_data.json").await;
+        assert_eq!(status, StatusCode::OK);
+        let meta_data: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(
+            meta_data,
+            serde_json::json!({
+                "uuid": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+                "name": "test-host",
+                "hostname": "test-host",
+                "availability_zone": "test-site",
+                "meta": {
+                    "asn": "65000",
+                    "machine-id": "fm100ht6n80e7do39u8gmt7cvhm89pb32st9ngevgdolu542l1nfa4an0rg",
+                    "sitename": "test-site",
+                },
+            })
+        );
+
+        let (status, _, body) = get("openstack/latest/network_data.json").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "{}");
+
+        let (_, _, body) = get("openstack/latest/user_data").await;
+        assert_eq!(body, "cloud-init-data");
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_openstack_rejects_forwarded_requests() {
+        let state = make_test_state();
+        state.update_config(make_test_config());
+        let (server, port) = setup_metadata_server(state).await;
+
+        for header in [
+            ("X-Forwarded-For", "10.0.0.2"),
+            ("Forwarded", "for=10.0.0.2"),
+            ("Via", "1.1 proxy"),
+        ] {
+            for path in ["openstack/", "openstack/latest/meta_data.json"] {
+                let (status, _, _) = send_request(port, hyper::Method::GET, path, &[header]).await;
+                assert_eq!(status, StatusCode::FORBIDDEN, "{path} with {header:?}");
+            }
+        }
+
+        let (status, _, _) = send_request(
+            port,
+            hyper::Method::GET,
+            "openstack/latest/meta_data.json",
+            &[],
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        server.abort();
+    }
+
+    #[test]
+    fn test_bind_listener_hop_limit() {
+        let default_ttl = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .ttl()
+            .unwrap();
+        let listener = bind_listener("127.0.0.1:0".parse().unwrap(), None).unwrap();
+        assert_eq!(listener.ttl().unwrap(), default_ttl);
+
+        let listener = bind_listener("127.0.0.1:0".parse().unwrap(), Some(2)).unwrap();
+        assert_eq!(listener.ttl().unwrap(), 2);
+
+        // Skipped where the host has no IPv6 loopback
+        if std::net::TcpListener::bind("[::1]:0").is_err() {
+            return;
+        }
+        let listener = bind_listener("[::1]:0".parse().unwrap(), Some(3)).unwrap();
+        let socket = Socket::from(listener);
+        assert_eq!(socket.unicast_hops_v6().unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_compat_trees_without_user_data() {
+        let state = make_test_state();
+        state.update_config(FmdsConfig {
+            user_data: String::new(),
+            ..make_test_config()
+        });
+        let (server, port) = setup_metadata_server(state).await;
+
+        for path in ["2021-03-23/user-data", "openstack/latest/user_data"] {
+            let (status, _, _) = send_request(port, hyper::Method::GET, path, &[]).await;
+            assert_eq!(status, StatusCode::NOT_FOUND, "{path}");
+        }
+
+        server.abort();
+    }
+
     // Test integration from gRPC push -> REST read.
     #[tokio::test]
     async fn test_grpc_push_then_rest_read() {
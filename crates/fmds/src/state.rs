@@ -15,7 +15,9 @@
  * limitations under the License.
  */
 
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use arc_swap::ArcSwapOption;
 use carbide_uuid::infiniband::IBPartitionId;
@@ -28,6 +30,9 @@ use nonzero_ext::nonzero;
 use rpc::forge_tls_client::ForgeClientConfig;
 
 const PHONE_HOME_RATE_LIMIT: Quota = Quota::per_minute(nonzero!(10u32));
+/// The most session tokens kept at once. Issuing more evicts the tokens that
+/// expire first, so tenants can't grow the store without bound.
+const MAX_SESSION_TOKENS: usize = 1024;
 
 /// Shared state between the gRPC server (writer) and REST server (reader).
 pub struct FmdsState {
@@ -37,6 +42,10 @@ pub struct FmdsState {
     pub forge_client_config: Option<Arc<ForgeClientConfig>>,
     pub outbound_governor:
         Arc<RateLimiter<NotKeyed, InMemoryState, clock::DefaultClock, NoOpMiddleware>>,
+    pub session_tokens: SessionTokens,
+    /// Reject EC2-style metadata requests without a session token, like
+    /// `http-tokens=required` on EC2.
+    pub require_session_tokens: bool,
 }
 
 impl FmdsState {
@@ -47,9 +56,16 @@ impl FmdsState {
             forge_api,
             forge_client_config,
             outbound_governor: Arc::new(RateLimiter::direct(PHONE_HOME_RATE_LIMIT)),
+            session_tokens: SessionTokens::default(),
+            require_session_tokens: false,
         }
     }
 
+    pub fn with_required_session_tokens(mut self, required: bool) -> Self {
+        self.require_session_tokens = required;
+        self
+    }
+
     pub fn update_config(&self, config: FmdsConfig) {
         // Stash the machine_id separately for phone_home lookups.
         if let Some(ref mid) = config.machine_id {
@@ -86,6 +102,46 @@ pub struct IBInstanceConfig {
     pub lid: u32,
 }
 
+/// Session tokens handed out by the EC2 IMDSv2 `PUT /latest/api/token`
+/// endpoint, with the time they expire.
+#[derive(Default)]
+pub struct SessionTokens {
+    tokens: Mutex<HashMap<String, Instant>>,
+}
+
+impl SessionTokens {
+    /// Issues a new token that is valid for `ttl`.
+    pub fn issue(&self, ttl: Duration) -> String {
+        let bytes: [u8; 32] = rand::random();
+        let token: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+        let now = Instant::now();
+
+        let mut tokens = self.tokens.lock().unwrap();
+        tokens.retain(|_, expires| *expires > now);
+        while tokens.len() >= MAX_SESSION_TOKENS {
+            let Some(first_to_expire) = tokens
+                .iter()
+                .min_by_key(|(_, expires)| **expires)
+                .map(|(token, _)| token.clone())
+            else {
+                break;
+            };
+            tokens.remove(&first_to_expire);
+        }
+        tokens.insert(token.clone(), now + ttl);
+        token
+    }
+
+    /// Whether `token` was issued and hasn't expired yet.
+    pub fn is_valid(&self, token: &str) -> bool {
+        self.tokens
+            .lock()
+            .unwrap()
+            .get(token)
+            .is_some_and(|expires| *expires > Instant::now())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -155,6 +211,32 @@ mod tests {
         assert!(state.machine_id.load_full().is_none());
     }
 
+    #[test]
+    fn test_session_tokens_expire() {
+        let tokens = SessionTokens::default();
+
+        let token = tokens.issue(Duration::from_secs(60));
+        assert_eq!(token.len(), 64);
+        assert!(tokens.is_valid(&token));
+        assert!(!tokens.is_valid("not-a-token"));
+
+        let expired = tokens.issue(Duration::ZERO);
+        assert!(!tokens.is_valid(&expired));
+    }
+
+    #[test]
+    fn test_session_tokens_are_bounded() {
+        let tokens = SessionTokens::default();
+
+        let first = tokens.issue(Duration::from_secs(1));
+        for _ in 0..MAX_SESSION_TOKENS {
+            tokens.issue(Duration::from_secs(60));
+        }
+
+        assert_eq!(tokens.tokens.lock().unwrap().len(), MAX_SESSION_TOKENS);
+        assert!(!tokens.is_valid(&first));
+    }
+
     #[test]
     fn test_update_config_replaces_previous() {
         let state = FmdsState::new("https://api.test".to_string(), None);
@@ -166,6 +166,27 @@ impl HealthReport {
         }
     }
 
+    /// Returns a health report that indicates that a host stayed in a state for longer
+    /// than the SLA of the state
+    pub fn state_sla_exceeded(
+        source: String,
+        state: String,
+        message: String,
+        in_alert_since: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        Self {
+            source,
+            observed_at: Some(chrono::Utc::now()),
+            successes: vec![],
+            alerts: vec![HealthProbeAlert::state_sla_exceeded(
+                state,
+                message,
+                in_alert_since,
+            )],
+            triggered_by: None,
+        }
+    }
+
     /// Returns a health report that indicates that the DPU agent is on a stale version, older than
     /// some threshold
     pub fn stale_agent_version(
@@ -428,6 +449,25 @@ impl HealthProbeAlert {
         }
     }
 
+    /// Creates a StateSlaExceeded alert
+    ///
+    /// The alert carries no classifications. It only makes hosts which are stuck
+    /// in a state visible.
+    pub fn state_sla_exceeded(
+        state: String,
+        message: String,
+        in_alert_since: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        Self {
+            id: HealthProbeId::state_sla_exceeded(),
+            target: Some(state),
+            in_alert_since: Some(in_alert_since),
+            message,
+            tenant_message: None,
+            classifications: vec![],
+        }
+    }
+
     /// Creates a MissingReport alert
     pub fn missing_report() -> Self {
         Self {
@@ -567,6 +607,12 @@ impl HealthProbeId {
         HealthProbeId("StaleAgentVersion".to_string())
     }
 
+    /// Returns the ID of the HealthProbe that indicates that a host stayed in a
+    /// state for longer than the SLA of the state
+    pub fn state_sla_exceeded() -> Self {
+        HealthProbeId("StateSlaExceeded".to_string())
+    }
+
     /// The alert indicates that no health report was received, where health report
     /// was expected. It is different from `heartbeat_timeout` in the following sense
     /// - HeartbeatTimeout alerts can be emitted if data is available, but stale.
@@ -57,6 +57,7 @@ fn event_context() -> EventContext {
         metadata: Some(EndpointMetadata::Machine(MachineData {
             machine_id: MACHINE_ID.parse().expect("valid machine id"),
             machine_serial: None,
+            hw_sku: None,
         })),
         rack_id: None,
     }
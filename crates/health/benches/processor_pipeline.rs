@@ -94,6 +94,7 @@ fn event_context() -> EventContext {
         metadata: Some(EndpointMetadata::Machine(MachineData {
             machine_id: MACHINE_ID.parse().expect("valid machine id"),
             machine_serial: None,
+            hw_sku: None,
         })),
         rack_id: None,
     }
@@ -268,6 +269,7 @@ fn rack_event_contexts(rack_id: &str, tray_count: usize) -> Vec<EventContext> {
                 metadata: Some(EndpointMetadata::Machine(MachineData {
                     machine_id: MACHINE_ID.parse().expect("valid machine id"),
                     machine_serial: None,
+                    hw_sku: None,
                 })),
                 rack_id: Some(RackId::new(rack_id)),
             }
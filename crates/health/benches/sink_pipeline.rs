@@ -67,6 +67,7 @@ fn event_context_for_machine(machine_id: &str) -> EventContext {
         metadata: Some(EndpointMetadata::Machine(MachineData {
             machine_id: machine_id.parse().expect("valid machine id"),
             machine_serial: None,
+            hw_sku: None,
         })),
         rack_id: None,
     }
@@ -185,6 +185,7 @@ impl ApiClientWrapper {
                 EndpointMetadata::Machine(MachineData {
                     machine_id,
                     machine_serial: info.dmi_data.map(|dmi| dmi.chassis_serial),
+                    hw_sku: machine.hw_sku.clone(),
                 })
             });
 
@@ -284,6 +285,24 @@ impl ApiClientWrapper {
 
         Ok(())
     }
+
+    pub async fn record_sensor_samples(
+        &self,
+        machine_id: &carbide_uuid::machine::MachineId,
+        samples: Vec<rpc::forge::SensorSample>,
+    ) -> Result<(), HealthError> {
+        let request = rpc::forge::RecordMachineSensorSamplesRequest {
+            machine_id: Some(*machine_id),
+            samples,
+        };
+
+        self.client
+            .record_machine_sensor_samples(request)
+            .await
+            .map_err(HealthError::ApiInvocationError)?;
+
+        Ok(())
+    }
 }
 
 impl EndpointSource for ApiClientWrapper {
@@ -15,6 +15,7 @@
  * limitations under the License.
  */
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::net::SocketAddr;
 use std::path::Path;
@@ -140,6 +141,12 @@ pub struct SinksConfig {
 
     /// OTLP log export sink: streams events to an OpenTelemetry collector via gRPC.
     pub otlp: Configurable<OtlpSinkConfig>,
+
+    /// Alert routing sink: notifies webhooks, Alertmanager or files about health alerts.
+    pub alert_routing: Configurable<AlertRoutingSinkConfig>,
+
+    /// Sensor history sink: stores downsampled sensor readings of machines in Carbide API.
+    pub sensor_history: Configurable<SensorHistorySinkConfig>,
 }
 
 impl Default for SinksConfig {
@@ -151,6 +158,8 @@ impl Default for SinksConfig {
             rack_health_report: Configurable::Enabled(RackHealthReportSinkConfig::default()),
             log_file: Configurable::Disabled,
             otlp: Configurable::Disabled,
+            alert_routing: Configurable::Disabled,
+            sensor_history: Configurable::Disabled,
         }
     }
 }
@@ -200,6 +209,228 @@ impl Default for OtlpSinkConfig {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertRoutingSinkConfig {
+    /// How long to wait before notifying about a new alert group, so that
+    /// alerts which fire together are delivered in a single notification.
+    #[serde(with = "humantime_serde")]
+    pub group_wait: Duration,
+
+    /// Minimum time between notifications for a group whose alerts changed.
+    #[serde(with = "humantime_serde")]
+    pub group_interval: Duration,
+
+    /// Interval after which a notification for unchanged firing alerts is repeated.
+    #[serde(with = "humantime_serde")]
+    pub repeat_interval: Duration,
+
+    /// Interval in which alert groups are evaluated and notifications are sent.
+    #[serde(with = "humantime_serde")]
+    pub evaluation_interval: Duration,
+
+    /// Timeout for delivering a notification to a receiver.
+    #[serde(with = "humantime_serde")]
+    pub delivery_timeout: Duration,
+
+    /// Routes which select alerts and the receivers to notify. Evaluated in order.
+    pub routes: Vec<AlertRouteConfig>,
+
+    /// Rules which mute alerts while other alerts are firing.
+    pub inhibit_rules: Vec<AlertInhibitRuleConfig>,
+
+    /// Time ranges in which matching alerts are muted.
+    pub silences: Vec<AlertSilenceConfig>,
+
+    /// Destinations for notifications.
+    pub receivers: Vec<AlertReceiverConfig>,
+}
+
+impl Default for AlertRoutingSinkConfig {
+    fn default() -> Self {
+        Self {
+            group_wait: Duration::from_secs(30),
+            group_interval: Duration::from_secs(300),
+            repeat_interval: Duration::from_secs(4 * 3600),
+            evaluation_interval: Duration::from_secs(5),
+            delivery_timeout: Duration::from_secs(10),
+            routes: Vec::new(),
+            inhibit_rules: Vec::new(),
+            silences: Vec::new(),
+            receivers: Vec::new(),
+        }
+    }
+}
+
+impl AlertRoutingSinkConfig {
+    fn validate(&self) -> Result<(), String> {
+        let mut receiver_names = std::collections::HashSet::new();
+        for receiver in &self.receivers {
+            if !receiver_names.insert(receiver.name.as_str()) {
+                return Err(format!(
+                    "sinks.alert_routing.receivers: duplicate receiver {}",
+                    receiver.name
+                ));
+            }
+        }
+
+        for route in &self.routes {
+            if route.receivers.is_empty() {
+                return Err(format!(
+                    "sinks.alert_routing.routes: route {} has no receivers",
+                    route.name
+                ));
+            }
+            if let Some(receiver) = route
+                .receivers
+                .iter()
+                .find(|r| !receiver_names.contains(r.as_str()))
+            {
+                return Err(format!(
+                    "sinks.alert_routing.routes: route {} references unknown receiver {receiver}",
+                    route.name
+                ));
+            }
+        }
+
+        for silence in &self.silences {
+            if silence.matchers.is_empty() {
+                return Err("sinks.alert_routing.silences: matchers must not be empty".to_string());
+            }
+            if silence.ends_at <= silence.starts_at {
+                return Err(
+                    "sinks.alert_routing.silences: ends_at must be after starts_at".to_string(),
+                );
+            }
+        }
+
+        if self.evaluation_interval.is_zero() {
+            return Err(
+                "sinks.alert_routing.evaluation_interval must be greater than 0".to_string(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Selects health alerts and the receivers which get notified about them.
+///
+/// An alert matches a route if it matches any of the classifications and
+/// sources (an empty list matches everything), and all of the label matchers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRouteConfig {
+    /// Name of the route. Used as the `alertname` label of the alerts it selects.
+    pub name: String,
+
+    /// Alert classifications, e.g. `Leak` or `SensorCritical`.
+    #[serde(default)]
+    pub classifications: Vec<String>,
+
+    /// Health report sources, e.g. `tray-leak-detection`.
+    #[serde(default)]
+    pub sources: Vec<String>,
+
+    /// Labels which must have the given values.
+    #[serde(default)]
+    pub matchers: HashMap<String, String>,
+
+    /// Value of the `severity` label of selected alerts.
+    #[serde(default = "default_alert_severity")]
+    pub severity: String,
+
+    /// Names of the receivers to notify.
+    pub receivers: Vec<String>,
+
+    /// Labels by which alerts are grouped into notifications.
+    #[serde(default = "default_alert_group_by")]
+    pub group_by: Vec<String>,
+
+    /// Whether later routes are evaluated for alerts which matched this route.
+    #[serde(default, rename = "continue")]
+    pub continue_matching: bool,
+}
+
+fn default_alert_severity() -> String {
+    "critical".to_string()
+}
+
+fn default_alert_group_by() -> Vec<String> {
+    vec!["alertname".to_string(), "rack_id".to_string()]
+}
+
+/// Mutes alerts matching `target_matchers` while an alert matching
+/// `source_matchers` fires which has the same values for the `equal` labels.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertInhibitRuleConfig {
+    pub source_matchers: HashMap<String, String>,
+    pub target_matchers: HashMap<String, String>,
+    #[serde(default)]
+    pub equal: Vec<String>,
+}
+
+/// Mutes alerts which carry all of the given labels between `starts_at` and `ends_at`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertSilenceConfig {
+    pub matchers: HashMap<String, String>,
+    pub starts_at: chrono::DateTime<chrono::Utc>,
+    pub ends_at: chrono::DateTime<chrono::Utc>,
+    #[serde(default)]
+    pub comment: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertReceiverConfig {
+    pub name: String,
+    #[serde(flatten)]
+    pub kind: AlertReceiverKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AlertReceiverKind {
+    /// Posts notifications in the Alertmanager webhook format to an URL.
+    Webhook {
+        url: Url,
+        #[serde(default)]
+        headers: HashMap<String, String>,
+    },
+    /// Posts alerts to the v2 API of an Alertmanager.
+    Alertmanager { url: Url },
+    /// Appends notifications as JSON lines to a local file.
+    File { path: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SensorHistorySinkConfig {
+    #[serde(flatten)]
+    pub connection: CarbideApiConnectionConfig,
+
+    /// Minimum time between two stored readings of the same sensor.
+    #[serde(with = "humantime_serde")]
+    pub sample_interval: Duration,
+
+    /// Interval in which buffered readings are sent to Carbide API.
+    #[serde(with = "humantime_serde")]
+    pub flush_interval: Duration,
+
+    /// Maximum number of readings buffered per machine. The oldest readings
+    /// are dropped if Carbide API can't keep up.
+    pub max_buffered_samples: usize,
+}
+
+impl Default for SensorHistorySinkConfig {
+    fn default() -> Self {
+        Self {
+            connection: CarbideApiConnectionConfig::default(),
+            sample_interval: Duration::from_secs(60),
+            flush_interval: Duration::from_secs(30),
+            max_buffered_samples: 10_000,
+        }
+    }
+}
+
 /// Shared Carbide API connection configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -322,6 +553,9 @@ pub struct ProcessorsConfig {
 
     /// Rack-level leak processor: aggregates tray leak reports per rack.
     pub rack_leak: Configurable<RackLeakProcessorConfig>,
+
+    /// Sensor anomaly processor: reports drifting, stuck and outlier sensor readings.
+    pub sensor_anomaly: Configurable<SensorAnomalyProcessorConfig>,
 }
 
 impl Default for ProcessorsConfig {
@@ -329,6 +563,7 @@ impl Default for ProcessorsConfig {
         Self {
             leak_detection: Configurable::Enabled(LeakDetectionProcessorConfig::default()),
             rack_leak: Configurable::Enabled(RackLeakProcessorConfig::default()),
+            sensor_anomaly: Configurable::Disabled,
         }
     }
 }
@@ -364,6 +599,89 @@ impl Default for RackLeakProcessorConfig {
     }
 }
 
+/// Machines whose sensors are compared with each other to find outliers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SensorAnomalyPeerGroup {
+    Rack,
+    Sku,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SensorAnomalyProcessorConfig {
+    /// Redfish reading types (in snake case) of the sensors which are checked.
+    pub reading_types: Vec<String>,
+
+    /// Machines whose sensors are compared to find outliers.
+    pub peer_group: SensorAnomalyPeerGroup,
+
+    /// Number of readings of a sensor before drift and outliers are reported.
+    pub warmup_samples: u32,
+
+    /// Smoothing factor of the long-term baseline of a sensor.
+    pub baseline_alpha: f64,
+
+    /// Smoothing factor of the short-term average of a sensor.
+    pub recent_alpha: f64,
+
+    /// Relative deviation of the short-term average from the baseline which is reported as drift.
+    pub drift_ratio: f64,
+
+    /// Number of identical consecutive readings after which a sensor is reported as stuck.
+    pub stuck_samples: u32,
+
+    /// Minimum number of sibling machines with the same sensor to report outliers.
+    pub min_peers: usize,
+
+    /// Deviation from the median of the siblings, in scaled median absolute
+    /// deviations, which is reported as outlier.
+    pub outlier_threshold: f64,
+
+    /// Minimum relative deviation from the median of the siblings which is reported as outlier.
+    pub outlier_min_ratio: f64,
+}
+
+impl Default for SensorAnomalyProcessorConfig {
+    fn default() -> Self {
+        Self {
+            reading_types: vec![
+                "temperature".to_string(),
+                "rotational".to_string(),
+                "power".to_string(),
+            ],
+            peer_group: SensorAnomalyPeerGroup::Rack,
+            warmup_samples: 30,
+            baseline_alpha: 0.02,
+            recent_alpha: 0.3,
+            drift_ratio: 0.15,
+            stuck_samples: 60,
+            min_peers: 4,
+            outlier_threshold: 4.0,
+            outlier_min_ratio: 0.1,
+        }
+    }
+}
+
+impl SensorAnomalyProcessorConfig {
+    fn validate(&self) -> Result<(), String> {
+        for (name, alpha) in [
+            ("baseline_alpha", self.baseline_alpha),
+            ("recent_alpha", self.recent_alpha),
+        ] {
+            if !(alpha > 0.0 && alpha <= 1.0) {
+                return Err(format!(
+                    "processors.sensor_anomaly.{name} must be in the range (0, 1]"
+                ));
+            }
+        }
+        if self.stuck_samples < 2 {
+            return Err("processors.sensor_anomaly.stuck_samples must be at least 2".to_string());
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct SensorCollectorConfig {
@@ -659,6 +977,23 @@ impl Config {
                 .map_err(|_| format!("invalid sinks.otlp.endpoint: {}", otlp.endpoint))?;
         }
 
+        if let Configurable::Enabled(ref alert_routing) = self.sinks.alert_routing {
+            alert_routing.validate()?;
+        }
+
+        if let Configurable::Enabled(ref sensor_anomaly) = self.processors.sensor_anomaly {
+            sensor_anomaly.validate()?;
+        }
+
+        if let Configurable::Enabled(ref sensor_history) = self.sinks.sensor_history
+            && (sensor_history.flush_interval.is_zero() || sensor_history.max_buffered_samples == 0)
+        {
+            return Err(
+                "sinks.sensor_history.flush_interval and max_buffered_samples must be greater than 0"
+                    .to_string(),
+            );
+        }
+
         self.metrics_addr()?;
 
         Ok(())
@@ -962,6 +1297,71 @@ cache_size = 50
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn test_alert_routing_config_parsing() {
+        let toml_content = r#"
+[sinks.alert_routing]
+group_wait = "10s"
+
+[[sinks.alert_routing.routes]]
+name = "leaks"
+classifications = ["Leak"]
+receivers = ["oncall", "archive"]
+group_by = ["alertname", "rack_id"]
+continue = true
+
+[[sinks.alert_routing.inhibit_rules]]
+source_matchers = { classification = "Leak" }
+target_matchers = { classification = "SensorCritical" }
+equal = ["endpoint"]
+
+[[sinks.alert_routing.silences]]
+matchers = { rack_id = "rack-1" }
+starts_at = "2026-01-01T00:00:00Z"
+ends_at = "2026-01-02T00:00:00Z"
+comment = "rack move"
+
+[[sinks.alert_routing.receivers]]
+name = "oncall"
+type = "alertmanager"
+url = "http://alertmanager:9093"
+
+[[sinks.alert_routing.receivers]]
+name = "archive"
+type = "file"
+path = "/tmp/alerts.jsonl"
+"#;
+        let mut config: Config = Figment::new()
+            .merge(Toml::string(toml_content))
+            .extract()
+            .expect("could not parse config toml file");
+
+        let Configurable::Enabled(ref alert_routing) = config.sinks.alert_routing else {
+            panic!("alert routing sink is disabled");
+        };
+        assert_eq!(alert_routing.group_wait, Duration::from_secs(10));
+        assert_eq!(alert_routing.repeat_interval, Duration::from_secs(4 * 3600));
+        assert_eq!(alert_routing.routes.len(), 1);
+        assert!(alert_routing.routes[0].continue_matching);
+        assert_eq!(alert_routing.routes[0].severity, "critical");
+        assert!(matches!(
+            alert_routing.receivers[0].kind,
+            AlertReceiverKind::Alertmanager { .. }
+        ));
+        assert!(matches!(
+            alert_routing.receivers[1].kind,
+            AlertReceiverKind::File { .. }
+        ));
+        config.validate().expect("config should be valid");
+
+        if let Configurable::Enabled(ref mut alert_routing) = config.sinks.alert_routing {
+            alert_routing.routes[0]
+                .receivers
+                .push("unknown".to_string());
+        }
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_load_defaults() {
         let config = Config::load(None).expect("should load defaults");
@@ -131,6 +131,7 @@ impl EndpointMetadata {
 pub struct MachineData {
     pub machine_id: MachineId,
     pub machine_serial: Option<String>,
+    pub hw_sku: Option<String>,
 }
 
 #[derive(Clone, Debug)]
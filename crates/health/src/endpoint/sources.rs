@@ -62,6 +62,7 @@ impl StaticEndpointSource {
                         Ok(machine_id) => Some(EndpointMetadata::Machine(MachineData {
                             machine_id,
                             machine_serial: None,
+                            hw_sku: None,
                         })),
                         Err(error) => {
                             tracing::warn!(?error, machine_id = ?machine_id_str, "Invalid machine_id in static endpoint config");
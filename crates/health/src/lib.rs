@@ -43,13 +43,13 @@ use crate::limiter::{BucketLimiter, NoopLimiter, RateLimiter};
 use crate::metrics::{MetricsManager, run_metrics_server};
 use crate::processor::{
     EventProcessingPipeline, EventProcessor, HealthReportProcessor, LeakEventProcessor,
-    RackLeakProcessor,
+    RackLeakProcessor, SensorAnomalyProcessor,
 };
 use crate::sharding::ShardManager;
 use crate::sink::event_mapper::{OpenBmcEventMapper, RedfishEventMapper};
 use crate::sink::{
-    CompositeDataSink, DataSink, HealthReportSink, LogFileSink, OtlpSink, PrometheusSink,
-    RackHealthReportSink, TracingSink,
+    AlertRoutingSink, CompositeDataSink, DataSink, HealthReportSink, LogFileSink, OtlpSink,
+    PrometheusSink, RackHealthReportSink, SensorHistorySink, TracingSink,
 };
 
 #[derive(thiserror::Error, Debug)]
@@ -161,6 +161,7 @@ fn build_data_sink(
     if config.sinks.tracing.is_enabled()
         || config.sinks.health_report.is_enabled()
         || config.processors.leak_detection.is_enabled()
+        || config.sinks.alert_routing.is_enabled()
     {
         processors.push(Arc::new(HealthReportProcessor::new()));
     }
@@ -177,6 +178,12 @@ fn build_data_sink(
         )));
     }
 
+    if let Configurable::Enabled(ref sensor_anomaly_cfg) = config.processors.sensor_anomaly {
+        processors.push(Arc::new(SensorAnomalyProcessor::new(
+            sensor_anomaly_cfg.clone(),
+        )));
+    }
+
     if let Configurable::Enabled(ref sink_cfg) = config.sinks.log_file {
         sinks.push(Arc::new(
             LogFileSink::new(sink_cfg).map_err(HealthError::GenericError)?,
@@ -201,6 +208,18 @@ fn build_data_sink(
         )?));
     }
 
+    if let Configurable::Enabled(ref sink_cfg) = config.sinks.sensor_history {
+        sinks.push(Arc::new(SensorHistorySink::new(sink_cfg)?));
+    }
+
+    if let Configurable::Enabled(ref sink_cfg) = config.sinks.alert_routing {
+        sinks.push(Arc::new(AlertRoutingSink::new(
+            sink_cfg,
+            &metrics_manager,
+            &config.metrics.prefix,
+        )?));
+    }
+
     if sinks.is_empty() {
         return Ok(None);
     }
@@ -276,6 +276,7 @@ mod tests {
                     .parse()
                     .expect("valid machine id"),
                 machine_serial: None,
+                hw_sku: None,
             })),
             rack_id: None,
         }
@@ -23,9 +23,11 @@ use std::time::Instant;
 mod health_report;
 mod leak_events;
 mod rack_leak;
+mod sensor_anomaly;
 pub use health_report::HealthReportProcessor;
 pub use leak_events::LeakEventProcessor;
 pub use rack_leak::RackLeakProcessor;
+pub use sensor_anomaly::SensorAnomalyProcessor;
 
 use crate::metrics::{ComponentMetrics, MetricsManager};
 use crate::sink::{CollectorEvent, DataSink, EventContext};
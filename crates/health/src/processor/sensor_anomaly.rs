@@ -0,0 +1,504 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Early warnings for BMC sensors which behave unusually before their hardware
+//! thresholds trip.
+//!
+//! For each sensor a long-term baseline and a short-term average are learned
+//! with exponentially weighted moving averages. Three kinds of anomalies are
+//! reported:
+//! - drift: the short-term average moved away from the baseline
+//! - stuck-at: a sensor which used to change keeps reporting the same value
+//! - outlier: the short-term average deviates from the median of the same
+//!   sensor on sibling machines (same rack or SKU)
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use dashmap::DashMap;
+
+use super::{EventContext, EventProcessor};
+use crate::config::{SensorAnomalyPeerGroup, SensorAnomalyProcessorConfig};
+use crate::sink::{
+    Classification, CollectorEvent, HealthReport, HealthReportAlert, HealthReportSuccess, Probe,
+    ReportSource, SensorHealthData,
+};
+
+/// Scale factor which makes the median absolute deviation comparable to a
+/// standard deviation for normally distributed values
+const MAD_SCALE: f64 = 1.4826;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct SensorKey {
+    endpoint_key: String,
+    sensor: String,
+}
+
+/// Identifies the same sensor on sibling machines
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct PeerKey {
+    group: String,
+    metric_type: String,
+    sensor_id: String,
+}
+
+#[derive(Debug, Clone)]
+struct SensorBaseline {
+    samples: u32,
+    baseline: f64,
+    recent: f64,
+    last_value: f64,
+    /// Number of consecutive readings which equal the last value
+    unchanged: u32,
+    /// Whether the reading ever changed. Sensors which never changed are not
+    /// reported as stuck, since they may legitimately report a constant value.
+    changed: bool,
+}
+
+impl SensorBaseline {
+    fn new(value: f64) -> Self {
+        Self {
+            samples: 1,
+            baseline: value,
+            recent: value,
+            last_value: value,
+            unchanged: 1,
+            changed: false,
+        }
+    }
+
+    fn update(&mut self, value: f64, baseline_alpha: f64, recent_alpha: f64) {
+        self.samples = self.samples.saturating_add(1);
+        self.baseline += baseline_alpha * (value - self.baseline);
+        self.recent += recent_alpha * (value - self.recent);
+        if value == self.last_value {
+            self.unchanged = self.unchanged.saturating_add(1);
+        } else {
+            self.unchanged = 1;
+            self.changed = true;
+        }
+        self.last_value = value;
+    }
+}
+
+#[derive(Default)]
+struct AnomalyWindow {
+    successes: Vec<HealthReportSuccess>,
+    alerts: Vec<HealthReportAlert>,
+}
+
+pub struct SensorAnomalyProcessor {
+    config: SensorAnomalyProcessorConfig,
+    baselines: DashMap<SensorKey, SensorBaseline>,
+    /// Short-term averages of each sensor, by peer group and endpoint
+    peers: DashMap<PeerKey, HashMap<String, f64>>,
+    windows: DashMap<String, AnomalyWindow>,
+}
+
+impl SensorAnomalyProcessor {
+    pub fn new(config: SensorAnomalyProcessorConfig) -> Self {
+        Self {
+            config,
+            baselines: DashMap::new(),
+            peers: DashMap::new(),
+            windows: DashMap::new(),
+        }
+    }
+
+    fn stream_key(context: &EventContext) -> String {
+        format!("{}::{}", context.endpoint_key(), context.collector_type)
+    }
+
+    fn peer_group(&self, context: &EventContext) -> Option<String> {
+        match self.config.peer_group {
+            SensorAnomalyPeerGroup::Rack => context.rack_id().map(|rack_id| rack_id.to_string()),
+            SensorAnomalyPeerGroup::Sku => context.hw_sku().map(str::to_string),
+        }
+    }
+
+    /// Updates the baseline of a sensor and returns the anomalies of the new reading
+    fn evaluate(
+        &self,
+        context: &EventContext,
+        metric: &SensorHealthData,
+        sensor_id: &str,
+    ) -> Vec<(Classification, String)> {
+        let key = SensorKey {
+            endpoint_key: context.endpoint_key().to_string(),
+            sensor: metric.key.clone(),
+        };
+        let baseline = self
+            .baselines
+            .entry(key)
+            .and_modify(|baseline| {
+                baseline.update(
+                    metric.value,
+                    self.config.baseline_alpha,
+                    self.config.recent_alpha,
+                )
+            })
+            .or_insert_with(|| SensorBaseline::new(metric.value))
+            .clone();
+
+        let mut anomalies = Vec::new();
+
+        if baseline.samples >= self.config.warmup_samples {
+            let deviation = (baseline.recent - baseline.baseline).abs();
+            if deviation > self.config.drift_ratio * baseline.baseline.abs() {
+                anomalies.push((
+                    Classification::SensorDrift,
+                    format!(
+                        "recent average {:.2}{unit} drifted from baseline {:.2}{unit}",
+                        baseline.recent,
+                        baseline.baseline,
+                        unit = metric.unit,
+                    ),
+                ));
+            }
+        }
+
+        if baseline.changed && baseline.unchanged >= self.config.stuck_samples {
+            anomalies.push((
+                Classification::SensorStuck,
+                format!(
+                    "reading stuck at {:.2}{} for {} samples",
+                    baseline.last_value, metric.unit, baseline.unchanged
+                ),
+            ));
+        }
+
+        if let Some(group) = self.peer_group(context) {
+            let peer_key = PeerKey {
+                group,
+                metric_type: metric.metric_type.clone(),
+                sensor_id: sensor_id.to_string(),
+            };
+            let peer_values: Vec<f64> = {
+                let mut peers = self.peers.entry(peer_key).or_default();
+                peers.insert(context.endpoint_key().to_string(), baseline.recent);
+                peers
+                    .iter()
+                    .filter(|(endpoint_key, _)| endpoint_key.as_str() != context.endpoint_key())
+                    .map(|(_, value)| *value)
+                    .collect()
+            };
+
+            if baseline.samples >= self.config.warmup_samples
+                && peer_values.len() >= self.config.min_peers
+                && let Some(message) = self.outlier(baseline.recent, peer_values, &metric.unit)
+            {
+                anomalies.push((Classification::SensorOutlier, message));
+            }
+        }
+
+        anomalies
+    }
+
+    fn outlier(&self, value: f64, mut peer_values: Vec<f64>, unit: &str) -> Option<String> {
+        let peer_median = median(&mut peer_values);
+        let mut deviations: Vec<f64> = peer_values
+            .iter()
+            .map(|peer| (peer - peer_median).abs())
+            .collect();
+        let mad = median(&mut deviations) * MAD_SCALE;
+
+        let deviation = (value - peer_median).abs();
+        if deviation <= self.config.outlier_min_ratio * peer_median.abs() {
+            return None;
+        }
+        // Peers which agree perfectly make every deviation beyond the minimum ratio an outlier
+        if mad > 0.0 && deviation / mad <= self.config.outlier_threshold {
+            return None;
+        }
+
+        Some(format!(
+            "recent average {value:.2}{unit} deviates from the median {peer_median:.2}{unit} of {} sibling machines",
+            peer_values.len()
+        ))
+    }
+
+    fn remove_endpoint(&self, endpoint_key: &str) {
+        self.baselines
+            .retain(|key, _| key.endpoint_key != endpoint_key);
+        self.peers.retain(|_, peers| {
+            peers.remove(endpoint_key);
+            !peers.is_empty()
+        });
+    }
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+impl EventProcessor for SensorAnomalyProcessor {
+    fn processor_type(&self) -> &'static str {
+        "sensor_anomaly_processor"
+    }
+
+    fn process_event(&self, context: &EventContext, event: &CollectorEvent) -> Vec<CollectorEvent> {
+        match event {
+            CollectorEvent::MetricCollectionStart => {
+                self.windows
+                    .insert(Self::stream_key(context), AnomalyWindow::default());
+            }
+            CollectorEvent::Metric(metric) => {
+                let Some(health) = metric.context.as_ref() else {
+                    return Vec::new();
+                };
+                if !metric.value.is_finite()
+                    || !self
+                        .config
+                        .reading_types
+                        .iter()
+                        .any(|reading_type| *reading_type == metric.metric_type)
+                {
+                    return Vec::new();
+                }
+
+                let anomalies = self.evaluate(context, metric, &health.sensor_id);
+
+                let mut window = self.windows.entry(Self::stream_key(context)).or_default();
+                if anomalies.is_empty() {
+                    window.successes.push(HealthReportSuccess {
+                        probe_id: Probe::SensorAnomaly,
+                        target: Some(health.sensor_id.clone()),
+                    });
+                } else {
+                    let (classifications, messages): (Vec<_>, Vec<_>) =
+                        anomalies.into_iter().unzip();
+                    window.alerts.push(HealthReportAlert {
+                        probe_id: Probe::SensorAnomaly,
+                        target: Some(health.sensor_id.clone()),
+                        message: format!(
+                            "{} '{}': {}",
+                            metric.metric_type,
+                            health.sensor_id,
+                            messages.join("; ")
+                        ),
+                        classifications,
+                    });
+                }
+            }
+            CollectorEvent::MetricCollectionEnd => {
+                let Some((_, window)) = self.windows.remove(&Self::stream_key(context)) else {
+                    return Vec::new();
+                };
+                if window.successes.is_empty() && window.alerts.is_empty() {
+                    return Vec::new();
+                }
+
+                return vec![CollectorEvent::HealthReport(Arc::new(HealthReport {
+                    source: ReportSource::SensorAnomalyDetection,
+                    observed_at: Some(chrono::Utc::now()),
+                    successes: window.successes,
+                    alerts: window.alerts,
+                }))];
+            }
+            CollectorEvent::CollectorRemoved => {
+                self.windows.remove(&Self::stream_key(context));
+                self.remove_endpoint(context.endpoint_key());
+            }
+            CollectorEvent::Log(_)
+            | CollectorEvent::Firmware(_)
+            | CollectorEvent::HealthReport(_) => {}
+        }
+
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::str::FromStr;
+
+    use carbide_uuid::rack::RackId;
+    use mac_address::MacAddress;
+    use nv_redfish::resource::Health as BmcHealth;
+
+    use super::*;
+    use crate::endpoint::BmcAddr;
+    use crate::sink::SensorHealthContext;
+
+    fn context(endpoint: &str) -> EventContext {
+        EventContext {
+            endpoint_key: endpoint.to_string(),
+            addr: BmcAddr {
+                ip: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+                port: Some(443),
+                mac: MacAddress::from_str("42:9e:b1:bd:9d:dd").expect("valid mac"),
+            },
+            collector_type: "sensor_collector",
+            metadata: None,
+            rack_id: Some(RackId::new("rack-1")),
+        }
+    }
+
+    fn reading(value: f64) -> CollectorEvent {
+        CollectorEvent::Metric(
+            SensorHealthData {
+                key: "/redfish/v1/Chassis/1/Sensors/Fan1".to_string(),
+                name: "hw_sensor".to_string(),
+                metric_type: "rotational".to_string(),
+                unit: "rpm".to_string(),
+                value,
+                labels: vec![],
+                context: Some(SensorHealthContext {
+                    entity_type: "sensor".to_string(),
+                    sensor_id: "Fan1".to_string(),
+                    upper_fatal: None,
+                    lower_fatal: None,
+                    upper_critical: None,
+                    lower_critical: None,
+                    upper_caution: None,
+                    lower_caution: None,
+                    range_max: None,
+                    range_min: None,
+                    bmc_health: BmcHealth::Ok,
+                }),
+            }
+            .into(),
+        )
+    }
+
+    fn config() -> SensorAnomalyProcessorConfig {
+        SensorAnomalyProcessorConfig {
+            warmup_samples: 5,
+            stuck_samples: 5,
+            min_peers: 3,
+            ..Default::default()
+        }
+    }
+
+    /// Runs a collection iteration with a single reading and returns the emitted report
+    fn collect(
+        processor: &SensorAnomalyProcessor,
+        context: &EventContext,
+        value: f64,
+    ) -> Arc<HealthReport> {
+        let _ = processor.process_event(context, &CollectorEvent::MetricCollectionStart);
+        let _ = processor.process_event(context, &reading(value));
+        let emitted = processor.process_event(context, &CollectorEvent::MetricCollectionEnd);
+        let Some(CollectorEvent::HealthReport(report)) = emitted.into_iter().next() else {
+            panic!("expected health report event");
+        };
+        report
+    }
+
+    fn classifications(report: &HealthReport) -> Vec<Classification> {
+        report
+            .alerts
+            .iter()
+            .flat_map(|alert| alert.classifications.iter().copied())
+            .collect()
+    }
+
+    #[test]
+    fn stable_sensor_reports_success() {
+        let processor = SensorAnomalyProcessor::new(config());
+        let context = context("a");
+
+        for value in [5000.0, 5010.0, 4990.0, 5005.0, 4995.0, 5000.0] {
+            let report = collect(&processor, &context, value);
+            assert_eq!(report.source, ReportSource::SensorAnomalyDetection);
+            assert!(report.alerts.is_empty());
+            assert_eq!(report.successes.len(), 1);
+        }
+    }
+
+    #[test]
+    fn drift_from_baseline_is_reported() {
+        let processor = SensorAnomalyProcessor::new(config());
+        let context = context("a");
+
+        for value in [5000.0, 5010.0, 4990.0, 5005.0, 4995.0] {
+            collect(&processor, &context, value);
+        }
+        let mut report = collect(&processor, &context, 2000.0);
+        for _ in 0..3 {
+            report = collect(&processor, &context, 2000.0);
+        }
+        assert!(classifications(&report).contains(&Classification::SensorDrift));
+    }
+
+    #[test]
+    fn stuck_sensor_is_reported() {
+        let processor = SensorAnomalyProcessor::new(config());
+        let context = context("a");
+
+        collect(&processor, &context, 5010.0);
+        let mut report = collect(&processor, &context, 5000.0);
+        for _ in 0..4 {
+            assert!(report.alerts.is_empty());
+            report = collect(&processor, &context, 5000.0);
+        }
+        assert_eq!(classifications(&report), vec![Classification::SensorStuck]);
+    }
+
+    #[test]
+    fn constant_sensor_is_not_stuck() {
+        let processor = SensorAnomalyProcessor::new(config());
+        let context = context("a");
+
+        for _ in 0..10 {
+            let report = collect(&processor, &context, 0.0);
+            assert!(report.alerts.is_empty());
+        }
+    }
+
+    #[test]
+    fn outlier_among_siblings_is_reported() {
+        let processor = SensorAnomalyProcessor::new(config());
+        let siblings = [("a", 5000.0), ("b", 5100.0), ("c", 4950.0), ("d", 9000.0)];
+
+        let mut reports = HashMap::new();
+        for i in 0..6 {
+            for (endpoint, value) in siblings {
+                // Alternate the value slightly to avoid stuck alerts
+                let value = value + (i % 2) as f64;
+                reports.insert(endpoint, collect(&processor, &context(endpoint), value));
+            }
+        }
+
+        assert!(reports["a"].alerts.is_empty());
+        assert_eq!(
+            classifications(&reports["d"]),
+            vec![Classification::SensorOutlier]
+        );
+    }
+
+    #[test]
+    fn collector_removed_forgets_endpoint() {
+        let processor = SensorAnomalyProcessor::new(config());
+        let context = context("a");
+
+        collect(&processor, &context, 5000.0);
+        assert_eq!(processor.baselines.len(), 1);
+        assert_eq!(processor.peers.len(), 1);
+
+        let _ = processor.process_event(&context, &CollectorEvent::CollectorRemoved);
+        assert!(processor.baselines.is_empty());
+        assert!(processor.peers.is_empty());
+    }
+}
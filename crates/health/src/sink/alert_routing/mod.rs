@@ -0,0 +1,146 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod receiver;
+mod router;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use chrono::Utc;
+use prometheus::{IntCounterVec, Opts};
+
+use self::receiver::AlertReceiver;
+use self::router::AlertRouter;
+use super::{CollectorEvent, DataSink, EventContext};
+use crate::HealthError;
+use crate::config::AlertRoutingSinkConfig;
+use crate::metrics::MetricsManager;
+
+/// Routes health report alerts to receivers which notify humans.
+///
+/// Alerts are grouped, inhibited and silenced according to the configured
+/// rules. Notifications are sent by a background task, so handling events
+/// never waits on a receiver.
+pub struct AlertRoutingSink {
+    router: Arc<Mutex<AlertRouter>>,
+}
+
+impl AlertRoutingSink {
+    pub fn new(
+        config: &AlertRoutingSinkConfig,
+        metrics_manager: &MetricsManager,
+        prefix: &str,
+    ) -> Result<Self, HealthError> {
+        let handle = tokio::runtime::Handle::try_current().map_err(|e| {
+            HealthError::GenericError(format!(
+                "alert routing sink requires active tokio runtime: {e}"
+            ))
+        })?;
+
+        let notifications_total = IntCounterVec::new(
+            Opts::new(
+                format!("{prefix}_alert_routing_notifications_total"),
+                "total alert notifications delivered to receivers",
+            ),
+            &["receiver", "result"],
+        )?;
+        metrics_manager
+            .global_registry()
+            .register(Box::new(notifications_total.clone()))?;
+
+        let receivers = config
+            .receivers
+            .iter()
+            .map(|receiver_cfg| {
+                AlertReceiver::new(receiver_cfg, config.delivery_timeout)
+                    .map(|receiver| (receiver.name.clone(), Arc::new(receiver)))
+            })
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        let router = Arc::new(Mutex::new(AlertRouter::new(config)));
+
+        let evaluation_router = Arc::clone(&router);
+        let evaluation_interval = config.evaluation_interval;
+        handle.spawn(async move {
+            let mut interval = tokio::time::interval(evaluation_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+
+                let notifications = match evaluation_router.lock() {
+                    Ok(mut router) => router.flush(Instant::now(), Utc::now()),
+                    Err(_) => {
+                        tracing::error!("alert router lock poisoned");
+                        return;
+                    }
+                };
+
+                for notification in notifications {
+                    let Some(receiver) = receivers.get(&notification.receiver) else {
+                        continue;
+                    };
+                    let receiver = Arc::clone(receiver);
+                    let notifications_total = notifications_total.clone();
+                    tokio::spawn(async move {
+                        let result = match receiver.deliver(&notification).await {
+                            Ok(()) => "success",
+                            Err(error) => {
+                                tracing::warn!(
+                                    ?error,
+                                    receiver = %receiver.name,
+                                    group_key = %notification.group_key,
+                                    "failed to deliver alert notification"
+                                );
+                                "failure"
+                            }
+                        };
+                        notifications_total
+                            .with_label_values(&[receiver.name.as_str(), result])
+                            .inc();
+                    });
+                }
+            }
+        });
+
+        Ok(Self { router })
+    }
+}
+
+impl DataSink for AlertRoutingSink {
+    fn sink_type(&self) -> &'static str {
+        "alert_routing_sink"
+    }
+
+    fn handle_event(&self, context: &EventContext, event: &CollectorEvent) {
+        let Ok(mut router) = self.router.lock() else {
+            tracing::error!("alert router lock poisoned");
+            return;
+        };
+
+        match event {
+            CollectorEvent::HealthReport(report) => {
+                router.observe(context, report, Instant::now(), Utc::now());
+            }
+            CollectorEvent::CollectorRemoved => {
+                router.collector_removed(context, Utc::now());
+            }
+            _ => {}
+        }
+    }
+}
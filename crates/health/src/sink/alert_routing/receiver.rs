@@ -0,0 +1,332 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Delivery of alert notifications to receivers.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
+use serde::Serialize;
+use url::Url;
+
+use super::router::{AlertStatus, Labels, Notification, RoutedAlert};
+use crate::HealthError;
+use crate::config::{AlertReceiverConfig, AlertReceiverKind};
+
+pub(crate) struct AlertReceiver {
+    pub name: String,
+    kind: ReceiverKind,
+}
+
+enum ReceiverKind {
+    Webhook {
+        client: reqwest::Client,
+        url: Url,
+        headers: HeaderMap,
+    },
+    /// Alertmanager resolves alerts which were not re-sent within its
+    /// `resolve_timeout`, so `repeat_interval` should be shorter than that.
+    Alertmanager {
+        client: reqwest::Client,
+        url: Url,
+    },
+    File {
+        path: PathBuf,
+        lock: Mutex<()>,
+    },
+}
+
+impl AlertReceiver {
+    pub fn new(config: &AlertReceiverConfig, timeout: Duration) -> Result<Self, HealthError> {
+        let client = || {
+            reqwest::Client::builder()
+                .timeout(timeout)
+                .build()
+                .map_err(|e| {
+                    HealthError::GenericError(format!(
+                        "alert receiver {}: failed to create HTTP client: {e}",
+                        config.name
+                    ))
+                })
+        };
+
+        let kind = match &config.kind {
+            AlertReceiverKind::Webhook { url, headers } => {
+                let mut header_map = HeaderMap::new();
+                for (name, value) in headers {
+                    let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+                        HealthError::GenericError(format!(
+                            "alert receiver {}: invalid header name {name}: {e}",
+                            config.name
+                        ))
+                    })?;
+                    let value = HeaderValue::from_str(value).map_err(|e| {
+                        HealthError::GenericError(format!(
+                            "alert receiver {}: invalid value for header {name}: {e}",
+                            config.name
+                        ))
+                    })?;
+                    header_map.insert(name, value);
+                }
+                ReceiverKind::Webhook {
+                    client: client()?,
+                    url: url.clone(),
+                    headers: header_map,
+                }
+            }
+            AlertReceiverKind::Alertmanager { url } => {
+                let url = Url::parse(&format!(
+                    "{}/api/v2/alerts",
+                    url.as_str().trim_end_matches('/')
+                ))
+                .map_err(|e| {
+                    HealthError::GenericError(format!(
+                        "alert receiver {}: invalid Alertmanager URL {url}: {e}",
+                        config.name
+                    ))
+                })?;
+                ReceiverKind::Alertmanager {
+                    client: client()?,
+                    url,
+                }
+            }
+            AlertReceiverKind::File { path } => ReceiverKind::File {
+                path: PathBuf::from(path),
+                lock: Mutex::new(()),
+            },
+        };
+
+        Ok(Self {
+            name: config.name.clone(),
+            kind,
+        })
+    }
+
+    pub async fn deliver(&self, notification: &Notification) -> Result<(), HealthError> {
+        match &self.kind {
+            ReceiverKind::Webhook {
+                client,
+                url,
+                headers,
+            } => {
+                let body = serde_json::to_vec(&WebhookMessage::new(notification))?;
+                let request = client.post(url.as_str()).headers(headers.clone());
+                post_json(request, url, body).await
+            }
+            ReceiverKind::Alertmanager { client, url } => {
+                let alerts: Vec<PostableAlert> = notification
+                    .alerts
+                    .iter()
+                    .map(PostableAlert::from)
+                    .collect();
+                let body = serde_json::to_vec(&alerts)?;
+                post_json(client.post(url.as_str()), url, body).await
+            }
+            ReceiverKind::File { path, lock } => {
+                let mut line = serde_json::to_string(&WebhookMessage::new(notification))?;
+                line.push('\n');
+
+                let _guard = lock
+                    .lock()
+                    .map_err(|_| HealthError::GenericError("alert file lock poisoned".into()))?;
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .and_then(|mut file| file.write_all(line.as_bytes()))
+                    .map_err(|e| {
+                        HealthError::GenericError(format!(
+                            "failed to write notification to {}: {e}",
+                            path.display()
+                        ))
+                    })
+            }
+        }
+    }
+}
+
+async fn post_json(
+    request: reqwest::RequestBuilder,
+    url: &Url,
+    body: Vec<u8>,
+) -> Result<(), HealthError> {
+    let response = request
+        .header(CONTENT_TYPE, "application/json")
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| HealthError::HttpError(format!("{url}: request failed: {e}")))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(HealthError::HttpError(format!(
+            "{url}: HTTP {status}: {body}"
+        )));
+    }
+    Ok(())
+}
+
+/// Notification payload compatible with the Alertmanager webhook format
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookMessage<'a> {
+    version: &'static str,
+    receiver: &'a str,
+    status: AlertStatus,
+    group_key: &'a str,
+    group_labels: &'a Labels,
+    common_labels: Labels,
+    alerts: Vec<WebhookAlert<'a>>,
+}
+
+impl<'a> WebhookMessage<'a> {
+    fn new(notification: &'a Notification) -> Self {
+        Self {
+            version: "4",
+            receiver: &notification.receiver,
+            status: notification.status(),
+            group_key: &notification.group_key,
+            group_labels: &notification.group_labels,
+            common_labels: notification.common_labels(),
+            alerts: notification.alerts.iter().map(WebhookAlert::from).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookAlert<'a> {
+    status: AlertStatus,
+    labels: &'a Labels,
+    annotations: Annotations<'a>,
+    starts_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ends_at: Option<DateTime<Utc>>,
+    fingerprint: &'a str,
+}
+
+impl<'a> From<&'a RoutedAlert> for WebhookAlert<'a> {
+    fn from(alert: &'a RoutedAlert) -> Self {
+        Self {
+            status: alert.status(),
+            labels: &alert.labels,
+            annotations: Annotations {
+                summary: &alert.summary,
+            },
+            starts_at: alert.starts_at,
+            ends_at: alert.ends_at,
+            fingerprint: &alert.fingerprint,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Annotations<'a> {
+    summary: &'a str,
+}
+
+/// Alert in the format of the Alertmanager v2 API
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PostableAlert<'a> {
+    labels: &'a Labels,
+    annotations: Annotations<'a>,
+    starts_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ends_at: Option<DateTime<Utc>>,
+}
+
+impl<'a> From<&'a RoutedAlert> for PostableAlert<'a> {
+    fn from(alert: &'a RoutedAlert) -> Self {
+        Self {
+            labels: &alert.labels,
+            annotations: Annotations {
+                summary: &alert.summary,
+            },
+            starts_at: alert.starts_at,
+            ends_at: alert.ends_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification() -> Notification {
+        let labels = Labels::from([
+            ("alertname".to_string(), "leak".to_string()),
+            ("classification".to_string(), "Leak".to_string()),
+        ]);
+        Notification {
+            receiver: "oncall".to_string(),
+            group_key: "oncall/leak".to_string(),
+            group_labels: Labels::from([("alertname".to_string(), "leak".to_string())]),
+            alerts: vec![RoutedAlert {
+                fingerprint: "0123456789abcdef".to_string(),
+                labels,
+                summary: "leak detected".to_string(),
+                starts_at: Utc::now(),
+                ends_at: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn webhook_message_format() {
+        let notification = notification();
+        let json = serde_json::to_value(WebhookMessage::new(&notification)).unwrap();
+
+        assert_eq!(json["version"], "4");
+        assert_eq!(json["status"], "firing");
+        assert_eq!(json["groupKey"], "oncall/leak");
+        assert_eq!(json["commonLabels"]["classification"], "Leak");
+        assert_eq!(json["alerts"][0]["annotations"]["summary"], "leak detected");
+        assert!(json["alerts"][0].get("endsAt").is_none());
+    }
+
+    #[tokio::test]
+    async fn file_receiver_appends_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("alerts.jsonl");
+        let receiver = AlertReceiver::new(
+            &AlertReceiverConfig {
+                name: "file".to_string(),
+                kind: AlertReceiverKind::File {
+                    path: path.display().to_string(),
+                },
+            },
+            Duration::from_secs(1),
+        )
+        .unwrap();
+
+        receiver.deliver(&notification()).await.unwrap();
+        receiver.deliver(&notification()).await.unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 2);
+        let line: serde_json::Value =
+            serde_json::from_str(contents.lines().next().unwrap()).unwrap();
+        assert_eq!(line["receiver"], "oncall");
+    }
+}
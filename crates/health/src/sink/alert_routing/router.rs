@@ -0,0 +1,647 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Evaluation of alert routes, inhibitions and silences, and grouping of
+//! routed alerts into notifications.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::config::{
+    AlertInhibitRuleConfig, AlertRouteConfig, AlertRoutingSinkConfig, AlertSilenceConfig,
+};
+use crate::sink::{EventContext, HealthReport, ReportSource};
+
+pub(crate) type Labels = BTreeMap<String, String>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum AlertStatus {
+    Firing,
+    Resolved,
+}
+
+/// A health report alert which was selected by a route
+#[derive(Debug, Clone)]
+pub(crate) struct RoutedAlert {
+    pub fingerprint: String,
+    pub labels: Labels,
+    pub summary: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+impl RoutedAlert {
+    pub fn status(&self) -> AlertStatus {
+        match self.ends_at {
+            Some(_) => AlertStatus::Resolved,
+            None => AlertStatus::Firing,
+        }
+    }
+}
+
+/// A notification about a group of alerts which should be delivered to a receiver
+#[derive(Debug, Clone)]
+pub(crate) struct Notification {
+    pub receiver: String,
+    pub group_key: String,
+    pub group_labels: Labels,
+    pub alerts: Vec<RoutedAlert>,
+}
+
+impl Notification {
+    /// A notification is firing as long as any of its alerts is firing
+    pub fn status(&self) -> AlertStatus {
+        if self
+            .alerts
+            .iter()
+            .any(|alert| alert.status() == AlertStatus::Firing)
+        {
+            AlertStatus::Firing
+        } else {
+            AlertStatus::Resolved
+        }
+    }
+
+    /// Labels which all alerts of the notification share
+    pub fn common_labels(&self) -> Labels {
+        let mut alerts = self.alerts.iter();
+        let Some(first) = alerts.next() else {
+            return Labels::new();
+        };
+        let mut common = first.labels.clone();
+        for alert in alerts {
+            common.retain(|key, value| alert.labels.get(key) == Some(value));
+        }
+        common
+    }
+}
+
+/// Identifies the stream of health reports which raised an alert
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ReportKey {
+    endpoint_key: String,
+    collector_type: &'static str,
+    source: ReportSource,
+}
+
+struct GroupState {
+    receiver: String,
+    group_labels: Labels,
+    fingerprints: HashSet<String>,
+    created: Instant,
+    last_sent: Option<Instant>,
+    /// The firing alerts which were part of the last notification
+    last_sent_firing: BTreeSet<String>,
+}
+
+pub(crate) struct AlertRouter {
+    routes: Vec<AlertRouteConfig>,
+    inhibit_rules: Vec<AlertInhibitRuleConfig>,
+    silences: Vec<AlertSilenceConfig>,
+    group_wait: Duration,
+    group_interval: Duration,
+    repeat_interval: Duration,
+    alerts: HashMap<String, RoutedAlert>,
+    /// The alerts raised by the latest report of each report stream
+    reports: HashMap<ReportKey, HashSet<String>>,
+    groups: HashMap<String, GroupState>,
+}
+
+impl AlertRouter {
+    pub fn new(config: &AlertRoutingSinkConfig) -> Self {
+        Self {
+            routes: config.routes.clone(),
+            inhibit_rules: config.inhibit_rules.clone(),
+            silences: config.silences.clone(),
+            group_wait: config.group_wait,
+            group_interval: config.group_interval,
+            repeat_interval: config.repeat_interval,
+            alerts: HashMap::new(),
+            reports: HashMap::new(),
+            groups: HashMap::new(),
+        }
+    }
+
+    /// Routes the alerts of a health report. Alerts which were raised by the
+    /// previous report of the same stream but are missing now get resolved.
+    pub fn observe(
+        &mut self,
+        context: &EventContext,
+        report: &HealthReport,
+        now: Instant,
+        now_utc: DateTime<Utc>,
+    ) {
+        let mut raised = HashSet::new();
+
+        for alert in &report.alerts {
+            let mut base = Labels::new();
+            base.insert("endpoint".to_string(), context.endpoint_key.clone());
+            base.insert("source".to_string(), report.source.as_str().to_string());
+            base.insert("probe".to_string(), alert.probe_id.as_str().to_string());
+            if let Some(target) = &alert.target {
+                base.insert("target".to_string(), target.clone());
+            }
+            if let Some(machine_id) = context.machine_id() {
+                base.insert("machine_id".to_string(), machine_id.to_string());
+            }
+            if let Some(rack_id) = context.rack_id() {
+                base.insert("rack_id".to_string(), rack_id.to_string());
+            }
+            if let Some(serial_number) = context.serial_number() {
+                base.insert("serial_number".to_string(), serial_number.to_string());
+            }
+
+            for classification in &alert.classifications {
+                let mut labels = base.clone();
+                labels.insert(
+                    "classification".to_string(),
+                    classification.as_str().to_string(),
+                );
+
+                for route_idx in 0..self.routes.len() {
+                    if !route_matches(&self.routes[route_idx], &labels) {
+                        continue;
+                    }
+                    let fingerprint = self.raise(
+                        route_idx,
+                        labels.clone(),
+                        &alert.message,
+                        report.observed_at.unwrap_or(now_utc),
+                        now,
+                    );
+                    raised.insert(fingerprint);
+                    if !self.routes[route_idx].continue_matching {
+                        break;
+                    }
+                }
+            }
+        }
+
+        let key = ReportKey {
+            endpoint_key: context.endpoint_key.clone(),
+            collector_type: context.collector_type,
+            source: report.source,
+        };
+        let previous = if raised.is_empty() {
+            self.reports.remove(&key)
+        } else {
+            self.reports.insert(key, raised.clone())
+        };
+        for fingerprint in previous.unwrap_or_default().difference(&raised) {
+            self.resolve(fingerprint, now_utc);
+        }
+    }
+
+    /// Resolves all alerts which were raised by reports of a removed collector
+    pub fn collector_removed(&mut self, context: &EventContext, now_utc: DateTime<Utc>) {
+        let removed: Vec<ReportKey> = self
+            .reports
+            .keys()
+            .filter(|key| {
+                key.endpoint_key == context.endpoint_key
+                    && key.collector_type == context.collector_type
+            })
+            .cloned()
+            .collect();
+        for key in removed {
+            for fingerprint in self.reports.remove(&key).unwrap_or_default() {
+                self.resolve(&fingerprint, now_utc);
+            }
+        }
+    }
+
+    fn raise(
+        &mut self,
+        route_idx: usize,
+        mut labels: Labels,
+        summary: &str,
+        starts_at: DateTime<Utc>,
+        now: Instant,
+    ) -> String {
+        let route = &self.routes[route_idx];
+        labels.insert("alertname".to_string(), route.name.clone());
+        labels.insert("severity".to_string(), route.severity.clone());
+        let fingerprint = fingerprint(&labels);
+
+        let alert = self
+            .alerts
+            .entry(fingerprint.clone())
+            .or_insert_with(|| RoutedAlert {
+                fingerprint: fingerprint.clone(),
+                labels: labels.clone(),
+                summary: summary.to_string(),
+                starts_at,
+                ends_at: None,
+            });
+        if alert.ends_at.is_some() {
+            // The alert fires again after it had been resolved
+            alert.starts_at = starts_at;
+            alert.ends_at = None;
+        }
+        alert.summary = summary.to_string();
+
+        for receiver in &route.receivers {
+            let group_labels: Labels = route
+                .group_by
+                .iter()
+                .filter_map(|name| labels.get(name).map(|value| (name.clone(), value.clone())))
+                .collect();
+            let group_key = format!("{receiver}/{}:{group_labels:?}", route.name);
+            self.groups
+                .entry(group_key)
+                .or_insert_with(|| GroupState {
+                    receiver: receiver.clone(),
+                    group_labels,
+                    fingerprints: HashSet::new(),
+                    created: now,
+                    last_sent: None,
+                    last_sent_firing: BTreeSet::new(),
+                })
+                .fingerprints
+                .insert(fingerprint.clone());
+        }
+
+        fingerprint
+    }
+
+    fn resolve(&mut self, fingerprint: &str, now_utc: DateTime<Utc>) {
+        if let Some(alert) = self.alerts.get_mut(fingerprint)
+            && alert.ends_at.is_none()
+        {
+            alert.ends_at = Some(now_utc);
+        }
+    }
+
+    /// Returns the notifications which are due at time `now`
+    pub fn flush(&mut self, now: Instant, now_utc: DateTime<Utc>) -> Vec<Notification> {
+        let muted = self.muted_alerts(now_utc);
+        let mut notifications = Vec::new();
+
+        for (group_key, group) in self.groups.iter_mut() {
+            let mut firing = BTreeSet::new();
+            let mut resolved = Vec::new();
+            for fingerprint in &group.fingerprints {
+                let Some(alert) = self.alerts.get(fingerprint) else {
+                    continue;
+                };
+                match alert.status() {
+                    AlertStatus::Firing if !muted.contains(fingerprint) => {
+                        firing.insert(fingerprint.clone());
+                    }
+                    AlertStatus::Firing => {}
+                    AlertStatus::Resolved => resolved.push(fingerprint.clone()),
+                }
+            }
+            // Only report resolved alerts the receiver knows about
+            let resolved_notify: Vec<&String> = resolved
+                .iter()
+                .filter(|fingerprint| group.last_sent_firing.contains(*fingerprint))
+                .collect();
+
+            let due = match group.last_sent {
+                None => !firing.is_empty() && now.duration_since(group.created) >= self.group_wait,
+                Some(last_sent) => {
+                    let elapsed = now.duration_since(last_sent);
+                    if firing != group.last_sent_firing {
+                        elapsed >= self.group_interval
+                    } else {
+                        !firing.is_empty() && elapsed >= self.repeat_interval
+                    }
+                }
+            };
+
+            if due {
+                let alerts = firing
+                    .iter()
+                    .chain(resolved_notify.iter().copied())
+                    .filter_map(|fingerprint| self.alerts.get(fingerprint).cloned())
+                    .collect();
+                notifications.push(Notification {
+                    receiver: group.receiver.clone(),
+                    group_key: group_key.clone(),
+                    group_labels: group.group_labels.clone(),
+                    alerts,
+                });
+                group.last_sent = Some(now);
+                group.last_sent_firing = firing;
+            }
+            if due || resolved_notify.is_empty() {
+                for fingerprint in &resolved {
+                    group.fingerprints.remove(fingerprint);
+                }
+            }
+        }
+
+        self.groups.retain(|_, group| {
+            !group.fingerprints.is_empty() || !group.last_sent_firing.is_empty()
+        });
+        let referenced: HashSet<&String> = self
+            .groups
+            .values()
+            .flat_map(|group| group.fingerprints.iter())
+            .collect();
+        self.alerts.retain(|fingerprint, alert| {
+            alert.status() == AlertStatus::Firing || referenced.contains(fingerprint)
+        });
+
+        notifications
+    }
+
+    /// Returns the firing alerts which are silenced or inhibited
+    fn muted_alerts(&self, now_utc: DateTime<Utc>) -> HashSet<String> {
+        let firing: Vec<&RoutedAlert> = self
+            .alerts
+            .values()
+            .filter(|alert| alert.status() == AlertStatus::Firing)
+            .collect();
+
+        firing
+            .iter()
+            .filter(|alert| {
+                let silenced = self.silences.iter().any(|silence| {
+                    silence.starts_at <= now_utc
+                        && now_utc < silence.ends_at
+                        && labels_match(&silence.matchers, &alert.labels)
+                });
+                let inhibited = self.inhibit_rules.iter().any(|rule| {
+                    labels_match(&rule.target_matchers, &alert.labels)
+                        && firing.iter().any(|source| {
+                            source.fingerprint != alert.fingerprint
+                                && labels_match(&rule.source_matchers, &source.labels)
+                                && rule.equal.iter().all(|label| {
+                                    source.labels.get(label) == alert.labels.get(label)
+                                })
+                        })
+                });
+                silenced || inhibited
+            })
+            .map(|alert| alert.fingerprint.clone())
+            .collect()
+    }
+}
+
+fn route_matches(route: &AlertRouteConfig, labels: &Labels) -> bool {
+    let any_of = |values: &[String], label: &str| {
+        values.is_empty()
+            || labels
+                .get(label)
+                .is_some_and(|value| values.iter().any(|v| v == value))
+    };
+    any_of(&route.classifications, "classification")
+        && any_of(&route.sources, "source")
+        && labels_match(&route.matchers, labels)
+}
+
+fn labels_match(matchers: &HashMap<String, String>, labels: &Labels) -> bool {
+    matchers
+        .iter()
+        .all(|(name, value)| labels.get(name) == Some(value))
+}
+
+fn fingerprint(labels: &Labels) -> String {
+    let mut hasher = DefaultHasher::new();
+    labels.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use carbide_uuid::rack::RackId;
+    use mac_address::MacAddress;
+
+    use super::*;
+    use crate::config::AlertRouteConfig;
+    use crate::endpoint::BmcAddr;
+    use crate::sink::{Classification, HealthReportAlert, Probe};
+
+    fn context(endpoint: &str, rack_id: &str) -> EventContext {
+        EventContext {
+            endpoint_key: endpoint.to_string(),
+            addr: BmcAddr {
+                ip: "10.0.0.1".parse().expect("valid ip"),
+                port: Some(443),
+                mac: MacAddress::from_str("42:9e:b1:bd:9d:dd").unwrap(),
+            },
+            collector_type: "sensor_collector",
+            metadata: None,
+            rack_id: Some(RackId::new(rack_id)),
+        }
+    }
+
+    fn report(classifications: &[Classification]) -> HealthReport {
+        HealthReport {
+            source: ReportSource::BmcSensors,
+            observed_at: None,
+            successes: Vec::new(),
+            alerts: classifications
+                .iter()
+                .map(|classification| HealthReportAlert {
+                    probe_id: Probe::Sensor,
+                    target: Some(format!("{classification:?}")),
+                    message: format!("{classification:?} detected"),
+                    classifications: vec![*classification],
+                })
+                .collect(),
+        }
+    }
+
+    fn route(name: &str, classifications: &[&str]) -> AlertRouteConfig {
+        AlertRouteConfig {
+            name: name.to_string(),
+            classifications: classifications.iter().map(|c| c.to_string()).collect(),
+            sources: Vec::new(),
+            matchers: HashMap::new(),
+            severity: "critical".to_string(),
+            receivers: vec!["oncall".to_string()],
+            group_by: vec!["alertname".to_string(), "rack_id".to_string()],
+            continue_matching: false,
+        }
+    }
+
+    fn router(routes: Vec<AlertRouteConfig>) -> AlertRouter {
+        AlertRouter::new(&AlertRoutingSinkConfig {
+            group_wait: Duration::from_secs(30),
+            group_interval: Duration::from_secs(300),
+            repeat_interval: Duration::from_secs(3600),
+            routes,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn groups_alerts_and_waits_for_group_wait() {
+        let mut router = router(vec![route("leak", &["Leak"])]);
+        let start = Instant::now();
+        let now_utc = Utc::now();
+
+        router.observe(
+            &context("a", "rack-1"),
+            &report(&[Classification::Leak, Classification::SensorCritical]),
+            start,
+            now_utc,
+        );
+        router.observe(
+            &context("b", "rack-1"),
+            &report(&[Classification::Leak]),
+            start,
+            now_utc,
+        );
+        router.observe(
+            &context("c", "rack-2"),
+            &report(&[Classification::Leak]),
+            start,
+            now_utc,
+        );
+
+        assert!(router.flush(start, now_utc).is_empty());
+
+        let mut notifications = router.flush(start + Duration::from_secs(30), now_utc);
+        notifications.sort_by(|a, b| a.group_key.cmp(&b.group_key));
+        assert_eq!(notifications.len(), 2);
+        assert_eq!(notifications[0].alerts.len(), 2);
+        assert_eq!(notifications[0].group_labels["rack_id"], "rack-1");
+        assert_eq!(notifications[0].common_labels()["classification"], "Leak");
+        assert_eq!(notifications[1].alerts.len(), 1);
+
+        // Nothing changed, so nothing is sent until the repeat interval elapsed
+        assert!(
+            router
+                .flush(start + Duration::from_secs(600), now_utc)
+                .is_empty()
+        );
+        assert_eq!(
+            router
+                .flush(start + Duration::from_secs(3630), now_utc)
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn notifies_about_resolved_alerts() {
+        let mut router = router(vec![route("leak", &["Leak"])]);
+        let start = Instant::now();
+        let now_utc = Utc::now();
+
+        router.observe(
+            &context("a", "rack-1"),
+            &report(&[Classification::Leak]),
+            start,
+            now_utc,
+        );
+        assert_eq!(
+            router.flush(start + Duration::from_secs(30), now_utc).len(),
+            1
+        );
+
+        router.observe(&context("a", "rack-1"), &report(&[]), start, now_utc);
+        assert!(
+            router
+                .flush(start + Duration::from_secs(60), now_utc)
+                .is_empty()
+        );
+
+        let notifications = router.flush(start + Duration::from_secs(330), now_utc);
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].status(), AlertStatus::Resolved);
+        assert!(notifications[0].alerts[0].ends_at.is_some());
+
+        // Resolved alerts are dropped once they were delivered
+        assert!(router.alerts.is_empty());
+        assert!(router.groups.is_empty());
+    }
+
+    #[test]
+    fn silences_and_inhibitions_mute_alerts() {
+        let mut leak = route("leak", &["Leak"]);
+        leak.continue_matching = true;
+        let mut router = router(vec![leak, route("sensor", &["SensorCritical"])]);
+        router.inhibit_rules.push(AlertInhibitRuleConfig {
+            source_matchers: HashMap::from([("classification".to_string(), "Leak".to_string())]),
+            target_matchers: HashMap::from([(
+                "classification".to_string(),
+                "SensorCritical".to_string(),
+            )]),
+            equal: vec!["endpoint".to_string()],
+        });
+        let now_utc = Utc::now();
+        router.silences.push(AlertSilenceConfig {
+            matchers: HashMap::from([("rack_id".to_string(), "rack-2".to_string())]),
+            starts_at: now_utc - chrono::Duration::hours(1),
+            ends_at: now_utc + chrono::Duration::hours(1),
+            comment: String::new(),
+        });
+        let start = Instant::now();
+
+        router.observe(
+            &context("a", "rack-1"),
+            &report(&[Classification::Leak, Classification::SensorCritical]),
+            start,
+            now_utc,
+        );
+        router.observe(
+            &context("b", "rack-1"),
+            &report(&[Classification::SensorCritical]),
+            start,
+            now_utc,
+        );
+        router.observe(
+            &context("c", "rack-2"),
+            &report(&[Classification::Leak]),
+            start,
+            now_utc,
+        );
+
+        let mut notifications = router.flush(start + Duration::from_secs(30), now_utc);
+        notifications.sort_by(|a, b| a.group_key.cmp(&b.group_key));
+        assert_eq!(notifications.len(), 2);
+        // The leak on rack-2 is silenced
+        assert_eq!(notifications[0].group_labels["alertname"], "leak");
+        assert_eq!(notifications[0].alerts.len(), 1);
+        // The critical sensor on endpoint a is inhibited by the leak
+        assert_eq!(notifications[1].group_labels["alertname"], "sensor");
+        assert_eq!(notifications[1].alerts.len(), 1);
+        assert_eq!(notifications[1].alerts[0].labels["endpoint"], "b");
+    }
+
+    #[test]
+    fn collector_removal_resolves_alerts() {
+        let mut router = router(vec![route("leak", &[])]);
+        let start = Instant::now();
+        let now_utc = Utc::now();
+        let ctx = context("a", "rack-1");
+
+        router.observe(&ctx, &report(&[Classification::Leak]), start, now_utc);
+        assert_eq!(
+            router.flush(start + Duration::from_secs(30), now_utc).len(),
+            1
+        );
+
+        router.collector_removed(&ctx, now_utc);
+        let notifications = router.flush(start + Duration::from_secs(330), now_utc);
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].status(), AlertStatus::Resolved);
+    }
+}
@@ -68,6 +68,13 @@ impl EventContext {
     pub fn rack_id(&self) -> Option<&RackId> {
         self.rack_id.as_ref()
     }
+
+    pub fn hw_sku(&self) -> Option<&str> {
+        match &self.metadata {
+            Some(EndpointMetadata::Machine(machine)) => machine.hw_sku.as_deref(),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -148,6 +155,7 @@ pub enum ReportSource {
     BmcSensors,
     TrayLeakDetection,
     RackLeakDetection,
+    SensorAnomalyDetection,
 }
 
 impl ReportSource {
@@ -156,6 +164,7 @@ impl ReportSource {
             Self::BmcSensors => "bmc-sensors",
             Self::TrayLeakDetection => "tray-leak-detection",
             Self::RackLeakDetection => "rack-leak-detection",
+            Self::SensorAnomalyDetection => "sensor-anomaly-detection",
         }
     }
 }
@@ -164,6 +173,7 @@ impl ReportSource {
 pub enum Probe {
     Sensor,
     LeakDetection,
+    SensorAnomaly,
 }
 
 impl Probe {
@@ -171,6 +181,7 @@ impl Probe {
         match self {
             Self::Sensor => "BmcSensor",
             Self::LeakDetection => "BmcLeakDetection",
+            Self::SensorAnomaly => "BmcSensorAnomaly",
         }
     }
 }
@@ -184,6 +195,9 @@ pub enum Classification {
     SensorFailure,
     Leak,
     LeakDetector,
+    SensorDrift,
+    SensorStuck,
+    SensorOutlier,
 }
 
 impl Classification {
@@ -196,6 +210,9 @@ impl Classification {
             Self::SensorFailure => "SensorFailure",
             Self::Leak => "Leak",
             Self::LeakDetector => "LeakDetector",
+            Self::SensorDrift => "SensorDrift",
+            Self::SensorStuck => "SensorStuck",
+            Self::SensorOutlier => "SensorOutlier",
         }
     }
 }
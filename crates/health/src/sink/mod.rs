@@ -15,6 +15,7 @@
  * limitations under the License.
  */
 
+mod alert_routing;
 mod composite;
 mod dedup_queue;
 #[cfg(not(feature = "bench-hooks"))]
@@ -27,8 +28,10 @@ mod log_file;
 pub(crate) mod otlp;
 mod prometheus;
 mod rack_health_report;
+mod sensor_history;
 mod tracing;
 
+pub use alert_routing::AlertRoutingSink;
 pub use composite::CompositeDataSink;
 pub use events::{
     Classification, CollectorEvent, EventContext, FirmwareInfo, HealthReport, HealthReportAlert,
@@ -38,6 +41,7 @@ pub use health_report::HealthReportSink;
 pub use log_file::LogFileSink;
 pub use prometheus::PrometheusSink;
 pub use rack_health_report::RackHealthReportSink;
+pub use sensor_history::SensorHistorySink;
 pub use tracing::TracingSink;
 
 #[cfg(not(feature = "bench-hooks"))]
@@ -156,6 +160,7 @@ mod tests {
                     .parse()
                     .expect("valid machine id"),
                 machine_serial: None,
+                hw_sku: None,
             })),
             rack_id: None,
         };
@@ -216,6 +221,7 @@ mod tests {
                     .parse()
                     .expect("valid machine id"),
                 machine_serial: None,
+                hw_sku: None,
             })),
             rack_id: None,
         };
@@ -268,6 +274,7 @@ mod tests {
                     .parse()
                     .expect("valid machine id"),
                 machine_serial: None,
+                hw_sku: None,
             })),
             rack_id: None,
         };
@@ -0,0 +1,241 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use carbide_uuid::machine::MachineId;
+use chrono::Utc;
+
+use super::{CollectorEvent, DataSink, EventContext};
+use crate::HealthError;
+use crate::api_client::ApiClientWrapper;
+use crate::config::SensorHistorySinkConfig;
+
+/// Buffered sensor readings of a single machine
+#[derive(Default)]
+struct MachineSamples {
+    /// The last time a reading was buffered, per sensor
+    last_sampled: HashMap<String, Instant>,
+    pending: VecDeque<rpc::forge::SensorSample>,
+}
+
+struct SampleBuffer {
+    sample_interval: Duration,
+    max_buffered_samples: usize,
+    machines: HashMap<MachineId, MachineSamples>,
+}
+
+impl SampleBuffer {
+    /// Buffers a reading unless the sensor was sampled within the sample interval
+    fn push(&mut self, machine_id: MachineId, sample: rpc::forge::SensorSample, now: Instant) {
+        let machine = self.machines.entry(machine_id).or_default();
+        if machine
+            .last_sampled
+            .get(&sample.sensor)
+            .is_some_and(|last| now.duration_since(*last) < self.sample_interval)
+        {
+            return;
+        }
+        machine.last_sampled.insert(sample.sensor.clone(), now);
+
+        if machine.pending.len() >= self.max_buffered_samples {
+            machine.pending.pop_front();
+        }
+        machine.pending.push_back(sample);
+    }
+
+    fn take_pending(&mut self) -> Vec<(MachineId, Vec<rpc::forge::SensorSample>)> {
+        self.machines
+            .iter_mut()
+            .filter(|(_, machine)| !machine.pending.is_empty())
+            .map(|(machine_id, machine)| (*machine_id, machine.pending.drain(..).collect()))
+            .collect()
+    }
+
+    fn remove_machine(&mut self, machine_id: &MachineId) {
+        self.machines.remove(machine_id);
+    }
+}
+
+/// Sends sensor readings of machines to Carbide API, which keeps a downsampled
+/// history of them. Readings are sampled at most once per `sample_interval`
+/// per sensor and sent in batches.
+pub struct SensorHistorySink {
+    buffer: Arc<Mutex<SampleBuffer>>,
+}
+
+impl SensorHistorySink {
+    pub fn new(config: &SensorHistorySinkConfig) -> Result<Self, HealthError> {
+        let handle = tokio::runtime::Handle::try_current().map_err(|error| {
+            HealthError::GenericError(format!(
+                "sensor history sink requires active Tokio runtime: {error}"
+            ))
+        })?;
+
+        let client = ApiClientWrapper::new(
+            config.connection.root_ca.clone(),
+            config.connection.client_cert.clone(),
+            config.connection.client_key.clone(),
+            &config.connection.api_url,
+        );
+
+        let buffer = Arc::new(Mutex::new(SampleBuffer {
+            sample_interval: config.sample_interval,
+            max_buffered_samples: config.max_buffered_samples,
+            machines: HashMap::new(),
+        }));
+
+        let flush_buffer = Arc::clone(&buffer);
+        let flush_interval = config.flush_interval;
+        handle.spawn(async move {
+            let mut interval = tokio::time::interval(flush_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+
+                let pending = match flush_buffer.lock() {
+                    Ok(mut buffer) => buffer.take_pending(),
+                    Err(_) => {
+                        tracing::error!("sensor history buffer lock poisoned");
+                        return;
+                    }
+                };
+
+                for (machine_id, samples) in pending {
+                    let count = samples.len();
+                    if let Err(error) = client.record_sensor_samples(&machine_id, samples).await {
+                        tracing::warn!(
+                            ?error,
+                            %machine_id,
+                            count,
+                            "Failed to record sensor samples"
+                        );
+                    }
+                }
+            }
+        });
+
+        Ok(Self { buffer })
+    }
+}
+
+impl DataSink for SensorHistorySink {
+    fn sink_type(&self) -> &'static str {
+        "sensor_history_sink"
+    }
+
+    fn handle_event(&self, context: &EventContext, event: &CollectorEvent) {
+        let Some(machine_id) = context.machine_id() else {
+            return;
+        };
+
+        match event {
+            // Only readings of BMC sensors are recorded, derived metrics are not
+            CollectorEvent::Metric(metric) if metric.value.is_finite() => {
+                let Some(sensor_context) = &metric.context else {
+                    return;
+                };
+                let sample = rpc::forge::SensorSample {
+                    sensor: metric.key.clone(),
+                    name: sensor_context.sensor_id.clone(),
+                    unit: metric.unit.clone(),
+                    value: metric.value,
+                    time: Some(Utc::now().into()),
+                };
+                if let Ok(mut buffer) = self.buffer.lock() {
+                    buffer.push(machine_id, sample, Instant::now());
+                }
+            }
+            CollectorEvent::CollectorRemoved => {
+                if let Ok(mut buffer) = self.buffer.lock() {
+                    buffer.remove_machine(&machine_id);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer(max_buffered_samples: usize) -> SampleBuffer {
+        SampleBuffer {
+            sample_interval: Duration::from_secs(60),
+            max_buffered_samples,
+            machines: HashMap::new(),
+        }
+    }
+
+    fn sample(sensor: &str, value: f64) -> rpc::forge::SensorSample {
+        rpc::forge::SensorSample {
+            sensor: sensor.to_string(),
+            name: sensor.to_string(),
+            unit: "Cel".to_string(),
+            value,
+            time: None,
+        }
+    }
+
+    fn machine_id() -> MachineId {
+        "fm100htjtiaehv1n5vh67tbmqq4eabcjdng40f7jupsadbedhruh6rag1l0"
+            .parse()
+            .expect("valid machine id")
+    }
+
+    #[test]
+    fn readings_are_sampled_per_sensor() {
+        let mut buffer = buffer(100);
+        let start = Instant::now();
+
+        buffer.push(machine_id(), sample("cpu0", 40.0), start);
+        buffer.push(machine_id(), sample("cpu1", 30.0), start);
+        buffer.push(
+            machine_id(),
+            sample("cpu0", 41.0),
+            start + Duration::from_secs(30),
+        );
+        buffer.push(
+            machine_id(),
+            sample("cpu0", 42.0),
+            start + Duration::from_secs(60),
+        );
+
+        let pending = buffer.take_pending();
+        assert_eq!(pending.len(), 1);
+        let values: Vec<f64> = pending[0].1.iter().map(|s| s.value).collect();
+        assert_eq!(values, vec![40.0, 30.0, 42.0]);
+        assert!(buffer.take_pending().is_empty());
+    }
+
+    #[test]
+    fn oldest_readings_are_dropped_when_buffer_is_full() {
+        let mut buffer = buffer(2);
+        let start = Instant::now();
+
+        for (i, sensor) in ["a", "b", "c"].into_iter().enumerate() {
+            buffer.push(machine_id(), sample(sensor, i as f64), start);
+        }
+
+        let pending = buffer.take_pending();
+        let sensors: Vec<&str> = pending[0].1.iter().map(|s| s.sensor.as_str()).collect();
+        assert_eq!(sensors, vec!["b", "c"]);
+    }
+}
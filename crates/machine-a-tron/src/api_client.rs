@@ -482,6 +482,7 @@ impl ApiClient {
                 arch: arch.into(),
                 interface_id: Some(interface_id),
                 product,
+                script_format: rpc::forge::BootScriptFormat::Ipxe as i32,
             })
             .await
             .map_err(ClientApiError::InvocationError)
@@ -122,6 +122,14 @@ pub struct MachineConfig {
 
     #[serde(default)]
     pub dpu_agent_version: Option<String>,
+
+    /// bmc-mock fault-injection scenario (YAML) started on the BMC of every host in this section.
+    #[serde(default)]
+    pub host_bmc_scenario: Option<PathBuf>,
+
+    /// Same as `host_bmc_scenario`, for the BMCs of the DPUs.
+    #[serde(default)]
+    pub dpu_bmc_scenario: Option<PathBuf>,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone, Eq, PartialEq)]
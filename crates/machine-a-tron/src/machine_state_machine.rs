@@ -943,10 +943,23 @@ impl MachineStateMachine {
             self.mat_host_id,
         );
 
+        let scenario_path = match self.machine_info {
+            MachineInfo::Host(_) => self.config.host_bmc_scenario.as_ref(),
+            MachineInfo::Dpu(_) => self.config.dpu_bmc_scenario.as_ref(),
+        };
+        if let Some(path) = scenario_path {
+            let scenario = bmc_mock::scenario::Scenario::from_file(path)?;
+            bmc_mock.state().injected_bugs.start_scenario(scenario);
+        }
+
         let maybe_bmc_mock_handle = match &self.app_context.bmc_registration_mode {
             BmcRegistrationMode::None(port) => {
                 let address = SocketAddr::new(ip_address.into(), *port);
                 let handle = bmc_mock.start(address, true).await?;
+                bmc_mock
+                    .state()
+                    .injected_bugs
+                    .attach_tls_config(handle._bmc_mock.tls_config());
                 self.live_state.write().unwrap().ssh_host_key =
                     handle.ssh_handle.as_ref().map(|h| h.host_pubkey.clone());
                 Some(Arc::new(handle))
@@ -1050,6 +1063,8 @@ pub enum MachineStateError {
     PxeError(#[from] PxeError),
     #[error("BMC mock TLS error: {0}")]
     BmcMockTls(#[from] bmc_mock::tls::Error),
+    #[error("BMC mock scenario error: {0}")]
+    BmcMockScenario(#[from] bmc_mock::scenario::Error),
     #[error("Mock SSH server error: {0}")]
     MockSshServer(String),
     #[error("{0}")]
@@ -0,0 +1,812 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/*!
+ *  Parser for the binary TCG PC Client event log, as exposed by the
+ *  kernel at /sys/kernel/security/tpm0/binary_bios_measurements.
+ *
+ *  Both the crypto-agile format (where the first event carries a
+ *  "Spec ID Event03" header describing the digest algorithms in use)
+ *  and the legacy SHA-1 only format are supported. Event data is decoded
+ *  for the event types that matter when explaining a PCR change (EFI
+ *  images, UEFI variables, firmware blobs and text events); everything
+ *  else is kept as raw bytes.
+ */
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+const SPEC_ID_EVENT03_SIGNATURE: &[u8] = b"Spec ID Event03\0";
+const STARTUP_LOCALITY_SIGNATURE: &[u8] = b"StartupLocality\0";
+const SHA1_DIGEST_SIZE: usize = 20;
+
+// EFI_GLOBAL_VARIABLE, which owns SecureBoot, PK, KEK and the Boot#### variables.
+const EFI_GLOBAL_VARIABLE_GUID: &str = "8be4df61-93ca-11d2-aa0d-00e098032b8c";
+// EFI_IMAGE_SECURITY_DATABASE_GUID, which owns db, dbx, dbt and dbr.
+const EFI_IMAGE_SECURITY_DATABASE_GUID: &str = "d719b2cb-3d3a-4596-a3bc-dad00e67656f";
+
+/// HashAlgorithm is a TPM2 hash algorithm (TPM_ALG_ID) that can
+/// appear in a crypto-agile event log.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub enum HashAlgorithm {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+    Sm3_256,
+}
+
+impl HashAlgorithm {
+    pub fn from_alg_id(alg_id: u16) -> Option<Self> {
+        match alg_id {
+            0x0004 => Some(Self::Sha1),
+            0x000b => Some(Self::Sha256),
+            0x000c => Some(Self::Sha384),
+            0x000d => Some(Self::Sha512),
+            0x0012 => Some(Self::Sm3_256),
+            _ => None,
+        }
+    }
+
+    pub fn alg_id(&self) -> u16 {
+        match self {
+            Self::Sha1 => 0x0004,
+            Self::Sha256 => 0x000b,
+            Self::Sha384 => 0x000c,
+            Self::Sha512 => 0x000d,
+            Self::Sm3_256 => 0x0012,
+        }
+    }
+
+    pub fn digest_size(&self) -> usize {
+        match self {
+            Self::Sha1 => 20,
+            Self::Sha256 | Self::Sm3_256 => 32,
+            Self::Sha384 => 48,
+            Self::Sha512 => 64,
+        }
+    }
+
+    /// for_digest_size maps the size of a quoted PCR value back to the
+    /// bank it came from. A 32 byte value is assumed to be SHA-256, since
+    /// that is the bank scout quotes from.
+    pub fn for_digest_size(size: usize) -> Option<Self> {
+        match size {
+            20 => Some(Self::Sha1),
+            32 => Some(Self::Sha256),
+            48 => Some(Self::Sha384),
+            64 => Some(Self::Sha512),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Sha1 => "sha1",
+            Self::Sha256 => "sha256",
+            Self::Sha384 => "sha384",
+            Self::Sha512 => "sha512",
+            Self::Sm3_256 => "sm3_256",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for HashAlgorithm {
+    type Err = super::Error;
+
+    fn from_str(input: &str) -> super::Result<Self> {
+        match input {
+            "sha1" => Ok(Self::Sha1),
+            "sha256" => Ok(Self::Sha256),
+            "sha384" => Ok(Self::Sha384),
+            "sha512" => Ok(Self::Sha512),
+            "sm3_256" => Ok(Self::Sm3_256),
+            _ => Err(super::Error::Parse(format!(
+                "unknown hash algorithm: {input}"
+            ))),
+        }
+    }
+}
+
+/// EventType is the raw TCG event type of a log entry. Known types
+/// are available as constants, and Display renders the TCG name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct EventType(pub u32);
+
+impl EventType {
+    pub const PREBOOT_CERT: Self = Self(0x0000_0000);
+    pub const POST_CODE: Self = Self(0x0000_0001);
+    pub const NO_ACTION: Self = Self(0x0000_0003);
+    pub const SEPARATOR: Self = Self(0x0000_0004);
+    pub const ACTION: Self = Self(0x0000_0005);
+    pub const EVENT_TAG: Self = Self(0x0000_0006);
+    pub const S_CRTM_CONTENTS: Self = Self(0x0000_0007);
+    pub const S_CRTM_VERSION: Self = Self(0x0000_0008);
+    pub const CPU_MICROCODE: Self = Self(0x0000_0009);
+    pub const PLATFORM_CONFIG_FLAGS: Self = Self(0x0000_000a);
+    pub const TABLE_OF_DEVICES: Self = Self(0x0000_000b);
+    pub const COMPACT_HASH: Self = Self(0x0000_000c);
+    pub const IPL: Self = Self(0x0000_000d);
+    pub const IPL_PARTITION_DATA: Self = Self(0x0000_000e);
+    pub const NONHOST_CODE: Self = Self(0x0000_000f);
+    pub const NONHOST_CONFIG: Self = Self(0x0000_0010);
+    pub const NONHOST_INFO: Self = Self(0x0000_0011);
+    pub const OMIT_BOOT_DEVICE_EVENTS: Self = Self(0x0000_0012);
+    pub const EFI_VARIABLE_DRIVER_CONFIG: Self = Self(0x8000_0001);
+    pub const EFI_VARIABLE_BOOT: Self = Self(0x8000_0002);
+    pub const EFI_BOOT_SERVICES_APPLICATION: Self = Self(0x8000_0003);
+    pub const EFI_BOOT_SERVICES_DRIVER: Self = Self(0x8000_0004);
+    pub const EFI_RUNTIME_SERVICES_DRIVER: Self = Self(0x8000_0005);
+    pub const EFI_GPT_EVENT: Self = Self(0x8000_0006);
+    pub const EFI_ACTION: Self = Self(0x8000_0007);
+    pub const EFI_PLATFORM_FIRMWARE_BLOB: Self = Self(0x8000_0008);
+    pub const EFI_HANDOFF_TABLES: Self = Self(0x8000_0009);
+    pub const EFI_PLATFORM_FIRMWARE_BLOB2: Self = Self(0x8000_000a);
+    pub const EFI_HANDOFF_TABLES2: Self = Self(0x8000_000b);
+    pub const EFI_VARIABLE_BOOT2: Self = Self(0x8000_000c);
+    pub const EFI_HCRTM_EVENT: Self = Self(0x8000_0010);
+    pub const EFI_VARIABLE_AUTHORITY: Self = Self(0x8000_00e0);
+    pub const EFI_SPDM_FIRMWARE_BLOB: Self = Self(0x8000_00e1);
+    pub const EFI_SPDM_FIRMWARE_CONFIG: Self = Self(0x8000_00e2);
+
+    pub fn name(&self) -> Option<&'static str> {
+        let name = match *self {
+            Self::PREBOOT_CERT => "EV_PREBOOT_CERT",
+            Self::POST_CODE => "EV_POST_CODE",
+            Self::NO_ACTION => "EV_NO_ACTION",
+            Self::SEPARATOR => "EV_SEPARATOR",
+            Self::ACTION => "EV_ACTION",
+            Self::EVENT_TAG => "EV_EVENT_TAG",
+            Self::S_CRTM_CONTENTS => "EV_S_CRTM_CONTENTS",
+            Self::S_CRTM_VERSION => "EV_S_CRTM_VERSION",
+            Self::CPU_MICROCODE => "EV_CPU_MICROCODE",
+            Self::PLATFORM_CONFIG_FLAGS => "EV_PLATFORM_CONFIG_FLAGS",
+            Self::TABLE_OF_DEVICES => "EV_TABLE_OF_DEVICES",
+            Self::COMPACT_HASH => "EV_COMPACT_HASH",
+            Self::IPL => "EV_IPL",
+            Self::IPL_PARTITION_DATA => "EV_IPL_PARTITION_DATA",
+            Self::NONHOST_CODE => "EV_NONHOST_CODE",
+            Self::NONHOST_CONFIG => "EV_NONHOST_CONFIG",
+            Self::NONHOST_INFO => "EV_NONHOST_INFO",
+            Self::OMIT_BOOT_DEVICE_EVENTS => "EV_OMIT_BOOT_DEVICE_EVENTS",
+            Self::EFI_VARIABLE_DRIVER_CONFIG => "EV_EFI_VARIABLE_DRIVER_CONFIG",
+            Self::EFI_VARIABLE_BOOT => "EV_EFI_VARIABLE_BOOT",
+            Self::EFI_BOOT_SERVICES_APPLICATION => "EV_EFI_BOOT_SERVICES_APPLICATION",
+            Self::EFI_BOOT_SERVICES_DRIVER => "EV_EFI_BOOT_SERVICES_DRIVER",
+            Self::EFI_RUNTIME_SERVICES_DRIVER => "EV_EFI_RUNTIME_SERVICES_DRIVER",
+            Self::EFI_GPT_EVENT => "EV_EFI_GPT_EVENT",
+            Self::EFI_ACTION => "EV_EFI_ACTION",
+            Self::EFI_PLATFORM_FIRMWARE_BLOB => "EV_EFI_PLATFORM_FIRMWARE_BLOB",
+            Self::EFI_HANDOFF_TABLES => "EV_EFI_HANDOFF_TABLES",
+            Self::EFI_PLATFORM_FIRMWARE_BLOB2 => "EV_EFI_PLATFORM_FIRMWARE_BLOB2",
+            Self::EFI_HANDOFF_TABLES2 => "EV_EFI_HANDOFF_TABLES2",
+            Self::EFI_VARIABLE_BOOT2 => "EV_EFI_VARIABLE_BOOT2",
+            Self::EFI_HCRTM_EVENT => "EV_EFI_HCRTM_EVENT",
+            Self::EFI_VARIABLE_AUTHORITY => "EV_EFI_VARIABLE_AUTHORITY",
+            Self::EFI_SPDM_FIRMWARE_BLOB => "EV_EFI_SPDM_FIRMWARE_BLOB",
+            Self::EFI_SPDM_FIRMWARE_CONFIG => "EV_EFI_SPDM_FIRMWARE_CONFIG",
+            _ => return None,
+        };
+        Some(name)
+    }
+}
+
+impl fmt::Display for EventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => write!(f, "{name}"),
+            None => write!(f, "0x{:08x}", self.0),
+        }
+    }
+}
+
+/// SpecIdDigestSize is an entry from the digestSizes table of the
+/// "Spec ID Event03" header, describing how large the digests for
+/// a given algorithm are in every subsequent event.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SpecIdDigestSize {
+    pub alg_id: u16,
+    pub digest_size: u16,
+}
+
+/// SpecIdEvent is the TCG_EfiSpecIdEvent header carried in the first
+/// event of a crypto-agile log.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SpecIdEvent {
+    pub platform_class: u32,
+    pub spec_version_major: u8,
+    pub spec_version_minor: u8,
+    pub spec_errata: u8,
+    pub uintn_size: u8,
+    pub digest_sizes: Vec<SpecIdDigestSize>,
+}
+
+impl SpecIdEvent {
+    /// algorithms returns the known hash algorithms the log
+    /// carries digests for.
+    pub fn algorithms(&self) -> Vec<HashAlgorithm> {
+        self.digest_sizes
+            .iter()
+            .filter_map(|entry| HashAlgorithm::from_alg_id(entry.alg_id))
+            .collect()
+    }
+}
+
+/// EventDigest is a single digest of an event, for one bank.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct EventDigest {
+    pub alg_id: u16,
+    pub digest: Vec<u8>,
+}
+
+impl EventDigest {
+    pub fn algorithm(&self) -> Option<HashAlgorithm> {
+        HashAlgorithm::from_alg_id(self.alg_id)
+    }
+}
+
+/// EventLogEvent is a single measurement from the event log.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct EventLogEvent {
+    // index is the position of the event in the log, starting at 0
+    // for the header event.
+    pub index: usize,
+    pub pcr_index: u32,
+    pub event_type: EventType,
+    pub digests: Vec<EventDigest>,
+    pub data: Vec<u8>,
+}
+
+impl EventLogEvent {
+    /// digest returns the digest of this event for the given bank.
+    pub fn digest(&self, algorithm: HashAlgorithm) -> Option<&[u8]> {
+        self.digests
+            .iter()
+            .find(|digest| digest.alg_id == algorithm.alg_id())
+            .map(|digest| digest.digest.as_slice())
+    }
+
+    /// is_extended returns whether this event was extended into a PCR.
+    /// EV_NO_ACTION events are informational only.
+    pub fn is_extended(&self) -> bool {
+        self.event_type != EventType::NO_ACTION
+    }
+
+    /// startup_locality returns the locality from a StartupLocality
+    /// EV_NO_ACTION event, which sets the initial value of PCR 0.
+    pub fn startup_locality(&self) -> Option<u8> {
+        if self.event_type != EventType::NO_ACTION || self.pcr_index != 0 {
+            return None;
+        }
+        self.data
+            .strip_prefix(STARTUP_LOCALITY_SIGNATURE)
+            .and_then(|rest| rest.first().copied())
+    }
+
+    /// detail decodes the event data for the event types that
+    /// carry something worth showing to a human.
+    pub fn detail(&self) -> EventDetail {
+        decode_detail(self.event_type, &self.data).unwrap_or(EventDetail::Raw {
+            length: self.data.len(),
+        })
+    }
+
+    /// description is a one line, human readable summary of the
+    /// event, e.g. "EFI driver FvFile(...)" or "Secure Boot variable db".
+    pub fn description(&self) -> String {
+        let detail = self.detail();
+        match detail {
+            EventDetail::Raw { .. } => format!("{} ({})", self.event_type, detail),
+            _ => detail.to_string(),
+        }
+    }
+}
+
+/// VariableKind groups UEFI variables by what they mean for the
+/// boot policy, so a diff can call out Secure Boot changes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum VariableKind {
+    SecureBoot,
+    Boot,
+    Other,
+}
+
+/// EventDetail is the decoded event data of an event.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub enum EventDetail {
+    /// A UEFI_VARIABLE_DATA event (EV_EFI_VARIABLE_*).
+    EfiVariable {
+        name: String,
+        vendor_guid: String,
+        kind: VariableKind,
+        authority: bool,
+        data_length: usize,
+    },
+    /// A UEFI_IMAGE_LOAD_EVENT (EV_EFI_BOOT_SERVICES_* and
+    /// EV_EFI_RUNTIME_SERVICES_DRIVER).
+    EfiImage {
+        image_type: EfiImageType,
+        device_path: Option<String>,
+        length: u64,
+    },
+    /// An EV_EFI_PLATFORM_FIRMWARE_BLOB(2) event.
+    FirmwareBlob {
+        description: Option<String>,
+        length: u64,
+    },
+    /// An EV_SEPARATOR event. A value other than 0 means the firmware
+    /// hit an error before the measurement.
+    Separator { value: u32 },
+    /// Events whose data is a human readable string.
+    Text(String),
+    /// Anything else.
+    Raw { length: usize },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum EfiImageType {
+    Application,
+    Driver,
+    RuntimeDriver,
+}
+
+impl fmt::Display for EfiImageType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Application => write!(f, "EFI application"),
+            Self::Driver => write!(f, "EFI driver"),
+            Self::RuntimeDriver => write!(f, "EFI runtime driver"),
+        }
+    }
+}
+
+impl EventDetail {
+    /// identity is used to line up the "same" event across two logs
+    /// (e.g. the same UEFI variable, or an image loaded from the same
+    /// device path), so that a digest change can be reported as a
+    /// change rather than an add and a remove.
+    pub fn identity(&self, event_type: EventType) -> String {
+        match self {
+            Self::EfiVariable {
+                name, vendor_guid, ..
+            } => format!("{event_type}:{vendor_guid}:{name}"),
+            Self::EfiImage {
+                device_path: Some(path),
+                ..
+            } => format!("{event_type}:{path}"),
+            Self::FirmwareBlob {
+                description: Some(description),
+                ..
+            } => format!("{event_type}:{description}"),
+            _ => event_type.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for EventDetail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EfiVariable {
+                name,
+                kind,
+                authority,
+                ..
+            } => {
+                let prefix = match kind {
+                    VariableKind::SecureBoot => "Secure Boot variable",
+                    VariableKind::Boot => "boot variable",
+                    VariableKind::Other => "UEFI variable",
+                };
+                if *authority {
+                    write!(f, "{prefix} {name} (image authority)")
+                } else {
+                    write!(f, "{prefix} {name}")
+                }
+            }
+            Self::EfiImage {
+                image_type,
+                device_path,
+                length,
+            } => match device_path {
+                Some(path) => write!(f, "{image_type} {path}"),
+                None => write!(f, "{image_type} ({length} bytes)"),
+            },
+            Self::FirmwareBlob {
+                description,
+                length,
+            } => match description {
+                Some(description) => write!(f, "firmware blob {description}"),
+                None => write!(f, "firmware blob ({length} bytes)"),
+            },
+            Self::Separator { value: 0 } => write!(f, "separator"),
+            Self::Separator { value } => write!(f, "separator (error 0x{value:08x})"),
+            Self::Text(text) => write!(f, "{text}"),
+            Self::Raw { length } => write!(f, "{length} bytes"),
+        }
+    }
+}
+
+/// EventLog is a parsed TCG PC Client event log.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct EventLog {
+    // spec_id is the crypto-agile header, or None for a legacy
+    // SHA-1 only log.
+    pub spec_id: Option<SpecIdEvent>,
+    pub events: Vec<EventLogEvent>,
+}
+
+impl EventLog {
+    /// parse parses a binary event log. The header event is kept as
+    /// the first entry of `events`.
+    pub fn parse(raw: &[u8]) -> super::Result<Self> {
+        let mut reader = Reader::new(raw);
+        let header = read_sha1_event(&mut reader, 0)?;
+
+        let spec_id = if header.event_type == EventType::NO_ACTION
+            && header.data.starts_with(SPEC_ID_EVENT03_SIGNATURE)
+        {
+            Some(parse_spec_id_event(&header.data)?)
+        } else {
+            None
+        };
+
+        let mut events = vec![header];
+        while !reader.is_empty() {
+            let index = events.len();
+            let event = match &spec_id {
+                Some(spec_id) => read_crypto_agile_event(&mut reader, index, spec_id)?,
+                None => read_sha1_event(&mut reader, index)?,
+            };
+            events.push(event);
+        }
+
+        Ok(Self { spec_id, events })
+    }
+
+    /// algorithms returns the banks the log carries digests for.
+    pub fn algorithms(&self) -> Vec<HashAlgorithm> {
+        match &self.spec_id {
+            Some(spec_id) => spec_id.algorithms(),
+            None => vec![HashAlgorithm::Sha1],
+        }
+    }
+
+    /// measurements returns the events that were extended
+    /// into PCRs, skipping informational EV_NO_ACTION events.
+    pub fn measurements(&self) -> impl Iterator<Item = &EventLogEvent> {
+        self.events.iter().filter(|event| event.is_extended())
+    }
+}
+
+/// looks_like_event_log does a cheap check of whether the given bytes
+/// are a binary event log (as opposed to, say, tpm2_eventlog YAML output
+/// sent by older scout builds).
+pub fn looks_like_event_log(raw: &[u8]) -> bool {
+    // The header event is a TCG_PCClientPCREvent in PCR 0.
+    raw.len() >= 32 && raw[0..4] == [0, 0, 0, 0]
+}
+
+fn read_sha1_event(reader: &mut Reader, index: usize) -> super::Result<EventLogEvent> {
+    let pcr_index = reader.u32()?;
+    let event_type = EventType(reader.u32()?);
+    let digest = reader.bytes(SHA1_DIGEST_SIZE)?.to_vec();
+    let event_size = reader.u32()? as usize;
+    let data = reader.bytes(event_size)?.to_vec();
+    Ok(EventLogEvent {
+        index,
+        pcr_index,
+        event_type,
+        digests: vec![EventDigest {
+            alg_id: HashAlgorithm::Sha1.alg_id(),
+            digest,
+        }],
+        data,
+    })
+}
+
+fn read_crypto_agile_event(
+    reader: &mut Reader,
+    index: usize,
+    spec_id: &SpecIdEvent,
+) -> super::Result<EventLogEvent> {
+    let pcr_index = reader.u32()?;
+    let event_type = EventType(reader.u32()?);
+    let digest_count = reader.u32()?;
+    let mut digests = Vec::new();
+    for _ in 0..digest_count {
+        let alg_id = reader.u16()?;
+        let digest_size = spec_id
+            .digest_sizes
+            .iter()
+            .find(|entry| entry.alg_id == alg_id)
+            .map(|entry| entry.digest_size as usize)
+            .ok_or_else(|| {
+                super::Error::Parse(format!(
+                    "event {index} has a digest for algorithm 0x{alg_id:04x}, which is not in the log header"
+                ))
+            })?;
+        digests.push(EventDigest {
+            alg_id,
+            digest: reader.bytes(digest_size)?.to_vec(),
+        });
+    }
+    let event_size = reader.u32()? as usize;
+    let data = reader.bytes(event_size)?.to_vec();
+    Ok(EventLogEvent {
+        index,
+        pcr_index,
+        event_type,
+        digests,
+        data,
+    })
+}
+
+fn parse_spec_id_event(data: &[u8]) -> super::Result<SpecIdEvent> {
+    let mut reader = Reader::new(data);
+    reader.bytes(SPEC_ID_EVENT03_SIGNATURE.len())?;
+    let platform_class = reader.u32()?;
+    let spec_version_minor = reader.u8()?;
+    let spec_version_major = reader.u8()?;
+    let spec_errata = reader.u8()?;
+    let uintn_size = reader.u8()?;
+    let algorithm_count = reader.u32()?;
+    let mut digest_sizes = Vec::new();
+    for _ in 0..algorithm_count {
+        digest_sizes.push(SpecIdDigestSize {
+            alg_id: reader.u16()?,
+            digest_size: reader.u16()?,
+        });
+    }
+    Ok(SpecIdEvent {
+        platform_class,
+        spec_version_major,
+        spec_version_minor,
+        spec_errata,
+        uintn_size,
+        digest_sizes,
+    })
+}
+
+fn decode_detail(event_type: EventType, data: &[u8]) -> Option<EventDetail> {
+    match event_type {
+        EventType::EFI_VARIABLE_DRIVER_CONFIG
+        | EventType::EFI_VARIABLE_BOOT
+        | EventType::EFI_VARIABLE_BOOT2
+        | EventType::EFI_VARIABLE_AUTHORITY => decode_variable(event_type, data),
+        EventType::EFI_BOOT_SERVICES_APPLICATION => decode_image(EfiImageType::Application, data),
+        EventType::EFI_BOOT_SERVICES_DRIVER => decode_image(EfiImageType::Driver, data),
+        EventType::EFI_RUNTIME_SERVICES_DRIVER => decode_image(EfiImageType::RuntimeDriver, data),
+        EventType::EFI_PLATFORM_FIRMWARE_BLOB => {
+            let mut reader = Reader::new(data);
+            let _base = reader.u64().ok()?;
+            Some(EventDetail::FirmwareBlob {
+                description: None,
+                length: reader.u64().ok()?,
+            })
+        }
+        EventType::EFI_PLATFORM_FIRMWARE_BLOB2 => {
+            let mut reader = Reader::new(data);
+            let description_size = reader.u8().ok()? as usize;
+            let description = ascii_text(reader.bytes(description_size).ok()?);
+            let _base = reader.u64().ok()?;
+            Some(EventDetail::FirmwareBlob {
+                description,
+                length: reader.u64().ok()?,
+            })
+        }
+        EventType::SEPARATOR => {
+            let mut reader = Reader::new(data);
+            Some(EventDetail::Separator {
+                value: reader.u32().ok()?,
+            })
+        }
+        EventType::S_CRTM_VERSION => utf16_text(data).map(EventDetail::Text),
+        EventType::ACTION
+        | EventType::EFI_ACTION
+        | EventType::IPL
+        | EventType::POST_CODE
+        | EventType::EVENT_TAG
+        | EventType::COMPACT_HASH => ascii_text(data).map(EventDetail::Text),
+        _ => None,
+    }
+}
+
+fn decode_variable(event_type: EventType, data: &[u8]) -> Option<EventDetail> {
+    let mut reader = Reader::new(data);
+    let vendor_guid = format_guid(reader.bytes(16).ok()?);
+    let name_length = reader.u64().ok()? as usize;
+    let data_length = reader.u64().ok()? as usize;
+    let name = utf16_text(reader.bytes(name_length.checked_mul(2)?).ok()?)?;
+
+    let kind = match (vendor_guid.as_str(), name.as_str()) {
+        (EFI_GLOBAL_VARIABLE_GUID, "SecureBoot" | "PK" | "KEK")
+        | (EFI_IMAGE_SECURITY_DATABASE_GUID, _) => VariableKind::SecureBoot,
+        (EFI_GLOBAL_VARIABLE_GUID, "BootOrder" | "BootNext" | "BootCurrent") => VariableKind::Boot,
+        (EFI_GLOBAL_VARIABLE_GUID, name)
+            if name.len() == 8
+                && name.starts_with("Boot")
+                && name[4..].chars().all(|c| c.is_ascii_hexdigit()) =>
+        {
+            VariableKind::Boot
+        }
+        _ => VariableKind::Other,
+    };
+
+    Some(EventDetail::EfiVariable {
+        name,
+        vendor_guid,
+        kind,
+        authority: event_type == EventType::EFI_VARIABLE_AUTHORITY,
+        data_length,
+    })
+}
+
+fn decode_image(image_type: EfiImageType, data: &[u8]) -> Option<EventDetail> {
+    let mut reader = Reader::new(data);
+    let _location = reader.u64().ok()?;
+    let length = reader.u64().ok()?;
+    let _link_time_address = reader.u64().ok()?;
+    let device_path_length = reader.u64().ok()? as usize;
+    let device_path = reader
+        .bytes(device_path_length)
+        .ok()
+        .and_then(format_device_path);
+    Some(EventDetail::EfiImage {
+        image_type,
+        device_path,
+        length,
+    })
+}
+
+/// format_device_path renders the interesting nodes of an EFI device
+/// path (partitions, firmware volumes and file paths) in the UEFI text
+/// form, e.g. `HD(1)/\EFI\BOOT\BOOTX64.EFI`.
+fn format_device_path(raw: &[u8]) -> Option<String> {
+    let mut reader = Reader::new(raw);
+    let mut nodes = Vec::new();
+    while !reader.is_empty() {
+        let node_type = reader.u8().ok()?;
+        let sub_type = reader.u8().ok()?;
+        let length = reader.u16().ok()? as usize;
+        if length < 4 {
+            return None;
+        }
+        let node = reader.bytes(length - 4).ok()?;
+        match (node_type, sub_type) {
+            // End of device path.
+            (0x7f, 0xff) => break,
+            // Media: hard drive; the partition number is the first field.
+            (0x04, 0x01) if node.len() >= 4 => {
+                let partition = u32::from_le_bytes(node[0..4].try_into().ok()?);
+                nodes.push(format!("HD({partition})"));
+            }
+            // Media: file path.
+            (0x04, 0x04) => nodes.push(utf16_text(node)?),
+            // Media: PIWG firmware file and firmware volume.
+            (0x04, 0x06) if node.len() >= 16 => {
+                nodes.push(format!("FvFile({})", format_guid(&node[0..16])))
+            }
+            (0x04, 0x07) if node.len() >= 16 => {
+                nodes.push(format!("Fv({})", format_guid(&node[0..16])))
+            }
+            _ => {}
+        }
+    }
+    if nodes.is_empty() {
+        None
+    } else {
+        Some(nodes.join("/"))
+    }
+}
+
+/// format_guid renders an EFI_GUID, whose first three fields
+/// are little endian.
+fn format_guid(raw: &[u8]) -> String {
+    format!(
+        "{:02x}{:02x}{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
+        raw[3],
+        raw[2],
+        raw[1],
+        raw[0],
+        raw[5],
+        raw[4],
+        raw[7],
+        raw[6],
+        raw[8],
+        raw[9],
+        raw[10],
+        raw[11],
+        raw[12],
+        raw[13],
+        raw[14],
+        raw[15]
+    )
+}
+
+fn utf16_text(raw: &[u8]) -> Option<String> {
+    let units: Vec<u16> = raw
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .take_while(|unit| *unit != 0)
+        .collect();
+    String::from_utf16(&units).ok()
+}
+
+fn ascii_text(raw: &[u8]) -> Option<String> {
+    let trimmed = match raw.iter().position(|b| *b == 0) {
+        Some(end) => &raw[..end],
+        None => raw,
+    };
+    if trimmed.is_empty() || !trimmed.iter().all(|b| b.is_ascii_graphic() || *b == b' ') {
+        return None;
+    }
+    Some(String::from_utf8_lossy(trimmed).into_owned())
+}
+
+/// Reader is a small little endian cursor over the raw log.
+struct Reader<'a> {
+    raw: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(raw: &'a [u8]) -> Self {
+        Self { raw, offset: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.offset >= self.raw.len()
+    }
+
+    fn bytes(&mut self, len: usize) -> super::Result<&'a [u8]> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.raw.len())
+            .ok_or_else(|| {
+                super::Error::Parse(format!(
+                    "event log truncated: wanted {len} bytes at offset {}, have {}",
+                    self.offset,
+                    self.raw.len() - self.offset
+                ))
+            })?;
+        let bytes = &self.raw[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> super::Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> super::Result<u16> {
+        Ok(u16::from_le_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> super::Result<u32> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> super::Result<u64> {
+        Ok(u64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+    }
+}
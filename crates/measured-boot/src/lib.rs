@@ -15,11 +15,14 @@
  * limitations under the License.
  */
 pub mod bundle;
+pub mod event_log;
 pub mod journal;
 pub mod machine;
 pub mod pcr;
+pub mod policy;
 pub mod profile;
 pub mod records;
+pub mod replay;
 pub mod report;
 pub mod site;
 
@@ -29,6 +32,8 @@ pub enum Error {
     Parse(String),
     #[error("{0}")]
     RpcConversion(String),
+    #[error("{0}")]
+    Replay(String),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,550 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/*!
+ *  Event-level measurement policies.
+ *
+ *  A MeasurementBundle approves an exact set of PCR values, so any BIOS
+ *  setting tweak or shim update needs a new bundle. A MeasurementPolicy
+ *  instead approves what the event log says was measured: which boot
+ *  loaders may run, which Secure Boot db/dbx contents are acceptable, and
+ *  what the kernel command line may look like. Policies are evaluated
+ *  against the parsed event log of a report, and every rule explains why
+ *  it passed or failed.
+ */
+
+use std::fmt;
+
+use carbide_uuid::measured_boot::{MeasurementPolicyId, MeasurementSystemProfileId};
+use chrono::Utc;
+use regex::Regex;
+#[cfg(feature = "cli")]
+use rpc::admin_cli::ToTable;
+use rpc::errors::RpcDataConversionError;
+use rpc::protos::measured_boot::{
+    MeasurementBootloaderRulePb, MeasurementKernelCmdlineRulePb, MeasurementPolicyEvaluationPb,
+    MeasurementPolicyPb, MeasurementPolicyRulePb, MeasurementRuleResultPb,
+    MeasurementSecureBootRulePb, measurement_policy_rule_pb,
+};
+use serde::{Deserialize, Serialize};
+
+use super::event_log::{EventDetail, EventLog, EventLogEvent, EventType, HashAlgorithm};
+
+// grub measures the kernel command line into PCR 8 as an
+// EV_IPL event prefixed with this.
+const KERNEL_CMDLINE_PREFIX: &str = "kernel_cmdline: ";
+
+/// PolicyRule is a single rule of a MeasurementPolicy. Digests are hex
+/// encoded, in the same bank as the PCR values the machine quotes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PolicyRule {
+    /// Every EFI application loaded by the boot manager (PCR 4), e.g.
+    /// shim and grub, must have one of the allowed digests.
+    BootloaderDigests { allowed_digests: Vec<String> },
+    /// The measured contents of a Secure Boot variable (PK, KEK, db,
+    /// dbx, SecureBoot) in PCR 7 must have one of the allowed digests.
+    SecureBootVariable {
+        variable: String,
+        allowed_digests: Vec<String>,
+    },
+    /// Every measured kernel command line must fully match one of the
+    /// allowed regular expressions.
+    KernelCommandLine { allowed_patterns: Vec<String> },
+}
+
+impl fmt::Display for PolicyRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BootloaderDigests { allowed_digests } => {
+                write!(f, "bootloader digests ({} allowed)", allowed_digests.len())
+            }
+            Self::SecureBootVariable {
+                variable,
+                allowed_digests,
+            } => write!(
+                f,
+                "Secure Boot variable {variable} ({} allowed)",
+                allowed_digests.len()
+            ),
+            Self::KernelCommandLine { allowed_patterns } => write!(
+                f,
+                "kernel command line ({} patterns)",
+                allowed_patterns.len()
+            ),
+        }
+    }
+}
+
+impl PolicyRule {
+    /// validate makes sure the digests are hex and the
+    /// patterns compile, so a broken rule can't be stored.
+    pub fn validate(&self) -> super::Result<()> {
+        let check_digests = |digests: &[String]| {
+            if digests.is_empty() {
+                return Err(super::Error::Parse(format!(
+                    "rule \"{self}\" needs at least one allowed digest"
+                )));
+            }
+            for digest in digests {
+                if digest.is_empty() || hex::decode(digest).is_err() {
+                    return Err(super::Error::Parse(format!(
+                        "rule \"{self}\" has an invalid digest: {digest}"
+                    )));
+                }
+            }
+            Ok(())
+        };
+        match self {
+            Self::BootloaderDigests { allowed_digests } => check_digests(allowed_digests),
+            Self::SecureBootVariable {
+                variable,
+                allowed_digests,
+            } => {
+                if variable.is_empty() {
+                    return Err(super::Error::Parse(
+                        "Secure Boot variable rule needs a variable name".to_string(),
+                    ));
+                }
+                check_digests(allowed_digests)
+            }
+            Self::KernelCommandLine { allowed_patterns } => {
+                if allowed_patterns.is_empty() {
+                    return Err(super::Error::Parse(format!(
+                        "rule \"{self}\" needs at least one allowed pattern"
+                    )));
+                }
+                compile_patterns(allowed_patterns).map(|_| ())
+            }
+        }
+    }
+
+    /// evaluate checks the rule against the measured events of `log`.
+    pub fn evaluate(&self, log: &EventLog, algorithm: HashAlgorithm) -> RuleResult {
+        let (passed, detail) = match self {
+            Self::BootloaderDigests { allowed_digests } => {
+                evaluate_bootloaders(log, algorithm, allowed_digests)
+            }
+            Self::SecureBootVariable {
+                variable,
+                allowed_digests,
+            } => evaluate_secure_boot_variable(log, algorithm, variable, allowed_digests),
+            Self::KernelCommandLine { allowed_patterns } => {
+                evaluate_kernel_cmdlines(log, allowed_patterns)
+            }
+        };
+        RuleResult {
+            rule: self.to_string(),
+            passed,
+            detail,
+        }
+    }
+}
+
+fn digest_allowed(event: &EventLogEvent, algorithm: HashAlgorithm, allowed: &[String]) -> bool {
+    event.digest(algorithm).is_some_and(|digest| {
+        let digest = hex::encode(digest);
+        allowed
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(&digest))
+    })
+}
+
+fn short_digest(event: &EventLogEvent, algorithm: HashAlgorithm) -> String {
+    event
+        .digest(algorithm)
+        .map(|digest| hex::encode(digest).chars().take(16).collect())
+        .unwrap_or_else(|| format!("<no {algorithm} digest>"))
+}
+
+fn evaluate_bootloaders(
+    log: &EventLog,
+    algorithm: HashAlgorithm,
+    allowed_digests: &[String],
+) -> (bool, String) {
+    let applications: Vec<&EventLogEvent> = log
+        .measurements()
+        .filter(|event| {
+            event.pcr_index == 4 && event.event_type == EventType::EFI_BOOT_SERVICES_APPLICATION
+        })
+        .collect();
+    if applications.is_empty() {
+        return (false, "no EFI application was measured".to_string());
+    }
+    if let Some(denied) = applications
+        .iter()
+        .find(|event| !digest_allowed(event, algorithm, allowed_digests))
+    {
+        return (
+            false,
+            format!(
+                "{} (digest {}) is not allowed",
+                denied.description(),
+                short_digest(denied, algorithm)
+            ),
+        );
+    }
+    (
+        true,
+        format!("all {} EFI applications are allowed", applications.len()),
+    )
+}
+
+fn evaluate_secure_boot_variable(
+    log: &EventLog,
+    algorithm: HashAlgorithm,
+    variable: &str,
+    allowed_digests: &[String],
+) -> (bool, String) {
+    // Use the last measurement, in case the firmware measured
+    // the variable more than once.
+    let measured = log
+        .measurements()
+        .filter(|event| {
+            event.pcr_index == 7 && event.event_type == EventType::EFI_VARIABLE_DRIVER_CONFIG
+        })
+        .filter(|event| matches!(event.detail(), EventDetail::EfiVariable { name, .. } if name == variable))
+        .last();
+    match measured {
+        None => (false, format!("{variable} was not measured")),
+        Some(event) if digest_allowed(event, algorithm, allowed_digests) => (
+            true,
+            format!(
+                "{variable} (digest {}) is allowed",
+                short_digest(event, algorithm)
+            ),
+        ),
+        Some(event) => (
+            false,
+            format!(
+                "{variable} (digest {}) is not allowed",
+                short_digest(event, algorithm)
+            ),
+        ),
+    }
+}
+
+fn evaluate_kernel_cmdlines(log: &EventLog, allowed_patterns: &[String]) -> (bool, String) {
+    let patterns = match compile_patterns(allowed_patterns) {
+        Ok(patterns) => patterns,
+        Err(e) => return (false, e.to_string()),
+    };
+    let cmdlines: Vec<String> = log
+        .measurements()
+        .filter(|event| event.event_type == EventType::IPL)
+        .filter_map(|event| match event.detail() {
+            EventDetail::Text(text) => text
+                .strip_prefix(KERNEL_CMDLINE_PREFIX)
+                .map(|cmdline| cmdline.trim().to_string()),
+            _ => None,
+        })
+        .collect();
+    if cmdlines.is_empty() {
+        return (false, "no kernel command line was measured".to_string());
+    }
+    if let Some(denied) = cmdlines
+        .iter()
+        .find(|cmdline| !patterns.iter().any(|pattern| pattern.is_match(cmdline)))
+    {
+        return (
+            false,
+            format!("kernel command line \"{denied}\" matches no allowed pattern"),
+        );
+    }
+    (
+        true,
+        format!("all {} kernel command lines are allowed", cmdlines.len()),
+    )
+}
+
+/// compile_patterns compiles the allowed patterns, anchored so a
+/// pattern has to match the whole command line.
+fn compile_patterns(patterns: &[String]) -> super::Result<Vec<Regex>> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            Regex::new(&format!("^(?:{pattern})$")).map_err(|e| {
+                super::Error::Parse(format!(
+                    "invalid kernel command line pattern {pattern}: {e}"
+                ))
+            })
+        })
+        .collect()
+}
+
+/// RuleResult is the outcome of evaluating a single rule.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RuleResult {
+    pub rule: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// PolicyEvaluation is the outcome of evaluating a policy against
+/// the event log of a report. A policy passes if all rules pass.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PolicyEvaluation {
+    pub policy_id: MeasurementPolicyId,
+    pub name: String,
+    pub passed: bool,
+    pub rules: Vec<RuleResult>,
+}
+
+/// MeasurementPolicy is a named set of rules. Policies without a
+/// profile_id apply to every system profile.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct MeasurementPolicy {
+    pub policy_id: MeasurementPolicyId,
+    pub name: String,
+    pub profile_id: Option<MeasurementSystemProfileId>,
+    pub rules: Vec<PolicyRule>,
+    pub ts: chrono::DateTime<Utc>,
+}
+
+impl MeasurementPolicy {
+    pub fn applies_to(&self, profile_id: MeasurementSystemProfileId) -> bool {
+        self.profile_id
+            .is_none_or(|policy_profile| policy_profile == profile_id)
+    }
+
+    pub fn evaluate(&self, log: &EventLog, algorithm: HashAlgorithm) -> PolicyEvaluation {
+        let rules: Vec<RuleResult> = self
+            .rules
+            .iter()
+            .map(|rule| rule.evaluate(log, algorithm))
+            .collect();
+        PolicyEvaluation {
+            policy_id: self.policy_id,
+            name: self.name.clone(),
+            passed: rules.iter().all(|rule| rule.passed),
+            rules,
+        }
+    }
+
+    ////////////////////////////////////////////////////////////
+    /// from_grpc takes an optional protobuf (as populated in a
+    /// proto response from the API) and attempts to convert it
+    /// to the backing model.
+    ////////////////////////////////////////////////////////////
+    pub fn from_grpc(some_pb: Option<&MeasurementPolicyPb>) -> super::Result<Self> {
+        some_pb
+            .ok_or(super::Error::RpcConversion(
+                "policy is unexpectedly empty".to_string(),
+            ))
+            .and_then(|pb| {
+                Self::try_from(pb.clone()).map_err(|e| {
+                    super::Error::RpcConversion(format!("policy failed pb->model conversion: {e}"))
+                })
+            })
+    }
+}
+
+/// evaluate_policies evaluates every policy that applies to
+/// `profile_id` against the event log.
+pub fn evaluate_policies(
+    policies: &[MeasurementPolicy],
+    profile_id: MeasurementSystemProfileId,
+    log: &EventLog,
+    algorithm: HashAlgorithm,
+) -> Vec<PolicyEvaluation> {
+    policies
+        .iter()
+        .filter(|policy| policy.applies_to(profile_id))
+        .map(|policy| policy.evaluate(log, algorithm))
+        .collect()
+}
+
+impl From<PolicyRule> for MeasurementPolicyRulePb {
+    fn from(val: PolicyRule) -> Self {
+        let rule = match val {
+            PolicyRule::BootloaderDigests { allowed_digests } => {
+                measurement_policy_rule_pb::Rule::Bootloader(MeasurementBootloaderRulePb {
+                    allowed_digests,
+                })
+            }
+            PolicyRule::SecureBootVariable {
+                variable,
+                allowed_digests,
+            } => measurement_policy_rule_pb::Rule::SecureBoot(MeasurementSecureBootRulePb {
+                variable,
+                allowed_digests,
+            }),
+            PolicyRule::KernelCommandLine { allowed_patterns } => {
+                measurement_policy_rule_pb::Rule::KernelCmdline(MeasurementKernelCmdlineRulePb {
+                    allowed_patterns,
+                })
+            }
+        };
+        Self { rule: Some(rule) }
+    }
+}
+
+impl TryFrom<MeasurementPolicyRulePb> for PolicyRule {
+    type Error = RpcDataConversionError;
+
+    fn try_from(msg: MeasurementPolicyRulePb) -> Result<Self, RpcDataConversionError> {
+        Ok(
+            match msg
+                .rule
+                .ok_or(RpcDataConversionError::MissingArgument("rule"))?
+            {
+                measurement_policy_rule_pb::Rule::Bootloader(rule) => Self::BootloaderDigests {
+                    allowed_digests: rule.allowed_digests,
+                },
+                measurement_policy_rule_pb::Rule::SecureBoot(rule) => Self::SecureBootVariable {
+                    variable: rule.variable,
+                    allowed_digests: rule.allowed_digests,
+                },
+                measurement_policy_rule_pb::Rule::KernelCmdline(rule) => Self::KernelCommandLine {
+                    allowed_patterns: rule.allowed_patterns,
+                },
+            },
+        )
+    }
+}
+
+impl From<MeasurementPolicy> for MeasurementPolicyPb {
+    fn from(val: MeasurementPolicy) -> Self {
+        Self {
+            policy_id: Some(val.policy_id),
+            name: val.name,
+            profile_id: val.profile_id,
+            rules: val.rules.into_iter().map(Into::into).collect(),
+            ts: Some(val.ts.into()),
+        }
+    }
+}
+
+impl TryFrom<MeasurementPolicyPb> for MeasurementPolicy {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(msg: MeasurementPolicyPb) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            policy_id: msg
+                .policy_id
+                .ok_or(RpcDataConversionError::MissingArgument("policy_id"))?,
+            name: msg.name,
+            profile_id: msg.profile_id,
+            rules: msg
+                .rules
+                .into_iter()
+                .map(PolicyRule::try_from)
+                .collect::<Result<Vec<PolicyRule>, RpcDataConversionError>>()?,
+            ts: chrono::DateTime::<chrono::Utc>::try_from(
+                msg.ts
+                    .ok_or(RpcDataConversionError::MissingArgument("ts"))?,
+            )?,
+        })
+    }
+}
+
+impl From<PolicyEvaluation> for MeasurementPolicyEvaluationPb {
+    fn from(val: PolicyEvaluation) -> Self {
+        Self {
+            policy_id: Some(val.policy_id),
+            name: val.name,
+            passed: val.passed,
+            rules: val
+                .rules
+                .into_iter()
+                .map(|rule| MeasurementRuleResultPb {
+                    rule: rule.rule,
+                    passed: rule.passed,
+                    detail: rule.detail,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<MeasurementPolicyEvaluationPb> for PolicyEvaluation {
+    type Error = RpcDataConversionError;
+
+    fn try_from(msg: MeasurementPolicyEvaluationPb) -> Result<Self, RpcDataConversionError> {
+        Ok(Self {
+            policy_id: msg
+                .policy_id
+                .ok_or(RpcDataConversionError::MissingArgument("policy_id"))?,
+            name: msg.name,
+            passed: msg.passed,
+            rules: msg
+                .rules
+                .into_iter()
+                .map(|rule| RuleResult {
+                    rule: rule.rule,
+                    passed: rule.passed,
+                    detail: rule.detail,
+                })
+                .collect(),
+        })
+    }
+}
+
+// When `policy show` gets called, and the output format is
+// the default table view, this gets used to print a pretty table.
+#[cfg(feature = "cli")]
+impl ToTable for MeasurementPolicy {
+    fn into_table(self) -> eyre::Result<String> {
+        let mut table = prettytable::Table::new();
+        let mut rules_table = prettytable::Table::new();
+        rules_table.add_row(prettytable::row!["rule", "allowed"]);
+        for rule in self.rules.iter() {
+            let allowed = match rule {
+                PolicyRule::BootloaderDigests { allowed_digests }
+                | PolicyRule::SecureBootVariable {
+                    allowed_digests, ..
+                } => allowed_digests.join("\n"),
+                PolicyRule::KernelCommandLine { allowed_patterns } => allowed_patterns.join("\n"),
+            };
+            rules_table.add_row(prettytable::row![rule, allowed]);
+        }
+        table.add_row(prettytable::row!["policy_id", self.policy_id]);
+        table.add_row(prettytable::row!["name", self.name]);
+        table.add_row(prettytable::row![
+            "profile_id",
+            match self.profile_id {
+                Some(profile_id) => profile_id.to_string(),
+                None => "<all profiles>".to_string(),
+            }
+        ]);
+        table.add_row(prettytable::row!["created_ts", self.ts]);
+        table.add_row(prettytable::row!["rules", rules_table]);
+        Ok(table.to_string())
+    }
+}
+
+#[cfg(feature = "cli")]
+impl PolicyEvaluation {
+    /// to_nested_prettytable renders the per-rule outcome of a policy,
+    /// for showing as part of a report.
+    pub fn to_nested_prettytable(&self) -> prettytable::Table {
+        let mut table = prettytable::Table::new();
+        table.add_row(prettytable::row![
+            "policy",
+            self.name,
+            if self.passed { "passed" } else { "failed" }
+        ]);
+        for rule in self.rules.iter() {
+            table.add_row(prettytable::row![
+                rule.rule,
+                if rule.passed { "passed" } else { "failed" },
+                rule.detail
+            ]);
+        }
+        table
+    }
+}
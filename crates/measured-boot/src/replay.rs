@@ -0,0 +1,510 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/*!
+ *  PCR replay and event-level diffing on top of a parsed event log.
+ *
+ *  Replaying extends every measured event digest into a virtual PCR bank,
+ *  which can then be compared against the PCR values from a quote. When a
+ *  report doesn't match, diffing its event log against a reference log
+ *  (e.g. from the last report that matched a bundle) shows which EFI
+ *  image, boot variable or Secure Boot database change moved the PCRs.
+ */
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use carbide_uuid::measured_boot::{MeasurementReportId, MeasurementSystemProfileId};
+#[cfg(feature = "cli")]
+use rpc::admin_cli::ToTable;
+use rpc::protos::measured_boot::{
+    MeasurementEventChangePb, MeasurementEventDiffPb, MeasurementEventLogAnalysisPb,
+    MeasurementPcrReplayPb,
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+use super::event_log::{EventLog, EventLogEvent, HashAlgorithm};
+use super::pcr::PcrRegisterValue;
+use super::policy::{MeasurementPolicy, PolicyEvaluation, evaluate_policies};
+
+/// replay_pcrs replays every measured event in the log into a fresh
+/// PCR bank for `algorithm`, returning the resulting value of every
+/// PCR the log touched.
+pub fn replay_pcrs(
+    log: &EventLog,
+    algorithm: HashAlgorithm,
+) -> super::Result<BTreeMap<u32, Vec<u8>>> {
+    let mut pcrs: BTreeMap<u32, Vec<u8>> = BTreeMap::new();
+    for event in log.events.iter() {
+        if let Some(locality) = event.startup_locality() {
+            let mut initial = vec![0u8; algorithm.digest_size()];
+            if let Some(last) = initial.last_mut() {
+                *last = locality;
+            }
+            pcrs.insert(0, initial);
+            continue;
+        }
+        if !event.is_extended() {
+            continue;
+        }
+        let digest = event.digest(algorithm).ok_or_else(|| {
+            super::Error::Replay(format!(
+                "event {} ({}) has no {algorithm} digest",
+                event.index, event.event_type
+            ))
+        })?;
+        let current = pcrs
+            .entry(event.pcr_index)
+            .or_insert_with(|| vec![0u8; algorithm.digest_size()]);
+        *current = extend(algorithm, current, digest)?;
+    }
+    Ok(pcrs)
+}
+
+fn extend(algorithm: HashAlgorithm, current: &[u8], digest: &[u8]) -> super::Result<Vec<u8>> {
+    let extended = match algorithm {
+        HashAlgorithm::Sha256 => Sha256::new()
+            .chain_update(current)
+            .chain_update(digest)
+            .finalize()
+            .to_vec(),
+        HashAlgorithm::Sha384 => Sha384::new()
+            .chain_update(current)
+            .chain_update(digest)
+            .finalize()
+            .to_vec(),
+        HashAlgorithm::Sha512 => Sha512::new()
+            .chain_update(current)
+            .chain_update(digest)
+            .finalize()
+            .to_vec(),
+        HashAlgorithm::Sha1 | HashAlgorithm::Sm3_256 => {
+            return Err(super::Error::Replay(format!(
+                "replaying the {algorithm} bank is not supported"
+            )));
+        }
+    };
+    Ok(extended)
+}
+
+/// PcrReplay is the result of replaying the event log for a
+/// single quoted PCR register.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PcrReplay {
+    pub pcr_register: i16,
+    pub quoted: String,
+    // replayed is None when no event in the log was extended into
+    // this register (e.g. PCRs owned by the OS), in which case the
+    // log can't vouch for the quoted value either way.
+    pub replayed: Option<String>,
+}
+
+impl PcrReplay {
+    pub fn matches(&self) -> bool {
+        match &self.replayed {
+            Some(replayed) => replayed.eq_ignore_ascii_case(&self.quoted),
+            None => true,
+        }
+    }
+}
+
+/// EventChange describes how an event differs from the reference log.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum EventChange {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl From<EventChange> for MeasurementEventChangePb {
+    fn from(val: EventChange) -> Self {
+        match val {
+            EventChange::Added => Self::Added,
+            EventChange::Removed => Self::Removed,
+            EventChange::Changed => Self::Changed,
+        }
+    }
+}
+
+impl From<MeasurementEventChangePb> for EventChange {
+    fn from(msg: MeasurementEventChangePb) -> Self {
+        match msg {
+            MeasurementEventChangePb::Added => Self::Added,
+            MeasurementEventChangePb::Removed => Self::Removed,
+            MeasurementEventChangePb::Changed => Self::Changed,
+        }
+    }
+}
+
+/// EventLogDiff is a single event-level difference between a
+/// reference event log and the event log of a report.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct EventLogDiff {
+    pub pcr_register: i16,
+    pub change: EventChange,
+    pub event_type: String,
+    pub description: String,
+    pub reference_digest: Option<String>,
+    pub digest: Option<String>,
+}
+
+/// diff_event_logs compares the measured events of `current` against
+/// `reference`, PCR by PCR, in the `algorithm` bank. Events are lined up
+/// by digest; an unmatched event on both sides that refers to the same
+/// thing (same variable, same image path, etc) is reported as Changed.
+pub fn diff_event_logs(
+    reference: &EventLog,
+    current: &EventLog,
+    algorithm: HashAlgorithm,
+) -> Vec<EventLogDiff> {
+    let mut by_pcr: BTreeMap<u32, (Vec<&EventLogEvent>, Vec<&EventLogEvent>)> = BTreeMap::new();
+    for event in reference.measurements() {
+        by_pcr.entry(event.pcr_index).or_default().0.push(event);
+    }
+    for event in current.measurements() {
+        by_pcr.entry(event.pcr_index).or_default().1.push(event);
+    }
+
+    by_pcr
+        .into_iter()
+        .flat_map(|(pcr_index, (reference, current))| {
+            diff_pcr_events(pcr_index, &reference, &current, algorithm)
+        })
+        .collect()
+}
+
+fn diff_pcr_events(
+    pcr_index: u32,
+    reference: &[&EventLogEvent],
+    current: &[&EventLogEvent],
+    algorithm: HashAlgorithm,
+) -> Vec<EventLogDiff> {
+    let same = |a: &EventLogEvent, b: &EventLogEvent| {
+        a.event_type == b.event_type && a.digest(algorithm) == b.digest(algorithm)
+    };
+
+    // Longest common subsequence over (event type, digest), so that an
+    // inserted driver doesn't make every following event look changed.
+    let (n, m) = (reference.len(), current.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if same(reference[i], current[j]) {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut unmatched: Vec<(EventChange, &EventLogEvent)> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && same(reference[i], current[j]) {
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            unmatched.push((EventChange::Added, current[j]));
+            j += 1;
+        } else {
+            unmatched.push((EventChange::Removed, reference[i]));
+            i += 1;
+        }
+    }
+
+    // Pair up removed and added events that describe the same thing.
+    let mut consumed = vec![false; unmatched.len()];
+    let mut diffs = Vec::new();
+    for idx in 0..unmatched.len() {
+        if consumed[idx] {
+            continue;
+        }
+        let (change, event) = unmatched[idx];
+        let identity = event.detail().identity(event.event_type);
+        let opposite = match change {
+            EventChange::Added => EventChange::Removed,
+            _ => EventChange::Added,
+        };
+        let partner = (idx + 1..unmatched.len()).find(|other| {
+            !consumed[*other]
+                && unmatched[*other].0 == opposite
+                && unmatched[*other]
+                    .1
+                    .detail()
+                    .identity(unmatched[*other].1.event_type)
+                    == identity
+        });
+
+        let hex_digest = |event: &EventLogEvent| event.digest(algorithm).map(hex::encode);
+        let diff = match partner {
+            Some(other) => {
+                consumed[other] = true;
+                let (old, new) = match change {
+                    EventChange::Removed => (event, unmatched[other].1),
+                    _ => (unmatched[other].1, event),
+                };
+                EventLogDiff {
+                    pcr_register: pcr_index as i16,
+                    change: EventChange::Changed,
+                    event_type: new.event_type.to_string(),
+                    description: new.description(),
+                    reference_digest: hex_digest(old),
+                    digest: hex_digest(new),
+                }
+            }
+            None => EventLogDiff {
+                pcr_register: pcr_index as i16,
+                change,
+                event_type: event.event_type.to_string(),
+                description: event.description(),
+                reference_digest: match change {
+                    EventChange::Removed => hex_digest(event),
+                    _ => None,
+                },
+                digest: match change {
+                    EventChange::Removed => None,
+                    _ => hex_digest(event),
+                },
+            },
+        };
+        diffs.push(diff);
+    }
+    diffs
+}
+
+/// EventLogAnalysis is what gets attached to a MeasurementReport when
+/// the machine sent a binary event log along with its quote: the replay
+/// of the log against the quoted PCR values and, if a reference log was
+/// available, the event-level differences against it, along with the
+/// outcome of any measurement policies that apply to the machine.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct EventLogAnalysis {
+    pub algorithm: HashAlgorithm,
+    pub event_count: usize,
+    pub pcrs: Vec<PcrReplay>,
+    pub reference_report_id: Option<MeasurementReportId>,
+    pub diffs: Vec<EventLogDiff>,
+    pub policies: Vec<PolicyEvaluation>,
+}
+
+/// quoted_algorithm picks the PCR bank from the size of the
+/// quoted values.
+pub fn quoted_algorithm(quoted: &[PcrRegisterValue]) -> super::Result<HashAlgorithm> {
+    quoted
+        .first()
+        .and_then(|value| HashAlgorithm::for_digest_size(value.sha_any.len() / 2))
+        .ok_or_else(|| {
+            super::Error::Replay("cannot determine PCR bank from quoted values".to_string())
+        })
+}
+
+impl EventLogAnalysis {
+    /// analyze replays `log` against the quoted PCR values, and diffs
+    /// it against the reference log (if any). The bank to replay is
+    /// picked from the size of the quoted values.
+    pub fn analyze(
+        log: &EventLog,
+        quoted: &[PcrRegisterValue],
+        reference: Option<(MeasurementReportId, &EventLog)>,
+    ) -> super::Result<Self> {
+        let algorithm = quoted_algorithm(quoted)?;
+
+        let replayed = replay_pcrs(log, algorithm)?;
+        let pcrs = quoted
+            .iter()
+            .map(|value| PcrReplay {
+                pcr_register: value.pcr_register,
+                quoted: value.sha_any.clone(),
+                replayed: replayed.get(&(value.pcr_register as u32)).map(hex::encode),
+            })
+            .collect();
+
+        let (reference_report_id, diffs) = match reference {
+            Some((report_id, reference_log)) => (
+                Some(report_id),
+                diff_event_logs(reference_log, log, algorithm),
+            ),
+            None => (None, Vec::new()),
+        };
+
+        Ok(Self {
+            algorithm,
+            event_count: log.events.len(),
+            pcrs,
+            reference_report_id,
+            diffs,
+            policies: Vec::new(),
+        })
+    }
+
+    /// with_policies evaluates the policies that apply to `profile_id`
+    /// against `log`, the same log the analysis was made from.
+    pub fn with_policies(
+        mut self,
+        policies: &[MeasurementPolicy],
+        profile_id: MeasurementSystemProfileId,
+        log: &EventLog,
+    ) -> Self {
+        self.policies = evaluate_policies(policies, profile_id, log, self.algorithm);
+        self
+    }
+
+    /// is_consistent returns whether the log replays to the quoted
+    /// values for every PCR it covers.
+    pub fn is_consistent(&self) -> bool {
+        self.pcrs.iter().all(PcrReplay::matches)
+    }
+
+    pub fn mismatched_pcrs(&self) -> Vec<i16> {
+        self.pcrs
+            .iter()
+            .filter(|pcr| !pcr.matches())
+            .map(|pcr| pcr.pcr_register)
+            .collect()
+    }
+}
+
+impl From<EventLogAnalysis> for MeasurementEventLogAnalysisPb {
+    fn from(val: EventLogAnalysis) -> Self {
+        Self {
+            algorithm: val.algorithm.to_string(),
+            event_count: val.event_count as u32,
+            pcrs: val
+                .pcrs
+                .into_iter()
+                .map(|pcr| MeasurementPcrReplayPb {
+                    pcr_register: pcr.pcr_register as i32,
+                    quoted: pcr.quoted,
+                    replayed: pcr.replayed,
+                })
+                .collect(),
+            reference_report_id: val.reference_report_id,
+            diffs: val
+                .diffs
+                .into_iter()
+                .map(|diff| MeasurementEventDiffPb {
+                    pcr_register: diff.pcr_register as i32,
+                    change: MeasurementEventChangePb::from(diff.change) as i32,
+                    event_type: diff.event_type,
+                    description: diff.description,
+                    reference_digest: diff.reference_digest,
+                    digest: diff.digest,
+                })
+                .collect(),
+            policies: val.policies.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<MeasurementEventLogAnalysisPb> for EventLogAnalysis {
+    type Error = super::Error;
+
+    fn try_from(msg: MeasurementEventLogAnalysisPb) -> super::Result<Self> {
+        let diffs = msg
+            .diffs
+            .into_iter()
+            .map(|diff| EventLogDiff {
+                pcr_register: diff.pcr_register as i16,
+                change: diff.change().into(),
+                event_type: diff.event_type,
+                description: diff.description,
+                reference_digest: diff.reference_digest,
+                digest: diff.digest,
+            })
+            .collect();
+
+        Ok(Self {
+            algorithm: HashAlgorithm::from_str(&msg.algorithm)?,
+            event_count: msg.event_count as usize,
+            pcrs: msg
+                .pcrs
+                .into_iter()
+                .map(|pcr| PcrReplay {
+                    pcr_register: pcr.pcr_register as i16,
+                    quoted: pcr.quoted,
+                    replayed: pcr.replayed,
+                })
+                .collect(),
+            reference_report_id: msg.reference_report_id,
+            diffs,
+            policies: msg
+                .policies
+                .into_iter()
+                .map(PolicyEvaluation::try_from)
+                .collect::<Result<Vec<PolicyEvaluation>, _>>()
+                .map_err(|e| super::Error::RpcConversion(e.to_string()))?,
+        })
+    }
+}
+
+// Rendered as part of `report show id <report-id>`.
+#[cfg(feature = "cli")]
+impl ToTable for EventLogAnalysis {
+    fn into_table(self) -> eyre::Result<String> {
+        let mut table = prettytable::Table::new();
+        let mut pcrs_table = prettytable::Table::new();
+        pcrs_table.add_row(prettytable::row![
+            "pcr_register",
+            "quoted",
+            "replayed",
+            "matches"
+        ]);
+        for pcr in self.pcrs.iter() {
+            pcrs_table.add_row(prettytable::row![
+                pcr.pcr_register,
+                pcr.quoted,
+                pcr.replayed.as_deref().unwrap_or("-"),
+                pcr.matches()
+            ]);
+        }
+        let mut diffs_table = prettytable::Table::new();
+        diffs_table.add_row(prettytable::row![
+            "pcr_register",
+            "change",
+            "event_type",
+            "description"
+        ]);
+        for diff in self.diffs.iter() {
+            diffs_table.add_row(prettytable::row![
+                diff.pcr_register,
+                format!("{:?}", diff.change),
+                diff.event_type,
+                diff.description
+            ]);
+        }
+        table.add_row(prettytable::row!["algorithm", self.algorithm]);
+        table.add_row(prettytable::row!["event_count", self.event_count]);
+        table.add_row(prettytable::row!["replay", pcrs_table]);
+        match self.reference_report_id {
+            Some(reference_report_id) => {
+                table.add_row(prettytable::row![
+                    "reference_report_id",
+                    reference_report_id
+                ]);
+                table.add_row(prettytable::row!["diffs", diffs_table]);
+            }
+            None => {
+                table.add_row(prettytable::row!["reference_report_id", "-"]);
+            }
+        }
+        for policy in self.policies.iter() {
+            table.add_row(prettytable::row!["policy", policy.to_nested_prettytable()]);
+        }
+        Ok(table.to_string())
+    }
+}
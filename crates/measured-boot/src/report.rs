@@ -34,17 +34,24 @@ use serde::Serialize;
 
 use super::pcr::PcrRegisterValue;
 use super::records::MeasurementReportValueRecord;
+use super::replay::EventLogAnalysis;
 
 /// MeasurementReport is a composition of a MeasurementReportRecord,
 /// whose attributes are essentially copied directly it, as well as
 /// the associated attributes (which are complete instances of
 /// MeasurementReportValueRecord, along with its UUID and timestamp).
+///
+/// If the machine sent a binary event log with its quote, event_log
+/// carries the replay of that log against the values (and the diff
+/// against a reference log); it is only populated when a single
+/// report is being shown.
 #[derive(Debug, Serialize, Clone)]
 pub struct MeasurementReport {
     pub report_id: MeasurementReportId,
     pub machine_id: MachineId,
     pub ts: chrono::DateTime<Utc>,
     pub values: Vec<MeasurementReportValueRecord>,
+    pub event_log: Option<EventLogAnalysis>,
 }
 
 impl MeasurementReport {
@@ -82,6 +89,7 @@ impl From<MeasurementReport> for MeasurementReportPb {
                 .map(|value| value.clone().into())
                 .collect(),
             ts: Some(val.ts.into()),
+            event_log: val.event_log.map(Into::into),
         }
     }
 }
@@ -113,6 +121,7 @@ impl TryFrom<MeasurementReportPb> for MeasurementReport {
             machine_id: MachineId::from_str(&msg.machine_id)?,
             values: values?,
             ts: chrono::DateTime::<chrono::Utc>::try_from(msg.ts.unwrap())?,
+            event_log: msg.event_log.map(EventLogAnalysis::try_from).transpose()?,
         })
     }
 }
@@ -135,6 +144,9 @@ impl ToTable for MeasurementReport {
         table.add_row(prettytable::row!["machine_id", self.machine_id]);
         table.add_row(prettytable::row!["created_ts", self.ts]);
         table.add_row(prettytable::row!["values", values_table]);
+        if let Some(event_log) = self.event_log {
+            table.add_row(prettytable::row!["event_log", event_log.into_table()?]);
+        }
         Ok(table.to_string())
     }
 }
@@ -0,0 +1,58 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! gRPC API for inspecting the artifact cache.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use ::rpc::artifact_cache::artifact_cache_server::{self, ArtifactCacheServer};
+use ::rpc::artifact_cache::{ArtifactCacheStatus, GetArtifactCacheStatusRequest};
+use tonic::{Request, Response, Status};
+
+use crate::artifact_cache::ArtifactCache;
+
+/// Serves the artifact cache API on `address` in the background.
+pub(crate) fn spawn(address: SocketAddr, cache: Arc<ArtifactCache>) {
+    tokio::spawn(async move {
+        println!("Artifact cache API listening on {address}");
+        if let Err(err) = tonic::transport::Server::builder()
+            .add_service(ArtifactCacheServer::new(ArtifactCacheApi { cache }))
+            .serve(address)
+            .await
+        {
+            eprintln!("Artifact cache API failed: {err}");
+        }
+    });
+}
+
+struct ArtifactCacheApi {
+    cache: Arc<ArtifactCache>,
+}
+
+#[tonic::async_trait]
+impl artifact_cache_server::ArtifactCache for ArtifactCacheApi {
+    async fn get_artifact_cache_status(
+        &self,
+        request: Request<GetArtifactCacheStatusRequest>,
+    ) -> Result<Response<ArtifactCacheStatus>, Status> {
+        let request = request.into_inner();
+        Ok(Response::new(
+            self.cache.status(request.operating_system_id),
+        ))
+    }
+}
@@ -0,0 +1,707 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Cache for iPXE template artifacts.
+//!
+//! Operating system definitions can mark their artifacts (kernels, initrds, images, ...) as
+//! `CACHE_AS_NEEDED` or `CACHED_ONLY`. The cache periodically lists all operating systems from
+//! carbide-api, downloads their cachable artifacts, verifies them against the recorded sha256 and
+//! stores them content-addressed (see [`store`]). Cached artifacts are served by carbide-pxe under
+//! `/artifacts/sha256/<digest>/<file name>`, and the artifacts' `cached_url` is pointed at that
+//! location, so hosts boot from the local copy.
+//!
+//! When the cache is full, blobs no longer referenced by any artifact are evicted first, then
+//! blobs of `CACHE_AS_NEEDED` artifacts, least recently served first. Blobs of `CACHED_ONLY`
+//! artifacts are never evicted, since those artifacts can't be booted without them. Downloads of
+//! `CACHE_AS_NEEDED` artifacts only evict unreferenced blobs, so such artifacts don't keep
+//! evicting each other.
+
+pub(crate) mod api_server;
+mod store;
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use ::rpc::artifact_cache::{self, ArtifactCacheState, ArtifactStatus};
+use ::rpc::forge::{self as rpc, IpxeTemplateArtifact, IpxeTemplateArtifactCacheStrategy};
+use ::rpc::forge_api_client::ForgeApiClient;
+use ::rpc::forge_tls_client::{ApiConfig, ForgeClientConfig};
+use carbide_uuid::operating_system::OperatingSystemId;
+use forge_tls::client_config::ClientCert;
+use futures_util::StreamExt;
+use metrics::{counter, gauge};
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncWriteExt;
+
+use self::store::{BlobStore, CacheFull, is_sha256_digest};
+use crate::config::{ArtifactCacheConfig, RuntimeConfig};
+
+type ArtifactKey = (OperatingSystemId, u32);
+
+#[derive(Debug)]
+pub(crate) struct ArtifactCache {
+    store: Mutex<BlobStore>,
+    state: Mutex<CacheState>,
+    /// Prefix of the cached_url of artifacts cached here
+    cached_url_base: String,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    artifacts: BTreeMap<ArtifactKey, ArtifactStatus>,
+    /// Digests of artifacts without a recorded sha, as first downloaded, by URL
+    unverified_digests: HashMap<String, String>,
+    last_sync_time: Option<SystemTime>,
+}
+
+/// Digests referenced by the artifacts of all operating systems
+#[derive(Default)]
+struct References {
+    all: HashSet<String>,
+    cached_only: HashSet<String>,
+}
+
+enum DownloadError {
+    Full(CacheFull),
+    Failed(String),
+}
+
+impl<E: Display> From<E> for DownloadError {
+    fn from(err: E) -> Self {
+        Self::Failed(err.to_string())
+    }
+}
+
+impl ArtifactCache {
+    /// Opens the cache and starts synchronizing it with carbide-api in the background.
+    pub(crate) fn start(
+        config: &ArtifactCacheConfig,
+        runtime_config: &RuntimeConfig,
+    ) -> Result<Arc<Self>, String> {
+        let store =
+            BlobStore::open(&config.cache_directory, config.max_size_bytes).map_err(|err| {
+                format!(
+                    "Could not open artifact cache at {}: {err}",
+                    config.cache_directory.display()
+                )
+            })?;
+        gauge!("artifact_cache_used_bytes").set(store.used_bytes() as f64);
+
+        let cache = Arc::new(Self {
+            store: Mutex::new(store),
+            state: Mutex::new(CacheState::default()),
+            cached_url_base: format!("{}/artifacts/sha256/", runtime_config.pxe_url),
+        });
+
+        let api_client = ForgeApiClient::new(&ApiConfig::new(
+            &runtime_config.internal_api_url,
+            &ForgeClientConfig::new(
+                runtime_config.forge_root_ca_path.clone(),
+                Some(ClientCert {
+                    cert_path: runtime_config.server_cert_path.clone(),
+                    key_path: runtime_config.server_key_path.clone(),
+                }),
+            ),
+        ));
+        let http_client = reqwest::Client::builder()
+            .connect_timeout(std::time::Duration::from_secs(30))
+            .read_timeout(std::time::Duration::from_secs(60))
+            .build()
+            .map_err(|err| format!("Could not build artifact download client: {err}"))?;
+
+        let sync_interval = config.sync_interval;
+        let sync_cache = cache.clone();
+        tokio::spawn(async move {
+            loop {
+                if let Err(err) = sync_cache.sync(&api_client, &http_client).await {
+                    eprintln!("Error synchronizing artifact cache: {err}");
+                }
+                tokio::time::sleep(sync_interval).await;
+            }
+        });
+
+        Ok(cache)
+    }
+
+    /// Returns the path of a cached blob to serve, and records the access for eviction.
+    pub(crate) fn serve(&self, sha256: &str) -> Option<PathBuf> {
+        if !is_sha256_digest(sha256) {
+            return None;
+        }
+        let mut store = self.store.lock().unwrap();
+        if !store.contains(sha256) {
+            return None;
+        }
+        store.touch(sha256);
+        Some(store.blob_path(sha256))
+    }
+
+    pub(crate) fn status(
+        &self,
+        operating_system_id: Option<OperatingSystemId>,
+    ) -> artifact_cache::ArtifactCacheStatus {
+        let (used_bytes, max_bytes) = {
+            let store = self.store.lock().unwrap();
+            (store.used_bytes(), store.max_size_bytes())
+        };
+        let state = self.state.lock().unwrap();
+        artifact_cache::ArtifactCacheStatus {
+            artifacts: state
+                .artifacts
+                .iter()
+                .filter(|((os_id, _), _)| operating_system_id.is_none_or(|id| id == *os_id))
+                .map(|(_, status)| status.clone())
+                .collect(),
+            used_bytes,
+            max_bytes,
+            last_sync_time: state.last_sync_time.map(Into::into),
+        }
+    }
+
+    async fn sync(
+        &self,
+        api_client: &ForgeApiClient,
+        http_client: &reqwest::Client,
+    ) -> Result<(), String> {
+        let os_ids = api_client
+            .find_operating_system_ids(rpc::OperatingSystemSearchFilter {
+                tenant_organization_id: None,
+            })
+            .await
+            .map_err(|err| format!("Error listing operating systems: {err}"))?
+            .ids;
+
+        let mut operating_systems = Vec::with_capacity(os_ids.len());
+        for os_id in os_ids {
+            match api_client
+                .get_operating_system_cachable_ipxe_template_artifacts(
+                    rpc::GetOperatingSystemCachableIpxeTemplateArtifactsRequest { id: Some(os_id) },
+                )
+                .await
+            {
+                Ok(list) => operating_systems.push((os_id, list.artifacts)),
+                // Deleted since it was listed
+                Err(status) if status.code() == tonic::Code::NotFound => {}
+                Err(status) => {
+                    return Err(format!(
+                        "Error fetching artifacts of operating system {os_id}: {status}"
+                    ));
+                }
+            }
+        }
+
+        self.forget_removed_artifacts(&operating_systems);
+        let references = self.references(&operating_systems);
+
+        for (os_id, artifacts) in &operating_systems {
+            for (index, artifact) in artifacts.iter().enumerate() {
+                if let Some(strategy) = cachable_strategy(artifact) {
+                    self.cache_artifact(
+                        (*os_id, index as u32),
+                        artifact,
+                        strategy,
+                        &references,
+                        http_client,
+                    )
+                    .await;
+                }
+            }
+        }
+
+        // Only update cached_url once everything is downloaded, since later downloads might have
+        // evicted blobs cached earlier
+        for (os_id, artifacts) in &operating_systems {
+            self.update_cached_urls(api_client, *os_id, artifacts).await;
+        }
+
+        let used_bytes = self.store.lock().unwrap().used_bytes();
+        gauge!("artifact_cache_used_bytes").set(used_bytes as f64);
+        self.state.lock().unwrap().last_sync_time = Some(SystemTime::now());
+        Ok(())
+    }
+
+    /// Drops the status of artifacts which no longer exist.
+    fn forget_removed_artifacts(
+        &self,
+        operating_systems: &[(OperatingSystemId, Vec<IpxeTemplateArtifact>)],
+    ) {
+        let existing = operating_systems
+            .iter()
+            .flat_map(|(os_id, artifacts)| {
+                artifacts
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, artifact)| cachable_strategy(artifact).is_some())
+                    .map(|(index, _)| (*os_id, index as u32))
+            })
+            .collect::<HashSet<_>>();
+        self.state
+            .lock()
+            .unwrap()
+            .artifacts
+            .retain(|key, _| existing.contains(key));
+    }
+
+    fn references(
+        &self,
+        operating_systems: &[(OperatingSystemId, Vec<IpxeTemplateArtifact>)],
+    ) -> References {
+        let state = self.state.lock().unwrap();
+        let mut references = References::default();
+        for (_, artifacts) in operating_systems {
+            for artifact in artifacts {
+                let Some(strategy) = cachable_strategy(artifact) else {
+                    continue;
+                };
+                let digest = match expected_sha256(artifact) {
+                    Ok(Some(digest)) => digest,
+                    Ok(None) => match state.unverified_digests.get(&artifact.url) {
+                        Some(digest) => digest.clone(),
+                        None => continue,
+                    },
+                    Err(_) => continue,
+                };
+                if strategy == IpxeTemplateArtifactCacheStrategy::CachedOnly {
+                    references.cached_only.insert(digest.clone());
+                }
+                references.all.insert(digest);
+            }
+        }
+        references
+    }
+
+    /// Makes sure `artifact` is cached, downloading it if necessary, and records its status.
+    async fn cache_artifact(
+        &self,
+        key: ArtifactKey,
+        artifact: &IpxeTemplateArtifact,
+        strategy: IpxeTemplateArtifactCacheStrategy,
+        references: &References,
+        http_client: &reqwest::Client,
+    ) {
+        let expected = match expected_sha256(artifact) {
+            Ok(expected) => expected,
+            Err(err) => {
+                self.set_status(key, artifact, |status| {
+                    status.set_state(ArtifactCacheState::Failed);
+                    status.error = Some(err);
+                });
+                return;
+            }
+        };
+        let verified = expected.is_some();
+        let known_digest = expected.clone().or_else(|| {
+            self.state
+                .lock()
+                .unwrap()
+                .unverified_digests
+                .get(&artifact.url)
+                .cloned()
+        });
+
+        if let Some(digest) = known_digest {
+            let size = self.store.lock().unwrap().size(&digest);
+            if let Some(size) = size {
+                self.set_status(key, artifact, |status| {
+                    status.set_state(ArtifactCacheState::Cached);
+                    status.sha256 = Some(digest);
+                    status.verified = verified;
+                    status.size_bytes = Some(size);
+                    status.error = None;
+                });
+                return;
+            }
+        }
+
+        self.set_status(key, artifact, |status| {
+            status.set_state(ArtifactCacheState::Downloading);
+        });
+        // CACHE_AS_NEEDED artifacts may only replace blobs nothing references anymore
+        let pinned = match strategy {
+            IpxeTemplateArtifactCacheStrategy::CachedOnly => &references.cached_only,
+            _ => &references.all,
+        };
+
+        match self
+            .download(
+                artifact,
+                expected.as_deref(),
+                pinned,
+                &references.all,
+                http_client,
+            )
+            .await
+        {
+            Ok((digest, size)) => {
+                counter!("artifact_cache_downloads_total", "result" => "success").increment(1);
+                if !verified {
+                    self.state
+                        .lock()
+                        .unwrap()
+                        .unverified_digests
+                        .insert(artifact.url.clone(), digest.clone());
+                }
+                self.set_status(key, artifact, |status| {
+                    status.set_state(ArtifactCacheState::Cached);
+                    status.sha256 = Some(digest);
+                    status.verified = verified;
+                    status.size_bytes = Some(size);
+                    status.error = None;
+                });
+            }
+            Err(err) => {
+                counter!("artifact_cache_downloads_total", "result" => "failure").increment(1);
+                let (evicted, message) = match err {
+                    DownloadError::Full(CacheFull {
+                        needed_bytes,
+                        available_bytes,
+                    }) => (
+                        true,
+                        format!(
+                            "Not enough space in the cache: need {needed_bytes} bytes, {available_bytes} bytes can be made available"
+                        ),
+                    ),
+                    DownloadError::Failed(message) => (false, message),
+                };
+                eprintln!(
+                    "Error caching artifact {} from {}: {message}",
+                    artifact.name, artifact.url
+                );
+                self.set_status(key, artifact, |status| {
+                    // An evicted artifact stays evicted until there's space for it again
+                    if !(evicted && status.state() == ArtifactCacheState::Evicted) {
+                        status.set_state(ArtifactCacheState::Failed);
+                    }
+                    status.error = Some(message);
+                });
+            }
+        }
+    }
+
+    /// Downloads `artifact` into the store, returning its digest and size.
+    async fn download(
+        &self,
+        artifact: &IpxeTemplateArtifact,
+        expected: Option<&str>,
+        pinned: &HashSet<String>,
+        referenced: &HashSet<String>,
+        http_client: &reqwest::Client,
+    ) -> Result<(String, u64), DownloadError> {
+        let mut request = http_client.get(&artifact.url);
+        if let Some(token) = &artifact.auth_token {
+            match artifact
+                .auth_type
+                .as_deref()
+                .map(str::to_ascii_lowercase)
+                .as_deref()
+            {
+                Some("basic") => {
+                    request =
+                        request.header(reqwest::header::AUTHORIZATION, format!("Basic {token}"));
+                }
+                Some("bearer") => {
+                    request = request.bearer_auth(token);
+                }
+                _ => {}
+            }
+        }
+        let response = request.send().await?.error_for_status()?;
+
+        if let Some(content_length) = response.content_length() {
+            self.make_room(content_length, pinned, referenced)?;
+        }
+
+        let tmp_path = self
+            .store
+            .lock()
+            .unwrap()
+            .tmp_directory()
+            .join(uuid::Uuid::new_v4().to_string());
+        let result: Result<(String, u64), DownloadError> = async {
+            let mut file = tokio::fs::File::create(&tmp_path).await?;
+            let mut hasher = Sha256::new();
+            let mut size = 0u64;
+            let mut stream = response.bytes_stream();
+            while let Some(chunk) = stream.next().await {
+                let chunk = chunk?;
+                hasher.update(&chunk);
+                file.write_all(&chunk).await?;
+                size += chunk.len() as u64;
+            }
+            file.sync_all().await?;
+
+            let digest = hex::encode(hasher.finalize());
+            if let Some(expected) = expected
+                && digest != expected
+            {
+                return Err(DownloadError::Failed(format!(
+                    "Checksum mismatch: expected sha256 {expected}, got {digest}"
+                )));
+            }
+
+            // The size wasn't known up front, or the server lied about it
+            self.make_room(size, pinned, referenced)?;
+            self.store.lock().unwrap().insert(&tmp_path, &digest)?;
+            Ok((digest, size))
+        }
+        .await;
+
+        if result.is_err() {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+        }
+        result
+    }
+
+    fn make_room(
+        &self,
+        needed_bytes: u64,
+        pinned: &HashSet<String>,
+        referenced: &HashSet<String>,
+    ) -> Result<(), DownloadError> {
+        let evicted = self
+            .store
+            .lock()
+            .unwrap()
+            .make_room(needed_bytes, pinned, referenced)
+            .map_err(DownloadError::Full)?;
+        if evicted.is_empty() {
+            return Ok(());
+        }
+
+        counter!("artifact_cache_evictions_total").increment(evicted.len() as u64);
+        let mut state = self.state.lock().unwrap();
+        for status in state.artifacts.values_mut() {
+            if status
+                .sha256
+                .as_ref()
+                .is_some_and(|digest| evicted.contains(digest))
+            {
+                status.set_state(ArtifactCacheState::Evicted);
+                status.updated_at = Some(SystemTime::now().into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Points the cached_url of the artifacts of an operating system at their cached copies, or
+    /// clears it if the cached copy is gone.
+    async fn update_cached_urls(
+        &self,
+        api_client: &ForgeApiClient,
+        os_id: OperatingSystemId,
+        artifacts: &[IpxeTemplateArtifact],
+    ) {
+        let cached_urls = {
+            let state = self.state.lock().unwrap();
+            let store = self.store.lock().unwrap();
+            artifacts
+                .iter()
+                .enumerate()
+                .map(|(index, artifact)| {
+                    let current = artifact.cached_url.clone().filter(|url| !url.is_empty());
+                    let cached_digest = state
+                        .artifacts
+                        .get(&(os_id, index as u32))
+                        .filter(|status| status.state() == ArtifactCacheState::Cached)
+                        .and_then(|status| status.sha256.as_ref())
+                        .filter(|digest| store.contains(digest));
+                    match cached_digest {
+                        Some(digest) => Some(self.cached_url(digest, artifact)),
+                        // Leave cached_urls which were set by somebody else alone
+                        None if current
+                            .as_ref()
+                            .is_some_and(|url| url.starts_with(&self.cached_url_base)) =>
+                        {
+                            None
+                        }
+                        None => current,
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let changed = artifacts
+            .iter()
+            .zip(&cached_urls)
+            .any(|(artifact, cached_url)| {
+                artifact.cached_url.as_ref().filter(|url| !url.is_empty()) != cached_url.as_ref()
+            });
+        if changed {
+            // Updates are matched to artifacts by name and position, so send all of them
+            let request = rpc::UpdateOperatingSystemIpxeTemplateArtifactRequest {
+                id: Some(os_id),
+                updates: artifacts
+                    .iter()
+                    .zip(&cached_urls)
+                    .map(
+                        |(artifact, cached_url)| rpc::IpxeTemplateArtifactUpdateRequest {
+                            name: artifact.name.clone(),
+                            cached_url: cached_url.clone(),
+                        },
+                    )
+                    .collect(),
+            };
+            if let Err(err) = api_client
+                .update_operating_system_cachable_ipxe_template_artifacts(request)
+                .await
+            {
+                eprintln!("Error updating cached artifact URLs of operating system {os_id}: {err}");
+                return;
+            }
+        }
+
+        let mut state = self.state.lock().unwrap();
+        for (index, cached_url) in cached_urls.into_iter().enumerate() {
+            if let Some(status) = state.artifacts.get_mut(&(os_id, index as u32)) {
+                status.cached_url = cached_url;
+            }
+        }
+    }
+
+    fn cached_url(&self, digest: &str, artifact: &IpxeTemplateArtifact) -> String {
+        format!("{}{digest}/{}", self.cached_url_base, file_name(artifact))
+    }
+
+    fn set_status(
+        &self,
+        key: ArtifactKey,
+        artifact: &IpxeTemplateArtifact,
+        update: impl FnOnce(&mut ArtifactStatus),
+    ) {
+        let mut state = self.state.lock().unwrap();
+        let status = state.artifacts.entry(key).or_default();
+        status.operating_system_id = Some(key.0);
+        status.index = key.1;
+        status.name = artifact.name.clone();
+        status.url = artifact.url.clone();
+        status.cache_strategy = artifact.cache_strategy;
+        update(status);
+        status.updated_at = Some(SystemTime::now().into());
+    }
+}
+
+/// Returns the cache strategy of `artifact` if it is one the cache handles.
+fn cachable_strategy(artifact: &IpxeTemplateArtifact) -> Option<IpxeTemplateArtifactCacheStrategy> {
+    match artifact.cache_strategy() {
+        strategy @ (IpxeTemplateArtifactCacheStrategy::CacheAsNeeded
+        | IpxeTemplateArtifactCacheStrategy::CachedOnly) => Some(strategy),
+        _ => None,
+    }
+}
+
+/// Returns the sha256 recorded on `artifact` as a lowercase hex digest, if any.
+fn expected_sha256(artifact: &IpxeTemplateArtifact) -> Result<Option<String>, String> {
+    let Some(sha) = artifact
+        .sha
+        .as_deref()
+        .map(str::trim)
+        .filter(|sha| !sha.is_empty())
+    else {
+        return Ok(None);
+    };
+    let digest = sha
+        .strip_prefix("sha256:")
+        .unwrap_or(sha)
+        .to_ascii_lowercase();
+    if is_sha256_digest(&digest) {
+        Ok(Some(digest))
+    } else {
+        Err(format!("Invalid sha256 {sha}"))
+    }
+}
+
+/// The file name to serve an artifact under. iPXE names images after the last path component of
+/// their URL, and boot configurations may refer to them by that name (e.g. `initrd=initrd.img`),
+/// so keep the original one if possible.
+fn file_name(artifact: &IpxeTemplateArtifact) -> String {
+    let original = artifact
+        .url
+        .split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .unwrap_or_default();
+    let valid = |name: &str| {
+        !name.is_empty()
+            && !name.starts_with('.')
+            && name
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b'_'))
+    };
+    if valid(original) {
+        original.to_string()
+    } else if valid(&artifact.name) {
+        artifact.name.clone()
+    } else {
+        "artifact".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn artifact(url: &str, sha: Option<&str>) -> IpxeTemplateArtifact {
+        IpxeTemplateArtifact {
+            name: "kernel".to_string(),
+            url: url.to_string(),
+            sha: sha.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_expected_sha256() {
+        let digest = "ab".repeat(32);
+        assert_eq!(expected_sha256(&artifact("", None)), Ok(None));
+        assert_eq!(expected_sha256(&artifact("", Some(" "))), Ok(None));
+        assert_eq!(
+            expected_sha256(&artifact("", Some(&digest.to_uppercase()))),
+            Ok(Some(digest.clone()))
+        );
+        assert_eq!(
+            expected_sha256(&artifact("", Some(&format!("sha256:{digest}")))),
+            Ok(Some(digest))
+        );
+        assert!(expected_sha256(&artifact("", Some("abcd"))).is_err());
+    }
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(
+            file_name(&artifact(
+                "https://example.com/images/vmlinuz-6.8?token=x",
+                None
+            )),
+            "vmlinuz-6.8"
+        );
+        assert_eq!(
+            file_name(&artifact("https://example.com/images/", None)),
+            "kernel"
+        );
+        assert_eq!(
+            file_name(&artifact("https://example.com/download?id=%2e%2e", None)),
+            "download"
+        );
+        assert_eq!(
+            file_name(&artifact("https://example.com/..", None)),
+            "kernel"
+        );
+    }
+}
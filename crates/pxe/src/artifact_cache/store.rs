@@ -0,0 +1,282 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Content-addressed storage for cached artifacts.
+//!
+//! Blobs are stored as `<cache directory>/sha256/<hex digest>`. Downloads are written to
+//! `<cache directory>/tmp` first and moved into place once their digest is known, so a blob is
+//! never visible under a digest it doesn't match.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+#[derive(Debug)]
+pub(crate) struct BlobStore {
+    directory: PathBuf,
+    max_size_bytes: u64,
+    blobs: HashMap<String, Blob>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Blob {
+    size: u64,
+    last_access: SystemTime,
+}
+
+/// There is not enough space in the cache, even after evicting everything that may be evicted.
+#[derive(Debug)]
+pub(crate) struct CacheFull {
+    pub needed_bytes: u64,
+    pub available_bytes: u64,
+}
+
+impl BlobStore {
+    /// Opens the store at `directory`, indexing existing blobs and removing leftover downloads.
+    pub(crate) fn open(directory: &Path, max_size_bytes: u64) -> io::Result<Self> {
+        let blob_directory = directory.join("sha256");
+        let tmp_directory = directory.join("tmp");
+        fs::create_dir_all(&blob_directory)?;
+        let _ = fs::remove_dir_all(&tmp_directory);
+        fs::create_dir_all(&tmp_directory)?;
+
+        let mut blobs = HashMap::new();
+        for entry in fs::read_dir(&blob_directory)? {
+            let entry = entry?;
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            let metadata = entry.metadata()?;
+            if !metadata.is_file() || !is_sha256_digest(&name) {
+                continue;
+            }
+            blobs.insert(
+                name,
+                Blob {
+                    size: metadata.len(),
+                    last_access: metadata.modified().unwrap_or_else(|_| SystemTime::now()),
+                },
+            );
+        }
+
+        Ok(Self {
+            directory: directory.to_path_buf(),
+            max_size_bytes,
+            blobs,
+        })
+    }
+
+    pub(crate) fn blob_path(&self, sha256: &str) -> PathBuf {
+        self.directory.join("sha256").join(sha256)
+    }
+
+    pub(crate) fn tmp_directory(&self) -> PathBuf {
+        self.directory.join("tmp")
+    }
+
+    pub(crate) fn contains(&self, sha256: &str) -> bool {
+        self.blobs.contains_key(sha256)
+    }
+
+    pub(crate) fn size(&self, sha256: &str) -> Option<u64> {
+        self.blobs.get(sha256).map(|blob| blob.size)
+    }
+
+    /// Records that a blob was used, which keeps it from being evicted before blobs used less
+    /// recently.
+    pub(crate) fn touch(&mut self, sha256: &str) {
+        if let Some(blob) = self.blobs.get_mut(sha256) {
+            blob.last_access = SystemTime::now();
+        }
+    }
+
+    pub(crate) fn used_bytes(&self) -> u64 {
+        self.blobs.values().map(|blob| blob.size).sum()
+    }
+
+    pub(crate) fn max_size_bytes(&self) -> u64 {
+        self.max_size_bytes
+    }
+
+    /// Evicts blobs until `needed_bytes` more fit in the cache, returning the digests of the
+    /// evicted blobs.
+    ///
+    /// Blobs in `pinned` are never evicted. Blobs which aren't `referenced` by any artifact are
+    /// evicted first, then referenced ones, least recently used first. Nothing is evicted if
+    /// enough space can't be made.
+    pub(crate) fn make_room(
+        &mut self,
+        needed_bytes: u64,
+        pinned: &HashSet<String>,
+        referenced: &HashSet<String>,
+    ) -> Result<Vec<String>, CacheFull> {
+        let used_bytes = self.used_bytes();
+        if used_bytes + needed_bytes <= self.max_size_bytes {
+            return Ok(Vec::new());
+        }
+
+        let mut candidates = self
+            .blobs
+            .iter()
+            .filter(|(sha256, _)| !pinned.contains(*sha256))
+            .map(|(sha256, blob)| {
+                (
+                    referenced.contains(sha256),
+                    blob.last_access,
+                    sha256.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let evictable_bytes = candidates
+            .iter()
+            .map(|(_, _, sha256)| self.blobs[sha256].size)
+            .sum::<u64>();
+        let available_bytes = self
+            .max_size_bytes
+            .saturating_sub(used_bytes - evictable_bytes);
+        if needed_bytes > available_bytes {
+            return Err(CacheFull {
+                needed_bytes,
+                available_bytes,
+            });
+        }
+
+        candidates.sort();
+        let mut evicted = Vec::new();
+        let mut used_bytes = used_bytes;
+        for (_, _, sha256) in candidates {
+            if used_bytes + needed_bytes <= self.max_size_bytes {
+                break;
+            }
+            match self.remove(&sha256) {
+                Ok(size) => {
+                    used_bytes -= size;
+                    evicted.push(sha256);
+                }
+                Err(err) => eprintln!("Error evicting cached artifact {sha256}: {err}"),
+            }
+        }
+        Ok(evicted)
+    }
+
+    /// Moves a completed download at `path` into the store under `sha256`.
+    pub(crate) fn insert(&mut self, path: &Path, sha256: &str) -> io::Result<()> {
+        let size = fs::metadata(path)?.len();
+        fs::rename(path, self.blob_path(sha256))?;
+        self.blobs.insert(
+            sha256.to_string(),
+            Blob {
+                size,
+                last_access: SystemTime::now(),
+            },
+        );
+        Ok(())
+    }
+
+    fn remove(&mut self, sha256: &str) -> io::Result<u64> {
+        match fs::remove_file(self.blob_path(sha256)) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+        Ok(self
+            .blobs
+            .remove(sha256)
+            .map(|blob| blob.size)
+            .unwrap_or_default())
+    }
+}
+
+/// Returns whether `s` is a lowercase hex SHA256 digest, which also makes it safe to use as a
+/// file name.
+pub(crate) fn is_sha256_digest(s: &str) -> bool {
+    s.len() == 64 && s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn digest(n: u8) -> String {
+        format!("{n:064x}")
+    }
+
+    fn add_blob(store: &mut BlobStore, sha256: &str, size: usize, age_secs: u64) {
+        let path = store.tmp_directory().join(sha256);
+        fs::write(&path, vec![0u8; size]).unwrap();
+        store.insert(&path, sha256).unwrap();
+        store.blobs.get_mut(sha256).unwrap().last_access =
+            SystemTime::now() - Duration::from_secs(age_secs);
+    }
+
+    #[test]
+    fn test_open_indexes_existing_blobs() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut store = BlobStore::open(directory.path(), 1000).unwrap();
+        add_blob(&mut store, &digest(1), 10, 0);
+        fs::write(store.tmp_directory().join("partial"), b"partial").unwrap();
+        fs::write(directory.path().join("sha256").join("not-a-digest"), b"x").unwrap();
+
+        let store = BlobStore::open(directory.path(), 1000).unwrap();
+        assert!(store.contains(&digest(1)));
+        assert_eq!(store.size(&digest(1)), Some(10));
+        assert_eq!(store.used_bytes(), 10);
+        assert!(!store.tmp_directory().join("partial").exists());
+    }
+
+    #[test]
+    fn test_make_room_evicts_unreferenced_then_least_recently_used() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut store = BlobStore::open(directory.path(), 100).unwrap();
+        add_blob(&mut store, &digest(1), 30, 10); // referenced, oldest
+        add_blob(&mut store, &digest(2), 30, 0); // unreferenced, newest
+        add_blob(&mut store, &digest(3), 30, 5); // referenced
+
+        let referenced = HashSet::from([digest(1), digest(3)]);
+        let evicted = store.make_room(30, &HashSet::new(), &referenced).unwrap();
+        assert_eq!(evicted, vec![digest(2)]);
+        assert!(!store.blob_path(&digest(2)).exists());
+
+        add_blob(&mut store, &digest(4), 30, 0);
+        let evicted = store.make_room(30, &HashSet::new(), &referenced).unwrap();
+        assert_eq!(evicted, vec![digest(1)]);
+        assert_eq!(store.used_bytes(), 60);
+    }
+
+    #[test]
+    fn test_make_room_never_evicts_pinned_blobs() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut store = BlobStore::open(directory.path(), 100).unwrap();
+        add_blob(&mut store, &digest(1), 60, 10);
+        add_blob(&mut store, &digest(2), 30, 0);
+
+        let pinned = HashSet::from([digest(1)]);
+        let err = store.make_room(50, &pinned, &pinned).unwrap_err();
+        assert_eq!(err.available_bytes, 40);
+        // Nothing is evicted if there isn't going to be enough space anyway
+        assert!(store.contains(&digest(2)));
+
+        assert_eq!(
+            store.make_room(40, &pinned, &pinned).unwrap(),
+            vec![digest(2)]
+        );
+    }
+}
@@ -14,6 +14,8 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::sync::Arc;
+
 use axum_template::engine::Engine;
 use carbide_uuid::machine::MachineInterfaceId;
 use metrics_exporter_prometheus::PrometheusHandle;
@@ -21,6 +23,7 @@ use rpc::forge::CloudInitInstructions;
 use serde::{Deserialize, Serialize};
 use tera::Tera;
 
+use crate::artifact_cache::ArtifactCache;
 use crate::config::RuntimeConfig;
 use crate::extractors::machine_architecture;
 // use crate::middleware::metrics::RequestMetrics;
@@ -47,4 +50,5 @@ pub(crate) struct AppState {
     // pub request_metrics: RequestMetrics,
     pub runtime_config: RuntimeConfig,
     pub prometheus_handle: PrometheusHandle,
+    pub artifact_cache: Option<Arc<ArtifactCache>>,
 }
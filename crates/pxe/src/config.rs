@@ -15,6 +15,8 @@
  * limitations under the License.
  */
 use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Clone, Debug)]
 pub(crate) struct RuntimeConfig {
@@ -28,6 +30,57 @@ pub(crate) struct RuntimeConfig {
     pub bind_address: String,
     pub bind_port: u16,
     pub template_directory: String,
+    /// Directory holding the signed UEFI HTTP Boot images, one subdirectory
+    /// per architecture (`x86_64/shimx64.efi`, `aarch64/grubaa64.efi`, ...).
+    pub http_boot_directory: PathBuf,
+    pub artifact_cache: Option<ArtifactCacheConfig>,
+}
+
+/// Configuration for the iPXE artifact cache (see [`crate::artifact_cache`]). The cache is only
+/// enabled if `CARBIDE_PXE_ARTIFACT_CACHE_DIR` is set. Other settings:
+///
+/// - `CARBIDE_PXE_ARTIFACT_CACHE_MAX_SIZE_BYTES`: size limit of the cache, default 100 GiB
+/// - `CARBIDE_PXE_ARTIFACT_CACHE_SYNC_INTERVAL_SECS`: how often to look for new artifacts,
+///   default 300
+/// - `CARBIDE_PXE_ARTIFACT_CACHE_API_PORT`: port of the cache status gRPC API, default 8081
+#[derive(Clone, Debug)]
+pub(crate) struct ArtifactCacheConfig {
+    pub cache_directory: PathBuf,
+    pub max_size_bytes: u64,
+    pub sync_interval: Duration,
+    pub api_port: u16,
+}
+
+impl ArtifactCacheConfig {
+    fn from_env() -> Result<Option<Self>, String> {
+        let Ok(cache_directory) = env::var("CARBIDE_PXE_ARTIFACT_CACHE_DIR") else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            cache_directory: PathBuf::from(cache_directory),
+            max_size_bytes: env::var("CARBIDE_PXE_ARTIFACT_CACHE_MAX_SIZE_BYTES")
+                .unwrap_or_else(|_| (100u64 << 30).to_string())
+                .parse::<u64>()
+                .map_err(|_| {
+                    "not a parsable artifact cache size for runtime config?".to_string()
+                })?,
+            sync_interval: Duration::from_secs(
+                env::var("CARBIDE_PXE_ARTIFACT_CACHE_SYNC_INTERVAL_SECS")
+                    .unwrap_or_else(|_| "300".to_string())
+                    .parse::<u64>()
+                    .map_err(|_| {
+                        "not a parsable artifact cache sync interval for runtime config?"
+                            .to_string()
+                    })?,
+            ),
+            api_port: env::var("CARBIDE_PXE_ARTIFACT_CACHE_API_PORT")
+                .unwrap_or_else(|_| "8081".to_string())
+                .parse::<u16>()
+                .map_err(|_| {
+                    "not a parsable artifact cache API port for runtime config?".to_string()
+                })?,
+        }))
+    }
 }
 
 impl RuntimeConfig {
@@ -58,6 +111,11 @@ impl RuntimeConfig {
                 .map_err(|_| "not a parsable bind port for runtime config?".to_string())?,
             template_directory: env::var("CARBIDE_PXE_TEMPLATE_DIRECTORY")
                 .unwrap_or_else(|_| "/opt/carbide/pxe/templates".to_string()),
+            http_boot_directory: PathBuf::from(
+                env::var("CARBIDE_PXE_HTTP_BOOT_DIRECTORY")
+                    .unwrap_or_else(|_| "/opt/carbide/pxe/http-boot".to_string()),
+            ),
+            artifact_cache: ArtifactCacheConfig::from_env()?,
         };
 
         Ok(this)
@@ -15,7 +15,7 @@
  * limitations under the License.
  */
 use std::fmt::Debug;
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::str::FromStr;
 
 use axum::middleware::{map_request, map_response};
@@ -28,6 +28,7 @@ use tera::Tera;
 use tower_http::services::ServeDir;
 use tower_layer::Layer;
 
+mod artifact_cache;
 mod common;
 mod config;
 mod extractors;
@@ -83,10 +84,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     )
     .expect("unable to construct socket address from runtime config?");
 
+    let artifact_cache = match &runtime_config.artifact_cache {
+        Some(cache_config) => {
+            let cache = artifact_cache::ArtifactCache::start(cache_config, &runtime_config)?;
+            artifact_cache::api_server::spawn(
+                SocketAddr::new(
+                    IpAddr::from_str(&runtime_config.bind_address)?,
+                    cache_config.api_port,
+                ),
+                cache.clone(),
+            );
+            Some(cache)
+        }
+        None => None,
+    };
+
     let app_state = AppState {
         engine: Engine::from(tera),
         runtime_config,
         prometheus_handle,
+        artifact_cache,
     };
 
     let app = Router::new()
@@ -103,7 +120,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         // we'd have to see if it's actually worthwhile in a real load test scenario
         .merge(routes::ipxe::get_router("/api/v0/pxe"))
         .merge(routes::cloud_init::get_router("/api/v0/cloud-init"))
+        .merge(routes::http_boot::get_router("/api/v0/http-boot"))
         .merge(routes::tls::get_router("/api/v0/tls"))
+        .merge(routes::artifacts::get_router("/artifacts"))
         .route_layer(axum::middleware::from_fn(middleware::logging::logger))
         .layer(map_response(middleware::fix_content_length_header))
         .layer(middleware::metrics::MetricLayer::default())
@@ -0,0 +1,67 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use axum::Router;
+use axum::body::Body;
+use axum::extract::{Path, Request, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use tower_http::services::ServeFile;
+
+use crate::common::AppState;
+
+/// Serves an artifact from the artifact cache. The file name is only there so iPXE names the
+/// image like the original artifact; the content is looked up by digest alone.
+async fn cached_artifact(
+    Path((sha256, _file_name)): Path<(String, String)>,
+    headers: HeaderMap,
+    state: State<AppState>,
+) -> impl IntoResponse {
+    let Some(path) = state
+        .artifact_cache
+        .as_ref()
+        .and_then(|cache| cache.serve(&sha256))
+    else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let mut req = Request::new(Body::empty());
+    *req.headers_mut() = headers;
+
+    match ServeFile::new_with_mime(&path, &mime::APPLICATION_OCTET_STREAM)
+        .try_call(req)
+        .await
+    {
+        Ok(response) => response.into_response(),
+        Err(err) => {
+            eprintln!("Error reading cached artifact {}: {err}", path.display());
+            Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from("error reading cached artifact?"))
+                .unwrap()
+                .into_response()
+        }
+    }
+}
+
+pub fn get_router(path_prefix: &str) -> Router<AppState> {
+    Router::new().route(
+        format!("{}/{}", path_prefix, "sha256/{sha256}/{file_name}").as_str(),
+        get(cached_artifact),
+    )
+}
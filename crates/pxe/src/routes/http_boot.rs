@@ -0,0 +1,265 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! UEFI HTTP Boot images and the grub.cfg for hosts that boot without iPXE.
+//!
+//! The API hands these hosts a boot URL of the form
+//! `<prefix>/<interface_id>/<arch>/shimx64.efi` (or a UKI). shim loads grub
+//! from the same directory, and grub fetches `grub.cfg` relative to it, which
+//! is how the interface ID reaches us without any DHCP options.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use axum::Router;
+use axum::body::Body;
+use axum::extract::{Path, Request, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use carbide_uuid::machine::MachineInterfaceId;
+use forge_tls::client_config::ClientCert;
+use rpc::forge_tls_client::ForgeClientConfig;
+use tower_http::services::ServeFile;
+
+use crate::common::AppState;
+use crate::extractors::machine_architecture::MachineArchitecture;
+use crate::routes::RpcContext;
+
+/// Signed images that may be served, per architecture directory. Anything
+/// else in the HTTP Boot directory is not reachable.
+fn is_boot_image(arch: &MachineArchitecture, file: &str) -> bool {
+    match arch {
+        MachineArchitecture::X86 => {
+            matches!(
+                file,
+                "shimx64.efi" | "grubx64.efi" | "mmx64.efi" | "uki.efi"
+            )
+        }
+        MachineArchitecture::Arm => {
+            matches!(
+                file,
+                "shimaa64.efi" | "grubaa64.efi" | "mmaa64.efi" | "uki.efi"
+            )
+        }
+    }
+}
+
+/// Maps the architecture path segment, which uses the names of the
+/// `internal/<arch>` blob directories.
+fn parse_arch(arch: &str) -> Option<MachineArchitecture> {
+    match arch {
+        "x86_64" => Some(MachineArchitecture::X86),
+        "aarch64" => Some(MachineArchitecture::Arm),
+        _ => None,
+    }
+}
+
+/// Converts an `http://host[:port]/path` URL to grub's network device syntax,
+/// `(http,host[,port])/path`. grub has no HTTPS support.
+fn grub_http_path(url: &str) -> Option<String> {
+    let rest = url.strip_prefix("http://")?;
+    let (authority, path) = match rest.find('/') {
+        Some(idx) => rest.split_at(idx),
+        None => (rest, ""),
+    };
+    // Bracketed IPv6 literals contain colons of their own.
+    let (host, port) = match authority.rfind(':') {
+        Some(idx) if !authority[idx..].contains(']') => {
+            (&authority[..idx], Some(&authority[idx + 1..]))
+        }
+        _ => (authority, None),
+    };
+    let path = path.trim_end_matches('/');
+    Some(match port {
+        Some(port) => format!("(http,{host},{port}){path}"),
+        None => format!("(http,{host}){path}"),
+    })
+}
+
+async fn boot_image(
+    Path((interface_id, arch, file)): Path<(String, String, String)>,
+    headers: HeaderMap,
+    state: State<AppState>,
+) -> impl IntoResponse {
+    if MachineInterfaceId::from_str(&interface_id).is_err() {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    let Some(machine_arch) = parse_arch(&arch) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    if !is_boot_image(&machine_arch, &file) {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
+    let path = state
+        .runtime_config
+        .http_boot_directory
+        .join(&arch)
+        .join(&file);
+
+    let mut req = Request::new(Body::empty());
+    *req.headers_mut() = headers;
+
+    match ServeFile::new_with_mime(&path, &mime::APPLICATION_OCTET_STREAM)
+        .try_call(req)
+        .await
+    {
+        Ok(response) => response.into_response(),
+        Err(err) => {
+            eprintln!("Error reading HTTP Boot image {}: {err}", path.display());
+            Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from("error reading HTTP Boot image?"))
+                .unwrap()
+                .into_response()
+        }
+    }
+}
+
+async fn grub_cfg(
+    Path((interface_id, arch)): Path<(String, String)>,
+    state: State<AppState>,
+) -> impl IntoResponse {
+    let Ok(interface_id) = MachineInterfaceId::from_str(&interface_id) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let Some(machine_arch) = parse_arch(&arch) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let pxe_response = RpcContext::get_pxe_instructions(
+        machine_arch.into(),
+        interface_id,
+        None,
+        rpc::forge::BootScriptFormat::Grub,
+        &state.runtime_config.internal_api_url,
+        &ForgeClientConfig::new(
+            state.runtime_config.forge_root_ca_path.clone(),
+            Some(ClientCert {
+                cert_path: state.runtime_config.server_cert_path.clone(),
+                key_path: state.runtime_config.server_key_path.clone(),
+            }),
+        ),
+    )
+    .await;
+
+    let (api_url, pxe_url, static_pxe_url) = match &pxe_response {
+        Ok(resp) => (
+            resp.api_url_override
+                .clone()
+                .unwrap_or_else(|| state.runtime_config.client_facing_api_url.clone()),
+            resp.pxe_url_override
+                .clone()
+                .unwrap_or_else(|| state.runtime_config.pxe_url.clone()),
+            resp.static_pxe_url_override
+                .clone()
+                .unwrap_or_else(|| state.runtime_config.static_pxe_url.clone()),
+        ),
+        Err(_) => (
+            state.runtime_config.client_facing_api_url.clone(),
+            state.runtime_config.pxe_url.clone(),
+            state.runtime_config.static_pxe_url.clone(),
+        ),
+    };
+
+    let Some(base_url) = grub_http_path(&format!("{static_pxe_url}/public/blobs")) else {
+        eprintln!("Static PXE URL {static_pxe_url} can't be used by grub, it must be plain http");
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+
+    let instructions = pxe_response
+        .map(|resp| resp.pxe_script)
+        .unwrap_or_else(|err| {
+            eprintln!("{err}");
+            format!(
+                r#"
+echo Failed to fetch boot instructions: {err}
+sleep 5
+exit
+"#
+            )
+        })
+        .replace("[api_url]", &api_url)
+        .replace("[pxe_url]", &pxe_url);
+
+    let template_data = HashMap::from([
+        ("base_url".to_string(), base_url),
+        (
+            "cloudinit_url".to_string(),
+            format!("{pxe_url}/api/v0/cloud-init/"),
+        ),
+        ("grub".to_string(), instructions),
+    ]);
+
+    axum_template::Render("grub", state.engine.clone(), template_data).into_response()
+}
+
+pub fn get_router(path_prefix: &str) -> Router<AppState> {
+    Router::new()
+        .route(
+            format!("{}/{}", path_prefix, "{interface_id}/{arch}/grub.cfg").as_str(),
+            get(grub_cfg),
+        )
+        // Signed grub builds look for their config under a `grub` prefix.
+        .route(
+            format!("{}/{}", path_prefix, "{interface_id}/{arch}/grub/grub.cfg").as_str(),
+            get(grub_cfg),
+        )
+        .route(
+            format!("{}/{}", path_prefix, "{interface_id}/{arch}/{file}").as_str(),
+            get(boot_image),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grub_http_path() {
+        assert_eq!(
+            grub_http_path("http://carbide-pxe.forge/public/blobs").as_deref(),
+            Some("(http,carbide-pxe.forge)/public/blobs")
+        );
+        assert_eq!(
+            grub_http_path("http://10.0.0.1:8080/public/blobs/").as_deref(),
+            Some("(http,10.0.0.1,8080)/public/blobs")
+        );
+        assert_eq!(
+            grub_http_path("http://[fd00::1]:8080/x").as_deref(),
+            Some("(http,[fd00::1],8080)/x")
+        );
+        assert_eq!(
+            grub_http_path("http://[fd00::1]/x").as_deref(),
+            Some("(http,[fd00::1])/x")
+        );
+        assert_eq!(grub_http_path("https://carbide-pxe.forge/x"), None);
+    }
+
+    #[test]
+    fn test_boot_image_allowlist() {
+        assert!(is_boot_image(&MachineArchitecture::X86, "shimx64.efi"));
+        assert!(is_boot_image(&MachineArchitecture::Arm, "grubaa64.efi"));
+        assert!(!is_boot_image(&MachineArchitecture::Arm, "shimx64.efi"));
+        assert!(!is_boot_image(
+            &MachineArchitecture::X86,
+            "../x86_64/shimx64.efi"
+        ));
+        assert!(!is_boot_image(&MachineArchitecture::X86, "grub.cfg"));
+    }
+}
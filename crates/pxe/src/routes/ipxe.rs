@@ -102,6 +102,7 @@ pub async fn boot(contents: MachineInterface, state: State<AppState>) -> impl In
                 arch.into(),
                 machine_interface_id,
                 contents.product,
+                rpc::forge::BootScriptFormat::Ipxe,
                 &state.runtime_config.internal_api_url,
                 &ForgeClientConfig::new(
                     state.runtime_config.forge_root_ca_path.clone(),
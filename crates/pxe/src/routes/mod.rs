@@ -18,7 +18,9 @@ use ::rpc::forge as rpc;
 use ::rpc::forge_tls_client::{self, ApiConfig, ForgeClientConfig};
 use carbide_uuid::machine::MachineInterfaceId;
 
+pub(crate) mod artifacts;
 pub(crate) mod cloud_init;
+pub(crate) mod http_boot;
 pub(crate) mod ipxe;
 pub(crate) mod metrics;
 pub(crate) mod tls;
@@ -30,6 +32,7 @@ impl RpcContext {
         arch: rpc::MachineArchitecture,
         interface_id: MachineInterfaceId,
         product: Option<String>,
+        script_format: rpc::BootScriptFormat,
         url: &str,
         client_config: &ForgeClientConfig,
     ) -> Result<rpc::PxeInstructions, String> {
@@ -41,6 +44,7 @@ impl RpcContext {
             arch: arch as i32,
             interface_id: Some(interface_id),
             product,
+            script_format: script_format as i32,
         });
         client
             .get_pxe_instructions(request)
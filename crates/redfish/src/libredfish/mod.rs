@@ -19,6 +19,7 @@ mod implementation;
 
 pub mod auth;
 pub mod error;
+pub mod recording;
 #[cfg(feature = "test-support")]
 pub mod test_support;
 
@@ -0,0 +1,772 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Redfish clients which record operations that change the state of a BMC
+//! instead of executing them
+//!
+//! Operations which only read from the BMC are forwarded to the wrapped client.
+//! This allows to determine which Redfish operations a component would perform,
+//! without affecting the machine.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use forge_secrets::credentials::CredentialReader;
+use libredfish::model::ODataId;
+use libredfish::model::certificate::Certificate;
+use libredfish::model::oem::nvidia_dpu::{HostPrivilegeLevel, NicMode};
+use libredfish::model::sensor::GPUSensors;
+use libredfish::model::service_root::RedfishVendor;
+use libredfish::model::storage::Drives;
+use libredfish::model::task::Task;
+use libredfish::model::update_service::{ComponentType, TransferProtocolType, UpdateService};
+use libredfish::{
+    Assembly, Chassis, Collection, EnabledDisabled, JobState, NetworkAdapter, Redfish,
+    RedfishError, Resource, SystemPowerControl,
+};
+
+use crate::libredfish::{RedfishAuth, RedfishClientCreationError, RedfishClientPool};
+
+/// A Redfish operation which was recorded instead of being executed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedRedfishCall {
+    /// The BMC the operation was targeted at
+    pub host: String,
+    /// The name of the operation, e.g. `power`
+    pub operation: &'static str,
+    /// The arguments of the operation. Secrets like passwords are omitted.
+    pub details: String,
+}
+
+/// A [`RedfishClientPool`] whose clients record operations which would change
+/// the state of the BMC instead of executing them
+pub struct RecordingRedfishClientPool {
+    inner: Arc<dyn RedfishClientPool>,
+    calls: Arc<Mutex<Vec<RecordedRedfishCall>>>,
+}
+
+impl RecordingRedfishClientPool {
+    pub fn new(inner: Arc<dyn RedfishClientPool>) -> Self {
+        Self {
+            inner,
+            calls: Default::default(),
+        }
+    }
+
+    /// Returns all operations recorded so far, in the order they were called
+    pub fn recorded_calls(&self) -> Vec<RecordedRedfishCall> {
+        self.calls.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl RedfishClientPool for RecordingRedfishClientPool {
+    async fn create_client(
+        &self,
+        host: &str,
+        port: Option<u16>,
+        auth: RedfishAuth,
+        vendor: Option<RedfishVendor>,
+    ) -> Result<Box<dyn Redfish>, RedfishClientCreationError> {
+        let inner = self.inner.create_client(host, port, auth, vendor).await?;
+        Ok(Box::new(RecordingRedfishClient {
+            inner,
+            host: host.to_string(),
+            calls: self.calls.clone(),
+        }))
+    }
+
+    fn credential_reader(&self) -> &dyn CredentialReader {
+        self.inner.credential_reader()
+    }
+}
+
+struct RecordingRedfishClient {
+    inner: Box<dyn Redfish>,
+    host: String,
+    calls: Arc<Mutex<Vec<RecordedRedfishCall>>>,
+}
+
+impl RecordingRedfishClient {
+    fn record(&self, operation: &'static str, details: String) {
+        self.calls.lock().unwrap().push(RecordedRedfishCall {
+            host: self.host.clone(),
+            operation,
+            details,
+        });
+    }
+}
+
+/// The error returned for recorded operations whose result can not be made up
+fn not_executed(operation: &str) -> RedfishError {
+    RedfishError::NotSupported(format!("{operation} is only recorded and not executed"))
+}
+
+#[async_trait]
+impl Redfish for RecordingRedfishClient {
+    async fn get_power_state(&self) -> Result<libredfish::PowerState, RedfishError> {
+        self.inner.get_power_state().await
+    }
+
+    async fn get_power_metrics(&self) -> Result<libredfish::model::power::Power, RedfishError> {
+        self.inner.get_power_metrics().await
+    }
+
+    async fn power(&self, action: libredfish::SystemPowerControl) -> Result<(), RedfishError> {
+        self.record("power", format!("action: {action:?}"));
+        Ok(())
+    }
+
+    fn ac_powercycle_supported_by_power(&self) -> bool {
+        self.inner.ac_powercycle_supported_by_power()
+    }
+
+    async fn bmc_reset(&self) -> Result<(), RedfishError> {
+        self.record("bmc_reset", String::new());
+        Ok(())
+    }
+
+    async fn get_thermal_metrics(
+        &self,
+    ) -> Result<libredfish::model::thermal::Thermal, RedfishError> {
+        self.inner.get_thermal_metrics().await
+    }
+
+    async fn machine_setup(
+        &self,
+        boot_interface_mac: Option<&str>,
+        _bios_profiles: &HashMap<
+            libredfish::model::service_root::RedfishVendor,
+            HashMap<
+                String,
+                HashMap<libredfish::BiosProfileType, HashMap<String, serde_json::Value>>,
+            >,
+        >,
+        profile_type: libredfish::BiosProfileType,
+        _oem_manager_profiles: &HashMap<
+            libredfish::model::service_root::RedfishVendor,
+            HashMap<
+                String,
+                HashMap<libredfish::BiosProfileType, HashMap<String, serde_json::Value>>,
+            >,
+        >,
+    ) -> Result<Option<String>, RedfishError> {
+        self.record(
+            "machine_setup",
+            format!("boot_interface_mac: {boot_interface_mac:?}, profile_type: {profile_type:?}"),
+        );
+        Ok(None)
+    }
+
+    async fn machine_setup_status(
+        &self,
+        boot_interface_mac: Option<&str>,
+    ) -> Result<libredfish::MachineSetupStatus, RedfishError> {
+        self.inner.machine_setup_status(boot_interface_mac).await
+    }
+
+    async fn lockdown(&self, target: libredfish::EnabledDisabled) -> Result<(), RedfishError> {
+        self.record("lockdown", format!("target: {target:?}"));
+        Ok(())
+    }
+
+    async fn lockdown_status(&self) -> Result<libredfish::Status, RedfishError> {
+        self.inner.lockdown_status().await
+    }
+
+    async fn setup_serial_console(&self) -> Result<(), RedfishError> {
+        self.record("setup_serial_console", String::new());
+        Ok(())
+    }
+
+    async fn serial_console_status(&self) -> Result<libredfish::Status, RedfishError> {
+        self.inner.serial_console_status().await
+    }
+
+    async fn get_boot_options(&self) -> Result<libredfish::BootOptions, RedfishError> {
+        self.inner.get_boot_options().await
+    }
+
+    async fn get_boot_option(
+        &self,
+        option_id: &str,
+    ) -> Result<libredfish::model::BootOption, RedfishError> {
+        self.inner.get_boot_option(option_id).await
+    }
+
+    async fn boot_once(&self, target: libredfish::Boot) -> Result<(), RedfishError> {
+        self.record("boot_once", format!("target: {target:?}"));
+        Ok(())
+    }
+
+    async fn boot_first(&self, target: libredfish::Boot) -> Result<(), RedfishError> {
+        self.record("boot_first", format!("target: {target:?}"));
+        Ok(())
+    }
+
+    async fn clear_tpm(&self) -> Result<(), RedfishError> {
+        self.record("clear_tpm", String::new());
+        Ok(())
+    }
+
+    async fn bios(&self) -> Result<HashMap<String, serde_json::Value>, RedfishError> {
+        self.inner.bios().await
+    }
+
+    async fn set_bios(
+        &self,
+        values: HashMap<String, serde_json::Value>,
+    ) -> Result<(), RedfishError> {
+        self.record(
+            "set_bios",
+            format!("attributes: {:?}", values.keys().collect::<Vec<_>>()),
+        );
+        Ok(())
+    }
+
+    async fn pending(&self) -> Result<HashMap<String, serde_json::Value>, RedfishError> {
+        self.inner.pending().await
+    }
+
+    async fn clear_pending(&self) -> Result<(), RedfishError> {
+        self.record("clear_pending", String::new());
+        Ok(())
+    }
+
+    async fn pcie_devices(&self) -> Result<Vec<libredfish::PCIeDevice>, RedfishError> {
+        self.inner.pcie_devices().await
+    }
+
+    async fn change_password(&self, user: &str, _new: &str) -> Result<(), RedfishError> {
+        self.record("change_password", format!("user: {user:?}"));
+        Ok(())
+    }
+
+    async fn change_password_by_id(
+        &self,
+        account_id: &str,
+        _new_pass: &str,
+    ) -> Result<(), RedfishError> {
+        self.record(
+            "change_password_by_id",
+            format!("account_id: {account_id:?}"),
+        );
+        Ok(())
+    }
+
+    async fn get_firmware(
+        &self,
+        id: &str,
+    ) -> Result<libredfish::model::software_inventory::SoftwareInventory, RedfishError> {
+        self.inner.get_firmware(id).await
+    }
+
+    async fn update_firmware(
+        &self,
+        _firmware: tokio::fs::File,
+    ) -> Result<libredfish::model::task::Task, RedfishError> {
+        self.record("update_firmware", String::new());
+        Err(not_executed("update_firmware"))
+    }
+
+    async fn update_firmware_simple_update(
+        &self,
+        image_uri: &str,
+        targets: Vec<String>,
+        transfer_protocol: TransferProtocolType,
+    ) -> Result<libredfish::model::task::Task, RedfishError> {
+        self.record("update_firmware_simple_update", format!("image_uri: {image_uri:?}, targets: {targets:?}, transfer_protocol: {transfer_protocol:?}"));
+        Err(not_executed("update_firmware_simple_update"))
+    }
+
+    async fn get_task(&self, id: &str) -> Result<libredfish::model::task::Task, RedfishError> {
+        self.inner.get_task(id).await
+    }
+
+    async fn get_chassis_all(&self) -> Result<Vec<String>, RedfishError> {
+        self.inner.get_chassis_all().await
+    }
+
+    async fn get_chassis(&self, id: &str) -> Result<Chassis, RedfishError> {
+        self.inner.get_chassis(id).await
+    }
+
+    async fn get_chassis_network_adapters(
+        &self,
+        chassis_id: &str,
+    ) -> Result<Vec<String>, RedfishError> {
+        self.inner.get_chassis_network_adapters(chassis_id).await
+    }
+
+    async fn get_chassis_network_adapter(
+        &self,
+        chassis_id: &str,
+        id: &str,
+    ) -> Result<libredfish::model::chassis::NetworkAdapter, RedfishError> {
+        self.inner.get_chassis_network_adapter(chassis_id, id).await
+    }
+
+    async fn get_chassis_assembly(&self, id: &str) -> Result<Assembly, RedfishError> {
+        self.inner.get_chassis_assembly(id).await
+    }
+
+    async fn get_manager_ethernet_interfaces(
+        &self,
+    ) -> Result<Vec<std::string::String>, RedfishError> {
+        self.inner.get_manager_ethernet_interfaces().await
+    }
+
+    async fn get_manager_ethernet_interface(
+        &self,
+        id: &str,
+    ) -> Result<libredfish::model::ethernet_interface::EthernetInterface, RedfishError> {
+        self.inner.get_manager_ethernet_interface(id).await
+    }
+
+    async fn get_system_ethernet_interfaces(
+        &self,
+    ) -> Result<Vec<std::string::String>, RedfishError> {
+        self.inner.get_system_ethernet_interfaces().await
+    }
+
+    async fn get_system_ethernet_interface(
+        &self,
+        id: &str,
+    ) -> Result<libredfish::model::ethernet_interface::EthernetInterface, RedfishError> {
+        self.inner.get_system_ethernet_interface(id).await
+    }
+
+    async fn get_software_inventories(&self) -> Result<Vec<std::string::String>, RedfishError> {
+        self.inner.get_software_inventories().await
+    }
+
+    async fn get_system(&self) -> Result<libredfish::model::ComputerSystem, RedfishError> {
+        self.inner.get_system().await
+    }
+
+    async fn get_secure_boot(
+        &self,
+    ) -> Result<libredfish::model::secure_boot::SecureBoot, RedfishError> {
+        self.inner.get_secure_boot().await
+    }
+
+    async fn disable_secure_boot(&self) -> Result<(), RedfishError> {
+        self.record("disable_secure_boot", String::new());
+        Ok(())
+    }
+
+    async fn get_network_device_functions(
+        &self,
+        chassis_id: &str,
+    ) -> Result<Vec<std::string::String>, RedfishError> {
+        self.inner.get_network_device_functions(chassis_id).await
+    }
+
+    async fn get_network_device_function(
+        &self,
+        chassis_id: &str,
+        id: &str,
+        port: Option<&str>,
+    ) -> Result<libredfish::model::network_device_function::NetworkDeviceFunction, RedfishError>
+    {
+        self.inner
+            .get_network_device_function(chassis_id, id, port)
+            .await
+    }
+
+    async fn get_ports(
+        &self,
+        chassis_id: &str,
+        network_adapter: &str,
+    ) -> Result<Vec<std::string::String>, RedfishError> {
+        self.inner.get_ports(chassis_id, network_adapter).await
+    }
+
+    async fn get_port(
+        &self,
+        chassis_id: &str,
+        network_adapter: &str,
+        id: &str,
+    ) -> Result<libredfish::model::port::NetworkPort, RedfishError> {
+        self.inner.get_port(chassis_id, network_adapter, id).await
+    }
+
+    async fn change_uefi_password(
+        &self,
+        _current_uefi_password: &str,
+        _new_uefi_password: &str,
+    ) -> Result<Option<String>, RedfishError> {
+        self.record("change_uefi_password", String::new());
+        Ok(None)
+    }
+
+    async fn change_boot_order(&self, boot_array: Vec<String>) -> Result<(), RedfishError> {
+        self.record("change_boot_order", format!("boot_array: {boot_array:?}"));
+        Ok(())
+    }
+
+    async fn create_user(
+        &self,
+        username: &str,
+        _password: &str,
+        role_id: libredfish::RoleId,
+    ) -> Result<(), RedfishError> {
+        self.record(
+            "create_user",
+            format!("username: {username:?}, role_id: {role_id:?}"),
+        );
+        Ok(())
+    }
+
+    async fn delete_user(&self, username: &str) -> Result<(), RedfishError> {
+        self.record("delete_user", format!("username: {username:?}"));
+        Ok(())
+    }
+
+    async fn get_service_root(
+        &self,
+    ) -> Result<libredfish::model::service_root::ServiceRoot, RedfishError> {
+        self.inner.get_service_root().await
+    }
+
+    async fn get_systems(&self) -> Result<Vec<String>, RedfishError> {
+        self.inner.get_systems().await
+    }
+
+    async fn get_managers(&self) -> Result<Vec<String>, RedfishError> {
+        self.inner.get_managers().await
+    }
+
+    async fn get_manager(&self) -> Result<libredfish::model::Manager, RedfishError> {
+        self.inner.get_manager().await
+    }
+
+    async fn bmc_reset_to_defaults(&self) -> Result<(), RedfishError> {
+        self.record("bmc_reset_to_defaults", String::new());
+        Ok(())
+    }
+
+    async fn get_system_event_log(
+        &self,
+    ) -> Result<Vec<libredfish::model::sel::LogEntry>, RedfishError> {
+        self.inner.get_system_event_log().await
+    }
+
+    async fn get_bmc_event_log(
+        &self,
+        from: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<libredfish::model::sel::LogEntry>, RedfishError> {
+        self.inner.get_bmc_event_log(from).await
+    }
+
+    async fn get_tasks(&self) -> Result<Vec<String>, RedfishError> {
+        self.inner.get_tasks().await
+    }
+
+    async fn add_secure_boot_certificate(
+        &self,
+        _arg0: &str,
+        _arg1: &str,
+    ) -> Result<Task, RedfishError> {
+        self.record("add_secure_boot_certificate", String::new());
+        Err(not_executed("add_secure_boot_certificate"))
+    }
+
+    async fn enable_secure_boot(&self) -> Result<(), RedfishError> {
+        self.record("enable_secure_boot", String::new());
+        Ok(())
+    }
+
+    async fn change_username(&self, old_name: &str, new_name: &str) -> Result<(), RedfishError> {
+        self.record(
+            "change_username",
+            format!("old_name: {old_name:?}, new_name: {new_name:?}"),
+        );
+        Ok(())
+    }
+
+    async fn get_accounts(
+        &self,
+    ) -> Result<Vec<libredfish::model::account_service::ManagerAccount>, RedfishError> {
+        self.inner.get_accounts().await
+    }
+
+    async fn set_machine_password_policy(&self) -> Result<(), RedfishError> {
+        self.record("set_machine_password_policy", String::new());
+        Ok(())
+    }
+
+    async fn update_firmware_multipart(
+        &self,
+        filename: &Path,
+        reboot: bool,
+        _timeout: Duration,
+        component_type: ComponentType,
+    ) -> Result<String, RedfishError> {
+        self.record(
+            "update_firmware_multipart",
+            format!(
+                "filename: {filename:?}, reboot: {reboot:?}, component_type: {component_type:?}"
+            ),
+        );
+        Err(not_executed("update_firmware_multipart"))
+    }
+
+    async fn get_job_state(&self, job_id: &str) -> Result<JobState, RedfishError> {
+        self.inner.get_job_state(job_id).await
+    }
+
+    async fn get_collection(&self, id: ODataId) -> Result<Collection, RedfishError> {
+        self.inner.get_collection(id).await
+    }
+
+    async fn get_resource(&self, id: ODataId) -> Result<Resource, RedfishError> {
+        self.inner.get_resource(id).await
+    }
+
+    async fn set_boot_order_dpu_first(
+        &self,
+        mac_address: &str,
+    ) -> Result<Option<String>, RedfishError> {
+        self.record(
+            "set_boot_order_dpu_first",
+            format!("mac_address: {mac_address:?}"),
+        );
+        Ok(None)
+    }
+
+    async fn clear_uefi_password(
+        &self,
+        _current_uefi_password: &str,
+    ) -> Result<Option<String>, RedfishError> {
+        self.record("clear_uefi_password", String::new());
+        Ok(None)
+    }
+
+    async fn get_base_network_adapters(
+        &self,
+        system_id: &str,
+    ) -> Result<Vec<String>, RedfishError> {
+        self.inner.get_base_network_adapters(system_id).await
+    }
+
+    async fn get_base_network_adapter(
+        &self,
+        system_id: &str,
+        id: &str,
+    ) -> Result<NetworkAdapter, RedfishError> {
+        self.inner.get_base_network_adapter(system_id, id).await
+    }
+
+    async fn chassis_reset(
+        &self,
+        chassis_id: &str,
+        reset_type: SystemPowerControl,
+    ) -> Result<(), RedfishError> {
+        self.record(
+            "chassis_reset",
+            format!("chassis_id: {chassis_id:?}, reset_type: {reset_type:?}"),
+        );
+        Ok(())
+    }
+
+    async fn get_update_service(&self) -> Result<UpdateService, RedfishError> {
+        self.inner.get_update_service().await
+    }
+
+    async fn get_base_mac_address(&self) -> Result<Option<String>, RedfishError> {
+        self.inner.get_base_mac_address().await
+    }
+
+    async fn lockdown_bmc(&self, target: EnabledDisabled) -> Result<(), RedfishError> {
+        self.record("lockdown_bmc", format!("target: {target:?}"));
+        Ok(())
+    }
+
+    async fn get_gpu_sensors(&self) -> Result<Vec<GPUSensors>, RedfishError> {
+        self.inner.get_gpu_sensors().await
+    }
+
+    async fn get_drives_metrics(&self) -> Result<Vec<Drives>, RedfishError> {
+        self.inner.get_drives_metrics().await
+    }
+
+    async fn is_ipmi_over_lan_enabled(&self) -> Result<bool, RedfishError> {
+        self.inner.is_ipmi_over_lan_enabled().await
+    }
+
+    async fn enable_ipmi_over_lan(&self, target: EnabledDisabled) -> Result<(), RedfishError> {
+        self.record("enable_ipmi_over_lan", format!("target: {target:?}"));
+        Ok(())
+    }
+
+    async fn enable_rshim_bmc(&self) -> Result<(), RedfishError> {
+        self.record("enable_rshim_bmc", String::new());
+        Ok(())
+    }
+
+    async fn clear_nvram(&self) -> Result<(), RedfishError> {
+        self.record("clear_nvram", String::new());
+        Ok(())
+    }
+
+    async fn get_nic_mode(&self) -> Result<Option<NicMode>, RedfishError> {
+        self.inner.get_nic_mode().await
+    }
+
+    async fn set_nic_mode(&self, mode: NicMode) -> Result<(), RedfishError> {
+        self.record("set_nic_mode", format!("mode: {mode:?}"));
+        Ok(())
+    }
+
+    async fn enable_infinite_boot(&self) -> Result<(), RedfishError> {
+        self.record("enable_infinite_boot", String::new());
+        Ok(())
+    }
+
+    async fn is_infinite_boot_enabled(&self) -> Result<Option<bool>, RedfishError> {
+        self.inner.is_infinite_boot_enabled().await
+    }
+
+    async fn reset_bios(&self) -> Result<(), RedfishError> {
+        self.record("reset_bios", String::new());
+        Ok(())
+    }
+
+    async fn set_host_rshim(&self, enabled: EnabledDisabled) -> Result<(), RedfishError> {
+        self.record("set_host_rshim", format!("enabled: {enabled:?}"));
+        Ok(())
+    }
+
+    async fn get_host_rshim(&self) -> Result<Option<EnabledDisabled>, RedfishError> {
+        self.inner.get_host_rshim().await
+    }
+
+    async fn set_idrac_lockdown(&self, enabled: EnabledDisabled) -> Result<(), RedfishError> {
+        self.record("set_idrac_lockdown", format!("enabled: {enabled:?}"));
+        Ok(())
+    }
+
+    async fn get_boss_controller(&self) -> Result<Option<String>, RedfishError> {
+        self.inner.get_boss_controller().await
+    }
+
+    async fn decommission_storage_controller(
+        &self,
+        controller_id: &str,
+    ) -> Result<Option<String>, RedfishError> {
+        self.record(
+            "decommission_storage_controller",
+            format!("controller_id: {controller_id:?}"),
+        );
+        Ok(None)
+    }
+
+    async fn create_storage_volume(
+        &self,
+        controller_id: &str,
+        volume_name: &str,
+    ) -> Result<Option<String>, RedfishError> {
+        self.record(
+            "create_storage_volume",
+            format!("controller_id: {controller_id:?}, volume_name: {volume_name:?}"),
+        );
+        Ok(None)
+    }
+
+    async fn is_boot_order_setup(&self, boot_interface_mac: &str) -> Result<bool, RedfishError> {
+        self.inner.is_boot_order_setup(boot_interface_mac).await
+    }
+
+    async fn is_bios_setup(&self, arg0: Option<&str>) -> Result<bool, RedfishError> {
+        self.inner.is_bios_setup(arg0).await
+    }
+
+    async fn get_secure_boot_certificate(
+        &self,
+        database_id: &str,
+        certificate_id: &str,
+    ) -> Result<Certificate, RedfishError> {
+        self.inner
+            .get_secure_boot_certificate(database_id, certificate_id)
+            .await
+    }
+
+    async fn get_secure_boot_certificates(
+        &self,
+        database_id: &str,
+    ) -> Result<Vec<String>, RedfishError> {
+        self.inner.get_secure_boot_certificates(database_id).await
+    }
+
+    async fn get_component_integrities(
+        &self,
+    ) -> Result<libredfish::model::component_integrity::ComponentIntegrities, RedfishError> {
+        self.inner.get_component_integrities().await
+    }
+
+    async fn get_firmware_for_component(
+        &self,
+        component_integrity_id: &str,
+    ) -> Result<libredfish::model::software_inventory::SoftwareInventory, RedfishError> {
+        self.inner
+            .get_firmware_for_component(component_integrity_id)
+            .await
+    }
+
+    async fn get_component_ca_certificate(
+        &self,
+        url: &str,
+    ) -> Result<libredfish::model::component_integrity::CaCertificate, RedfishError> {
+        self.inner.get_component_ca_certificate(url).await
+    }
+
+    async fn trigger_evidence_collection(
+        &self,
+        url: &str,
+        nonce: &str,
+    ) -> Result<Task, RedfishError> {
+        self.record(
+            "trigger_evidence_collection",
+            format!("url: {url:?}, nonce: {nonce:?}"),
+        );
+        Err(not_executed("trigger_evidence_collection"))
+    }
+
+    async fn get_evidence(
+        &self,
+        url: &str,
+    ) -> Result<libredfish::model::component_integrity::Evidence, RedfishError> {
+        self.inner.get_evidence(url).await
+    }
+
+    async fn set_host_privilege_level(
+        &self,
+        level: HostPrivilegeLevel,
+    ) -> Result<(), RedfishError> {
+        self.record("set_host_privilege_level", format!("level: {level:?}"));
+        Ok(())
+    }
+
+    async fn set_utc_timezone(&self) -> Result<(), RedfishError> {
+        self.record("set_utc_timezone", String::new());
+        Ok(())
+    }
+}
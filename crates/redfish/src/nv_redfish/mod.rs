@@ -97,6 +97,16 @@ impl NvRedfishClientPool {
         }
     }
 
+    /// Creates an uncached BMC handle, e.g. for long-lived event streams that
+    /// should not share a connection with the cached service roots.
+    pub fn bmc(
+        &self,
+        bmc_address: SocketAddr,
+        credentials: Credentials,
+    ) -> Result<Arc<RedfishBmc>, Error> {
+        self.create_bmc(bmc_address, credentials, false)
+    }
+
     fn cached_root(
         &self,
         bmc_address: SocketAddr,
@@ -46,6 +46,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .extern_path(".google.protobuf.Duration", "crate::Duration")
         .extern_path(".google.protobuf.Timestamp", "crate::Timestamp")
         .extern_path(".common.DomainId", "::carbide_uuid::domain::DomainId")
+        .extern_path(".common.TenantDnsRecordId", "::carbide_uuid::domain::TenantDnsRecordId")
         .extern_path(".common.DpaInterfaceId", "::carbide_uuid::dpa_interface::DpaInterfaceId")
         .extern_path(".common.IBPartitionId", "::carbide_uuid::infiniband::IBPartitionId")
         .extern_path(".common.InstanceId", "::carbide_uuid::instance::InstanceId")
@@ -67,6 +68,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .extern_path(".common.ComputeAllocationId", "::carbide_uuid::compute_allocation::ComputeAllocationId")
         .extern_path(".common.OperatingSystemId", "::carbide_uuid::operating_system::OperatingSystemId")
         .extern_path(".common.IpxeTemplateId", "::carbide_uuid::ipxe_template::IpxeTemplateId")
+        .extern_path(".common.MaintenanceWindowId", "::carbide_uuid::maintenance_window::MaintenanceWindowId")
         .extern_path(".measured_boot.MeasurementSystemProfileId", "::carbide_uuid::measured_boot::MeasurementSystemProfileId")
         .extern_path(".measured_boot.MeasurementSystemProfileAttrId", "::carbide_uuid::measured_boot::MeasurementSystemProfileAttrId")
         .extern_path(".measured_boot.MeasurementBundleId", "::carbide_uuid::measured_boot::MeasurementBundleId")
@@ -76,6 +78,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .extern_path(".measured_boot.MeasurementJournalId", "::carbide_uuid::measured_boot::MeasurementJournalId")
         .extern_path(".measured_boot.MeasurementApprovedMachineId", "::carbide_uuid::measured_boot::MeasurementApprovedMachineId")
         .extern_path(".measured_boot.MeasurementApprovedProfileId", "::carbide_uuid::measured_boot::MeasurementApprovedProfileId")
+        .extern_path(".measured_boot.MeasurementPolicyId", "::carbide_uuid::measured_boot::MeasurementPolicyId")
         .include_file("prost_common.rs")
         .type_attribute(".health", "#[derive(serde::Deserialize, serde::Serialize)]")
         .type_attribute(
@@ -382,6 +385,28 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             "forge.StateHistoryRecord",
             "#[derive(serde::Serialize)]",
         )
+        .type_attribute(
+            "forge.ManagedHostStateTimeline",
+            "#[derive(serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.ManagedHostStateTimelineEntry",
+            "#[derive(serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.ExplainMachineNextStepResponse",
+            "#[derive(serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.MachineNextStepSideEffect",
+            "#[derive(serde::Serialize)]",
+        )
+        .type_attribute("forge.MaintenanceWindow", "#[derive(serde::Serialize)]")
+        .type_attribute("forge.MaintenanceWindowScope", "#[derive(serde::Serialize)]")
+        .type_attribute("forge.MaintenanceWindowList", "#[derive(serde::Serialize)]")
+        .type_attribute("forge.MachineSensorHistory", "#[derive(serde::Serialize)]")
+        .type_attribute("forge.SensorSeries", "#[derive(serde::Serialize)]")
+        .type_attribute("forge.SensorSeriesPoint", "#[derive(serde::Serialize)]")
         .type_attribute("forge.StorageCluster", "#[derive(serde::Serialize)]")
         .type_attribute("forge.StoragePoolAttributes", "#[derive(serde::Serialize)]")
         .type_attribute("forge.StoragePool", "#[derive(serde::Serialize)]")
@@ -851,6 +876,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "proto/site_explorer.proto",
                 "proto/dns.proto",
                 "proto/fmds.proto",
+                "proto/ssh_console.proto",
+                "proto/artifact_cache.proto",
             ],
             &["proto"],
         )
@@ -865,6 +892,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         extern_paths: vec![
             (".common.MachineId", "::carbide_uuid::machine::MachineId"),
             (".common.DomainId", "::carbide_uuid::domain::DomainId"),
+            (
+                ".common.TenantDnsRecordId",
+                "::carbide_uuid::domain::TenantDnsRecordId",
+            ),
             (
                 ".common.RemediationId",
                 "::carbide_uuid::dpu_remediations::RemediationId",
@@ -913,6 +944,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 ".common.IpxeTemplateId",
                 "::carbide_uuid::ipxe_template::IpxeTemplateId",
             ),
+            (
+                ".common.MaintenanceWindowId",
+                "::carbide_uuid::maintenance_window::MaintenanceWindowId",
+            ),
             (".common.RackId", "::carbide_uuid::rack::RackId"),
             (
                 ".common.RackProfileId",
@@ -966,6 +1001,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 ".measured_boot.MeasurementApprovedProfileId",
                 "::carbide_uuid::measured_boot::MeasurementApprovedProfileId",
             ),
+            (
+                ".measured_boot.MeasurementPolicyId",
+                "::carbide_uuid::measured_boot::MeasurementPolicyId",
+            ),
         ],
     })?;
 
@@ -58,7 +58,7 @@ pub use crate::protos::machine_discovery::{
     self, BlockDevice, Cpu, DiscoveryInfo, DmiData, NetworkInterface, NvmeDevice,
     PciDeviceProperties,
 };
-pub use crate::protos::{fmds, health, site_explorer};
+pub use crate::protos::{artifact_cache, fmds, health, site_explorer, ssh_console};
 
 pub mod errors;
 pub mod forge_tls_client;
@@ -85,8 +85,15 @@ pub type DynForge = dyn forge::forge_server::Forge<
                     + Send,
             >,
         >,
+        WatchManagedHostStatesStream = WatchStream<forge::ManagedHostStateChange>,
+        WatchInstanceStatesStream = WatchStream<forge::InstanceStateChange>,
+        WatchRackStatesStream = WatchStream<forge::RackStateChange>,
+        WatchSwitchStatesStream = WatchStream<forge::SwitchStateChange>,
     >;
 
+/// The stream type returned by the `Watch*States` RPCs
+pub type WatchStream<T> = Pin<Box<dyn Stream<Item = Result<T, tonic::Status>> + Send>>;
+
 pub fn get_encoded_reflection_service_fd() -> Vec<u8> {
     let mut expected = Vec::new();
     prost_types::FileDescriptorSet::decode(REFLECTION_API_SERVICE_DESCRIPTOR)
@@ -965,6 +972,7 @@ mod tests {
 
         let domain_metadata = Metadata {
             allow_axfr_from: vec![],
+            ..Default::default()
         };
         let domain = Domain {
             id: Some(uuid),
@@ -51,6 +51,14 @@ pub mod dns;
 #[rustfmt::skip]
 pub mod fmds;
 
+#[allow(non_snake_case, unknown_lints, clippy::all)]
+#[rustfmt::skip]
+pub mod ssh_console;
+
+#[allow(non_snake_case, unknown_lints, clippy::all)]
+#[rustfmt::skip]
+pub mod artifact_cache;
+
 #[allow(clippy::all, deprecated)]
 #[rustfmt::skip]
 pub mod forge_api_client;
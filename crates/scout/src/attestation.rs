@@ -16,7 +16,6 @@
  */
 
 use std::ffi::CString;
-use std::process::Command;
 use std::str::FromStr;
 use std::vec::Vec;
 
@@ -339,28 +338,17 @@ pub(crate) fn create_quote_request(
     Ok(request)
 }
 
-pub(crate) fn get_tpm_eventlog() -> Option<Vec<u8>> {
-    let output_res = Command::new("sh")
-        .arg("-c")
-        .arg("tpm2_eventlog /sys/kernel/security/tpm0/binary_bios_measurements")
-        .output();
+/// The binary TCG event log the kernel exposes for the firmware
+/// measurements. It is sent as-is; carbide-api parses and replays it.
+const BINARY_BIOS_MEASUREMENTS_PATH: &str = "/sys/kernel/security/tpm0/binary_bios_measurements";
 
-    let output = match output_res {
-        Ok(output) => output,
+pub(crate) fn get_tpm_eventlog() -> Option<Vec<u8>> {
+    match std::fs::read(BINARY_BIOS_MEASUREMENTS_PATH) {
+        Ok(event_log) => Some(event_log),
         Err(e) => {
             tracing::error!("Could not retrieve TPM Event Log {0}", e.to_string());
-            return None;
+            None
         }
-    };
-
-    if !output.status.success() {
-        tracing::error!(
-            "Error retrieving TPM Event Log: {0}",
-            String::from_utf8(output.stderr).unwrap_or("<could not parse stderr log>".to_string())
-        );
-        None
-    } else {
-        Some(output.stdout)
     }
 }
 
@@ -33,6 +33,7 @@ use model::machine::MachineInterfaceSnapshot;
 use model::site_explorer::{EndpointExplorationError, EndpointExplorationReport, LockdownStatus};
 
 use super::EndpointExplorer;
+use super::bmc_event_listener::BmcEventStream;
 use super::config::SiteExplorerExploreMode;
 use super::credentials::{CredentialClient, get_bmc_root_credential_key};
 use super::metrics::SiteExplorationMetrics;
@@ -729,6 +730,17 @@ impl EndpointExplorer for BmcEndpointExplorer {
         }
     }
 
+    async fn subscribe_events(
+        &self,
+        bmc_ip_address: SocketAddr,
+        interface: &MachineInterfaceSnapshot,
+    ) -> Result<BmcEventStream, EndpointExplorationError> {
+        let credentials = self.get_bmc_root_credentials(interface.mac_address).await?;
+        self.redfish_client
+            .subscribe_events(bmc_ip_address, credentials)
+            .await
+    }
+
     async fn redfish_power_control(
         &self,
         bmc_ip_address: SocketAddr,
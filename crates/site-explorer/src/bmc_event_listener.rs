@@ -0,0 +1,222 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Event-driven re-exploration of BMC endpoints.
+//!
+//! When `bmc_event_subscriptions` is enabled, `SiteExplorer` keeps a Redfish event
+//! stream open for every successfully explored BMC. Events which indicate that the
+//! inventory or power state of the endpoint changed set the `exploration_requested`
+//! flag of the endpoint and wake up `SiteExplorer`, so that the change is picked up
+//! without waiting for the next full sweep.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use futures_util::stream::BoxStream;
+use model::machine::MachineInterfaceSnapshot;
+use model::site_explorer::EndpointExplorationError;
+use sqlx::PgPool;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+use super::EndpointExplorer;
+
+/// A Redfish event record reported by a BMC
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BmcEvent {
+    /// The `MessageId` of the event record, e.g. `ResourceEvent.1.3.ResourceCreated`
+    pub message_id: String,
+    /// The `@odata.id` of the resource which caused the event
+    pub origin: Option<String>,
+}
+
+impl BmcEvent {
+    /// Whether the event indicates a change that an exploration report would reflect
+    pub fn triggers_exploration(&self) -> bool {
+        let key = self
+            .message_id
+            .rsplit('.')
+            .next()
+            .unwrap_or(&self.message_id);
+        matches!(key, "ResourceCreated" | "ResourceAdded" | "ResourceRemoved")
+            || key.starts_with("ResourcePower")
+    }
+}
+
+pub type BmcEventStream = BoxStream<'static, Result<BmcEvent, EndpointExplorationError>>;
+
+/// Maintains one event subscription per explored BMC
+pub(crate) struct BmcEventListener {
+    database_connection: PgPool,
+    endpoint_explorer: Arc<dyn EndpointExplorer>,
+    retry_interval: Duration,
+    wakeup: Arc<Notify>,
+    subscriptions: Mutex<HashMap<IpAddr, JoinHandle<()>>>,
+}
+
+impl BmcEventListener {
+    pub(crate) fn new(
+        database_connection: PgPool,
+        endpoint_explorer: Arc<dyn EndpointExplorer>,
+        retry_interval: Duration,
+    ) -> Self {
+        Self {
+            database_connection,
+            endpoint_explorer,
+            retry_interval,
+            wakeup: Arc::new(Notify::new()),
+            subscriptions: Mutex::default(),
+        }
+    }
+
+    /// Resolves once a subscribed BMC reported an event that requested an exploration
+    pub(crate) async fn notified(&self) {
+        self.wakeup.notified().await
+    }
+
+    /// Subscribes to all given endpoints which don't have a subscription yet, and
+    /// drops the subscriptions of endpoints which are no longer part of the set.
+    pub(crate) fn sync<'a>(
+        &self,
+        endpoints: impl IntoIterator<Item = (SocketAddr, &'a MachineInterfaceSnapshot)>,
+    ) {
+        let mut subscriptions = self
+            .subscriptions
+            .lock()
+            .expect("BMC event subscriptions mutex poisoned");
+
+        let mut active = HashMap::with_capacity(subscriptions.len());
+        for (address, interface) in endpoints {
+            let handle = match subscriptions.remove(&address.ip()) {
+                Some(handle) if !handle.is_finished() => handle,
+                _ => {
+                    tracing::debug!(%address, "Subscribing to BMC events");
+                    tokio::spawn(listen(
+                        self.database_connection.clone(),
+                        self.endpoint_explorer.clone(),
+                        self.wakeup.clone(),
+                        self.retry_interval,
+                        address,
+                        interface.clone(),
+                    ))
+                }
+            };
+            active.insert(address.ip(), handle);
+        }
+
+        for (address, handle) in subscriptions.drain() {
+            tracing::debug!(%address, "Dropping BMC event subscription");
+            handle.abort();
+        }
+        *subscriptions = active;
+    }
+}
+
+impl Drop for BmcEventListener {
+    fn drop(&mut self) {
+        if let Ok(subscriptions) = self.subscriptions.get_mut() {
+            for (_, handle) in subscriptions.drain() {
+                handle.abort();
+            }
+        }
+    }
+}
+
+async fn listen(
+    database_connection: PgPool,
+    endpoint_explorer: Arc<dyn EndpointExplorer>,
+    wakeup: Arc<Notify>,
+    retry_interval: Duration,
+    address: SocketAddr,
+    interface: MachineInterfaceSnapshot,
+) {
+    loop {
+        match endpoint_explorer
+            .subscribe_events(address, &interface)
+            .await
+        {
+            Ok(mut events) => {
+                while let Some(event) = events.next().await {
+                    let event = match event {
+                        Ok(event) => event,
+                        Err(error) => {
+                            tracing::info!(%address, %error, "BMC event stream failed");
+                            break;
+                        }
+                    };
+                    if !event.triggers_exploration() {
+                        continue;
+                    }
+
+                    tracing::info!(
+                        %address,
+                        message_id = %event.message_id,
+                        origin = ?event.origin,
+                        "BMC event requests re-exploration"
+                    );
+                    if let Err(error) =
+                        request_exploration(&database_connection, address.ip()).await
+                    {
+                        tracing::warn!(%address, %error, "Failed to request exploration");
+                        continue;
+                    }
+                    wakeup.notify_one();
+                }
+            }
+            Err(error) => {
+                tracing::debug!(%address, %error, "Cannot subscribe to BMC events");
+            }
+        }
+
+        tokio::time::sleep(retry_interval).await;
+    }
+}
+
+async fn request_exploration(
+    database_connection: &PgPool,
+    address: IpAddr,
+) -> Result<(), db::DatabaseError> {
+    let mut txn = db::Transaction::begin(database_connection).await?;
+    db::explored_endpoints::request_exploration_for_addresses(&[address], txn.as_pgconn()).await?;
+    txn.commit().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(message_id: &str) -> BmcEvent {
+        BmcEvent {
+            message_id: message_id.to_string(),
+            origin: None,
+        }
+    }
+
+    #[test]
+    fn test_triggers_exploration() {
+        assert!(event("ResourceEvent.1.3.ResourceCreated").triggers_exploration());
+        assert!(event("ResourceEvent.1.3.ResourceRemoved").triggers_exploration());
+        assert!(event("ResourceEvent.1.3.ResourcePoweredOn").triggers_exploration());
+        assert!(event("ResourceEvent.1.3.ResourcePowerStateChanged").triggers_exploration());
+        assert!(!event("ResourceEvent.1.3.ResourceChanged").triggers_exploration());
+        assert!(!event("TaskEvent.1.0.TaskProgressChanged").triggers_exploration());
+        assert!(event("ResourceCreated").triggers_exploration());
+    }
+}
@@ -186,6 +186,21 @@ pub struct SiteExplorerConfig {
     /// CompareResult for side-by-side validation).
     #[serde(default = "SiteExplorerConfig::default_explore_mode")]
     pub explore_mode: SiteExplorerExploreMode,
+
+    /// Whether SiteExplorer subscribes to the Redfish event stream of explored BMCs.
+    /// Power state changes and added/removed resources reported by a BMC mark its
+    /// endpoint for re-exploration instead of waiting for the next full sweep.
+    #[serde(default)]
+    pub bmc_event_subscriptions: bool,
+
+    /// Minimum time between two SiteExplorer runs triggered by BMC events.
+    /// Default is 10 seconds.
+    #[serde(
+        default = "SiteExplorerConfig::default_bmc_event_min_interval",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub bmc_event_min_interval: std::time::Duration,
 }
 
 impl Default for SiteExplorerConfig {
@@ -213,6 +228,8 @@ impl Default for SiteExplorerConfig {
             rotate_switch_nvos_credentials: Self::default_rotate_switch_nvos_credentials(),
             force_dpu_nic_mode: Arc::new(false.into()),
             explore_mode: Self::default_explore_mode(),
+            bmc_event_subscriptions: false,
+            bmc_event_min_interval: Self::default_bmc_event_min_interval(),
         }
     }
 }
@@ -294,6 +311,10 @@ impl SiteExplorerConfig {
     pub const fn default_explore_mode() -> SiteExplorerExploreMode {
         SiteExplorerExploreMode::LibRedfish
     }
+
+    pub const fn default_bmc_event_min_interval() -> std::time::Duration {
+        std::time::Duration::from_secs(10)
+    }
 }
 
 pub fn bmc_proxy(s: Option<HostPortPair>) -> Arc<ArcSwap<Option<HostPortPair>>> {
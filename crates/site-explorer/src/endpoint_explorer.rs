@@ -24,6 +24,7 @@ use model::expected_entity::ExpectedEntity;
 use model::machine::MachineInterfaceSnapshot;
 use model::site_explorer::{EndpointExplorationError, EndpointExplorationReport, LockdownStatus};
 
+use super::bmc_event_listener::BmcEventStream;
 use super::metrics::SiteExplorationMetrics;
 
 /// This trait defines how the `SiteExplorer` will query information about endpoints
@@ -156,4 +157,18 @@ pub trait EndpointExplorer: Send + Sync + 'static {
         interface: &MachineInterfaceSnapshot,
         username: &str,
     ) -> Result<(), EndpointExplorationError>;
+
+    /// Subscribe to the event stream of a BMC.
+    ///
+    /// Explorers that can't stream events keep the default, which makes
+    /// `SiteExplorer` fall back to its periodic sweeps for that endpoint.
+    async fn subscribe_events(
+        &self,
+        _address: SocketAddr,
+        _interface: &MachineInterfaceSnapshot,
+    ) -> Result<BmcEventStream, EndpointExplorationError> {
+        Err(EndpointExplorationError::Other {
+            details: "event subscriptions are not supported".to_string(),
+        })
+    }
 }
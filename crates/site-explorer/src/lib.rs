@@ -63,6 +63,9 @@ mod credentials;
 mod metrics;
 pub use metrics::SiteExplorationMetrics;
 mod bmc_endpoint_explorer;
+mod bmc_event_listener;
+use bmc_event_listener::BmcEventListener;
+pub use bmc_event_listener::{BmcEvent, BmcEventStream};
 mod redfish;
 pub use bmc_endpoint_explorer::BmcEndpointExplorer;
 mod boot_order_tracker;
@@ -219,6 +222,7 @@ pub struct SiteExplorer {
     machine_creator: MachineCreator,
     switch_creator: SwitchCreator,
     boot_order_tracker: BootOrderTracker,
+    bmc_event_listener: Option<BmcEventListener>,
     // rms_client: Option<Arc<dyn RmsApi>>,
 }
 
@@ -250,6 +254,14 @@ impl SiteExplorer {
             &explorer_config,
         ));
 
+        let bmc_event_listener = explorer_config.bmc_event_subscriptions.then(|| {
+            BmcEventListener::new(
+                database_connection.clone(),
+                endpoint_explorer.clone(),
+                explorer_config.run_interval,
+            )
+        });
+
         SiteExplorer {
             machine_creator: MachineCreator::new(
                 database_connection.clone(),
@@ -269,6 +281,7 @@ impl SiteExplorer {
             firmware_config,
             work_lock_manager_handle,
             boot_order_tracker: BootOrderTracker::default(),
+            bmc_event_listener,
         }
     }
 
@@ -291,6 +304,7 @@ impl SiteExplorer {
         let timer = PeriodicTimer::new(self.config.run_interval);
         loop {
             let tick = timer.tick();
+            let iteration_start = tokio::time::Instant::now();
 
             if self.config.enabled.load(Ordering::Relaxed) {
                 match self.run_single_iteration().await {
@@ -307,6 +321,9 @@ impl SiteExplorer {
 
             tokio::select! {
                 _ = tick.sleep() => {},
+                _ = self.bmc_event_wakeup(iteration_start) => {
+                    tracing::debug!("SiteExplorer was woken up by a BMC event");
+                },
                 _ = cancel_token.cancelled() => {
                     tracing::info!("SiteExplorer stop was requested");
                     return;
@@ -315,6 +332,20 @@ impl SiteExplorer {
         }
     }
 
+    /// Resolves when a BMC event requested an exploration, but not earlier than
+    /// `bmc_event_min_interval` after the start of the last iteration.
+    /// Never resolves if BMC event subscriptions are disabled.
+    async fn bmc_event_wakeup(&self, iteration_start: tokio::time::Instant) {
+        match &self.bmc_event_listener {
+            Some(listener) => {
+                listener.notified().await;
+                tokio::time::sleep_until(iteration_start + self.config.bmc_event_min_interval)
+                    .await;
+            }
+            None => std::future::pending().await,
+        }
+    }
+
     // This function can just async when
     // https://github.com/rust-lang/rust/issues/110011 will be
     // implemented
@@ -1452,6 +1483,23 @@ impl SiteExplorer {
             }
         }
 
+        // Keep event subscriptions for all BMCs that we were able to explore
+        if let Some(listener) = &self.bmc_event_listener {
+            let bmc_target_port = self.config.override_target_port.unwrap_or(443);
+            listener.sync(
+                priority_update_endpoints
+                    .iter()
+                    .chain(update_endpoints.iter())
+                    .filter(|(_, _, endpoint)| {
+                        endpoint.report.endpoint_type == EndpointType::Bmc
+                            && endpoint.report.last_exploration_error.is_none()
+                    })
+                    .map(|(address, iface, _)| {
+                        (SocketAddr::new(*address, bmc_target_port), *iface)
+                    }),
+            );
+        }
+
         // The unknown endpoints can quickly be cleaned up
         if !delete_endpoints.is_empty() {
             let mut txn = self.txn_begin().await?;
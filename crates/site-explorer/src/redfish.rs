@@ -25,8 +25,11 @@ use carbide_network::deserialize_input_mac_to_address;
 use carbide_redfish::libredfish::{
     RedfishAuth, RedfishClientCreationError, RedfishClientPool, redact_password,
 };
-use carbide_redfish::nv_redfish::NvRedfishClientPool;
+use carbide_redfish::nv_redfish::{
+    Error as NvRedfishError, NvRedfishClientPool, ServiceRoot as NvServiceRoot,
+};
 use forge_secrets::credentials::Credentials;
+use futures_util::{FutureExt, StreamExt};
 use libredfish::model::oem::nvidia_dpu::NicMode;
 use libredfish::model::service_root::RedfishVendor;
 use libredfish::{Redfish, RedfishError};
@@ -37,8 +40,11 @@ use model::site_explorer::{
     InternalLockdownStatus, Inventory, LockdownStatus, MachineSetupDiff, MachineSetupStatus,
     Manager, NetworkAdapter, PCIeDevice, SecureBootStatus, Service, UefiDevicePath,
 };
+use nv_redfish::event_service::EventStreamPayload;
 use regex::Regex;
 
+use super::bmc_event_listener::{BmcEvent, BmcEventStream};
+
 const NOT_FOUND: u16 = 404;
 
 // RedfishClient is a wrapper around a redfish client pool and implements redfish utility functions that the site explorer utilizes.
@@ -354,6 +360,55 @@ impl RedfishClient {
         .map_err(map_nv_redfish_explore_error)
     }
 
+    /// Opens the Redfish SSE event stream of a BMC and maps its event records to [`BmcEvent`]s.
+    pub async fn subscribe_events(
+        &self,
+        bmc_ip_address: SocketAddr,
+        credentials: Credentials,
+    ) -> Result<BmcEventStream, EndpointExplorationError> {
+        let map_err = |err: NvRedfishError| EndpointExplorationError::Other {
+            details: format!("Cannot subscribe to Redfish events: {err}"),
+        };
+        let bmc = self
+            .nv_redfish_client_pool
+            .bmc(bmc_ip_address, credentials)
+            .map_err(map_err)?;
+        let service_root = NvServiceRoot::new(bmc.clone()).await.map_err(map_err)?;
+        let event_service = service_root
+            .event_service()
+            .await
+            .map_err(map_err)?
+            .ok_or_else(|| EndpointExplorationError::Other {
+                details: "BMC does not expose an EventService".to_string(),
+            })?;
+        let events = event_service.events().await.map_err(map_err)?;
+
+        Ok(events
+            .flat_map(move |payload| {
+                let events = match payload {
+                    Ok(EventStreamPayload::Event(event)) => event
+                        .events
+                        .iter()
+                        // Records of SSE events are sent inline and resolve without a fetch
+                        .filter_map(|nav| nav.get(bmc.as_ref()).now_or_never())
+                        .map(|record| {
+                            record.map_err(map_err).map(|record| BmcEvent {
+                                message_id: record.message_id.clone(),
+                                origin: record
+                                    .origin_of_condition
+                                    .as_ref()
+                                    .map(|origin| origin.odata_id.to_string()),
+                            })
+                        })
+                        .collect(),
+                    Ok(EventStreamPayload::MetricReport(_)) => Vec::new(),
+                    Err(err) => vec![Err(map_err(err))],
+                };
+                futures_util::stream::iter(events)
+            })
+            .boxed())
+    }
+
     pub async fn reset_bmc(
         &self,
         bmc_ip_address: SocketAddr,
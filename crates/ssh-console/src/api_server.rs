@@ -0,0 +1,146 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! gRPC API for querying the consoles ssh-console is connected to.
+
+use std::net::SocketAddr;
+
+use chrono::{DateTime, Utc};
+use regex::RegexBuilder;
+use rpc::ssh_console::ssh_console_server::{SshConsole, SshConsoleServer};
+use rpc::ssh_console::{
+    ConsoleOutputLine, SearchConsoleOutputRequest, SearchConsoleOutputResponse,
+};
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tonic::transport::server::TcpIncoming;
+use tonic::{Request, Response, Status};
+
+use crate::bmc::client_pool::BmcConnectionStore;
+use crate::shutdown_handle::ShutdownHandle;
+
+/// How many lines to return from a search if the request doesn't say
+static DEFAULT_SEARCH_LIMIT: u32 = 1000;
+
+/// Limit on the compiled size of search patterns, so a request can't use excessive memory
+static SEARCH_PATTERN_SIZE_LIMIT: usize = 1 << 20;
+
+pub async fn spawn(
+    listen_address: SocketAddr,
+    connection_store: BmcConnectionStore,
+) -> Result<ApiServerHandle, SpawnError> {
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let listener = TcpListener::bind(listen_address)
+        .await
+        .map_err(SpawnError::Listen)?;
+
+    tracing::info!("API listening on {listen_address}");
+
+    let join_handle = tokio::spawn(async move {
+        let result = tonic::transport::Server::builder()
+            .add_service(SshConsoleServer::new(SshConsoleApi { connection_store }))
+            .serve_with_incoming_shutdown(TcpIncoming::from(listener), async move {
+                shutdown_rx.await.ok();
+            })
+            .await;
+        match result {
+            Ok(()) => tracing::info!("API service shutting down"),
+            Err(error) => tracing::error!(%error, "API service failed"),
+        }
+    });
+
+    Ok(ApiServerHandle {
+        shutdown_tx,
+        join_handle,
+    })
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum SpawnError {
+    #[error("error listening on API address: {0}")]
+    Listen(std::io::Error),
+}
+
+pub struct ApiServerHandle {
+    shutdown_tx: oneshot::Sender<()>,
+    join_handle: JoinHandle<()>,
+}
+
+impl ShutdownHandle<()> for ApiServerHandle {
+    fn into_parts(self) -> (oneshot::Sender<()>, JoinHandle<()>) {
+        (self.shutdown_tx, self.join_handle)
+    }
+}
+
+struct SshConsoleApi {
+    connection_store: BmcConnectionStore,
+}
+
+#[tonic::async_trait]
+impl SshConsole for SshConsoleApi {
+    async fn search_console_output(
+        &self,
+        request: Request<SearchConsoleOutputRequest>,
+    ) -> Result<Response<SearchConsoleOutputResponse>, Status> {
+        let request = request.into_inner();
+        let machine_id = request
+            .machine_id
+            .ok_or_else(|| Status::invalid_argument("missing machine_id"))?;
+        let history = self
+            .connection_store
+            .console_history(&machine_id)
+            .ok_or_else(|| {
+                Status::not_found(format!("no console connection for machine {machine_id}"))
+            })?;
+
+        let start_time = request
+            .start_time
+            .map(DateTime::<Utc>::try_from)
+            .transpose()
+            .map_err(|_| Status::invalid_argument("invalid start_time"))?;
+        let end_time = request
+            .end_time
+            .map(DateTime::<Utc>::try_from)
+            .transpose()
+            .map_err(|_| Status::invalid_argument("invalid end_time"))?;
+        let pattern = request
+            .pattern
+            .map(|pattern| {
+                RegexBuilder::new(&pattern)
+                    .case_insensitive(request.ignore_case)
+                    .size_limit(SEARCH_PATTERN_SIZE_LIMIT)
+                    .build()
+            })
+            .transpose()
+            .map_err(|error| Status::invalid_argument(format!("invalid pattern: {error}")))?;
+        let limit = request.limit.unwrap_or(DEFAULT_SEARCH_LIMIT) as usize;
+
+        let (lines, truncated) = history.search(start_time, end_time, pattern.as_ref(), limit);
+
+        Ok(Response::new(SearchConsoleOutputResponse {
+            lines: lines
+                .into_iter()
+                .map(|line| ConsoleOutputLine {
+                    timestamp: Some(line.timestamp.into()),
+                    text: line.text,
+                })
+                .collect(),
+            truncated,
+        }))
+    }
+}
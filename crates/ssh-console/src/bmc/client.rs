@@ -24,6 +24,7 @@ use carbide_uuid::machine::MachineId;
 use chrono::{DateTime, Utc};
 use futures_util::FutureExt;
 use opentelemetry::KeyValue;
+use rpc::forge_api_client::ForgeApiClient;
 use russh::ChannelMsg;
 use tokio::net::TcpStream;
 use tokio::sync::{MutexGuard, broadcast, mpsc, oneshot};
@@ -36,7 +37,9 @@ use crate::bmc::message_proxy::{
     ConnectionChangeMessage, ExecReply, ToBmcMessage, ToFrontendMessage,
 };
 use crate::config::Config;
+use crate::console_lock::ConsoleLock;
 use crate::console_logger;
+use crate::console_monitor::{self, ConsoleHistory};
 use crate::shutdown_handle::ShutdownHandle;
 use crate::ssh_server::ServerMetrics;
 
@@ -46,6 +49,7 @@ use crate::ssh_server::ServerMetrics;
 pub fn spawn(
     connection_details: ConnectionDetails,
     config: Arc<Config>,
+    forge_api_client: ForgeApiClient,
     metrics: Arc<BmcPoolMetrics>,
 ) -> ClientHandle {
     // Shutdown handle for the retry loop that is retrying this connection
@@ -59,6 +63,18 @@ pub fn spawn(
     // Always consume messages from the frontend broadcast channel, even if there are no frontends.
     dev_null(broadcast_to_frontend_rx);
 
+    // Write lock shared by all frontends attached to this console
+    let console_lock = Arc::new(ConsoleLock::new(
+        broadcast_to_frontend_tx.downgrade(),
+        config.console_lock_idle_timeout,
+    ));
+
+    // Recent console output, for searching via the API
+    let console_history = Arc::new(ConsoleHistory::new(
+        config.console_output_max_lines,
+        config.console_output_retention,
+    ));
+
     let connection_state = Arc::new(AtomicConnectionState::default());
     let machine_id = connection_details.machine_id();
     let kind = connection_details.kind();
@@ -72,6 +88,8 @@ pub fn spawn(
     let bmc_client = BmcClient {
         connection_details,
         config,
+        forge_api_client,
+        console_history: console_history.clone(),
         connection_state: connection_state.clone(),
         broadcast_to_frontend_tx: broadcast_to_frontend_tx.clone(),
         shutdown_rx,
@@ -89,12 +107,16 @@ pub fn spawn(
         join_handle,
         connection_state,
         kind,
+        console_lock,
+        console_history,
     }
 }
 
 struct BmcClient {
     connection_details: ConnectionDetails,
     config: Arc<Config>,
+    forge_api_client: ForgeApiClient,
+    console_history: Arc<ConsoleHistory>,
     connection_state: Arc<AtomicConnectionState>,
     shutdown_rx: oneshot::Receiver<()>,
     broadcast_to_frontend_tx: broadcast::Sender<ToFrontendMessage>,
@@ -119,6 +141,15 @@ impl BmcClient {
             None
         };
 
+        // Spawn a task to keep recent output and raise health alerts on configured patterns.
+        let monitor_handle = console_monitor::spawn(
+            machine_id,
+            self.broadcast_to_frontend_tx.subscribe(),
+            self.console_history.clone(),
+            self.config.clone(),
+            self.forge_api_client.clone(),
+        );
+
         // Keep track of when we were last disconnected, for relaying status
         let last_disconnect_time: Arc<RwLock<Option<DateTime<Utc>>>> = Default::default();
 
@@ -275,11 +306,12 @@ impl BmcClient {
             }
         }
 
-        // Clean up: Shut down message relay and logger
+        // Clean up: Shut down message relay, logger and monitor
         bmc_message_relay.shutdown_and_wait().await;
         if let Some(logger_handle) = logger_handle {
             logger_handle.shutdown_and_wait().await;
         }
+        monitor_handle.shutdown_and_wait().await;
     }
 }
 
@@ -473,6 +505,8 @@ pub struct ClientHandle {
     shutdown_tx: oneshot::Sender<()>,
     join_handle: JoinHandle<()>,
     pub connection_state: Arc<AtomicConnectionState>, // pub for metrics gathering
+    console_lock: Arc<ConsoleLock>,
+    pub console_history: Arc<ConsoleHistory>, // pub for the API server
 }
 
 impl ShutdownHandle<()> for ClientHandle {
@@ -496,6 +530,7 @@ impl ClientHandle {
             to_bmc_msg_tx: self.to_bmc_msg_tx.clone(),
             metrics,
             kind: self.kind,
+            console_lock: self.console_lock.clone(),
         }
     }
 }
@@ -507,6 +542,7 @@ pub struct BmcConnectionSubscription {
     pub to_frontend_msg_weak_tx: broadcast::WeakSender<ToFrontendMessage>,
     pub to_bmc_msg_tx: mpsc::Sender<ToBmcMessage>,
     pub kind: connection::Kind,
+    pub console_lock: Arc<ConsoleLock>,
     // Not pub, to make sure we go through ClientHandle::subscribe() to build, so we get the
     // right metrics
     metrics: Arc<ServerMetrics>,
@@ -36,6 +36,7 @@ use crate::bmc::client_pool::GetConnectionError::InvalidMachineId;
 use crate::bmc::connection::State;
 use crate::bmc::{client, connection};
 use crate::config::Config;
+use crate::console_monitor::ConsoleHistory;
 use crate::shutdown_handle::{ReadyHandle, ShutdownHandle};
 use crate::ssh_server::ServerMetrics;
 
@@ -164,6 +165,15 @@ impl BmcConnectionStore {
             })
         }
     }
+
+    /// Recent console output of a machine, if we're connected to its BMC
+    pub fn console_history(&self, machine_id: &MachineId) -> Option<Arc<ConsoleHistory>> {
+        self.0
+            .read()
+            .expect("lock poisoned")
+            .get(machine_id)
+            .map(|handle| handle.console_history.clone())
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -412,6 +422,7 @@ impl BmcPool {
                 let bmc_session_handle = client::spawn(
                     connection_details,
                     self.config.clone(),
+                    self.forge_api_client.clone(),
                     self.metrics.clone(),
                 );
                 guard.insert(machine_id, bmc_session_handle);
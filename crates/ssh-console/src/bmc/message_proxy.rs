@@ -90,6 +90,8 @@ pub enum ToFrontendMessage {
     ConnectionChanged(ConnectionChangeMessage),
     /// A reply to the user pressing the Enter key when the BMC is disconnected
     InformDisconnectedSince(Option<DateTime<Utc>>),
+    /// A change to who holds the console's write lock, see [`crate::console_lock`]
+    ConsoleNotice(String),
 }
 
 #[derive(Clone)]
@@ -117,6 +119,10 @@ impl From<ToFrontendMessage> for Arc<ChannelMsg> {
                 let data: CryptoVec = b"--- Console not connected ---\r\n".to_vec().into();
                 Arc::new(ChannelMsg::Data { data })
             }
+            ToFrontendMessage::ConsoleNotice(notice) => {
+                let data: CryptoVec = format!("\r\n--- {notice} ---\r\n").into_bytes().into();
+                Arc::new(ChannelMsg::Data { data })
+            }
             ToFrontendMessage::Channel(msg) => msg,
         }
     }
@@ -40,6 +40,8 @@ pub struct Config {
     pub listen_address: SocketAddr,
     #[serde(default = "Defaults::metrics_address")]
     pub metrics_address: SocketAddr,
+    #[serde(default)]
+    pub api_listen_address: Option<SocketAddr>,
     #[serde(
         rename = "carbide_url",
         default = "Defaults::carbide_uri",
@@ -89,6 +91,32 @@ pub struct Config {
     pub console_logs_path: PathBuf,
     #[serde(default = "Defaults::console_logging_enabled")]
     pub console_logging_enabled: bool,
+    #[serde(default = "Defaults::session_recordings_path")]
+    pub session_recordings_path: PathBuf,
+    #[serde(default = "Defaults::session_recording_enabled")]
+    pub session_recording_enabled: bool,
+    #[serde(default = "Defaults::console_locking_enabled")]
+    pub console_locking_enabled: bool,
+    #[serde(
+        default = "Defaults::console_lock_idle_timeout",
+        serialize_with = "serialize_duration",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub console_lock_idle_timeout: Duration,
+    #[serde(
+        default = "Defaults::console_output_retention",
+        serialize_with = "serialize_duration",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub console_output_retention: Duration,
+    #[serde(default = "Defaults::console_output_max_lines")]
+    pub console_output_max_lines: usize,
+    #[serde(
+        default = "Defaults::console_alert_clear_after",
+        serialize_with = "serialize_duration",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub console_alert_clear_after: Duration,
     #[serde(default)]
     pub override_bmc_ssh_host: Option<String>,
     #[serde(
@@ -115,6 +143,51 @@ pub struct Config {
     pub log_rotate_max_rotated_files: usize,
     #[serde(default = "Defaults::cert_authorization")]
     pub openssh_certificate_authorization: CertAuthorization,
+    #[serde(default = "Defaults::console_matchers")]
+    pub console_matchers: Vec<ConsoleMatcher>,
+}
+
+/// A pattern to look for in console output, raising a health alert for the machine when it
+/// matches.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConsoleMatcher {
+    /// Health probe ID of the alert
+    pub id: String,
+    /// Regular expression matched against each line of console output, with ANSI escapes removed
+    pub regex: String,
+    #[serde(default)]
+    pub ignore_case: bool,
+    /// Alert message, followed by the matching line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Alert classifications. Defaults to `SerialConsole`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub classifications: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constraints: Option<ConsoleMatcherConstraints>,
+}
+
+/// Only alert if the pattern matched at least `count` times within `duration`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConsoleMatcherConstraints {
+    pub count: u32,
+    #[serde(
+        serialize_with = "serialize_duration",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub duration: Duration,
+}
+
+impl ConsoleMatcher {
+    pub fn compile(&self) -> Result<regex::Regex, ConfigError> {
+        regex::RegexBuilder::new(&self.regex)
+            .case_insensitive(self.ignore_case)
+            .build()
+            .map_err(|error| ConfigError::InvalidConsoleMatcher {
+                id: self.id.clone(),
+                error,
+            })
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -165,10 +238,14 @@ impl Config {
             path: path.to_string_lossy().to_string(),
             error,
         })?;
-        toml::from_str::<Self>(&cfg).map_err(|error| ConfigError::InvalidToml {
+        let config = toml::from_str::<Self>(&cfg).map_err(|error| ConfigError::InvalidToml {
             path: path.to_string_lossy().to_string(),
             error,
-        })
+        })?;
+        for matcher in &config.console_matchers {
+            matcher.compile()?;
+        }
+        Ok(config)
     }
 
     pub async fn override_bmc_ssh_addr(
@@ -198,6 +275,7 @@ impl Config {
         let Self {
             listen_address,
             metrics_address,
+            api_listen_address: _,
             authorized_keys_path: _,
             override_bmcs: _,
             host_key_path,
@@ -216,6 +294,13 @@ impl Config {
             api_poll_interval,
             console_logs_path,
             console_logging_enabled,
+            session_recordings_path,
+            session_recording_enabled,
+            console_locking_enabled,
+            console_lock_idle_timeout,
+            console_output_retention,
+            console_output_max_lines,
+            console_alert_clear_after,
             override_bmc_ssh_host: _,
             reconnect_interval_base,
             reconnect_interval_max,
@@ -223,8 +308,12 @@ impl Config {
             log_rotate_max_size,
             log_rotate_max_rotated_files,
             openssh_certificate_authorization,
+            console_matchers,
         } = self;
         let api_poll_interval = format!("{}s", api_poll_interval.as_secs());
+        let console_lock_idle_timeout = format!("{}s", console_lock_idle_timeout.as_secs());
+        let console_output_retention = format!("{}s", console_output_retention.as_secs());
+        let console_alert_clear_after = format!("{}s", console_alert_clear_after.as_secs());
         let reconnect_interval_base = format!("{}s", reconnect_interval_base.as_secs());
         let reconnect_interval_max = format!("{}s", reconnect_interval_max.as_secs());
         let successful_connection_minimum_duration =
@@ -248,6 +337,15 @@ impl Config {
             value
         };
 
+        // Render as an array of tables, e.g. [[console_matchers]]
+        let console_matchers = {
+            #[derive(Serialize)]
+            struct ConsoleMatchers {
+                console_matchers: Vec<ConsoleMatcher>,
+            }
+            toml::to_string(&ConsoleMatchers { console_matchers }).expect("Invalid default config")
+        };
+
         let cert_authorization_keyid_format_field_separator = openssh_certificate_authorization
             .keyid_format
             .field_separator;
@@ -273,6 +371,10 @@ listen_address = {listen_address:?}
 ## Address to listen on for prometheus metrics requests (HTTP)
 metrics_address = {metrics_address:?}
 
+## Optional: Address to listen on for the ssh-console gRPC API (searching console output.) The API is
+## unauthenticated, so only listen on an address reachable from trusted networks.
+# api_listen_address = "[::]:3224"
+
 ## Address for carbide-api
 carbide_url = {carbide_uri:?}
 
@@ -327,6 +429,32 @@ console_logging_enabled = {console_logging_enabled:?}
 ## Where to write console logs for each machine, if enabled
 console_logs_path = {console_logs_path:?}
 
+## Whether to record each user session (output with timing, user input, and the authenticated
+## principal) in asciicast v2 format, for auditing console access
+session_recording_enabled = {session_recording_enabled:?}
+
+## Where to write session recordings, if enabled. Recordings are stored in a directory per machine,
+## along with an index.jsonl file listing the machine's sessions.
+session_recordings_path = {session_recordings_path:?}
+
+## Whether only one user at a time can type into a console. The first user to type takes the write
+## lock, and everyone else attached to the same console is a read-only spectator until the lock is
+## released. Users can request or take over the lock with escape sequences (type `~h` after a
+## newline for help.) Admins can always take over the lock.
+console_locking_enabled = {console_locking_enabled:?}
+
+## How long the writer has to be idle before a non-admin user can take over the write lock
+console_lock_idle_timeout = {console_lock_idle_timeout:?}
+
+## How long to keep console output in memory, for searching via the API
+console_output_retention = {console_output_retention:?}
+
+## The maximum number of lines of console output to keep in memory per machine
+console_output_max_lines = {console_output_max_lines}
+
+## How long after the last match of a `console_matchers` pattern its health alert is cleared
+console_alert_clear_after = {console_alert_clear_after:?}
+
 ## If set, use this host to override all BMC backends. Useful for machine-a-tron mocks where we use
 ## a single SSH server to mock all BMC SSH connections.
 # override_bmc_ssh_host = <hostname>
@@ -363,6 +491,14 @@ user_field = {cert_authorization_keyid_format_user_field:?}
 role_field = {cert_authorization_keyid_format_role_field:?}
 role_separator = {cert_authorization_keyid_format_role_separator:?}
 
+## Patterns to look for in console output. When one matches, a health alert with the given `id` is
+## raised for the machine, until `console_alert_clear_after` passes without another match. Set
+## `constraints` to only alert if the pattern matches `count` times within `duration`, e.g.:
+# [console_matchers.constraints]
+# count = 3
+# duration = "10m"
+{console_matchers}
+
 ## Optional: For development mode, you can hardcode a list of BMC's to talk to.
 # [[bmcs]]
 # # machine_id: the machine ID this BMC overrides
@@ -453,6 +589,13 @@ impl Default for Config {
             api_poll_interval: Defaults::api_poll_interval(),
             console_logs_path: Defaults::console_logs_path(),
             console_logging_enabled: Defaults::console_logging_enabled(),
+            session_recordings_path: Defaults::session_recordings_path(),
+            session_recording_enabled: Defaults::session_recording_enabled(),
+            console_locking_enabled: Defaults::console_locking_enabled(),
+            console_lock_idle_timeout: Defaults::console_lock_idle_timeout(),
+            console_output_retention: Defaults::console_output_retention(),
+            console_output_max_lines: Defaults::console_output_max_lines(),
+            console_alert_clear_after: Defaults::console_alert_clear_after(),
             successful_connection_minimum_duration:
                 Defaults::successful_connection_minimum_duration(),
             log_rotate_max_size: Defaults::log_rotate_max_size(),
@@ -462,6 +605,8 @@ impl Default for Config {
             dpus: Defaults::dpus(),
             hosts: Defaults::hosts(),
             openssh_certificate_authorization: Defaults::cert_authorization(),
+            console_matchers: Defaults::console_matchers(),
+            api_listen_address: None,
             override_bmc_ssh_port: None,
             override_ipmi_port: None,
             authorized_keys_path: None,
@@ -496,6 +641,8 @@ pub enum ConfigError {
     HostNotFound { what: String, host: String },
     #[error("Invalid machine_id in BMC override config: {0}")]
     InvalidBmcOverrideMachineId(MachineIdParseError),
+    #[error("Invalid regex in console matcher {id}: {error}")]
+    InvalidConsoleMatcher { id: String, error: regex::Error },
 }
 
 impl Defaults {
@@ -553,6 +700,55 @@ impl Defaults {
         true
     }
 
+    pub fn session_recordings_path() -> PathBuf {
+        "/var/log/consoles/recordings".into()
+    }
+
+    pub fn session_recording_enabled() -> bool {
+        true
+    }
+
+    pub fn console_locking_enabled() -> bool {
+        true
+    }
+
+    pub fn console_lock_idle_timeout() -> Duration {
+        Duration::from_secs(300)
+    }
+
+    pub fn console_output_retention() -> Duration {
+        Duration::from_secs(3600)
+    }
+
+    pub fn console_output_max_lines() -> usize {
+        5000
+    }
+
+    pub fn console_alert_clear_after() -> Duration {
+        Duration::from_secs(3600)
+    }
+
+    pub fn console_matchers() -> Vec<ConsoleMatcher> {
+        vec![
+            ConsoleMatcher {
+                id: "ConsoleKernelPanic".to_string(),
+                regex: r"Kernel panic - not syncing".to_string(),
+                ignore_case: false,
+                message: Some("Kernel panic on serial console".to_string()),
+                classifications: vec![],
+                constraints: None,
+            },
+            ConsoleMatcher {
+                id: "ConsoleMachineCheck".to_string(),
+                regex: r"Machine check|mce: \[Hardware Error\]".to_string(),
+                ignore_case: false,
+                message: Some("Machine check exception on serial console".to_string()),
+                classifications: vec![],
+                constraints: None,
+            },
+        ]
+    }
+
     pub fn reconnect_interval_base() -> Duration {
         Duration::from_secs(10)
     }
@@ -760,4 +956,44 @@ mod tests {
         // Should be equivalent to the default config
         assert_eq!(partial_config, Config::default());
     }
+
+    #[test]
+    fn test_console_matchers_config() {
+        let config = indoc! {r#"
+        [[console_matchers]]
+        id = "ConsoleUefiError"
+        regex = "uefi error"
+        ignore_case = true
+
+        [console_matchers.constraints]
+        count = 3
+        duration = "10m"
+        "#};
+
+        let config = toml::from_str::<Config>(config).expect("Couldn't parse config toml");
+        assert_eq!(config.console_matchers.len(), 1);
+        let matcher = &config.console_matchers[0];
+        assert_eq!(
+            matcher.constraints,
+            Some(ConsoleMatcherConstraints {
+                count: 3,
+                duration: Duration::from_secs(600),
+            })
+        );
+        assert!(
+            matcher
+                .compile()
+                .expect("valid regex")
+                .is_match("Detected UEFI Error in boot")
+        );
+
+        let invalid = ConsoleMatcher {
+            regex: "(unclosed".to_string(),
+            ..matcher.clone()
+        };
+        assert!(matches!(
+            invalid.compile(),
+            Err(ConfigError::InvalidConsoleMatcher { .. })
+        ));
+    }
 }
@@ -0,0 +1,452 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Exclusive write access to a shared serial console.
+//!
+//! Every frontend attached to a BMC console gets the console output, but only one of them (the
+//! "writer") can type into it at a time: The first attached user who types something takes the
+//! write lock, and everyone else is a read-only spectator until the writer releases the lock or
+//! detaches. Users control the lock with escape sequences, which (like OpenSSH's escapes) are only
+//! recognized immediately after a newline:
+//!
+//! - `~w`: Request the write lock. Takes it if nobody holds it, otherwise asks the writer for it.
+//! - `~s`: Take over the write lock. Allowed for admins, or if the writer has been idle for longer
+//!   than `console_lock_idle_timeout`.
+//! - `~r`: Release the write lock.
+//! - `~l`: List who is attached.
+//! - `~h`: Help.
+//! - `~~`: Send a literal `~`.
+//!
+//! Changes to the lock are announced to every attached user through the console output, so they
+//! also end up in console logs and session recordings.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::sync::broadcast;
+
+use crate::bmc::message_proxy::ToFrontendMessage;
+
+static CONSOLE_LOCK_HELP: &str = "\
+Console lock escapes (recognized immediately after newline):\r\n\
+  ~w  request the write lock\r\n\
+  ~s  take over the write lock (admins, or if the writer is idle)\r\n\
+  ~r  release the write lock\r\n\
+  ~l  list attached users\r\n\
+  ~h  this help\r\n\
+  ~~  send a literal ~\r\n\
+";
+
+/// The write lock of a single BMC console, shared by all frontends attached to it.
+pub struct ConsoleLock {
+    state: Mutex<State>,
+    // Weak, so that the lock doesn't keep the BMC's broadcast channel alive
+    notice_tx: broadcast::WeakSender<ToFrontendMessage>,
+    idle_timeout: Duration,
+}
+
+#[derive(Default)]
+struct State {
+    next_id: u64,
+    attached: BTreeMap<u64, Attendee>,
+    writer: Option<u64>,
+}
+
+struct Attendee {
+    name: String,
+    admin: bool,
+    last_input: Instant,
+}
+
+impl State {
+    fn name(&self, id: u64) -> &str {
+        self.attached
+            .get(&id)
+            .map(|a| a.name.as_str())
+            .unwrap_or("<unknown>")
+    }
+
+    fn summary(&self) -> String {
+        let attendees = self
+            .attached
+            .iter()
+            .map(|(id, attendee)| {
+                if self.writer == Some(*id) {
+                    format!("{} (writing)", attendee.name)
+                } else {
+                    attendee.name.clone()
+                }
+            })
+            .collect::<Vec<_>>();
+        format!("Attached: {}", attendees.join(", "))
+    }
+}
+
+impl ConsoleLock {
+    pub fn new(
+        notice_tx: broadcast::WeakSender<ToFrontendMessage>,
+        idle_timeout: Duration,
+    ) -> Self {
+        Self {
+            state: Mutex::default(),
+            notice_tx,
+            idle_timeout,
+        }
+    }
+
+    /// Attach a frontend to the console as a spectator. It becomes the writer when it first types
+    /// something while nobody else holds the lock.
+    pub fn attach(self: &Arc<Self>, name: String, admin: bool) -> Attachment {
+        let mut state = self.lock_state();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.attached.insert(
+            id,
+            Attendee {
+                name: name.clone(),
+                admin,
+                last_input: Instant::now(),
+            },
+        );
+        self.notice(format!("{name} attached"));
+
+        Attachment {
+            console_lock: self.clone(),
+            id,
+        }
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("console lock mutex poisoned")
+    }
+
+    /// Announce a lock change to all attached frontends
+    fn notice(&self, notice: String) {
+        if let Some(tx) = self.notice_tx.upgrade() {
+            tx.send(ToFrontendMessage::ConsoleNotice(notice)).ok();
+        }
+    }
+}
+
+/// Whether an attached frontend may write to the console
+#[derive(Debug, PartialEq, Eq)]
+pub enum WriteAccess {
+    Granted,
+    ReadOnly { writer: String },
+}
+
+/// A frontend's attachment to a console. Detaches (releasing the write lock, if held) when dropped.
+pub struct Attachment {
+    console_lock: Arc<ConsoleLock>,
+    id: u64,
+}
+
+impl Attachment {
+    /// Check whether this frontend may write to the console, taking the write lock if nobody
+    /// holds it.
+    pub fn write_access(&self) -> WriteAccess {
+        let mut state = self.console_lock.lock_state();
+        match state.writer {
+            Some(writer) if writer != self.id => WriteAccess::ReadOnly {
+                writer: state.name(writer).to_string(),
+            },
+            writer => {
+                if writer.is_none() {
+                    state.writer = Some(self.id);
+                    self.console_lock
+                        .notice(format!("{} has the write lock", state.name(self.id)));
+                }
+                if let Some(attendee) = state.attached.get_mut(&self.id) {
+                    attendee.last_input = Instant::now();
+                }
+                WriteAccess::Granted
+            }
+        }
+    }
+
+    /// Handle a lock escape sequence, returning the reply for this frontend.
+    pub fn command(&self, command: LockCommand) -> String {
+        match command {
+            LockCommand::Request => self.request(),
+            LockCommand::Steal => self.steal(),
+            LockCommand::Release => self.release(),
+            LockCommand::List => self.summary(),
+            LockCommand::Help => CONSOLE_LOCK_HELP.to_string(),
+        }
+    }
+
+    /// Who is attached, and who is writing
+    pub fn summary(&self) -> String {
+        self.console_lock.lock_state().summary()
+    }
+
+    fn request(&self) -> String {
+        let mut state = self.console_lock.lock_state();
+        let name = state.name(self.id).to_string();
+        match state.writer {
+            Some(writer) if writer == self.id => "You already have the write lock".to_string(),
+            Some(writer) => {
+                let writer = state.name(writer).to_string();
+                self.console_lock.notice(format!(
+                    "{name} requests the write lock from {writer} (release it with ~r)"
+                ));
+                format!("Requested the write lock from {writer}")
+            }
+            None => {
+                state.writer = Some(self.id);
+                self.console_lock
+                    .notice(format!("{name} has the write lock"));
+                "You have the write lock".to_string()
+            }
+        }
+    }
+
+    fn steal(&self) -> String {
+        let mut state = self.console_lock.lock_state();
+        let Some(attendee) = state.attached.get(&self.id) else {
+            return "Not attached".to_string();
+        };
+        let (name, admin) = (attendee.name.clone(), attendee.admin);
+
+        let Some(writer) = state.writer.filter(|writer| *writer != self.id) else {
+            if state.writer.is_none() {
+                state.writer = Some(self.id);
+                self.console_lock
+                    .notice(format!("{name} has the write lock"));
+            }
+            return "You have the write lock".to_string();
+        };
+
+        let writer_idle = state
+            .attached
+            .get(&writer)
+            .is_none_or(|w| w.last_input.elapsed() >= self.console_lock.idle_timeout);
+        if !admin && !writer_idle {
+            return format!(
+                "{} is writing. Only admins can take over the write lock unless the writer is idle for {}s",
+                state.name(writer),
+                self.console_lock.idle_timeout.as_secs()
+            );
+        }
+
+        let previous = state.name(writer).to_string();
+        state.writer = Some(self.id);
+        self.console_lock
+            .notice(format!("{name} took over the write lock from {previous}"));
+        "You have the write lock".to_string()
+    }
+
+    fn release(&self) -> String {
+        let mut state = self.console_lock.lock_state();
+        if state.writer != Some(self.id) {
+            return "You don't have the write lock".to_string();
+        }
+        state.writer = None;
+        self.console_lock
+            .notice(format!("{} released the write lock", state.name(self.id)));
+        "Released the write lock".to_string()
+    }
+
+    /// Detach from the console. Called when the frontend disconnects, and on drop.
+    pub fn detach(&self) {
+        let mut state = self.console_lock.lock_state();
+        let Some(attendee) = state.attached.remove(&self.id) else {
+            return;
+        };
+        if state.writer == Some(self.id) {
+            state.writer = None;
+            self.console_lock.notice(format!(
+                "{} detached, the write lock is free",
+                attendee.name
+            ));
+        } else {
+            self.console_lock
+                .notice(format!("{} detached", attendee.name));
+        }
+    }
+}
+
+impl Drop for Attachment {
+    fn drop(&mut self) {
+        self.detach();
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockCommand {
+    Request,
+    Steal,
+    Release,
+    List,
+    Help,
+}
+
+impl LockCommand {
+    fn from_escape(byte: u8) -> Option<Self> {
+        match byte {
+            b'w' => Some(LockCommand::Request),
+            b's' => Some(LockCommand::Steal),
+            b'r' => Some(LockCommand::Release),
+            b'l' => Some(LockCommand::List),
+            b'h' => Some(LockCommand::Help),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum UserInput {
+    Data(Vec<u8>),
+    Command(LockCommand),
+}
+
+/// Extracts lock escape sequences from a user's input stream.
+pub struct EscapeParser {
+    at_line_start: bool,
+    pending_tilde: bool,
+}
+
+impl Default for EscapeParser {
+    fn default() -> Self {
+        Self {
+            at_line_start: true,
+            pending_tilde: false,
+        }
+    }
+}
+
+impl EscapeParser {
+    /// Split `input` into data to forward and lock commands, in order. A trailing `~` at the start
+    /// of a line is held back until the next call.
+    pub fn feed(&mut self, input: &[u8]) -> Vec<UserInput> {
+        let mut result = Vec::new();
+        let mut data = Vec::with_capacity(input.len());
+
+        for &byte in input {
+            if self.pending_tilde {
+                self.pending_tilde = false;
+                if let Some(command) = LockCommand::from_escape(byte) {
+                    if !data.is_empty() {
+                        result.push(UserInput::Data(std::mem::take(&mut data)));
+                    }
+                    result.push(UserInput::Command(command));
+                    // Stay at line start, so escapes can be chained
+                    continue;
+                }
+                data.push(b'~');
+                if byte == b'~' {
+                    self.at_line_start = false;
+                    continue;
+                }
+            } else if self.at_line_start && byte == b'~' {
+                self.pending_tilde = true;
+                continue;
+            }
+
+            data.push(byte);
+            self.at_line_start = byte == b'\r' || byte == b'\n';
+        }
+
+        if !data.is_empty() {
+            result.push(UserInput::Data(data));
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn console_lock() -> (Arc<ConsoleLock>, broadcast::Sender<ToFrontendMessage>) {
+        let (tx, _rx) = broadcast::channel(16);
+        let console_lock = Arc::new(ConsoleLock::new(tx.downgrade(), Duration::from_secs(300)));
+        (console_lock, tx)
+    }
+
+    #[test]
+    fn test_first_writer_gets_the_lock() {
+        let (console_lock, _tx) = console_lock();
+        let alice = console_lock.attach("alice".to_string(), false);
+        let bob = console_lock.attach("bob".to_string(), false);
+
+        assert_eq!(bob.write_access(), WriteAccess::Granted);
+        assert_eq!(
+            alice.write_access(),
+            WriteAccess::ReadOnly {
+                writer: "bob".to_string()
+            }
+        );
+        assert_eq!(alice.summary(), "Attached: alice, bob (writing)");
+
+        // Non-admins can't take over from an active writer
+        alice.command(LockCommand::Steal);
+        assert_ne!(alice.write_access(), WriteAccess::Granted);
+
+        // Releasing makes the lock available to the next writer
+        bob.command(LockCommand::Release);
+        assert_eq!(alice.write_access(), WriteAccess::Granted);
+
+        // Detaching releases the lock too
+        drop(alice);
+        assert_eq!(bob.write_access(), WriteAccess::Granted);
+        assert_eq!(bob.summary(), "Attached: bob (writing)");
+    }
+
+    #[test]
+    fn test_admin_takes_over() {
+        let (console_lock, _tx) = console_lock();
+        let tenant = console_lock.attach("tenant".to_string(), false);
+        let admin = console_lock.attach("admin".to_string(), true);
+
+        assert_eq!(tenant.write_access(), WriteAccess::Granted);
+        assert_eq!(admin.command(LockCommand::Steal), "You have the write lock");
+        assert_eq!(admin.write_access(), WriteAccess::Granted);
+        assert_eq!(
+            tenant.write_access(),
+            WriteAccess::ReadOnly {
+                writer: "admin".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_escape_parser() {
+        let mut parser = EscapeParser::default();
+        assert_eq!(
+            parser.feed(b"~wls\r~r~~x"),
+            vec![
+                UserInput::Command(LockCommand::Request),
+                UserInput::Data(b"ls\r".to_vec()),
+                UserInput::Command(LockCommand::Release),
+                UserInput::Data(b"~x".to_vec()),
+            ]
+        );
+
+        // Not at line start: passed through
+        assert_eq!(parser.feed(b"a~w"), vec![UserInput::Data(b"a~w".to_vec())]);
+
+        // Escape split across reads
+        assert_eq!(parser.feed(b"\n~"), vec![UserInput::Data(b"\n".to_vec())]);
+        assert_eq!(
+            parser.feed(b"l"),
+            vec![UserInput::Command(LockCommand::List)]
+        );
+        assert_eq!(parser.feed(b"~q"), vec![UserInput::Data(b"~q".to_vec())]);
+    }
+}
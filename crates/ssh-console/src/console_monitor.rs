@@ -0,0 +1,497 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Watches the output of a BMC console: Keeps recent lines in memory so they can be searched via
+//! the API, and raises health alerts for the machine when a line matches one of the configured
+//! [`ConsoleMatcher`]s.
+
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, TimeDelta, Utc};
+use health_report::{
+    HealthAlertClassification, HealthProbeAlert, HealthProbeId, HealthProbeSuccess, HealthReport,
+    HealthReportConversionError,
+};
+use regex::Regex;
+use rpc::forge;
+use rpc::forge_api_client::ForgeApiClient;
+use russh::ChannelMsg;
+use tokio::sync::{broadcast, oneshot};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::bmc::message_proxy::ToFrontendMessage;
+use crate::config::{Config, ConfigError, ConsoleMatcher, ConsoleMatcherConstraints};
+use crate::shutdown_handle::ShutdownHandle;
+
+/// Source of the health reports sent to carbide-api
+static HEALTH_REPORT_SOURCE: &str = "ssh-console";
+
+/// How often to check for alerts to clear, and to retry failed health reports
+static ALERT_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Lines longer than this are split, so a console without newlines can't grow the buffer forever
+static MAX_LINE_LENGTH: usize = 4096;
+
+/// Spawn a background task which watches all output from a BMC
+pub fn spawn(
+    machine_id: MachineId,
+    message_rx: broadcast::Receiver<ToFrontendMessage>,
+    history: Arc<ConsoleHistory>,
+    config: Arc<Config>,
+    forge_api_client: ForgeApiClient,
+) -> ConsoleMonitorHandle {
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    let matchers = config
+        .console_matchers
+        .iter()
+        .filter_map(|matcher| {
+            Matcher::new(matcher)
+                .inspect_err(|error| {
+                    tracing::error!(%machine_id, %error, "ignoring invalid console matcher")
+                })
+                .ok()
+        })
+        .collect();
+
+    let console_monitor = ConsoleMonitor {
+        machine_id,
+        history,
+        matchers,
+        alert_clear_after: config.console_alert_clear_after,
+        forge_api_client,
+        report_pending: false,
+    };
+    let join_handle = tokio::spawn(console_monitor.run(shutdown_rx, message_rx));
+
+    ConsoleMonitorHandle {
+        shutdown_tx,
+        join_handle,
+    }
+}
+
+pub struct ConsoleMonitorHandle {
+    shutdown_tx: oneshot::Sender<()>,
+    join_handle: JoinHandle<()>,
+}
+
+impl ShutdownHandle<()> for ConsoleMonitorHandle {
+    fn into_parts(self) -> (oneshot::Sender<()>, JoinHandle<()>) {
+        (self.shutdown_tx, self.join_handle)
+    }
+}
+
+/// A line of console output
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConsoleLine {
+    /// When the end of the line was received
+    pub timestamp: DateTime<Utc>,
+    /// The line with ANSI escapes and the trailing newline removed
+    pub text: String,
+}
+
+/// Recent output of a BMC console, bounded by line count and age.
+pub struct ConsoleHistory {
+    lines: Mutex<VecDeque<ConsoleLine>>,
+    max_lines: usize,
+    retention: TimeDelta,
+}
+
+impl ConsoleHistory {
+    pub fn new(max_lines: usize, retention: Duration) -> Self {
+        Self {
+            lines: Mutex::default(),
+            max_lines,
+            retention: TimeDelta::from_std(retention).unwrap_or(TimeDelta::MAX),
+        }
+    }
+
+    fn push(&self, line: ConsoleLine) {
+        let mut lines = self.lines.lock().expect("lock poisoned");
+        lines.push_back(line);
+        while lines.len() > self.max_lines {
+            lines.pop_front();
+        }
+        Self::expire(&mut lines, self.retention);
+    }
+
+    fn expire(lines: &mut VecDeque<ConsoleLine>, retention: TimeDelta) {
+        let Some(cutoff) = Utc::now().checked_sub_signed(retention) else {
+            return;
+        };
+        while lines.front().is_some_and(|line| line.timestamp < cutoff) {
+            lines.pop_front();
+        }
+    }
+
+    /// Find lines received in `[start, end)` which match `pattern`, returning the most recent
+    /// `limit` of them, and whether any more matched.
+    pub fn search(
+        &self,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+        pattern: Option<&Regex>,
+        limit: usize,
+    ) -> (Vec<ConsoleLine>, bool) {
+        let mut lines = self.lines.lock().expect("lock poisoned");
+        Self::expire(&mut lines, self.retention);
+
+        let mut found = lines
+            .iter()
+            .rev()
+            .filter(|line| start.is_none_or(|start| line.timestamp >= start))
+            .filter(|line| end.is_none_or(|end| line.timestamp < end))
+            .filter(|line| pattern.is_none_or(|pattern| pattern.is_match(&line.text)))
+            .take(limit.saturating_add(1))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let truncated = found.len() > limit;
+        found.truncate(limit);
+        found.reverse();
+        (found, truncated)
+    }
+}
+
+/// A compiled [`ConsoleMatcher`] and its alert state.
+struct Matcher {
+    id: HealthProbeId,
+    regex: Regex,
+    message: Option<String>,
+    classifications: Vec<HealthAlertClassification>,
+    constraints: Option<ConsoleMatcherConstraints>,
+    // Matches within the constraints' duration, if there are constraints
+    recent_matches: VecDeque<DateTime<Utc>>,
+    alert: Option<ActiveAlert>,
+}
+
+struct ActiveAlert {
+    since: DateTime<Utc>,
+    last_match: DateTime<Utc>,
+    line: String,
+}
+
+#[derive(thiserror::Error, Debug)]
+enum MatcherError {
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error("Invalid console matcher {id}: {error}")]
+    HealthReport {
+        id: String,
+        error: HealthReportConversionError,
+    },
+}
+
+impl Matcher {
+    fn new(config: &ConsoleMatcher) -> Result<Self, MatcherError> {
+        let invalid = |error| MatcherError::HealthReport {
+            id: config.id.clone(),
+            error,
+        };
+        let classifications = if config.classifications.is_empty() {
+            vec![HealthAlertClassification::from_str("SerialConsole").map_err(invalid)?]
+        } else {
+            config
+                .classifications
+                .iter()
+                .map(|c| HealthAlertClassification::from_str(c))
+                .collect::<Result<_, _>>()
+                .map_err(invalid)?
+        };
+
+        Ok(Self {
+            id: HealthProbeId::from_str(&config.id).map_err(invalid)?,
+            regex: config.compile()?,
+            message: config.message.clone(),
+            classifications,
+            constraints: config.constraints.clone(),
+            recent_matches: VecDeque::new(),
+            alert: None,
+        })
+    }
+
+    /// Check a line against this matcher, returning true if it raised a new alert.
+    fn observe(&mut self, line: &ConsoleLine) -> bool {
+        if !self.regex.is_match(&line.text) {
+            return false;
+        }
+
+        if let Some(alert) = &mut self.alert {
+            alert.last_match = line.timestamp;
+            return false;
+        }
+
+        if let Some(constraints) = &self.constraints {
+            let window = TimeDelta::from_std(constraints.duration).unwrap_or(TimeDelta::MAX);
+            self.recent_matches.push_back(line.timestamp);
+            while self
+                .recent_matches
+                .front()
+                .is_some_and(|first| line.timestamp.signed_duration_since(*first) > window)
+            {
+                self.recent_matches.pop_front();
+            }
+            if self.recent_matches.len() < constraints.count as usize {
+                return false;
+            }
+            self.recent_matches.clear();
+        }
+
+        self.alert = Some(ActiveAlert {
+            since: line.timestamp,
+            last_match: line.timestamp,
+            line: line.text.clone(),
+        });
+        true
+    }
+
+    /// Clear the alert if it hasn't matched for `clear_after`, returning true if it was cleared.
+    fn expire(&mut self, now: DateTime<Utc>, clear_after: Duration) -> bool {
+        let clear_after = TimeDelta::from_std(clear_after).unwrap_or(TimeDelta::MAX);
+        if self
+            .alert
+            .as_ref()
+            .is_some_and(|alert| now.signed_duration_since(alert.last_match) >= clear_after)
+        {
+            self.alert = None;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+struct ConsoleMonitor {
+    machine_id: MachineId,
+    history: Arc<ConsoleHistory>,
+    matchers: Vec<Matcher>,
+    alert_clear_after: Duration,
+    forge_api_client: ForgeApiClient,
+    // Set if the alerts changed but we couldn't tell carbide-api yet
+    report_pending: bool,
+}
+
+impl ConsoleMonitor {
+    async fn run(
+        mut self,
+        mut shutdown_rx: oneshot::Receiver<()>,
+        mut message_rx: broadcast::Receiver<ToFrontendMessage>,
+    ) {
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut alert_check_interval = tokio::time::interval(ALERT_CHECK_INTERVAL);
+        alert_check_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = &mut shutdown_rx => {
+                    break;
+                }
+
+                _ = alert_check_interval.tick() => {
+                    let now = Utc::now();
+                    for matcher in self.matchers.iter_mut() {
+                        if matcher.expire(now, self.alert_clear_after) {
+                            tracing::info!(machine_id=%self.machine_id, probe_id=%matcher.id, "clearing console alert");
+                            self.report_pending = true;
+                        }
+                    }
+                    self.send_report_if_pending().await;
+                }
+
+                res = message_rx.recv() => match res {
+                    // Only look at output from the BMC itself, not messages from ssh-console
+                    Ok(ToFrontendMessage::Channel(msg)) => {
+                        if let ChannelMsg::Data { data } = msg.as_ref() {
+                            buffer.extend_from_slice(data.as_ref());
+                            while let Some(end) = buffer
+                                .iter()
+                                .position(|&b| b == b'\n')
+                                .or((buffer.len() >= MAX_LINE_LENGTH).then_some(MAX_LINE_LENGTH - 1))
+                            {
+                                let line_bytes: Vec<u8> = buffer.drain(..=end).collect();
+                                self.process_line(&line_bytes);
+                            }
+                            self.send_report_if_pending().await;
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(broadcast::error::RecvError::Closed) => {
+                        break;
+                    }
+                    Err(broadcast::error::RecvError::Lagged(count)) => {
+                        tracing::warn!(machine_id=%self.machine_id, "console monitor is lagged by {count} messages (typically bytes), output may be missed");
+                    }
+                },
+            }
+        }
+
+        tracing::debug!(machine_id=%self.machine_id, "shutting down console monitor");
+    }
+
+    fn process_line(&mut self, line_bytes: &[u8]) {
+        let clean = strip_ansi_escapes::strip(line_bytes);
+        let text = String::from_utf8_lossy(&clean)
+            .trim_end_matches(['\r', '\n'])
+            .to_string();
+        if text.is_empty() {
+            return;
+        }
+        let line = ConsoleLine {
+            timestamp: Utc::now(),
+            text,
+        };
+
+        for matcher in self.matchers.iter_mut() {
+            if matcher.observe(&line) {
+                tracing::warn!(machine_id=%self.machine_id, probe_id=%matcher.id, line=%line.text, "console output matched, raising alert");
+                self.report_pending = true;
+            }
+        }
+        self.history.push(line);
+    }
+
+    async fn send_report_if_pending(&mut self) {
+        if !self.report_pending {
+            return;
+        }
+
+        let request = forge::InsertMachineHealthReportRequest {
+            machine_id: Some(self.machine_id),
+            health_report_entry: Some(forge::HealthReportEntry {
+                mode: forge::HealthReportApplyMode::Replace.into(),
+                report: Some(self.health_report().into()),
+            }),
+        };
+        match self
+            .forge_api_client
+            .insert_machine_health_report(request)
+            .await
+        {
+            Ok(_) => self.report_pending = false,
+            Err(error) => {
+                tracing::error!(machine_id=%self.machine_id, %error, "error sending console health report, will retry");
+            }
+        }
+    }
+
+    fn health_report(&self) -> HealthReport {
+        let mut report = HealthReport::empty(HEALTH_REPORT_SOURCE.to_string());
+        report.observed_at = Some(Utc::now());
+        for matcher in &self.matchers {
+            match &matcher.alert {
+                Some(alert) => report.alerts.push(HealthProbeAlert {
+                    id: matcher.id.clone(),
+                    target: None,
+                    in_alert_since: Some(alert.since),
+                    message: match &matcher.message {
+                        Some(message) => format!("{message}: {}", alert.line),
+                        None => alert.line.clone(),
+                    },
+                    tenant_message: None,
+                    classifications: matcher.classifications.clone(),
+                }),
+                None => report.successes.push(HealthProbeSuccess {
+                    id: matcher.id.clone(),
+                    target: None,
+                }),
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(text: &str, timestamp: DateTime<Utc>) -> ConsoleLine {
+        ConsoleLine {
+            timestamp,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_history_search() {
+        let history = ConsoleHistory::new(3, Duration::from_secs(3600));
+        let now = Utc::now();
+        for (i, text) in ["booting", "error: one", "ok", "error: two"]
+            .iter()
+            .enumerate()
+        {
+            history.push(line(text, now + TimeDelta::seconds(i as i64)));
+        }
+
+        // The oldest line is dropped to stay within max_lines
+        let (all, truncated) = history.search(None, None, None, 10);
+        assert_eq!(
+            all.iter().map(|l| l.text.as_str()).collect::<Vec<_>>(),
+            vec!["error: one", "ok", "error: two"]
+        );
+        assert!(!truncated);
+
+        let pattern = Regex::new("^error").unwrap();
+        let (errors, truncated) = history.search(None, None, Some(&pattern), 1);
+        assert_eq!(
+            errors,
+            vec![line("error: two", now + TimeDelta::seconds(3))]
+        );
+        assert!(truncated);
+
+        let (in_range, _) = history.search(
+            Some(now + TimeDelta::seconds(1)),
+            Some(now + TimeDelta::seconds(3)),
+            None,
+            10,
+        );
+        assert_eq!(in_range.len(), 2);
+    }
+
+    #[test]
+    fn test_matcher_constraints() {
+        let mut matcher = Matcher::new(&ConsoleMatcher {
+            id: "ConsoleMachineCheck".to_string(),
+            regex: "Machine check".to_string(),
+            ignore_case: false,
+            message: None,
+            classifications: vec![],
+            constraints: Some(ConsoleMatcherConstraints {
+                count: 2,
+                duration: Duration::from_secs(60),
+            }),
+        })
+        .unwrap();
+        let now = Utc::now();
+
+        assert!(!matcher.observe(&line("Machine check: bank 1", now)));
+        // Second match outside of the window
+        let later = now + TimeDelta::seconds(120);
+        assert!(!matcher.observe(&line("Machine check: bank 2", later)));
+        assert!(!matcher.observe(&line("unrelated", later)));
+        assert!(matcher.observe(&line("Machine check: bank 3", later)));
+        // Already alerting
+        assert!(!matcher.observe(&line("Machine check: bank 4", later)));
+
+        assert!(!matcher.expire(later, Duration::from_secs(3600)));
+        assert!(matcher.expire(later + TimeDelta::hours(1), Duration::from_secs(3600)));
+        assert!(matcher.alert.is_none());
+    }
+}
@@ -24,11 +24,11 @@ use carbide_uuid::machine::MachineId;
 use lazy_static::lazy_static;
 use rpc::forge::ValidateTenantPublicKeyRequest;
 use rpc::forge_api_client::ForgeApiClient;
-use russh::keys::ssh_key::AuthorizedKeys;
+use russh::keys::ssh_key::{AuthorizedKeys, HashAlg};
 use russh::keys::{Certificate, PublicKey, PublicKeyBase64};
 use russh::server::{Auth, Msg, Session};
 use russh::{Channel, ChannelId, ChannelMsg, MethodKind, MethodSet, Pty};
-use tokio::sync::oneshot;
+use tokio::sync::{mpsc, oneshot};
 use tonic::Code;
 use uuid::Uuid;
 
@@ -38,6 +38,9 @@ use crate::bmc::connection::Kind;
 use crate::bmc::message_proxy;
 use crate::bmc::message_proxy::{ExecReply, ToBmcMessage};
 use crate::config::Config;
+use crate::console_lock::{Attachment, EscapeParser, UserInput, WriteAccess};
+use crate::session_recording;
+use crate::session_recording::{AuthMethod, Principal, RecordedInput, Recording, SessionInfo};
 use crate::shutdown_handle::ShutdownHandle;
 use crate::ssh_cert_parsing::{certificate_contains_role, get_user_from_certificate};
 use crate::ssh_server::ServerMetrics;
@@ -85,6 +88,8 @@ pub struct Handler {
     bmc_connection_store: BmcConnectionStore,
     /// The machine_id or instance_id the user is attempting to log into. Used as the username in the ssh command line (ie. ssh machine_id@ssh-console)
     authenticated_machine_string: Option<String>,
+    /// Who authenticated, recorded in session recordings
+    authenticated_principal: Option<Principal>,
     per_client_state: HashMap<ChannelId, PerClientState>,
     metrics: Arc<ServerMetrics>,
     last_auth_failure: Option<AuthFailureReason>,
@@ -96,6 +101,75 @@ struct PerClientState {
     bmc_connection: BmcConnectionSubscription,
     // Option so that it can be taken with .take() when we get a shell_request or exec_request
     client_channel: Option<Channel<Msg>>,
+    // Terminal size from the pty_request, if any, used in the recording header
+    window_size: Option<(u32, u32)>,
+    // Set once a shell session is being recorded
+    recorder_input_tx: Option<mpsc::UnboundedSender<RecordedInput>>,
+    // Set once a shell session is attached to the console's write lock
+    console: Option<ConsoleAttachment>,
+}
+
+struct ConsoleAttachment {
+    attachment: Arc<Attachment>,
+    escapes: EscapeParser,
+    // So we only tell spectators once that their input is being ignored
+    notified_read_only: bool,
+}
+
+impl ConsoleAttachment {
+    /// Handle lock escapes in the user's input, returning the data they're allowed to send to the
+    /// BMC, and anything to reply to the user.
+    fn filter_input(&mut self, data: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let mut allowed = Vec::new();
+        let mut reply = Vec::new();
+        for input in self.escapes.feed(data) {
+            match input {
+                UserInput::Command(command) => {
+                    let response = self.attachment.command(command);
+                    reply.extend(format!("\r\n{}\r\n", response.trim_end()).into_bytes());
+                }
+                UserInput::Data(data) => match self.attachment.write_access() {
+                    WriteAccess::Granted => {
+                        self.notified_read_only = false;
+                        allowed.extend(data);
+                    }
+                    WriteAccess::ReadOnly { writer } => {
+                        if !self.notified_read_only {
+                            self.notified_read_only = true;
+                            reply.extend(
+                                format!(
+                                    "\r\n--- Read-only: {writer} has the write lock (~w to request it) ---\r\n"
+                                )
+                                .into_bytes(),
+                            );
+                        }
+                    }
+                },
+            }
+        }
+        (allowed, reply)
+    }
+}
+
+/// What's needed to start a recording for a session on this connection
+struct RecordingContext {
+    recordings_path: std::path::PathBuf,
+    connected_as: String,
+    principal: Principal,
+    peer_addr: String,
+}
+
+impl RecordingContext {
+    fn session(&self, machine_id: MachineId, exec_command: Option<String>) -> SessionInfo {
+        SessionInfo {
+            session_id: Uuid::new_v4(),
+            machine_id: machine_id.to_string(),
+            connected_as: self.connected_as.clone(),
+            principal: self.principal.clone(),
+            peer_addr: self.peer_addr.clone(),
+            exec_command,
+        }
+    }
 }
 
 impl Handler {
@@ -112,6 +186,7 @@ impl Handler {
             forge_api_client,
             bmc_connection_store,
             authenticated_machine_string: None,
+            authenticated_principal: None,
             per_client_state: HashMap::new(),
             metrics,
             last_auth_failure: Default::default(),
@@ -121,6 +196,19 @@ impl Handler {
         }
     }
 
+    /// Returns `None` if session recording is disabled.
+    fn recording_context(&self) -> Option<RecordingContext> {
+        if !self.config.session_recording_enabled {
+            return None;
+        }
+        Some(RecordingContext {
+            recordings_path: self.config.session_recordings_path.clone(),
+            connected_as: self.authenticated_machine_string.clone()?,
+            principal: self.authenticated_principal.clone()?,
+            peer_addr: self.peer_addr.clone(),
+        })
+    }
+
     fn get_client_state_or_report_error(
         &mut self,
         session: &mut Session,
@@ -209,6 +297,9 @@ impl russh::server::Handler for Handler {
             PerClientState {
                 bmc_connection,
                 client_channel: Some(channel),
+                window_size: None,
+                recorder_input_tx: None,
+                console: None,
             },
         );
 
@@ -302,6 +393,14 @@ impl russh::server::Handler for Handler {
             );
         }
         self.authenticated_machine_string = Some(machine_string.to_owned());
+        self.authenticated_principal = Some(Principal {
+            auth_method: AuthMethod::OpensshCertificate,
+            user: Some(user.unwrap_or_else(|| certificate.key_id().to_owned())),
+            fingerprint: certificate
+                .public_key()
+                .fingerprint(HashAlg::Sha256)
+                .to_string(),
+        });
         Ok(Auth::Accept)
     }
 
@@ -318,41 +417,49 @@ impl russh::server::Handler for Handler {
         // 2. If not found in file, validate via carbide-api
         // 3. If insecure mode is enabled, accept all connections
 
-        let success = if pubkey_auth_admin_authorized_keys(public_key, &self.config, machine_string)
-            .map_err(|error| PubkeyAuthAdminAuthorizedKeys {
-                machine_id: machine_string.to_owned(),
-                error,
-            })? {
-            true
-        } else if Uuid::from_str(machine_string).is_ok() {
-            // Only try tenant auth if the user is a valid-looking UUID.
-            pubkey_auth_tenant(machine_string, public_key, &self.forge_api_client)
-                .await
-                .map_err(|error| PubkeyAuthTenant {
-                    instance_id: machine_string.to_owned(),
+        let auth_method =
+            if pubkey_auth_admin_authorized_keys(public_key, &self.config, machine_string).map_err(
+                |error| PubkeyAuthAdminAuthorizedKeys {
+                    machine_id: machine_string.to_owned(),
                     error,
-                })?
-        } else {
-            tracing::debug!(
-                peer_addr = self.peer_addr,
-                machine_string,
-                "rejecting public key for user {machine_string}"
-            );
-            false
-        };
+                },
+            )? {
+                Some(AuthMethod::AuthorizedKeys)
+            } else if Uuid::from_str(machine_string).is_ok() {
+                // Only try tenant auth if the user is a valid-looking UUID.
+                pubkey_auth_tenant(machine_string, public_key, &self.forge_api_client)
+                    .await
+                    .map_err(|error| PubkeyAuthTenant {
+                        instance_id: machine_string.to_owned(),
+                        error,
+                    })?
+                    .then_some(AuthMethod::TenantPublicKey)
+            } else {
+                tracing::debug!(
+                    peer_addr = self.peer_addr,
+                    machine_string,
+                    "rejecting public key for user {machine_string}"
+                );
+                None
+            };
 
-        let success = if !success && self.config.insecure {
+        let auth_method = if auth_method.is_none() && self.config.insecure {
             tracing::info!(
                 peer_addr = self.peer_addr,
                 "Overriding public-key rejection because we are in insecure (testing) mode"
             );
-            true
+            Some(AuthMethod::Insecure)
         } else {
-            success
+            auth_method
         };
 
-        if success {
+        if let Some(auth_method) = auth_method {
             self.authenticated_machine_string = Some(machine_string.to_owned());
+            self.authenticated_principal = Some(Principal {
+                auth_method,
+                user: None,
+                fingerprint: public_key.fingerprint(HashAlg::Sha256).to_string(),
+            });
             Ok(Auth::Accept)
         } else {
             self.last_auth_failure = Some(AuthFailureReason::PubKey {
@@ -374,11 +481,32 @@ impl russh::server::Handler for Handler {
     ) -> Result<(), Self::Error> {
         tracing::trace!(peer_addr = self.peer_addr, "data");
         if let Some(client_state) = self.get_client_state_or_report_error(session, channel) {
+            // Spectators' input is dropped, and lock escapes are handled here instead of being
+            // sent to the BMC.
+            let data = match &mut client_state.console {
+                Some(console) => {
+                    let (allowed, reply) = console.filter_input(data);
+                    if !reply.is_empty() {
+                        session.data(channel, reply.into()).ok();
+                    }
+                    allowed
+                }
+                None => data.to_vec(),
+            };
+            if data.is_empty() {
+                return Ok(());
+            }
+
+            if let Some(recorder_input_tx) = &client_state.recorder_input_tx {
+                recorder_input_tx
+                    .send(RecordedInput::Data(data.clone()))
+                    .ok();
+            }
             client_state
                 .bmc_connection
                 .to_bmc_msg_tx
                 .send(ToBmcMessage::ChannelMsg(ChannelMsg::Data {
-                    data: data.to_vec().into(),
+                    data: data.into(),
                 }))
                 .await
                 .map_err(|_| HandlerError::WritingToChannel { what: "data" })?;
@@ -415,14 +543,17 @@ impl russh::server::Handler for Handler {
         &mut self,
         channel: ChannelId,
         _term: &str,
-        _col_width: u32,
-        _row_height: u32,
+        col_width: u32,
+        row_height: u32,
         _pix_width: u32,
         _pix_height: u32,
         _modes: &[(Pty, u32)],
         session: &mut Session,
     ) -> Result<(), Self::Error> {
         tracing::trace!(peer_addr = self.peer_addr, "pty_request");
+        if let Some(client_state) = self.per_client_state.get_mut(&channel) {
+            client_state.window_size = Some((col_width, row_height));
+        }
         session.channel_success(channel)?;
         Ok(())
     }
@@ -434,6 +565,15 @@ impl russh::server::Handler for Handler {
     ) -> Result<(), Self::Error> {
         tracing::trace!(peer_addr = self.peer_addr, "shell_request");
         let peer_addr = self.peer_addr.clone();
+        let recording_context = self.recording_context();
+        let console_locking_enabled = self.config.console_locking_enabled;
+        let (console_user, console_admin) = match &self.authenticated_principal {
+            Some(principal) => (
+                format!("{principal} from {peer_addr}"),
+                principal.auth_method.is_admin(),
+            ),
+            None => (format!("<unknown> from {peer_addr}"), false),
+        };
         let Some(client_state) = self.get_client_state_or_report_error(session, channel_id) else {
             return Ok(());
         };
@@ -458,14 +598,38 @@ impl russh::server::Handler for Handler {
             return Ok(());
         };
         let machine_id = client_state.bmc_connection.machine_id;
-        let Some(from_bmc_rx) = client_state
+        let Some(to_frontend_msg_tx) = client_state
             .bmc_connection
             .to_frontend_msg_weak_tx
             .upgrade()
-            .map(|tx| tx.subscribe())
         else {
             return Err(HandlerError::BmcDisconnectedBeforeSubscribe { machine_id })?;
         };
+        let from_bmc_rx = to_frontend_msg_tx.subscribe();
+
+        // Record the session, if enabled. Failing to record doesn't prevent the session.
+        let recorder_handle = if let Some(recording_context) = recording_context {
+            match session_recording::spawn(
+                &recording_context.recordings_path,
+                recording_context.session(machine_id, None),
+                client_state.window_size,
+                to_frontend_msg_tx.subscribe(),
+            )
+            .await
+            {
+                Ok(handle) => {
+                    client_state.recorder_input_tx = Some(handle.input_tx.clone());
+                    Some(handle)
+                }
+                Err(error) => {
+                    tracing::error!(peer_addr, %machine_id, %error, "could not start session recording");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        std::mem::drop(to_frontend_msg_tx);
 
         // Output the banner with instructions
         let banner = match client_state.bmc_connection.kind {
@@ -474,6 +638,35 @@ impl russh::server::Handler for Handler {
         };
         session.data(channel_id, banner.into()).ok();
 
+        // Attach to the console's write lock, and show who else is here
+        let console_attachment = if console_locking_enabled {
+            let attachment = Arc::new(
+                client_state
+                    .bmc_connection
+                    .console_lock
+                    .attach(console_user, console_admin),
+            );
+            session
+                .data(
+                    channel_id,
+                    format!(
+                        "{}\r\n(Type ~h after a newline for console lock help.)\r\n",
+                        attachment.summary()
+                    )
+                    .into_bytes()
+                    .into(),
+                )
+                .ok();
+            client_state.console = Some(ConsoleAttachment {
+                attachment: attachment.clone(),
+                escapes: EscapeParser::default(),
+                notified_read_only: false,
+            });
+            Some(attachment)
+        } else {
+            None
+        };
+
         // Tell the backend to return any "pending line": data since the last newline
         let (mut channel_rx, channel_tx) = channel.split();
         let (pending_line_reply_tx, pending_line_reply_rx) = oneshot::channel();
@@ -505,6 +698,13 @@ impl russh::server::Handler for Handler {
                     }
                 }
                 proxy_handle.shutdown_and_wait().await;
+                // Don't hold the write lock after the user is gone
+                if let Some(console_attachment) = console_attachment {
+                    console_attachment.detach();
+                }
+                if let Some(recorder_handle) = recorder_handle {
+                    recorder_handle.shutdown_and_wait().await;
+                }
             }
         });
 
@@ -518,9 +718,12 @@ impl russh::server::Handler for Handler {
         session: &mut Session,
     ) -> Result<(), Self::Error> {
         tracing::trace!(peer_addr = self.peer_addr, "exec_request");
+        let peer_addr = self.peer_addr.clone();
+        let recording_context = self.recording_context();
         let Some(PerClientState {
             client_channel,
             bmc_connection,
+            ..
         }) = self.get_client_state_or_report_error(session, channel_id)
         else {
             return Ok(());
@@ -543,6 +746,27 @@ impl russh::server::Handler for Handler {
             return Ok(());
         };
 
+        let machine_id = bmc_connection.machine_id;
+        let mut recording = match recording_context {
+            Some(recording_context) => match Recording::create(
+                &recording_context.recordings_path,
+                recording_context.session(machine_id, Some(String::from_utf8_lossy(data).into())),
+                None,
+            )
+            .await
+            {
+                Ok(recording) => Some(recording),
+                Err(error) => {
+                    tracing::error!(peer_addr, %machine_id, %error, "could not start session recording");
+                    None
+                }
+            },
+            None => None,
+        };
+        if let Some(recording) = recording.as_mut() {
+            recording.input(data).await.ok();
+        }
+
         let (reply_tx, reply_rx) = oneshot::channel();
         bmc_connection
             .to_bmc_msg_tx
@@ -555,29 +779,28 @@ impl russh::server::Handler for Handler {
                 what: "exec request",
             })?;
 
-        tokio::select! {
+        let (output, exit_status) = tokio::select! {
             _ = tokio::time::sleep(EXEC_TIMEOUT) => {
-                    channel
-                        .data(b"Error: request timeout\r\n".as_slice())
-                        .await
-                        .ok();
-                    channel.exit_status(1).await.ok();
+                (b"Error: request timeout\r\n".to_vec(), 1)
             }
             res = reply_rx => match res {
                 Ok(ExecReply {
                     output,
                     exit_status,
-                }) => {
-                    channel.data(output.as_slice()).await.ok();
-                    channel.exit_status(exit_status).await.ok();
-                }
-                Err(_) => {
-                    channel
-                        .data(b"Error: BMC disconnected\r\n".as_slice())
-                        .await
-                        .ok();
-                    channel.exit_status(1).await.ok();
-                }
+                }) => (output, exit_status),
+                Err(_) => (b"Error: BMC disconnected\r\n".to_vec(), 1),
+            }
+        };
+        channel.data(output.as_slice()).await.ok();
+        channel.exit_status(exit_status).await.ok();
+
+        if let Some(mut recording) = recording {
+            let result = match recording.output(&output).await {
+                Ok(()) => recording.finish().await,
+                Err(error) => Err(error),
+            };
+            if let Err(error) = result {
+                tracing::error!(peer_addr, %machine_id, %error, "error writing session recording");
             }
         }
 
@@ -598,6 +821,14 @@ impl russh::server::Handler for Handler {
     ) -> Result<(), Self::Error> {
         tracing::trace!(peer_addr = self.peer_addr, "window_change_request");
         if let Some(client_state) = self.get_client_state_or_report_error(session, channel) {
+            if let Some(recorder_input_tx) = &client_state.recorder_input_tx {
+                recorder_input_tx
+                    .send(RecordedInput::WindowChange {
+                        width: col_width,
+                        height: row_height,
+                    })
+                    .ok();
+            }
             client_state
                 .bmc_connection
                 .to_bmc_msg_tx
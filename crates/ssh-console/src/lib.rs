@@ -15,17 +15,21 @@
  * limitations under the License.
  */
 
+mod api_server;
 mod bmc;
 mod io_util;
 mod metrics;
 mod ssh_cert_parsing;
 mod ssh_server;
 
+mod console_lock;
 mod console_logger;
+mod console_monitor;
 mod frontend;
 
-// pub mods are only ones used by main.rs and integration tests
+// pub mods are only ones used by main.rs, integration tests, and carbide-admin-cli
 pub mod config;
+pub mod session_recording;
 pub mod shutdown_handle;
 
 // Used by fuzz tests
@@ -68,10 +72,21 @@ pub async fn spawn(config: Config) -> Result<SpawnHandle, SpawnError> {
     // 3) Start metrics server
     let metrics_handle = metrics::spawn(config.clone(), metrics).await?;
 
-    // 4) Wait for a shutdown signal, then shut down the above
+    // 4) Start API server, if configured
+    let api_handle = match config.api_listen_address {
+        Some(api_listen_address) => {
+            Some(api_server::spawn(api_listen_address, bmc_client_pool.connection_store()).await?)
+        }
+        None => None,
+    };
+
+    // 5) Wait for a shutdown signal, then shut down the above
     let (shutdown_tx, shutdown_rx) = oneshot::channel();
     let join_handle = tokio::spawn(async move {
         shutdown_rx.await.ok();
+        if let Some(api_handle) = api_handle {
+            api_handle.shutdown_and_wait().await;
+        }
         metrics_handle.shutdown_and_wait().await;
         bmc_client_pool.shutdown_and_wait().await;
         server.shutdown_and_wait().await;
@@ -91,6 +106,8 @@ pub enum SpawnError {
     SshServerSpawn(#[from] ssh_server::SpawnError),
     #[error("Error spawning metrics server: {0}")]
     MetricsSpawn(#[from] metrics::SpawnError),
+    #[error("Error spawning API server: {0}")]
+    ApiSpawn(#[from] api_server::SpawnError),
 }
 
 pub struct SpawnHandle {
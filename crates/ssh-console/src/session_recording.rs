@@ -0,0 +1,627 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Per-session console recordings in [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/)
+//! format.
+//!
+//! Every shell or exec session is recorded to its own `.cast` file, containing the output sent to
+//! the user with timestamps, everything the user typed, and terminal resizes. Recordings are stored
+//! per machine, alongside an `index.jsonl` file listing the sessions for that machine and who
+//! authenticated them:
+//!
+//! ```text
+//! <session_recordings_path>/<machine_id>/index.jsonl
+//! <session_recordings_path>/<machine_id>/<started_at>_<session_id>.cast
+//! ```
+//!
+//! The index is append-only: A line is written when a session starts and another one (with
+//! `ended_at` set) when it ends. [`list_sessions`] merges them.
+//!
+//! This module is public so that carbide-admin-cli can list and play back recordings.
+
+use std::collections::HashMap;
+use std::io;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use russh::ChannelMsg;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use crate::bmc::message_proxy::ToFrontendMessage;
+use crate::shutdown_handle::ShutdownHandle;
+
+pub const INDEX_FILE_NAME: &str = "index.jsonl";
+
+static DEFAULT_WIDTH: u32 = 80;
+static DEFAULT_HEIGHT: u32 = 24;
+
+/// How a session was authenticated
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthMethod {
+    /// OpenSSH certificate signed by a trusted CA, containing the admin role
+    OpensshCertificate,
+    /// Public key listed in the admin authorized_keys file
+    AuthorizedKeys,
+    /// Tenant public key, validated by carbide-api for the instance
+    TenantPublicKey,
+    /// Public key which was rejected, but accepted anyway because ssh-console runs in insecure mode
+    Insecure,
+}
+
+impl AuthMethod {
+    /// Whether the principal is a site admin, as opposed to a tenant
+    pub fn is_admin(&self) -> bool {
+        matches!(
+            self,
+            AuthMethod::OpensshCertificate | AuthMethod::AuthorizedKeys
+        )
+    }
+}
+
+/// Who authenticated a session
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Principal {
+    pub auth_method: AuthMethod,
+    /// The user from the SSH certificate (see [`crate::config::KeyIdFormat`]), or the certificate's
+    /// key ID if no user could be extracted. `None` for public key authentication.
+    pub user: Option<String>,
+    /// SHA256 fingerprint of the public key the user authenticated with
+    pub fingerprint: String,
+}
+
+impl std::fmt::Display for Principal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.user {
+            Some(user) => write!(f, "{user} ({})", self.fingerprint),
+            None => write!(f, "{}", self.fingerprint),
+        }
+    }
+}
+
+/// Metadata about a recorded session, stored in the header of the recording and in the index.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SessionInfo {
+    pub session_id: Uuid,
+    pub machine_id: String,
+    /// The username used in the ssh command line: Either the machine ID or the instance ID
+    pub connected_as: String,
+    pub principal: Principal,
+    pub peer_addr: String,
+    /// The command for exec sessions, `None` for interactive shell sessions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exec_command: Option<String>,
+}
+
+/// An entry in a machine's session index
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SessionIndexEntry {
+    #[serde(flatten)]
+    pub session: SessionInfo,
+    pub started_at: DateTime<Utc>,
+    #[serde(default)]
+    pub ended_at: Option<DateTime<Utc>>,
+    /// File name of the recording, relative to the machine's recording directory
+    pub recording: String,
+}
+
+impl SessionIndexEntry {
+    pub fn recording_path(&self, recordings_path: &Path) -> PathBuf {
+        recordings_path
+            .join(&self.session.machine_id)
+            .join(&self.recording)
+    }
+}
+
+/// The header (first line) of an asciicast v2 recording
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Header {
+    pub version: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Unix timestamp of the start of the recording
+    pub timestamp: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// ssh-console specific metadata. Players ignore unknown header fields.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_console: Option<SessionInfo>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventKind {
+    /// Data sent to the user's terminal
+    Output,
+    /// Data typed by the user
+    Input,
+    /// Terminal resize, data is `<width>x<height>`
+    Resize,
+    /// A marker, used to annotate the recording (e.g. when data was lost)
+    Marker,
+}
+
+impl EventKind {
+    fn code(&self) -> &'static str {
+        match self {
+            EventKind::Output => "o",
+            EventKind::Input => "i",
+            EventKind::Resize => "r",
+            EventKind::Marker => "m",
+        }
+    }
+}
+
+/// An event line of an asciicast v2 recording: `[time, code, data]`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Event {
+    /// Seconds since the start of the recording
+    pub time: f64,
+    pub kind: EventKind,
+    pub data: String,
+}
+
+impl Serialize for Event {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.time, self.kind.code(), &self.data).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Event {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (time, code, data) = <(f64, String, String)>::deserialize(deserializer)?;
+        let kind = match code.as_str() {
+            "o" => EventKind::Output,
+            "i" => EventKind::Input,
+            "r" => EventKind::Resize,
+            "m" => EventKind::Marker,
+            other => {
+                return Err(serde::de::Error::custom(format!(
+                    "unknown asciicast event code {other:?}"
+                )));
+            }
+        };
+        Ok(Event { time, kind, data })
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RecordingError {
+    #[error("error {context}: {error}")]
+    Io { context: String, error: io::Error },
+    #[error("invalid line {line} in {path}: {error}")]
+    Parse {
+        path: PathBuf,
+        line: usize,
+        error: serde_json::Error,
+    },
+    #[error("recording at {path} is empty")]
+    Empty { path: PathBuf },
+}
+
+/// List the recorded sessions of a machine, in the order they were started.
+pub fn list_sessions(
+    recordings_path: &Path,
+    machine_id: &str,
+) -> Result<Vec<SessionIndexEntry>, RecordingError> {
+    let index_path = recordings_path.join(machine_id).join(INDEX_FILE_NAME);
+    let index = match std::fs::File::open(&index_path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => {
+            return Err(RecordingError::Io {
+                context: format!("opening session index at {}", index_path.display()),
+                error,
+            });
+        }
+    };
+
+    let mut sessions: Vec<SessionIndexEntry> = Vec::new();
+    let mut positions: HashMap<Uuid, usize> = HashMap::new();
+    for (i, line) in io::BufReader::new(index).lines().enumerate() {
+        let line = line.map_err(|error| RecordingError::Io {
+            context: format!("reading session index at {}", index_path.display()),
+            error,
+        })?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: SessionIndexEntry =
+            serde_json::from_str(&line).map_err(|error| RecordingError::Parse {
+                path: index_path.clone(),
+                line: i + 1,
+                error,
+            })?;
+        // Later entries for the same session (written when it ends) replace the earlier ones.
+        match positions.get(&entry.session.session_id) {
+            Some(&pos) => sessions[pos] = entry,
+            None => {
+                positions.insert(entry.session.session_id, sessions.len());
+                sessions.push(entry);
+            }
+        }
+    }
+
+    Ok(sessions)
+}
+
+/// Read a recording, returning its header and all events.
+pub fn read_recording(path: &Path) -> Result<(Header, Vec<Event>), RecordingError> {
+    let contents = std::fs::read_to_string(path).map_err(|error| RecordingError::Io {
+        context: format!("reading recording at {}", path.display()),
+        error,
+    })?;
+    let parse_error = |line: usize, error| RecordingError::Parse {
+        path: path.to_path_buf(),
+        line,
+        error,
+    };
+
+    let mut lines = contents
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty());
+    let Some((i, header)) = lines.next() else {
+        return Err(RecordingError::Empty {
+            path: path.to_path_buf(),
+        });
+    };
+    let header: Header = serde_json::from_str(header).map_err(|e| parse_error(i + 1, e))?;
+    let events = lines
+        .map(|(i, line)| serde_json::from_str::<Event>(line).map_err(|e| parse_error(i + 1, e)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((header, events))
+}
+
+/// An open recording of a single session
+pub(crate) struct Recording {
+    file: File,
+    index_path: PathBuf,
+    index_entry: SessionIndexEntry,
+    started: Instant,
+    // Trailing bytes of an incomplete UTF-8 sequence, per event kind
+    partial_output: Vec<u8>,
+    partial_input: Vec<u8>,
+}
+
+impl Recording {
+    /// Create the recording file, write its header, and add the session to the machine's index.
+    pub(crate) async fn create(
+        recordings_path: &Path,
+        session: SessionInfo,
+        window_size: Option<(u32, u32)>,
+    ) -> Result<Self, RecordingError> {
+        let machine_dir = recordings_path.join(&session.machine_id);
+        tokio::fs::create_dir_all(&machine_dir)
+            .await
+            .map_err(|error| RecordingError::Io {
+                context: format!("creating recording directory {}", machine_dir.display()),
+                error,
+            })?;
+
+        let started_at = Utc::now();
+        let recording = format!(
+            "{}_{}.cast",
+            started_at.format("%Y%m%dT%H%M%SZ"),
+            session.session_id
+        );
+        let recording_path = machine_dir.join(&recording);
+        let file = OpenOptions::new()
+            .create_new(true)
+            .write(true)
+            .open(&recording_path)
+            .await
+            .map_err(|error| RecordingError::Io {
+                context: format!("creating recording at {}", recording_path.display()),
+                error,
+            })?;
+
+        let (width, height) = window_size.unwrap_or((DEFAULT_WIDTH, DEFAULT_HEIGHT));
+        let header = Header {
+            version: 2,
+            width,
+            height,
+            timestamp: started_at.timestamp(),
+            title: Some(format!("{} by {}", session.connected_as, session.principal)),
+            ssh_console: Some(session.clone()),
+        };
+
+        let mut recording = Self {
+            file,
+            index_path: machine_dir.join(INDEX_FILE_NAME),
+            index_entry: SessionIndexEntry {
+                session,
+                started_at,
+                ended_at: None,
+                recording,
+            },
+            started: Instant::now(),
+            partial_output: Vec::new(),
+            partial_input: Vec::new(),
+        };
+
+        recording.write_line(&header).await?;
+        recording.append_index().await?;
+        Ok(recording)
+    }
+
+    pub(crate) async fn output(&mut self, data: &[u8]) -> Result<(), RecordingError> {
+        let data = take_utf8(&mut self.partial_output, data);
+        self.event(EventKind::Output, data).await
+    }
+
+    pub(crate) async fn input(&mut self, data: &[u8]) -> Result<(), RecordingError> {
+        let data = take_utf8(&mut self.partial_input, data);
+        self.event(EventKind::Input, data).await
+    }
+
+    pub(crate) async fn resize(&mut self, width: u32, height: u32) -> Result<(), RecordingError> {
+        self.event(EventKind::Resize, format!("{width}x{height}"))
+            .await
+    }
+
+    pub(crate) async fn marker(&mut self, label: String) -> Result<(), RecordingError> {
+        self.event(EventKind::Marker, label).await
+    }
+
+    /// Flush the recording and mark the session as ended in the index.
+    pub(crate) async fn finish(mut self) -> Result<(), RecordingError> {
+        self.file
+            .flush()
+            .await
+            .map_err(|error| RecordingError::Io {
+                context: "flushing recording".to_string(),
+                error,
+            })?;
+        self.index_entry.ended_at = Some(Utc::now());
+        self.append_index().await
+    }
+
+    async fn event(&mut self, kind: EventKind, data: String) -> Result<(), RecordingError> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        let event = Event {
+            time: self.started.elapsed().as_secs_f64(),
+            kind,
+            data,
+        };
+        self.write_line(&event).await
+    }
+
+    async fn write_line(&mut self, value: &impl Serialize) -> Result<(), RecordingError> {
+        let mut line = serde_json::to_vec(value).expect("BUG: recording line is not serializable");
+        line.push(b'\n');
+        self.file
+            .write_all(&line)
+            .await
+            .map_err(|error| RecordingError::Io {
+                context: "writing recording".to_string(),
+                error,
+            })
+    }
+
+    async fn append_index(&self) -> Result<(), RecordingError> {
+        let mut line = serde_json::to_vec(&self.index_entry)
+            .expect("BUG: session index entry is not serializable");
+        line.push(b'\n');
+        // Index lines are written with a single append, so concurrent sessions don't interleave.
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.index_path)
+            .await
+            .map_err(|error| RecordingError::Io {
+                context: format!("opening session index at {}", self.index_path.display()),
+                error,
+            })?
+            .write_all(&line)
+            .await
+            .map_err(|error| RecordingError::Io {
+                context: format!("writing session index at {}", self.index_path.display()),
+                error,
+            })
+    }
+}
+
+/// Append `data` to `partial` and return everything up to the last complete UTF-8 sequence,
+/// leaving an incomplete trailing sequence in `partial` for the next call. (asciicast data must be
+/// valid UTF-8, and BMC output can split multi-byte characters across messages.)
+fn take_utf8(partial: &mut Vec<u8>, data: &[u8]) -> String {
+    partial.extend_from_slice(data);
+    let valid_up_to = match std::str::from_utf8(partial) {
+        Ok(_) => partial.len(),
+        // An incomplete sequence at the end: keep it for later
+        Err(e) if e.error_len().is_none() => e.valid_up_to(),
+        // Invalid bytes: don't hold on to them, replace them instead
+        Err(_) => partial.len(),
+    };
+    let rest = partial.split_off(valid_up_to);
+    let taken = std::mem::replace(partial, rest);
+    String::from_utf8_lossy(&taken).into_owned()
+}
+
+/// Input from the user's connection, to be recorded
+pub(crate) enum RecordedInput {
+    Data(Vec<u8>),
+    WindowChange { width: u32, height: u32 },
+}
+
+/// Spawn a background task which records a shell session: Everything sent from the BMC to the
+/// user, and everything the user sends via [`Handle::input_tx`].
+pub(crate) async fn spawn(
+    recordings_path: &Path,
+    session: SessionInfo,
+    window_size: Option<(u32, u32)>,
+    mut from_bmc_rx: broadcast::Receiver<ToFrontendMessage>,
+) -> Result<Handle, RecordingError> {
+    let mut recording = Recording::create(recordings_path, session, window_size).await?;
+    let (shutdown_tx, mut shutdown_rx) = oneshot::channel();
+    let (input_tx, mut input_rx) = mpsc::unbounded_channel();
+
+    let join_handle = tokio::spawn(async move {
+        loop {
+            let result = tokio::select! {
+                _ = &mut shutdown_rx => {
+                    break;
+                }
+
+                res = from_bmc_rx.recv() => match res {
+                    Ok(msg) => match Arc::<ChannelMsg>::from(msg).as_ref() {
+                        ChannelMsg::Data { data } | ChannelMsg::ExtendedData { data, .. } => {
+                            recording.output(data).await
+                        }
+                        _ => Ok(()),
+                    },
+                    Err(broadcast::error::RecvError::Closed) => {
+                        break;
+                    }
+                    Err(broadcast::error::RecvError::Lagged(count)) => {
+                        recording
+                            .marker(format!("recording lagged by {count} messages, output is missing"))
+                            .await
+                    }
+                },
+
+                Some(input) = input_rx.recv() => match input {
+                    RecordedInput::Data(data) => recording.input(&data).await,
+                    RecordedInput::WindowChange { width, height } => {
+                        recording.resize(width, height).await
+                    }
+                },
+            };
+
+            if let Err(error) = result {
+                tracing::error!(%error, "error writing session recording, stopping recording");
+                break;
+            }
+        }
+
+        let session_id = recording.index_entry.session.session_id;
+        if let Err(error) = recording.finish().await {
+            tracing::error!(%session_id, %error, "error finishing session recording");
+        }
+    });
+
+    Ok(Handle {
+        shutdown_tx,
+        join_handle,
+        input_tx,
+    })
+}
+
+pub(crate) struct Handle {
+    shutdown_tx: oneshot::Sender<()>,
+    join_handle: JoinHandle<()>,
+    pub input_tx: mpsc::UnboundedSender<RecordedInput>,
+}
+
+impl ShutdownHandle<()> for Handle {
+    fn into_parts(self) -> (oneshot::Sender<()>, JoinHandle<()>) {
+        (self.shutdown_tx, self.join_handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use temp_dir::TempDir;
+
+    use super::*;
+
+    fn session_info(session_id: Uuid) -> SessionInfo {
+        SessionInfo {
+            session_id,
+            machine_id: "fm100htasujpl2icpvjedluh5qjlmba1v6ln075me7rvtdiqsfht08rrkjg".to_string(),
+            connected_as: "d40ad750-b925-4b34-b25a-d7f94458cc9e".to_string(),
+            principal: Principal {
+                auth_method: AuthMethod::OpensshCertificate,
+                user: Some("alice".to_string()),
+                fingerprint: "SHA256:abc".to_string(),
+            },
+            peer_addr: "127.0.0.1:50000".to_string(),
+            exec_command: None,
+        }
+    }
+
+    #[test]
+    fn test_take_utf8_keeps_incomplete_sequences() {
+        let mut partial = Vec::new();
+        let euro = "€".as_bytes();
+        assert_eq!(take_utf8(&mut partial, &[b'a', euro[0], euro[1]]), "a");
+        assert_eq!(partial, &euro[..2]);
+        assert_eq!(take_utf8(&mut partial, &[euro[2], b'b']), "€b");
+        assert!(partial.is_empty());
+        assert_eq!(take_utf8(&mut partial, &[0xff, b'c']), "\u{fffd}c");
+    }
+
+    #[tokio::test]
+    async fn test_recording_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+
+        let mut recording = Recording::create(dir.path(), session_info(first), Some((120, 40)))
+            .await
+            .unwrap();
+        recording.output(b"login: ").await.unwrap();
+        recording.input(b"root\r").await.unwrap();
+        recording.resize(100, 30).await.unwrap();
+
+        // A second session, still running
+        let _running = Recording::create(dir.path(), session_info(second), None)
+            .await
+            .unwrap();
+        recording.finish().await.unwrap();
+
+        let sessions = list_sessions(dir.path(), &session_info(first).machine_id).unwrap();
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].session, session_info(first));
+        assert!(sessions[0].ended_at.is_some());
+        assert_eq!(sessions[1].session, session_info(second));
+        assert!(sessions[1].ended_at.is_none());
+
+        let (header, events) = read_recording(&sessions[0].recording_path(dir.path())).unwrap();
+        assert_eq!(header.version, 2);
+        assert_eq!((header.width, header.height), (120, 40));
+        assert_eq!(header.ssh_console, Some(session_info(first)));
+        assert_eq!(
+            events
+                .iter()
+                .map(|e| (e.kind, e.data.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (EventKind::Output, "login: "),
+                (EventKind::Input, "root\r"),
+                (EventKind::Resize, "100x30"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_sessions_without_index() {
+        let dir = TempDir::new().unwrap();
+        assert!(list_sessions(dir.path(), "unknown").unwrap().is_empty());
+    }
+}
@@ -28,6 +28,8 @@ use tokio::time::Instant;
 
 mod util;
 
+use ::ssh_console::session_recording;
+use ::ssh_console::session_recording::{AuthMethod, EventKind};
 use ::ssh_console::shutdown_handle::ShutdownHandle;
 use api_test_helper::utils::REPO_ROOT;
 use util::ssh_console_test_helper;
@@ -154,6 +156,40 @@ async fn test_ssh_console() -> eyre::Result<()> {
             log_path.display(),
             logs
         );
+
+        // Both the admin and the tenant session should have been recorded
+        let recordings_path = logs_path.join("recordings");
+        let sessions =
+            session_recording::list_sessions(&recordings_path, &mock_host.machine_id.to_string())?;
+        for (connected_as, auth_method) in [
+            (mock_host.machine_id.to_string(), AuthMethod::AuthorizedKeys),
+            (
+                mock_host.instance_id.to_string(),
+                AuthMethod::TenantPublicKey,
+            ),
+        ] {
+            let session = sessions
+                .iter()
+                .find(|s| {
+                    s.session.connected_as == connected_as
+                        && s.session.principal.auth_method == auth_method
+                })
+                .unwrap_or_else(|| {
+                    panic!(
+                        "no recorded session as {connected_as} via {auth_method:?} for {}: {sessions:?}",
+                        mock_host.machine_id
+                    )
+                });
+            let (header, events) =
+                session_recording::read_recording(&session.recording_path(&recordings_path))?;
+            assert_eq!(header.version, 2);
+            assert_eq!(header.ssh_console.as_ref(), Some(&session.session));
+            assert!(
+                events.iter().any(|e| e.kind == EventKind::Output),
+                "recording of session {} has no output",
+                session.session.session_id
+            );
+        }
     }
 
     Ok(())
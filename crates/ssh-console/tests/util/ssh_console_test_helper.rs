@@ -62,6 +62,7 @@ pub async fn spawn(
     let config = ssh_console::config::Config {
         listen_address,
         metrics_address,
+        api_listen_address: None,
         carbide_uri: format!("https://localhost:{carbide_port}")
             .try_into()
             .expect("Invalid URI?"),
@@ -81,6 +82,14 @@ pub async fn spawn(
         api_poll_interval: Duration::from_secs(1),
         console_logging_enabled: true,
         console_logs_path: logs_dir.path().to_path_buf(),
+        session_recording_enabled: true,
+        session_recordings_path: logs_dir.path().join("recordings"),
+        console_locking_enabled: true,
+        console_lock_idle_timeout: Duration::from_secs(300),
+        console_output_retention: Defaults::console_output_retention(),
+        console_output_max_lines: Defaults::console_output_max_lines(),
+        console_alert_clear_after: Defaults::console_alert_clear_after(),
+        console_matchers: Defaults::console_matchers(),
         override_bmc_ssh_host: None,
         // Eagerly retry if the connection was only open a short while (needed for tests to avoid
         // long backoff intervals.)
@@ -55,6 +55,10 @@ pub struct IterationConfig {
     /// The duration of this needs to be longer than the time between state handler
     /// invocations for the object
     pub metric_hold_time: std::time::Duration,
+
+    /// Configures how the processing capacity is shared between objects of different
+    /// [`QueuePriority`](crate::queue_priority::QueuePriority) classes
+    pub priority_lanes: PriorityLanesConfig,
 }
 
 impl Default for IterationConfig {
@@ -71,6 +75,117 @@ impl Default for IterationConfig {
             processor_dispatch_interval: Duration::from_secs(2),
             metric_emission_interval: Duration::from_secs(60),
             metric_hold_time: Duration::from_secs(5 * 60),
+            priority_lanes: PriorityLanesConfig::default(),
+        }
+    }
+}
+
+/// Settings for a single priority class of queued objects
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PriorityLaneConfig {
+    /// The share of dispatch slots that objects of this class receive while
+    /// objects of multiple classes are queued, relative to the weights of the
+    /// other classes.
+    /// With a weight of 0, objects of this class only get dispatched if no
+    /// objects of other classes are waiting.
+    pub weight: u32,
+
+    /// The maximum amount of objects of this class that are processed concurrently.
+    /// If not set, only `IterationConfig::max_concurrency` applies.
+    pub max_concurrency: Option<usize>,
+}
+
+/// Settings for all priority classes of queued objects
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PriorityLanesConfig {
+    pub tenant_impacting: PriorityLaneConfig,
+    pub operator_requested: PriorityLaneConfig,
+    pub background: PriorityLaneConfig,
+}
+
+impl Default for PriorityLanesConfig {
+    fn default() -> Self {
+        Self {
+            tenant_impacting: PriorityLaneConfig {
+                weight: 6,
+                max_concurrency: None,
+            },
+            operator_requested: PriorityLaneConfig {
+                weight: 3,
+                max_concurrency: None,
+            },
+            background: PriorityLaneConfig {
+                weight: 1,
+                max_concurrency: None,
+            },
+        }
+    }
+}
+
+/// Settings for delivering state changes from the transactional outbox to durable hooks
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct OutboxRelayConfig {
+    /// How often the relay checks the outbox for entries which are due for delivery
+    pub poll_interval: Duration,
+
+    /// The maximum amount of entries which are loaded from the outbox in one iteration
+    pub batch_size: u32,
+
+    /// The maximum amount of entries which are delivered in parallel.
+    /// Entries for the same object are always delivered in order.
+    pub max_concurrency: usize,
+
+    /// The amount of delivery attempts after which an entry is moved into the
+    /// dead-letter table
+    pub max_attempts: u32,
+
+    /// The time to wait before the first retry. The time doubles with every
+    /// further attempt, up to `max_retry_backoff`.
+    pub initial_retry_backoff: Duration,
+
+    /// The maximum time to wait between two delivery attempts
+    pub max_retry_backoff: Duration,
+}
+
+impl Default for OutboxRelayConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(1),
+            batch_size: 100,
+            max_concurrency: 16,
+            max_attempts: 10,
+            initial_retry_backoff: Duration::from_secs(1),
+            max_retry_backoff: Duration::from_secs(5 * 60),
         }
     }
 }
+
+impl OutboxRelayConfig {
+    /// Returns how long to wait before the next delivery attempt, given the
+    /// amount of attempts that already failed
+    pub fn retry_backoff(&self, failed_attempts: u32) -> Duration {
+        let exponent = failed_attempts.saturating_sub(1).min(31);
+        self.initial_retry_backoff
+            .saturating_mul(1u32 << exponent)
+            .min(self.max_retry_backoff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outbox_retry_backoff() {
+        let config = OutboxRelayConfig {
+            initial_retry_backoff: Duration::from_secs(2),
+            max_retry_backoff: Duration::from_secs(60),
+            ..Default::default()
+        };
+        assert_eq!(config.retry_backoff(1), Duration::from_secs(2));
+        assert_eq!(config.retry_backoff(2), Duration::from_secs(4));
+        assert_eq!(config.retry_backoff(4), Duration::from_secs(16));
+        assert_eq!(config.retry_backoff(6), Duration::from_secs(60));
+        assert_eq!(config.retry_backoff(100), Duration::from_secs(60));
+    }
+}
@@ -23,12 +23,14 @@ use sqlx::{FromRow, Row};
 
 use crate::controller::periodic_enqueuer::PeriodicEnqueuer;
 use crate::io::StateControllerIO;
+use crate::queue_priority::QueuePriority;
 use crate::state_handler::StateHandlerError;
 
 mod builder;
 pub mod db;
 mod enqueuer;
 pub use enqueuer::Enqueuer;
+mod outbox_relay;
 
 pub mod periodic_enqueuer;
 pub mod processor;
@@ -71,15 +73,36 @@ pub struct QueuedObject {
     /// Identifies the processor which is executing the state handler
     /// The value of this field will be NULL in case the object is not yet processed
     pub processed_by: Option<String>,
+    /// The priority class the object is queued in
+    pub priority: QueuePriority,
 }
 
 impl<'r> FromRow<'r, PgRow> for QueuedObject {
     fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
         let object_id = row.try_get("object_id")?;
         let processed_by: Option<String> = row.try_get("processed_by")?;
+        let priority: i16 = row.try_get("priority")?;
         Ok(QueuedObject {
             object_id,
             processed_by,
+            priority: QueuePriority::from_db(priority),
+        })
+    }
+}
+
+/// A queued object which has been acquired by a processor
+#[derive(Debug, Clone)]
+pub struct AcquiredObject {
+    pub object: QueuedObject,
+    /// When the object was queued
+    pub queued_at: DateTime<Utc>,
+}
+
+impl<'r> FromRow<'r, PgRow> for AcquiredObject {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(AcquiredObject {
+            object: QueuedObject::from_row(row)?,
+            queued_at: row.try_get("queued_at")?,
         })
     }
 }
@@ -95,6 +118,8 @@ impl<'r> FromRow<'r, PgRow> for QueuedObject {
 pub struct StateController<IO: StateControllerIO> {
     enqueuer: PeriodicEnqueuer<IO>,
     processor: processor::StateProcessor<IO>,
+    /// Only present if durable state change hooks are registered
+    outbox_relay: Option<outbox_relay::OutboxRelay<IO>>,
 }
 
 impl<IO: StateControllerIO> StateController<IO> {
@@ -136,6 +161,22 @@ impl<IO: StateControllerIO> StateController<IO> {
         // Immediately emit the latest set of metrics
         self.processor.emit_metrics();
     }
+
+    /// Delivers pending entries of the state change outbox to durable hooks,
+    /// and returns the amount of entries that have been processed
+    #[cfg(any(test, feature = "test-support"))]
+    pub async fn run_outbox_relay_iteration(&self) -> usize {
+        let Some(outbox_relay) = &self.outbox_relay else {
+            return 0;
+        };
+        match outbox_relay.run_single_iteration().await {
+            Ok(num_entries) => num_entries,
+            Err(err) => {
+                tracing::error!(%err, "Outbox relay iteration error");
+                0
+            }
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -15,7 +15,7 @@
  * limitations under the License.
  */
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use db::work_lock_manager::WorkLockManagerHandle;
@@ -25,12 +25,16 @@ use tokio_util::sync::CancellationToken;
 
 use crate::config::IterationConfig;
 use crate::controller::StateController;
+use crate::controller::outbox_relay::{OutboxRelay, OutboxRelayMetricsEmitter};
 use crate::controller::periodic_enqueuer::{EnqueuerMetricsEmitter, PeriodicEnqueuer};
 use crate::controller::processor::{ProcessorMetricsEmitter, StateProcessor};
 use crate::io::StateControllerIO;
 use crate::metrics::MetricHolder;
 use crate::state_change_emitter::StateChangeEmitter;
 use crate::state_handler::{NoopStateHandler, StateHandler, StateHandlerContextObjects};
+use crate::state_handler_middleware::{
+    DynStateHandlerMiddleware, MiddlewareChain, MiddlewareMetricsEmitter,
+};
 
 /// The return value of `[Builder::build_internal]`
 struct BuildOrSpawn<IO: StateControllerIO> {
@@ -63,6 +67,9 @@ pub struct Builder<IO: StateControllerIO> {
                 ObjectId = IO::ObjectId,
             >,
     >,
+    middlewares: Vec<
+        DynStateHandlerMiddleware<IO::ObjectId, IO::State, IO::ControllerState, IO::ContextObjects>,
+    >,
     services: Option<Arc<<IO::ContextObjects as StateHandlerContextObjects>::Services>>,
     state_change_emitter: Arc<StateChangeEmitter<IO::ObjectId, IO::ControllerState>>,
     processor_id: Option<String>,
@@ -82,6 +89,7 @@ impl<IO: StateControllerIO> Default for Builder<IO> {
                 IO::ControllerState,
                 IO::ContextObjects,
             >::default()),
+            middlewares: Vec::new(),
             meter: None,
             object_type_for_metrics: None,
             services: None,
@@ -130,6 +138,16 @@ impl<IO: StateControllerIO> Builder<IO> {
                 build_or_spawn.controller_name
             ))
             .spawn(async move { build_or_spawn.controller.processor.run().await })?;
+
+        if let Some(outbox_relay) = build_or_spawn.controller.outbox_relay {
+            join_set
+                .build_task()
+                .name(&format!(
+                    "state_change_outbox_relay {}",
+                    build_or_spawn.controller_name
+                ))
+                .spawn(async move { outbox_relay.run().await })?;
+        }
         Ok(())
     }
 
@@ -179,6 +197,20 @@ impl<IO: StateControllerIO> Builder<IO> {
             .clone()
             .map(|meter| EnqueuerMetricsEmitter::new(&controller_name, &meter));
 
+        let outbox_relay = if self.state_change_emitter.has_durable_hooks() {
+            Some(OutboxRelay::<IO> {
+                pool: database.clone(),
+                work_lock_manager_handle: work_lock_manager_handle.clone(),
+                state_change_emitter: self.state_change_emitter.clone(),
+                metric_emitter: meter
+                    .as_ref()
+                    .map(|meter| OutboxRelayMetricsEmitter::new(&controller_name, meter)),
+                cancel_token: cancel_token.clone(),
+            })
+        } else {
+            None
+        };
+
         let enqueuer = PeriodicEnqueuer::<IO> {
             pool: database.clone(),
             work_lock_manager_handle,
@@ -198,6 +230,17 @@ impl<IO: StateControllerIO> Builder<IO> {
             span_id,
             controller = IO::LOG_SPAN_CONTROLLER_NAME,
         );
+        let mut state_handler = self.state_handler;
+        if !self.middlewares.is_empty() {
+            state_handler = Arc::new(MiddlewareChain::new(
+                state_handler,
+                self.middlewares,
+                meter
+                    .as_ref()
+                    .map(|meter| MiddlewareMetricsEmitter::new(&controller_name, meter)),
+            ));
+        }
+
         let processor_metric_emitter =
             meter.map(|meter| ProcessorMetricsEmitter::new(&controller_name, &meter));
 
@@ -207,13 +250,14 @@ impl<IO: StateControllerIO> Builder<IO> {
             iteration_config: self.iteration_config,
             handler_services: services,
             io: self.io.unwrap_or_default(),
-            state_handler: self.state_handler.clone(),
+            state_handler,
             metric_emitter: processor_metric_emitter,
             metric_holder,
             state_change_emitter: self.state_change_emitter,
-            in_flight: HashSet::new(),
+            in_flight: HashMap::new(),
+            lane_scheduler: Default::default(),
             completed_objects: HashSet::new(),
-            requeue_objects: HashSet::new(),
+            requeue_objects: HashMap::new(),
             task_sender,
             task_receiver,
             object_metrics: Default::default(),
@@ -227,6 +271,7 @@ impl<IO: StateControllerIO> Builder<IO> {
         let controller = StateController::<IO> {
             processor,
             enqueuer,
+            outbox_relay,
         };
 
         Ok(BuildOrSpawn {
@@ -299,6 +344,40 @@ impl<IO: StateControllerIO> Builder<IO> {
         self
     }
 
+    /// Adds a middleware which wraps the invocations of the state handler.
+    ///
+    /// Middlewares are invoked in the order in which they are added.
+    pub fn middleware(
+        mut self,
+        middleware: DynStateHandlerMiddleware<
+            IO::ObjectId,
+            IO::State,
+            IO::ControllerState,
+            IO::ContextObjects,
+        >,
+    ) -> Self {
+        self.middlewares.push(middleware);
+        self
+    }
+
+    /// Adds multiple middlewares which wrap the invocations of the state handler.
+    ///
+    /// Middlewares are invoked in the order in which they are added.
+    pub fn middlewares(
+        mut self,
+        middlewares: impl IntoIterator<
+            Item = DynStateHandlerMiddleware<
+                IO::ObjectId,
+                IO::State,
+                IO::ControllerState,
+                IO::ContextObjects,
+            >,
+        >,
+    ) -> Self {
+        self.middlewares.extend(middlewares);
+        self
+    }
+
     /// Sets the state change emitter for broadcasting state transitions to hooks
     pub fn state_change_emitter(
         mut self,
@@ -22,8 +22,10 @@ use db::{BIND_LIMIT, DatabaseError};
 use sqlx::{PgConnection, PgPool};
 
 use crate::controller::{
-    ControllerIteration, ControllerIterationId, LockedControllerIteration, QueuedObject,
+    AcquiredObject, ControllerIteration, ControllerIterationId, LockedControllerIteration,
+    QueuedObject,
 };
+use crate::queue_priority::QueuePriority;
 
 /// Inserts a new entry into the iteration table
 async fn create_iteration(
@@ -110,10 +112,14 @@ pub enum LockIterationTableError {
 /// Enqueues object IDs for processing into the queued objects table with name `table_id`
 /// If the object is enqueued, then keep the current entry. That guarantees that the object will be processed
 /// with the oldest possible run id and that the processed_by field won't get lost.
+/// If the object is enqueued with a higher priority than the existing entry, the priority
+/// of the entry is raised.
+///
+/// Returns the amount of objects which had not been queued before.
 pub async fn queue_objects(
     txn: &mut PgConnection,
     table_id: &str,
-    queued_objects: &[String],
+    queued_objects: &[(String, QueuePriority)],
 ) -> Result<usize, DatabaseError> {
     // Object IDs need to be sorted in order to avoid a deadlock on concurrent calls to this
     // method.
@@ -131,6 +137,9 @@ pub async fn queue_objects(
     // of sort order across all callers.
     let mut sorted = queued_objects.to_vec();
     sorted.sort();
+    // An object can only be inserted once per statement. Keep the entry with the
+    // highest priority, which is the first one after sorting.
+    sorted.dedup_by(|a, b| a.0 == b.0);
     // Make sure we are not running into the BIND_LIMIT
     // The theoretical limit would be BIND_LIMIT
     // However shorter transactions are ok here - we still queue 1k objects
@@ -142,20 +151,25 @@ pub async fn queue_objects(
     for queued_objects in sorted.chunks(OBJECTS_PER_QUERY) {
         let mut builder = sqlx::QueryBuilder::new("INSERT INTO ");
         builder.push(table_id);
-        builder.push("(object_id)");
+        builder.push("(object_id, priority)");
 
-        builder.push_values(queued_objects, |mut b, object_id| {
+        builder.push_values(queued_objects, |mut b, (object_id, priority)| {
             b.push_bind(object_id);
+            b.push_bind(priority.to_db());
         });
 
-        builder.push("ON CONFLICT (object_id) DO NOTHING");
-        let query = builder.build();
+        // Only rows which got inserted or whose priority got raised are returned.
+        // `xmax` is 0 for rows which had been inserted by this statement.
+        builder.push(" ON CONFLICT (object_id) DO UPDATE SET priority = EXCLUDED.priority WHERE ");
+        builder.push(table_id);
+        builder.push(".priority > EXCLUDED.priority RETURNING (xmax = 0) AS inserted");
+        let query = builder.build_query_scalar::<bool>();
 
-        let result = query
-            .execute(&mut *txn)
+        let inserted = query
+            .fetch_all(&mut *txn)
             .await
             .map_err(|e| DatabaseError::new("StateController::queue_object", e))?;
-        num_enqueued += result.rows_affected() as usize;
+        num_enqueued += inserted.into_iter().filter(|inserted| *inserted).count();
     }
 
     Ok(num_enqueued)
@@ -181,18 +195,21 @@ pub async fn fetch_queued_objects(
 /// current processor.
 /// The objects will be marked as `processed_by` with the given ID - which will avoid
 /// other processors to pick up the objects.
+/// If `priority` is set, only objects of the given priority class are acquired.
 pub async fn acquire_queued_objects(
     txn: &mut PgConnection,
     table_id: &str,
     count: u32, // u32 to avoid u64 numbers getting passed that are not valid in postgres
+    priority: Option<QueuePriority>,
     processor_id: &str,
     max_outdated: std::time::Duration,
-) -> Result<Vec<QueuedObject>, DatabaseError> {
+) -> Result<Vec<AcquiredObject>, DatabaseError> {
     // Grab the oldest ones first
     let query = format!(
         "WITH dequeued_ids AS (
             SELECT object_id FROM {table_id} WHERE (processed_by IS NULL OR processing_started_at + $1::interval < now())
-            ORDER BY processing_started_at ASC
+            AND ($3::smallint IS NULL OR priority = $3)
+            ORDER BY processing_started_at ASC, queued_at ASC
             FOR UPDATE SKIP LOCKED
             LIMIT {count}
         )
@@ -202,6 +219,7 @@ pub async fn acquire_queued_objects(
     let result = sqlx::query_as(&query)
         .bind(max_outdated)
         .bind(processor_id)
+        .bind(priority.map(QueuePriority::to_db))
         .fetch_all(txn)
         .await
         .map_err(|e| DatabaseError::new("StateController::acquire_queued_objects", e))?;
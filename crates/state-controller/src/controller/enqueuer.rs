@@ -19,6 +19,7 @@ use ::db::DatabaseError;
 
 use super::db;
 use crate::io::StateControllerIO;
+use crate::queue_priority::QueuePriority;
 
 /// Allows to request state handling for objects of a certain type
 #[derive(Debug, Clone)]
@@ -36,13 +37,30 @@ impl<IO: StateControllerIO> Enqueuer<IO> {
     }
 
     /// Requests state handling for the given object
+    ///
+    /// The object is queued with [`QueuePriority::OperatorRequested`], since explicit
+    /// requests for state handling are usually the reaction to an event that needs
+    /// to be handled faster than periodic background work.
     pub async fn enqueue_object(&self, object_id: &IO::ObjectId) -> Result<bool, DatabaseError> {
+        self.enqueue_object_with_priority(object_id, QueuePriority::OperatorRequested)
+            .await
+    }
+
+    /// Requests state handling for the given object with a certain priority
+    ///
+    /// If the object is already queued with a lower priority, its priority is raised.
+    /// Returns `true` if the object had not been queued before.
+    pub async fn enqueue_object_with_priority(
+        &self,
+        object_id: &IO::ObjectId,
+        priority: QueuePriority,
+    ) -> Result<bool, DatabaseError> {
         let mut conn = self.pool.acquire().await.map_err(DatabaseError::acquire)?;
 
         let num_enqueued = db::queue_objects(
             &mut conn,
             IO::DB_QUEUED_OBJECTS_TABLE_NAME,
-            &[object_id.to_string()],
+            &[(object_id.to_string(), priority)],
         )
         .await?;
 
@@ -0,0 +1,325 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use ::db::DatabaseError;
+use ::db::state_change_outbox::StateChangeOutboxEntry;
+use ::db::work_lock_manager::WorkLockManagerHandle;
+use carbide_utils::periodic_timer::PeriodicTimer;
+use futures::StreamExt;
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Histogram, Meter};
+use tokio_util::sync::CancellationToken;
+
+use crate::io::StateControllerIO;
+use crate::state_change_emitter::StateChangeEmitter;
+use crate::state_change_outbox::{DeliveryError, OutboxStateChange};
+
+/// Delivers the state changes that have been written into the transactional
+/// outbox to the durable hooks of a [`StateChangeEmitter`].
+///
+/// The relay is guaranteed to only run on a single carbide instance at a time.
+pub(super) struct OutboxRelay<IO: StateControllerIO> {
+    pub(super) pool: sqlx::PgPool,
+    pub(super) work_lock_manager_handle: WorkLockManagerHandle,
+    pub(super) state_change_emitter: Arc<StateChangeEmitter<IO::ObjectId, IO::ControllerState>>,
+    pub(super) metric_emitter: Option<OutboxRelayMetricsEmitter>,
+    pub(super) cancel_token: CancellationToken,
+}
+
+/// The outcome of delivering a single outbox entry
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum DeliveryOutcome {
+    Delivered,
+    Retry,
+    DeadLetter,
+    /// No hook with the name of the entry is registered. This happens if the
+    /// hook has been removed from the configuration while the relay was running.
+    /// The entry is kept in the outbox.
+    UnknownHook,
+}
+
+impl DeliveryOutcome {
+    fn as_str(self) -> &'static str {
+        match self {
+            DeliveryOutcome::Delivered => "delivered",
+            DeliveryOutcome::Retry => "retry",
+            DeliveryOutcome::DeadLetter => "dead_letter",
+            DeliveryOutcome::UnknownHook => "unknown_hook",
+        }
+    }
+}
+
+impl<IO: StateControllerIO> OutboxRelay<IO> {
+    fn work_key() -> String {
+        format!(
+            "state_change_outbox_relay::{}",
+            IO::LOG_SPAN_CONTROLLER_NAME
+        )
+    }
+
+    /// Delivers outbox entries until cancellation is requested
+    pub(super) async fn run(self) {
+        let config = *self.state_change_emitter.outbox_relay_config();
+        let timer = PeriodicTimer::new(config.poll_interval);
+
+        loop {
+            let mut tick = timer.tick();
+            match self.run_single_iteration().await {
+                Ok(num_entries) => {
+                    if num_entries >= config.batch_size as usize {
+                        // There are likely more entries waiting. Continue right away.
+                        tick.set_interval(Duration::ZERO);
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        controller = IO::LOG_SPAN_CONTROLLER_NAME,
+                        error = %e,
+                        "State change outbox relay error"
+                    );
+                }
+            }
+
+            tokio::select! {
+                _ = tick.sleep() => {},
+                _ = self.cancel_token.cancelled() => {
+                    tracing::info!(controller = IO::LOG_SPAN_CONTROLLER_NAME, "OutboxRelay stop was requested");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Performs a single relay iteration, and returns the amount of outbox entries
+    /// that have been processed
+    pub(super) async fn run_single_iteration(&self) -> Result<usize, DatabaseError> {
+        let Ok(_lock) = self
+            .work_lock_manager_handle
+            .try_acquire_lock(Self::work_key())
+            .await
+        else {
+            return Ok(0);
+        };
+
+        let config = *self.state_change_emitter.outbox_relay_config();
+
+        let hook_names: Vec<&str> = self
+            .state_change_emitter
+            .durable_hooks()
+            .iter()
+            .map(|hook| hook.name())
+            .collect();
+
+        let mut conn = self.pool.acquire().await.map_err(DatabaseError::acquire)?;
+        let entries = ::db::state_change_outbox::find_deliverable(
+            &mut conn,
+            IO::LOG_SPAN_CONTROLLER_NAME,
+            &hook_names,
+            config.batch_size,
+        )
+        .await?;
+        drop(conn);
+
+        let num_entries = entries.len();
+        // Each returned entry belongs to a different object and hook, and can
+        // therefore be delivered independently
+        let results: Vec<Result<(), DatabaseError>> = futures::stream::iter(entries)
+            .map(|entry| self.process_entry(entry))
+            .buffer_unordered(config.max_concurrency.max(1))
+            .collect()
+            .await;
+        for result in results {
+            result?;
+        }
+
+        if let Some(metric_emitter) = &self.metric_emitter {
+            let mut conn = self.pool.acquire().await.map_err(DatabaseError::acquire)?;
+            let pending =
+                ::db::state_change_outbox::count_pending(&mut conn, IO::LOG_SPAN_CONTROLLER_NAME)
+                    .await?;
+            metric_emitter
+                .pending_entries
+                .record(pending.max(0) as u64, &[]);
+        }
+
+        Ok(num_entries)
+    }
+
+    async fn process_entry(&self, entry: StateChangeOutboxEntry) -> Result<(), DatabaseError> {
+        let config = *self.state_change_emitter.outbox_relay_config();
+        let attempt = entry.attempts.max(0) as u32 + 1;
+
+        let (outcome, error) = match self.deliver(&entry, attempt).await {
+            None => (DeliveryOutcome::UnknownHook, None),
+            Some(Ok(())) => (DeliveryOutcome::Delivered, None),
+            Some(Err(e)) => {
+                let outcome = match e {
+                    DeliveryError::Transient(_) if attempt < config.max_attempts => {
+                        DeliveryOutcome::Retry
+                    }
+                    _ => DeliveryOutcome::DeadLetter,
+                };
+                (outcome, Some(e.to_string()))
+            }
+        };
+        let error = error.unwrap_or_default();
+
+        let mut conn = self.pool.acquire().await.map_err(DatabaseError::acquire)?;
+        match outcome {
+            DeliveryOutcome::Delivered => {
+                ::db::state_change_outbox::delete(&mut conn, entry.id).await?;
+            }
+            DeliveryOutcome::Retry => {
+                let backoff = chrono::Duration::from_std(config.retry_backoff(attempt))
+                    .unwrap_or_else(|_| chrono::Duration::hours(1));
+                ::db::state_change_outbox::record_failure(
+                    &mut conn,
+                    entry.id,
+                    &error,
+                    chrono::Utc::now() + backoff,
+                )
+                .await?;
+            }
+            DeliveryOutcome::DeadLetter => {
+                tracing::error!(
+                    controller = IO::LOG_SPAN_CONTROLLER_NAME,
+                    hook = entry.hook,
+                    object_id = entry.object_id,
+                    state_version = %entry.state_version,
+                    attempt,
+                    error,
+                    "State change could not be delivered and is moved to the dead-letter table"
+                );
+                ::db::state_change_outbox::move_to_dead_letters(&mut conn, entry.id, &error)
+                    .await?;
+            }
+            DeliveryOutcome::UnknownHook => {}
+        }
+
+        if let Some(metric_emitter) = &self.metric_emitter {
+            metric_emitter.emit_delivery(&entry, outcome);
+        }
+
+        Ok(())
+    }
+
+    /// Delivers an entry to its hook. Returns `None` if the hook is not registered.
+    async fn deliver(
+        &self,
+        entry: &StateChangeOutboxEntry,
+        attempt: u32,
+    ) -> Option<Result<(), DeliveryError>> {
+        let hook = self
+            .state_change_emitter
+            .durable_hooks()
+            .iter()
+            .find(|hook| hook.name() == entry.hook)?;
+        let codec = self.state_change_emitter.outbox_codec()?;
+
+        let change = IO::ObjectId::from_str(&entry.object_id)
+            .map_err(|_| DeliveryError::Permanent(format!("Invalid object ID {}", entry.object_id)))
+            .and_then(|object_id| {
+                let new_state = (codec.deserialize)(&entry.state).map_err(|e| {
+                    DeliveryError::Permanent(format!("Failed to deserialize state: {e}"))
+                })?;
+                Ok(OutboxStateChange {
+                    object_id,
+                    new_state,
+                    new_version: entry.state_version,
+                    timestamp: entry.created,
+                    attempt,
+                })
+            });
+
+        let result = match change {
+            Ok(change) => hook.deliver(&change).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = &result {
+            tracing::warn!(
+                controller = IO::LOG_SPAN_CONTROLLER_NAME,
+                hook = entry.hook,
+                object_id = entry.object_id,
+                attempt,
+                error = %e,
+                "Failed to deliver state change"
+            );
+        }
+        Some(result)
+    }
+}
+
+pub(super) struct OutboxRelayMetricsEmitter {
+    deliveries_counter: Counter<u64>,
+    delivery_delay: Histogram<f64>,
+    pending_entries: opentelemetry::metrics::Gauge<u64>,
+}
+
+impl OutboxRelayMetricsEmitter {
+    pub(super) fn new(object_type: &str, meter: &Meter) -> Self {
+        let deliveries_counter = meter
+            .u64_counter(format!("{object_type}_outbox_deliveries"))
+            .with_description(format!(
+                "The amount of attempts to deliver state changes of objects of type {object_type} to durable hooks"
+            ))
+            .build();
+
+        let delivery_delay = meter
+            .f64_histogram(format!("{object_type}_outbox_delivery_delay"))
+            .with_description(format!(
+                "The time between a state change of an object of type {object_type} and its delivery to a durable hook"
+            ))
+            .with_unit("ms")
+            .build();
+
+        let pending_entries = meter
+            .u64_gauge(format!("{object_type}_outbox_pending_entries"))
+            .with_description(format!(
+                "The amount of state changes of objects of type {object_type} that are waiting for delivery to durable hooks"
+            ))
+            .build();
+
+        Self {
+            deliveries_counter,
+            delivery_delay,
+            pending_entries,
+        }
+    }
+
+    fn emit_delivery(&self, entry: &StateChangeOutboxEntry, outcome: DeliveryOutcome) {
+        let attrs = &[
+            KeyValue::new("hook", entry.hook.clone()),
+            KeyValue::new("status", outcome.as_str()),
+        ];
+        self.deliveries_counter.add(1, attrs);
+
+        if outcome == DeliveryOutcome::Delivered {
+            let delay = chrono::Utc::now()
+                .signed_duration_since(entry.created)
+                .to_std()
+                .unwrap_or_default();
+            self.delivery_delay.record(
+                delay.as_secs_f64() * 1000.0,
+                &[KeyValue::new("hook", entry.hook.clone())],
+            );
+        }
+    }
+}
@@ -205,11 +205,11 @@ impl<IO: StateControllerIO> PeriodicEnqueuer<IO> {
         // have been added to the system. And no object should ever be removed
         // outside of the state controller
         let mut txn = self.pool.begin().await?;
-        let object_ids = self.io.list_objects(&mut txn).await?;
+        let object_ids = self.io.list_objects_with_priority(&mut txn).await?;
 
         let queued_objects: Vec<_> = object_ids
             .iter()
-            .map(|object_id| object_id.to_string())
+            .map(|(object_id, priority)| (object_id.to_string(), *priority))
             .collect();
         txn.commit().await?;
 
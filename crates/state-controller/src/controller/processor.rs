@@ -27,13 +27,14 @@ use sqlx_query_tracing::SqlxQueryDataAggregation;
 use tokio_util::sync::CancellationToken;
 use tracing::Instrument;
 
-use super::db;
+use super::{AcquiredObject, db};
 use crate::config::IterationConfig;
 use crate::db_write_batch::DbWriteBatch;
 use crate::io::StateControllerIO;
 use crate::metrics::{
     IterationMetrics, MetricHolder, ObjectHandlerMetrics, StateProcessorMetricEmitter,
 };
+use crate::queue_priority::{LaneScheduler, QueuePriority};
 use crate::state_change_emitter::{StateChangeEmitter, StateChangeEvent};
 use crate::state_handler::{
     FromStateHandlerResult, StateHandler, StateHandlerContext, StateHandlerContextObjects,
@@ -61,14 +62,18 @@ pub(super) struct StateProcessor<IO: StateControllerIO> {
     pub(super) object_metrics: HashMap<IO::ObjectId, CollectedMetrics<IO>>,
     pub(super) cancel_token: CancellationToken,
     pub(super) iteration_config: IterationConfig,
-    /// IDs of objects where the task handler is currently executed
-    pub(super) in_flight: HashSet<IO::ObjectId>,
+    /// IDs of objects where the task handler is currently executed,
+    /// together with the priority class they had been queued in
+    pub(super) in_flight: HashMap<IO::ObjectId, QueuePriority>,
+    /// Distributes dispatch slots between priority classes
+    pub(super) lane_scheduler: LaneScheduler,
     /// Objects where the state handling task was finished but where the entry
     /// in the database has not yet been deleted.
     pub(super) completed_objects: HashSet<IO::ObjectId>,
     /// Objects for which another object handling task should be queued since
-    /// the state handler returned `Transition`
-    pub(super) requeue_objects: HashSet<IO::ObjectId>,
+    /// the state handler returned `Transition`, together with the priority
+    /// for the next state
+    pub(super) requeue_objects: HashMap<IO::ObjectId, QueuePriority>,
     pub(super) task_sender: tokio::sync::mpsc::UnboundedSender<ObjectHandlingTaskResult<IO>>,
     pub(super) task_receiver: tokio::sync::mpsc::UnboundedReceiver<ObjectHandlingTaskResult<IO>>,
     /// The last time a log message had been emitted
@@ -203,6 +208,24 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
             .saturating_sub(self.in_flight.len())
     }
 
+    /// Calculates how many additional object handling tasks can be spawned
+    /// for objects of a certain priority class
+    fn remaining_lane_capacity(&self, priority: QueuePriority) -> usize {
+        let remaining = self.remaining_capacity();
+        match self
+            .iteration_config
+            .priority_lanes
+            .lane(priority)
+            .max_concurrency
+        {
+            Some(max_concurrency) => {
+                let in_flight = self.in_flight.values().filter(|&&p| p == priority).count();
+                max_concurrency.saturating_sub(in_flight).min(remaining)
+            }
+            None => remaining,
+        }
+    }
+
     /// Performs a single state processor iteration.
     /// The iteration will dispatch as many object handling tasks as possible,
     /// and then wait for the specified time for as many completions as possible.
@@ -387,62 +410,141 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
         // Determine how many new objects can still be processed and dequeue that amount
         let capacity = self.remaining_capacity();
         let objects = if capacity > 0 {
-            // Acquire new object handling tasks
-            // If processing of an object was already start by another state controller
-            // but not committed, it can be acquired after a certain amount of time.
-            // The time is higher than the task handling timeout on each state controller.
-            // This guarantees that the task is no longer processed by the original owner.
-            let capacity = capacity.min(u32::MAX as usize) as u32;
-            let mut txn = self.pool.begin().await?;
-            let queued = db::acquire_queued_objects(
-                &mut txn,
-                IO::DB_QUEUED_OBJECTS_TABLE_NAME,
-                capacity,
-                &self.processor_id,
-                self.iteration_config.max_object_handling_time * 3,
-            )
-            .await?;
-            txn.commit().await?;
-            queued
+            self.acquire_queued_objects(capacity).await?
         } else {
             Vec::new()
         };
 
-        let objects: Vec<IO::ObjectId> = objects
+        let now = chrono::Utc::now();
+        if let Some(emitter) = &self.metric_emitter {
+            for acquired in objects.iter() {
+                let latency = now
+                    .signed_duration_since(acquired.queued_at)
+                    .to_std()
+                    .unwrap_or_default();
+                emitter.queue_latency.record(
+                    1000.0 * latency.as_secs_f64(),
+                    &[KeyValue::new("priority", acquired.object.priority.as_str())],
+                );
+            }
+        }
+
+        let objects: Vec<(IO::ObjectId, QueuePriority)> = objects
             .into_iter()
-            .filter_map(|object| match IO::ObjectId::from_str(&object.object_id) {
-                Ok(id) => Some(id),
-                Err(_) => {
-                    tracing::error!(
-                        controller = IO::LOG_SPAN_CONTROLLER_NAME,
-                        "Can not convert queued object ID \"{}\" to IO::ObjectID format",
-                        object.object_id
-                    );
-                    None
-                }
-            })
+            .filter_map(
+                |acquired| match IO::ObjectId::from_str(&acquired.object.object_id) {
+                    Ok(id) => Some((id, acquired.object.priority)),
+                    Err(_) => {
+                        tracing::error!(
+                            controller = IO::LOG_SPAN_CONTROLLER_NAME,
+                            "Can not convert queued object ID \"{}\" to IO::ObjectID format",
+                            acquired.object.object_id
+                        );
+                        None
+                    }
+                },
+            )
             .collect();
 
         let num_dispatched_tasks = objects.len();
         self.stats_since_last_log.num_dispatched_tasks += num_dispatched_tasks;
 
         // Send off the new objects for processing
-        for object_id in objects {
+        let mut dispatched_per_priority: HashMap<QueuePriority, u64> = HashMap::new();
+        for (object_id, priority) in objects {
             self.dispatch_object_handling_task(object_id.clone());
-            self.in_flight.insert(object_id);
+            self.in_flight.insert(object_id, priority);
+            *dispatched_per_priority.entry(priority).or_default() += 1;
         }
 
-        if let Some(emitter) = &self.metric_emitter
-            && num_dispatched_tasks > 0
-        {
-            emitter
-                .dispatched_tasks_counter
-                .add(num_dispatched_tasks as u64, &[]);
+        if let Some(emitter) = &self.metric_emitter {
+            for (priority, num_dispatched) in dispatched_per_priority {
+                emitter.dispatched_tasks_counter.add(
+                    num_dispatched,
+                    &[KeyValue::new("priority", priority.as_str())],
+                );
+            }
         }
 
         Ok(num_dispatched_tasks)
     }
 
+    /// Acquires up to `capacity` queued objects.
+    ///
+    /// The available slots are first distributed between the priority classes
+    /// according to their weights. Slots which can not be used by a class - because
+    /// not enough objects of the class are queued - are afterwards offered to the
+    /// other classes, starting with the class with the highest priority.
+    async fn acquire_queued_objects(
+        &mut self,
+        capacity: usize,
+    ) -> Result<Vec<AcquiredObject>, IterationError> {
+        let lanes = self.iteration_config.priority_lanes;
+        let headroom: HashMap<QueuePriority, usize> = QueuePriority::ALL
+            .into_iter()
+            .map(|p| (p, self.remaining_lane_capacity(p)))
+            .collect();
+        let slots = self
+            .lane_scheduler
+            .assign_slots(capacity, &lanes, |p| headroom[&p]);
+
+        // If processing of an object was already start by another state controller
+        // but not committed, it can be acquired after a certain amount of time.
+        // The time is higher than the task handling timeout on each state controller.
+        // This guarantees that the task is no longer processed by the original owner.
+        let max_outdated = self.iteration_config.max_object_handling_time * 3;
+        let mut acquired = Vec::new();
+        let mut acquired_per_priority: HashMap<QueuePriority, usize> = HashMap::new();
+        let mut exhausted: HashSet<QueuePriority> = HashSet::new();
+
+        let mut txn = self.pool.begin().await?;
+        for (priority, num_slots) in slots {
+            let objects = db::acquire_queued_objects(
+                &mut txn,
+                IO::DB_QUEUED_OBJECTS_TABLE_NAME,
+                num_slots.min(u32::MAX as usize) as u32,
+                Some(priority),
+                &self.processor_id,
+                max_outdated,
+            )
+            .await?;
+            if objects.len() < num_slots {
+                exhausted.insert(priority);
+            }
+            acquired_per_priority.insert(priority, objects.len());
+            acquired.extend(objects);
+        }
+
+        for priority in QueuePriority::ALL {
+            let remaining = capacity.saturating_sub(acquired.len());
+            if remaining == 0 {
+                break;
+            }
+            if exhausted.contains(&priority) {
+                continue;
+            }
+            let lane_remaining = headroom[&priority]
+                .saturating_sub(acquired_per_priority.get(&priority).copied().unwrap_or(0));
+            let count = remaining.min(lane_remaining);
+            if count == 0 {
+                continue;
+            }
+            let objects = db::acquire_queued_objects(
+                &mut txn,
+                IO::DB_QUEUED_OBJECTS_TABLE_NAME,
+                count.min(u32::MAX as usize) as u32,
+                Some(priority),
+                &self.processor_id,
+                max_outdated,
+            )
+            .await?;
+            acquired.extend(objects);
+        }
+        txn.commit().await?;
+
+        Ok(acquired)
+    }
+
     // Executes the state handling function for all objects for a single queued object
     fn dispatch_object_handling_task(&mut self, object_id: IO::ObjectId) {
         let cloned_object_id = object_id.clone();
@@ -521,10 +623,10 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
             return Ok(());
         }
 
-        let queue_objects: Vec<String> = self
+        let queue_objects: Vec<(String, QueuePriority)> = self
             .requeue_objects
             .iter()
-            .map(|id| id.to_string())
+            .map(|(id, priority)| (id.to_string(), *priority))
             .collect();
         let mut txn = self.pool.begin().await?;
         let num_requeued =
@@ -551,8 +653,11 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
         self.completed_objects.insert(task_result.object_id.clone());
         // If the state handler returned `Transition`, then run the handler again
         // as soon as possible.
-        if allow_requeue && task_result.metrics.common.next_state.is_some() {
-            self.requeue_objects.insert(task_result.object_id.clone());
+        if allow_requeue && let Some(next_state) = &task_result.metrics.common.next_state {
+            self.requeue_objects.insert(
+                task_result.object_id.clone(),
+                self.io.queue_priority(next_state),
+            );
         }
 
         self.stats_since_last_log.num_completed_tasks += 1;
@@ -604,6 +709,7 @@ async fn process_object<IO: StateControllerIO>(
     let mut metrics = ObjectHandlerMetrics::<IO>::default();
 
     let start = Instant::now();
+    let mut persisted_version = None;
 
     // Note that this inner async block is required to be able to use
     // the ? operator in the inner block, and then return a `Result`
@@ -669,6 +775,7 @@ async fn process_object<IO: StateControllerIO>(
         };
 
         let mut next_state = None;
+        let mut next_version = None;
         if let Ok(StateHandlerOutcome::Transition {
             next_state: next, ..
         }) = &handler_outcome
@@ -679,6 +786,7 @@ async fn process_object<IO: StateControllerIO>(
                 tracing::warn!(state=?next, %object_id, "Transition to current state");
             }
             let new_version = controller_state.version.increment();
+            next_version = Some(new_version);
             if io
                 .persist_controller_state(
                     &mut txn,
@@ -691,6 +799,15 @@ async fn process_object<IO: StateControllerIO>(
             {
                 io.persist_state_history(&mut txn, &object_id, new_version, next)
                     .await?;
+                state_change_emitter
+                    .enqueue_durable(
+                        &mut txn,
+                        IO::LOG_SPAN_CONTROLLER_NAME,
+                        &object_id,
+                        new_version,
+                        next,
+                    )
+                    .await?;
             }
         }
 
@@ -734,6 +851,7 @@ async fn process_object<IO: StateControllerIO>(
         // Only emit the next state as metric if the transaction was actually
         // committed and we are sure we reached the next state
         metrics.common.next_state = next_state;
+        persisted_version = next_version;
 
         handler_outcome
     })
@@ -741,12 +859,13 @@ async fn process_object<IO: StateControllerIO>(
     metrics.common.handler_latency = start.elapsed();
 
     // Emit the state changed event to registered hooks
-    if let Some(next_state) = &metrics.common.next_state {
+    if let (Some(next_state), Some(new_version)) = (&metrics.common.next_state, persisted_version) {
         state_change_emitter.emit(StateChangeEvent {
             object_id: &object_id,
             #[cfg(any(test, feature = "test-support"))]
             previous_state: metrics.common.initial_state.as_ref(),
             new_state: next_state,
+            new_version,
             timestamp: chrono::Utc::now(),
         });
     }
@@ -783,6 +902,7 @@ pub(super) struct ProcessorMetricsEmitter {
     dispatched_tasks_counter: Counter<u64>,
     completed_tasks_counter: Counter<u64>,
     requeued_tasks_counter: Counter<u64>,
+    queue_latency: Histogram<f64>,
     db: sqlx_query_tracing::DatabaseMetricEmitters,
 }
 
@@ -819,12 +939,21 @@ impl ProcessorMetricsEmitter {
             ))
             .build();
 
+        let queue_latency = meter
+            .f64_histogram(format!("{object_type}_queue_latency"))
+            .with_description(format!(
+                "The time objects of type {object_type} spent in the queue until they got dispatched for processing"
+            ))
+            .with_unit("ms")
+            .build();
+
         Self {
             iteration_latency,
             db,
             dispatched_tasks_counter,
             completed_tasks_counter,
             requeued_tasks_counter,
+            queue_latency,
         }
     }
 
@@ -54,6 +54,12 @@ pub trait WriteOp: Send {
         self: Box<Self>,
         txn: &'a mut PgTransaction<'t>,
     ) -> Result<(), StateHandlerError>;
+
+    /// A human readable description of the write operation, used to report
+    /// the writes a state handler would perform without applying them.
+    fn description(&self) -> String {
+        std::any::type_name::<Self>().to_string()
+    }
 }
 
 impl std::fmt::Debug for DbWriteBatch {
@@ -79,6 +85,10 @@ impl WriteOp for WriteOpFn {
     ) -> Result<(), StateHandlerError> {
         (*self)(txn).await
     }
+
+    fn description(&self) -> String {
+        "Custom write operation".to_string()
+    }
 }
 
 impl DbWriteBatch {
@@ -90,6 +100,12 @@ impl DbWriteBatch {
         self.writes.push(Box::new(op));
     }
 
+    /// Returns the descriptions of all enqueued write operations, in the order
+    /// in which they would be applied.
+    pub fn descriptions(&self) -> Vec<String> {
+        self.writes.iter().map(|w| w.description()).collect()
+    }
+
     pub async fn apply_all(self, txn: &mut PgTransaction<'_>) -> Result<(), StateHandlerError> {
         for w in self.writes {
             w.apply(txn).await?;
@@ -0,0 +1,163 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Runs a state handler for a single object without applying its outcome
+//!
+//! A dry run loads the current state of the object the same way the state controller
+//! does and invokes the state handler with it. Instead of persisting the outcome,
+//! it reports what the handler intended to do:
+//! - the transition or wait reason the handler returned
+//! - the database writes the handler enqueued in the [`DbWriteBatch`]
+//! - whether the handler returned a transaction, which is rolled back
+//!
+//! Side effects on external systems are not intercepted here. Callers are
+//! expected to pass services in which those are replaced by recording stand-ins.
+
+use std::panic::Location;
+
+use config_version::Versioned;
+use sqlx::PgPool;
+
+use crate::db_write_batch::DbWriteBatch;
+use crate::io::StateControllerIO;
+use crate::state_handler::{
+    StateHandler, StateHandlerContext, StateHandlerContextObjects, StateHandlerError,
+    StateHandlerOutcome,
+};
+
+/// What the state handler decided to do with the object
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DryRunOutcome<S> {
+    /// The handler would transition the object into `next_state`
+    Transition { next_state: S },
+    /// The handler waits for a condition to be met
+    Wait { reason: String },
+    /// The handler has nothing to do
+    DoNothing,
+    /// The handler would delete the object
+    Deleted,
+    /// The handler failed
+    Error { error: String },
+}
+
+/// The result of running a state handler for a single object in dry-run mode
+#[derive(Debug)]
+pub struct DryRunReport<S> {
+    /// The state the object is currently in
+    pub current_state: Versioned<S>,
+    /// Whether the object is in the current state for longer than the SLA permits.
+    /// The state controller turns `Wait` and `DoNothing` outcomes into errors in this case.
+    pub time_in_state_above_sla: bool,
+    pub outcome: DryRunOutcome<S>,
+    /// The location in the state handler that returned the outcome
+    pub source_ref: Option<&'static Location<'static>>,
+    /// Descriptions of the database writes the handler enqueued
+    pub db_writes: Vec<String>,
+    /// Whether the handler returned a transaction together with the outcome.
+    /// Any writes in that transaction have been rolled back.
+    pub transaction_discarded: bool,
+}
+
+/// Runs `handler` for the object with the given ID and reports what it would do,
+/// without applying any of it.
+///
+/// Returns `Ok(None)` if the object does not exist.
+pub async fn dry_run<IO: StateControllerIO>(
+    io: &IO,
+    handler: &dyn StateHandler<
+        State = IO::State,
+        ControllerState = IO::ControllerState,
+        ContextObjects = IO::ContextObjects,
+        ObjectId = IO::ObjectId,
+    >,
+    object_id: &IO::ObjectId,
+    pool: &PgPool,
+    services: &mut <IO::ContextObjects as StateHandlerContextObjects>::Services,
+    max_object_handling_time: std::time::Duration,
+) -> Result<Option<DryRunReport<IO::ControllerState>>, StateHandlerError> {
+    let mut txn = pool.begin().await?;
+    let Some(mut snapshot) = io.load_object_state(&mut txn, object_id).await? else {
+        return Ok(None);
+    };
+    let controller_state = io
+        .load_controller_state(&mut txn, object_id, &snapshot)
+        .await?;
+    txn.rollback().await?;
+
+    let state_sla = io.state_sla(&controller_state, &snapshot);
+
+    let mut metrics = <IO::ContextObjects as StateHandlerContextObjects>::ObjectMetrics::default();
+    let mut pending_db_writes = DbWriteBatch::new();
+    let mut ctx = StateHandlerContext {
+        services,
+        metrics: &mut metrics,
+        pending_db_writes: &mut pending_db_writes,
+    };
+
+    let handler_output = tokio::time::timeout(
+        max_object_handling_time,
+        handler.handle_object_state(object_id, &mut snapshot, &controller_state.value, &mut ctx),
+    )
+    .await
+    .unwrap_or_else(|_| {
+        Err(StateHandlerError::Timeout {
+            object_id: object_id.to_string(),
+            state: format!("{:?}", controller_state.value),
+        })
+    });
+
+    let mut transaction_discarded = false;
+    let (outcome, source_ref) = match handler_output {
+        Ok(mut outcome) => {
+            if let Some(txn) = outcome.take_transaction() {
+                transaction_discarded = true;
+                txn.rollback().await?;
+            }
+            match outcome {
+                StateHandlerOutcome::Transition {
+                    next_state,
+                    source_ref,
+                    ..
+                } => (DryRunOutcome::Transition { next_state }, Some(source_ref)),
+                StateHandlerOutcome::Wait {
+                    reason, source_ref, ..
+                } => (DryRunOutcome::Wait { reason }, Some(source_ref)),
+                StateHandlerOutcome::DoNothing { source_ref, .. } => {
+                    (DryRunOutcome::DoNothing, Some(source_ref))
+                }
+                StateHandlerOutcome::Deleted { _source_ref, .. } => {
+                    (DryRunOutcome::Deleted, Some(_source_ref))
+                }
+            }
+        }
+        Err(e) => (
+            DryRunOutcome::Error {
+                error: e.to_string(),
+            },
+            None,
+        ),
+    };
+
+    Ok(Some(DryRunReport {
+        current_state: controller_state,
+        time_in_state_above_sla: state_sla.time_in_state_above_sla,
+        outcome,
+        source_ref,
+        db_writes: pending_db_writes.descriptions(),
+        transaction_discarded,
+    }))
+}
@@ -21,6 +21,7 @@ use model::controller_outcome::PersistentStateHandlerOutcome;
 use sqlx::PgConnection;
 
 use crate::metrics::MetricsEmitter;
+use crate::queue_priority::QueuePriority;
 use crate::state_handler::StateHandlerContextObjects;
 
 /// This trait defines on what objects a state controller instance will act,
@@ -72,6 +73,30 @@ pub trait StateControllerIO: Send + Sync + std::fmt::Debug + 'static + Default {
         txn: &mut PgConnection,
     ) -> Result<Vec<Self::ObjectId>, DatabaseError>;
 
+    /// Resolves the list of objects that the state controller should act upon,
+    /// together with the priority they should be queued with.
+    ///
+    /// By default all objects are queued with [`QueuePriority::Background`].
+    async fn list_objects_with_priority(
+        &self,
+        txn: &mut PgConnection,
+    ) -> Result<Vec<(Self::ObjectId, QueuePriority)>, DatabaseError> {
+        Ok(self
+            .list_objects(txn)
+            .await?
+            .into_iter()
+            .map(|object_id| (object_id, QueuePriority::Background))
+            .collect())
+    }
+
+    /// Returns the priority with which an object that transitioned into `state`
+    /// is queued again for state handling.
+    ///
+    /// By default all objects are queued with [`QueuePriority::Background`].
+    fn queue_priority(&self, _state: &Self::ControllerState) -> QueuePriority {
+        QueuePriority::Background
+    }
+
     /// Loads a state of an object
     async fn load_object_state(
         &self,
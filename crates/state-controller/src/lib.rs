@@ -50,10 +50,14 @@ macro_rules! test_assert {
 pub mod config;
 pub mod controller;
 pub mod db_write_batch;
+pub mod dry_run;
 pub mod io;
 pub mod metrics;
+pub mod queue_priority;
 pub mod state_change_emitter;
+pub mod state_change_outbox;
 pub mod state_handler;
+pub mod state_handler_middleware;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,230 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Priority classes for objects which are queued for state handling
+//!
+//! Every queued object belongs to a priority class. The state processor
+//! distributes its free dispatch slots between the classes according to the
+//! weights configured in [`PriorityLanesConfig`], so that a burst of objects in
+//! one class can not starve objects of the other classes.
+
+use crate::config::{PriorityLaneConfig, PriorityLanesConfig};
+
+/// The priority with which an object is queued for state handling
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum QueuePriority {
+    /// Objects whose state handling directly affects tenants, e.g. hosts that
+    /// are assigned to an instance or that are cleaned up after an instance got released
+    TenantImpacting,
+    /// Objects for which state handling was explicitly requested,
+    /// e.g. by an operator or in reaction to an event reported for the object
+    OperatorRequested,
+    /// Objects which are enqueued periodically and have no urgent work pending
+    #[default]
+    Background,
+}
+
+impl QueuePriority {
+    /// All priority classes, ordered from the highest to the lowest priority
+    pub const ALL: [QueuePriority; 3] = [
+        QueuePriority::TenantImpacting,
+        QueuePriority::OperatorRequested,
+        QueuePriority::Background,
+    ];
+
+    /// The name of the priority class as used in metrics and logs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QueuePriority::TenantImpacting => "tenant_impacting",
+            QueuePriority::OperatorRequested => "operator_requested",
+            QueuePriority::Background => "background",
+        }
+    }
+
+    /// The value that is stored in the `priority` column of queued objects tables.
+    /// Lower values indicate a higher priority.
+    pub fn to_db(self) -> i16 {
+        match self {
+            QueuePriority::TenantImpacting => 0,
+            QueuePriority::OperatorRequested => 1,
+            QueuePriority::Background => 2,
+        }
+    }
+
+    /// Converts the value of the `priority` column back into the priority class.
+    /// Unknown values are treated as `Background`.
+    pub fn from_db(value: i16) -> Self {
+        match value {
+            0 => QueuePriority::TenantImpacting,
+            1 => QueuePriority::OperatorRequested,
+            _ => QueuePriority::Background,
+        }
+    }
+
+    fn index(self) -> usize {
+        self.to_db() as usize
+    }
+}
+
+impl std::fmt::Display for QueuePriority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Distributes dispatch slots between priority classes using smooth weighted
+/// round-robin.
+///
+/// The scheduler keeps its credits across calls. This makes sure that classes
+/// with a low weight also get their share of slots if only a single slot is
+/// available per dispatch.
+#[derive(Debug, Default)]
+pub(crate) struct LaneScheduler {
+    credits: [i64; 3],
+}
+
+impl LaneScheduler {
+    /// Assigns up to `capacity` slots to the priority classes.
+    ///
+    /// `headroom` returns how many additional objects of a class may be dispatched
+    /// before the per-class concurrency limit is reached. Classes with a weight
+    /// of 0 or without headroom don't get any slots.
+    pub(crate) fn assign_slots(
+        &mut self,
+        capacity: usize,
+        lanes: &PriorityLanesConfig,
+        headroom: impl Fn(QueuePriority) -> usize,
+    ) -> Vec<(QueuePriority, usize)> {
+        let mut assigned = [0usize; 3];
+        for _ in 0..capacity {
+            let eligible: Vec<QueuePriority> = QueuePriority::ALL
+                .into_iter()
+                .filter(|&p| lanes.lane(p).weight > 0 && assigned[p.index()] < headroom(p))
+                .collect();
+            if eligible.is_empty() {
+                break;
+            }
+
+            let total_weight: i64 = eligible.iter().map(|&p| lanes.lane(p).weight as i64).sum();
+            let mut selected = eligible[0];
+            for &p in eligible.iter() {
+                self.credits[p.index()] += lanes.lane(p).weight as i64;
+                // On equal credits the class with the higher priority wins,
+                // since classes are evaluated in priority order
+                if self.credits[p.index()] > self.credits[selected.index()] {
+                    selected = p;
+                }
+            }
+            self.credits[selected.index()] -= total_weight;
+            assigned[selected.index()] += 1;
+        }
+
+        QueuePriority::ALL
+            .into_iter()
+            .map(|p| (p, assigned[p.index()]))
+            .filter(|(_, slots)| *slots > 0)
+            .collect()
+    }
+}
+
+impl PriorityLanesConfig {
+    /// Returns the configuration for a priority class
+    pub fn lane(&self, priority: QueuePriority) -> &PriorityLaneConfig {
+        match priority {
+            QueuePriority::TenantImpacting => &self.tenant_impacting,
+            QueuePriority::OperatorRequested => &self.operator_requested,
+            QueuePriority::Background => &self.background,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lanes(weights: [u32; 3]) -> PriorityLanesConfig {
+        PriorityLanesConfig {
+            tenant_impacting: PriorityLaneConfig {
+                weight: weights[0],
+                max_concurrency: None,
+            },
+            operator_requested: PriorityLaneConfig {
+                weight: weights[1],
+                max_concurrency: None,
+            },
+            background: PriorityLaneConfig {
+                weight: weights[2],
+                max_concurrency: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_queue_priority_db_roundtrip() {
+        for priority in QueuePriority::ALL {
+            assert_eq!(QueuePriority::from_db(priority.to_db()), priority);
+        }
+        assert_eq!(QueuePriority::from_db(42), QueuePriority::Background);
+    }
+
+    #[test]
+    fn test_assign_slots_by_weight() {
+        let mut scheduler = LaneScheduler::default();
+        let slots = scheduler.assign_slots(10, &lanes([6, 3, 1]), |_| usize::MAX);
+        assert_eq!(
+            slots,
+            vec![
+                (QueuePriority::TenantImpacting, 6),
+                (QueuePriority::OperatorRequested, 3),
+                (QueuePriority::Background, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_assign_single_slots_is_fair_across_calls() {
+        let mut scheduler = LaneScheduler::default();
+        let mut counts = std::collections::HashMap::new();
+        for _ in 0..10 {
+            for (priority, slots) in scheduler.assign_slots(1, &lanes([6, 3, 1]), |_| usize::MAX) {
+                *counts.entry(priority).or_insert(0) += slots;
+            }
+        }
+        assert_eq!(counts[&QueuePriority::TenantImpacting], 6);
+        assert_eq!(counts[&QueuePriority::OperatorRequested], 3);
+        assert_eq!(counts[&QueuePriority::Background], 1);
+    }
+
+    #[test]
+    fn test_assign_slots_respects_headroom_and_zero_weight() {
+        let mut scheduler = LaneScheduler::default();
+        let slots = scheduler.assign_slots(10, &lanes([6, 0, 1]), |p| match p {
+            QueuePriority::TenantImpacting => 2,
+            _ => usize::MAX,
+        });
+        assert_eq!(
+            slots,
+            vec![
+                (QueuePriority::TenantImpacting, 2),
+                (QueuePriority::Background, 8),
+            ]
+        );
+
+        let slots = scheduler.assign_slots(10, &lanes([1, 1, 1]), |_| 0);
+        assert!(slots.is_empty());
+    }
+}
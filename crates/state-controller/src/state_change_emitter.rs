@@ -17,7 +17,17 @@
 
 //! Generic state change emitter for broadcasting state transitions to registered hooks.
 
+use std::sync::Arc;
+
 use chrono::{DateTime, Utc};
+use config_version::ConfigVersion;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use sqlx::PgConnection;
+
+use crate::config::OutboxRelayConfig;
+use crate::state_change_outbox::{DurableStateChangeHook, OutboxCodec};
+use crate::state_handler::StateHandlerError;
 
 /// Event emitted when a state transition occurs.
 ///
@@ -33,6 +43,8 @@ pub struct StateChangeEvent<'a, Id, S> {
     pub previous_state: Option<&'a S>,
     /// The new state after the transition.
     pub new_state: &'a S,
+    /// The version that was persisted together with `new_state`.
+    pub new_version: ConfigVersion,
     /// Timestamp when the state change occurred.
     pub timestamp: DateTime<Utc>,
 }
@@ -53,8 +65,13 @@ pub trait StateChangeHook<Id: Clone, S: Clone>: Send + Sync + 'static {
 /// Handle for emitting state change events to registered hooks.
 ///
 /// Events are dispatched synchronously to all registered hooks.
+/// For durable hooks, the emitter writes the state change into the outbox,
+/// from which it is delivered by the outbox relay of the state controller.
 pub struct StateChangeEmitter<Id: Clone, S: Clone> {
     hooks: Vec<Box<dyn StateChangeHook<Id, S>>>,
+    durable_hooks: Vec<Arc<dyn DurableStateChangeHook<Id, S>>>,
+    outbox_codec: Option<OutboxCodec<S>>,
+    outbox_relay_config: OutboxRelayConfig,
 }
 
 impl<Id: Clone + Send + 'static, S: Clone + Send + 'static> Default for StateChangeEmitter<Id, S> {
@@ -73,18 +90,81 @@ impl<Id: Clone + Send + 'static, S: Clone + Send + 'static> StateChangeEmitter<I
             hook.on_state_changed(&event);
         }
     }
+
+    /// Returns whether durable hooks are registered, which requires running
+    /// the outbox relay.
+    pub fn has_durable_hooks(&self) -> bool {
+        !self.durable_hooks.is_empty()
+    }
+
+    pub(crate) fn durable_hooks(&self) -> &[Arc<dyn DurableStateChangeHook<Id, S>>] {
+        &self.durable_hooks
+    }
+
+    pub(crate) fn outbox_codec(&self) -> Option<OutboxCodec<S>> {
+        self.outbox_codec
+    }
+
+    pub(crate) fn outbox_relay_config(&self) -> &OutboxRelayConfig {
+        &self.outbox_relay_config
+    }
+
+    /// Writes the state change into the outbox for all durable hooks.
+    ///
+    /// This needs to be called with the same transaction that persists the
+    /// state transition, so that either both or neither get committed.
+    pub async fn enqueue_durable(
+        &self,
+        txn: &mut PgConnection,
+        controller: &str,
+        object_id: &Id,
+        new_version: ConfigVersion,
+        new_state: &S,
+    ) -> Result<(), StateHandlerError>
+    where
+        Id: std::fmt::Display,
+    {
+        let Some(codec) = self.outbox_codec.filter(|_| self.has_durable_hooks()) else {
+            return Ok(());
+        };
+
+        let state = (codec.serialize)(new_state).map_err(|e| {
+            StateHandlerError::GenericError(eyre::eyre!(
+                "Failed to serialize state for outbox: {e}"
+            ))
+        })?;
+        let hook_names: Vec<&str> = self.durable_hooks.iter().map(|hook| hook.name()).collect();
+        db::state_change_outbox::enqueue(
+            txn,
+            controller,
+            &hook_names,
+            object_id,
+            &state,
+            new_version,
+        )
+        .await?;
+        Ok(())
+    }
 }
 
 /// Builder for creating a [`StateChangeEmitter`] with registered hooks.
 pub struct StateChangeEmitterBuilder<Id: Clone + Send + 'static, S: Clone + Send + 'static> {
     hooks: Vec<Box<dyn StateChangeHook<Id, S>>>,
+    durable_hooks: Vec<Arc<dyn DurableStateChangeHook<Id, S>>>,
+    outbox_codec: Option<OutboxCodec<S>>,
+    outbox_relay_config: OutboxRelayConfig,
 }
 
 impl<Id: Clone + Send + 'static, S: Clone + Send + 'static> Default
     for StateChangeEmitterBuilder<Id, S>
 {
     fn default() -> Self {
-        Self { hooks: Vec::new() }
+        Self {
+            hooks: Vec::new(),
+            durable_hooks: Vec::new(),
+            outbox_codec: None,
+            outbox_relay_config: OutboxRelayConfig::default(),
+        }
     }
 }
 
@@ -95,9 +175,32 @@ impl<Id: Clone + Send + 'static, S: Clone + Send + 'static> StateChangeEmitterBu
         self
     }
 
+    /// Register a hook which is guaranteed to observe every state change.
+    ///
+    /// State changes for the hook are stored in the transactional outbox.
+    pub fn durable_hook(mut self, hook: Arc<dyn DurableStateChangeHook<Id, S>>) -> Self
+    where
+        S: Serialize + DeserializeOwned,
+    {
+        self.durable_hooks.push(hook);
+        self.outbox_codec = Some(OutboxCodec::json());
+        self
+    }
+
+    /// Configures how the outbox relay delivers state changes to durable hooks.
+    pub fn outbox_relay_config(mut self, config: OutboxRelayConfig) -> Self {
+        self.outbox_relay_config = config;
+        self
+    }
+
     /// Build the emitter with the registered hooks.
     pub fn build(self) -> StateChangeEmitter<Id, S> {
-        StateChangeEmitter { hooks: self.hooks }
+        StateChangeEmitter {
+            hooks: self.hooks,
+            durable_hooks: self.durable_hooks,
+            outbox_codec: self.outbox_codec,
+            outbox_relay_config: self.outbox_relay_config,
+        }
     }
 }
 
@@ -135,6 +238,7 @@ mod tests {
             object_id: &id,
             previous_state: None,
             new_state: &state,
+            new_version: ConfigVersion::initial(),
             timestamp: Utc::now(),
         });
     }
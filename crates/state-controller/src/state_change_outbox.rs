@@ -0,0 +1,98 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Durable state change hooks with at-least-once delivery.
+//!
+//! In contrast to [`crate::state_change_emitter::StateChangeHook`]s, state changes
+//! for durable hooks are written into a transactional outbox as part of the
+//! same transaction that persists the state transition. A relay task which runs
+//! next to the state controller delivers the outbox entries to the hooks, retries
+//! failed deliveries, and moves entries which can not be delivered into a
+//! dead-letter table.
+
+use chrono::{DateTime, Utc};
+use config_version::ConfigVersion;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// A state change that is delivered to a [`DurableStateChangeHook`]
+#[derive(Debug, Clone)]
+pub struct OutboxStateChange<Id, S> {
+    /// The ID of the object that changed state.
+    pub object_id: Id,
+    /// The new state after the transition.
+    pub new_state: S,
+    /// The version that was persisted together with `new_state`.
+    pub new_version: ConfigVersion,
+    /// Timestamp when the state change was written into the outbox.
+    pub timestamp: DateTime<Utc>,
+    /// The number of this delivery attempt, starting at 1.
+    /// Hooks are called again for the same state change if an earlier attempt
+    /// failed, or if the outcome of the earlier attempt could not be recorded.
+    pub attempt: u32,
+}
+
+/// Error returned by [`DurableStateChangeHook::deliver`]
+#[derive(Debug, thiserror::Error)]
+pub enum DeliveryError {
+    /// Delivery failed, but might succeed if it is retried later
+    #[error("{0}")]
+    Transient(String),
+    /// Delivery will never succeed. The state change is moved into the
+    /// dead-letter table without further attempts.
+    #[error("{0}")]
+    Permanent(String),
+}
+
+/// Trait for hooks which need to observe every state transition.
+///
+/// Deliveries happen at least once. Hooks might observe the same state change
+/// multiple times, and should use `new_version` for de-duplication if required.
+/// All state changes of an object are delivered in the order they happened.
+#[async_trait::async_trait]
+pub trait DurableStateChangeHook<Id, S>: Send + Sync + 'static {
+    /// The name of the hook. It is stored with each outbox entry, and therefore
+    /// must not change between releases.
+    fn name(&self) -> &'static str;
+
+    /// Delivers a single state change.
+    async fn deliver(&self, change: &OutboxStateChange<Id, S>) -> Result<(), DeliveryError>;
+}
+
+/// Converts controller states to and from the JSON representation that is
+/// stored in the outbox
+pub(crate) struct OutboxCodec<S> {
+    pub serialize: fn(&S) -> serde_json::Result<serde_json::Value>,
+    pub deserialize: fn(&str) -> serde_json::Result<S>,
+}
+
+impl<S> Clone for OutboxCodec<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for OutboxCodec<S> {}
+
+impl<S: Serialize + DeserializeOwned> OutboxCodec<S> {
+    pub fn json() -> Self {
+        Self {
+            serialize: |state| serde_json::to_value(state),
+            deserialize: |state| serde_json::from_str(state),
+        }
+    }
+}
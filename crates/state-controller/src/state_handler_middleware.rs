@@ -0,0 +1,264 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Middleware which wraps the invocation of a [`StateHandler`].
+//!
+//! Middlewares allow to implement policies which apply to all objects managed
+//! by a state controller - e.g. to veto or delay transitions while an object is
+//! quarantined - without having to inline them into the state handler itself.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Histogram, Meter};
+
+use crate::state_handler::{
+    StateHandler, StateHandlerContext, StateHandlerContextObjects, StateHandlerError,
+    StateHandlerOutcome,
+};
+
+/// The decision of a middleware on whether the state handler should be invoked
+pub enum MiddlewareDecision<S> {
+    /// Continue with the next middleware in the chain, and finally the state handler
+    Proceed,
+    /// Skip the remaining middlewares as well as the state handler, and use the
+    /// given outcome as the result of state handling.
+    /// This allows to veto a transition (e.g. via `DoNothing`) or to delay it (via `Wait`).
+    Intercept(StateHandlerOutcome<S>),
+}
+
+/// A middleware which gets invoked before and after the [`StateHandler`] of a
+/// state controller.
+///
+/// Middlewares are invoked in the order in which they are registered on the
+/// state controller builder. `before_handle` is called from the first to the
+/// last middleware, and `after_handle` is called in reverse order. If a middleware
+/// intercepts state handling, only the `after_handle` functions of the middlewares
+/// that ran before it will be called.
+#[async_trait::async_trait]
+pub trait StateHandlerMiddleware: std::fmt::Debug + Send + Sync + 'static {
+    type ObjectId: Clone + std::fmt::Display + std::fmt::Debug + Send + Sync;
+    type State: Send + Sync;
+    type ControllerState: Send + Sync;
+    type ContextObjects: StateHandlerContextObjects;
+
+    /// The name of the middleware. Used for logging and metrics.
+    fn name(&self) -> &'static str;
+
+    /// Called before the state handler is invoked
+    async fn before_handle(
+        &self,
+        _object_id: &Self::ObjectId,
+        _state: &mut Self::State,
+        _controller_state: &Self::ControllerState,
+        _ctx: &mut StateHandlerContext<Self::ContextObjects>,
+    ) -> Result<MiddlewareDecision<Self::ControllerState>, StateHandlerError> {
+        Ok(MiddlewareDecision::Proceed)
+    }
+
+    /// Called after the state handler had been invoked - or after a later
+    /// middleware intercepted state handling.
+    ///
+    /// The middleware can inspect and modify the outcome, e.g. in order to
+    /// annotate the reason of a `Wait` outcome.
+    async fn after_handle(
+        &self,
+        _object_id: &Self::ObjectId,
+        _state: &mut Self::State,
+        _controller_state: &Self::ControllerState,
+        _ctx: &mut StateHandlerContext<Self::ContextObjects>,
+        _outcome: &mut Result<StateHandlerOutcome<Self::ControllerState>, StateHandlerError>,
+    ) {
+    }
+}
+
+/// A reference to a [`StateHandlerMiddleware`] with the given types
+pub type DynStateHandlerMiddleware<Id, S, CS, CO> = Arc<
+    dyn StateHandlerMiddleware<ObjectId = Id, State = S, ControllerState = CS, ContextObjects = CO>,
+>;
+
+/// A reference to a [`StateHandler`] with the given types
+pub type DynStateHandler<Id, S, CS, CO> =
+    Arc<dyn StateHandler<ObjectId = Id, State = S, ControllerState = CS, ContextObjects = CO>>;
+
+/// A [`StateHandler`] which runs a chain of [`StateHandlerMiddleware`]s around
+/// another state handler
+pub struct MiddlewareChain<Id, S, CS, CO> {
+    handler: DynStateHandler<Id, S, CS, CO>,
+    middlewares: Vec<DynStateHandlerMiddleware<Id, S, CS, CO>>,
+    metric_emitter: Option<MiddlewareMetricsEmitter>,
+}
+
+impl<Id, S, CS, CO> MiddlewareChain<Id, S, CS, CO> {
+    pub fn new(
+        handler: DynStateHandler<Id, S, CS, CO>,
+        middlewares: Vec<DynStateHandlerMiddleware<Id, S, CS, CO>>,
+        metric_emitter: Option<MiddlewareMetricsEmitter>,
+    ) -> Self {
+        Self {
+            handler,
+            middlewares,
+            metric_emitter,
+        }
+    }
+}
+
+impl<Id, S, CS, CO> std::fmt::Debug for MiddlewareChain<Id, S, CS, CO> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MiddlewareChain")
+            .field("handler", &self.handler)
+            .field("middlewares", &self.middlewares)
+            .finish()
+    }
+}
+
+#[async_trait::async_trait]
+impl<Id, S, CS, CO> StateHandler for MiddlewareChain<Id, S, CS, CO>
+where
+    Id: Clone + std::fmt::Display + std::fmt::Debug + Send + Sync + 'static,
+    S: Send + Sync + 'static,
+    CS: Send + Sync + 'static,
+    CO: StateHandlerContextObjects,
+{
+    type ObjectId = Id;
+    type State = S;
+    type ControllerState = CS;
+    type ContextObjects = CO;
+
+    async fn handle_object_state(
+        &self,
+        object_id: &Id,
+        state: &mut S,
+        controller_state: &CS,
+        ctx: &mut StateHandlerContext<CO>,
+    ) -> Result<StateHandlerOutcome<CS>, StateHandlerError> {
+        // The amount of middlewares whose `before_handle` function got called
+        let mut num_entered = 0;
+        let mut intercepted = None;
+
+        for middleware in self.middlewares.iter() {
+            num_entered += 1;
+            let start = Instant::now();
+            let decision = middleware
+                .before_handle(object_id, state, controller_state, ctx)
+                .await;
+            let decision_name = match &decision {
+                Ok(MiddlewareDecision::Proceed) => "proceed",
+                Ok(MiddlewareDecision::Intercept(_)) => "intercept",
+                Err(_) => "error",
+            };
+            if let Some(emitter) = &self.metric_emitter {
+                emitter.emit(middleware.name(), "before", start, decision_name);
+            }
+
+            match decision {
+                Ok(MiddlewareDecision::Proceed) => {}
+                Ok(MiddlewareDecision::Intercept(outcome)) => {
+                    tracing::info!(
+                        %object_id,
+                        middleware = middleware.name(),
+                        %outcome,
+                        "State handling was intercepted by middleware"
+                    );
+                    intercepted = Some(Ok(outcome));
+                    break;
+                }
+                Err(e) => {
+                    intercepted = Some(Err(e));
+                    break;
+                }
+            }
+        }
+
+        let mut outcome = match intercepted {
+            Some(outcome) => outcome,
+            None => {
+                self.handler
+                    .handle_object_state(object_id, state, controller_state, ctx)
+                    .await
+            }
+        };
+
+        for middleware in self.middlewares[..num_entered].iter().rev() {
+            let start = Instant::now();
+            middleware
+                .after_handle(object_id, state, controller_state, ctx, &mut outcome)
+                .await;
+            if let Some(emitter) = &self.metric_emitter {
+                let result = if outcome.is_ok() { "ok" } else { "error" };
+                emitter.emit(middleware.name(), "after", start, result);
+            }
+        }
+
+        outcome
+    }
+}
+
+/// Emits metrics for the middlewares of a state controller
+pub struct MiddlewareMetricsEmitter {
+    invocations_counter: Counter<u64>,
+    latency: Histogram<f64>,
+}
+
+impl MiddlewareMetricsEmitter {
+    pub fn new(object_type: &str, meter: &Meter) -> Self {
+        let invocations_counter = meter
+            .u64_counter(format!("{object_type}_middleware_invocations"))
+            .with_description(format!(
+                "The amount of times state handler middlewares for objects of type {object_type} have been invoked"
+            ))
+            .build();
+
+        let latency = meter
+            .f64_histogram(format!("{object_type}_middleware_latency"))
+            .with_description(format!(
+                "The time it took to run state handler middlewares for objects of type {object_type}"
+            ))
+            .with_unit("ms")
+            .build();
+
+        Self {
+            invocations_counter,
+            latency,
+        }
+    }
+
+    fn emit(
+        &self,
+        middleware: &'static str,
+        hook: &'static str,
+        start: Instant,
+        result: &'static str,
+    ) {
+        self.invocations_counter.add(
+            1,
+            &[
+                KeyValue::new("middleware", middleware),
+                KeyValue::new("hook", hook),
+                KeyValue::new("result", result),
+            ],
+        );
+        self.latency.record(
+            1000.0 * start.elapsed().as_secs_f64(),
+            &[
+                KeyValue::new("middleware", middleware),
+                KeyValue::new("hook", hook),
+            ],
+        );
+    }
+}
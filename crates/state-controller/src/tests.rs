@@ -23,7 +23,7 @@ use std::time::Duration;
 use carbide_utils::test_support::test_meter::TestMeter;
 use config_version::{ConfigVersion, Versioned};
 use db::DatabaseError;
-use futures::StreamExt;
+use futures::{FutureExt, StreamExt};
 use model::StateSla;
 use model::controller_outcome::PersistentStateHandlerOutcome;
 use serde::{self, Deserialize, Serialize};
@@ -32,15 +32,20 @@ use sqlx::{FromRow, PgConnection, Row};
 use tokio::task::JoinSet;
 use tokio_util::sync::CancellationToken;
 
-use crate::config::IterationConfig;
+use crate::config::{IterationConfig, OutboxRelayConfig};
 use crate::controller::{self, Enqueuer, QueuedObject, StateController};
+use crate::db_write_batch::WriteOpFn;
+use crate::dry_run::{self, DryRunOutcome};
 use crate::io::StateControllerIO;
 use crate::metrics::NoopMetricsEmitter;
+use crate::queue_priority::QueuePriority;
 use crate::state_change_emitter::{StateChangeEmitterBuilder, StateChangeEvent, StateChangeHook};
+use crate::state_change_outbox::{DeliveryError, DurableStateChangeHook, OutboxStateChange};
 use crate::state_handler::{
     StateHandler, StateHandlerContext, StateHandlerContextObjects, StateHandlerError,
     StateHandlerOutcome,
 };
+use crate::state_handler_middleware::{MiddlewareDecision, StateHandlerMiddleware};
 
 #[carbide_macros::sqlx_test]
 async fn test_start_iteration(pool: sqlx::PgPool) -> eyre::Result<()> {
@@ -169,7 +174,7 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
     let num_enqueued = controller::db::queue_objects(
         &mut txn,
         TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
-        &["0".to_string()],
+        &[("0".to_string(), QueuePriority::Background)],
     )
     .await
     .unwrap();
@@ -177,7 +182,10 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
     let num_enqueued = controller::db::queue_objects(
         &mut txn,
         TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
-        &["1".to_string(), "2".to_string()],
+        &[
+            ("1".to_string(), QueuePriority::Background),
+            ("2".to_string(), QueuePriority::Background),
+        ],
     )
     .await
     .unwrap();
@@ -196,14 +204,17 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
             QueuedObject {
                 object_id: "0".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Background,
             },
             QueuedObject {
                 object_id: "1".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Background,
             },
             QueuedObject {
                 object_id: "2".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Background,
             },
         ]
     );
@@ -216,7 +227,7 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
     let num_enqueued = controller::db::queue_objects(
         &mut txn,
         TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
-        &["0".to_string()],
+        &[("0".to_string(), QueuePriority::Background)],
     )
     .await
     .unwrap();
@@ -224,7 +235,10 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
     let num_enqueued = controller::db::queue_objects(
         &mut txn,
         TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
-        &["3".to_string(), "2".to_string()],
+        &[
+            ("3".to_string(), QueuePriority::Background),
+            ("2".to_string(), QueuePriority::Background),
+        ],
     )
     .await
     .unwrap();
@@ -242,18 +256,22 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
             QueuedObject {
                 object_id: "0".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Background,
             },
             QueuedObject {
                 object_id: "1".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Background,
             },
             QueuedObject {
                 object_id: "2".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Background,
             },
             QueuedObject {
                 object_id: "3".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Background,
             },
         ]
     );
@@ -268,11 +286,15 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
         &mut txn,
         TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
         2,
+        None,
         &processor_id1,
         std::time::Duration::from_secs(60),
     )
     .await
-    .unwrap();
+    .unwrap()
+    .into_iter()
+    .map(|acquired| acquired.object)
+    .collect::<Vec<_>>();
     queued.sort_by(|a, b| a.object_id.cmp(&b.object_id));
     assert_eq!(
         queued,
@@ -280,10 +302,12 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
             QueuedObject {
                 object_id: "0".to_string(),
                 processed_by: Some(processor_id1.clone()),
+                priority: QueuePriority::Background,
             },
             QueuedObject {
                 object_id: "1".to_string(),
                 processed_by: Some(processor_id1.clone()),
+                priority: QueuePriority::Background,
             },
         ]
     );
@@ -291,17 +315,22 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
         &mut txn2,
         TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
         1,
+        None,
         &processor_id2,
         std::time::Duration::from_secs(60),
     )
     .await
-    .unwrap();
+    .unwrap()
+    .into_iter()
+    .map(|acquired| acquired.object)
+    .collect::<Vec<_>>();
     queued2.sort_by(|a, b| a.object_id.cmp(&b.object_id));
     assert_eq!(
         queued2,
         vec![QueuedObject {
             object_id: "2".to_string(),
             processed_by: Some(processor_id2.clone()),
+            priority: QueuePriority::Background,
         },]
     );
 
@@ -313,7 +342,7 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
     let num_deleted = controller::db::delete_queued_objects(
         &mut txn,
         TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
-        &["0".to_string()],
+        &[("0".to_string(), QueuePriority::Background)],
         &processor_id2,
     )
     .await
@@ -344,14 +373,17 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
             QueuedObject {
                 object_id: "0".to_string(),
                 processed_by: Some(processor_id1.clone()),
+                priority: QueuePriority::Background,
             },
             QueuedObject {
                 object_id: "2".to_string(),
                 processed_by: Some(processor_id2.clone()),
+                priority: QueuePriority::Background,
             },
             QueuedObject {
                 object_id: "3".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Background,
             },
         ]
     );
@@ -365,6 +397,7 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
         &mut txn,
         TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
         2,
+        None,
         &processor_id1,
         std::time::Duration::from_millis(500),
     )
@@ -376,6 +409,7 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
         .iter()
         .filter(|queued| {
             queued
+                .object
                 .processed_by
                 .as_ref()
                 .is_some_and(|by| by == &processor_id1)
@@ -391,6 +425,96 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
     Ok(())
 }
 
+#[carbide_macros::sqlx_test]
+async fn test_queue_priorities(pool: sqlx::PgPool) -> eyre::Result<()> {
+    create_test_state_controller_tables(&pool).await;
+
+    let mut txn = pool.begin().await.unwrap();
+    let num_enqueued = controller::db::queue_objects(
+        &mut txn,
+        TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
+        &[
+            ("0".to_string(), QueuePriority::Background),
+            ("1".to_string(), QueuePriority::Background),
+            ("2".to_string(), QueuePriority::OperatorRequested),
+            // Duplicates within a batch keep the highest priority
+            ("1".to_string(), QueuePriority::TenantImpacting),
+        ],
+    )
+    .await
+    .unwrap();
+    assert_eq!(num_enqueued, 3);
+
+    // Re-enqueuing raises the priority of an already queued object,
+    // but never lowers it
+    let num_enqueued = controller::db::queue_objects(
+        &mut txn,
+        TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
+        &[
+            ("0".to_string(), QueuePriority::OperatorRequested),
+            ("2".to_string(), QueuePriority::Background),
+        ],
+    )
+    .await
+    .unwrap();
+    assert_eq!(num_enqueued, 0);
+
+    let mut queued = controller::db::fetch_queued_objects(
+        &mut txn,
+        TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
+    )
+    .await
+    .unwrap();
+    queued.sort_by(|a, b| a.object_id.cmp(&b.object_id));
+    assert_eq!(
+        queued
+            .iter()
+            .map(|queued| (queued.object_id.as_str(), queued.priority))
+            .collect::<Vec<_>>(),
+        vec![
+            ("0", QueuePriority::OperatorRequested),
+            ("1", QueuePriority::TenantImpacting),
+            ("2", QueuePriority::OperatorRequested),
+        ]
+    );
+    txn.commit().await.unwrap();
+
+    // Acquisition can be restricted to a single priority class
+    let processor_id = "000000000001".to_string();
+    let mut txn = pool.begin().await.unwrap();
+    let acquired = controller::db::acquire_queued_objects(
+        &mut txn,
+        TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
+        10,
+        Some(QueuePriority::TenantImpacting),
+        &processor_id,
+        std::time::Duration::from_secs(60),
+    )
+    .await
+    .unwrap();
+    assert_eq!(acquired.len(), 1);
+    assert_eq!(acquired[0].object.object_id, "1");
+    assert_eq!(
+        acquired[0].object.processed_by.as_deref(),
+        Some(processor_id.as_str())
+    );
+
+    let acquired = controller::db::acquire_queued_objects(
+        &mut txn,
+        TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
+        10,
+        Some(QueuePriority::Background),
+        &processor_id,
+        std::time::Duration::from_secs(60),
+    )
+    .await
+    .unwrap();
+    assert!(acquired.is_empty());
+    txn.commit().await.unwrap();
+
+    Ok(())
+}
+
 #[derive(Debug, Default)]
 struct TestStateControllerIO {}
 
@@ -477,7 +601,9 @@ async fn create_test_state_controller_tables(pool: &sqlx::PgPool) {
         "CREATE TABLE test_state_controller_queued_objects(
         object_id VARCHAR PRIMARY KEY,
         processed_by TEXT NULL,
-        processing_started_at timestamptz NOT NULL DEFAULT NOW()
+        processing_started_at timestamptz NOT NULL DEFAULT NOW(),
+        priority SMALLINT NOT NULL DEFAULT 2,
+        queued_at timestamptz NOT NULL DEFAULT NOW()
     );",
     )
     .execute(&mut *txn)
@@ -1071,6 +1197,167 @@ async fn test_state_change_emitter_emits_events_on_transitions(
     Ok(())
 }
 
+/// A durable hook which fails each delivery attempt for which `fail` returns an error
+struct FailingDurableHook {
+    fail: fn(&TestObjectControllerState, u32) -> Option<DeliveryError>,
+    delivered: Mutex<Vec<(TestObjectControllerState, u32)>>,
+}
+
+#[async_trait::async_trait]
+impl DurableStateChangeHook<String, TestObjectControllerState> for FailingDurableHook {
+    fn name(&self) -> &'static str {
+        "test_hook"
+    }
+
+    async fn deliver(
+        &self,
+        change: &OutboxStateChange<String, TestObjectControllerState>,
+    ) -> Result<(), DeliveryError> {
+        if let Some(err) = (self.fail)(&change.new_state, change.attempt) {
+            return Err(err);
+        }
+        self.delivered
+            .lock()
+            .unwrap()
+            .push((change.new_state.clone(), change.attempt));
+        Ok(())
+    }
+}
+
+async fn build_outbox_test_controller(
+    pool: &sqlx::PgPool,
+    join_set: &mut JoinSet<()>,
+    hook: Arc<FailingDurableHook>,
+) -> eyre::Result<StateController<TestStateControllerIO>> {
+    let work_lock_manager_handle =
+        db::work_lock_manager::start(join_set, pool.clone(), Default::default()).await?;
+
+    let emitter = StateChangeEmitterBuilder::default()
+        .durable_hook(hook)
+        .outbox_relay_config(OutboxRelayConfig {
+            max_attempts: 2,
+            initial_retry_backoff: Duration::ZERO,
+            ..Default::default()
+        })
+        .build();
+
+    Ok(StateController::<TestStateControllerIO>::builder()
+        .iteration_config(IterationConfig {
+            iteration_time: Duration::from_millis(50),
+            ..Default::default()
+        })
+        .database(pool.clone(), work_lock_manager_handle)
+        .processor_id(uuid::Uuid::new_v4().to_string())
+        .services(Arc::new(()))
+        .state_handler(Arc::new(TestTransitionStateHandler))
+        .state_change_emitter(emitter)
+        .build_for_manual_iterations(CancellationToken::new())?)
+}
+
+#[carbide_macros::sqlx_test]
+async fn test_outbox_delivers_state_changes_in_order_with_retries(
+    pool: sqlx::PgPool,
+) -> eyre::Result<()> {
+    create_test_state_controller_tables(&pool).await;
+    let mut join_set = JoinSet::new();
+
+    let mut txn = pool.begin().await?;
+    create_test_object("test-obj-1".to_string(), &mut txn).await;
+    txn.commit().await?;
+
+    // The first attempt to deliver B fails
+    let hook = Arc::new(FailingDurableHook {
+        fail: |state, attempt| {
+            (*state == TestObjectControllerState::B && attempt == 1)
+                .then(|| DeliveryError::Transient("broker unavailable".to_string()))
+        },
+        delivered: Mutex::new(Vec::new()),
+    });
+    let mut controller = build_outbox_test_controller(&pool, &mut join_set, hook.clone()).await?;
+
+    // A -> B -> C
+    controller.run_single_iteration().await;
+    controller.run_single_iteration().await;
+
+    let mut conn = pool.acquire().await?;
+    assert_eq!(
+        db::state_change_outbox::count_pending(&mut conn, "test_state_controller").await?,
+        2
+    );
+
+    // Only the oldest entry for the object is attempted, and it fails.
+    assert_eq!(controller.run_outbox_relay_iteration().await, 1);
+    assert!(hook.delivered.lock().unwrap().is_empty());
+
+    // The retry of B succeeds. C is held back until B is delivered.
+    assert_eq!(controller.run_outbox_relay_iteration().await, 1);
+    assert_eq!(controller.run_outbox_relay_iteration().await, 1);
+    assert_eq!(controller.run_outbox_relay_iteration().await, 0);
+
+    assert_eq!(
+        *hook.delivered.lock().unwrap(),
+        vec![
+            (TestObjectControllerState::B, 2),
+            (TestObjectControllerState::C, 1)
+        ]
+    );
+    assert_eq!(
+        db::state_change_outbox::count_pending(&mut conn, "test_state_controller").await?,
+        0
+    );
+
+    Ok(())
+}
+
+#[carbide_macros::sqlx_test]
+async fn test_outbox_moves_undeliverable_state_changes_to_dead_letters(
+    pool: sqlx::PgPool,
+) -> eyre::Result<()> {
+    create_test_state_controller_tables(&pool).await;
+    let mut join_set = JoinSet::new();
+
+    let mut txn = pool.begin().await?;
+    create_test_object("test-obj-1".to_string(), &mut txn).await;
+    txn.commit().await?;
+
+    // B can never be delivered
+    let hook = Arc::new(FailingDurableHook {
+        fail: |state, _attempt| {
+            (*state == TestObjectControllerState::B)
+                .then(|| DeliveryError::Transient("rejected".to_string()))
+        },
+        delivered: Mutex::new(Vec::new()),
+    });
+    let mut controller = build_outbox_test_controller(&pool, &mut join_set, hook.clone()).await?;
+
+    controller.run_single_iteration().await;
+    controller.run_single_iteration().await;
+
+    // B gets dead-lettered after `max_attempts`, and C is delivered afterwards
+    for _ in 0..3 {
+        controller.run_outbox_relay_iteration().await;
+    }
+    assert_eq!(
+        *hook.delivered.lock().unwrap(),
+        vec![(TestObjectControllerState::C, 1)]
+    );
+
+    let mut conn = pool.acquire().await?;
+    let dead_letters =
+        db::state_change_outbox::find_dead_letters(&mut conn, "test_state_controller", 10).await?;
+    assert_eq!(dead_letters.len(), 1);
+    assert_eq!(dead_letters[0].object_id, "test-obj-1");
+    assert_eq!(dead_letters[0].hook, "test_hook");
+    assert_eq!(dead_letters[0].attempts, 2);
+    assert_eq!(dead_letters[0].last_error.as_deref(), Some("rejected"));
+    assert_eq!(
+        serde_json::from_str::<TestObjectControllerState>(&dead_letters[0].state)?,
+        TestObjectControllerState::B
+    );
+
+    Ok(())
+}
+
 #[carbide_macros::sqlx_test]
 async fn test_state_controller_manual_enqueuing(pool: sqlx::PgPool) -> eyre::Result<()> {
     create_test_state_controller_tables(&pool).await;
@@ -1124,9 +1411,265 @@ async fn test_state_controller_manual_enqueuing(pool: sqlx::PgPool) -> eyre::Res
         vec![QueuedObject {
             object_id: "test-obj-1".to_string(),
             processed_by: None,
+            priority: QueuePriority::OperatorRequested,
         },]
     );
     txn.commit().await.unwrap();
 
     Ok(())
 }
+
+/// A state handler which enqueues writes and returns a transaction with changes
+#[derive(Debug)]
+struct WritingStateHandler {
+    pool: sqlx::PgPool,
+}
+
+#[async_trait::async_trait]
+impl StateHandler for WritingStateHandler {
+    type State = TestObject;
+    type ControllerState = TestObjectControllerState;
+    type ObjectId = String;
+    type ContextObjects = TestStateControllerContextObjects;
+
+    async fn handle_object_state(
+        &self,
+        object_id: &String,
+        _state: &mut TestObject,
+        _controller_state: &Self::ControllerState,
+        ctx: &mut StateHandlerContext<Self::ContextObjects>,
+    ) -> Result<StateHandlerOutcome<Self::ControllerState>, StateHandlerError> {
+        let id = object_id.clone();
+        let write: WriteOpFn = Box::new(move |txn| {
+            async move {
+                sqlx::query("DELETE FROM test_objects WHERE id = $1")
+                    .bind(id)
+                    .execute(&mut **txn)
+                    .await?;
+                Ok(())
+            }
+            .boxed()
+        });
+        ctx.pending_db_writes.push(write);
+
+        let mut txn = self.pool.begin().await?;
+        sqlx::query("UPDATE test_objects SET controller_state_outcome = '{}'::jsonb")
+            .execute(&mut *txn)
+            .await?;
+        Ok(StateHandlerOutcome::transition(TestObjectControllerState::B).with_txn(txn))
+    }
+}
+
+#[carbide_macros::sqlx_test]
+async fn test_dry_run_does_not_apply_outcome(pool: sqlx::PgPool) -> eyre::Result<()> {
+    create_test_state_controller_tables(&pool).await;
+
+    let mut txn = pool.begin().await?;
+    let obj = create_test_object("test-obj-1".to_string(), &mut txn).await;
+    txn.commit().await?;
+
+    let handler = WritingStateHandler { pool: pool.clone() };
+    let report = dry_run::dry_run(
+        &TestStateControllerIO::default(),
+        &handler,
+        &obj.id,
+        &pool,
+        &mut (),
+        Duration::from_secs(10),
+    )
+    .await?
+    .expect("object should exist");
+
+    assert_eq!(report.current_state.value, TestObjectControllerState::A);
+    assert_eq!(
+        report.outcome,
+        DryRunOutcome::Transition {
+            next_state: TestObjectControllerState::B
+        }
+    );
+    assert!(report.source_ref.is_some());
+    assert_eq!(report.db_writes, vec!["Custom write operation".to_string()]);
+    assert!(report.transaction_discarded);
+
+    // Neither the state, the enqueued write nor the transaction have been applied
+    let mut txn = pool.begin().await?;
+    let stored = TestStateControllerIO::default()
+        .load_object_state(&mut txn, &obj.id)
+        .await?
+        .expect("object should not have been deleted");
+    assert_eq!(stored.controller_state.value, TestObjectControllerState::A);
+    assert_eq!(
+        stored.controller_state.version,
+        obj.controller_state.version
+    );
+    assert!(stored.controller_state_outcome.is_none());
+    txn.commit().await?;
+
+    // Missing objects are reported as such
+    let report = dry_run::dry_run(
+        &TestStateControllerIO::default(),
+        &handler,
+        &"missing".to_string(),
+        &pool,
+        &mut (),
+        Duration::from_secs(10),
+    )
+    .await?;
+    assert!(report.is_none());
+
+    Ok(())
+}
+
+/// A middleware which records its invocations, and which optionally
+/// intercepts state handling for a certain object
+#[derive(Debug)]
+struct RecordingMiddleware {
+    name: &'static str,
+    intercept_object: Option<String>,
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl StateHandlerMiddleware for RecordingMiddleware {
+    type State = TestObject;
+    type ControllerState = TestObjectControllerState;
+    type ObjectId = String;
+    type ContextObjects = TestStateControllerContextObjects;
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    async fn before_handle(
+        &self,
+        object_id: &String,
+        _state: &mut TestObject,
+        _controller_state: &Self::ControllerState,
+        _ctx: &mut StateHandlerContext<Self::ContextObjects>,
+    ) -> Result<MiddlewareDecision<Self::ControllerState>, StateHandlerError> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("{}:before:{object_id}", self.name));
+        if self.intercept_object.as_ref() == Some(object_id) {
+            return Ok(MiddlewareDecision::Intercept(StateHandlerOutcome::wait(
+                format!("blocked by {}", self.name),
+            )));
+        }
+        Ok(MiddlewareDecision::Proceed)
+    }
+
+    async fn after_handle(
+        &self,
+        object_id: &String,
+        _state: &mut TestObject,
+        _controller_state: &Self::ControllerState,
+        _ctx: &mut StateHandlerContext<Self::ContextObjects>,
+        outcome: &mut Result<StateHandlerOutcome<Self::ControllerState>, StateHandlerError>,
+    ) {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("{}:after:{object_id}", self.name));
+        if let Ok(StateHandlerOutcome::Wait { reason, .. }) = outcome {
+            *reason = format!("{reason} (seen by {})", self.name);
+        }
+    }
+}
+
+#[carbide_macros::sqlx_test]
+async fn test_state_handler_middlewares(pool: sqlx::PgPool) -> eyre::Result<()> {
+    create_test_state_controller_tables(&pool).await;
+    let mut join_set = JoinSet::new();
+    let cancel_token = CancellationToken::new();
+    let work_lock_manager_handle =
+        db::work_lock_manager::start(&mut join_set, pool.clone(), Default::default()).await?;
+
+    let mut txn = pool.begin().await?;
+    let allowed = create_test_object("allowed".to_string(), &mut txn).await;
+    let blocked = create_test_object("blocked".to_string(), &mut txn).await;
+    txn.commit().await?;
+
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let mut controller = StateController::<TestStateControllerIO>::builder()
+        .iteration_config(IterationConfig {
+            iteration_time: Duration::from_millis(50),
+            ..Default::default()
+        })
+        .database(pool.clone(), work_lock_manager_handle.clone())
+        .processor_id(uuid::Uuid::new_v4().to_string())
+        .services(Arc::new(()))
+        .state_handler(Arc::new(TestTransitionStateHandler))
+        .middleware(Arc::new(RecordingMiddleware {
+            name: "first",
+            intercept_object: None,
+            calls: calls.clone(),
+        }))
+        .middleware(Arc::new(RecordingMiddleware {
+            name: "second",
+            intercept_object: Some(blocked.id.clone()),
+            calls: calls.clone(),
+        }))
+        .middleware(Arc::new(RecordingMiddleware {
+            name: "third",
+            intercept_object: None,
+            calls: calls.clone(),
+        }))
+        .build_for_manual_iterations(cancel_token)?;
+
+    controller.run_single_iteration_ext(false).await;
+
+    let mut txn = pool.begin().await?;
+    let io = TestStateControllerIO::default();
+    let stored_allowed = io.load_object_state(&mut txn, &allowed.id).await?.unwrap();
+    assert_eq!(
+        stored_allowed.controller_state.value,
+        TestObjectControllerState::B
+    );
+
+    // The intercepting middleware vetoed the transition, and the outcome
+    // got annotated by the middleware which ran before it
+    let stored_blocked = io.load_object_state(&mut txn, &blocked.id).await?.unwrap();
+    assert_eq!(
+        stored_blocked.controller_state.value,
+        TestObjectControllerState::A
+    );
+    match stored_blocked.controller_state_outcome {
+        Some(PersistentStateHandlerOutcome::Wait { reason, .. }) => {
+            assert_eq!(reason, "blocked by second (seen by second) (seen by first)");
+        }
+        outcome => panic!("Unexpected outcome {outcome:?}"),
+    }
+    txn.commit().await?;
+
+    let calls = calls.lock().unwrap().clone();
+    let calls_for = |object_id: &str| {
+        calls
+            .iter()
+            .filter(|call| call.ends_with(&format!(":{object_id}")))
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        calls_for("allowed"),
+        vec![
+            "first:before:allowed",
+            "second:before:allowed",
+            "third:before:allowed",
+            "third:after:allowed",
+            "second:after:allowed",
+            "first:after:allowed",
+        ]
+    );
+    assert_eq!(
+        calls_for("blocked"),
+        vec![
+            "first:before:blocked",
+            "second:before:blocked",
+            "second:after:blocked",
+            "first:after:blocked",
+        ]
+    );
+
+    Ok(())
+}
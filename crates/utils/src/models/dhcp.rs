@@ -16,7 +16,7 @@
  */
 use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::net::Ipv4Addr;
+use std::net::{Ipv4Addr, Ipv6Addr};
 use std::str::FromStr;
 
 use carbide_uuid::UuidConversionError;
@@ -40,6 +40,33 @@ pub struct DhcpConfig {
     pub carbide_ntpservers: Vec<Ipv4Addr>,
     pub carbide_provisioning_server_ipv4: Ipv4Addr,
     pub carbide_dhcp_server: Ipv4Addr,
+    // DNS servers handed out in DHCPv6 replies.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub carbide_nameservers_v6: Vec<Ipv6Addr>,
+    // Router Advertisements are sent only in Dpu mode, and only when this is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub router_advertisement: Option<RouterAdvertisementConfig>,
+}
+
+/// Router Advertisement settings for IPv6 host interfaces. The Managed flag is always set so
+/// that hosts use DHCPv6 for their address; `slaac` additionally advertises /64 prefixes as
+/// autonomous.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RouterAdvertisementConfig {
+    pub interval_secs: u32,
+    pub router_lifetime_secs: u16,
+    pub slaac: bool,
+}
+
+impl Default for RouterAdvertisementConfig {
+    fn default() -> Self {
+        Self {
+            // RFC 4861 defaults for MaxRtrAdvInterval and AdvDefaultLifetime.
+            interval_secs: 600,
+            router_lifetime_secs: 1800,
+            slaac: false,
+        }
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -72,6 +99,9 @@ impl Default for DhcpConfig {
             // These two must be updated with valid values.
             carbide_provisioning_server_ipv4: Ipv4Addr::from([127, 0, 0, 1]),
             carbide_dhcp_server: Ipv4Addr::from([127, 0, 0, 1]),
+
+            carbide_nameservers_v6: vec![],
+            router_advertisement: None,
         }
     }
 }
@@ -113,7 +143,19 @@ pub struct InterfaceInfo {
     pub booturl: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mtu: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ipv6: Option<InterfaceIpv6Info>,
+}
+
+/// IPv6 side of a dual-stack host interface, served over DHCPv6.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterfaceIpv6Info {
+    pub address: Ipv6Addr,
+    pub prefix: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gateway: Option<Ipv6Addr>,
 }
+
 impl Default for InterfaceInfo {
     fn default() -> Self {
         InterfaceInfo {
@@ -123,6 +165,7 @@ impl Default for InterfaceInfo {
             fqdn: Default::default(),
             booturl: None,
             mtu: None,
+            ipv6: None,
         }
     }
 }
@@ -192,6 +235,21 @@ impl TryFrom<::rpc::forge::FlatInterfaceConfig> for InterfaceInfo {
             fqdn: value.fqdn,
             booturl: value.booturl,
             mtu: value.mtu,
+            ipv6: value
+                .ipv6_interface_config
+                .map(InterfaceIpv6Info::try_from)
+                .transpose()?,
+        })
+    }
+}
+
+impl TryFrom<::rpc::forge::FlatInterfaceIpv6Config> for InterfaceIpv6Info {
+    type Error = DhcpDataError;
+    fn try_from(value: ::rpc::forge::FlatInterfaceIpv6Config) -> Result<Self, Self::Error> {
+        Ok(InterfaceIpv6Info {
+            address: value.ip.parse()?,
+            prefix: value.interface_prefix,
+            gateway: value.svi_ip.map(|ip| ip.parse()).transpose()?,
         })
     }
 }
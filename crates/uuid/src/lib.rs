@@ -27,6 +27,7 @@ pub mod instance;
 pub mod instance_type;
 pub mod ipxe_template;
 pub mod machine;
+pub mod maintenance_window;
 pub mod measured_boot;
 pub mod network;
 pub mod network_security_group;
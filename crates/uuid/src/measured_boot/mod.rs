@@ -350,6 +350,32 @@ mod approved_machine_id_tests {
     );
 }
 
+// ============================================================================
+// MeasurementPolicyId
+// ============================================================================
+
+/// Marker type for MeasurementPolicyId.
+pub struct MeasurementPolicyIdMarker;
+
+impl UuidSubtype for MeasurementPolicyIdMarker {
+    const TYPE_NAME: &'static str = "MeasurementPolicyId";
+    const DB_COLUMN_NAME: &'static str = "policy_id";
+}
+
+/// Primary key for a measurement_policies table entry, where a policy is
+/// a set of event-level rules that reports can be approved by instead of
+/// an exact match against a measurement bundle.
+pub type MeasurementPolicyId = TypedUuid<MeasurementPolicyIdMarker>;
+
+#[cfg(test)]
+mod policy_id_tests {
+    use super::*;
+    use crate::typed_uuid_tests;
+    // Run all boilerplate TypedUuid tests for this type, also
+    // ensuring TYPE_NAME and DB_COLUMN_NAME test correctly.
+    typed_uuid_tests!(MeasurementPolicyId, "MeasurementPolicyId", "policy_id");
+}
+
 // ============================================================================
 // MeasurementApprovedProfileId
 // ============================================================================